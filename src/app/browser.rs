@@ -1,5 +1,10 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
+use anyhow::Result;
+use lazyoav::clean;
+use lazyoav::genignore;
 use walkdir::WalkDir;
 
 use super::state::{CodeBrowserState, FileEntry};
@@ -9,7 +14,9 @@ use super::state::{CodeBrowserState, FileEntry};
 /// Clears existing tree state. No-ops gracefully if the generator directory
 /// doesn't exist (e.g. before any pipeline run).
 pub fn refresh_file_tree(state: &mut CodeBrowserState, work_dir: &Path) {
+    state.all_files.clear();
     state.file_tree.clear();
+    state.ignore_patterns.clear();
     state.file_index = 0;
     state.file_content = None;
     state.opened_file_index = None;
@@ -24,6 +31,7 @@ pub fn refresh_file_tree(state: &mut CodeBrowserState, work_dir: &Path) {
     if !root.is_dir() {
         return;
     }
+    state.ignore_patterns = genignore::patterns(&root).unwrap_or_default();
 
     let walker = WalkDir::new(&root)
         .sort_by(|a, b| {
@@ -41,13 +49,194 @@ pub fn refresh_file_tree(state: &mut CodeBrowserState, work_dir: &Path) {
         let name = entry.file_name().to_string_lossy().into_owned();
         let is_dir = entry.file_type().is_dir();
         let path = entry.into_path();
-        state.file_tree.push(FileEntry {
+        let ignored = genignore::is_ignored(&state.ignore_patterns, &relative(&root, &path));
+        state.all_files.push(FileEntry {
             depth,
             name,
             is_dir,
             path,
+            ignored,
         });
     }
+    rebuild_visible(state);
+}
+
+/// Rebuild `file_tree` from `all_files`, dropping what sits in a collapsed
+/// directory or falls outside the filter. The selected and opened entries
+/// keep their place when they're still visible; a hidden selection moves to
+/// its nearest visible directory.
+pub fn rebuild_visible(state: &mut CodeBrowserState) {
+    let selected = state
+        .file_tree
+        .get(state.file_index)
+        .map(|e| e.path.clone());
+    let opened = state
+        .opened_file_index
+        .and_then(|i| state.file_tree.get(i))
+        .map(|e| e.path.clone());
+
+    // With a filter, a directory shows only if something below it matches.
+    let shown: Option<HashSet<PathBuf>> = state.filter.as_deref().map(|filter| {
+        let patterns: Vec<String> = filter
+            .split_whitespace()
+            .map(|p| match p.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None => format!("**/{p}"),
+            })
+            .collect();
+        let mut shown = HashSet::new();
+        for entry in state.all_files.iter().filter(|e| !e.is_dir) {
+            if patterns
+                .iter()
+                .any(|p| genignore::is_ignored(std::slice::from_ref(p), &tree_path(entry)))
+            {
+                shown.extend(entry.path.ancestors().map(Path::to_path_buf));
+            }
+        }
+        shown
+    });
+
+    state.file_tree = state
+        .all_files
+        .iter()
+        .filter(|e| shown.as_ref().is_none_or(|s| s.contains(&e.path)))
+        .filter(|e| {
+            !e.path
+                .ancestors()
+                .skip(1)
+                .any(|a| state.collapsed.contains(a))
+        })
+        .cloned()
+        .collect();
+
+    let position = |path: &Path| state.file_tree.iter().position(|e| e.path == path);
+    state.file_index = selected
+        .and_then(|path| path.ancestors().find_map(position))
+        .unwrap_or(0);
+    state.opened_file_index = opened.and_then(|path| position(&path));
+}
+
+/// Collapse the selected directory, or expand it if it is collapsed.
+/// Returns `false` if the selection isn't a directory.
+pub fn toggle_selected_dir(state: &mut CodeBrowserState) -> bool {
+    let Some(entry) = state.file_tree.get(state.file_index).filter(|e| e.is_dir) else {
+        return false;
+    };
+    let path = entry.path.clone();
+    if !state.collapsed.remove(&path) {
+        state.collapsed.insert(path);
+    }
+    rebuild_visible(state);
+    true
+}
+
+/// Expand every directory above `path` and select it, clearing a filter
+/// that would hide it. Returns `false` if `path` isn't in the tree.
+pub fn reveal(state: &mut CodeBrowserState, path: &Path) -> bool {
+    if !state.all_files.iter().any(|e| e.path == path) {
+        return false;
+    }
+    for ancestor in path.ancestors().skip(1) {
+        state.collapsed.remove(ancestor);
+    }
+    rebuild_visible(state);
+    if !state.file_tree.iter().any(|e| e.path == path) {
+        state.filter = None;
+        rebuild_visible(state);
+    }
+    if let Some(index) = state.file_tree.iter().position(|e| e.path == path) {
+        state.file_index = index;
+    }
+    true
+}
+
+/// The entry's path inside its generator's output, `/`-separated.
+fn tree_path(entry: &FileEntry) -> String {
+    let mut parts: Vec<_> = entry
+        .path
+        .iter()
+        .rev()
+        .take(entry.depth + 1)
+        .map(|c| c.to_string_lossy())
+        .collect();
+    parts.reverse();
+    parts.join("/")
+}
+
+/// `path` relative to `root`, `/`-separated as in the ignore file.
+fn relative(root: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.iter()
+        .map(|c| c.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Add the file tree entry at `index` to the active generator's
+/// `.openapi-generator-ignore`, so the next generate run leaves it alone.
+/// Returns the pattern added, or `None` if it was already there.
+pub fn ignore_entry(
+    state: &mut CodeBrowserState,
+    work_dir: &Path,
+    index: usize,
+) -> Result<Option<String>> {
+    let (Some(dir), Some(entry)) = (state.active_generator_dir(), state.file_tree.get(index))
+    else {
+        return Ok(None);
+    };
+    let root = work_dir.join(".oav/generated").join(dir);
+    let pattern = genignore::pattern_for(&relative(&root, &entry.path), entry.is_dir);
+    if !genignore::add(&root, &pattern)? {
+        return Ok(None);
+    }
+
+    state.ignore_patterns.push(pattern.clone());
+    for entry in state.all_files.iter_mut().chain(&mut state.file_tree) {
+        entry.ignored =
+            genignore::is_ignored(&state.ignore_patterns, &relative(&root, &entry.path));
+    }
+    Ok(Some(pattern))
+}
+
+/// Measure every generator's output on a background thread; results land
+/// in `usage` as [`drain_usage`] picks them up.
+pub fn start_usage_scan(state: &mut CodeBrowserState, work_dir: &Path) {
+    let keys: Vec<String> = state
+        .generators
+        .iter()
+        .map(|(generator, scope)| format!("{scope}/{generator}"))
+        .collect();
+    let work_dir = work_dir.to_path_buf();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for key in keys {
+            let usage = clean::disk_usage(&clean::generated_dir(&work_dir, Some(&key)));
+            if tx.send((key, usage)).is_err() {
+                return;
+            }
+        }
+    });
+    state.usage.clear();
+    state.usage_rx = Some(rx);
+}
+
+/// Collect finished measurements from the usage scan.
+pub fn drain_usage(state: &mut CodeBrowserState) {
+    let Some(rx) = &state.usage_rx else {
+        return;
+    };
+    loop {
+        match rx.try_recv() {
+            Ok((key, usage)) => {
+                state.usage.insert(key, usage);
+            }
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                state.usage_rx = None;
+                return;
+            }
+        }
+    }
 }
 
 /// Load the file at the current `file_index` into `file_content`.
@@ -150,6 +339,7 @@ pub fn load_selected_file(state: &mut CodeBrowserState) {
     state.opened_file_index = Some(state.file_index);
     state.content_version += 1;
     state.file_scroll = 0;
+    state.file_wrap.offset = 0;
 }
 
 /// Map a file extension to the syntect syntax name.
@@ -251,6 +441,128 @@ mod tests {
         assert_eq!(state.file_scroll, 0);
     }
 
+    #[test]
+    fn ignore_entry_marks_the_tree_and_persists() {
+        let tmp = TempDir::new().unwrap();
+        let gen_dir = tmp.path().join(".oav/generated/server/spring");
+        std::fs::create_dir_all(gen_dir.join("src/main")).unwrap();
+        std::fs::write(gen_dir.join("pom.xml"), "<project/>").unwrap();
+        std::fs::write(gen_dir.join("src/main/App.java"), "class App {}").unwrap();
+
+        let mut state = make_state();
+        state.generators = vec![("spring".into(), "server".into())];
+        refresh_file_tree(&mut state, tmp.path());
+        let index = |state: &CodeBrowserState, name: &str| {
+            state.file_tree.iter().position(|e| e.name == name).unwrap()
+        };
+        assert!(state.file_tree.iter().all(|e| !e.ignored));
+
+        let src = index(&state, "src");
+        let added = ignore_entry(&mut state, tmp.path(), src).unwrap();
+        assert_eq!(added.as_deref(), Some("src/**"));
+        assert!(state.file_tree[index(&state, "App.java")].ignored);
+        assert!(!state.file_tree[index(&state, "pom.xml")].ignored);
+        assert!(ignore_entry(&mut state, tmp.path(), src).unwrap().is_none());
+
+        // A fresh tree reads it back from the ignore file.
+        refresh_file_tree(&mut state, tmp.path());
+        assert_eq!(state.ignore_patterns, ["src/**"]);
+        assert!(state.file_tree[index(&state, "main")].ignored);
+        assert!(!state.file_tree[index(&state, genignore::IGNORE_FILE)].ignored);
+    }
+
+    fn spring_tree(tmp: &TempDir) -> CodeBrowserState {
+        let gen_dir = tmp.path().join(".oav/generated/server/spring");
+        let model = gen_dir.join("src/main/java/org/model");
+        std::fs::create_dir_all(&model).unwrap();
+        std::fs::create_dir_all(gen_dir.join("src/main/java/org/api")).unwrap();
+        std::fs::write(model.join("Pet.java"), "class Pet {}").unwrap();
+        std::fs::write(model.join("Pet.md"), "# Pet").unwrap();
+        std::fs::write(gen_dir.join("src/main/java/org/api/PetApi.java"), "").unwrap();
+        std::fs::write(gen_dir.join("pom.xml"), "<project/>").unwrap();
+
+        let mut state = make_state();
+        state.generators = vec![("spring".into(), "server".into())];
+        refresh_file_tree(&mut state, tmp.path());
+        state
+    }
+
+    fn names(state: &CodeBrowserState) -> Vec<&str> {
+        state.file_tree.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn collapsed_dirs_and_filters_hide_entries() {
+        let tmp = TempDir::new().unwrap();
+        let mut state = spring_tree(&tmp);
+        assert_eq!(state.file_tree.len(), state.all_files.len());
+
+        state.file_index = names(&state).iter().position(|n| *n == "java").unwrap();
+        assert!(toggle_selected_dir(&mut state));
+        assert_eq!(names(&state), ["src", "main", "java", "pom.xml"]);
+        assert_eq!(state.file_tree[state.file_index].name, "java");
+
+        // A file isn't a directory to fold.
+        state.file_index = 3;
+        assert!(!toggle_selected_dir(&mut state));
+
+        state.file_index = 2;
+        assert!(toggle_selected_dir(&mut state));
+        state.filter = Some("model/*.java".into());
+        rebuild_visible(&mut state);
+        assert_eq!(
+            names(&state),
+            ["src", "main", "java", "org", "model", "Pet.java"]
+        );
+
+        state.filter = Some("*.xml *.md".into());
+        rebuild_visible(&mut state);
+        assert_eq!(
+            names(&state),
+            ["src", "main", "java", "org", "model", "Pet.md", "pom.xml"]
+        );
+    }
+
+    #[test]
+    fn reveal_expands_to_a_file_and_selects_it() {
+        let tmp = TempDir::new().unwrap();
+        let mut state = spring_tree(&tmp);
+        let pet = state
+            .all_files
+            .iter()
+            .find(|e| e.name == "Pet.java")
+            .unwrap()
+            .path
+            .clone();
+
+        state.file_index = 0;
+        toggle_selected_dir(&mut state);
+        state.filter = Some("*.xml".into());
+        rebuild_visible(&mut state);
+        assert_eq!(names(&state), ["pom.xml"]);
+
+        assert!(reveal(&mut state, &pet));
+        assert!(state.filter.is_none());
+        assert!(state.collapsed.is_empty());
+        assert_eq!(state.file_tree[state.file_index].path, pet);
+        assert!(!reveal(&mut state, &tmp.path().join("missing.java")));
+    }
+
+    #[test]
+    fn usage_scan_measures_each_generator() {
+        let tmp = TempDir::new().unwrap();
+        let mut state = spring_tree(&tmp);
+        state.generators.push(("go".into(), "client".into()));
+
+        start_usage_scan(&mut state, tmp.path());
+        while state.usage_rx.is_some() {
+            drain_usage(&mut state);
+        }
+        assert_eq!(state.usage["server/spring"].files, 4);
+        assert_eq!(state.usage["server/spring"].bytes, 12 + 5 + 10);
+        assert_eq!(state.usage["client/go"], clean::Usage::default());
+    }
+
     #[test]
     fn refresh_no_generators_noops() {
         let mut state = make_state();
@@ -269,6 +581,7 @@ mod tests {
             name: "src".into(),
             is_dir: true,
             path: PathBuf::from("/tmp"),
+            ignored: false,
         });
         state.file_index = 0;
 
@@ -290,6 +603,7 @@ mod tests {
             name: "test.txt".into(),
             is_dir: false,
             path: path.clone(),
+            ignored: false,
         });
         state.file_index = 0;
         let version_before = state.content_version;
@@ -319,6 +633,7 @@ mod tests {
             name: "image.bin".into(),
             is_dir: false,
             path: path.clone(),
+            ignored: false,
         });
         state.file_index = 0;
 
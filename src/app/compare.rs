@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, HashSet};
+
+use lazyoav::pipeline::ValidateReport;
+
+use crate::log_parser::{self, LintError};
+
+/// What changed between two reports: phases that started or stopped
+/// failing, findings that came and went, and per-rule finding counts.
+pub struct ReportComparison {
+    /// `older → newer`, for the title.
+    pub label: String,
+    pub newly_failing: Vec<String>,
+    pub fixed_phases: Vec<String>,
+    pub new_findings: Vec<LintError>,
+    pub fixed_findings: Vec<LintError>,
+    /// `(rule, before, after)` for rules whose count changed.
+    pub rule_deltas: Vec<(String, usize, usize)>,
+    pub scroll: usize,
+}
+
+impl ReportComparison {
+    pub fn new(label: String, before: &ValidateReport, after: &ValidateReport) -> Self {
+        let before_phases = phase_statuses(before);
+        let after_phases = phase_statuses(after);
+        let passed_before = |name: &str| {
+            before_phases
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, passed)| *passed)
+        };
+        let mut newly_failing = Vec::new();
+        let mut fixed_phases = Vec::new();
+        for (name, passed) in &after_phases {
+            match (passed_before(name), passed) {
+                (Some(false), true) => fixed_phases.push(name.clone()),
+                (Some(true) | None, false) => newly_failing.push(name.clone()),
+                _ => {}
+            }
+        }
+
+        let before_findings = findings(before);
+        let after_findings = findings(after);
+        let before_keys: HashSet<_> = before_findings.iter().map(finding_key).collect();
+        let after_keys: HashSet<_> = after_findings.iter().map(finding_key).collect();
+        let new_findings = after_findings
+            .iter()
+            .filter(|e| !before_keys.contains(&finding_key(e)))
+            .cloned()
+            .collect();
+        let fixed_findings = before_findings
+            .iter()
+            .filter(|e| !after_keys.contains(&finding_key(e)))
+            .cloned()
+            .collect();
+
+        let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        for e in &before_findings {
+            counts.entry(e.rule.clone()).or_default().0 += 1;
+        }
+        for e in &after_findings {
+            counts.entry(e.rule.clone()).or_default().1 += 1;
+        }
+        let rule_deltas = counts
+            .into_iter()
+            .filter(|(_, (b, a))| b != a)
+            .map(|(rule, (b, a))| (rule, b, a))
+            .collect();
+
+        Self {
+            label,
+            newly_failing,
+            fixed_phases,
+            new_findings,
+            fixed_findings,
+            rule_deltas,
+            scroll: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Rows the overlay shows: one per entry plus a heading per section.
+    pub fn len(&self) -> usize {
+        [
+            self.newly_failing.len(),
+            self.fixed_phases.len(),
+            self.new_findings.len(),
+            self.fixed_findings.len(),
+            self.rule_deltas.len(),
+        ]
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|n| n + 1)
+        .sum()
+    }
+}
+
+/// `(phase, passed)` for every step the report ran.
+fn phase_statuses(report: &ValidateReport) -> Vec<(String, bool)> {
+    let phases = &report.phases;
+    let mut statuses = Vec::new();
+    if let Some(lint) = &phases.lint {
+        statuses.push(("lint".to_string(), lint.status == "pass"));
+    }
+    if let Some(security) = &phases.security {
+        statuses.push(("security".to_string(), security.status == "pass"));
+    }
+    let steps = [
+        ("generate", &phases.generate),
+        ("compile", &phases.compile),
+        ("test", &phases.test),
+        ("code lint", &phases.code_lint),
+    ];
+    for (kind, steps) in steps {
+        for step in steps.iter().flatten() {
+            let name = format!("{}/{} {kind}", step.scope, step.generator);
+            statuses.push((name, step.status == "pass"));
+        }
+    }
+    if let Some(docs) = &phases.docs {
+        statuses.push(("docs".to_string(), docs.status == "pass"));
+    }
+    for phase in phases.custom.iter().flatten() {
+        statuses.push((phase.name.clone(), phase.status == "pass"));
+    }
+    statuses
+}
+
+/// Lint and security findings of the report.
+fn findings(report: &ValidateReport) -> Vec<LintError> {
+    let phases = &report.phases;
+    phases
+        .lint
+        .iter()
+        .chain(&phases.security)
+        .flat_map(|result| log_parser::parse_lint_output(&result.log))
+        .collect()
+}
+
+/// What makes two findings the same across runs. Line numbers shift with
+/// unrelated edits, so they only count when there's no JSON path.
+fn finding_key(e: &LintError) -> (String, String, String) {
+    let location = e.json_path.clone().unwrap_or_else(|| e.line.to_string());
+    (e.rule.clone(), location, e.message.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lazyoav::pipeline::{LintResult, Phases, StepResult};
+
+    fn report(lint_log: &str, go: &str) -> ValidateReport {
+        ValidateReport {
+            phases: Phases {
+                lint: Some(LintResult {
+                    linter: "spectral".into(),
+                    status: "pass".into(),
+                    log: lint_log.into(),
+                    attempts: 1,
+                    ..Default::default()
+                }),
+                generate: Some(vec![StepResult {
+                    generator: "go".into(),
+                    scope: "server".into(),
+                    status: go.into(),
+                    log: String::new(),
+                    attempts: 1,
+                    ..Default::default()
+                }]),
+                ..Phases::default()
+            },
+            ..ValidateReport::default()
+        }
+    }
+
+    fn finding(code: &str, path: &str, line: usize) -> String {
+        format!(
+            r#"{{"code":"{code}","message":"{code} here","path":["paths","{path}"],"severity":1,"range":{{"start":{{"line":{line},"character":0}},"end":{{"line":{line},"character":4}}}}}}"#
+        )
+    }
+
+    #[test]
+    fn comparison_reports_phase_and_finding_changes() {
+        let before = report(
+            &format!(
+                "[{},{}]",
+                finding("operation-tags", "/pets", 3),
+                finding("operation-description", "/pets", 3)
+            ),
+            "pass",
+        );
+        // The tags finding moved down a line but is the same finding.
+        let after = report(
+            &format!(
+                "[{},{}]",
+                finding("operation-tags", "/pets", 9),
+                finding("info-contact", "/owners", 1)
+            ),
+            "fail",
+        );
+        let cmp = ReportComparison::new("a \u{2192} b".into(), &before, &after);
+
+        assert_eq!(cmp.newly_failing, vec!["server/go generate"]);
+        assert!(cmp.fixed_phases.is_empty());
+        let rules = |findings: &[LintError]| -> Vec<String> {
+            findings.iter().map(|e| e.rule.clone()).collect()
+        };
+        assert_eq!(rules(&cmp.new_findings), vec!["info-contact"]);
+        assert_eq!(rules(&cmp.fixed_findings), vec!["operation-description"]);
+        assert_eq!(
+            cmp.rule_deltas,
+            vec![
+                ("info-contact".to_string(), 0, 1),
+                ("operation-description".to_string(), 1, 0),
+            ]
+        );
+        assert_eq!(cmp.len(), 2 + 2 + 2 + 3);
+
+        let same = ReportComparison::new(String::new(), &after, &after);
+        assert!(same.is_empty());
+        assert_eq!(same.len(), 0);
+    }
+}
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use lazyoav::runs::{MAX_FILE_SIZE, RunRecord};
 use similar::TextDiff;
 use walkdir::WalkDir;
 
+use super::state::LineWrap;
+
 // ── Types ────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +14,9 @@ pub enum ChangeKind {
     Added,
     Modified,
     Deleted,
+    /// Added or changed, but larger than [`MAX_FILE_SIZE`] on either side
+    /// and not compared.
+    TooLarge,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +27,15 @@ pub enum DiffLine {
     HunkHeader(String),
 }
 
+/// A line of a unified diff with its 0-based index in the old and the new
+/// text; hunk headers have neither.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberedLine {
+    pub line: DiffLine,
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+}
+
 #[derive(Debug, Clone)]
 pub struct FileDiff {
     pub rel_path: String,
@@ -47,8 +62,12 @@ pub struct DiffViewState {
     pub active: bool,
     pub file_index: usize,
     pub scroll: u16,
+    pub wrap: LineWrap,
     pub focus: DiffPanel,
     pub active_generator: Option<String>,
+    /// What is being compared, when it isn't the last run against the one
+    /// before it.
+    pub label: Option<String>,
 }
 
 impl DiffViewState {
@@ -58,14 +77,17 @@ impl DiffViewState {
             active: false,
             file_index: 0,
             scroll: 0,
+            wrap: LineWrap::default(),
             focus: DiffPanel::FileList,
             active_generator: None,
+            label: None,
         }
     }
 
     pub fn reset_nav(&mut self) {
         self.file_index = 0;
         self.scroll = 0;
+        self.wrap.offset = 0;
         self.focus = DiffPanel::FileList;
     }
 
@@ -75,6 +97,50 @@ impl DiffViewState {
             .and_then(|key| self.diffs.get(key))
     }
 
+    /// Scroll the content to the next (or previous) hunk header of the
+    /// file shown. Returns `false` if there is none in that direction.
+    pub fn jump_hunk(&mut self, forward: bool) -> bool {
+        let Some(file) = self
+            .active_diff()
+            .and_then(|d| d.files.get(self.file_index))
+        else {
+            return false;
+        };
+        // After a jump to the end the scroll can run past the last line.
+        let current = usize::from(self.scroll).min(file.lines.len());
+        let mut headers = file
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| matches!(line, DiffLine::HunkHeader(_)))
+            .map(|(i, _)| i);
+        let target = if forward {
+            headers.find(|&i| i > current)
+        } else {
+            headers.rfind(|&i| i < current)
+        };
+        let Some(target) = target.and_then(|i| u16::try_from(i).ok()) else {
+            return false;
+        };
+        self.scroll = target;
+        true
+    }
+
+    /// Show the next (or previous) changed file from its top. Returns
+    /// `false` at either end of the list.
+    pub fn step_file(&mut self, forward: bool) -> bool {
+        let count = self.active_diff().map_or(0, |d| d.files.len());
+        let index = match forward {
+            true if self.file_index + 1 < count => self.file_index + 1,
+            false if self.file_index > 0 => self.file_index - 1,
+            _ => return false,
+        };
+        self.file_index = index;
+        self.scroll = 0;
+        self.wrap.offset = 0;
+        true
+    }
+
     #[cfg(test)]
     pub fn total_changed_files(&self) -> usize {
         self.diffs.values().map(|d| d.files.len()).sum()
@@ -83,11 +149,15 @@ impl DiffViewState {
 
 // ── Snapshot ─────────────────────────────────────────────────────────
 
-const MAX_FILE_SIZE: u64 = 512 * 1024;
 const BINARY_PROBE_SIZE: usize = 8192;
 
+/// Stands in for the contents of a file larger than [`MAX_FILE_SIZE`]; the
+/// NUL keeps it from matching any text file.
+const TOO_LARGE: &str = "\0too large";
+
 /// Walk `root` and return a map of relative paths → file contents.
-/// Skips binary files and files larger than `MAX_FILE_SIZE`.
+/// Skips binary files; files larger than `MAX_FILE_SIZE` map to
+/// [`TOO_LARGE`].
 pub fn snapshot_directory(root: &Path) -> HashMap<PathBuf, String> {
     let mut snapshot = HashMap::new();
 
@@ -100,9 +170,16 @@ pub fn snapshot_directory(root: &Path) -> HashMap<PathBuf, String> {
             continue;
         }
 
+        let rel = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .to_path_buf();
+
         if let Ok(meta) = entry.metadata()
             && meta.len() > MAX_FILE_SIZE
         {
+            snapshot.insert(rel, TOO_LARGE.to_string());
             continue;
         }
 
@@ -119,12 +196,6 @@ pub fn snapshot_directory(root: &Path) -> HashMap<PathBuf, String> {
             continue;
         };
 
-        let rel = entry
-            .path()
-            .strip_prefix(root)
-            .unwrap_or(entry.path())
-            .to_path_buf();
-
         snapshot.insert(rel, text);
     }
 
@@ -139,7 +210,77 @@ pub fn compute_diff(
     before: &HashMap<PathBuf, String>,
     gen_root: &Path,
 ) -> GeneratorDiff {
-    let after = snapshot_directory(gen_root);
+    diff_snapshots(generator, scope, before, &snapshot_directory(gen_root))
+}
+
+/// Diff the generated output of two recorded runs, keyed by
+/// `scope/generator`. Generators without changes are left out.
+pub fn compare_runs(
+    root: &Path,
+    older: &RunRecord,
+    newer: &RunRecord,
+) -> HashMap<String, GeneratorDiff> {
+    let keys: Vec<&String> = older.generators.iter().chain(&newer.generators).collect();
+    compare_snapshots(
+        &keys,
+        |key| run_snapshot(root, older, key),
+        |key| run_snapshot(root, newer, key),
+    )
+}
+
+/// The run's copy of `key`, with the files too large to copy put back as
+/// [`TOO_LARGE`].
+fn run_snapshot(root: &Path, run: &RunRecord, key: &str) -> HashMap<PathBuf, String> {
+    let mut snapshot = snapshot_directory(&run.generated_dir(root, key));
+    let rels = run
+        .too_large
+        .iter()
+        .filter_map(|path| path.strip_prefix(key)?.strip_prefix('/'));
+    for rel in rels {
+        snapshot.insert(PathBuf::from(rel), TOO_LARGE.to_string());
+    }
+    snapshot
+}
+
+/// Diff the directories `before` and `after` give for each
+/// `scope/generator` key. Generators without changes are left out.
+pub fn compare_trees(
+    keys: &[&String],
+    before: impl Fn(&str) -> PathBuf,
+    after: impl Fn(&str) -> PathBuf,
+) -> HashMap<String, GeneratorDiff> {
+    compare_snapshots(
+        keys,
+        |key| snapshot_directory(&before(key)),
+        |key| snapshot_directory(&after(key)),
+    )
+}
+
+fn compare_snapshots(
+    keys: &[&String],
+    before: impl Fn(&str) -> HashMap<PathBuf, String>,
+    after: impl Fn(&str) -> HashMap<PathBuf, String>,
+) -> HashMap<String, GeneratorDiff> {
+    let mut keys = keys.to_vec();
+    keys.sort_unstable();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (scope, generator) = key.split_once('/')?;
+            let old = before(key);
+            let new = after(key);
+            let diff = diff_snapshots(generator, scope, &old, &new);
+            (!diff.files.is_empty()).then(|| (key.clone(), diff))
+        })
+        .collect()
+}
+
+fn diff_snapshots(
+    generator: &str,
+    scope: &str,
+    before: &HashMap<PathBuf, String>,
+    after: &HashMap<PathBuf, String>,
+) -> GeneratorDiff {
     let mut files = Vec::new();
 
     for (rel, before_text) in before {
@@ -147,13 +288,26 @@ pub fn compute_diff(
             files.push(FileDiff {
                 rel_path: rel.to_string_lossy().into_owned(),
                 kind: ChangeKind::Deleted,
-                lines: make_delete_lines(before_text),
+                lines: if before_text == TOO_LARGE {
+                    too_large_lines()
+                } else {
+                    make_delete_lines(before_text)
+                },
             });
         }
     }
 
-    for (rel, after_text) in &after {
+    for (rel, after_text) in after {
+        let too_large = after_text == TOO_LARGE || before.get(rel).is_some_and(|t| t == TOO_LARGE);
         match before.get(rel) {
+            Some(before_text) if too_large && before_text == after_text => {}
+            _ if too_large => {
+                files.push(FileDiff {
+                    rel_path: rel.to_string_lossy().into_owned(),
+                    kind: ChangeKind::TooLarge,
+                    lines: too_large_lines(),
+                });
+            }
             None => {
                 files.push(FileDiff {
                     rel_path: rel.to_string_lossy().into_owned(),
@@ -181,6 +335,13 @@ pub fn compute_diff(
     }
 }
 
+fn too_large_lines() -> Vec<DiffLine> {
+    vec![DiffLine::Context(format!(
+        "Larger than {} KiB, not compared",
+        MAX_FILE_SIZE / 1024
+    ))]
+}
+
 fn make_unified_diff(old: &str, new: &str) -> Vec<DiffLine> {
     let text_diff = TextDiff::from_lines(old, new);
     let mut lines = Vec::new();
@@ -200,6 +361,37 @@ fn make_unified_diff(old: &str, new: &str) -> Vec<DiffLine> {
     lines
 }
 
+/// A unified diff of two sets of lines with three lines of context, each
+/// line knowing where it sits in `old` and `new`.
+pub fn numbered_diff(old: &[String], new: &[String]) -> Vec<NumberedLine> {
+    let old: Vec<&str> = old.iter().map(String::as_str).collect();
+    let new: Vec<&str> = new.iter().map(String::as_str).collect();
+    let text_diff = TextDiff::configure().diff_slices(&old, &new);
+    let mut lines = Vec::new();
+
+    for hunk in text_diff.unified_diff().context_radius(3).iter_hunks() {
+        lines.push(NumberedLine {
+            line: DiffLine::HunkHeader(hunk.header().to_string()),
+            old: None,
+            new: None,
+        });
+        for change in hunk.iter_changes() {
+            let text = change.value().to_string();
+            lines.push(NumberedLine {
+                line: match change.tag() {
+                    similar::ChangeTag::Equal => DiffLine::Context(text),
+                    similar::ChangeTag::Insert => DiffLine::Insert(text),
+                    similar::ChangeTag::Delete => DiffLine::Delete(text),
+                },
+                old: change.old_index(),
+                new: change.new_index(),
+            });
+        }
+    }
+
+    lines
+}
+
 fn make_add_lines(content: &str) -> Vec<DiffLine> {
     let mut lines = Vec::new();
     lines.push(DiffLine::HunkHeader("@@ new file @@".into()));
@@ -225,6 +417,71 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn numbered_diff_tracks_both_sides() {
+        let old: Vec<String> = ["a", "b", "c"].map(String::from).into();
+        let new: Vec<String> = ["a", "B", "c", "d"].map(String::from).into();
+        let lines = numbered_diff(&old, &new);
+        let sides: Vec<(Option<usize>, Option<usize>)> =
+            lines.iter().map(|l| (l.old, l.new)).collect();
+        assert_eq!(
+            sides,
+            vec![
+                (None, None),
+                (Some(0), Some(0)),
+                (Some(1), None),
+                (None, Some(1)),
+                (Some(2), Some(2)),
+                (None, Some(3)),
+            ]
+        );
+        assert_eq!(lines[3].line, DiffLine::Insert("B".into()));
+    }
+
+    #[test]
+    fn hunk_and_file_navigation() {
+        let file = |rel_path: &str, lines: Vec<DiffLine>| FileDiff {
+            rel_path: rel_path.into(),
+            kind: ChangeKind::Modified,
+            lines,
+        };
+        let header = || DiffLine::HunkHeader("@@ -1,3 +1,3 @@".into());
+        let context = || DiffLine::Context("x".into());
+        let mut state = DiffViewState::new();
+        state.diffs.insert(
+            "server/go".into(),
+            GeneratorDiff {
+                generator: "go".into(),
+                scope: "server".into(),
+                files: vec![
+                    file(
+                        "a.go",
+                        vec![header(), context(), context(), header(), context()],
+                    ),
+                    file("b.go", vec![header(), context()]),
+                ],
+            },
+        );
+        state.active_generator = Some("server/go".into());
+
+        assert!(state.jump_hunk(true));
+        assert_eq!(state.scroll, 3);
+        assert!(!state.jump_hunk(true));
+        assert!(state.jump_hunk(false));
+        assert_eq!(state.scroll, 0);
+        assert!(!state.jump_hunk(false));
+
+        state.scroll = u16::MAX;
+        assert!(state.jump_hunk(false));
+        assert_eq!(state.scroll, 3);
+
+        assert!(state.step_file(true));
+        assert_eq!((state.file_index, state.scroll), (1, 0));
+        assert!(!state.step_file(true));
+        assert!(state.step_file(false));
+        assert!(!state.step_file(false));
+    }
+
     #[test]
     fn snapshot_empty_dir() {
         let dir = tempfile::tempdir().unwrap();
@@ -256,6 +513,34 @@ mod tests {
         assert!(snap.contains_key(&PathBuf::from("text.txt")));
     }
 
+    #[test]
+    fn large_files_show_as_too_large() {
+        let dir = tempfile::tempdir().unwrap();
+        let big = vec![b'a'; MAX_FILE_SIZE as usize + 1];
+        fs::write(dir.path().join("big.txt"), &big).unwrap();
+        fs::write(dir.path().join("same.txt"), &big).unwrap();
+
+        let mut before = HashMap::new();
+        before.insert(PathBuf::from("big.txt"), "small".to_string());
+        before.insert(PathBuf::from("same.txt"), TOO_LARGE.to_string());
+        before.insert(PathBuf::from("gone.txt"), TOO_LARGE.to_string());
+
+        let diff = compute_diff("go", "server", &before, dir.path());
+        let kinds: Vec<_> = diff
+            .files
+            .iter()
+            .map(|f| (f.rel_path.as_str(), f.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("big.txt", ChangeKind::TooLarge),
+                ("gone.txt", ChangeKind::Deleted)
+            ]
+        );
+        assert_eq!(diff.files[1].lines, too_large_lines());
+    }
+
     #[test]
     fn snapshot_nonexistent_dir() {
         let snap = snapshot_directory(Path::new("/nonexistent/path/xyz"));
@@ -347,6 +632,42 @@ mod tests {
         assert!(diff.files.iter().all(|f| f.kind == ChangeKind::Added));
     }
 
+    #[test]
+    fn compare_runs_diffs_each_generator() {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |timestamp, generators: &[&str]| RunRecord {
+            timestamp,
+            spec: "openapi.yaml".into(),
+            generators: generators.iter().map(|g| g.to_string()).collect(),
+            passed: 1,
+            failed: 0,
+            too_large: Vec::new(),
+        };
+        let older = run(100, &["server/go", "client/ts"]);
+        let newer = run(200, &["server/go"]);
+        for (run, body) in [(&older, "v1"), (&newer, "v2")] {
+            let go = run.generated_dir(dir.path(), "server/go");
+            fs::create_dir_all(&go).unwrap();
+            fs::write(go.join("api.go"), body).unwrap();
+            fs::write(go.join("same.go"), "same").unwrap();
+        }
+        let ts = older.generated_dir(dir.path(), "client/ts");
+        fs::create_dir_all(&ts).unwrap();
+        fs::write(ts.join("api.ts"), "export {}").unwrap();
+
+        let diffs = compare_runs(dir.path(), &older, &newer);
+        let go = &diffs["server/go"];
+        assert_eq!(go.files.len(), 1);
+        assert_eq!(go.files[0].kind, ChangeKind::Modified);
+        assert_eq!(diffs["client/ts"].files[0].kind, ChangeKind::Deleted);
+
+        let mut newer = newer;
+        newer.too_large = vec!["server/go/same.go".into()];
+        let diffs = compare_runs(dir.path(), &older, &newer);
+        let kinds: Vec<_> = diffs["server/go"].files.iter().map(|f| f.kind).collect();
+        assert_eq!(kinds, [ChangeKind::Modified, ChangeKind::TooLarge]);
+    }
+
     #[test]
     fn diff_view_state_total_changed_files() {
         let mut state = DiffViewState::new();
@@ -0,0 +1,171 @@
+/// State of the inline edit overlay: a few spec lines edited in place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineEditState {
+    /// 1-based spec line of the first edited line.
+    pub first_line: usize,
+    /// The lines as read from the spec, to detect changes on disk.
+    pub original: Vec<String>,
+    pub lines: Vec<String>,
+    /// Cursor row in `lines`.
+    pub row: usize,
+    /// Cursor column, in characters.
+    pub col: usize,
+}
+
+impl InlineEditState {
+    /// Start editing `original` at 1-based `first_line`, with the cursor
+    /// after the indentation of the first line.
+    pub fn new(first_line: usize, original: Vec<String>) -> Self {
+        let col = original.first().map_or(0, |line| {
+            line.chars().take_while(|c| c.is_whitespace()).count()
+        });
+        Self {
+            first_line,
+            lines: original.clone(),
+            original,
+            row: 0,
+            col,
+        }
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.lines != self.original
+    }
+
+    fn line(&self) -> &str {
+        &self.lines[self.row]
+    }
+
+    fn line_len(&self) -> usize {
+        self.line().chars().count()
+    }
+
+    /// Byte offset of the cursor in the current line.
+    fn offset(&self) -> usize {
+        self.line()
+            .char_indices()
+            .nth(self.col)
+            .map_or(self.line().len(), |(i, _)| i)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let at = self.offset();
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+    }
+
+    /// Split the line at the cursor; the new line keeps the indentation.
+    pub fn newline(&mut self) {
+        let at = self.offset();
+        let rest = self.lines[self.row].split_off(at);
+        let indent: String = self
+            .line()
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        self.col = indent.chars().count();
+        self.lines
+            .insert(self.row + 1, format!("{indent}{}", rest.trim_start()));
+        self.row += 1;
+    }
+
+    /// Delete the character before the cursor, joining lines at column 0.
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let at = self.offset();
+            self.lines[self.row].remove(at);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// Delete the character under the cursor, joining the next line at the end.
+    pub fn delete(&mut self) {
+        if self.col < self.line_len() {
+            let at = self.offset();
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    pub fn left(&mut self) {
+        self.col = self.col.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.col = (self.col + 1).min(self.line_len());
+    }
+
+    pub fn home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.col = self.line_len();
+    }
+
+    pub fn up(&mut self) {
+        self.row = self.row.saturating_sub(1);
+        self.col = self.col.min(self.line_len());
+    }
+
+    pub fn down(&mut self) {
+        self.row = (self.row + 1).min(self.lines.len() - 1);
+        self.col = self.col.min(self.line_len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(lines: &[&str]) -> InlineEditState {
+        InlineEditState::new(7, lines.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn typing_and_deleting() {
+        let mut e = edit(&["  title: Pet Stroe"]);
+        assert_eq!(e.col, 2);
+        e.end();
+        e.left();
+        e.left();
+        e.backspace();
+        e.right();
+        e.insert('r');
+        assert_eq!(e.lines, ["  title: Pet Store"]);
+        assert!(e.is_modified());
+
+        // Multi-byte characters are edited by character.
+        let mut e = edit(&["é"]);
+        e.delete();
+        e.insert('ü');
+        assert_eq!(e.lines, ["ü"]);
+    }
+
+    #[test]
+    fn newline_keeps_indent_and_backspace_joins() {
+        let mut e = edit(&["  a: 1 b: 2"]);
+        e.end();
+        for _ in 0..4 {
+            e.left();
+        }
+        e.newline();
+        assert_eq!(e.lines, ["  a: 1 ", "  b: 2"]);
+        assert_eq!((e.row, e.col), (1, 2));
+
+        e.home();
+        e.backspace();
+        assert_eq!(e.lines, ["  a: 1   b: 2"]);
+        assert_eq!((e.row, e.col), (0, 7));
+        e.up();
+        e.down();
+        assert_eq!(e.row, 0);
+    }
+}
@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+use lazyoav::config::Config;
+use lazyoav::durations::{self, Durations, Pending};
+use lazyoav::pipeline::Phase;
+
+/// The steps a running pipeline has left, timed against earlier runs for
+/// the bottom bar's remaining time.
+pub struct Eta {
+    pub durations: Durations,
+    pub config: Config,
+    /// Planned steps that haven't finished, with when they started.
+    pub steps: Vec<(Phase, Option<Instant>)>,
+}
+
+impl Eta {
+    pub fn new(durations: Durations, config: Config, plan: Vec<Phase>) -> Self {
+        Self {
+            durations,
+            config,
+            steps: plan.into_iter().map(|phase| (phase, None)).collect(),
+        }
+    }
+
+    pub fn started(&mut self, phase: &Phase, at: Instant) {
+        if let Some((_, started)) = self.steps.iter_mut().find(|(p, _)| p == phase) {
+            *started = Some(at);
+        }
+    }
+
+    /// Drop `phase`, and the steps its failure means won't run: compile
+    /// waits for every generator, test and code lint for every compile.
+    pub fn finished(&mut self, phase: &Phase, success: bool) {
+        self.steps.retain(|(p, _)| p != phase);
+        if success {
+            return;
+        }
+        match phase {
+            Phase::Generate { .. } => self.steps.retain(|(p, _)| {
+                !matches!(
+                    p,
+                    Phase::Compile { .. } | Phase::Test { .. } | Phase::CodeLint { .. }
+                )
+            }),
+            Phase::Compile { .. } => self
+                .steps
+                .retain(|(p, _)| !matches!(p, Phase::Test { .. } | Phase::CodeLint { .. })),
+            _ => {}
+        }
+    }
+
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        let pending: Vec<Pending> = self
+            .steps
+            .iter()
+            .map(|(phase, started)| Pending {
+                phase: phase.clone(),
+                elapsed: started.map(|at| now.saturating_duration_since(at)),
+            })
+            .collect();
+        self.durations.remaining(&self.config, &pending)
+    }
+
+    /// The latest step to start and what's left of the run, e.g.
+    /// `spring generate ~2m remaining`.
+    pub fn label(&self, now: Instant) -> Option<String> {
+        let left = durations::format_remaining(self.remaining(now)?);
+        let current = self
+            .steps
+            .iter()
+            .filter_map(|(phase, started)| Some((phase, (*started)?)))
+            .max_by_key(|(_, started)| *started)
+            .map(|(phase, _)| phase);
+        Some(match current {
+            Some(phase) => format!("{phase} {left} remaining"),
+            None => format!("{left} remaining"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lazyoav::pipeline::{Invocation, Phases, StepResult, ValidateReport};
+
+    fn generate(generator: &str) -> Phase {
+        Phase::Generate {
+            generator: generator.into(),
+            scope: "server".into(),
+        }
+    }
+
+    fn compile(generator: &str) -> Phase {
+        Phase::Compile {
+            generator: generator.into(),
+            scope: "server".into(),
+        }
+    }
+
+    #[test]
+    fn label_names_the_running_step_and_drops_skipped_ones() {
+        let step = |generator: &str, secs: u64| StepResult {
+            generator: generator.into(),
+            scope: "server".into(),
+            invocation: Some(Invocation {
+                duration_ms: secs * 1_000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut durations = Durations::default();
+        durations.record(&ValidateReport {
+            phases: Phases {
+                generate: Some(vec![step("spring", 60)]),
+                compile: Some(vec![step("spring", 120)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut eta = Eta::new(
+            durations,
+            Config::default(),
+            vec![generate("spring"), compile("spring")],
+        );
+        let start = Instant::now();
+        eta.started(&generate("spring"), start);
+        assert_eq!(
+            eta.label(start + Duration::from_secs(30)).as_deref(),
+            Some("spring generate ~3m remaining")
+        );
+
+        eta.finished(&generate("spring"), false);
+        assert!(eta.steps.is_empty());
+        assert_eq!(eta.label(start), None);
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::mpsc::Receiver;
+
+use lazyoav::docker::CancelToken;
+use lazyoav::pipeline::matrix::{MatrixCell, MatrixEvent, MatrixVersion};
+
+/// The generator version matrix: one row per `scope/generator`, one column
+/// per image, filled in as the versions finish.
+pub struct MatrixView {
+    pub versions: Vec<MatrixVersion>,
+    pub rows: Vec<String>,
+    /// Cells by row, then version; `None` until that version has run.
+    pub cells: Vec<Vec<Option<MatrixCell>>>,
+    /// Version being run, while the matrix runs.
+    pub running: Option<usize>,
+    pub rx: Option<Receiver<MatrixEvent>>,
+    pub cancel: CancelToken,
+    /// Selected column, and the column it is diffed against.
+    pub column: usize,
+    pub base: usize,
+    /// Why the last run stopped early.
+    pub error: Option<String>,
+}
+
+impl MatrixView {
+    pub fn new(
+        versions: Vec<MatrixVersion>,
+        rows: Vec<String>,
+        rx: Receiver<MatrixEvent>,
+        cancel: CancelToken,
+    ) -> Self {
+        let cells = vec![vec![None; versions.len()]; rows.len()];
+        Self {
+            versions,
+            rows,
+            cells,
+            running: None,
+            rx: Some(rx),
+            cancel,
+            column: 0,
+            base: 0,
+            error: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Apply pending events. Returns `true` once the run has ended.
+    pub fn drain(&mut self) -> bool {
+        let Some(rx) = self.rx.take() else {
+            return false;
+        };
+        let mut ended = false;
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                MatrixEvent::VersionStarted(index) => self.running = Some(index),
+                MatrixEvent::VersionFinished { index, cells } => self.record(index, cells),
+                MatrixEvent::Completed => ended = true,
+                MatrixEvent::Aborted(reason) => {
+                    self.error = Some(reason);
+                    ended = true;
+                }
+            }
+        }
+        if ended {
+            self.running = None;
+        } else {
+            self.rx = Some(rx);
+        }
+        ended
+    }
+
+    fn record(&mut self, index: usize, cells: Vec<(String, MatrixCell)>) {
+        for (key, cell) in cells {
+            let row = match self.rows.iter().position(|r| *r == key) {
+                Some(row) => row,
+                None => {
+                    self.rows.push(key);
+                    self.cells.push(vec![None; self.versions.len()]);
+                    self.rows.len() - 1
+                }
+            };
+            self.cells[row][index] = Some(cell);
+        }
+    }
+
+    /// Whether version `index` has results to diff.
+    pub fn finished(&self, index: usize) -> bool {
+        self.cells.iter().any(|row| row[index].is_some())
+    }
+
+    pub fn move_column(&mut self, right: bool) {
+        let last = self.versions.len().saturating_sub(1);
+        self.column = if right {
+            (self.column + 1).min(last)
+        } else {
+            self.column.saturating_sub(1)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn version(label: &str) -> MatrixVersion {
+        MatrixVersion {
+            label: label.into(),
+            image: format!("cli:{label}"),
+        }
+    }
+
+    #[test]
+    fn drain_fills_cells_as_versions_finish() {
+        let (tx, rx) = mpsc::channel();
+        let mut view = MatrixView::new(
+            vec![version("v7.10.0"), version("v7.17.0")],
+            vec!["server/go".into()],
+            rx,
+            CancelToken::new(),
+        );
+        let pass = MatrixCell {
+            generate: true,
+            compile: Some(true),
+        };
+        tx.send(MatrixEvent::VersionStarted(0)).unwrap();
+        tx.send(MatrixEvent::VersionFinished {
+            index: 0,
+            cells: vec![("server/go".into(), pass), ("client/ts".into(), pass)],
+        })
+        .unwrap();
+        tx.send(MatrixEvent::VersionStarted(1)).unwrap();
+        assert!(!view.drain());
+        assert_eq!(view.running, Some(1));
+        assert_eq!(view.rows, vec!["server/go", "client/ts"]);
+        assert!(view.finished(0) && !view.finished(1));
+
+        tx.send(MatrixEvent::Aborted("Cancelled by user".into()))
+            .unwrap();
+        assert!(view.drain());
+        assert!(!view.is_running());
+        assert_eq!(view.error.as_deref(), Some("Cancelled by user"));
+    }
+}
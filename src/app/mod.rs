@@ -1,5 +1,20 @@
 pub mod browser;
+pub mod compare;
 pub mod diff;
+pub mod edit;
+pub mod eta;
+pub mod matrix;
+pub mod outline;
+pub mod picker;
+pub mod search;
 pub mod state;
+pub mod tour;
+pub mod wizard;
+pub mod workspace;
 
-pub use state::{App, BrowserPanel, Panel, PhaseStatus, ScreenMode, StatusLevel, ViewMode};
+pub use state::{
+    App, BaseSpec, BrowserPanel, ChangelogView, CleanPrompt, DryRunView, FailPolicy, FixPreview,
+    LineWrap, LogLine, MigratePrompt, Panel, PhaseStatus, RefGraphView, RuleStatsView,
+    RunHistoryView, SchemaView, ScreenMode, SpecCursor, StatusLevel, TimeoutPrompt, TrustPrompt,
+    ViewMode, format_secs, no_color, step_error_counts,
+};
@@ -0,0 +1,354 @@
+use std::path::Path;
+
+/// What a symbol in the outline declares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// A class, interface, enum, struct or similar.
+    Type,
+    /// A function or method.
+    Function,
+}
+
+/// A declaration found in a generated file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// 0-based line of the declaration.
+    pub line: usize,
+    /// Nesting level, from the declaration's indentation.
+    pub depth: usize,
+}
+
+/// The symbol outline overlay for the opened file, narrowed by `query`.
+pub struct OutlineView {
+    pub file: String,
+    pub symbols: Vec<Symbol>,
+    pub query: String,
+    /// Index into `visible()`.
+    pub selected: usize,
+}
+
+impl OutlineView {
+    pub fn new(file: String, symbols: Vec<Symbol>) -> Self {
+        Self {
+            file,
+            symbols,
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Symbols whose name contains the query, ignoring case.
+    pub fn visible(&self) -> Vec<&Symbol> {
+        let query = self.query.to_lowercase();
+        self.symbols
+            .iter()
+            .filter(|s| s.name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    pub fn selected_symbol(&self) -> Option<&Symbol> {
+        self.visible().get(self.selected).copied()
+    }
+}
+
+const TYPE_KEYWORDS: &[&str] = &[
+    "class",
+    "interface",
+    "enum",
+    "record",
+    "struct",
+    "trait",
+    "object",
+    "protocol",
+    "type",
+    "impl",
+];
+
+const FUNCTION_KEYWORDS: &[&str] = &["fn", "fun", "func", "def", "function"];
+
+/// Words that start a statement rather than a declaration.
+const STATEMENTS: &[&str] = &[
+    "if",
+    "for",
+    "while",
+    "switch",
+    "catch",
+    "return",
+    "new",
+    "throw",
+    "else",
+    "do",
+    "try",
+    "when",
+    "match",
+    "using",
+    "lock",
+    "synchronized",
+    "await",
+    "yield",
+    "case",
+];
+
+/// A lightweight outline of `lines`: declarations found line by line from
+/// their keywords, plus C-style method signatures (`ResponseEntity<Pet>
+/// getPetById(...) {`). Good enough for generated code, which is regular;
+/// not a parser.
+pub fn symbols(path: &Path, lines: &[String]) -> Vec<Symbol> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let c_style = matches!(
+        ext,
+        "java" | "cs" | "ts" | "tsx" | "js" | "jsx" | "dart" | "php" | "cpp" | "hpp" | "c" | "h"
+    );
+    let keywords = matches!(ext, "go" | "py" | "rs" | "kt" | "kts" | "scala" | "swift");
+    if !c_style && !keywords {
+        return Vec::new();
+    }
+
+    let mut found: Vec<(Symbol, usize)> = Vec::new();
+    for (line, text) in lines.iter().enumerate() {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("//")
+            || trimmed.starts_with('*')
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('#')
+            || trimmed.starts_with('@')
+        {
+            continue;
+        }
+        let symbol = keyword_declaration(trimmed)
+            .or_else(|| c_style.then(|| method_signature(trimmed, ext)).flatten());
+        if let Some((name, kind)) = symbol {
+            let indent = text.len() - trimmed.len();
+            found.push((
+                Symbol {
+                    name,
+                    kind,
+                    line,
+                    depth: 0,
+                },
+                indent,
+            ));
+        }
+    }
+
+    // Indentation widths, ranked, become nesting levels.
+    let mut widths: Vec<usize> = found.iter().map(|(_, indent)| *indent).collect();
+    widths.sort_unstable();
+    widths.dedup();
+    found
+        .into_iter()
+        .map(|(symbol, indent)| Symbol {
+            depth: widths.binary_search(&indent).unwrap_or(0),
+            ..symbol
+        })
+        .collect()
+}
+
+/// `class Pet`, `pub fn run`, `export async function list`, `func (c *Client)
+/// GetPet`: modifiers, a keyword, then the name.
+fn keyword_declaration(line: &str) -> Option<(String, SymbolKind)> {
+    let mut words = line.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        if TYPE_KEYWORDS.contains(&word) {
+            // `enum class Color`, `data class Pet`.
+            while words.peek().is_some_and(|w| TYPE_KEYWORDS.contains(w)) {
+                words.next();
+            }
+            if word == "impl" {
+                let rest: Vec<&str> = words.take_while(|w| *w != "{" && *w != "where").collect();
+                return (!rest.is_empty()).then(|| (rest.join(" "), SymbolKind::Type));
+            }
+            return identifier(words.next()?).map(|name| (name, SymbolKind::Type));
+        }
+        if FUNCTION_KEYWORDS.contains(&word) {
+            let mut next = words.next()?;
+            // A Go receiver: `func (c *Client) GetPet(`.
+            if next.starts_with('(') {
+                let mut rest = next;
+                while !rest.contains(')') {
+                    rest = words.next()?;
+                }
+                next = words.next()?;
+            }
+            return identifier(next).map(|name| (name, SymbolKind::Function));
+        }
+        if !is_modifier(word) {
+            return None;
+        }
+    }
+    None
+}
+
+/// A method declared by its return type: `public Pet getPetById(Long id) {`.
+fn method_signature(line: &str, ext: &str) -> Option<(String, SymbolKind)> {
+    let line = line.trim_end();
+    if line.ends_with(';') {
+        return None;
+    }
+    let open = line.find('(')?;
+    let before = line[..open].trim_end();
+    let name_start = before
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .map_or(0, |i| i + 1);
+    let name = identifier(&before[name_start..])?;
+    // `client.call(new Callback() {` calls a method on something.
+    if name_start > 0 && !before[..name_start].ends_with(char::is_whitespace) {
+        return None;
+    }
+    let prefix = before[..name_start].trim();
+    if STATEMENTS.contains(&name.as_str())
+        || prefix.split_whitespace().any(|w| STATEMENTS.contains(&w))
+        || !prefix
+            .chars()
+            .all(|c| c.is_alphanumeric() || c.is_whitespace() || "_<>,[]?.*&:$".contains(c))
+    {
+        return None;
+    }
+    // Without a return type it's a call, unless a TypeScript member
+    // signature gives itself away with a return type annotation.
+    if prefix.is_empty() && !(matches!(ext, "ts" | "tsx") && line.contains("):")) {
+        return None;
+    }
+    let declares = line.ends_with('{') || line.ends_with('(') || line.ends_with(',');
+    let closed = line.ends_with(')') || line.contains(") throws ") || line.contains("):");
+    (declares || closed).then_some((name, SymbolKind::Function))
+}
+
+fn is_modifier(word: &str) -> bool {
+    word.starts_with("pub(")
+        || (word.chars().all(|c| c.is_ascii_lowercase()) && !STATEMENTS.contains(&word))
+}
+
+/// The identifier at the start of `word`, if it starts with one.
+fn identifier(word: &str) -> Option<String> {
+    let name: String = word
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+        .collect();
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        .then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(file: &str, source: &str) -> Vec<(String, SymbolKind, usize)> {
+        let lines: Vec<String> = source.lines().map(str::to_string).collect();
+        symbols(Path::new(file), &lines)
+            .into_iter()
+            .map(|s| (s.name, s.kind, s.depth))
+            .collect()
+    }
+
+    fn names(symbols: &[(String, SymbolKind, usize)]) -> Vec<&str> {
+        symbols.iter().map(|(name, ..)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn java_controller_lists_class_and_methods() {
+        let source = r#"
+package org.openapitools.api;
+
+/**
+ * Pet operations.
+ */
+@RestController
+public class PetApiController implements PetApi {
+
+    private final NativeWebRequest request;
+
+    @Autowired
+    public PetApiController(NativeWebRequest request) {
+        this.request = request;
+    }
+
+    @GetMapping(value = "/pet/{petId}")
+    public ResponseEntity<Pet> getPetById(
+        @PathVariable("petId") Long petId
+    ) {
+        if (petId == null) {
+            return ResponseEntity.badRequest().build();
+        }
+        return service.find(petId);
+    }
+
+    default Optional<NativeWebRequest> getRequest() {
+        return Optional.ofNullable(request);
+    }
+
+    public enum StatusEnum {
+        AVAILABLE("available");
+    }
+}
+"#;
+        let symbols = outline("PetApiController.java", source);
+        assert_eq!(
+            names(&symbols),
+            [
+                "PetApiController",
+                "PetApiController",
+                "getPetById",
+                "getRequest",
+                "StatusEnum",
+            ]
+        );
+        assert_eq!(symbols[0].1, SymbolKind::Type);
+        assert_eq!(symbols[0].2, 0);
+        assert_eq!(symbols[2], ("getPetById".into(), SymbolKind::Function, 1));
+    }
+
+    #[test]
+    fn keyword_languages_list_their_declarations() {
+        let go = "\
+type Pet struct {
+\tName string
+}
+
+func (c *PetApiService) GetPetById(ctx context.Context, id int64) (Pet, error) {
+\treturn c.get(id)
+}
+
+func NewPetApiService() *PetApiService {
+";
+        assert_eq!(
+            names(&outline("api_pet.go", go)),
+            ["Pet", "GetPetById", "NewPetApiService"]
+        );
+
+        let python = "\
+class PetApi:
+    def __init__(self, client=None):
+        pass
+
+    async def get_pet_by_id(self, pet_id):
+        return await self.call(pet_id)
+";
+        let symbols = outline("pet_api.py", python);
+        assert_eq!(names(&symbols), ["PetApi", "__init__", "get_pet_by_id"]);
+        assert_eq!(symbols[2].2, 1);
+
+        let kotlin = "data class Pet(val id: Long)\nenum class Status { A }\nsuspend fun getPet(id: Long): Pet {";
+        assert_eq!(
+            names(&outline("Pet.kt", kotlin)),
+            ["Pet", "Status", "getPet"]
+        );
+
+        let typescript = "\
+export class PetApi extends BaseAPI {
+    public async getPetById(petId: number): Promise<Pet> {
+        const response = await this.request(petId);
+        doSomething(response);
+    }
+    listPets(limit?: number): Observable<Pet[]> {
+";
+        assert_eq!(
+            names(&outline("PetApi.ts", typescript)),
+            ["PetApi", "getPetById", "listPets"]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::mpsc::Receiver;
+
+use lazyoav::config::{Config, Mode};
+use lazyoav::custom::CustomGeneratorDef;
+use lazyoav::docker::OutputLine;
+use lazyoav::generators;
+
+/// Where a picker entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerSource {
+    Builtin,
+    Custom,
+    /// Reported by `openapi-generator list`, no curated config.
+    Live,
+    /// Named in `.oavc` but not found anywhere else.
+    Configured,
+}
+
+#[derive(Debug, Clone)]
+pub struct PickerEntry {
+    pub name: String,
+    pub scope: String,
+    pub enabled: bool,
+    pub source: PickerSource,
+}
+
+/// State for the generator picker overlay.
+pub struct GeneratorPickerState {
+    pub entries: Vec<PickerEntry>,
+    pub index: usize,
+    /// Output of a running `openapi-generator list` container, if any.
+    pub live_rx: Option<Receiver<OutputLine>>,
+}
+
+impl GeneratorPickerState {
+    /// Build the entry list for the scopes `cfg.mode` covers.
+    ///
+    /// Empty generator lists mean "all built-in and custom generators", so
+    /// every entry of that scope starts enabled.
+    pub fn new(cfg: &Config, custom_defs: &[CustomGeneratorDef]) -> Self {
+        let mut entries = Vec::new();
+        for scope in scopes_for_mode(cfg.mode) {
+            let active = match *scope {
+                "server" => &cfg.server_generators,
+                _ => &cfg.client_generators,
+            };
+            let is_enabled = |name: &str| active.is_empty() || active.iter().any(|g| g == name);
+
+            for def in generators::builtin_generators_for_scope(scope) {
+                entries.push(PickerEntry {
+                    name: def.name.to_string(),
+                    scope: scope.to_string(),
+                    enabled: is_enabled(def.name),
+                    source: PickerSource::Builtin,
+                });
+            }
+            for def in custom_defs.iter().filter(|d| d.scope == *scope) {
+                entries.push(PickerEntry {
+                    name: def.name.clone(),
+                    scope: scope.to_string(),
+                    enabled: is_enabled(&def.name),
+                    source: PickerSource::Custom,
+                });
+            }
+            for name in active {
+                if !entries.iter().any(|e| e.scope == *scope && e.name == *name) {
+                    entries.push(PickerEntry {
+                        name: name.clone(),
+                        scope: scope.to_string(),
+                        enabled: true,
+                        source: PickerSource::Configured,
+                    });
+                }
+            }
+        }
+
+        Self {
+            entries,
+            index: 0,
+            live_rx: None,
+        }
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(entry) = self.entries.get_mut(self.index) {
+            entry.enabled = !entry.enabled;
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let max = self.entries.len().saturating_sub(1);
+        self.index = self.index.saturating_add_signed(delta).min(max);
+    }
+
+    /// Append generators from a live `openapi-generator list` that aren't
+    /// already shown. Returns how many were added.
+    pub fn merge_live(&mut self, pairs: Vec<(String, String)>) -> usize {
+        let scopes: Vec<String> = self.entries.iter().map(|e| e.scope.clone()).collect();
+        let mut added = 0;
+        for (name, scope) in pairs {
+            if !scopes.contains(&scope)
+                || self
+                    .entries
+                    .iter()
+                    .any(|e| e.scope == scope && e.name == name)
+            {
+                continue;
+            }
+            self.entries.push(PickerEntry {
+                name,
+                scope,
+                enabled: false,
+                source: PickerSource::Live,
+            });
+            added += 1;
+        }
+        added
+    }
+
+    /// Enabled generator names for a scope, in display order.
+    pub fn enabled(&self, scope: &str) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.scope == scope && e.enabled)
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    /// Scopes shown in the picker that have nothing enabled.
+    ///
+    /// Saving an empty list would silently mean "all generators", so these
+    /// block saving.
+    pub fn empty_scopes(&self) -> Vec<&str> {
+        let mut empty: Vec<&str> = Vec::new();
+        for entry in &self.entries {
+            let scope = entry.scope.as_str();
+            if !empty.contains(&scope) && self.enabled(scope).is_empty() {
+                empty.push(scope);
+            }
+        }
+        empty
+    }
+}
+
+fn scopes_for_mode(mode: Mode) -> &'static [&'static str] {
+    match mode {
+        Mode::Server => &["server"],
+        Mode::Client => &["client"],
+        Mode::Both => &["server", "client"],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: Mode, server: &[&str], client: &[&str]) -> Config {
+        Config {
+            mode,
+            server_generators: server.iter().map(|s| s.to_string()).collect(),
+            client_generators: client.iter().map(|s| s.to_string()).collect(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn empty_list_enables_every_builtin() {
+        let picker = GeneratorPickerState::new(&config(Mode::Server, &[], &[]), &[]);
+        assert_eq!(picker.entries.len(), 6);
+        assert!(picker.entries.iter().all(|e| e.enabled));
+    }
+
+    #[test]
+    fn explicit_list_enables_only_named() {
+        let picker = GeneratorPickerState::new(&config(Mode::Server, &["spring"], &[]), &[]);
+        assert_eq!(picker.enabled("server"), vec!["spring"]);
+    }
+
+    #[test]
+    fn unknown_configured_generator_is_listed() {
+        let picker = GeneratorPickerState::new(&config(Mode::Client, &[], &["rust"]), &[]);
+        let rust = picker.entries.iter().find(|e| e.name == "rust").unwrap();
+        assert_eq!(rust.source, PickerSource::Configured);
+        assert!(rust.enabled);
+    }
+
+    #[test]
+    fn toggle_and_empty_scopes() {
+        let mut picker = GeneratorPickerState::new(&config(Mode::Server, &["spring"], &[]), &[]);
+        picker.index = 0;
+        assert_eq!(picker.entries[0].name, "spring");
+        picker.toggle_selected();
+        assert_eq!(picker.empty_scopes(), vec!["server"]);
+    }
+
+    #[test]
+    fn merge_live_skips_known_and_hidden_scopes() {
+        let mut picker = GeneratorPickerState::new(&config(Mode::Server, &[], &[]), &[]);
+        let added = picker.merge_live(vec![
+            ("spring".into(), "server".into()),
+            ("rust-axum".into(), "server".into()),
+            ("rust".into(), "client".into()),
+        ]);
+        assert_eq!(added, 1);
+        let last = picker.entries.last().unwrap();
+        assert_eq!(last.name, "rust-axum");
+        assert!(!last.enabled);
+    }
+
+    #[test]
+    fn move_by_clamps() {
+        let mut picker = GeneratorPickerState::new(&config(Mode::Server, &[], &[]), &[]);
+        picker.move_by(-3);
+        assert_eq!(picker.index, 0);
+        picker.move_by(100);
+        assert_eq!(picker.index, 5);
+    }
+}
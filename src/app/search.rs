@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+/// `/` search over lines of text: the whole spec from the Spec Context
+/// panel, or the Raw Log tab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextSearch {
+    pub query: String,
+    /// Whether the query is still being typed.
+    pub editing: bool,
+    /// 1-based lines containing the query, in order.
+    pub matches: Vec<usize>,
+    /// Index into `matches` of the match shown.
+    pub current: usize,
+    /// Phase and error selected when the search started; selecting another
+    /// finding hands the spec panel back to it. A log search only follows
+    /// the phase.
+    pub anchor: (usize, usize),
+}
+
+impl TextSearch {
+    pub fn new(anchor: (usize, usize)) -> Self {
+        Self {
+            query: String::new(),
+            editing: true,
+            matches: Vec::new(),
+            current: 0,
+            anchor,
+        }
+    }
+
+    /// Recompute the matches in `lines` and select the first one at or after
+    /// 1-based line `from`, wrapping to the top.
+    pub fn update<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>, from: usize) {
+        self.matches = lines
+            .into_iter()
+            .enumerate()
+            .filter(|(_, line)| !self.ranges(line).is_empty())
+            .map(|(i, _)| i + 1)
+            .collect();
+        self.current = self.matches.iter().position(|&l| l >= from).unwrap_or(0);
+    }
+
+    /// Line of the selected match.
+    pub fn current_line(&self) -> Option<usize> {
+        self.matches.get(self.current).copied()
+    }
+
+    /// Select the next (or previous) match, wrapping around.
+    pub fn step(&mut self, forward: bool) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % count
+        } else {
+            (self.current + count - 1) % count
+        };
+    }
+
+    /// Byte ranges of the query in `line`. Case-insensitive unless the query
+    /// has an uppercase letter.
+    pub fn ranges(&self, line: &str) -> Vec<Range<usize>> {
+        if self.query.is_empty() {
+            return Vec::new();
+        }
+        let smart_case = self.query.chars().any(char::is_uppercase);
+        // ASCII folding keeps byte offsets valid for the original line.
+        let (haystack, needle) = if smart_case {
+            (line.to_string(), self.query.clone())
+        } else {
+            (line.to_ascii_lowercase(), self.query.to_ascii_lowercase())
+        };
+        haystack
+            .match_indices(&needle)
+            .map(|(start, m)| start..start + m.len())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn finds_matches_from_a_line_and_wraps() {
+        let spec = lines("paths:\n  /pets:\n    get: {}\n  /pet/{id}:\n    get: {}");
+        let mut search = TextSearch::new((0, 0));
+        search.query = "pet".into();
+        search.update(spec.iter().map(String::as_str), 3);
+        assert_eq!(search.matches, vec![2, 4]);
+        assert_eq!(search.current_line(), Some(4));
+        search.step(true);
+        assert_eq!(search.current_line(), Some(2));
+        search.step(false);
+        assert_eq!(search.current_line(), Some(4));
+
+        search.update(spec.iter().map(String::as_str), 5);
+        assert_eq!(search.current_line(), Some(2));
+    }
+
+    #[test]
+    fn smart_case_ranges() {
+        let mut search = TextSearch::new((0, 0));
+        search.query = "pet".into();
+        assert_eq!(search.ranges("Pet pet"), vec![0..3, 4..7]);
+        search.query = "Pet".into();
+        assert_eq!(search.ranges("Pet pet"), vec![0..3]);
+    }
+}
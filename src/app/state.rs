@@ -1,19 +1,43 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::mpsc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::fix::FixProposal;
+use crate::fix::{self, FixOrigin, FixProposal};
 use crate::highlight::HighlightEngine;
-use crate::log_parser::LintError;
-use crate::spec::SpecIndex;
-use lazyoav::config::Config;
+use crate::log_parser::{
+    CodeFinding, LintError, LogFilter, Severity as LintSeverity, count_step_errors,
+};
+use crate::rule_docs::RuleDocs;
+use crate::spec::{Project, SpecIndex, parse_spec};
+use lazyoav::baseline::{Baseline, Finding};
+use lazyoav::changelog::Changelog;
+use lazyoav::clean::Usage;
+use lazyoav::config::{Config, Diagnostic, FailOn, Linter, Severity};
 use lazyoav::custom::CustomGeneratorDef;
-use lazyoav::docker::CancelToken;
+use lazyoav::docker::{CancelToken, ContainerControl};
 use lazyoav::keys::Keymap;
-use lazyoav::pipeline::{PipelineEvent, ValidateReport};
-
-use super::diff::DiffViewState;
+use lazyoav::leftovers::Leftovers;
+use lazyoav::pipeline::{Invocation, Phase, PipelineEvent, ValidateReport};
+use lazyoav::plugins::{PluginFinding, Registry};
+use lazyoav::regions;
+use lazyoav::runs::RunRecord;
+use lazyoav::score::Score;
+use lazyoav::stats::Stats;
+use lazyoav::suppress::Suppression;
+
+use super::compare::ReportComparison;
+use super::diff::{DiffLine, DiffViewState, NumberedLine, numbered_diff};
+use super::edit::InlineEditState;
+use super::matrix::MatrixView;
+use super::outline::OutlineView;
+use super::picker::GeneratorPickerState;
+use super::search::TextSearch;
+use super::tour::TourState;
+use super::wizard::InitWizardState;
+use super::workspace::WorkspaceView;
 
 /// Top-level view: validator grid or generated code browser.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,11 +54,14 @@ pub enum BrowserPanel {
 }
 
 /// A single entry in the flattened file tree.
+#[derive(Clone)]
 pub struct FileEntry {
     pub depth: usize,
     pub name: String,
     pub is_dir: bool,
     pub path: PathBuf,
+    /// Matched by the output directory's `.openapi-generator-ignore`.
+    pub ignored: bool,
 }
 
 /// State for the generated code browser view.
@@ -43,8 +70,19 @@ pub struct CodeBrowserState {
     pub generators: Vec<(String, String)>,
     /// Currently selected generator tab.
     pub generator_index: usize,
-    /// Flattened file tree for the active generator.
+    /// Every file and directory of the active generator's output.
+    pub all_files: Vec<FileEntry>,
+    /// The rows of `all_files` on screen: those inside a collapsed directory
+    /// or not matching `filter` are left out.
     pub file_tree: Vec<FileEntry>,
+    /// Directories whose contents are hidden.
+    pub collapsed: HashSet<PathBuf>,
+    /// Glob patterns the tree is filtered down to, space-separated.
+    pub filter: Option<String>,
+    /// The filter prompt being typed, while it is open.
+    pub filter_input: Option<String>,
+    /// Patterns of the active generator's `.openapi-generator-ignore`.
+    pub ignore_patterns: Vec<String>,
     /// Selected item in the file tree.
     pub file_index: usize,
     /// Loaded file content (lines), if any.
@@ -53,6 +91,8 @@ pub struct CodeBrowserState {
     pub opened_file_index: Option<usize>,
     /// Vertical scroll offset in the file content viewer.
     pub file_scroll: u16,
+    /// Line wrapping in the file content viewer.
+    pub file_wrap: LineWrap,
     /// Which sub-panel currently has focus.
     pub browser_focus: BrowserPanel,
     /// Monotonic counter incremented on each file load (ensures highlight cache miss).
@@ -61,6 +101,13 @@ pub struct CodeBrowserState {
     pub highlight_engine: RefCell<HighlightEngine>,
     /// State for the generation diff toggle mode.
     pub diff_state: DiffViewState,
+    /// Size and file count of each generator's output, by
+    /// `"{scope}/{generator}"`, as the background scan reports them.
+    pub usage: HashMap<String, Usage>,
+    /// Results of a running usage scan.
+    pub usage_rx: Option<mpsc::Receiver<(String, Usage)>>,
+    /// Findings of the last run's code lint steps, by `"{scope}/{generator}"`.
+    pub code_findings: HashMap<String, Vec<CodeFinding>>,
 }
 
 impl CodeBrowserState {
@@ -68,15 +115,24 @@ impl CodeBrowserState {
         Self {
             generators: Vec::new(),
             generator_index: 0,
+            all_files: Vec::new(),
             file_tree: Vec::new(),
+            collapsed: HashSet::new(),
+            filter: None,
+            filter_input: None,
+            ignore_patterns: Vec::new(),
             file_index: 0,
             file_content: None,
             opened_file_index: None,
             file_scroll: 0,
+            file_wrap: LineWrap::default(),
             browser_focus: BrowserPanel::FileTree,
             content_version: 0,
             highlight_engine: RefCell::new(HighlightEngine::new()),
             diff_state: DiffViewState::new(),
+            usage: HashMap::new(),
+            usage_rx: None,
+            code_findings: HashMap::new(),
         }
     }
 
@@ -86,6 +142,207 @@ impl CodeBrowserState {
             .get(self.generator_index)
             .map(|(generator, scope)| format!("{scope}/{generator}"))
     }
+
+    /// The active generator's code lint findings in the file at `path`.
+    pub fn findings_in(&self, path: &Path) -> Vec<&CodeFinding> {
+        let Some(findings) = self
+            .active_generator_dir()
+            .and_then(|dir| self.code_findings.get(&dir))
+        else {
+            return Vec::new();
+        };
+        findings
+            .iter()
+            .filter(|f| path.ends_with(&f.file))
+            .collect()
+    }
+}
+
+/// The fix overlay's diff: the whole region a fix changes, every edit
+/// included, scrolled by the app's `fix_scroll`.
+pub struct FixPreview {
+    pub lines: Vec<NumberedLine>,
+    /// Version of the spec the old side's line indices point into, for
+    /// highlighting; `None` when the diff only covers the proposal's own
+    /// context lines.
+    pub spec_version: Option<u64>,
+    /// The spec with the fix applied, for highlighting the new side.
+    pub after: Option<SpecIndex>,
+    /// 0-based spec line of the first old and new line, when the diff only
+    /// covers the proposal's context lines.
+    pub first_line: usize,
+}
+
+impl FixPreview {
+    pub fn new(proposal: &FixProposal, spec: Option<&SpecIndex>) -> Self {
+        if let Some(spec) = spec
+            && let Ok(after) = fix::applied(proposal, spec.lines())
+        {
+            return Self {
+                lines: numbered_diff(spec.lines(), &after),
+                spec_version: Some(spec.version()),
+                after: parse_spec(&after.join("\n")).ok(),
+                first_line: 0,
+            };
+        }
+        // Without the spec there's one hunk, and its header would count
+        // from the first context line.
+        let (first_line, old, new) = proposal.own_lines();
+        let mut lines = numbered_diff(&old, &new);
+        lines.retain(|l| !matches!(l.line, DiffLine::HunkHeader(_)));
+        Self {
+            lines,
+            spec_version: None,
+            after: None,
+            first_line,
+        }
+    }
+}
+
+/// The API changelog overlay: changes since `base`, scrolled by `scroll`.
+pub struct ChangelogView {
+    pub base: String,
+    pub changelog: Changelog,
+    pub scroll: usize,
+}
+
+/// The spec as committed at a git revision, kept so narrowing to changed
+/// lines again after a run doesn't ask git again.
+pub struct BaseSpec {
+    pub spec: PathBuf,
+    pub revision: String,
+    /// The committed spec, or why git couldn't show it.
+    pub contents: Result<String, String>,
+}
+
+/// Findings narrowed to the spec lines changed since `base`, kept beside
+/// the full lists so switching back to all findings is instant.
+#[derive(Default)]
+pub struct ChangedFindings {
+    /// What the spec was compared with: a git revision, or `last run`.
+    pub base: String,
+    /// 1-based line ranges that differ from `base`.
+    pub ranges: Vec<Range<usize>>,
+    pub structure: Vec<LintError>,
+    /// Lint findings in `lint_errors` order, with where the known and the
+    /// suppressed ones begin among them.
+    pub lint: Vec<LintError>,
+    pub known_start: usize,
+    pub suppressed_start: usize,
+    /// Security findings in `security_errors` order, likewise.
+    pub security: Vec<LintError>,
+    pub security_known_start: usize,
+    pub security_suppressed_start: usize,
+}
+
+/// The rule statistics overlay, scrolled by `scroll`.
+pub struct RuleStatsView {
+    pub stats: Stats,
+    pub scroll: usize,
+}
+
+/// The dry run overlay: the `docker` command line of every step a run
+/// would start, with the selected one copyable.
+pub struct DryRunView {
+    /// `(step, command line)` in start order.
+    pub steps: Vec<(String, String)>,
+    pub selected: usize,
+}
+
+/// The resolved schema overlay for the schema at `pointer`: its tree and an
+/// example payload, one of which is shown and scrolled by `scroll`.
+pub struct SchemaView {
+    pub pointer: String,
+    pub tree: Vec<String>,
+    pub payload: Vec<String>,
+    pub show_payload: bool,
+    pub scroll: usize,
+}
+
+impl SchemaView {
+    /// The lines currently on screen: the payload or the tree.
+    pub fn lines(&self) -> &[String] {
+        if self.show_payload {
+            &self.payload
+        } else {
+            &self.tree
+        }
+    }
+}
+
+/// The reference graph overlay around `subject`: what references it and
+/// what it references, each with its depth from `subject`.
+pub struct RefGraphView {
+    pub subject: String,
+    pub used_by: Vec<(usize, String)>,
+    pub uses: Vec<(usize, String)>,
+    pub scroll: usize,
+}
+
+/// Free navigation in the Spec Context panel: a cursor line moved by hand
+/// instead of following the selected finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecCursor {
+    /// 1-based spec line.
+    pub line: usize,
+    /// A pinned cursor stays put when another finding is selected.
+    pub pinned: bool,
+    /// Phase and error selected when navigation started; an unpinned cursor
+    /// gives way once the selection moves.
+    pub anchor: (usize, usize),
+}
+
+/// The run history overlay: recorded runs, newest first, to pick two whose
+/// generated output to compare.
+pub struct RunHistoryView {
+    pub runs: Vec<RunRecord>,
+    pub selected: usize,
+    /// Run marked as the other side of the comparison. Without one, the
+    /// selected run is compared with the run before it.
+    pub base: Option<usize>,
+}
+
+/// How a content panel fits lines wider than itself: wrapped, or cut off
+/// and scrolled sideways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineWrap {
+    pub wrap: bool,
+    /// Columns scrolled past on the left while not wrapping.
+    pub offset: u16,
+}
+
+impl Default for LineWrap {
+    fn default() -> Self {
+        Self {
+            wrap: true,
+            offset: 0,
+        }
+    }
+}
+
+impl LineWrap {
+    /// Columns moved per sideways scroll.
+    const STEP: u16 = 8;
+
+    pub fn toggle(&mut self) {
+        self.wrap = !self.wrap;
+        self.offset = 0;
+    }
+
+    /// Scroll sideways. Returns `false` when there is nothing to do (the
+    /// panel wraps, or it is already at the left edge), so the key can fall
+    /// back to its other meaning.
+    pub fn scroll(&mut self, right: bool) -> bool {
+        if self.wrap || (!right && self.offset == 0) {
+            return false;
+        }
+        self.offset = if right {
+            self.offset.saturating_add(Self::STEP)
+        } else {
+            self.offset.saturating_sub(Self::STEP)
+        };
+        true
+    }
 }
 
 /// Which panel currently has focus.
@@ -175,11 +432,82 @@ impl PhaseStatus {
     }
 }
 
+/// Which visible lint findings fail the lint phase, from `fail_on` and
+/// `fail_on_rules`. A `None` threshold never fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailPolicy {
+    pub threshold: Option<LintSeverity>,
+    pub rules: HashMap<String, Option<LintSeverity>>,
+    /// Whether `fail_on` or `fail_on_rules` is set, so the linter's own
+    /// exit status may disagree with the policy.
+    pub configured: bool,
+}
+
+impl Default for FailPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: Some(LintSeverity::Error),
+            rules: HashMap::new(),
+            configured: false,
+        }
+    }
+}
+
+impl FailPolicy {
+    /// Without `fail_on`, the lowest severity that fails the linter itself:
+    /// `spectral_fail_severity` for Spectral; Redocly only fails on errors.
+    pub fn from_config(cfg: &Config) -> Self {
+        let linter = match cfg.linter {
+            Linter::Spectral => LintSeverity::from_str_lossy(&cfg.spectral_fail_severity),
+            _ => LintSeverity::Error,
+        };
+        Self {
+            threshold: cfg.fail_on.map_or(Some(linter), fail_on_threshold),
+            rules: cfg
+                .fail_on_rules
+                .iter()
+                .map(|(rule, &fail_on)| (rule.clone(), fail_on_threshold(fail_on)))
+                .collect(),
+            configured: cfg.fail_on.is_some() || !cfg.fail_on_rules.is_empty(),
+        }
+    }
+
+    /// Whether `err` fails the lint phase.
+    pub fn blocks(&self, err: &LintError) -> bool {
+        self.rules
+            .get(&err.rule)
+            .copied()
+            .unwrap_or(self.threshold)
+            .is_some_and(|threshold| err.severity >= threshold)
+    }
+
+    /// Short description for the Summary, e.g. `warning (+2 rules)`.
+    pub fn describe(&self) -> String {
+        let mut text = self
+            .threshold
+            .map_or_else(|| "never".to_string(), |t| t.to_string());
+        if !self.rules.is_empty() {
+            text.push_str(&format!(" (+{} rules)", self.rules.len()));
+        }
+        text
+    }
+}
+
+fn fail_on_threshold(fail_on: FailOn) -> Option<LintSeverity> {
+    match fail_on {
+        FailOn::Error => Some(LintSeverity::Error),
+        FailOn::Warning => Some(LintSeverity::Warning),
+        FailOn::Never => None,
+    }
+}
+
 /// A flattened phase entry for the phases list.
 pub struct PhaseEntry {
     pub label: String,
     pub status: PhaseStatus,
     pub error_count: usize,
+    /// How many of `error_count` are in the baseline; `None` without one.
+    pub known_count: Option<usize>,
 }
 
 /// Severity level for a transient status message.
@@ -199,6 +527,87 @@ pub struct StatusMessage {
     pub level: StatusLevel,
 }
 
+/// A captured line of pipeline output.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// Time since the step started; `None` for lines the TUI adds itself.
+    pub elapsed: Option<Duration>,
+    pub text: String,
+}
+
+impl LogLine {
+    /// Relative time prefix such as `+03.2s`, or blank padding when untimed.
+    pub fn time_prefix(&self) -> String {
+        match self.elapsed {
+            Some(d) => format!("+{:04.1}s", d.as_secs_f64()),
+            None => " ".repeat(6),
+        }
+    }
+}
+
+/// A step that is about to hit its timeout, awaiting the user's decision.
+#[derive(Debug)]
+pub struct TimeoutPrompt {
+    pub phase: Phase,
+    /// Time left when the warning arrived.
+    pub remaining: Duration,
+    pub received: Instant,
+    pub control: mpsc::Sender<ContainerControl>,
+}
+
+impl TimeoutPrompt {
+    /// Time left before the container is killed, counting down from arrival.
+    pub fn time_left(&self) -> Duration {
+        self.remaining.saturating_sub(self.received.elapsed())
+    }
+}
+
+/// Confirmation before deleting generated output, with what each choice
+/// would reclaim.
+pub struct CleanPrompt {
+    /// The code browser's active generator (`"{scope}/{generator}"`) and
+    /// the size of its output, when there is one.
+    pub generator: Option<(String, u64)>,
+    /// Size of all generated output.
+    pub all: u64,
+}
+
+/// Question asked before a project's `.oavc` may run commands on the host.
+pub struct TrustPrompt {
+    pub root: PathBuf,
+    /// What it would run, e.g. `2 hooks`.
+    pub commands: Vec<String>,
+}
+
+/// Confirmation before rewriting the spec for OpenAPI 3.1.
+pub struct MigratePrompt {
+    /// Fixes for the migration findings that have one.
+    pub proposals: Vec<FixProposal>,
+    /// How many migration findings there are.
+    pub findings: usize,
+}
+
+/// Whether `NO_COLOR` asks for output without colour, see
+/// <https://no-color.org>: set to anything but the empty string.
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Format a whole number of seconds as `5 min` or `90s`.
+pub fn format_secs(secs: u64) -> String {
+    if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// The rule the fix assistant was asked about, and its proposal or error.
+pub type AssistAnswer = (String, Result<Option<FixProposal>, String>);
+
+/// The finding a plugin was asked to fix, and its proposal or error.
+pub type PluginFixAnswer = (LintError, Result<Option<FixProposal>, String>);
+
 /// Top-level application state.
 pub struct App {
     pub running: bool,
@@ -222,9 +631,78 @@ pub struct App {
     pub report: Option<ValidateReport>,
     /// Whether a validation is currently running.
     pub validating: bool,
-
-    /// Parsed lint errors from the report's lint log.
+    /// Spec opened in a remote editor, with its modification time then;
+    /// saving it there re-runs validation.
+    pub watched_spec: Option<(PathBuf, SystemTime)>,
+    /// The spec and its modification time when last seen, to notice edits
+    /// made to it outside lazyoav.
+    pub spec_seen: Option<(PathBuf, SystemTime)>,
+    /// The spec changed on disk since the report was made.
+    pub spec_changed: bool,
+    /// When `.oavc` was last loaded from disk, to reload it on change.
+    pub config_modified: Option<SystemTime>,
+    /// Settings that change what a run reports were edited since the
+    /// report was made.
+    pub report_stale: bool,
+    /// Work units done and in total while validating, once the pipeline has
+    /// reported any; see [`PipelineEvent::Progress`](lazyoav::pipeline::PipelineEvent).
+    pub progress: Option<(f32, usize)>,
+    /// Steps left while validating, for the remaining time in the bottom bar.
+    pub eta: Option<crate::app::eta::Eta>,
+
+    /// Syntax errors and schema violations from the structure check.
+    pub structure_errors: Vec<LintError>,
+    /// Parsed lint errors from the report's lint log, with the local
+    /// analyses' findings. Suppressed findings are kept at the end; see
+    /// [`App::set_lint_errors`].
     pub lint_errors: Vec<LintError>,
+    /// The findings parsed from the lint log alone, before severity
+    /// overrides: the only ones the fail policy looks at.
+    pub linter_findings: Vec<LintError>,
+    /// Findings of the security phase, listed under their own phase entry
+    /// and ordered like `lint_errors`; see [`App::set_security_errors`].
+    pub security_errors: Vec<LintError>,
+    /// Failed tests of each test step, in step order.
+    pub test_errors: Vec<Vec<LintError>>,
+    /// Findings of each code lint step, in step order.
+    pub code_lint_errors: Vec<Vec<LintError>>,
+    /// Findings of the plugins' analyzers, listed under their own entry
+    /// after the phases; they never decide whether lint passes.
+    pub plugin_findings: Vec<LintError>,
+    /// What a 3.0 spec needs for 3.1, listed under their own entry after
+    /// the phases.
+    pub migration_findings: Vec<LintError>,
+    /// How many entries at the end of `lint_errors` are suppressed.
+    pub suppressed_lint_count: usize,
+    /// Accepted findings from `.oav-ignore`.
+    pub suppressions: Vec<Suppression>,
+    /// Severities from `rule_severity_overrides`, by rule id.
+    pub severity_overrides: HashMap<String, LintSeverity>,
+    /// How many entries in `lint_errors` have an overridden severity. Counted
+    /// by rule, so it survives storing already overridden findings again.
+    pub overridden_lint_count: usize,
+    /// Which lint findings fail the lint phase and the run.
+    pub fail_policy: FailPolicy,
+    /// Whether suppressed findings are listed (dimmed) in the Errors panel.
+    pub show_suppressed: bool,
+    /// Set while only findings on changed spec lines are listed.
+    pub changed: Option<ChangedFindings>,
+    /// The base the changed lines were last taken against.
+    pub base_spec: Option<BaseSpec>,
+    /// Error lines counted in each generate, then compile, step log of the
+    /// report; see [`step_error_counts`].
+    pub step_error_counts: Vec<usize>,
+    /// Findings accepted by `.oav/baseline.json`, if the project has one.
+    pub baseline: Option<Baseline>,
+    /// How many visible entries in `lint_errors` are in the baseline. They
+    /// follow the new findings, ahead of the suppressed ones.
+    pub known_lint_count: usize,
+    /// How many entries at the end of `security_errors` are suppressed.
+    pub suppressed_security_count: usize,
+    /// How many visible entries in `security_errors` are in the baseline.
+    pub known_security_count: usize,
+    /// Documentation for lint rules, shown in the Detail panel's Docs tab.
+    pub rule_docs: RuleDocs,
     /// Parsed spec index for source mapping.
     pub spec_index: Option<SpecIndex>,
 
@@ -233,22 +711,136 @@ pub struct App {
     /// Token to cancel a running pipeline.
     pub cancel_token: Option<CancelToken>,
     /// Real-time log output from the active pipeline phase.
-    pub live_log: String,
+    pub live_log: Vec<LogLine>,
+    /// Timed output of every phase in the current/last run, for the raw log view.
+    pub phase_logs: HashMap<Phase, Vec<LogLine>>,
+    /// Whether log views prefix lines with their relative time.
+    pub show_log_times: bool,
+    /// Line wrapping in the Raw Log tab.
+    pub log_wrap: LineWrap,
+    /// Steps about to time out, oldest first; the first one is prompted.
+    pub timeout_prompts: Vec<TimeoutPrompt>,
+    /// Confirmation before deleting generated output, if asked.
+    pub clean_prompt: Option<CleanPrompt>,
+    /// What the clean prompt's choices would reclaim, being measured.
+    pub clean_rx: Option<mpsc::Receiver<CleanPrompt>>,
+    pub migrate_prompt: Option<MigratePrompt>,
+    /// Leftovers of crashed sessions found on startup, offered for cleanup.
+    pub leftover_prompt: Option<Leftovers>,
+    /// Leftovers being looked for when a project loads.
+    pub leftover_rx: Option<mpsc::Receiver<Leftovers>>,
+    /// An untrusted project that wants to run host commands, asked about
+    /// on startup; validation waits for the answer.
+    pub trust_prompt: Option<TrustPrompt>,
 
     /// Path to the OpenAPI spec file, if discovered.
     pub spec_path: Option<PathBuf>,
+    /// Spec given on the command line, relative to the project; takes
+    /// precedence over `spec` in `.oavc`.
+    pub spec_override: Option<String>,
 
     /// Loaded config, reused across validation runs.
     pub config: Option<Config>,
     /// Custom generator definitions loaded from `custom_generators_dir`.
     pub custom_defs: Vec<CustomGeneratorDef>,
+    /// Analyzers and fix rules from `.oav/plugins/`, shared with the
+    /// threads that run them.
+    pub plugins: Arc<Registry>,
+    /// The analyzers' findings and failures, while they run.
+    pub plugin_rx: Option<mpsc::Receiver<(Vec<PluginFinding>, Vec<String>)>>,
+    /// A plugin fix rule's answer, while it runs.
+    pub plugin_fix_rx: Option<mpsc::Receiver<PluginFixAnswer>>,
+    /// What each running automation handler reports, or why it failed.
+    pub automation_rx: Vec<mpsc::Receiver<Result<Vec<String>, String>>>,
+    /// Why recording a finished run failed, while it's copied.
+    pub record_rx: Vec<mpsc::Receiver<Result<(), String>>>,
 
     /// Transient status message for the bottom bar.
     pub status_message: Option<StatusMessage>,
     /// Active fix proposal overlay, if any.
     pub fix_proposal: Option<FixProposal>,
+    /// The diff shown in the fix overlay, built when it opens.
+    pub fix_preview: Option<FixPreview>,
+    /// What the open fix was proposed for; `None` for the fix assistant's,
+    /// which isn't asked again on its own.
+    pub fix_origin: Option<FixOrigin>,
+    pub fix_scroll: u16,
+    /// The fix assistant's answer for the named rule, while it's thinking.
+    pub fix_assist_rx: Option<mpsc::Receiver<AssistAnswer>>,
+    /// The copy prefix was pressed; the next key picks what to copy.
+    pub copy_pending: bool,
+    /// Active API changelog overlay, if any.
+    pub changelog: Option<ChangelogView>,
+    /// Active resolved schema overlay, if any.
+    pub schema_view: Option<SchemaView>,
+    /// Active reference graph overlay, if any.
+    pub ref_graph: Option<RefGraphView>,
+    /// Active run history overlay, if any.
+    pub run_history: Option<RunHistoryView>,
+    /// Active report comparison overlay, if any.
+    pub comparison: Option<ReportComparison>,
+    /// Active dry run overlay, if any.
+    pub dry_run: Option<DryRunView>,
+    /// Symbol outline of the opened generated file, if shown.
+    pub outline: Option<OutlineView>,
+    /// The last generator version matrix, kept to reopen after a diff.
+    pub matrix: Option<MatrixView>,
+    /// Whether the matrix overlay is showing.
+    pub show_matrix: bool,
+    /// The last workspace run, kept to reopen.
+    pub workspace: Option<WorkspaceView>,
+    /// Whether the workspace overlay is showing.
+    pub show_workspace: bool,
+    /// Where lazyoav was started, when that holds several projects.
+    pub project_root: Option<PathBuf>,
+    /// Projects under `project_root`; `work_dir` is one of them.
+    pub projects: Vec<Project>,
+    /// The project in use. Everything reads and writes relative to it,
+    /// never to the process's current directory, which threads may share.
+    pub work_dir: PathBuf,
+    /// Index of the project in use, if it's one of `projects`.
+    pub project: Option<usize>,
+    /// Projects being looked for under the start directory.
+    pub project_rx: Option<mpsc::Receiver<Vec<Project>>>,
+    /// Validate once the project is loaded and no startup question holds it
+    /// back; off for a project switched to, which waits for the user.
+    pub validate_on_load: bool,
+    /// Selected row of the project switcher, while it's open.
+    pub project_switcher: Option<usize>,
+    /// `/` search in the Spec Context panel.
+    pub spec_search: Option<TextSearch>,
+    /// `/` search in the Raw Log tab.
+    pub log_search: Option<TextSearch>,
+    /// Quick filters applied to the Raw Log tab.
+    pub log_filter: LogFilter,
+    /// Free navigation cursor in the Spec Context panel.
+    pub spec_cursor: Option<SpecCursor>,
+    /// Line number being typed at the go-to-line prompt.
+    pub goto_line: Option<String>,
+    /// Quality score of the last completed run.
+    pub score: Option<Score>,
+    /// Totals of recorded runs, oldest first, ending with `score`.
+    pub score_history: Vec<u8>,
+    /// Whether to show the score breakdown overlay.
+    pub show_score: bool,
+    /// Active rule statistics overlay, if any.
+    pub rule_stats: Option<RuleStatsView>,
+    /// Active inline edit overlay, if any.
+    pub inline_edit: Option<InlineEditState>,
     /// Whether to show the help overlay.
     pub show_help: bool,
+    /// Active generator picker overlay, if any.
+    pub generator_picker: Option<GeneratorPickerState>,
+    /// Active `.oavc` init wizard, offered when the project has no config.
+    pub init_wizard: Option<InitWizardState>,
+    /// Active onboarding tour, shown on first launch.
+    pub tour: Option<TourState>,
+    /// Problems found in `.oavc` and custom generators at load time.
+    pub config_diagnostics: Vec<Diagnostic>,
+    /// Whether to show the config diagnostics overlay.
+    pub show_diagnostics: bool,
+    /// Scroll offset within the diagnostics overlay.
+    pub diagnostics_scroll: usize,
     /// Whether Docker is available on the host.
     pub docker_available: bool,
     /// Pre-pipeline snapshots of generated output, keyed by `"{scope}/{generator}"`.
@@ -259,6 +851,9 @@ pub struct App {
     pub highlight_engine: RefCell<HighlightEngine>,
     /// Keybinding map (default or user-customized from .oavc).
     pub keymap: Keymap,
+    /// Draw without colour and with ASCII icons and text markers; see
+    /// [`Config::accessible`].
+    pub accessible: bool,
 }
 
 impl App {
@@ -276,22 +871,106 @@ impl App {
             detail_tab: 0,
             report: None,
             validating: false,
+            watched_spec: None,
+            spec_seen: None,
+            spec_changed: false,
+            config_modified: None,
+            report_stale: false,
+            progress: None,
+            eta: None,
             lint_errors: Vec::new(),
+            linter_findings: Vec::new(),
+            security_errors: Vec::new(),
+            structure_errors: Vec::new(),
+            plugin_findings: Vec::new(),
+            migration_findings: Vec::new(),
+            test_errors: Vec::new(),
+            code_lint_errors: Vec::new(),
+            suppressed_lint_count: 0,
+            suppressions: Vec::new(),
+            severity_overrides: HashMap::new(),
+            overridden_lint_count: 0,
+            fail_policy: FailPolicy::default(),
+            show_suppressed: false,
+            changed: None,
+            base_spec: None,
+            step_error_counts: Vec::new(),
+            rule_docs: RuleDocs::default(),
+            baseline: None,
+            known_lint_count: 0,
+            suppressed_security_count: 0,
+            known_security_count: 0,
             spec_index: None,
             pipeline_rx: None,
             cancel_token: None,
-            live_log: String::new(),
+            live_log: Vec::new(),
+            phase_logs: HashMap::new(),
+            show_log_times: false,
+            log_wrap: LineWrap::default(),
+            timeout_prompts: Vec::new(),
+            clean_prompt: None,
+            clean_rx: None,
+            migrate_prompt: None,
+            leftover_prompt: None,
+            leftover_rx: None,
+            trust_prompt: None,
             spec_path: None,
+            spec_override: None,
             config: None,
             custom_defs: Vec::new(),
+            plugins: Arc::default(),
+            plugin_rx: None,
+            plugin_fix_rx: None,
+            automation_rx: Vec::new(),
+            record_rx: Vec::new(),
             status_message: None,
             fix_proposal: None,
+            fix_preview: None,
+            fix_origin: None,
+            fix_scroll: 0,
+            fix_assist_rx: None,
+            copy_pending: false,
+            changelog: None,
+            schema_view: None,
+            ref_graph: None,
+            run_history: None,
+            comparison: None,
+            dry_run: None,
+            outline: None,
+            matrix: None,
+            show_matrix: false,
+            workspace: None,
+            show_workspace: false,
+            project_root: None,
+            projects: Vec::new(),
+            work_dir: std::env::current_dir().unwrap_or_default(),
+            project: None,
+            project_rx: None,
+            validate_on_load: true,
+            project_switcher: None,
+            spec_search: None,
+            log_search: None,
+            log_filter: LogFilter::default(),
+            spec_cursor: None,
+            goto_line: None,
+            score: None,
+            score_history: Vec::new(),
+            show_score: false,
+            rule_stats: None,
+            inline_edit: None,
             show_help: false,
+            generator_picker: None,
+            init_wizard: None,
+            tour: None,
+            config_diagnostics: Vec::new(),
+            show_diagnostics: false,
+            diagnostics_scroll: 0,
             docker_available: false,
             snapshots: HashMap::new(),
             tick: 0,
             highlight_engine: RefCell::new(HighlightEngine::new()),
             keymap: Keymap::default_keymap(),
+            accessible: no_color(),
         }
     }
 
@@ -311,21 +990,68 @@ impl App {
         });
     }
 
+    /// Show `proposal`, made for `origin`, in the fix overlay, diffed
+    /// against the spec.
+    pub fn open_fix(&mut self, proposal: FixProposal, origin: Option<FixOrigin>) {
+        self.fix_preview = Some(FixPreview::new(&proposal, self.spec_index.as_ref()));
+        self.fix_origin = origin;
+        self.fix_scroll = 0;
+        self.fix_proposal = Some(proposal);
+    }
+
+    /// Close the fix overlay, returning its proposal.
+    pub fn close_fix(&mut self) -> Option<FixProposal> {
+        self.fix_preview = None;
+        self.fix_origin = None;
+        self.fix_proposal.take()
+    }
+
+    /// Seconds added when the user accepts a timeout prompt.
+    pub fn timeout_extension(&self) -> u64 {
+        self.config
+            .as_ref()
+            .map_or(Config::default().timeout_extension, |c| c.timeout_extension)
+    }
+
     /// Number of phases without allocating entry labels.
     pub fn phase_count(&self) -> usize {
         let Some(report) = &self.report else {
             return 0;
         };
         let mut count = 0;
+        if report.phases.structure.is_some() {
+            count += 1;
+        }
         if report.phases.lint.is_some() {
             count += 1;
         }
+        if report.phases.security.is_some() {
+            count += 1;
+        }
         if let Some(steps) = &report.phases.generate {
             count += steps.len();
         }
         if let Some(steps) = &report.phases.compile {
             count += steps.len();
         }
+        if let Some(steps) = &report.phases.test {
+            count += steps.len();
+        }
+        if let Some(steps) = &report.phases.code_lint {
+            count += steps.len();
+        }
+        if report.phases.docs.is_some() {
+            count += 1;
+        }
+        if let Some(custom) = &report.phases.custom {
+            count += custom.len();
+        }
+        if !self.plugin_findings.is_empty() {
+            count += 1;
+        }
+        if !self.migration_findings.is_empty() {
+            count += 1;
+        }
         count
     }
 
@@ -337,20 +1063,41 @@ impl App {
 
         let mut entries = Vec::new();
 
+        if let Some(structure) = &report.phases.structure {
+            entries.push(PhaseEntry {
+                label: "Structure (local)".to_string(),
+                status: PhaseStatus::from_status_str(&structure.status),
+                error_count: self.structure_errors.len(),
+                known_count: None,
+            });
+        }
+
         if let Some(lint) = &report.phases.lint {
             entries.push(PhaseEntry {
                 label: format!("Lint ({})", lint.linter),
                 status: PhaseStatus::from_status_str(&lint.status),
-                error_count: self.lint_errors.len(),
+                error_count: self.suppressed_start(),
+                known_count: self.baseline.as_ref().map(|_| self.known_lint_count),
             });
         }
 
+        if let Some(security) = &report.phases.security {
+            entries.push(PhaseEntry {
+                label: format!("Security ({})", security.linter),
+                status: PhaseStatus::from_status_str(&security.status),
+                error_count: self.security_suppressed_start(),
+                known_count: self.baseline.as_ref().map(|_| self.known_security_count),
+            });
+        }
+
+        let mut step_errors = self.step_error_counts.iter().copied();
         if let Some(steps) = &report.phases.generate {
             for step in steps {
                 entries.push(PhaseEntry {
                     label: format!("Generate ({}/{})", step.generator, step.scope),
                     status: PhaseStatus::from_status_str(&step.status),
-                    error_count: 0,
+                    error_count: step_errors.next().unwrap_or(0),
+                    known_count: None,
                 });
             }
         }
@@ -360,151 +1107,952 @@ impl App {
                 entries.push(PhaseEntry {
                     label: format!("Compile ({}/{})", step.generator, step.scope),
                     status: PhaseStatus::from_status_str(&step.status),
-                    error_count: 0,
+                    error_count: step_errors.next().unwrap_or(0),
+                    known_count: None,
                 });
             }
         }
 
-        entries
-    }
+        if let Some(steps) = &report.phases.test {
+            for (i, step) in steps.iter().enumerate() {
+                entries.push(PhaseEntry {
+                    label: format!("Test ({}/{})", step.generator, step.scope),
+                    status: PhaseStatus::from_status_str(&step.status),
+                    error_count: self.test_errors.get(i).map_or(0, Vec::len),
+                    known_count: None,
+                });
+            }
+        }
 
-    /// Errors for the currently selected phase (lint only for now).
-    pub fn current_errors(&self) -> &[LintError] {
-        if let Some(report) = &self.report
-            && report.phases.lint.is_some()
-            && self.phase_index == 0
-        {
-            return &self.lint_errors;
+        if let Some(steps) = &report.phases.code_lint {
+            for (i, step) in steps.iter().enumerate() {
+                entries.push(PhaseEntry {
+                    label: format!("Code lint ({}/{})", step.generator, step.scope),
+                    status: PhaseStatus::from_status_str(&step.status),
+                    error_count: self.code_lint_errors.get(i).map_or(0, Vec::len),
+                    known_count: None,
+                });
+            }
         }
-        &[]
-    }
 
-    /// The currently selected error, if any.
-    pub fn selected_error(&self) -> Option<&LintError> {
-        let errors = self.current_errors();
-        errors.get(self.error_index)
-    }
+        if let Some(docs) = &report.phases.docs {
+            entries.push(PhaseEntry {
+                label: "Docs (redoc)".to_string(),
+                status: PhaseStatus::from_status_str(&docs.status),
+                error_count: 0,
+                known_count: None,
+            });
+        }
 
-    /// Clamp phase_index and error_index to valid bounds.
-    pub fn clamp_indices(&mut self) {
-        let count = self.phase_count();
-        if count > 0 {
-            self.phase_index = self.phase_index.min(count - 1);
-        } else {
-            self.phase_index = 0;
+        for phase in report.phases.custom.iter().flatten() {
+            entries.push(PhaseEntry {
+                label: format!("{} (custom)", phase.name),
+                status: PhaseStatus::from_status_str(&phase.status),
+                error_count: 0,
+                known_count: None,
+            });
         }
 
-        let error_count = self.current_errors().len();
-        if error_count > 0 {
-            self.error_index = self.error_index.min(error_count - 1);
-        } else {
-            self.error_index = 0;
+        if !self.plugin_findings.is_empty() {
+            entries.push(PhaseEntry {
+                label: "Plugins".to_string(),
+                status: PhaseStatus::Pending,
+                error_count: self.plugin_findings.len(),
+                known_count: None,
+            });
+        }
+
+        if !self.migration_findings.is_empty() {
+            entries.push(PhaseEntry {
+                label: "Migrate to 3.1".to_string(),
+                status: PhaseStatus::Pending,
+                error_count: self.migration_findings.len(),
+                known_count: None,
+            });
         }
+
+        entries
     }
 
-    /// Raw log text for the currently selected phase.
-    pub fn current_phase_log(&self) -> &str {
+    /// Errors for the currently selected phase: lint or security findings,
+    /// a test step's failed tests, a code lint step's findings, the
+    /// plugins' findings or the 3.1 migration findings.
+    pub fn current_errors(&self) -> &[LintError] {
         let Some(report) = &self.report else {
-            return "";
+            return &[];
         };
-
-        if self.phase_count() == 0 {
-            return "";
+        if self.migration_selected() {
+            return &self.migration_findings;
         }
-
-        // Phase 0 is always lint if present
-        let mut idx = self.phase_index;
-
-        if report.phases.lint.is_some() {
-            if idx == 0 {
-                return &report.phases.lint.as_ref().unwrap().log;
-            }
-            idx -= 1;
+        if self.plugins_selected() {
+            return &self.plugin_findings;
         }
-
-        if let Some(steps) = &report.phases.generate {
-            if idx < steps.len() {
-                return &steps[idx].log;
-            }
-            idx -= steps.len();
+        let changed = self.changed.as_ref();
+        let structure = usize::from(report.phases.structure.is_some());
+        if structure == 1 && self.phase_index == 0 {
+            return changed.map_or(&self.structure_errors, |c| &c.structure);
         }
-
-        if let Some(steps) = &report.phases.compile
-            && idx < steps.len()
-        {
-            return &steps[idx].log;
+        let lint = usize::from(report.phases.lint.is_some());
+        if lint == 1 && self.phase_index == structure {
+            let (errors, suppressed_start) = match changed {
+                Some(c) => (&c.lint, c.suppressed_start),
+                None => (&self.lint_errors, self.suppressed_start()),
+            };
+            let shown = if self.show_suppressed {
+                errors.len()
+            } else {
+                suppressed_start
+            };
+            return &errors[..shown];
         }
+        let security = usize::from(report.phases.security.is_some());
+        if security == 1 && self.phase_index == structure + lint {
+            let (errors, suppressed_start) = match changed {
+                Some(c) => (&c.security, c.security_suppressed_start),
+                None => (&self.security_errors, self.security_suppressed_start()),
+            };
+            let shown = if self.show_suppressed {
+                errors.len()
+            } else {
+                suppressed_start
+            };
+            return &errors[..shown];
+        }
+        let steps = |steps: &Option<Vec<_>>| steps.as_ref().map_or(0, Vec::len);
+        let first_test = structure
+            + lint
+            + security
+            + steps(&report.phases.generate)
+            + steps(&report.phases.compile);
+        let Some(i) = self.phase_index.checked_sub(first_test) else {
+            return &[];
+        };
+        let tests = steps(&report.phases.test);
+        let errors = if i < tests {
+            self.test_errors.get(i)
+        } else {
+            self.code_lint_errors.get(i - tests)
+        };
+        errors.map_or(&[], Vec::as_slice)
+    }
 
-        ""
+    /// Whether the 3.1 migration entry, always the last, is selected.
+    pub fn migration_selected(&self) -> bool {
+        !self.migration_findings.is_empty() && self.phase_index + 1 == self.phase_count()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::log_parser::Severity;
-    use lazyoav::pipeline::{LintResult, Phases, StepResult, Summary, ValidateReport};
+    /// Whether the plugins' entry, just before the migration one, is
+    /// selected.
+    pub fn plugins_selected(&self) -> bool {
+        let after = usize::from(!self.migration_findings.is_empty());
+        !self.plugin_findings.is_empty() && self.phase_index + 1 + after == self.phase_count()
+    }
 
-    fn make_report(
-        lint: Option<LintResult>,
-        generate: Option<Vec<StepResult>>,
-        compile: Option<Vec<StepResult>>,
-    ) -> ValidateReport {
-        ValidateReport {
-            spec: "petstore.yaml".into(),
-            mode: "both".into(),
-            phases: Phases {
-                lint,
-                generate,
-                compile,
-            },
-            summary: Summary {
-                total: 3,
-                passed: 2,
-                failed: 1,
-            },
-        }
+    /// Index in `lint_errors` where the suppressed findings begin.
+    pub fn suppressed_start(&self) -> usize {
+        self.lint_errors
+            .len()
+            .saturating_sub(self.suppressed_lint_count)
     }
 
-    fn make_lint_result(status: &str) -> LintResult {
-        LintResult {
-            linter: "spectral".into(),
-            status: status.into(),
-            log: "1:1  error  test-rule  test message".into(),
-        }
+    pub fn is_suppressed(&self, err: &LintError) -> bool {
+        self.suppressions
+            .iter()
+            .any(|s| s.matches(&err.rule, err.json_path.as_deref()))
     }
 
-    fn make_step(generator: &str, scope: &str, status: &str) -> StepResult {
-        StepResult {
-            generator: generator.into(),
-            scope: scope.into(),
-            status: status.into(),
-            log: format!("{generator}/{scope} log output"),
-        }
+    /// Index in `lint_errors` where the baseline's known findings begin.
+    pub fn known_start(&self) -> usize {
+        self.suppressed_start()
+            .saturating_sub(self.known_lint_count)
     }
 
-    fn make_lint_error(rule: &str, severity: Severity) -> LintError {
-        LintError {
-            line: 10,
-            col: 1,
-            severity,
-            rule: rule.into(),
-            message: format!("{rule} message"),
-            json_path: Some("/paths/~1pets".into()),
+    /// Where the known and the suppressed findings begin in the lint
+    /// findings listed, which may be narrowed to changed lines.
+    pub fn lint_bounds(&self) -> (usize, usize) {
+        match &self.changed {
+            Some(c) => (c.known_start, c.suppressed_start),
+            None => (self.known_start(), self.suppressed_start()),
         }
     }
 
-    // ── PhaseStatus ───────────────────────────────────────────────────
+    /// Index in `security_errors` where the suppressed findings begin.
+    pub fn security_suppressed_start(&self) -> usize {
+        self.security_errors
+            .len()
+            .saturating_sub(self.suppressed_security_count)
+    }
 
-    #[test]
-    fn phase_status_from_known_strings() {
-        assert_eq!(PhaseStatus::from_status_str("pass"), PhaseStatus::Pass);
-        assert_eq!(PhaseStatus::from_status_str("passed"), PhaseStatus::Pass);
-        assert_eq!(PhaseStatus::from_status_str("success"), PhaseStatus::Pass);
-        assert_eq!(PhaseStatus::from_status_str("PASS"), PhaseStatus::Pass);
+    /// Index in `security_errors` where the baseline's known findings begin.
+    pub fn security_known_start(&self) -> usize {
+        self.security_suppressed_start()
+            .saturating_sub(self.known_security_count)
+    }
 
-        assert_eq!(PhaseStatus::from_status_str("fail"), PhaseStatus::Fail);
-        assert_eq!(PhaseStatus::from_status_str("failed"), PhaseStatus::Fail);
+    /// Like [`App::lint_bounds`], for the security findings listed.
+    pub fn security_bounds(&self) -> (usize, usize) {
+        match &self.changed {
+            Some(c) => (c.security_known_start, c.security_suppressed_start),
+            None => (
+                self.security_known_start(),
+                self.security_suppressed_start(),
+            ),
+        }
+    }
+
+    /// List only the findings on lines in `ranges`, changed since `base`.
+    pub fn narrow_to_changed(&mut self, base: String, ranges: Vec<Range<usize>>) {
+        self.changed = Some(ChangedFindings {
+            base,
+            ranges,
+            ..ChangedFindings::default()
+        });
+        self.refresh_changed();
+    }
+
+    /// Narrow the findings again after they changed. Findings without a
+    /// spec line are left out.
+    pub fn refresh_changed(&mut self) {
+        let Some(mut changed) = self.changed.take() else {
+            return;
+        };
+        let ranges = &changed.ranges;
+        let keep = |err: &&LintError| {
+            self.error_spec_line(err)
+                .is_some_and(|line| regions::contains(ranges, line))
+        };
+        // The kept findings of `errors`, with how many of them come before
+        // each of `starts`.
+        let narrow = |errors: &[LintError], starts: [usize; 2]| {
+            let kept: Vec<(usize, &LintError)> = errors
+                .iter()
+                .enumerate()
+                .filter(|(_, err)| keep(err))
+                .collect();
+            let before = starts.map(|start| kept.iter().filter(|(i, _)| *i < start).count());
+            let kept: Vec<LintError> = kept.into_iter().map(|(_, err)| err.clone()).collect();
+            (kept, before)
+        };
+        changed.structure = self.structure_errors.iter().filter(keep).cloned().collect();
+        let (lint, [known_start, suppressed_start]) = narrow(
+            &self.lint_errors,
+            [self.known_start(), self.suppressed_start()],
+        );
+        changed.lint = lint;
+        changed.known_start = known_start;
+        changed.suppressed_start = suppressed_start;
+        let (security, [known_start, suppressed_start]) = narrow(
+            &self.security_errors,
+            [
+                self.security_known_start(),
+                self.security_suppressed_start(),
+            ],
+        );
+        changed.security = security;
+        changed.security_known_start = known_start;
+        changed.security_suppressed_start = suppressed_start;
+        self.changed = Some(changed);
+    }
+
+    pub fn is_known(&self, err: &LintError) -> bool {
+        self.baseline
+            .as_ref()
+            .is_some_and(|b| b.contains(&baseline_finding(err)))
+    }
+
+    /// Take the severity overrides from config. Unknown severities are
+    /// skipped; config validation reports them.
+    pub fn set_severity_overrides(&mut self, overrides: &BTreeMap<String, String>) {
+        self.severity_overrides = overrides
+            .iter()
+            .filter_map(|(rule, s)| Some((rule.clone(), LintSeverity::parse(s)?)))
+            .collect();
+    }
+
+    /// Store the findings parsed from the lint log and the analysis
+    /// findings shown with them; only the former can fail the lint phase.
+    pub fn set_lint_findings(&mut self, linter: Vec<LintError>, analysis: Vec<LintError>) {
+        self.linter_findings = linter.clone();
+        let mut errors = linter;
+        errors.extend(analysis);
+        self.set_lint_errors(errors);
+    }
+
+    /// Store parsed lint findings as new, then known, then suppressed, so
+    /// `current_errors()` can hide the suppressed ones by slicing and new
+    /// findings come first. Severity overrides are applied first.
+    pub fn set_lint_errors(&mut self, mut errors: Vec<LintError>) {
+        self.overridden_lint_count = 0;
+        for err in &mut errors {
+            if let Some(&severity) = self.severity_overrides.get(&err.rule) {
+                err.severity = severity;
+                self.overridden_lint_count += 1;
+            }
+        }
+        let (errors, known, suppressed) = self.order_findings(errors);
+        self.known_lint_count = known;
+        self.suppressed_lint_count = suppressed;
+        self.lint_errors = errors;
+        self.refresh_changed();
+    }
+
+    /// Store the security phase's findings ordered like
+    /// [`App::set_lint_errors`], so suppressions and the baseline apply to
+    /// them too.
+    pub fn set_security_errors(&mut self, errors: Vec<LintError>) {
+        let (errors, known, suppressed) = self.order_findings(errors);
+        self.known_security_count = known;
+        self.suppressed_security_count = suppressed;
+        self.security_errors = errors;
+        self.refresh_changed();
+    }
+
+    /// `errors` as new, then known, then suppressed findings, with how many
+    /// are known and suppressed.
+    fn order_findings(&self, errors: Vec<LintError>) -> (Vec<LintError>, usize, usize) {
+        let (visible, suppressed): (Vec<_>, Vec<_>) =
+            errors.into_iter().partition(|e| !self.is_suppressed(e));
+        let (mut new, known): (Vec<_>, Vec<_>) =
+            visible.into_iter().partition(|e| !self.is_known(e));
+        let counts = (known.len(), suppressed.len());
+        new.extend(known);
+        new.extend(suppressed);
+        (new, counts.0, counts.1)
+    }
+
+    /// The lint findings that aren't suppressed, as recorded in the rule
+    /// history.
+    pub fn unsuppressed_findings(&self) -> Vec<Finding> {
+        self.lint_errors[..self.suppressed_start()]
+            .iter()
+            .map(baseline_finding)
+            .collect()
+    }
+
+    /// Snapshot every current lint and security finding, suppressed ones
+    /// included, as the new baseline.
+    pub fn snapshot_baseline(&self) -> Baseline {
+        Baseline::new(
+            self.lint_errors
+                .iter()
+                .chain(&self.security_errors)
+                .map(baseline_finding)
+                .collect(),
+        )
+    }
+
+    /// Re-evaluate the lint and security statuses after suppressions,
+    /// severity overrides and the fail policy; see
+    /// [`App::settled_lint_status`]. Returns whether either changed.
+    pub fn settle_lint_status(&mut self) -> bool {
+        let Some(phases) = self.report.as_ref().map(|r| &r.phases) else {
+            return false;
+        };
+        let lint = phases
+            .lint
+            .as_ref()
+            .and_then(|lint| self.settled_lint_status(&lint.status, &self.linter_findings));
+        let security = phases
+            .security
+            .as_ref()
+            .and_then(|security| self.settled_lint_status(&security.status, &self.security_errors));
+        let Some(report) = self.report.as_mut() else {
+            return false;
+        };
+        let settled = [
+            (lint, report.phases.lint.as_mut()),
+            (security, report.phases.security.as_mut()),
+        ];
+        let mut changed = false;
+        for (status, result) in settled {
+            let (Some(status), Some(result)) = (status, result) else {
+                continue;
+            };
+            result.status = status.into();
+            if status == "pass" {
+                report.summary.passed += 1;
+                report.summary.failed = report.summary.failed.saturating_sub(1);
+            } else {
+                report.summary.failed += 1;
+                report.summary.passed = report.summary.passed.saturating_sub(1);
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    /// The status a lint phase that ended with `status` settles to, given
+    /// the linter's own `findings`, or `None` when it stands: a failed
+    /// phase with findings passes when no visible one fails the policy,
+    /// and a passed one fails when one does. Analysis findings never fail
+    /// the phase, so they aren't passed in.
+    pub fn settled_lint_status(
+        &self,
+        status: &str,
+        findings: &[LintError],
+    ) -> Option<&'static str> {
+        let mut adjusted = self.fail_policy.configured;
+        let mut blocking = false;
+        for err in findings {
+            let mut err = err.clone();
+            if let Some(&severity) = self.severity_overrides.get(&err.rule) {
+                err.severity = severity;
+                adjusted = true;
+            }
+            if self.is_suppressed(&err) {
+                adjusted = true;
+            } else {
+                blocking |= self.fail_policy.blocks(&err);
+            }
+        }
+        if !adjusted {
+            return None;
+        }
+        // A failed lint without findings is the linter itself failing,
+        // which no policy excuses.
+        match (status, blocking) {
+            ("fail", false) if !findings.is_empty() => Some("pass"),
+            ("pass", true) => Some("fail"),
+            _ => None,
+        }
+    }
+
+    /// The currently selected error, if any.
+    pub fn selected_error(&self) -> Option<&LintError> {
+        let errors = self.current_errors();
+        errors.get(self.error_index)
+    }
+
+    /// Free navigation cursor, if it still applies to the selection.
+    pub fn active_spec_cursor(&self) -> Option<SpecCursor> {
+        self.spec_cursor
+            .filter(|c| c.pinned || c.anchor == (self.phase_index, self.error_index))
+    }
+
+    /// Spec line the Spec Context panel centres on: the free navigation
+    /// cursor, else the current search match while the search belongs to the
+    /// selected finding, else that finding's line.
+    pub fn spec_target_line(&self) -> Option<usize> {
+        if let Some(cursor) = self.active_spec_cursor() {
+            return Some(cursor.line);
+        }
+        let search_line = self
+            .spec_search
+            .as_ref()
+            .filter(|s| s.anchor == (self.phase_index, self.error_index))
+            .and_then(|s| s.current_line());
+        search_line.or_else(|| {
+            self.selected_error()
+                .and_then(|err| self.error_spec_line(err))
+        })
+    }
+
+    /// Put the free navigation cursor on `line`, clamped to the spec, keeping
+    /// the pin if there is one.
+    pub fn move_spec_cursor(&mut self, line: usize) {
+        let last = self
+            .spec_index
+            .as_ref()
+            .map_or(1, |i| i.line_count().max(1));
+        let pinned = self.active_spec_cursor().is_some_and(|c| c.pinned);
+        self.spec_cursor = Some(SpecCursor {
+            line: line.clamp(1, last),
+            pinned,
+            anchor: (self.phase_index, self.error_index),
+        });
+        self.spec_scroll = 0;
+    }
+
+    /// Structure, unsuppressed lint and security findings that point at a
+    /// spec position, with that 1-based line and column.
+    pub fn located_findings(&self) -> Vec<(usize, usize, &LintError)> {
+        let lint = &self.lint_errors[..self.suppressed_start()];
+        self.structure_errors
+            .iter()
+            .chain(lint)
+            .chain(&self.security_errors[..self.security_suppressed_start()])
+            .filter_map(|err| {
+                let (line, col) = self.error_spec_position(err)?;
+                Some((line, col, err))
+            })
+            .collect()
+    }
+
+    /// Spec line an error points at: its resolved `json_path` when present,
+    /// otherwise the line reported by the linter.
+    pub fn error_spec_line(&self, err: &LintError) -> Option<usize> {
+        self.error_spec_position(err).map(|(line, _)| line)
+    }
+
+    /// 1-based line and column an error points at, like [`error_spec_line`].
+    /// The linter's column is kept when the path resolves to its line.
+    ///
+    /// [`error_spec_line`]: Self::error_spec_line
+    pub fn error_spec_position(&self, err: &LintError) -> Option<(usize, usize)> {
+        let linter = (err.line > 0).then_some((err.line, err.col.max(1)));
+        let resolved = err
+            .json_path
+            .as_ref()
+            .and_then(|path| self.spec_index.as_ref()?.resolve(path));
+        match (resolved, linter) {
+            (Some(span), Some(pos)) if span.line == pos.0 => Some(pos),
+            (Some(span), _) => Some((span.line, span.col + 1)),
+            (None, pos) => pos,
+        }
+    }
+
+    /// Indices into `current_errors()` of every finding on the selected
+    /// error's spec line, in list order. Empty when nothing is selected.
+    pub fn line_findings(&self) -> Vec<usize> {
+        let Some(target) = self
+            .selected_error()
+            .and_then(|err| self.error_spec_line(err))
+        else {
+            return Vec::new();
+        };
+        self.current_errors()
+            .iter()
+            .enumerate()
+            .filter(|(_, err)| self.error_spec_line(err) == Some(target))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Findings in `current_errors()` by spec line: the most severe one on
+    /// each line and how many there are.
+    pub fn findings_by_line(&self) -> BTreeMap<usize, (LintSeverity, usize)> {
+        let mut lines: BTreeMap<usize, (LintSeverity, usize)> = BTreeMap::new();
+        for err in self.current_errors() {
+            let Some(line) = self.error_spec_line(err) else {
+                continue;
+            };
+            lines
+                .entry(line)
+                .and_modify(|(worst, count)| {
+                    *worst = (*worst).max(err.severity);
+                    *count += 1;
+                })
+                .or_insert((err.severity, 1));
+        }
+        lines
+    }
+
+    /// Select the next (or previous) finding on the same spec line, wrapping.
+    /// Returns `false` if the line has no other findings.
+    pub fn cycle_line_finding(&mut self, forward: bool) -> bool {
+        let siblings = self.line_findings();
+        let len = siblings.len();
+        if len < 2 {
+            return false;
+        }
+        let pos = siblings
+            .iter()
+            .position(|&i| i == self.error_index)
+            .unwrap_or(0);
+        let next = if forward {
+            (pos + 1) % len
+        } else {
+            (pos + len - 1) % len
+        };
+        self.error_index = siblings[next];
+        self.detail_scroll = 0;
+        true
+    }
+
+    /// Select the first failing phase and its first error, and focus the
+    /// Errors panel. Returns `false` if no phase failed.
+    pub fn focus_first_failure(&mut self) -> bool {
+        let Some(index) = self
+            .phase_entries()
+            .iter()
+            .position(|e| e.status == PhaseStatus::Fail)
+        else {
+            return false;
+        };
+        self.phase_index = index;
+        self.error_index = 0;
+        self.detail_scroll = 0;
+        self.focused_panel = Panel::Errors;
+        true
+    }
+
+    /// Clamp phase_index and error_index to valid bounds.
+    pub fn clamp_indices(&mut self) {
+        let count = self.phase_count();
+        if count > 0 {
+            self.phase_index = self.phase_index.min(count - 1);
+        } else {
+            self.phase_index = 0;
+        }
+
+        let error_count = self.current_errors().len();
+        if error_count > 0 {
+            self.error_index = self.error_index.min(error_count - 1);
+        } else {
+            self.error_index = 0;
+        }
+    }
+
+    /// Short count of config problems, e.g. "1 error, 2 warnings".
+    pub fn diagnostics_summary(&self) -> Option<String> {
+        if self.config_diagnostics.is_empty() {
+            return None;
+        }
+        let errors = self
+            .config_diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        let warnings = self.config_diagnostics.len() - errors;
+        let plural = |n: usize, word: &str| {
+            if n == 1 {
+                format!("{n} {word}")
+            } else {
+                format!("{n} {word}s")
+            }
+        };
+        Some(match (errors, warnings) {
+            (0, w) => plural(w, "warning"),
+            (e, 0) => plural(e, "error"),
+            (e, w) => format!("{}, {}", plural(e, "error"), plural(w, "warning")),
+        })
+    }
+
+    /// The selected phase, if it's a generate, compile, test or code lint
+    /// step that failed.
+    pub fn selected_failed_step(&self) -> Option<Phase> {
+        let phase = self.selected_phase()?;
+        let entry = self.phase_entries().into_iter().nth(self.phase_index)?;
+        match phase {
+            Phase::Generate { .. }
+            | Phase::Compile { .. }
+            | Phase::Test { .. }
+            | Phase::CodeLint { .. }
+                if entry.status == PhaseStatus::Fail =>
+            {
+                Some(phase)
+            }
+            _ => None,
+        }
+    }
+
+    /// Pipeline phase behind the selected entry in the phases list.
+    pub fn selected_phase(&self) -> Option<Phase> {
+        let report = self.report.as_ref()?;
+        let mut idx = self.phase_index;
+
+        if report.phases.structure.is_some() {
+            if idx == 0 {
+                return Some(Phase::Structure);
+            }
+            idx -= 1;
+        }
+
+        if report.phases.lint.is_some() {
+            if idx == 0 {
+                return Some(Phase::Lint);
+            }
+            idx -= 1;
+        }
+
+        if report.phases.security.is_some() {
+            if idx == 0 {
+                return Some(Phase::Security);
+            }
+            idx -= 1;
+        }
+
+        if let Some(steps) = &report.phases.generate {
+            if let Some(step) = steps.get(idx) {
+                return Some(Phase::Generate {
+                    generator: step.generator.clone(),
+                    scope: step.scope.clone(),
+                });
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(steps) = &report.phases.compile {
+            if let Some(step) = steps.get(idx) {
+                return Some(Phase::Compile {
+                    generator: step.generator.clone(),
+                    scope: step.scope.clone(),
+                });
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(steps) = &report.phases.test {
+            if let Some(step) = steps.get(idx) {
+                return Some(Phase::Test {
+                    generator: step.generator.clone(),
+                    scope: step.scope.clone(),
+                });
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(steps) = &report.phases.code_lint {
+            if let Some(step) = steps.get(idx) {
+                return Some(Phase::CodeLint {
+                    generator: step.generator.clone(),
+                    scope: step.scope.clone(),
+                });
+            }
+            idx -= steps.len();
+        }
+
+        if report.phases.docs.is_some() {
+            if idx == 0 {
+                return Some(Phase::Docs);
+            }
+            idx -= 1;
+        }
+
+        let phase = report.phases.custom.as_ref()?.get(idx)?;
+        Some(Phase::Custom {
+            name: phase.name.clone(),
+        })
+    }
+
+    /// Timed log lines for the raw log view: the live stream while a run is
+    /// in progress, otherwise the captured output of the selected phase.
+    pub fn current_timed_log(&self) -> Option<&[LogLine]> {
+        if self.validating && self.report.is_none() {
+            return Some(&self.live_log);
+        }
+        let phase = self.selected_phase()?;
+        self.phase_logs.get(&phase).map(Vec::as_slice)
+    }
+
+    /// Lines of the Raw Log tab after the quick filters, each with its timing
+    /// when the tab shows timed lines: for phases run in this session (and
+    /// the live stream) with timestamps on, or while no report is loaded.
+    pub fn raw_log_lines(&self) -> Vec<(&str, Option<&LogLine>)> {
+        let filter = self.log_filter;
+        if let Some(timed) = self.current_timed_log()
+            && (self.show_log_times || self.report.is_none())
+            && !timed.is_empty()
+        {
+            return timed
+                .iter()
+                .filter(|l| filter.keep(&l.text))
+                .map(|l| (l.text.as_str(), Some(l)))
+                .collect();
+        }
+        self.current_phase_log()
+            .lines()
+            .filter(|l| filter.keep(l))
+            .map(|l| (l, None))
+            .collect()
+    }
+
+    /// The Raw Log search, if it belongs to the selected phase.
+    pub fn active_log_search(&self) -> Option<&TextSearch> {
+        self.log_search
+            .as_ref()
+            .filter(|s| s.anchor.0 == self.phase_index)
+    }
+
+    /// Raw log text for the currently selected phase.
+    pub fn current_phase_log(&self) -> &str {
+        self.selected_phase_run().map_or("", |(log, _)| log)
+    }
+
+    /// How the selected phase's container was run, if it ran one.
+    pub fn current_invocation(&self) -> Option<&Invocation> {
+        self.selected_phase_run()?.1
+    }
+
+    /// Log and container invocation of the currently selected phase.
+    fn selected_phase_run(&self) -> Option<(&str, Option<&Invocation>)> {
+        let report = self.report.as_ref()?;
+
+        let mut idx = self.phase_index;
+
+        if let Some(structure) = &report.phases.structure {
+            if idx == 0 {
+                return Some((&structure.log, None));
+            }
+            idx -= 1;
+        }
+
+        if let Some(lint) = &report.phases.lint {
+            if idx == 0 {
+                return Some((&lint.log, lint.invocation.as_ref()));
+            }
+            idx -= 1;
+        }
+
+        if let Some(security) = &report.phases.security {
+            if idx == 0 {
+                return Some((&security.log, security.invocation.as_ref()));
+            }
+            idx -= 1;
+        }
+
+        if let Some(steps) = &report.phases.generate {
+            if idx < steps.len() {
+                return Some((&steps[idx].log, steps[idx].invocation.as_ref()));
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(steps) = &report.phases.compile {
+            if idx < steps.len() {
+                return Some((&steps[idx].log, steps[idx].invocation.as_ref()));
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(steps) = &report.phases.test {
+            if idx < steps.len() {
+                return Some((&steps[idx].log, steps[idx].invocation.as_ref()));
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(steps) = &report.phases.code_lint {
+            if idx < steps.len() {
+                return Some((&steps[idx].log, steps[idx].invocation.as_ref()));
+            }
+            idx -= steps.len();
+        }
+
+        if let Some(docs) = &report.phases.docs {
+            if idx == 0 {
+                return Some((&docs.log, docs.invocation.as_ref()));
+            }
+            idx -= 1;
+        }
+
+        let phase = report.phases.custom.as_ref()?.get(idx)?;
+        Some((&phase.log, phase.invocation.as_ref()))
+    }
+}
+
+/// Error lines in each generate, then compile, step log of `report`, in
+/// phase order. Counted once per report since logs can be long.
+pub fn step_error_counts(report: &ValidateReport) -> Vec<usize> {
+    let generate = report.phases.generate.iter().flatten();
+    let compile = report.phases.compile.iter().flatten();
+    generate
+        .chain(compile)
+        .map(|step| count_step_errors(&step.log))
+        .collect()
+}
+
+/// A lint finding as recorded in `.oav/baseline.json`.
+fn baseline_finding(err: &LintError) -> Finding {
+    Finding {
+        rule: err.rule.clone(),
+        path: err.json_path.clone(),
+        message: err.message.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_parser::Severity;
+    use lazyoav::pipeline::{
+        CustomPhaseResult, DocsResult, LintResult, Phases, StepResult, StructureResult, Summary,
+        ValidateReport,
+    };
+
+    fn make_report(
+        lint: Option<LintResult>,
+        generate: Option<Vec<StepResult>>,
+        compile: Option<Vec<StepResult>>,
+    ) -> ValidateReport {
+        ValidateReport {
+            spec: "petstore.yaml".into(),
+            mode: "both".into(),
+            phases: Phases {
+                structure: None,
+                lint,
+                generate,
+                compile,
+                test: None,
+                code_lint: None,
+                docs: None,
+                security: None,
+                custom: None,
+            },
+            summary: Summary {
+                total: 3,
+                passed: 2,
+                failed: 1,
+            },
+            images: Default::default(),
+        }
+    }
+
+    fn make_lint_result(status: &str) -> LintResult {
+        LintResult {
+            linter: "spectral".into(),
+            status: status.into(),
+            log: "1:1  error  test-rule  test message".into(),
+            attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    fn make_step(generator: &str, scope: &str, status: &str) -> StepResult {
+        StepResult {
+            generator: generator.into(),
+            scope: scope.into(),
+            status: status.into(),
+            log: format!("{generator}/{scope} log output"),
+            attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    fn make_lint_error(rule: &str, severity: Severity) -> LintError {
+        LintError {
+            line: 10,
+            col: 1,
+            end: None,
+            severity,
+            rule: rule.into(),
+            message: format!("{rule} message"),
+            json_path: Some("/paths/~1pets".into()),
+        }
+    }
+
+    // ── diagnostics_summary ───────────────────────────────────────────
+
+    #[test]
+    fn diagnostics_summary_counts_by_severity() {
+        let mut app = App::new();
+        assert_eq!(app.diagnostics_summary(), None);
+
+        app.config_diagnostics = vec![
+            Diagnostic::error("mode", "bad"),
+            Diagnostic::warning("compile", "odd"),
+            Diagnostic::warning("keys", "odd"),
+        ];
+        assert_eq!(
+            app.diagnostics_summary().as_deref(),
+            Some("1 error, 2 warnings")
+        );
+
+        app.config_diagnostics.truncate(1);
+        assert_eq!(app.diagnostics_summary().as_deref(), Some("1 error"));
+    }
+
+    // ── PhaseStatus ───────────────────────────────────────────────────
+
+    #[test]
+    fn phase_status_from_known_strings() {
+        assert_eq!(PhaseStatus::from_status_str("pass"), PhaseStatus::Pass);
+        assert_eq!(PhaseStatus::from_status_str("passed"), PhaseStatus::Pass);
+        assert_eq!(PhaseStatus::from_status_str("success"), PhaseStatus::Pass);
+        assert_eq!(PhaseStatus::from_status_str("PASS"), PhaseStatus::Pass);
+
+        assert_eq!(PhaseStatus::from_status_str("fail"), PhaseStatus::Fail);
+        assert_eq!(PhaseStatus::from_status_str("failed"), PhaseStatus::Fail);
         assert_eq!(PhaseStatus::from_status_str("error"), PhaseStatus::Fail);
 
         assert_eq!(
@@ -551,6 +2099,29 @@ mod tests {
         assert_eq!(entries[0].error_count, 2);
     }
 
+    #[test]
+    fn structure_check_comes_first() {
+        let mut app = App::new();
+        let mut report = make_report(Some(make_lint_result("pass")), None, None);
+        report.phases.structure = Some(StructureResult {
+            status: "fail".into(),
+            ..Default::default()
+        });
+        app.report = Some(report);
+        app.structure_errors = vec![make_lint_error("structure", Severity::Error)];
+        app.lint_errors = vec![make_lint_error("rule-a", Severity::Warning)];
+
+        let entries = app.phase_entries();
+        assert_eq!(entries[0].label, "Structure (local)");
+        assert_eq!(entries[0].status, PhaseStatus::Fail);
+        assert_eq!(app.current_errors()[0].rule, "structure");
+        assert_eq!(app.selected_phase(), Some(Phase::Structure));
+
+        app.phase_index = 1;
+        assert_eq!(app.current_errors()[0].rule, "rule-a");
+        assert_eq!(app.selected_phase(), Some(Phase::Lint));
+    }
+
     #[test]
     fn phase_entries_all_phases() {
         let mut app = App::new();
@@ -564,12 +2135,106 @@ mod tests {
         ));
 
         let entries = app.phase_entries();
-        assert_eq!(entries.len(), 4);
-        assert_eq!(entries[0].label, "Lint (spectral)");
-        assert_eq!(entries[1].label, "Generate (go/server)");
-        assert_eq!(entries[2].label, "Compile (go/server)");
-        assert_eq!(entries[3].label, "Compile (typescript/client)");
-        assert_eq!(entries[3].status, PhaseStatus::Fail);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].label, "Lint (spectral)");
+        assert_eq!(entries[1].label, "Generate (go/server)");
+        assert_eq!(entries[2].label, "Compile (go/server)");
+        assert_eq!(entries[3].label, "Compile (typescript/client)");
+        assert_eq!(entries[3].status, PhaseStatus::Fail);
+    }
+
+    #[test]
+    fn security_phase_has_its_own_findings() {
+        let mut app = App::new();
+        let mut report = make_report(
+            Some(make_lint_result("pass")),
+            Some(vec![make_step("go", "server", "pass")]),
+            None,
+        );
+        report.phases.security = Some(LintResult {
+            linter: "owasp".into(),
+            status: "fail".into(),
+            log: "owasp log".into(),
+            attempts: 1,
+            ..Default::default()
+        });
+        app.report = Some(report);
+        app.lint_errors = vec![make_lint_error("style", Severity::Warning)];
+        app.security_errors = vec![make_lint_error("owasp:api3", Severity::Error)];
+
+        let entries = app.phase_entries();
+        assert_eq!(app.phase_count(), 3);
+        assert_eq!(entries[1].label, "Security (owasp)");
+        assert_eq!(entries[1].error_count, 1);
+
+        app.phase_index = 1;
+        assert_eq!(app.selected_phase(), Some(Phase::Security));
+        assert_eq!(app.current_phase_log(), "owasp log");
+        assert_eq!(app.current_errors()[0].rule, "owasp:api3");
+
+        app.phase_index = 0;
+        assert_eq!(app.current_errors()[0].rule, "style");
+        app.phase_index = 2;
+        assert!(app.current_errors().is_empty());
+    }
+
+    #[test]
+    fn docs_phase_is_listed_last() {
+        let mut app = App::new();
+        let mut report = make_report(
+            Some(make_lint_result("pass")),
+            None,
+            Some(vec![make_step("go", "server", "pass")]),
+        );
+        report.phases.docs = Some(DocsResult {
+            status: "fail".into(),
+            log: "redoc failed".into(),
+            attempts: 1,
+            ..Default::default()
+        });
+        app.report = Some(report);
+
+        let entries = app.phase_entries();
+        assert_eq!(app.phase_count(), 3);
+        assert_eq!(entries[2].label, "Docs (redoc)");
+        assert_eq!(entries[2].status, PhaseStatus::Fail);
+
+        app.phase_index = 2;
+        assert_eq!(app.selected_phase(), Some(Phase::Docs));
+        assert_eq!(app.current_phase_log(), "redoc failed");
+    }
+
+    #[test]
+    fn custom_phases_follow_docs() {
+        let mut app = App::new();
+        let mut report = make_report(Some(make_lint_result("pass")), None, None);
+        report.phases.docs = Some(DocsResult {
+            status: "pass".into(),
+            ..DocsResult::default()
+        });
+        report.phases.custom = Some(vec![CustomPhaseResult {
+            name: "breaking".into(),
+            status: "fail".into(),
+            log: "2 breaking changes".into(),
+            attempts: 1,
+            ..Default::default()
+        }]);
+        app.report = Some(report);
+
+        let entries = app.phase_entries();
+        assert_eq!(app.phase_count(), 3);
+        assert_eq!(entries[2].label, "breaking (custom)");
+        assert_eq!(entries[2].status, PhaseStatus::Fail);
+
+        app.phase_index = 2;
+        assert_eq!(
+            app.selected_phase(),
+            Some(Phase::Custom {
+                name: "breaking".into()
+            })
+        );
+        assert_eq!(app.current_phase_log(), "2 breaking changes");
+        assert!(app.current_errors().is_empty());
     }
 
     // ── current_errors / selected_error ───────────────────────────────
@@ -627,6 +2292,156 @@ mod tests {
         assert_eq!(err.rule, "r2");
     }
 
+    // ── suppressions ──────────────────────────────────────────────────
+
+    fn suppress(app: &mut App, rule: &str) {
+        app.suppressions.push(Suppression {
+            rule: rule.into(),
+            path: None,
+        });
+    }
+
+    #[test]
+    fn located_findings_leave_out_suppressed_security_findings() {
+        let mut app = App::new();
+        suppress(&mut app, "owasp:api3");
+        app.set_security_errors(vec![
+            make_lint_error("owasp:api3", Severity::Error),
+            make_lint_error("owasp:api4", Severity::Error),
+        ]);
+        let rules: Vec<_> = app
+            .located_findings()
+            .into_iter()
+            .map(|(_, _, err)| err.rule.as_str())
+            .collect();
+        assert_eq!(rules, ["owasp:api4"]);
+    }
+
+    #[test]
+    fn suppressed_errors_are_hidden_until_toggled() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        suppress(&mut app, "r1");
+        app.set_lint_errors(vec![
+            make_lint_error("r1", Severity::Error),
+            make_lint_error("r2", Severity::Warning),
+        ]);
+        let rules = |app: &App| -> Vec<String> {
+            app.current_errors()
+                .iter()
+                .map(|e| e.rule.clone())
+                .collect()
+        };
+        assert_eq!(rules(&app), vec!["r2"]);
+        assert_eq!(app.phase_entries()[0].error_count, 1);
+
+        app.show_suppressed = true;
+        assert_eq!(rules(&app), vec!["r2", "r1"]);
+        assert_eq!(app.suppressed_start(), 1);
+    }
+
+    #[test]
+    fn changed_only_lists_findings_on_changed_lines() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        suppress(&mut app, "r3");
+        let at = |rule: &str, line| LintError {
+            line,
+            json_path: None,
+            ..make_lint_error(rule, Severity::Warning)
+        };
+        app.set_lint_errors(vec![at("r1", 3), at("r2", 10), at("r3", 11), at("r4", 20)]);
+        let rules = |app: &App| -> Vec<String> {
+            app.current_errors()
+                .iter()
+                .map(|e| e.rule.clone())
+                .collect()
+        };
+
+        app.narrow_to_changed("HEAD".into(), vec![1..3, 9..12]);
+        assert_eq!(rules(&app), ["r2"]);
+        assert_eq!(app.lint_bounds(), (1, 1));
+        app.show_suppressed = true;
+        assert_eq!(rules(&app), ["r2", "r3"]);
+
+        // Findings of a new run are narrowed as they come in.
+        app.set_lint_errors(vec![at("r5", 9), at("r6", 12)]);
+        assert_eq!(rules(&app), ["r5"]);
+        app.changed = None;
+        assert_eq!(rules(&app), ["r5", "r6"]);
+    }
+
+    #[test]
+    fn settle_lint_status_passes_when_only_suppressed_errors_fail() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        suppress(&mut app, "r1");
+        app.set_lint_findings(
+            vec![
+                make_lint_error("r1", Severity::Error),
+                make_lint_error("r2", Severity::Warning),
+            ],
+            Vec::new(),
+        );
+        assert!(app.settle_lint_status());
+        let report = app.report.as_ref().unwrap();
+        assert_eq!(report.phases.lint.as_ref().unwrap().status, "pass");
+        assert_eq!((report.summary.passed, report.summary.failed), (3, 0));
+    }
+
+    #[test]
+    fn settle_lint_status_keeps_failure_for_unsuppressed_errors() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        suppress(&mut app, "r1");
+        app.set_lint_findings(
+            vec![
+                make_lint_error("r1", Severity::Error),
+                make_lint_error("r2", Severity::Warning),
+            ],
+            Vec::new(),
+        );
+        // With a warning threshold the unsuppressed r2 still fails the phase.
+        app.fail_policy.threshold = Some(Severity::Warning);
+        assert!(!app.settle_lint_status());
+        assert_eq!(
+            app.report
+                .as_ref()
+                .unwrap()
+                .phases
+                .lint
+                .as_ref()
+                .unwrap()
+                .status,
+            "fail"
+        );
+    }
+
+    // ── focus_first_failure ───────────────────────────────────────────
+
+    #[test]
+    fn focus_first_failure_selects_failing_phase() {
+        let mut app = App::new();
+        app.report = Some(make_report(
+            Some(make_lint_result("pass")),
+            Some(vec![
+                make_step("spring", "server", "pass"),
+                make_step("typescript", "client", "fail"),
+            ]),
+            Some(vec![make_step("spring", "server", "fail")]),
+        ));
+        app.error_index = 3;
+        assert!(app.focus_first_failure());
+        assert_eq!(app.phase_index, 2);
+        assert_eq!(app.error_index, 0);
+        assert_eq!(app.focused_panel, Panel::Errors);
+
+        app.report = Some(make_report(Some(make_lint_result("pass")), None, None));
+        app.focused_panel = Panel::Phases;
+        assert!(!app.focus_first_failure());
+        assert_eq!(app.focused_panel, Panel::Phases);
+    }
+
     // ── clamp_indices ─────────────────────────────────────────────────
 
     #[test]
@@ -705,6 +2520,156 @@ mod tests {
         assert_eq!(app.current_phase_log(), "");
     }
 
+    #[test]
+    fn test_steps_list_their_failed_tests() {
+        let mut app = App::new();
+        let mut report = make_report(
+            Some(make_lint_result("pass")),
+            Some(vec![make_step("go", "server", "pass")]),
+            Some(vec![make_step("go", "server", "pass")]),
+        );
+        report.phases.test = Some(vec![make_step("go", "server", "fail")]);
+        app.report = Some(report);
+        app.test_errors = vec![vec![make_lint_error("failed-test", Severity::Error)]];
+
+        let entries = app.phase_entries();
+        assert_eq!(app.phase_count(), 4);
+        assert_eq!(entries[3].label, "Test (go/server)");
+        assert_eq!(entries[3].error_count, 1);
+
+        app.phase_index = 2;
+        assert!(app.current_errors().is_empty());
+        app.phase_index = 3;
+        assert_eq!(app.current_errors()[0].rule, "failed-test");
+        assert_eq!(app.current_phase_log(), "go/server log output");
+        assert_eq!(
+            app.selected_failed_step(),
+            Some(Phase::Test {
+                generator: "go".into(),
+                scope: "server".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn plugin_findings_sit_between_the_phases_and_the_migration() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("pass")), None, None));
+        app.plugin_findings = vec![make_lint_error("acme-naming", Severity::Error)];
+        app.migration_findings = vec![make_lint_error("migrate-nullable", Severity::Info)];
+
+        let entries = app.phase_entries();
+        assert_eq!(app.phase_count(), 3);
+        assert_eq!(entries[1].label, "Plugins");
+        assert!(app.current_errors().is_empty());
+
+        app.phase_index = 1;
+        assert!(app.plugins_selected());
+        assert_eq!(app.current_errors()[0].rule, "acme-naming");
+        app.phase_index = 2;
+        assert!(!app.plugins_selected());
+        assert_eq!(app.current_errors()[0].rule, "migrate-nullable");
+    }
+
+    #[test]
+    fn code_lint_steps_list_their_findings_after_the_tests() {
+        let mut app = App::new();
+        let mut report = make_report(None, Some(vec![make_step("go", "server", "pass")]), None);
+        report.phases.test = Some(vec![make_step("go", "server", "pass")]);
+        report.phases.code_lint = Some(vec![make_step("go", "server", "fail")]);
+        app.report = Some(report);
+        app.code_lint_errors = vec![vec![
+            make_lint_error("errcheck", Severity::Error),
+            make_lint_error("unused", Severity::Warning),
+        ]];
+
+        let entries = app.phase_entries();
+        assert_eq!(app.phase_count(), 3);
+        assert_eq!(entries[2].label, "Code lint (go/server)");
+        assert_eq!(entries[2].error_count, 2);
+
+        app.phase_index = 1;
+        assert!(app.current_errors().is_empty());
+        app.phase_index = 2;
+        assert_eq!(app.current_errors()[1].rule, "unused");
+        assert_eq!(
+            app.selected_phase(),
+            Some(Phase::CodeLint {
+                generator: "go".into(),
+                scope: "server".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn findings_in_match_the_active_generators_files() {
+        let finding = |file: &str| CodeFinding {
+            file: file.into(),
+            line: 3,
+            col: 0,
+            severity: Severity::Error,
+            rule: String::new(),
+            message: String::new(),
+        };
+        let mut browser = CodeBrowserState::new();
+        browser.generators = vec![("spring".into(), "server".into())];
+        browser.code_findings.insert(
+            "server/spring".into(),
+            vec![finding("src/main/java/Pet.java"), finding("org/Api.java")],
+        );
+        let root = Path::new("/work/.oav/generated/server/spring");
+        assert_eq!(
+            browser
+                .findings_in(&root.join("src/main/java/Pet.java"))
+                .len(),
+            1
+        );
+        assert_eq!(
+            browser
+                .findings_in(&root.join("src/main/java/org/Api.java"))
+                .len(),
+            1
+        );
+        assert!(
+            browser
+                .findings_in(&root.join("src/main/java/MyPet.java"))
+                .is_empty()
+        );
+        assert!(
+            browser
+                .findings_in(&root.join("src/main/java/other/Api.java"))
+                .is_empty()
+        );
+
+        browser.generators = vec![("go".into(), "server".into())];
+        assert!(
+            browser
+                .findings_in(&root.join("src/main/java/Pet.java"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn current_invocation_follows_selected_phase() {
+        let mut app = App::new();
+        let mut step = make_step("go", "server", "fail");
+        step.invocation = Some(Invocation {
+            args: vec!["run".into(), "--rm".into(), "cli:v7".into()],
+            image: Some("cli:v7".into()),
+            exit_code: Some(1),
+            duration_ms: 4_200,
+            ..Invocation::default()
+        });
+        app.report = Some(make_report(
+            Some(make_lint_result("pass")),
+            Some(vec![step]),
+            None,
+        ));
+        assert!(app.current_invocation().is_none());
+        app.phase_index = 1;
+        assert_eq!(app.current_invocation().and_then(|i| i.exit_code), Some(1));
+    }
+
     // ── Panel navigation ──────────────────────────────────────────────
 
     #[test]
@@ -736,4 +2701,359 @@ mod tests {
         let mode = ScreenMode::Half;
         assert_eq!(mode.cycle_next().cycle_prev(), mode);
     }
+
+    // ── Co-located findings ───────────────────────────────────────────
+
+    fn app_with_lines(lines: &[usize]) -> App {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        app.lint_errors = lines
+            .iter()
+            .enumerate()
+            .map(|(i, &line)| LintError {
+                line,
+                json_path: None,
+                ..make_lint_error(&format!("r{i}"), Severity::Error)
+            })
+            .collect();
+        app
+    }
+
+    #[test]
+    fn line_findings_groups_by_spec_line() {
+        let mut app = app_with_lines(&[4, 9, 4, 4]);
+        app.error_index = 2;
+        assert_eq!(app.line_findings(), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn error_spec_position_prefers_resolved_path() {
+        let mut app = App::new();
+        app.spec_index =
+            Some(crate::spec::parse_spec("openapi: 3.0.0\ninfo:\n  title: x\n").unwrap());
+        let at = |line, col, path: Option<&str>| LintError {
+            line,
+            col,
+            json_path: path.map(String::from),
+            ..make_lint_error("r", Severity::Error)
+        };
+        // The linter's column survives when the path agrees on the line.
+        assert_eq!(
+            app.error_spec_position(&at(3, 10, Some("info.title"))),
+            Some((3, 10))
+        );
+        assert_eq!(
+            app.error_spec_position(&at(0, 0, Some("info.title"))),
+            Some((3, 3))
+        );
+        assert_eq!(app.error_spec_position(&at(7, 0, None)), Some((7, 1)));
+        assert_eq!(app.error_spec_position(&at(0, 0, None)), None);
+    }
+
+    #[test]
+    fn line_findings_empty_without_selection() {
+        let app = App::new();
+        assert!(app.line_findings().is_empty());
+    }
+
+    #[test]
+    fn cycle_line_finding_wraps_both_ways() {
+        let mut app = app_with_lines(&[4, 9, 4, 4]);
+        app.error_index = 3;
+        assert!(app.cycle_line_finding(true));
+        assert_eq!(app.error_index, 0);
+        assert!(app.cycle_line_finding(false));
+        assert_eq!(app.error_index, 3);
+        assert!(app.cycle_line_finding(false));
+        assert_eq!(app.error_index, 2);
+    }
+
+    #[test]
+    fn cycle_line_finding_noop_for_single_finding() {
+        let mut app = app_with_lines(&[4, 9]);
+        app.error_index = 1;
+        assert!(!app.cycle_line_finding(true));
+        assert_eq!(app.error_index, 1);
+    }
+
+    #[test]
+    fn findings_by_line_keeps_the_worst_severity() {
+        let mut app = app_with_lines(&[4, 9, 4]);
+        app.lint_errors[0].severity = Severity::Warning;
+        let lines = app.findings_by_line();
+        assert_eq!(lines.get(&4), Some(&(Severity::Error, 2)));
+        assert_eq!(lines.get(&9), Some(&(Severity::Error, 1)));
+        assert_eq!(lines.len(), 2);
+    }
+
+    // ── Log timing ────────────────────────────────────────────────────
+
+    #[test]
+    fn log_line_time_prefix_formats_relative_seconds() {
+        let line = LogLine {
+            elapsed: Some(Duration::from_millis(3_240)),
+            text: String::new(),
+        };
+        assert_eq!(line.time_prefix(), "+03.2s");
+
+        let slow = LogLine {
+            elapsed: Some(Duration::from_secs(125)),
+            text: String::new(),
+        };
+        assert_eq!(slow.time_prefix(), "+125.0s");
+    }
+
+    #[test]
+    fn log_line_time_prefix_pads_untimed_lines() {
+        let line = LogLine {
+            elapsed: None,
+            text: "aborted".into(),
+        };
+        assert_eq!(line.time_prefix().len(), "+03.2s".len());
+    }
+
+    #[test]
+    fn selected_phase_maps_index_across_sections() {
+        let mut app = App::new();
+        app.report = Some(make_report(
+            Some(make_lint_result("pass")),
+            Some(vec![make_step("go", "server", "pass")]),
+            Some(vec![make_step("go", "server", "fail")]),
+        ));
+        assert_eq!(app.selected_phase(), Some(Phase::Lint));
+        app.phase_index = 2;
+        assert_eq!(
+            app.selected_phase(),
+            Some(Phase::Compile {
+                generator: "go".into(),
+                scope: "server".into(),
+            })
+        );
+        app.phase_index = 3;
+        assert_eq!(app.selected_phase(), None);
+    }
+
+    #[test]
+    fn current_timed_log_prefers_live_stream_while_validating() {
+        let mut app = App::new();
+        app.validating = true;
+        app.live_log.push(LogLine {
+            elapsed: Some(Duration::from_secs(1)),
+            text: "pulling".into(),
+        });
+        assert_eq!(app.current_timed_log().map(<[LogLine]>::len), Some(1));
+    }
+
+    // ── Timeout prompts ───────────────────────────────────────────────
+
+    #[test]
+    fn format_secs_prefers_minutes() {
+        assert_eq!(format_secs(300), "5 min");
+        assert_eq!(format_secs(90), "90s");
+        assert_eq!(format_secs(30), "30s");
+    }
+
+    #[test]
+    fn timeout_prompt_counts_down() {
+        let (control, _rx) = mpsc::channel();
+        let prompt = TimeoutPrompt {
+            phase: Phase::Lint,
+            remaining: Duration::from_secs(30),
+            received: Instant::now() - Duration::from_secs(10),
+            control,
+        };
+        let left = prompt.time_left();
+        assert!(left <= Duration::from_secs(20) && left > Duration::from_secs(18));
+    }
+
+    // ── Baseline ──────────────────────────────────────────────────────
+
+    #[test]
+    fn baseline_orders_new_before_known_and_counts_them() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        app.set_lint_errors(vec![
+            make_lint_error("r1", Severity::Error),
+            make_lint_error("r2", Severity::Warning),
+        ]);
+        assert_eq!(app.phase_entries()[0].known_count, None);
+
+        app.baseline = Some(app.snapshot_baseline());
+        suppress(&mut app, "r3");
+        app.set_lint_errors(vec![
+            make_lint_error("r1", Severity::Error),
+            make_lint_error("r3", Severity::Error),
+            make_lint_error("r4", Severity::Warning),
+        ]);
+        let rules: Vec<_> = app.lint_errors.iter().map(|e| e.rule.as_str()).collect();
+        assert_eq!(rules, vec!["r4", "r1", "r3"]);
+        assert_eq!((app.known_start(), app.suppressed_start()), (1, 2));
+        let entry = &app.phase_entries()[0];
+        assert_eq!((entry.error_count, entry.known_count), (2, Some(1)));
+    }
+
+    #[test]
+    fn security_findings_follow_the_baseline_and_suppressions() {
+        let mut app = App::new();
+        let mut report = make_report(None, None, None);
+        report.phases.security = Some(LintResult {
+            status: "fail".into(),
+            ..make_lint_result("fail")
+        });
+        app.report = Some(report);
+        app.set_security_errors(vec![make_lint_error("s1", Severity::Error)]);
+        app.baseline = Some(app.snapshot_baseline());
+        suppress(&mut app, "s3");
+        app.set_security_errors(vec![
+            make_lint_error("s1", Severity::Error),
+            make_lint_error("s3", Severity::Error),
+            make_lint_error("s4", Severity::Warning),
+        ]);
+        let rules: Vec<_> = app
+            .current_errors()
+            .iter()
+            .map(|e| e.rule.as_str())
+            .collect();
+        assert_eq!(rules, vec!["s4", "s1"]);
+        assert_eq!(app.security_bounds(), (1, 2));
+        let entry = &app.phase_entries()[0];
+        assert_eq!((entry.error_count, entry.known_count), (2, Some(1)));
+
+        // With its only error suppressed too, the phase passes.
+        suppress(&mut app, "s1");
+        let errors = std::mem::take(&mut app.security_errors);
+        app.set_security_errors(errors);
+        assert!(app.settle_lint_status());
+        let security = app.report.as_ref().unwrap().phases.security.as_ref();
+        assert_eq!(security.unwrap().status, "pass");
+    }
+
+    // ── Step error counts ─────────────────────────────────────────────
+
+    #[test]
+    fn step_error_counts_fill_generate_and_compile_entries() {
+        let mut failed = make_step("rust", "server", "fail");
+        failed.log = "error[E0308]: mismatched types\nerror: aborting due to previous error".into();
+        let report = make_report(
+            Some(make_lint_result("pass")),
+            Some(vec![make_step("rust", "server", "pass")]),
+            Some(vec![failed]),
+        );
+        let mut app = App::new();
+        app.step_error_counts = step_error_counts(&report);
+        app.report = Some(report);
+        let counts: Vec<_> = app.phase_entries().iter().map(|e| e.error_count).collect();
+        assert_eq!(counts, vec![0, 0, 1]);
+    }
+
+    // ── Severity overrides ────────────────────────────────────────────
+
+    fn overrides(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(rule, severity)| (rule.to_string(), severity.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn demoted_findings_pass_the_lint_phase() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        app.set_severity_overrides(&overrides(&[("r1", "hint"), ("r2", "bogus")]));
+        app.set_lint_findings(
+            vec![
+                make_lint_error("r1", Severity::Error),
+                make_lint_error("r2", Severity::Warning),
+            ],
+            Vec::new(),
+        );
+        assert_eq!(app.lint_errors[0].severity, Severity::Hint);
+        assert_eq!(app.lint_errors[1].severity, Severity::Warning);
+        assert!(app.settle_lint_status());
+        let report = app.report.as_ref().unwrap();
+        assert_eq!(report.phases.lint.as_ref().unwrap().status, "pass");
+        assert_eq!((report.summary.passed, report.summary.failed), (3, 0));
+
+        // Storing the overridden findings again keeps the override count.
+        let errors = std::mem::take(&mut app.lint_errors);
+        app.set_lint_errors(errors);
+        assert_eq!(app.overridden_lint_count, 1);
+    }
+
+    #[test]
+    fn promoted_findings_fail_the_lint_phase() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("pass")), None, None));
+        app.set_severity_overrides(&overrides(&[("r2", "error")]));
+        app.set_lint_findings(vec![make_lint_error("r2", Severity::Warning)], Vec::new());
+        assert!(app.settle_lint_status());
+        let report = app.report.as_ref().unwrap();
+        assert_eq!(report.phases.lint.as_ref().unwrap().status, "fail");
+        assert_eq!((report.summary.passed, report.summary.failed), (1, 2));
+    }
+
+    // ── Fail policy ───────────────────────────────────────────────────
+
+    #[test]
+    fn fail_on_warning_fails_a_passed_lint() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("pass")), None, None));
+        app.fail_policy = FailPolicy::from_config(&Config {
+            fail_on: Some(FailOn::Warning),
+            ..Config::default()
+        });
+        app.set_lint_findings(vec![make_lint_error("r1", Severity::Warning)], Vec::new());
+        assert!(app.settle_lint_status());
+        let report = app.report.as_ref().unwrap();
+        assert_eq!(report.phases.lint.as_ref().unwrap().status, "fail");
+        assert_eq!((report.summary.passed, report.summary.failed), (1, 2));
+    }
+
+    #[test]
+    fn fail_on_rules_override_the_default() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        let mut cfg = Config::default();
+        cfg.fail_on_rules.insert("r1".into(), FailOn::Never);
+        cfg.fail_on_rules.insert("r2".into(), FailOn::Warning);
+        app.fail_policy = FailPolicy::from_config(&cfg);
+        assert_eq!(app.fail_policy.describe(), "error (+2 rules)");
+
+        app.set_lint_findings(vec![make_lint_error("r1", Severity::Error)], Vec::new());
+        assert!(app.settle_lint_status());
+        assert_eq!(app.phase_entries()[0].status, PhaseStatus::Pass);
+
+        app.set_lint_findings(vec![make_lint_error("r2", Severity::Warning)], Vec::new());
+        assert!(app.settle_lint_status());
+        assert_eq!(app.phase_entries()[0].status, PhaseStatus::Fail);
+    }
+
+    #[test]
+    fn analysis_findings_never_fail_the_lint_phase() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("pass")), None, None));
+        app.fail_policy = FailPolicy::from_config(&Config {
+            fail_on: Some(FailOn::Warning),
+            ..Config::default()
+        });
+        app.set_lint_findings(Vec::new(), vec![make_lint_error("r1", Severity::Error)]);
+        assert!(!app.settle_lint_status());
+        assert_eq!(app.phase_entries()[0].status, PhaseStatus::Pass);
+        assert_eq!(app.phase_entries()[0].error_count, 1);
+    }
+
+    #[test]
+    fn fail_on_never_keeps_a_linter_failure_without_findings() {
+        let mut app = App::new();
+        app.report = Some(make_report(Some(make_lint_result("fail")), None, None));
+        app.fail_policy = FailPolicy::from_config(&Config {
+            fail_on: Some(FailOn::Never),
+            ..Config::default()
+        });
+        assert!(!app.settle_lint_status());
+
+        app.set_lint_findings(vec![make_lint_error("r1", Severity::Error)], Vec::new());
+        assert!(app.settle_lint_status());
+        assert_eq!(app.phase_entries()[0].status, PhaseStatus::Pass);
+    }
 }
@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::PathBuf;
+
+use lazyoav::config;
+use lazyoav::keys::KeyAction;
+
+use super::state::Panel;
+
+/// The screen region a tour step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourTarget {
+    Nothing,
+    Panel(Panel),
+    StatusBar,
+}
+
+/// One stop on the onboarding tour.
+pub struct TourStep {
+    pub title: &'static str,
+    pub body: &'static str,
+    pub target: TourTarget,
+    /// Keys worth knowing at this step, shown with their current binding.
+    pub keys: &'static [(KeyAction, &'static str)],
+}
+
+/// The tour, in order: the panels first, then the main flows.
+pub const TOUR: &[TourStep] = &[
+    TourStep {
+        title: "Welcome to lazyoav",
+        body: "lazyoav lints your OpenAPI spec, generates code from it and compiles the \
+               result, all in Docker. This short tour shows where everything lives.",
+        target: TourTarget::Nothing,
+        keys: &[],
+    },
+    TourStep {
+        title: "Phases",
+        body: "Every pipeline step and its status. Select a generate or compile step \
+               to see its log; a run starts automatically on launch.",
+        target: TourTarget::Panel(Panel::Phases),
+        keys: &[
+            (KeyAction::RunValidation, "run validation"),
+            (KeyAction::CancelValidation, "cancel a run"),
+            (KeyAction::OpenShell, "shell into a failed step"),
+        ],
+    },
+    TourStep {
+        title: "Errors",
+        body: "Lint findings, or the failures of the selected step. Move through the \
+               list to inspect each one.",
+        target: TourTarget::Panel(Panel::Errors),
+        keys: &[
+            (KeyAction::ScrollDown, "next error"),
+            (KeyAction::Select, "inspect"),
+        ],
+    },
+    TourStep {
+        title: "Detail",
+        body: "The selected error's message, its rule's documentation and the raw log output.",
+        target: TourTarget::Panel(Panel::Detail),
+        keys: &[
+            (KeyAction::NextDetailTab, "next tab"),
+            (KeyAction::ToggleLogTimes, "log timestamps"),
+        ],
+    },
+    TourStep {
+        title: "Spec context",
+        body: "The part of the spec the error points at. Open it in your editor, or \
+               let lazyoav propose a fix for common findings.",
+        target: TourTarget::Panel(Panel::SpecContext),
+        keys: &[
+            (KeyAction::OpenEditor, "open in $EDITOR"),
+            (KeyAction::ProposeFix, "propose a fix"),
+        ],
+    },
+    TourStep {
+        title: "Generated code",
+        body: "Switch to the code browser to read what each generator produced, and \
+               diff it against the previous run.",
+        target: TourTarget::Nothing,
+        keys: &[
+            (KeyAction::ToggleView, "code browser"),
+            (KeyAction::ToggleDiff, "diff (in the browser)"),
+        ],
+    },
+    TourStep {
+        title: "Status bar",
+        body: "Hints for the focused panel and status messages show up here.",
+        target: TourTarget::StatusBar,
+        keys: &[
+            (KeyAction::NextPanel, "next panel"),
+            (KeyAction::Help, "every keybinding"),
+            (KeyAction::StartTour, "replay this tour"),
+        ],
+    },
+];
+
+/// Position in the onboarding tour overlay.
+#[derive(Debug, Default)]
+pub struct TourState {
+    pub index: usize,
+    /// Where finishing or skipping the tour is recorded; `None` records nothing.
+    marker: Option<PathBuf>,
+}
+
+impl TourState {
+    /// Start from the first step, recording completion in the user config dir.
+    pub fn new() -> Self {
+        Self {
+            index: 0,
+            marker: seen_marker(),
+        }
+    }
+
+    pub fn step(&self) -> &'static TourStep {
+        &TOUR[self.index]
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.index + 1 >= TOUR.len()
+    }
+
+    /// Advance; returns `false` when the tour is over.
+    pub fn next(&mut self) -> bool {
+        if self.is_last() {
+            return false;
+        }
+        self.index += 1;
+        true
+    }
+
+    pub fn back(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    /// Remember that the tour was seen. Failures only mean it shows again.
+    pub fn mark_seen(&self) {
+        if let Some(marker) = &self.marker {
+            if let Some(dir) = marker.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let _ = fs::write(marker, "");
+        }
+    }
+}
+
+/// Marker recording that the tour was finished or skipped, next to the
+/// user-level config.
+fn seen_marker() -> Option<PathBuf> {
+    Some(config::user_config_path()?.parent()?.join("tour-seen"))
+}
+
+/// Whether this is the first launch, i.e. the tour hasn't been seen yet.
+pub fn first_launch() -> bool {
+    seen_marker().is_some_and(|marker| !marker.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tour_visits_every_panel() {
+        for panel in Panel::ALL {
+            assert!(
+                TOUR.iter().any(|s| s.target == TourTarget::Panel(panel)),
+                "{panel:?} missing from tour"
+            );
+        }
+    }
+
+    #[test]
+    fn next_and_back_stay_in_bounds() {
+        let mut tour = TourState::default();
+        tour.back();
+        assert_eq!(tour.index, 0);
+        while tour.next() {}
+        assert!(tour.is_last());
+        assert_eq!(tour.index, TOUR.len() - 1);
+        assert!(!tour.next());
+    }
+
+    #[test]
+    fn mark_seen_writes_marker() {
+        let tmp = tempfile::tempdir().unwrap();
+        let marker = tmp.path().join("lazyoav/tour-seen");
+        let tour = TourState {
+            index: 0,
+            marker: Some(marker.clone()),
+        };
+        tour.mark_seen();
+        assert!(marker.exists());
+    }
+}
@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use lazyoav::config::{Config, InitChoices, Linter, Mode};
+use lazyoav::custom::CustomGeneratorDef;
+
+use super::picker::GeneratorPickerState;
+
+/// Linters offered by the wizard, in display order.
+pub const LINTERS: [Linter; 3] = [Linter::Spectral, Linter::Redocly, Linter::None];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Spec,
+    Linter,
+    Generators,
+    Review,
+}
+
+impl WizardStep {
+    pub fn number(self) -> usize {
+        match self {
+            WizardStep::Spec => 1,
+            WizardStep::Linter => 2,
+            WizardStep::Generators => 3,
+            WizardStep::Review => 4,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            WizardStep::Spec => "Spec",
+            WizardStep::Linter => "Linter",
+            WizardStep::Generators => "Generators",
+            WizardStep::Review => "Review",
+        }
+    }
+}
+
+/// State for the `.oavc` init wizard overlay.
+pub struct InitWizardState {
+    pub step: WizardStep,
+    /// Discovered spec files, relative to the project root.
+    pub specs: Vec<String>,
+    pub spec_index: usize,
+    pub linter_index: usize,
+    pub recommended_linter: Linter,
+    /// Generator selection, reusing the picker's entry list (both scopes).
+    pub generators: GeneratorPickerState,
+}
+
+impl InitWizardState {
+    pub fn new(
+        specs: Vec<String>,
+        recommended_linter: Linter,
+        custom_defs: &[CustomGeneratorDef],
+    ) -> Self {
+        let all = Config {
+            mode: Mode::Both,
+            ..Config::default()
+        };
+        let mut generators = GeneratorPickerState::new(&all, custom_defs);
+        for entry in &mut generators.entries {
+            entry.enabled = false;
+        }
+
+        Self {
+            step: WizardStep::Spec,
+            specs,
+            spec_index: 0,
+            linter_index: LINTERS
+                .iter()
+                .position(|l| *l == recommended_linter)
+                .unwrap_or(0),
+            recommended_linter,
+            generators,
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let clamp = |index: usize, len: usize| {
+            index
+                .saturating_add_signed(delta)
+                .min(len.saturating_sub(1))
+        };
+        match self.step {
+            WizardStep::Spec => self.spec_index = clamp(self.spec_index, self.specs.len()),
+            WizardStep::Linter => self.linter_index = clamp(self.linter_index, LINTERS.len()),
+            WizardStep::Generators => self.generators.move_by(delta),
+            WizardStep::Review => {}
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        if self.step == WizardStep::Generators {
+            self.generators.toggle_selected();
+        }
+    }
+
+    /// Advance to the next step. Leaving the generator step requires at
+    /// least one generator, since an empty selection can't express a mode.
+    pub fn next(&mut self) -> Result<(), &'static str> {
+        self.step = match self.step {
+            WizardStep::Spec => WizardStep::Linter,
+            WizardStep::Linter => WizardStep::Generators,
+            WizardStep::Generators => {
+                if self.generators.entries.iter().all(|e| !e.enabled) {
+                    return Err("Select at least one generator");
+                }
+                WizardStep::Review
+            }
+            WizardStep::Review => WizardStep::Review,
+        };
+        Ok(())
+    }
+
+    /// Go back one step. Returns `false` on the first step.
+    pub fn back(&mut self) -> bool {
+        self.step = match self.step {
+            WizardStep::Spec => return false,
+            WizardStep::Linter => WizardStep::Spec,
+            WizardStep::Generators => WizardStep::Linter,
+            WizardStep::Review => WizardStep::Generators,
+        };
+        true
+    }
+
+    pub fn choices(&self) -> InitChoices {
+        InitChoices {
+            spec: self.specs.get(self.spec_index).cloned(),
+            linter: LINTERS[self.linter_index],
+            server_generators: self.generators.enabled("server"),
+            client_generators: self.generators.enabled("client"),
+        }
+    }
+}
+
+/// Suggest a linter from the ruleset files already in the project.
+///
+/// Spectral is the default, matching the Entur API guidelines ruleset.
+pub fn recommend_linter(root: &Path) -> Linter {
+    let has = |name: &str| root.join(name).is_file();
+    if has("redocly.yaml") || has("redocly.yml") || has(".redocly.yaml") {
+        Linter::Redocly
+    } else {
+        Linter::Spectral
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wizard() -> InitWizardState {
+        InitWizardState::new(
+            vec!["api/a.yaml".into(), "api/b.yaml".into()],
+            Linter::Spectral,
+            &[],
+        )
+    }
+
+    #[test]
+    fn starts_with_nothing_selected() {
+        let w = wizard();
+        assert_eq!(w.step, WizardStep::Spec);
+        assert!(w.generators.entries.iter().all(|e| !e.enabled));
+        assert!(w.generators.entries.iter().any(|e| e.scope == "client"));
+    }
+
+    #[test]
+    fn generators_step_requires_a_selection() {
+        let mut w = wizard();
+        w.next().unwrap();
+        w.next().unwrap();
+        assert_eq!(w.step, WizardStep::Generators);
+        assert!(w.next().is_err());
+
+        w.toggle();
+        w.next().unwrap();
+        assert_eq!(w.step, WizardStep::Review);
+    }
+
+    #[test]
+    fn choices_reflect_selection() {
+        let mut w = wizard();
+        w.move_by(5);
+        w.next().unwrap();
+        w.move_by(1);
+        w.next().unwrap();
+        w.toggle();
+
+        let choices = w.choices();
+        assert_eq!(choices.spec.as_deref(), Some("api/b.yaml"));
+        assert_eq!(choices.linter, Linter::Redocly);
+        assert_eq!(choices.server_generators.len(), 1);
+        assert!(choices.client_generators.is_empty());
+        assert_eq!(choices.mode(), Mode::Server);
+    }
+
+    #[test]
+    fn back_stops_at_first_step() {
+        let mut w = wizard();
+        w.next().unwrap();
+        assert!(w.back());
+        assert!(!w.back());
+        assert_eq!(w.step, WizardStep::Spec);
+    }
+
+    #[test]
+    fn recommends_redocly_when_configured() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(recommend_linter(tmp.path()), Linter::Spectral);
+        std::fs::write(tmp.path().join("redocly.yaml"), "rules: {}\n").unwrap();
+        assert_eq!(recommend_linter(tmp.path()), Linter::Redocly);
+    }
+}
@@ -0,0 +1,180 @@
+use std::sync::mpsc::Receiver;
+
+use lazyoav::docker::CancelToken;
+use lazyoav::pipeline::ValidateReport;
+use lazyoav::pipeline::workspace::{SpecRow, WorkspaceEvent, WorkspaceReport};
+
+use crate::app::App;
+use crate::log_parser;
+
+/// The workspace run: one row per spec, filled in as the specs finish.
+pub struct WorkspaceView {
+    pub specs: Vec<String>,
+    /// Rows by spec; `None` until that spec has run.
+    pub rows: Vec<Option<SpecRow>>,
+    /// Spec being checked, while the run is going.
+    pub running: Option<usize>,
+    pub rx: Option<Receiver<WorkspaceEvent>>,
+    pub cancel: CancelToken,
+    pub scroll: u16,
+    /// Why the last run stopped early.
+    pub error: Option<String>,
+}
+
+impl WorkspaceView {
+    pub fn new(specs: Vec<String>, rx: Receiver<WorkspaceEvent>, cancel: CancelToken) -> Self {
+        let rows = vec![None; specs.len()];
+        Self {
+            specs,
+            rows,
+            running: None,
+            rx: Some(rx),
+            cancel,
+            scroll: 0,
+            error: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Apply pending events, settling each spec's findings with `app`'s
+    /// suppressions, baseline, severity overrides and fail policy. Returns
+    /// `true` once the run has ended.
+    pub fn drain(&mut self, app: &App) -> bool {
+        let Some(rx) = self.rx.take() else {
+            return false;
+        };
+        let mut ended = false;
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                WorkspaceEvent::SpecStarted(index) => self.running = Some(index),
+                WorkspaceEvent::SpecFinished { index, report } => {
+                    self.rows[index] = Some(row(app, &self.specs[index], *report));
+                }
+                WorkspaceEvent::SpecFailed { index, reason } => {
+                    self.rows[index] = Some(SpecRow::failed(&self.specs[index], reason));
+                }
+                WorkspaceEvent::Completed => ended = true,
+                WorkspaceEvent::Aborted(reason) => {
+                    self.error = Some(reason);
+                    ended = true;
+                }
+            }
+        }
+        if ended {
+            self.running = None;
+        } else {
+            self.rx = Some(rx);
+        }
+        ended
+    }
+
+    /// The finished rows with their totals.
+    pub fn report(&self) -> WorkspaceReport {
+        WorkspaceReport::new(self.rows.iter().flatten().cloned().collect())
+    }
+}
+
+/// The row of one spec's run, settled the way the main view settles the
+/// last run: suppressed lint and security findings aren't counted, those in
+/// the baseline are counted as known, and the lint status is re-evaluated
+/// under the severity overrides and fail policy.
+fn row(app: &App, spec: &str, mut report: ValidateReport) -> SpecRow {
+    let phases = &mut report.phases;
+    let structure = phases.structure.as_ref().map_or(0, |s| s.findings.len());
+    let mut parsed = Vec::new();
+    if let Some(lint) = phases.lint.as_mut() {
+        let errors = log_parser::parse_lint_output(&lint.log);
+        if let Some(status) = app.settled_lint_status(&lint.status, &errors) {
+            lint.status = status.into();
+        }
+        parsed.extend(errors);
+    }
+    if let Some(security) = &phases.security {
+        parsed.extend(log_parser::parse_lint_output(&security.log));
+    }
+    parsed.retain(|e| !app.is_suppressed(e));
+    let known = parsed.iter().filter(|e| app.is_known(e)).count();
+    SpecRow::from_report(spec, &report, structure + parsed.len(), known)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lazyoav::pipeline::{LintResult, Phases, StructureResult};
+    use lazyoav::structure::StructureFinding;
+    use lazyoav::suppress::Suppression;
+    use std::sync::mpsc;
+
+    #[test]
+    fn drain_fills_rows_as_specs_finish() {
+        let (tx, rx) = mpsc::channel();
+        let mut view = WorkspaceView::new(
+            vec!["a/openapi.yaml".into(), "b/openapi.yaml".into()],
+            rx,
+            CancelToken::new(),
+        );
+        let report = ValidateReport {
+            phases: Phases {
+                structure: Some(StructureResult {
+                    status: "fail".into(),
+                    findings: vec![StructureFinding::default(); 2],
+                    ..StructureResult::default()
+                }),
+                ..Phases::default()
+            },
+            ..ValidateReport::default()
+        };
+        tx.send(WorkspaceEvent::SpecStarted(0)).unwrap();
+        tx.send(WorkspaceEvent::SpecFinished {
+            index: 0,
+            report: Box::new(report),
+        })
+        .unwrap();
+        tx.send(WorkspaceEvent::SpecStarted(1)).unwrap();
+        let app = App::new();
+        assert!(!view.drain(&app));
+        assert_eq!(view.running, Some(1));
+        let row = view.rows[0].as_ref().unwrap();
+        assert_eq!((row.structure, row.findings), (Some(false), 2));
+
+        tx.send(WorkspaceEvent::SpecFailed {
+            index: 1,
+            reason: "Spec file not found".into(),
+        })
+        .unwrap();
+        tx.send(WorkspaceEvent::Completed).unwrap();
+        assert!(view.drain(&app));
+        assert!(!view.is_running());
+        let report = view.report();
+        assert_eq!((report.passed, report.failed, report.findings), (0, 2, 2));
+    }
+
+    #[test]
+    fn rows_settle_lint_like_the_main_view() {
+        let log = r#"[
+            {"code": "r1", "message": "m", "severity": 0, "path": ["info"]},
+            {"code": "r2", "message": "m", "severity": 1, "path": ["paths"]}
+        ]"#;
+        let report = ValidateReport {
+            phases: Phases {
+                lint: Some(LintResult {
+                    status: "fail".into(),
+                    log: log.into(),
+                    ..LintResult::default()
+                }),
+                ..Phases::default()
+            },
+            ..ValidateReport::default()
+        };
+        let mut app = App::new();
+        app.suppressions.push(Suppression {
+            rule: "r1".into(),
+            path: None,
+        });
+        let row = row(&app, "openapi.yaml", report);
+        assert_eq!((row.lint, row.findings), (Some(true), 1));
+    }
+}
@@ -0,0 +1,290 @@
+//! The fix assistant: a language model asked for a fix when neither a
+//! built-in rule nor a plugin has one. Opt-in via `fix_assistant`; nothing
+//! is sent anywhere unless it's set, and where it's sent (`endpoint`,
+//! `api_key_env`, `command`) only comes from the user config.
+//!
+//! The assistant gets the finding, its rule's description and the spec
+//! lines around it, and answers `{"fix": null}` or `{"fix": {"description",
+//! "line", "removed", "inserted"}}`: replace the `removed` lines starting at
+//! 1-based `line` with `inserted`. A `command` reads the request as JSON on
+//! stdin and prints that answer; an `endpoint` is sent a chat completion
+//! request asking for it.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::config::FixAssistant;
+use crate::plugins::exchange;
+
+/// What the model is told about the answer it has to give.
+const INSTRUCTIONS: &str = "You fix findings of an OpenAPI linter. You get the finding, \
+the rule's description and the spec lines around it, numbered from `first_line`. \
+Answer with JSON only: {\"fix\": null} if it can't be fixed by editing these lines, \
+otherwise {\"fix\": {\"description\": \"<what the edit does>\", \"line\": <1-based line of \
+the first removed line, or the line the new lines go before>, \"removed\": [<the exact \
+lines replaced, may be empty>], \"inserted\": [<the new lines, indented to fit>]}}. \
+Keep the edit as small as possible.";
+
+/// A finding the assistant is asked to fix.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssistRequest {
+    pub rule: String,
+    pub message: String,
+    pub rule_description: Option<String>,
+    pub json_path: Option<String>,
+    /// 1-based line of the finding, or 0.
+    pub line: usize,
+    /// 1-based line of `context[0]`.
+    pub first_line: usize,
+    pub context: Vec<String>,
+}
+
+impl AssistRequest {
+    /// A request carrying `radius` lines of `spec_lines` on each side of
+    /// `line`.
+    pub fn new(
+        rule: &str,
+        message: &str,
+        json_path: Option<&str>,
+        line: usize,
+        spec_lines: &[String],
+        radius: usize,
+    ) -> Self {
+        let center = line.clamp(1, spec_lines.len().max(1)) - 1;
+        let start = center.saturating_sub(radius);
+        let end = (center + radius + 1).min(spec_lines.len());
+        Self {
+            rule: rule.to_string(),
+            message: message.to_string(),
+            rule_description: None,
+            json_path: json_path.map(str::to_string),
+            line,
+            first_line: start + 1,
+            context: spec_lines[start..end].to_vec(),
+        }
+    }
+}
+
+/// An edit the assistant suggests.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Suggestion {
+    pub description: String,
+    /// 1-based line of the first removed line, or the line `inserted`
+    /// goes before.
+    pub line: usize,
+    #[serde(default)]
+    pub removed: Vec<String>,
+    pub inserted: Vec<String>,
+}
+
+impl Suggestion {
+    /// Where the edit starts in `spec_lines` (0-based). Models are often a
+    /// line or two off, so when `removed` isn't at `line` it's accepted
+    /// wherever it appears exactly once; `None` when it doesn't fit.
+    pub fn locate(&self, spec_lines: &[String]) -> Option<usize> {
+        let at = self.line.checked_sub(1)?;
+        if self.removed.is_empty() {
+            return (at <= spec_lines.len()).then_some(at);
+        }
+        let fits = |start: usize| spec_lines[start..].starts_with(&self.removed);
+        if at < spec_lines.len() && fits(at) {
+            return Some(at);
+        }
+        let mut matches = (0..spec_lines.len()).filter(|&i| fits(i));
+        match (matches.next(), matches.next()) {
+            (Some(start), None) => Some(start),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Answer {
+    fix: Option<Suggestion>,
+}
+
+/// Ask the assistant configured in `cfg` to fix `request`. `root` is the
+/// project directory a `command` runs in.
+pub fn suggest(
+    root: &Path,
+    cfg: &FixAssistant,
+    request: &AssistRequest,
+) -> Result<Option<Suggestion>> {
+    let timeout = Duration::from_secs(cfg.timeout);
+    let answer = if let Some(command) = &cfg.command {
+        let input = serde_json::to_vec(request)?;
+        let output = exchange(
+            Command::new("sh").arg("-c").arg(command).current_dir(root),
+            &input,
+            timeout,
+        )?;
+        String::from_utf8_lossy(&output).into_owned()
+    } else if let Some(endpoint) = &cfg.endpoint {
+        complete(cfg, endpoint, request, timeout)?
+    } else {
+        bail!("fix_assistant has neither a command nor an endpoint");
+    };
+    parse_answer(&answer)
+}
+
+/// Send `request` to an OpenAI-compatible chat completions endpoint with
+/// the host's `curl`, returning the model's reply.
+fn complete(
+    cfg: &FixAssistant,
+    endpoint: &str,
+    request: &AssistRequest,
+    timeout: Duration,
+) -> Result<String> {
+    let model = cfg
+        .model
+        .as_deref()
+        .context("fix_assistant.model is not set")?;
+    let body = json!({
+        "model": model,
+        "temperature": 0,
+        "messages": [
+            {"role": "system", "content": INSTRUCTIONS},
+            {"role": "user", "content": serde_json::to_string_pretty(request)?},
+        ],
+    });
+    let mut headers = vec!["Content-Type: application/json".to_string()];
+    if let Some(var) = &cfg.api_key_env {
+        let key = std::env::var(var).with_context(|| format!("{var} is not set"))?;
+        headers.push(format!("Authorization: Bearer {key}"));
+    }
+    // Headers and body go to curl as a config on stdin, keeping the key
+    // off its command line, where other users could read it.
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "--fail", "--max-time"])
+        .arg(timeout.as_secs().to_string())
+        .args(["-X", "POST", "-K", "-"])
+        .arg(endpoint);
+    // curl enforces the timeout; this only catches a hung process.
+    let output = exchange(
+        &mut command,
+        curl_config(&headers, &serde_json::to_string(&body)?).as_bytes(),
+        timeout + Duration::from_secs(5),
+    )?;
+    let response: serde_json::Value = serde_json::from_slice(&output)
+        .context("endpoint answered with something other than JSON")?;
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(str::to_string)
+        .context("endpoint answered without a message")
+}
+
+/// A curl config sending `headers` and `body`.
+fn curl_config(headers: &[String], body: &str) -> String {
+    let quote = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut config: String = headers
+        .iter()
+        .map(|header| format!("header = \"{}\"\n", quote(header)))
+        .collect();
+    config.push_str(&format!("data-binary = \"{}\"\n", quote(body)));
+    config
+}
+
+/// Parse an answer, tolerating a Markdown code fence around the JSON.
+fn parse_answer(answer: &str) -> Result<Option<Suggestion>> {
+    let mut text = answer.trim();
+    if let Some(fenced) = text.strip_prefix("```") {
+        let body = fenced.split_once('\n').map_or("", |(_, rest)| rest);
+        text = body.trim_end().strip_suffix("```").unwrap_or(body).trim();
+    }
+    let answer: Answer =
+        serde_json::from_str(text).context("assistant's answer is not the expected JSON")?;
+    Ok(answer.fix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn request_carries_the_lines_around_the_finding() {
+        let spec = lines("a\nb\nc\nd\ne\nf");
+        let request = AssistRequest::new("r", "m", None, 2, &spec, 2);
+        assert_eq!(request.first_line, 1);
+        assert_eq!(request.context, lines("a\nb\nc\nd"));
+        let request = AssistRequest::new("r", "m", None, 6, &spec, 1);
+        assert_eq!(request.first_line, 5);
+        assert_eq!(request.context, lines("e\nf"));
+    }
+
+    #[test]
+    fn suggestions_are_located_even_when_off_by_a_line() {
+        let spec = lines("info:\n  title: Pets\n  version: 1.0.0\npaths: {}");
+        let suggestion = |line, removed: &str| Suggestion {
+            description: "d".into(),
+            line,
+            removed: lines(removed),
+            inserted: vec![],
+        };
+        assert_eq!(suggestion(2, "  title: Pets").locate(&spec), Some(1));
+        assert_eq!(suggestion(3, "  title: Pets").locate(&spec), Some(1));
+        assert_eq!(suggestion(2, "  title: Dogs").locate(&spec), None);
+        assert_eq!(suggestion(5, "").locate(&spec), Some(4));
+        assert_eq!(suggestion(6, "").locate(&spec), None);
+        assert_eq!(suggestion(0, "").locate(&spec), None);
+    }
+
+    #[test]
+    fn curl_gets_headers_and_body_as_a_config() {
+        let headers = ["Authorization: Bearer s3cret".to_string()];
+        let config = curl_config(&headers, r#"{"text":"a \"b\""}"#);
+        let mut lines = config.lines();
+        assert_eq!(
+            lines.next(),
+            Some(r#"header = "Authorization: Bearer s3cret""#)
+        );
+        assert_eq!(
+            lines.next(),
+            Some(r#"data-binary = "{\"text\":\"a \\\"b\\\"\"}""#)
+        );
+    }
+
+    #[test]
+    fn answers_may_be_fenced() {
+        let fix = parse_answer(
+            "```json\n{\"fix\": {\"description\": \"Add a description\", \"line\": 3, \"inserted\": [\"  description: Pets\"]}}\n```",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(fix.line, 3);
+        assert!(fix.removed.is_empty());
+        assert_eq!(parse_answer("{\"fix\": null}").unwrap(), None);
+        assert!(parse_answer("I can't help with that").is_err());
+    }
+
+    #[test]
+    fn commands_get_the_request_on_stdin() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cfg = FixAssistant {
+            command: Some(
+                r#"grep -q '"rule":"info-x-audience"' && echo '{"fix": {"description": "Add x-audience", "line": 2, "inserted": ["  x-audience: public"]}}'"#
+                    .into(),
+            ),
+            ..Default::default()
+        };
+        let spec = lines("info:\n  title: Pets");
+        let request = AssistRequest::new("info-x-audience", "missing", None, 1, &spec, 5);
+        let fix = suggest(tmp.path(), &cfg, &request).unwrap().unwrap();
+        assert_eq!(fix.inserted, ["  x-audience: public"]);
+
+        let failing = FixAssistant {
+            command: Some("exit 3".into()),
+            ..Default::default()
+        };
+        assert!(suggest(tmp.path(), &failing, &request).is_err());
+    }
+}
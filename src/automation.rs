@@ -0,0 +1,294 @@
+//! Automation scripts: a project's Rhai script, named by `automation` in
+//! `.oavc`, reacting to pipeline events. Like hooks, a project's script
+//! only runs once the user trusts the project (see [`crate::trust`]).
+//!
+//! The script defines the handlers it needs, and can call the actions
+//! registered here:
+//!
+//! ```rhai
+//! fn on_run_complete(run) {
+//!     if run.status == "fail" {
+//!         webhook("https://hooks.example.com/api", `${run.failed} step(s) failed`);
+//!     }
+//!     export_report("../artifacts/openapi-report.json");
+//! }
+//!
+//! fn on_fix_applied(fix) {
+//!     notify(`Fixed ${fix.rule}`);
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+
+use anyhow::{Context, Result, anyhow};
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
+
+use crate::pipeline::ValidateReport;
+use crate::reports::REPORT_FILE;
+
+/// Operations a handler may run before it's stopped, so a runaway loop
+/// can't freeze the TUI.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Seconds a webhook request may take.
+const WEBHOOK_TIMEOUT: &str = "10";
+
+/// Run `on_run_complete(run)` for a finished run. `lint` is the number of
+/// visible lint errors and warnings. Returns the messages the script passed
+/// to `notify` or `print`.
+pub fn run_complete(
+    root: &Path,
+    script: &str,
+    report: &ValidateReport,
+    lint: (usize, usize),
+) -> Result<Vec<String>> {
+    let mut run = Map::new();
+    let status = if report.summary.failed == 0 {
+        "pass"
+    } else {
+        "fail"
+    };
+    run.insert("spec".into(), report.spec.clone().into());
+    run.insert("status".into(), status.into());
+    run.insert("total".into(), (report.summary.total as i64).into());
+    run.insert("passed".into(), (report.summary.passed as i64).into());
+    run.insert("failed".into(), (report.summary.failed as i64).into());
+    run.insert("lint_errors".into(), (lint.0 as i64).into());
+    run.insert("lint_warnings".into(), (lint.1 as i64).into());
+    run.insert("report".into(), REPORT_FILE.into());
+    call(root, script, "on_run_complete", run.into())
+}
+
+/// Run `on_fix_applied(fix)` after `count` fixes for `rule` were written
+/// to the spec; `line` is the 1-based line of a single fix, or 0.
+pub fn fix_applied(
+    root: &Path,
+    script: &str,
+    rule: &str,
+    description: &str,
+    line: usize,
+    count: usize,
+) -> Result<Vec<String>> {
+    let mut fix = Map::new();
+    fix.insert("rule".into(), rule.into());
+    fix.insert("description".into(), description.into());
+    fix.insert("line".into(), (line as i64).into());
+    fix.insert("count".into(), (count as i64).into());
+    call(root, script, "on_fix_applied", fix.into())
+}
+
+/// Call `handler` in `script` with `arg`, if the script defines it.
+fn call(root: &Path, script: &str, handler: &str, arg: Dynamic) -> Result<Vec<String>> {
+    let path = root.join(script);
+    let source =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let engine = engine(root, &messages);
+    let ast = engine
+        .compile(&source)
+        .map_err(|e| anyhow!("{script}: {e}"))?;
+    if !ast.iter_functions().any(|f| f.name == handler) {
+        return Ok(Vec::new());
+    }
+    // Whatever the handler returns is ignored.
+    let _ = engine
+        .call_fn::<Dynamic>(&mut Scope::new(), &ast, handler, (arg,))
+        .map_err(|e| anyhow!("{script}: {handler}: {e}"))?;
+    Ok(messages.take())
+}
+
+/// An engine with the actions scripts may call. Paths are relative to
+/// `root`, and output goes to `messages` instead of the terminal.
+fn engine(root: &Path, messages: &Rc<RefCell<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    let out = Rc::clone(messages);
+    engine.on_print(move |text| out.borrow_mut().push(text.to_string()));
+    let out = Rc::clone(messages);
+    engine.on_debug(move |text, _, _| out.borrow_mut().push(text.to_string()));
+    let out = Rc::clone(messages);
+    engine.register_fn("notify", move |text: &str| {
+        out.borrow_mut().push(text.to_string())
+    });
+
+    let base = root.to_path_buf();
+    engine.register_fn("export_report", move |to: &str| {
+        copy(&base, REPORT_FILE, to)
+    });
+    let base = root.to_path_buf();
+    engine.register_fn("copy_file", move |from: &str, to: &str| {
+        copy(&base, from, to)
+    });
+    engine.register_fn("webhook", |url: &str, text: &str| {
+        let mut body = Map::new();
+        body.insert("text".into(), text.into());
+        post(url, &rhai::format_map_as_json(&body))
+    });
+    engine.register_fn("webhook", |url: &str, body: Map| {
+        post(url, &rhai::format_map_as_json(&body))
+    });
+    engine
+}
+
+fn copy(root: &Path, from: &str, to: &str) -> Result<(), Box<EvalAltResult>> {
+    let from = inside(root, from)?;
+    let to = inside(root, to)?;
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    fs::copy(&from, &to)
+        .map_err(|e| format!("Failed to copy {} to {}: {e}", from.display(), to.display()))?;
+    Ok(())
+}
+
+/// `path` under `root`, refused when it's absolute, climbs out with `..`
+/// or leads out through a symlink.
+fn inside(root: &Path, path: &str) -> Result<PathBuf, Box<EvalAltResult>> {
+    let outside = || format!("{path} is outside the project");
+    if !Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(outside().into());
+    }
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {e}", root.display()))?;
+    let joined = root.join(path);
+    // What doesn't exist yet can't be a symlink; resolve the rest.
+    let existing = joined.ancestors().find(|p| p.exists()).unwrap_or(&root);
+    let resolved = existing
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {e}", existing.display()))?;
+    if !resolved.starts_with(&root) {
+        return Err(outside().into());
+    }
+    match joined.strip_prefix(existing) {
+        Ok(rest) if !rest.as_os_str().is_empty() => Ok(resolved.join(rest)),
+        _ => Ok(resolved),
+    }
+}
+
+/// POST `json` to `url` with the host's `curl`.
+fn post(url: &str, json: &str) -> Result<(), Box<EvalAltResult>> {
+    let output = Command::new("curl")
+        .args(["-sS", "--fail", "--max-time", WEBHOOK_TIMEOUT, "-X", "POST"])
+        .args([
+            "-H",
+            "Content-Type: application/json",
+            "--data-binary",
+            json,
+        ])
+        .arg(url)
+        .output()
+        .map_err(|e| format!("Failed to run curl: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Webhook to {url} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Summary;
+
+    fn report(failed: usize) -> ValidateReport {
+        ValidateReport {
+            spec: "api.yaml".into(),
+            summary: Summary {
+                total: 3,
+                passed: 3 - failed,
+                failed,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn handlers_see_the_run_and_call_actions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join(".oav/reports")).unwrap();
+        fs::write(root.join(REPORT_FILE), "{}").unwrap();
+        fs::write(
+            root.join("auto.rhai"),
+            r#"
+            fn on_run_complete(run) {
+                if run.status == "fail" {
+                    notify(`${run.failed} of ${run.total} failed, ${run.lint_errors} errors`);
+                }
+                export_report("out/report.json");
+            }
+            "#,
+        )
+        .unwrap();
+
+        let messages = run_complete(root, "auto.rhai", &report(1), (4, 2)).unwrap();
+        assert_eq!(messages, vec!["1 of 3 failed, 4 errors"]);
+        assert!(root.join("out/report.json").is_file());
+
+        // A script without the handler does nothing.
+        assert!(
+            fix_applied(root, "auto.rhai", "info-contact", "Add contact", 3, 1)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn script_errors_and_runaway_loops_are_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join("bad.rhai"),
+            "fn on_fix_applied(fix) { copy_file(\"missing\", \"x\"); }\nfn on_run_complete(run) { loop {} }\n",
+        )
+        .unwrap();
+
+        let err = fix_applied(root, "bad.rhai", "r", "d", 1, 1).unwrap_err();
+        assert!(format!("{err}").contains("Failed to copy"));
+        assert!(run_complete(root, "bad.rhai", &report(0), (0, 0)).is_err());
+        assert!(run_complete(root, "missing.rhai", &report(0), (0, 0)).is_err());
+    }
+
+    #[test]
+    fn copies_stay_inside_the_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("api");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+
+        assert!(copy(&root, "a.txt", "./out/b.txt").is_ok());
+        assert!(root.join("out/b.txt").is_file());
+        for to in [
+            "../escaped.txt",
+            "out/../../escaped.txt",
+            "/tmp/escaped.txt",
+        ] {
+            let err = copy(&root, "a.txt", to).unwrap_err();
+            assert!(
+                err.to_string().contains("outside the project"),
+                "{to}: {err}"
+            );
+        }
+        assert!(copy(&root, "../api/a.txt", "c.txt").is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(tmp.path(), root.join("up")).unwrap();
+            assert!(copy(&root, "a.txt", "up/escaped.txt").is_err());
+        }
+        assert!(!tmp.path().join("escaped.txt").exists());
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Snapshot of accepted lint findings, relative to the project. Unlike
+/// `.oav/reports/` it isn't gitignored, so the team can share one baseline.
+pub const BASELINE_FILE: &str = ".oav/baseline.json";
+
+/// A lint finding as recorded in the baseline.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Finding {
+    pub rule: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl Finding {
+    /// Findings are the same when rule and spec path match. Line numbers
+    /// shift as the spec is edited, so they aren't compared; without a path
+    /// the message is the best remaining identity.
+    fn same_as(&self, other: &Finding) -> bool {
+        self.rule == other.rule
+            && match (&self.path, &other.path) {
+                (Some(a), Some(b)) => a == b,
+                (None, None) => self.message == other.message,
+                _ => false,
+            }
+    }
+}
+
+/// Findings that already existed when the baseline was taken.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub findings: Vec<Finding>,
+}
+
+impl Baseline {
+    pub fn new(mut findings: Vec<Finding>) -> Self {
+        findings.sort();
+        findings.dedup();
+        Self { findings }
+    }
+
+    pub fn contains(&self, finding: &Finding) -> bool {
+        self.findings.iter().any(|known| known.same_as(finding))
+    }
+}
+
+/// Read [`BASELINE_FILE`] from `root`, or `None` if there isn't one.
+pub fn load(root: &Path) -> Result<Option<Baseline>> {
+    let path = root.join(BASELINE_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let baseline = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(baseline))
+}
+
+/// Write `baseline` to [`BASELINE_FILE`], replacing any previous one.
+pub fn save(root: &Path, baseline: &Baseline) -> Result<()> {
+    let path = root.join(BASELINE_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(baseline).context("Failed to serialize baseline")?;
+    fs::write(&path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(rule: &str, path: Option<&str>, message: &str) -> Finding {
+        Finding {
+            rule: rule.into(),
+            path: path.map(Into::into),
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn contains_matches_path_or_message() {
+        let baseline = Baseline::new(vec![
+            finding("operation-tags", Some("paths./a.get"), "Missing tags"),
+            finding("info-contact", None, "Info object must have contact"),
+        ]);
+        // Same location, reworded message: still known.
+        assert!(baseline.contains(&finding("operation-tags", Some("paths./a.get"), "No tags")));
+        assert!(!baseline.contains(&finding(
+            "operation-tags",
+            Some("paths./b.get"),
+            "Missing tags"
+        )));
+        assert!(baseline.contains(&finding(
+            "info-contact",
+            None,
+            "Info object must have contact"
+        )));
+        assert!(!baseline.contains(&finding("info-contact", None, "Something else")));
+        assert!(!baseline.contains(&finding(
+            "info-contact",
+            Some("info"),
+            "Info object must have contact"
+        )));
+    }
+
+    #[test]
+    fn new_sorts_and_dedups() {
+        let a = finding("b-rule", None, "m");
+        let b = finding("a-rule", None, "m");
+        let baseline = Baseline::new(vec![a.clone(), b.clone(), a.clone()]);
+        assert_eq!(baseline.findings, vec![b, a]);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(load(tmp.path()).unwrap(), None);
+
+        let baseline = Baseline::new(vec![finding("r", Some("paths"), "m")]);
+        save(tmp.path(), &baseline).unwrap();
+        assert_eq!(load(tmp.path()).unwrap(), Some(baseline));
+
+        fs::write(tmp.path().join(BASELINE_FILE), "not json").unwrap();
+        assert!(load(tmp.path()).is_err());
+    }
+}
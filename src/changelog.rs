@@ -0,0 +1,540 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use serde_yaml::{Mapping, Value};
+
+use crate::openapi::{self, key, resolve_ref};
+
+/// Where the Markdown export is written, relative to the project.
+pub const CHANGELOG_FILE: &str = ".oav/reports/changelog.md";
+
+/// Whether a change can break existing clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Impact {
+    Breaking,
+    NonBreaking,
+}
+
+/// One difference between two versions of a spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub impact: Impact,
+    /// What changed, e.g. `GET /pets` or `schema Pet`.
+    pub subject: String,
+    /// How it changed, e.g. `added required query parameter limit`.
+    pub description: String,
+}
+
+/// The changes from one spec version to the next, operations first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changelog {
+    pub changes: Vec<Change>,
+}
+
+impl Changelog {
+    /// Compare two parsed specs.
+    pub fn between(old: &Value, new: &Value) -> Self {
+        let mut log = Self::default();
+        log.compare_operations(old, new);
+        log.compare_schemas(old, new);
+        log
+    }
+
+    /// Compare two spec documents (YAML or JSON).
+    pub fn between_sources(old: &str, new: &str) -> Result<Self> {
+        let old: Value = serde_yaml::from_str(old).context("Failed to parse the old spec")?;
+        let new: Value = serde_yaml::from_str(new).context("Failed to parse the current spec")?;
+        Ok(Self::between(&old, &new))
+    }
+
+    pub fn breaking_count(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| c.impact == Impact::Breaking)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Release notes for the changes since `base`, breaking changes first.
+    pub fn to_markdown(&self, base: &str) -> String {
+        let mut out = format!("# API changes since {base}\n");
+        if self.changes.is_empty() {
+            out.push_str("\nNo changes to operations or schemas.\n");
+            return out;
+        }
+        for (impact, heading) in [
+            (Impact::Breaking, "Breaking changes"),
+            (Impact::NonBreaking, "Other changes"),
+        ] {
+            let changes: Vec<&Change> =
+                self.changes.iter().filter(|c| c.impact == impact).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            let _ = write!(out, "\n## {heading}\n\n");
+            for change in changes {
+                let _ = writeln!(out, "- `{}`: {}", change.subject, change.description);
+            }
+        }
+        out
+    }
+
+    fn push(&mut self, impact: Impact, subject: &str, description: impl Into<String>) {
+        self.changes.push(Change {
+            impact,
+            subject: subject.to_string(),
+            description: description.into(),
+        });
+    }
+
+    fn compare_operations(&mut self, old: &Value, new: &Value) {
+        let old_ops = operations(old);
+        let new_ops = operations(new);
+        for (key, new_op) in &new_ops {
+            let subject = format!("{} {}", key.1.to_ascii_uppercase(), key.0);
+            match old_ops.iter().find(|(k, _)| k == key) {
+                None => self.push(Impact::NonBreaking, &subject, "added operation"),
+                Some((_, old_op)) => {
+                    self.compare_operation(&subject, &key.0, old, old_op, new, new_op)
+                }
+            }
+        }
+        for (key, _) in &old_ops {
+            if !new_ops.iter().any(|(k, _)| k == key) {
+                let subject = format!("{} {}", key.1.to_ascii_uppercase(), key.0);
+                self.push(Impact::Breaking, &subject, "removed operation");
+            }
+        }
+    }
+
+    fn compare_operation(
+        &mut self,
+        subject: &str,
+        path: &str,
+        old_root: &Value,
+        old: &Value,
+        new_root: &Value,
+        new: &Value,
+    ) {
+        if !is_true(old.get("deprecated")) && is_true(new.get("deprecated")) {
+            self.push(Impact::NonBreaking, subject, "deprecated");
+        }
+
+        let old_params = parameters(old_root, path, old);
+        let new_params = parameters(new_root, path, new);
+        for (key, param) in &new_params {
+            let required = is_true(param.get("required"));
+            match old_params.iter().find(|(k, _)| k == key) {
+                None if required => self.push(
+                    Impact::Breaking,
+                    subject,
+                    format!("added required {} parameter {}", key.1, key.0),
+                ),
+                None => self.push(
+                    Impact::NonBreaking,
+                    subject,
+                    format!("added optional {} parameter {}", key.1, key.0),
+                ),
+                Some((_, old_param)) if required && !is_true(old_param.get("required")) => self
+                    .push(
+                        Impact::Breaking,
+                        subject,
+                        format!("{} parameter {} is now required", key.1, key.0),
+                    ),
+                Some(_) => {}
+            }
+        }
+        for (key, _) in &old_params {
+            if !new_params.iter().any(|(k, _)| k == key) {
+                self.push(
+                    Impact::Breaking,
+                    subject,
+                    format!("removed {} parameter {}", key.1, key.0),
+                );
+            }
+        }
+
+        let old_body = old
+            .get("requestBody")
+            .map(|body| resolve_ref(old_root, body));
+        let new_body = new
+            .get("requestBody")
+            .map(|body| resolve_ref(new_root, body));
+        match (old_body, new_body) {
+            (None, Some(body)) if is_true(body.get("required")) => {
+                self.push(Impact::Breaking, subject, "added required request body");
+            }
+            (None, Some(_)) => self.push(Impact::NonBreaking, subject, "added request body"),
+            (Some(_), None) => self.push(Impact::Breaking, subject, "removed request body"),
+            (Some(old_body), Some(new_body))
+                if !is_true(old_body.get("required")) && is_true(new_body.get("required")) =>
+            {
+                self.push(Impact::Breaking, subject, "request body is now required");
+            }
+            _ => {}
+        }
+
+        let old_responses = keys(old.get("responses"));
+        let new_responses = keys(new.get("responses"));
+        for code in new_responses.difference(&old_responses) {
+            self.push(
+                Impact::NonBreaking,
+                subject,
+                format!("added response {code}"),
+            );
+        }
+        for code in old_responses.difference(&new_responses) {
+            self.push(
+                Impact::Breaking,
+                subject,
+                format!("removed response {code}"),
+            );
+        }
+    }
+
+    fn compare_schemas(&mut self, old: &Value, new: &Value) {
+        let old_schemas = schemas(old);
+        let new_schemas = schemas(new);
+        for (name, new_schema) in new_schemas.into_iter().flatten() {
+            let subject = format!("schema {}", key(name));
+            match old_schemas.and_then(|s| s.get(name)) {
+                None => self.push(Impact::NonBreaking, &subject, "added schema"),
+                Some(old_schema) => self.compare_schema(&subject, old_schema, new_schema),
+            }
+        }
+        for (name, _) in old_schemas.into_iter().flatten() {
+            if new_schemas.is_none_or(|s| s.get(name).is_none()) {
+                let subject = format!("schema {}", key(name));
+                self.push(Impact::Breaking, &subject, "removed schema");
+            }
+        }
+    }
+
+    fn compare_schema(&mut self, subject: &str, old: &Value, new: &Value) {
+        if let (Some(old_type), Some(new_type)) = (old.get("type"), new.get("type"))
+            && old_type != new_type
+        {
+            self.push(
+                Impact::Breaking,
+                subject,
+                format!("type changed from {} to {}", key(old_type), key(new_type)),
+            );
+        }
+
+        let old_props = keys(old.get("properties"));
+        let new_props = keys(new.get("properties"));
+        let old_required = strings(old.get("required"));
+        let new_required = strings(new.get("required"));
+        for name in new_props.difference(&old_props) {
+            if new_required.contains(name) {
+                self.push(
+                    Impact::Breaking,
+                    subject,
+                    format!("added required field {name}"),
+                );
+            } else {
+                self.push(Impact::NonBreaking, subject, format!("added field {name}"));
+            }
+        }
+        for name in old_props.difference(&new_props) {
+            self.push(Impact::Breaking, subject, format!("removed field {name}"));
+        }
+        for name in old_props.intersection(&new_props) {
+            let field = |schema: &Value| schema.get("properties")?.get(name.as_str()).cloned();
+            if let (Some(old_field), Some(new_field)) = (field(old), field(new))
+                && let (Some(old_type), Some(new_type)) =
+                    (old_field.get("type"), new_field.get("type"))
+                && old_type != new_type
+            {
+                self.push(
+                    Impact::Breaking,
+                    subject,
+                    format!(
+                        "field {name} changed type from {} to {}",
+                        key(old_type),
+                        key(new_type)
+                    ),
+                );
+            }
+            if new_required.contains(name) && !old_required.contains(name) {
+                self.push(
+                    Impact::Breaking,
+                    subject,
+                    format!("field {name} is now required"),
+                );
+            }
+        }
+    }
+}
+
+/// The spec as committed at `base` in the git repository containing `spec`,
+/// e.g. `HEAD` or a release tag.
+pub fn read_at_revision(spec: &Path, base: &str) -> Result<String> {
+    let dir = spec.parent().unwrap_or(Path::new("."));
+    let name = spec
+        .file_name()
+        .context("Spec path has no file name")?
+        .to_string_lossy();
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .arg("show")
+        .arg(format!("{base}:./{name}"))
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git show {base}: {}", stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Operations keyed by (path, method), in document order.
+fn operations(spec: &Value) -> Vec<((String, String), &Value)> {
+    openapi::operations(spec)
+        .map(|(path, method, op)| ((path.to_string(), method.to_string()), op))
+        .collect()
+}
+
+/// The parameters of the operation at `path`, those declared on the path
+/// item included, keyed by (name, location), with local `$ref`s resolved.
+/// The operation's own take precedence.
+fn parameters<'a>(
+    root: &'a Value,
+    path: &str,
+    op: &'a Value,
+) -> Vec<((String, String), &'a Value)> {
+    let item = root.get("paths").and_then(|paths| paths.get(path));
+    let mut params = declared_parameters(root, Some(op));
+    for (key, param) in declared_parameters(root, item) {
+        if !params.iter().any(|(k, _)| *k == key) {
+            params.push((key, param));
+        }
+    }
+    params
+}
+
+/// The `parameters` listed on an operation or path item.
+fn declared_parameters<'a>(
+    root: &'a Value,
+    holder: Option<&'a Value>,
+) -> Vec<((String, String), &'a Value)> {
+    let Some(params) = holder
+        .and_then(|h| h.get("parameters"))
+        .and_then(Value::as_sequence)
+    else {
+        return Vec::new();
+    };
+    params
+        .iter()
+        .filter_map(|param| {
+            let param = resolve_ref(root, param);
+            let name = param.get("name")?.as_str()?;
+            let location = param.get("in")?.as_str()?;
+            Some(((name.to_string(), location.to_string()), param))
+        })
+        .collect()
+}
+
+fn schemas(spec: &Value) -> Option<&Mapping> {
+    spec.get("components")?.get("schemas")?.as_mapping()
+}
+
+fn keys(value: Option<&Value>) -> BTreeSet<String> {
+    value
+        .and_then(Value::as_mapping)
+        .map(|map| map.keys().map(key).collect())
+        .unwrap_or_default()
+}
+
+fn strings(value: Option<&Value>) -> BTreeSet<String> {
+    value
+        .and_then(Value::as_sequence)
+        .map(|list| list.iter().map(key).collect())
+        .unwrap_or_default()
+}
+
+fn is_true(value: Option<&Value>) -> bool {
+    value.and_then(Value::as_bool).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+        - { name: tag, in: query }
+      responses:
+        '200': { description: ok }
+        '404': { description: missing }
+    delete:
+      responses:
+        '204': { description: gone }
+components:
+  parameters:
+    Limit: { name: limit, in: query }
+  schemas:
+    Pet:
+      type: object
+      required: [id]
+      properties:
+        id: { type: integer }
+        name: { type: string }
+        nickname: { type: string }
+    Legacy: { type: object }
+"#;
+
+    const NEW: &str = r#"
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      deprecated: true
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+        - { name: page, in: query }
+      responses:
+        200: { description: ok }
+        '500': { description: boom }
+    post:
+      requestBody: { required: true }
+      responses:
+        '201': { description: created }
+components:
+  parameters:
+    Limit: { name: limit, in: query, required: true }
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id: { type: string }
+        name: { type: string }
+        owner: { type: string }
+"#;
+
+    fn descriptions(log: &Changelog, impact: Impact) -> Vec<String> {
+        log.changes
+            .iter()
+            .filter(|c| c.impact == impact)
+            .map(|c| format!("{}: {}", c.subject, c.description))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_operation_and_schema_changes() {
+        let log = Changelog::between_sources(OLD, NEW).unwrap();
+        assert_eq!(
+            descriptions(&log, Impact::Breaking),
+            [
+                "GET /pets: query parameter limit is now required",
+                "GET /pets: removed query parameter tag",
+                "GET /pets: removed response 404",
+                "DELETE /pets: removed operation",
+                "schema Pet: removed field nickname",
+                "schema Pet: field id changed type from integer to string",
+                "schema Pet: field name is now required",
+                "schema Legacy: removed schema",
+            ]
+        );
+        assert_eq!(
+            descriptions(&log, Impact::NonBreaking),
+            [
+                "GET /pets: deprecated",
+                "GET /pets: added optional query parameter page",
+                "GET /pets: added response 500",
+                "POST /pets: added operation",
+                "schema Pet: added field owner",
+            ]
+        );
+        assert_eq!(log.breaking_count(), 8);
+    }
+
+    #[test]
+    fn path_item_parameters_count_for_each_operation() {
+        let old = "paths:\n  /pets/{id}:\n    parameters:\n      - { name: id, in: path, required: true }\n      - { name: trace, in: header }\n    get:\n      parameters:\n        - { name: trace, in: header, required: true }\n    delete: {}\n";
+        let new = "paths:\n  /pets/{id}:\n    parameters:\n      - { name: id, in: path, required: true }\n      - { name: tenant, in: header, required: true }\n    get:\n      parameters:\n        - { name: trace, in: header, required: true }\n    delete: {}\n";
+        let log = Changelog::between_sources(old, new).unwrap();
+        assert_eq!(
+            descriptions(&log, Impact::Breaking),
+            [
+                "GET /pets/{id}: added required header parameter tenant",
+                "DELETE /pets/{id}: added required header parameter tenant",
+                "DELETE /pets/{id}: removed header parameter trace",
+            ]
+        );
+        assert!(descriptions(&log, Impact::NonBreaking).is_empty());
+    }
+
+    #[test]
+    fn requiring_an_existing_request_body_is_breaking() {
+        let old = "paths:\n  /pets:\n    post:\n      requestBody: { $ref: '#/components/requestBodies/Pet' }\ncomponents:\n  requestBodies:\n    Pet: { required: false }\n";
+        let new = "paths:\n  /pets:\n    post:\n      requestBody: { required: true }\n";
+        let log = Changelog::between_sources(old, new).unwrap();
+        assert_eq!(
+            descriptions(&log, Impact::Breaking),
+            ["POST /pets: request body is now required"]
+        );
+        assert!(Changelog::between_sources(new, old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn identical_specs_have_no_changes() {
+        let log = Changelog::between_sources(OLD, OLD).unwrap();
+        assert!(log.is_empty());
+        assert_eq!(
+            log.to_markdown("HEAD"),
+            "# API changes since HEAD\n\nNo changes to operations or schemas.\n"
+        );
+    }
+
+    #[test]
+    fn reads_the_committed_spec() {
+        let tmp = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(tmp.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        let spec = tmp.path().join("api").join("openapi.yaml");
+        std::fs::create_dir(spec.parent().unwrap()).unwrap();
+        std::fs::write(&spec, "openapi: 3.0.0\n").unwrap();
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "spec"]);
+        std::fs::write(&spec, "openapi: 3.1.0\n").unwrap();
+
+        assert_eq!(read_at_revision(&spec, "HEAD").unwrap(), "openapi: 3.0.0\n");
+        assert!(read_at_revision(&spec, "no-such-tag").is_err());
+    }
+
+    #[test]
+    fn markdown_lists_breaking_changes_first() {
+        let old = "paths:\n  /a:\n    get: {}\n";
+        let new = "paths:\n  /b:\n    get: {}\n";
+        let log = Changelog::between_sources(old, new).unwrap();
+        assert_eq!(
+            log.to_markdown("v1.0.0"),
+            "# API changes since v1.0.0\n\n\
+             ## Breaking changes\n\n- `GET /a`: removed operation\n\n\
+             ## Other changes\n\n- `GET /b`: added operation\n"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Deleting generated output under `.oav/generated/`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+const GENERATED_DIR: &str = ".oav/generated";
+
+/// Kept when cleaning: the user's list of files the generator mustn't
+/// overwrite, which isn't output to reclaim.
+const KEPT: &str = ".openapi-generator-ignore";
+
+/// The output directory of `key` (`"{scope}/{generator}"`), or of every
+/// generator without one.
+pub fn generated_dir(work_dir: &Path, key: Option<&str>) -> PathBuf {
+    let dir = work_dir.join(GENERATED_DIR);
+    match key {
+        Some(key) => dir.join(key),
+        None => dir,
+    }
+}
+
+/// Space taken by a generated output tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub bytes: u64,
+    pub files: usize,
+}
+
+/// Bytes and number of files below `dir`. Symlinks count as themselves,
+/// not what they point to.
+pub fn disk_usage(dir: &Path) -> Usage {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_type().is_dir())
+        .filter_map(|e| e.metadata().ok())
+        .fold(Usage::default(), |usage, m| Usage {
+            bytes: usage.bytes + m.len(),
+            files: usage.files + 1,
+        })
+}
+
+/// Bytes that deleting `dir`'s output would reclaim: everything but the
+/// `.openapi-generator-ignore` files.
+pub fn reclaimable(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_type().is_dir() && e.file_name() != KEPT)
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Delete the generated output of `key`, or all of it, returning the bytes
+/// reclaimed. `.openapi-generator-ignore` files and the directories holding
+/// them stay. Nothing to delete reclaims nothing.
+pub fn remove(work_dir: &Path, key: Option<&str>) -> Result<u64> {
+    let dir = generated_dir(work_dir, key);
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut reclaimed = 0;
+    for entry in WalkDir::new(&dir).contents_first(true) {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        let path = entry.path();
+        if entry.file_type().is_dir() {
+            let empty = fs::read_dir(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .next()
+                .is_none();
+            if empty {
+                fs::remove_dir(path)
+                    .with_context(|| format!("Failed to delete {}", path.display()))?;
+            }
+        } else if entry.file_name() != KEPT {
+            reclaimed += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+        }
+    }
+    Ok(reclaimed)
+}
+
+/// `bytes` in the largest unit that keeps it at 1 or more, e.g. `1.4 GB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if size < 10.0 {
+        format!("{size:.1} {}", UNITS[unit])
+    } else {
+        format!("{size:.0} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_deletes_one_generator_or_all() {
+        let dir = tempfile::tempdir().unwrap();
+        let spring = generated_dir(dir.path(), Some("server/spring"));
+        let go = generated_dir(dir.path(), Some("client/go"));
+        fs::create_dir_all(spring.join("src")).unwrap();
+        fs::create_dir_all(&go).unwrap();
+        fs::write(spring.join("src/Api.java"), "x".repeat(1000)).unwrap();
+        fs::write(spring.join("pom.xml"), "x".repeat(24)).unwrap();
+        fs::write(go.join("api.go"), "x".repeat(100)).unwrap();
+        fs::write(go.join(KEPT), "api.go\n").unwrap();
+
+        assert_eq!(
+            disk_usage(&generated_dir(dir.path(), None)),
+            Usage {
+                bytes: 1131,
+                files: 4
+            }
+        );
+        assert_eq!(reclaimable(&generated_dir(dir.path(), None)), 1124);
+        assert_eq!(remove(dir.path(), Some("server/spring")).unwrap(), 1024);
+        assert!(!spring.exists());
+        assert!(go.exists());
+
+        assert_eq!(remove(dir.path(), None).unwrap(), 100);
+        assert!(!go.join("api.go").exists());
+        assert!(go.join(KEPT).exists());
+        assert_eq!(remove(dir.path(), None).unwrap(), 0);
+    }
+
+    #[test]
+    fn format_size_picks_a_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(340 * 1024 * 1024), "340 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024 / 2), "1.5 GB");
+    }
+}
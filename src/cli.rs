@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use lazyoav::config::CONFIG_FILE;
+use lazyoav::trace::{self, Target};
+
+pub const USAGE: &str = "\
+Usage: lazyoav [PATH]
+
+  PATH    Project directory, or a spec file to validate (defaults to the
+          current directory). For a spec file, the project is the nearest
+          parent directory with a .oavc, or the spec's own directory.
+
+Options:
+  --debug-log[=TARGETS]
+                   Trace pipeline events, docker invocations, parsing and
+                   UI actions to .oav/lazyoav.log. TARGETS narrows it down,
+                   e.g. docker,pipeline; LAZYOAV_LOG does the same
+  --quickfix       Print the last run's findings as file:line:col: lines
+                   for an editor's error list, and write them to
+                   .oav/quickfix.txt, without opening the TUI. Exits 1
+                   when that run failed, with fail_on applied
+  -h, --help       Print this help
+  -V, --version    Print the version
+";
+
+/// What the command line asks for.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(LaunchTarget),
+    Help,
+    Version,
+}
+
+/// Where to run: the project directory and, if a spec file was given, that
+/// spec relative to it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LaunchTarget {
+    pub root: Option<PathBuf>,
+    pub spec: Option<String>,
+    /// What `--debug-log` asked to trace, if given.
+    pub debug_log: Option<Vec<Target>>,
+    /// `--quickfix`: print the last run's findings instead of starting.
+    pub quickfix: bool,
+}
+
+pub fn parse(args: &[String]) -> Result<Command> {
+    let mut path = None;
+    let mut debug_log = None;
+    let mut quickfix = false;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--debug-log" => debug_log = Some(Target::ALL.to_vec()),
+            "--quickfix" => quickfix = true,
+            flag if flag.starts_with("--debug-log=") => {
+                debug_log = Some(trace::parse_filter(&flag["--debug-log=".len()..])?);
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                bail!("Unknown option '{flag}'\n\n{USAGE}")
+            }
+            _ if path.is_some() => bail!("Expected at most one PATH\n\n{USAGE}"),
+            _ => path = Some(arg),
+        }
+    }
+    let mut target = match path {
+        Some(path) => resolve_target(Path::new(path))?,
+        None => LaunchTarget::default(),
+    };
+    target.debug_log = debug_log;
+    target.quickfix = quickfix;
+    Ok(Command::Run(target))
+}
+
+/// Turn a directory or spec file argument into a [`LaunchTarget`].
+pub fn resolve_target(path: &Path) -> Result<LaunchTarget> {
+    let absolute = path
+        .canonicalize()
+        .with_context(|| format!("No such file or directory: {}", path.display()))?;
+
+    if absolute.is_dir() {
+        return Ok(LaunchTarget {
+            root: Some(absolute),
+            ..LaunchTarget::default()
+        });
+    }
+
+    let spec_dir = absolute
+        .parent()
+        .context("Spec file has no parent directory")?;
+    let root = spec_dir
+        .ancestors()
+        .find(|dir| dir.join(CONFIG_FILE).is_file())
+        .unwrap_or(spec_dir)
+        .to_path_buf();
+    let spec = absolute
+        .strip_prefix(&root)
+        .unwrap_or(&absolute)
+        .to_string_lossy()
+        .into_owned();
+    Ok(LaunchTarget {
+        root: Some(root),
+        spec: Some(spec),
+        debug_log: None,
+        quickfix: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn no_args_runs_in_place() {
+        assert_eq!(parse(&[]).unwrap(), Command::Run(LaunchTarget::default()));
+    }
+
+    #[test]
+    fn flags_and_errors() {
+        assert_eq!(parse(&args(&["--version"])).unwrap(), Command::Version);
+        assert_eq!(parse(&args(&["x", "-h"])).unwrap(), Command::Help);
+        assert!(parse(&args(&["--bogus"])).is_err());
+        assert!(parse(&args(&["a", "b"])).is_err());
+        assert!(parse(&args(&["/definitely/not/here"])).is_err());
+    }
+
+    #[test]
+    fn debug_log_takes_optional_targets() {
+        let Command::Run(target) = parse(&args(&["--debug-log"])).unwrap() else {
+            panic!("expected a run");
+        };
+        assert_eq!(target.debug_log.as_deref(), Some(Target::ALL));
+
+        let Command::Run(target) = parse(&args(&["--debug-log=docker"])).unwrap() else {
+            panic!("expected a run");
+        };
+        assert_eq!(target.debug_log, Some(vec![Target::Docker]));
+        assert!(parse(&args(&["--debug-log=bogus"])).is_err());
+    }
+
+    #[test]
+    fn quickfix_flag_is_kept() {
+        let Command::Run(target) = parse(&args(&["--quickfix"])).unwrap() else {
+            panic!("expected a run");
+        };
+        assert!(target.quickfix);
+    }
+
+    #[test]
+    fn directory_becomes_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = resolve_target(tmp.path()).unwrap();
+        assert_eq!(target.root, Some(tmp.path().canonicalize().unwrap()));
+        assert_eq!(target.spec, None);
+    }
+
+    #[test]
+    fn spec_file_uses_nearest_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("api/v2")).unwrap();
+        fs::write(root.join("api/v2/orders.yaml"), "openapi: 3.0.3\n").unwrap();
+
+        // Without a .oavc the spec's directory is the project.
+        let target = resolve_target(&root.join("api/v2/orders.yaml")).unwrap();
+        assert_eq!(target.root, Some(root.join("api/v2")));
+        assert_eq!(target.spec.as_deref(), Some("orders.yaml"));
+
+        fs::write(root.join(CONFIG_FILE), "mode: server\n").unwrap();
+        let target = resolve_target(&root.join("api/v2/orders.yaml")).unwrap();
+        assert_eq!(target.root, Some(root.clone()));
+        assert_eq!(target.spec.as_deref(), Some("api/v2/orders.yaml"));
+    }
+}
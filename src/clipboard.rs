@@ -0,0 +1,65 @@
+// Clipboard — copy text through the terminal with OSC 52, which reaches the
+// local clipboard over SSH too.
+
+use std::io::{self, Write};
+
+/// Copy `text` to the system clipboard of the terminal we're drawn in.
+pub fn copy(text: &str) -> io::Result<()> {
+    let tmux = std::env::var_os("TMUX").is_some();
+    let mut out = io::stdout();
+    out.write_all(sequence(text, tmux).as_bytes())?;
+    out.flush()
+}
+
+/// The OSC 52 escape sequence setting the clipboard to `text`. tmux only
+/// forwards it to the outer terminal when wrapped in a passthrough.
+fn sequence(text: &str, tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+    } else {
+        osc
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn sequence_wraps_for_tmux() {
+        assert_eq!(sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+}
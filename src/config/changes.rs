@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use super::Config;
+
+/// Settings that don't change what a validation run reports: how the TUI
+/// looks and behaves, how fast the pipeline runs, what gets kept around.
+const REPORT_NEUTRAL: &[&str] = &[
+    "accessible",
+    "auto_focus_failure",
+    "changelog_base",
+    "editor_cmd",
+    "editor_remote",
+    "generator_matrix",
+    "jobs",
+    "keys",
+    "log_retention",
+    "manage_gitignore",
+    "notify",
+    "redact",
+    "timeout_extension",
+    "workspace_specs",
+];
+
+/// Top-level `.oavc` keys whose values differ between `old` and `new`,
+/// sorted.
+pub fn changed_keys(old: &Config, new: &Config) -> Vec<String> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    let mut keys: Vec<String> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Whether changing `key` can change what a validation run reports, so a
+/// report from before the change no longer describes the current config.
+pub fn affects_report(key: &str) -> bool {
+    !REPORT_NEUTRAL.contains(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Jobs;
+
+    #[test]
+    fn changed_keys_lists_differing_settings() {
+        let old = Config::default();
+        assert!(changed_keys(&old, &old.clone()).is_empty());
+
+        let mut new = old.clone();
+        new.compile = !old.compile;
+        new.jobs = Jobs::Fixed(8);
+        new.container_env
+            .insert("JAVA_OPTS".into(), "-Xmx1g".into());
+        assert_eq!(
+            changed_keys(&old, &new),
+            ["compile", "container_env", "jobs"]
+        );
+    }
+
+    #[test]
+    fn only_pipeline_settings_affect_the_report() {
+        assert!(affects_report("compile"));
+        assert!(affects_report("spectral_ruleset"));
+        assert!(affects_report("rule_severity_overrides"));
+        assert!(!affects_report("keys"));
+        assert!(!affects_report("jobs"));
+    }
+}
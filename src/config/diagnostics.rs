@@ -0,0 +1,903 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Component, Path};
+
+use super::types::{
+    Config, FixAssistant, FixText, Format, GeneratorOptions, Linter, Mode, Naming, Notify,
+    Redaction,
+};
+use crate::custom::CustomGeneratorDef;
+use crate::docker::image::{self, ImageRef};
+use crate::generators;
+
+/// How serious a config problem is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Ignored or surprising, but the pipeline still runs as configured.
+    Warning,
+    /// The pipeline can't run as configured.
+    Error,
+}
+
+/// A single problem found in `.oavc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Config key the problem is about, e.g. `client_generators` or
+    /// `generator_options.spring`.
+    pub key: String,
+    pub message: String,
+    /// What to change to fix it, if there's a clear answer.
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            key: key.into(),
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    pub fn warning(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(key, message)
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+const FAIL_SEVERITIES: &[&str] = &["error", "warn", "info", "hint"];
+
+/// Severities accepted in `rule_severity_overrides`, in both spellings the
+/// linters print.
+const OVERRIDE_SEVERITIES: &[&str] = &["error", "warn", "warning", "info", "hint"];
+
+/// Built-in phases `hooks` can run around.
+const HOOK_PHASES: &[&str] = &[
+    "lint",
+    "security",
+    "generate",
+    "compile",
+    "test",
+    "code-lint",
+    "docs",
+];
+
+/// Report top-level and `generator_options.*` keys that `Config` doesn't know.
+///
+/// serde silently drops unknown keys, so a typo like `client_generator`
+/// would otherwise just fall back to the default.
+pub fn unknown_keys(doc: &serde_yaml::Value) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let Some(map) = doc.as_mapping() else {
+        return diagnostics;
+    };
+
+    let known = field_names(&Config::default());
+    for key in map.keys() {
+        let Some(key) = key.as_str() else {
+            diagnostics.push(Diagnostic::error(
+                format!("{key:?}"),
+                "Config keys must be strings",
+            ));
+            continue;
+        };
+        if !known.iter().any(|k| k == key) {
+            diagnostics.push(unknown_key(key.to_string(), key, &known));
+        }
+    }
+
+    if let Some(options) = map.get("generator_options").and_then(|v| v.as_mapping()) {
+        let known = field_names(&GeneratorOptions::default());
+        for (generator, opts) in options {
+            let (Some(generator), Some(opts)) = (generator.as_str(), opts.as_mapping()) else {
+                continue;
+            };
+            for key in opts.keys().filter_map(|k| k.as_str()) {
+                if !known.iter().any(|k| k == key) {
+                    diagnostics.push(unknown_key(
+                        format!("generator_options.{generator}.{key}"),
+                        key,
+                        &known,
+                    ));
+                }
+            }
+        }
+    }
+
+    let sections = [
+        ("redact", field_names(&Redaction::default())),
+        ("notify", field_names(&Notify::default())),
+        ("naming", field_names(&Naming::default())),
+        ("fix_text", field_names(&FixText::default())),
+        ("format", field_names(&Format::default())),
+        ("fix_assistant", field_names(&FixAssistant::default())),
+    ];
+    for (section, known) in sections {
+        let Some(fields) = map.get(section).and_then(|v| v.as_mapping()) else {
+            continue;
+        };
+        for key in fields.keys().filter_map(|k| k.as_str()) {
+            if !known.iter().any(|k| k == key) {
+                diagnostics.push(unknown_key(format!("{section}.{key}"), key, &known));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn unknown_key(path: String, key: &str, known: &[String]) -> Diagnostic {
+    let diagnostic = Diagnostic::warning(path, "Unknown key \u{2014} it has no effect");
+    match closest(key, known.iter().map(String::as_str)) {
+        Some(suggestion) => diagnostic.with_hint(format!("Did you mean '{suggestion}'?")),
+        None => diagnostic,
+    }
+}
+
+/// Field names of a serializable struct, in declaration order.
+fn field_names<T: serde::Serialize>(value: &T) -> Vec<String> {
+    match serde_yaml::to_value(value) {
+        Ok(serde_yaml::Value::Mapping(map)) => map
+            .keys()
+            .filter_map(|k| k.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Check a parsed config for problems that only show up once the pipeline
+/// runs: unknown or malformed generator names, generators the current `mode`
+/// skips, rulesets the lint container can't reach, and per-generator
+/// settings for generators that never run.
+///
+/// Unknown generators are warnings, not errors — they still run via bare `-g`.
+pub fn validate(root: &Path, cfg: &Config, custom_defs: &[CustomGeneratorDef]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let is_known = |name: &str, scope: &str| -> bool {
+        generators::find_builtin(name, scope).is_some()
+            || custom_defs
+                .iter()
+                .any(|d| d.name == name && d.scope == scope)
+    };
+
+    for (key, scope, names) in [
+        ("server_generators", "server", &cfg.server_generators),
+        ("client_generators", "client", &cfg.client_generators),
+    ] {
+        let mut seen = HashSet::new();
+        for name in names {
+            if !is_valid_generator_name(name) {
+                diagnostics.push(
+                    Diagnostic::error(key, format!("'{name}' is not a valid generator name"))
+                        .with_hint("Generator names use lowercase letters, digits and dashes"),
+                );
+            } else if !is_known(name, scope) {
+                let candidates: Vec<&str> = generators::builtin_generators_for_scope(scope)
+                    .iter()
+                    .map(|d| d.name)
+                    .chain(
+                        custom_defs
+                            .iter()
+                            .filter(|d| d.scope == scope)
+                            .map(|d| d.name.as_str()),
+                    )
+                    .collect();
+                let diagnostic = Diagnostic::warning(
+                    key,
+                    format!(
+                        "Unknown {scope} generator '{name}' \u{2014} no built-in or custom config available"
+                    ),
+                );
+                diagnostics.push(match closest(name, candidates) {
+                    Some(s) => diagnostic.with_hint(format!("Did you mean '{s}'?")),
+                    None => diagnostic,
+                });
+            }
+            if !seen.insert(name.as_str()) {
+                diagnostics.push(Diagnostic::warning(
+                    key,
+                    format!("'{name}' is listed more than once"),
+                ));
+            }
+        }
+    }
+
+    match cfg.mode {
+        Mode::Server if !cfg.client_generators.is_empty() => diagnostics.push(
+            Diagnostic::warning("client_generators", "Ignored because mode is server")
+                .with_hint("Set mode: both, or remove client_generators"),
+        ),
+        Mode::Client if !cfg.server_generators.is_empty() => diagnostics.push(
+            Diagnostic::warning("server_generators", "Ignored because mode is client")
+                .with_hint("Set mode: both, or remove server_generators"),
+        ),
+        _ => {}
+    }
+
+    let lints = cfg.lint && cfg.linter != Linter::None;
+    if !lints && !cfg.generate {
+        diagnostics.push(
+            Diagnostic::error("generate", "Linting and generation are both disabled")
+                .with_hint("Enable lint or generate \u{2014} there is nothing to run"),
+        );
+    } else if cfg.compile && !cfg.generate {
+        diagnostics.push(
+            Diagnostic::warning("compile", "Has no effect while generate is disabled")
+                .with_hint("Set generate: true, or compile: false"),
+        );
+    } else if cfg.test && !(cfg.generate && cfg.compile) {
+        diagnostics.push(
+            Diagnostic::warning("test", "Has no effect while compile is disabled")
+                .with_hint("Set compile: true, or test: false"),
+        );
+    }
+    if cfg.code_lint && !cfg.generate {
+        diagnostics.push(
+            Diagnostic::warning("code_lint", "Has no effect while generate is disabled")
+                .with_hint("Set generate: true, or code_lint: false"),
+        );
+    }
+
+    if lints && cfg.linter == Linter::Spectral {
+        diagnostics.extend(check_ruleset(root, &cfg.spectral_ruleset));
+        if !FAIL_SEVERITIES.contains(&cfg.spectral_fail_severity.as_str()) {
+            diagnostics.push(
+                Diagnostic::error(
+                    "spectral_fail_severity",
+                    format!(
+                        "'{}' is not a Spectral severity",
+                        cfg.spectral_fail_severity
+                    ),
+                )
+                .with_hint(format!("Use one of: {}", FAIL_SEVERITIES.join(", "))),
+            );
+        }
+    }
+
+    for (rule, severity) in &cfg.rule_severity_overrides {
+        if !OVERRIDE_SEVERITIES.contains(&severity.as_str()) {
+            diagnostics.push(
+                Diagnostic::error(
+                    format!("rule_severity_overrides.{rule}"),
+                    format!("'{severity}' is not a severity"),
+                )
+                .with_hint("Use one of: error, warn, info, hint"),
+            );
+        }
+    }
+
+    for (key, names) in [
+        (
+            "container_env",
+            cfg.container_env.keys().collect::<Vec<_>>(),
+        ),
+        ("env_passthrough", cfg.env_passthrough.iter().collect()),
+    ] {
+        for name in names {
+            if name.is_empty() || name.contains('=') || name.contains(char::is_whitespace) {
+                diagnostics.push(
+                    Diagnostic::error(key, format!("'{name}' is not a valid variable name"))
+                        .with_hint(
+                            "Use the bare name, e.g. HTTP_PROXY; set values in container_env",
+                        ),
+                );
+            }
+        }
+    }
+
+    let images = [
+        ("generator_image", &cfg.generator_image),
+        ("redocly_image", &cfg.redocly_image),
+        ("spectral_image", &cfg.spectral_image),
+    ]
+    .into_iter()
+    .chain(cfg.generator_matrix.iter().map(|i| ("generator_matrix", i)))
+    .chain(
+        cfg.custom_phases
+            .iter()
+            .map(|p| ("custom_phases", &p.image)),
+    )
+    .chain(custom_defs.iter().flat_map(|def| {
+        [&def.compile, &def.test, &def.code_lint]
+            .into_iter()
+            .flatten()
+            .map(|block| &block.image)
+            .chain([&def.generate.image])
+            .map(|image| ("custom_generators_dir", image))
+    }));
+    for (key, image) in images {
+        if let Some(digest) = ImageRef::parse(image).digest
+            && !image::is_valid_digest(digest)
+        {
+            diagnostics.push(
+                Diagnostic::error(key, format!("'{image}' has a malformed digest")).with_hint(
+                    "Pin with the full sha256:<64 hex digits> from `docker image inspect`",
+                ),
+            );
+        }
+    }
+
+    let mut phase_names = HashSet::new();
+    for phase in &cfg.custom_phases {
+        if !is_valid_phase_name(&phase.name) {
+            diagnostics.push(
+                Diagnostic::error(
+                    "custom_phases",
+                    format!("'{}' is not a valid phase name", phase.name),
+                )
+                .with_hint(
+                    "Phase names use lowercase letters, digits, dots, dashes and underscores",
+                ),
+            );
+        } else if !phase_names.insert(phase.name.as_str()) {
+            diagnostics.push(Diagnostic::error(
+                "custom_phases",
+                format!("Phase '{}' is declared more than once", phase.name),
+            ));
+        }
+        if phase.image.trim().is_empty() {
+            diagnostics.push(Diagnostic::error(
+                "custom_phases",
+                format!("Phase '{}' has no image", phase.name),
+            ));
+        }
+    }
+
+    for hook in &cfg.hooks {
+        let builtin = HOOK_PHASES.contains(&hook.phase.as_str());
+        if !builtin && !phase_names.contains(hook.phase.as_str()) {
+            let candidates = HOOK_PHASES
+                .iter()
+                .copied()
+                .chain(phase_names.iter().copied());
+            let diagnostic =
+                Diagnostic::error("hooks", format!("'{}' is not a pipeline phase", hook.phase));
+            diagnostics.push(match closest(&hook.phase, candidates) {
+                Some(s) => diagnostic.with_hint(format!("Did you mean '{s}'?")),
+                None => diagnostic.with_hint(format!(
+                    "Use one of: {}, or a custom phase's name",
+                    HOOK_PHASES.join(", ")
+                )),
+            });
+        } else if hook.generator.is_some()
+            && !matches!(
+                hook.phase.as_str(),
+                "generate" | "compile" | "test" | "code-lint"
+            )
+        {
+            diagnostics.push(Diagnostic::warning(
+                "hooks",
+                format!(
+                    "'generator' is ignored on {} hooks \u{2014} they never match",
+                    hook.phase
+                ),
+            ));
+        }
+        if hook.run.trim().is_empty() {
+            diagnostics.push(Diagnostic::error(
+                "hooks",
+                format!("A {} hook has nothing to run", hook.phase),
+            ));
+        }
+    }
+
+    if let Some(script) = &cfg.automation
+        && !root.join(script).is_file()
+    {
+        diagnostics.push(
+            Diagnostic::error("automation", format!("Script not found: {script}"))
+                .with_hint("Paths are relative to the directory containing .oavc"),
+        );
+    }
+
+    if !(1..=8).contains(&cfg.format.indent) {
+        diagnostics.push(
+            Diagnostic::error("format.indent", "Indent must be 1 to 8 spaces")
+                .with_hint("The default is 2"),
+        );
+    }
+
+    if let Some(assistant) = &cfg.fix_assistant {
+        match (&assistant.command, &assistant.endpoint) {
+            (None, None) => diagnostics.push(
+                Diagnostic::error("fix_assistant", "Neither command nor endpoint is set")
+                    .with_hint("Set one of them, or remove fix_assistant"),
+            ),
+            (Some(_), Some(_)) => diagnostics.push(Diagnostic::warning(
+                "fix_assistant",
+                "Both command and endpoint are set \u{2014} using the command",
+            )),
+            _ => {}
+        }
+        if assistant.endpoint.is_some() && assistant.model.is_none() {
+            diagnostics.push(Diagnostic::error(
+                "fix_assistant.model",
+                "The endpoint needs a model",
+            ));
+        }
+        if let Some(var) = &assistant.api_key_env
+            && std::env::var_os(var).is_none()
+        {
+            diagnostics.push(Diagnostic::warning(
+                "fix_assistant.api_key_env",
+                format!("{var} is not set"),
+            ));
+        }
+    }
+
+    let is_selected = |key: &str| -> bool {
+        let in_server = cfg.mode != Mode::Client
+            && if cfg.server_generators.is_empty() {
+                is_known(key, "server")
+            } else {
+                cfg.server_generators.iter().any(|g| g == key)
+            };
+        let in_client = cfg.mode != Mode::Server
+            && if cfg.client_generators.is_empty() {
+                is_known(key, "client")
+            } else {
+                cfg.client_generators.iter().any(|g| g == key)
+            };
+        in_server || in_client
+    };
+    let not_selected = |section: &str, key: &str| {
+        Diagnostic::warning(
+            format!("{section}.{key}"),
+            format!("'{key}' is not among the generators that run"),
+        )
+        .with_hint("Add it to server_generators or client_generators, or remove this entry")
+    };
+
+    for key in cfg.generator_config_overrides.keys() {
+        if !is_selected(key) {
+            diagnostics.push(not_selected("generator_config_overrides", key));
+        }
+    }
+
+    for (key, file) in &cfg.generator_configs {
+        if cfg.generator_config_overrides.contains_key(key) {
+            diagnostics.push(Diagnostic::warning(
+                format!("generator_configs.{key}"),
+                "Also set in generator_config_overrides \u{2014} using the override",
+            ));
+        } else if !is_selected(key) {
+            diagnostics.push(not_selected("generator_configs", key));
+        }
+        if !root.join(file).is_file() {
+            diagnostics.push(Diagnostic::error(
+                format!("generator_configs.{key}"),
+                format!("Config file not found: {file}"),
+            ));
+        }
+    }
+
+    for (key, dir) in &cfg.template_dirs {
+        if !is_selected(key) {
+            diagnostics.push(not_selected("template_dirs", key));
+        }
+        if !root.join(dir).is_dir() {
+            diagnostics.push(Diagnostic::error(
+                format!("template_dirs.{key}"),
+                format!("Template directory not found: {dir}"),
+            ));
+        }
+    }
+
+    for key in cfg.generator_options.keys() {
+        if !is_selected(key) {
+            diagnostics.push(not_selected("generator_options", key));
+        }
+    }
+
+    for spec in &cfg.workspace_specs {
+        if !crate::pipeline::workspace::is_inside_project(spec) {
+            diagnostics.push(
+                Diagnostic::error(
+                    "workspace_specs",
+                    format!("{spec} is outside the project and not visible to the containers"),
+                )
+                .with_hint("List specs relative to the directory containing .oavc"),
+            );
+        } else if !root.join(spec).is_file() {
+            diagnostics.push(Diagnostic::error(
+                "workspace_specs",
+                format!("Spec not found: {spec}"),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// The lint container only sees the project (mounted at `/work`), so a
+/// local ruleset must live inside it.
+fn check_ruleset(root: &Path, ruleset: &str) -> Option<Diagnostic> {
+    if ruleset.starts_with("http://") || ruleset.starts_with("https://") {
+        return None;
+    }
+    let rel = Path::new(ruleset);
+    let outside = if rel.is_absolute() {
+        !rel.starts_with(root)
+    } else {
+        rel.components().any(|c| c == Component::ParentDir)
+    };
+    if outside {
+        return Some(
+            Diagnostic::error(
+                "spectral_ruleset",
+                format!("{ruleset} is outside the project and not visible to the lint container"),
+            )
+            .with_hint("Move the ruleset into the project, or use a URL"),
+        );
+    }
+    if !root.join(rel).is_file() {
+        return Some(
+            Diagnostic::error("spectral_ruleset", format!("Ruleset not found: {ruleset}"))
+                .with_hint("Paths are relative to the directory containing .oavc"),
+        );
+    }
+    None
+}
+
+fn is_valid_generator_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Phase names end up in log file names, so they follow the same rules as
+/// custom generator names.
+pub fn is_valid_phase_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c))
+}
+
+/// The candidate closest to `name` by edit distance, if it's close enough
+/// to be a plausible typo.
+fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(name, c), c))
+        .filter(|(d, _)| *d <= max)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            row.push(substitute.min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_yaml(yaml: &str) -> Vec<Diagnostic> {
+        let tmp = tempfile::tempdir().unwrap();
+        let cfg: Config = serde_yaml::from_str(yaml).unwrap();
+        validate(tmp.path(), &cfg, &[])
+    }
+
+    fn keys(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.key.as_str()).collect()
+    }
+
+    #[test]
+    fn unknown_top_level_key_suggests_field() {
+        let doc = serde_yaml::from_str("spec: api.yaml\nclient_generator: [go]\n").unwrap();
+        let diagnostics = unknown_keys(&doc);
+        assert_eq!(keys(&diagnostics), vec!["client_generator"]);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].hint.as_deref(),
+            Some("Did you mean 'client_generators'?")
+        );
+    }
+
+    #[test]
+    fn unknown_generator_option_key_is_reported() {
+        let doc = serde_yaml::from_str(
+            "generator_options:\n  spring:\n    additional_properties: {}\n    global_props: {}\n",
+        )
+        .unwrap();
+        let diagnostics = unknown_keys(&doc);
+        assert_eq!(
+            keys(&diagnostics),
+            vec!["generator_options.spring.global_props"]
+        );
+    }
+
+    #[test]
+    fn unknown_redact_key_is_reported() {
+        let doc = serde_yaml::from_str("redact:\n  hosts: [corp]\n  email: false\n").unwrap();
+        let diagnostics = unknown_keys(&doc);
+        assert_eq!(keys(&diagnostics), vec!["redact.email"]);
+        assert_eq!(
+            diagnostics[0].hint.as_deref(),
+            Some("Did you mean 'emails'?")
+        );
+    }
+
+    #[test]
+    fn known_keys_are_clean() {
+        let doc = serde_yaml::to_value(Config::default()).unwrap();
+        assert!(unknown_keys(&doc).is_empty());
+    }
+
+    #[test]
+    fn default_config_validates_cleanly() {
+        assert!(validate_yaml("{}").is_empty());
+    }
+
+    #[test]
+    fn unknown_generator_suggests_builtin() {
+        let diagnostics = validate_yaml("server_generators: [sprng]\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].hint.as_deref(),
+            Some("Did you mean 'spring'?")
+        );
+    }
+
+    #[test]
+    fn malformed_generator_name_is_error() {
+        let diagnostics = validate_yaml("server_generators: [\"Spring Boot\"]\n");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn duplicate_generator_is_warned() {
+        let diagnostics = validate_yaml("server_generators: [spring, spring]\n");
+        assert!(diagnostics[0].message.contains("more than once"));
+    }
+
+    #[test]
+    fn mode_contradicting_generators_is_warned() {
+        let diagnostics = validate_yaml("mode: server\nclient_generators: [typescript-axios]\n");
+        assert_eq!(keys(&diagnostics), vec!["client_generators"]);
+    }
+
+    #[test]
+    fn nothing_to_run_is_error() {
+        let diagnostics = validate_yaml("lint: false\ngenerate: false\n");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn compile_without_generate_is_warned() {
+        let diagnostics = validate_yaml("generate: false\n");
+        assert_eq!(keys(&diagnostics), vec!["compile"]);
+    }
+
+    #[test]
+    fn test_without_compile_is_warned() {
+        let diagnostics = validate_yaml("compile: false\ntest: true\n");
+        assert_eq!(keys(&diagnostics), vec!["test"]);
+    }
+
+    #[test]
+    fn code_lint_without_generate_is_warned() {
+        let diagnostics = validate_yaml("generate: false\ncompile: false\ncode_lint: true\n");
+        assert_eq!(keys(&diagnostics), vec!["code_lint"]);
+    }
+
+    #[test]
+    fn missing_local_ruleset_is_error() {
+        let diagnostics = validate_yaml("spectral_ruleset: rules/.spectral.yml\n");
+        assert_eq!(keys(&diagnostics), vec!["spectral_ruleset"]);
+        assert!(diagnostics[0].message.contains("not found"));
+    }
+
+    #[test]
+    fn ruleset_outside_project_is_error() {
+        let diagnostics = validate_yaml("spectral_ruleset: /etc/.spectral.yml\n");
+        assert!(diagnostics[0].message.contains("outside the project"));
+    }
+
+    #[test]
+    fn local_ruleset_in_project_is_fine() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join(".spectral.yml"), "extends: spectral:oas\n").unwrap();
+        let cfg = Config {
+            spectral_ruleset: ".spectral.yml".into(),
+            ..Config::default()
+        };
+        assert!(validate(tmp.path(), &cfg, &[]).is_empty());
+    }
+
+    #[test]
+    fn unknown_override_severity_is_error() {
+        let diagnostics = validate_yaml(
+            "rule_severity_overrides:\n  info-contact: hint\n  no-empty-servers: fatal\n",
+        );
+        assert_eq!(
+            keys(&diagnostics),
+            vec!["rule_severity_overrides.no-empty-servers"]
+        );
+    }
+
+    #[test]
+    fn ruleset_is_ignored_for_redocly() {
+        assert!(validate_yaml("linter: redocly\nspectral_ruleset: missing.yml\n").is_empty());
+    }
+
+    #[test]
+    fn invalid_fail_severity_is_error() {
+        let diagnostics = validate_yaml("spectral_fail_severity: fatal\n");
+        assert_eq!(keys(&diagnostics), vec!["spectral_fail_severity"]);
+    }
+
+    #[test]
+    fn options_for_generator_skipped_by_mode_are_warned() {
+        let diagnostics =
+            validate_yaml("mode: server\ngenerator_options:\n  typescript-axios: {}\n");
+        assert_eq!(
+            keys(&diagnostics),
+            vec!["generator_options.typescript-axios"]
+        );
+    }
+
+    #[test]
+    fn template_dirs_must_exist_and_belong_to_a_generator_that_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("templates/spring")).unwrap();
+        let cfg: Config = serde_yaml::from_str(
+            "mode: server\nserver_generators: [spring]\ntemplate_dirs:\n  spring: templates/spring\n  typescript-axios: templates/ts\n",
+        )
+        .unwrap();
+        let diagnostics = validate(tmp.path(), &cfg, &[]);
+        let mut found: Vec<(&str, Severity)> = diagnostics
+            .iter()
+            .map(|d| (d.key.as_str(), d.severity))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("template_dirs.typescript-axios", Severity::Warning),
+                ("template_dirs.typescript-axios", Severity::Error),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_env_names_are_errors() {
+        let diagnostics = validate_yaml(
+            "env_passthrough: [HTTP_PROXY, \"JAVA_OPTS=-Xmx2g\"]\ncontainer_env:\n  NPM_CONFIG_REGISTRY: https://npm.example.com\n",
+        );
+        assert_eq!(keys(&diagnostics), vec!["env_passthrough"]);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn pinned_images_need_a_full_digest() {
+        let digest = format!("sha256:{}", "0f".repeat(32));
+        let diagnostics = validate_yaml(&format!(
+            "spectral_image: stoplight/spectral:6@{digest}\nredocly_image: redocly/cli@sha256:1234\n"
+        ));
+        assert_eq!(keys(&diagnostics), vec!["redocly_image"]);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn custom_generator_images_need_a_full_digest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let def = CustomGeneratorDef {
+            name: "mine".into(),
+            scope: "server".into(),
+            generate: crate::custom::GenerateBlock {
+                image: "gen:1".into(),
+                command: "gen".into(),
+            },
+            compile: Some(crate::custom::CompileBlock {
+                image: "maven:3@sha256:abc".into(),
+                command: "mvn".into(),
+            }),
+            test: None,
+            code_lint: None,
+        };
+        let diagnostics = validate(tmp.path(), &Config::default(), &[def]);
+        assert_eq!(keys(&diagnostics), vec!["custom_generators_dir"]);
+        assert!(diagnostics[0].message.contains("maven:3@sha256:abc"));
+    }
+
+    #[test]
+    fn custom_phases_need_unique_names_and_an_image() {
+        let diagnostics = validate_yaml(
+            "custom_phases:\n  - name: breaking\n    image: tufin/oasdiff\n  - name: breaking\n    image: tufin/oasdiff\n  - name: Bad Name\n    image: x\n  - name: fuzz\n    image: \"\"\n",
+        );
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Phase 'breaking' is declared more than once",
+                "'Bad Name' is not a valid phase name",
+                "Phase 'fuzz' has no image",
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_specs_must_exist_inside_the_project() {
+        let diagnostics =
+            validate_yaml("workspace_specs:\n  - ../shared/openapi.yaml\n  - pets/openapi.yaml\n");
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "../shared/openapi.yaml is outside the project and not visible to the containers",
+                "Spec not found: pets/openapi.yaml",
+            ]
+        );
+    }
+
+    #[test]
+    fn fix_assistant_needs_a_command_or_an_endpoint_with_a_model() {
+        let diagnostics = validate_yaml("fix_assistant:\n  context_lines: 5\n");
+        assert_eq!(keys(&diagnostics), vec!["fix_assistant"]);
+
+        let diagnostics = validate_yaml(
+            "fix_assistant:\n  endpoint: https://llm.example.com/v1/chat/completions\n",
+        );
+        assert_eq!(keys(&diagnostics), vec!["fix_assistant.model"]);
+
+        assert!(validate_yaml("fix_assistant:\n  command: ./suggest.sh\n").is_empty());
+    }
+
+    #[test]
+    fn hooks_need_a_known_phase_and_a_command() {
+        let diagnostics = validate_yaml(
+            "custom_phases:\n  - name: breaking\n    image: tufin/oasdiff\nhooks:\n  - phase: breaking\n    when: before\n    run: git fetch\n  - phase: generat\n    when: after\n    run: ./sync.sh\n  - phase: docs\n    when: after\n    generator: spring\n    run: \" \"\n",
+        );
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "'generat' is not a pipeline phase",
+                "'generator' is ignored on docs hooks \u{2014} they never match",
+                "A docs hook has nothing to run",
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].hint.as_deref(),
+            Some("Did you mean 'generate'?")
+        );
+    }
+
+    #[test]
+    fn closest_requires_plausible_typo() {
+        assert_eq!(closest("sprng", ["spring", "go-server"]), Some("spring"));
+        assert_eq!(closest("rust", ["spring", "go-server"]), None);
+    }
+}
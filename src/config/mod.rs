@@ -1,5 +1,14 @@
+mod changes;
+mod diagnostics;
+mod init;
 mod loader;
 mod types;
 
-pub use loader::{load, validate};
-pub use types::{Config, Jobs, Linter, Mode};
+pub use changes::{affects_report, changed_keys};
+pub use diagnostics::{Diagnostic, Severity, is_valid_phase_name, validate};
+pub use init::{InitChoices, write_init_config};
+pub use loader::{CONFIG_FILE, load, load_checked, save_generators, user_config_path};
+pub use types::{
+    Case, Config, CustomPhase, FailOn, FixAssistant, FixText, Format, GeneratorOptions, Hook,
+    HookWhen, Jobs, Linter, Mode, Naming, Notify, Quotes, Redaction, RunAfter,
+};
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use serde::de::{self, SeqAccess, Visitor};
@@ -40,6 +40,27 @@ impl Linter {
     }
 }
 
+/// Which lint findings fail the lint phase, and with it the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailOn {
+    Error,
+    /// Warnings and errors.
+    Warning,
+    /// No finding; lint only fails when the linter itself does.
+    Never,
+}
+
+impl FailOn {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailOn::Error => "error",
+            FailOn::Warning => "warning",
+            FailOn::Never => "never",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Jobs {
     Auto,
@@ -104,28 +125,435 @@ impl<'de> Deserialize<'de> for Jobs {
     }
 }
 
+/// Extra openapi-generator CLI options for a single generator.
+///
+/// ```yaml
+/// generator_options:
+///   spring:
+///     additional_properties:
+///       useSpringBoot3: true
+///     global_properties:
+///       skipFormModel: false
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratorOptions {
+    /// Passed as `--additional-properties key=value`; also visible to templates.
+    #[serde(deserialize_with = "deserialize_scalar_map")]
+    pub additional_properties: BTreeMap<String, String>,
+    /// Passed as `--global-property key=value`.
+    #[serde(deserialize_with = "deserialize_scalar_map")]
+    pub global_properties: BTreeMap<String, String>,
+    /// Passed as `--server-variables key=value` for templated server URLs.
+    #[serde(deserialize_with = "deserialize_scalar_map")]
+    pub server_variables: BTreeMap<String, String>,
+}
+
+/// What the shareable export bundle strips out of the spec, logs and reports.
+///
+/// ```yaml
+/// redact:
+///   hosts: [internal.corp]
+///   values: [ACME-4711]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Redaction {
+    /// Server URLs declared in the spec, and their host names.
+    pub server_urls: bool,
+    /// Anything that looks like an email address.
+    pub emails: bool,
+    /// Host names to hide; subdomains match too, so `internal.corp` also
+    /// covers `api.internal.corp`.
+    pub hosts: Vec<String>,
+    /// Literal strings to hide, e.g. customer names or account ids.
+    pub values: Vec<String>,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self {
+            server_urls: true,
+            emails: true,
+            hosts: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+/// A project-specific check, run in its own container as an extra phase.
+///
+/// ```yaml
+/// custom_phases:
+///   - name: naming
+///     image: ghcr.io/acme/api-naming:2
+///     args: [check, "{spec}"]
+///     after: lint
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomPhase {
+    pub name: String,
+    pub image: String,
+    /// Arguments after the image. `{spec}`, `{work}` and `{generated}` are
+    /// replaced with the spec, project and generated output paths inside
+    /// the container.
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub after: RunAfter,
+}
+
+/// The built-in phase a custom phase runs after. It runs whether or not
+/// that phase passed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunAfter {
+    /// After lint and security, alongside generation.
+    Lint,
+    Generate,
+    /// After compile, and the tests if they run.
+    Compile,
+    /// Last, after every built-in phase.
+    #[default]
+    All,
+}
+
+/// A shell command run on the host before or after a pipeline step, from
+/// the project directory.
+///
+/// ```yaml
+/// hooks:
+///   - phase: generate
+///     when: after
+///     generator: spring
+///     run: rsync -a "$OAV_GENERATED/" ../service/generated/
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hook {
+    /// `lint`, `security`, `generate`, `compile`, `test`, `docs`, or the
+    /// name of a custom phase.
+    pub phase: String,
+    pub when: HookWhen,
+    /// Only run around this generator's steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    pub run: String,
+}
+
+/// Which side of its step a hook runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookWhen {
+    /// Before the step's container; if the hook fails, the step fails
+    /// without running.
+    Before,
+    /// After the step passed; if the hook fails, so does the step.
+    After,
+}
+
+/// How to signal that a validation run has finished. Both are off by default.
+///
+/// ```yaml
+/// notify:
+///   bell: true
+///   desktop: true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Notify {
+    /// Ring the terminal bell.
+    pub bell: bool,
+    /// Send a desktop notification with the result and error count.
+    pub desktop: bool,
+}
+
+/// Naming conventions checked locally after each run; each is off unless
+/// set. Findings carry a fix renaming the definition and its references.
+///
+/// ```yaml
+/// naming:
+///   schemas: pascal
+///   properties: camel
+///   paths: kebab
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Naming {
+    /// Names under `components/schemas`.
+    pub schemas: Option<Case>,
+    /// Keys of every `properties` map.
+    pub properties: Option<Case>,
+    /// Path segments other than `{parameters}`.
+    pub paths: Option<Case>,
+}
+
+/// A naming convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    /// `PetOwner`
+    Pascal,
+    /// `petOwner`
+    Camel,
+    /// `pet_owner`
+    Snake,
+    /// `pet-owner`
+    Kebab,
+}
+
+impl Case {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pascal => "PascalCase",
+            Self::Camel => "camelCase",
+            Self::Snake => "snake_case",
+            Self::Kebab => "kebab-case",
+        }
+    }
+}
+
+/// House style for the summaries and descriptions the `operation-summary`
+/// and `operation-description` fixes write. `{summary}` and
+/// `{description}` are the derived text; `{method}`, `{path}`,
+/// `{operation_id}`, `{tag}` and `{resource}` describe the operation.
+///
+/// ```yaml
+/// fix_text:
+///   summary: "{tag}: {summary}"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FixText {
+    pub summary: String,
+    pub description: String,
+}
+
+impl Default for FixText {
+    fn default() -> Self {
+        Self {
+            summary: "{summary}".to_string(),
+            description: "{description}".to_string(),
+        }
+    }
+}
+
+/// Canonical layout the format action rewrites the spec to.
+///
+/// ```yaml
+/// format:
+///   indent: 4
+///   quotes: double
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Format {
+    /// Spaces per nesting level.
+    pub indent: usize,
+    /// Quote style for strings that need quoting in YAML.
+    pub quotes: Quotes,
+    /// Order of the top-level keys; keys not listed follow in their
+    /// original order.
+    pub key_order: Vec<String>,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            quotes: Quotes::Single,
+            key_order: [
+                "openapi",
+                "info",
+                "jsonSchemaDialect",
+                "servers",
+                "security",
+                "tags",
+                "paths",
+                "webhooks",
+                "components",
+                "externalDocs",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+/// How the format action quotes YAML strings that need it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quotes {
+    Single,
+    Double,
+}
+
+/// A language model asked for a fix when neither a built-in rule nor a
+/// plugin has one. Off unless set; give either `command` or `endpoint`.
+///
+/// ```yaml
+/// fix_assistant:
+///   endpoint: https://api.openai.com/v1/chat/completions
+///   model: gpt-4o-mini
+///   api_key_env: OPENAI_API_KEY
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FixAssistant {
+    /// A shell command, run from the project directory, reading the request
+    /// as JSON on stdin and printing its answer on stdout.
+    pub command: Option<String>,
+    /// An OpenAI-compatible chat completions URL.
+    pub endpoint: Option<String>,
+    pub model: Option<String>,
+    /// Environment variable holding the endpoint's API key.
+    pub api_key_env: Option<String>,
+    /// Spec lines sent on each side of the finding.
+    pub context_lines: usize,
+    /// Seconds to wait for a suggestion.
+    pub timeout: u64,
+}
+
+impl Default for FixAssistant {
+    fn default() -> Self {
+        Self {
+            command: None,
+            endpoint: None,
+            model: None,
+            api_key_env: None,
+            context_lines: 20,
+            timeout: 60,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub spec: Option<String>,
     pub mode: Mode,
+    /// Parse the spec and check it against the OpenAPI schema before any
+    /// container starts; a spec that fails skips the other phases.
+    pub structure: bool,
     pub lint: bool,
     pub generate: bool,
     pub compile: bool,
+    /// Run each generated project's test suite after it compiled.
+    pub test: bool,
+    /// Run the language's linters over each generated project after it
+    /// compiled: checkstyle and SpotBugs, tsc and ESLint, golangci-lint.
+    pub code_lint: bool,
+    /// Build HTML docs from the spec with Redoc after the other phases.
+    pub docs: bool,
+    /// Extra phases running project-specific checks.
+    pub custom_phases: Vec<CustomPhase>,
+    /// Host commands run around pipeline steps.
+    pub hooks: Vec<Hook>,
+    /// A Rhai script (relative to the project root) whose `on_run_complete`
+    /// and `on_fix_applied` functions run after those events.
+    pub automation: Option<String>,
+    /// Ask a language model for fixes no rule has; sends the finding and
+    /// the spec lines around it.
+    pub fix_assistant: Option<FixAssistant>,
+    /// Run Spectral with `security_ruleset` as a separate phase after lint.
+    pub security: bool,
     pub linter: Linter,
     pub server_generators: Vec<String>,
     pub client_generators: Vec<String>,
     pub generator_config_overrides: HashMap<String, String>,
+    /// Host config files (relative to the project root or absolute), mounted
+    /// into the generator container and passed via `-c`.
+    pub generator_configs: HashMap<String, String>,
+    /// Host directories of customized mustache templates (relative to the
+    /// project root or absolute), mounted into the generator container and
+    /// passed via `-t`.
+    pub template_dirs: HashMap<String, String>,
+    pub generator_options: HashMap<String, GeneratorOptions>,
+    /// Environment variables set in every container, e.g. `JAVA_OPTS`.
+    #[serde(deserialize_with = "deserialize_scalar_map")]
+    pub container_env: BTreeMap<String, String>,
+    /// Host environment variables copied into every container when set,
+    /// e.g. `HTTP_PROXY`.
+    pub env_passthrough: Vec<String>,
+    /// Forward the host's proxy variables (`HTTP_PROXY`, `NO_PROXY`, ...)
+    /// into every container.
+    pub forward_proxy: bool,
+    /// Proxies for ruleset downloads and containers, overriding the host's
+    /// `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY`.
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    /// Never fetch remote rulesets; lint with the copies cached under
+    /// `.oav/cache/rulesets/` by earlier runs.
+    pub offline: bool,
+    /// Plain-text UI for screen readers and monochrome terminals: ASCII
+    /// icons, text markers for focus and selection, and no colour. Also on
+    /// when `NO_COLOR` is set.
+    pub accessible: bool,
     pub generator_image: String,
+    /// openapi-generator tags (or full image references) to run side by
+    /// side in the version matrix, e.g. `[v7.10.0, v7.17.0]`.
+    pub generator_matrix: Vec<String>,
+    /// Specs checked together by the workspace run, relative to the
+    /// project. Empty: every spec found within `search_depth`.
+    pub workspace_specs: Vec<String>,
     pub redocly_image: String,
     pub spectral_image: String,
     pub spectral_ruleset: String,
     pub spectral_fail_severity: String,
+    /// OWASP-oriented ruleset for the security phase.
+    pub security_ruleset: String,
+    /// Severity to report a lint rule's findings at, by rule id, e.g.
+    /// `info-contact: hint`. Applied before findings are shown and counted.
+    pub rule_severity_overrides: BTreeMap<String, String>,
+    /// Which findings fail the lint phase and the run: `error`, `warning`
+    /// or `never`. Unset, `spectral_fail_severity` decides for Spectral and
+    /// errors fail Redocly.
+    pub fail_on: Option<FailOn>,
+    /// `fail_on` for single rules, by rule id, e.g. `operation-tags: never`.
+    pub fail_on_rules: BTreeMap<String, FailOn>,
     pub custom_generators_dir: Option<String>,
     pub docker_timeout: u64,
+    /// Seconds lint and security steps may run; `docker_timeout` if unset.
+    pub lint_timeout: Option<u64>,
+    /// Seconds generate steps may run; `docker_timeout` if unset.
+    pub generate_timeout: Option<u64>,
+    /// Seconds compile and test steps may run; `docker_timeout` if unset.
+    pub compile_timeout: Option<u64>,
+    /// Seconds each hook may run before it and what it started are killed.
+    pub hook_timeout: u64,
+    /// Seconds added when accepting the "about to time out" prompt.
+    pub timeout_extension: u64,
+    /// Extra attempts for steps that fail with a transient error (0 = no retry).
+    pub retries: u32,
+    /// Base delay in seconds before a retry; doubles on each further attempt.
+    pub retry_backoff: u64,
+    /// Runs whose full step logs are kept in `.oav/logs/` (0 = don't keep).
+    pub log_retention: usize,
     pub search_depth: usize,
     pub jobs: Jobs,
     pub manage_gitignore: bool,
+    /// After a failed run, select the first failing phase and its first
+    /// error, and focus the Errors panel.
+    pub auto_focus_failure: bool,
+    /// Command to open the spec in, e.g. `nvim +{line} {file}`; `{col}` is
+    /// also filled in. Defaults to `$VISUAL`/`$EDITOR`.
+    pub editor_cmd: Option<String>,
+    /// When running inside Neovim's or VS Code's terminal, jump that editor
+    /// to the error instead of opening a new one.
+    pub editor_remote: bool,
+    /// Git revision the API changelog compares the spec against, e.g. a
+    /// release tag.
+    pub changelog_base: String,
+    /// Redaction rules for the shareable export bundle.
+    pub redact: Redaction,
+    /// Notifications when a validation run completes.
+    pub notify: Notify,
+    /// Templates for the text the summary and description fixes write.
+    pub fix_text: FixText,
+    /// Naming conventions for schemas, properties and paths.
+    pub naming: Naming,
+    /// Layout for the format action.
+    pub format: Format,
     #[serde(default, deserialize_with = "deserialize_keys")]
     pub keys: HashMap<String, Vec<String>>,
 }
@@ -168,6 +596,56 @@ where
     deserializer.deserialize_map(KeysVisitor)
 }
 
+/// Accept strings, booleans and numbers as map values, keeping their text form.
+///
+/// Generator options are flag values, so `useSpringBoot3: true` and
+/// `useSpringBoot3: "true"` should mean the same thing.
+fn deserialize_scalar_map<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = BTreeMap::<String, ScalarString>::deserialize(deserializer)?;
+    Ok(map.into_iter().map(|(k, v)| (k, v.0)).collect())
+}
+
+struct ScalarString(String);
+
+impl<'de> Deserialize<'de> for ScalarString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ScalarVisitor;
+
+        impl<'de> Visitor<'de> for ScalarVisitor {
+            type Value = ScalarString;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string, boolean or number")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<ScalarString, E> {
+                Ok(ScalarString(value.to_owned()))
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<ScalarString, E> {
+                Ok(ScalarString(value.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<ScalarString, E> {
+                Ok(ScalarString(value.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<ScalarString, E> {
+                Ok(ScalarString(value.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<ScalarString, E> {
+                Ok(ScalarString(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(ScalarVisitor)
+    }
+}
+
 #[derive(Debug)]
 enum StringOrVec {
     Single(String),
@@ -216,25 +694,69 @@ impl Default for Config {
         Self {
             spec: None,
             mode: Mode::Server,
+            structure: true,
             lint: true,
             generate: true,
             compile: true,
+            test: false,
+            code_lint: false,
+            docs: false,
+            custom_phases: Vec::new(),
+            hooks: Vec::new(),
+            automation: None,
+            fix_assistant: None,
+            security: false,
             linter: Linter::Spectral,
             server_generators: Vec::new(),
             client_generators: Vec::new(),
             generator_config_overrides: HashMap::new(),
+            generator_configs: HashMap::new(),
+            template_dirs: HashMap::new(),
+            generator_options: HashMap::new(),
+            container_env: BTreeMap::new(),
+            env_passthrough: Vec::new(),
+            forward_proxy: true,
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: None,
+            offline: false,
+            accessible: false,
             custom_generators_dir: None,
             generator_image: "openapitools/openapi-generator-cli:v7.17.0".to_string(),
+            generator_matrix: Vec::new(),
+            workspace_specs: Vec::new(),
             redocly_image: "redocly/cli:1.25.5".to_string(),
             spectral_image: "stoplight/spectral:6".to_string(),
             spectral_ruleset:
                 "https://raw.githubusercontent.com/entur/api-guidelines/refs/tags/v2/.spectral.yml"
                     .to_string(),
             spectral_fail_severity: "error".to_string(),
+            security_ruleset:
+                "https://unpkg.com/@stoplight/spectral-owasp-ruleset/dist/ruleset.mjs".to_string(),
+            rule_severity_overrides: BTreeMap::new(),
+            fail_on: None,
+            fail_on_rules: BTreeMap::new(),
             docker_timeout: 300,
+            lint_timeout: None,
+            generate_timeout: None,
+            compile_timeout: None,
+            hook_timeout: 120,
+            timeout_extension: 300,
+            retries: 0,
+            retry_backoff: 2,
+            log_retention: 10,
             search_depth: 4,
             jobs: Jobs::Auto,
             manage_gitignore: true,
+            auto_focus_failure: true,
+            editor_cmd: None,
+            editor_remote: true,
+            changelog_base: "HEAD".to_string(),
+            redact: Redaction::default(),
+            notify: Notify::default(),
+            fix_text: FixText::default(),
+            naming: Naming::default(),
+            format: Format::default(),
             keys: HashMap::new(),
         }
     }
@@ -298,6 +820,61 @@ mod tests {
         assert_eq!(cfg.keys["scroll_up"], vec!["n"]);
     }
 
+    #[test]
+    fn retries_default_to_disabled() {
+        let cfg = parse_config("spec: api.yaml\n");
+        assert_eq!(cfg.retries, 0);
+        assert_eq!(cfg.retry_backoff, 2);
+    }
+
+    #[test]
+    fn retries_parse_from_yaml() {
+        let cfg = parse_config("retries: 3\nretry_backoff: 5\n");
+        assert_eq!(cfg.retries, 3);
+        assert_eq!(cfg.retry_backoff, 5);
+    }
+
+    #[test]
+    fn generator_options_accept_scalar_values() {
+        let cfg = parse_config(
+            "generator_options:\n  spring:\n    additional_properties:\n      useSpringBoot3: true\n      interfaceOnly: \"false\"\n      maxLength: 80\n",
+        );
+        let props = &cfg.generator_options["spring"].additional_properties;
+        assert_eq!(props["useSpringBoot3"], "true");
+        assert_eq!(props["interfaceOnly"], "false");
+        assert_eq!(props["maxLength"], "80");
+        assert!(cfg.generator_options["spring"].global_properties.is_empty());
+    }
+
+    #[test]
+    fn generator_options_reject_nested_values() {
+        let result = serde_yaml::from_str::<Config>(
+            "generator_options:\n  spring:\n    global_properties:\n      models: [a, b]\n",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn container_env_accepts_scalar_values() {
+        let cfg = parse_config(
+            "container_env:\n  JAVA_OPTS: -Xmx2g\n  NODE_TLS_REJECT_UNAUTHORIZED: 0\nenv_passthrough: [HTTP_PROXY]\n",
+        );
+        assert_eq!(cfg.container_env["JAVA_OPTS"], "-Xmx2g");
+        assert_eq!(cfg.container_env["NODE_TLS_REJECT_UNAUTHORIZED"], "0");
+        assert_eq!(cfg.env_passthrough, vec!["HTTP_PROXY"]);
+    }
+
+    #[test]
+    fn fail_on_parses_globally_and_per_rule() {
+        let cfg = parse_config("spec: api.yaml\n");
+        assert_eq!(cfg.fail_on, None);
+
+        let cfg = parse_config("fail_on: warning\nfail_on_rules:\n  operation-tags: never\n");
+        assert_eq!(cfg.fail_on, Some(FailOn::Warning));
+        assert_eq!(cfg.fail_on_rules["operation-tags"], FailOn::Never);
+        assert!(serde_yaml::from_str::<Config>("fail_on: info\n").is_err());
+    }
+
     #[test]
     fn keys_integer_value_is_rejected() {
         let result = serde_yaml::from_str::<Config>("keys:\n  scroll_down: 42\n");
@@ -13,6 +13,11 @@ pub struct CustomGeneratorDef {
     pub scope: String,
     pub generate: GenerateBlock,
     pub compile: Option<CompileBlock>,
+    /// Runs the generated project's tests, in its output directory like
+    /// `compile`.
+    pub test: Option<CompileBlock>,
+    /// Lints the generated project, in its output directory like `compile`.
+    pub code_lint: Option<CompileBlock>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -105,13 +110,21 @@ fn validate_def(def: &CustomGeneratorDef, path: &Path) -> Result<()> {
             def.name
         );
     }
-    if let Some(compile) = &def.compile {
-        if compile.image.trim().is_empty() {
-            bail!("custom generator '{}' has an empty compile.image", def.name);
+    let blocks = [
+        ("compile", &def.compile),
+        ("test", &def.test),
+        ("code_lint", &def.code_lint),
+    ];
+    for (step, block) in blocks {
+        let Some(block) = block else {
+            continue;
+        };
+        if block.image.trim().is_empty() {
+            bail!("custom generator '{}' has an empty {step}.image", def.name);
         }
-        if compile.command.trim().is_empty() {
+        if block.command.trim().is_empty() {
             bail!(
-                "custom generator '{}' has an empty compile.command",
+                "custom generator '{}' has an empty {step}.command",
                 def.name
             );
         }
@@ -258,7 +271,7 @@ mod tests {
     }
 
     #[test]
-    fn with_compile_block() {
+    fn with_compile_and_test_blocks() {
         let tmp = tempfile::tempdir().unwrap();
         let custom = tmp.path().join("generators");
         fs::create_dir(&custom).unwrap();
@@ -271,12 +284,16 @@ generate:
 compile:
   image: build:latest
   command: npm run build
+test:
+  image: build:latest
+  command: npm test
 ";
         fs::write(custom.join("my-gen.yaml"), yaml).unwrap();
 
         let defs = load(tmp.path(), "generators").unwrap();
         assert!(defs[0].compile.is_some());
         assert_eq!(defs[0].compile.as_ref().unwrap().image, "build:latest");
+        assert_eq!(defs[0].test.as_ref().unwrap().command, "npm test");
     }
 
     #[test]
@@ -290,6 +307,8 @@ compile:
                     command: "c".into(),
                 },
                 compile: None,
+                test: None,
+                code_lint: None,
             },
             CustomGeneratorDef {
                 name: "b".into(),
@@ -299,6 +318,8 @@ compile:
                     command: "c".into(),
                 },
                 compile: None,
+                test: None,
+                code_lint: None,
             },
         ];
         assert_eq!(server_names(&defs), vec!["a"]);
@@ -2,6 +2,8 @@ use std::process::Command;
 
 use anyhow::{Context, Result, bail};
 
+use super::image::named_digest;
+
 /// Verify that the Docker daemon and Compose plugin are reachable.
 pub fn ensure_available() -> Result<()> {
     let status = Command::new("docker")
@@ -32,8 +34,62 @@ pub fn ensure_available() -> Result<()> {
     Ok(())
 }
 
+/// Docker client and server versions (`client 27.0.1 / server 27.0.1`),
+/// or `None` if the daemon can't be reached.
+pub fn version() -> Option<String> {
+    let output = Command::new("docker")
+        .args([
+            "version",
+            "--format",
+            "client {{.Client.Version}} / server {{.Server.Version}}",
+        ])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resolve a local image reference to its registry digest
+/// (`name@sha256:...`), so a run can be repeated with the exact same image.
+/// Of an image pulled under several names, the digest of the one `image`
+/// names wins.
+///
+/// Returns `None` if the image isn't present locally or was built rather
+/// than pulled (no repo digest).
+pub fn image_digest(image: &str) -> Option<String> {
+    named_digest(image, &repo_digests(image)).cloned()
+}
+
+/// Every registry digest (`name@sha256:...`) the local copy of `image`
+/// was pulled under; empty if it isn't present or was built locally.
+pub fn repo_digests(image: &str) -> Vec<String> {
+    let output = Command::new("docker")
+        .args([
+            "image",
+            "inspect",
+            "--format",
+            "{{range .RepoDigests}}{{println .}}{{end}}",
+            image,
+        ])
+        .stderr(std::process::Stdio::null())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.contains("@sha256:"))
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Returns `["--user", "uid:gid"]` on Unix so containers write files
-/// as the invoking user. Empty on other platforms.
+/// as the invoking user. Empty on Windows, where Docker Desktop gives bind
+/// mounted files to the host user itself and there is no uid to pass.
 pub fn user_args() -> Vec<String> {
     #[cfg(unix)]
     {
@@ -0,0 +1,106 @@
+// Image references: `repository[:tag][@sha256:digest]`, as docker takes them.
+
+/// An image reference split into its parts. A registry port
+/// (`mirror:5000/cli`) stays part of the repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageRef<'a> {
+    pub repository: &'a str,
+    pub tag: Option<&'a str>,
+    /// `sha256:...`, when the reference is pinned.
+    pub digest: Option<&'a str>,
+}
+
+impl<'a> ImageRef<'a> {
+    pub fn parse(reference: &'a str) -> Self {
+        let (name, digest) = match reference.split_once('@') {
+            Some((name, digest)) => (name, Some(digest)),
+            None => (reference, None),
+        };
+        let (repository, tag) = match name.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag)),
+            _ => (name, None),
+        };
+        Self {
+            repository,
+            tag,
+            digest,
+        }
+    }
+
+    /// Pinned to a digest, so it can't change under the same reference.
+    pub fn is_pinned(&self) -> bool {
+        self.digest.is_some()
+    }
+}
+
+/// Whether `digest` is a well-formed `sha256:` digest.
+pub fn is_valid_digest(digest: &str) -> bool {
+    digest
+        .strip_prefix("sha256:")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Of the repo digests an image was pulled under, the one for the
+/// repository `image` names, or else the first.
+pub fn named_digest<'d>(image: &str, repo_digests: &'d [String]) -> Option<&'d String> {
+    let repository = ImageRef::parse(image).repository;
+    repo_digests
+        .iter()
+        .find(|digest| ImageRef::parse(digest).repository == repository)
+        .or(repo_digests.first())
+}
+
+/// The digest part of a `name@sha256:...` repo digest.
+pub fn digest_of(repo_digest: &str) -> &str {
+    repo_digest
+        .split_once('@')
+        .map_or(repo_digest, |(_, digest)| digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:4f53cda18c2baa0c0354bb5f9a3ecbe5ed12ab4d8e11ba873c2f11161202b945";
+
+    #[test]
+    fn parse_splits_repository_tag_and_digest() {
+        let tagged = ImageRef::parse("mirror.example.com:5000/openapi-generator-cli:v7.17.0");
+        assert_eq!(
+            tagged.repository,
+            "mirror.example.com:5000/openapi-generator-cli"
+        );
+        assert_eq!((tagged.tag, tagged.digest), (Some("v7.17.0"), None));
+        assert!(!tagged.is_pinned());
+
+        let reference = format!("stoplight/spectral:6@{DIGEST}");
+        let pinned = ImageRef::parse(&reference);
+        assert_eq!(pinned.repository, "stoplight/spectral");
+        assert_eq!((pinned.tag, pinned.digest), (Some("6"), Some(DIGEST)));
+
+        let reference = format!("mirror:5000/spectral@{DIGEST}");
+        let bare = ImageRef::parse(&reference);
+        assert_eq!((bare.repository, bare.tag), ("mirror:5000/spectral", None));
+        assert!(bare.is_pinned());
+    }
+
+    #[test]
+    fn digests_must_be_full_sha256() {
+        assert!(is_valid_digest(DIGEST));
+        assert!(!is_valid_digest("sha256:4f53cd"));
+        assert!(!is_valid_digest(&DIGEST.replace("sha256", "md5")));
+        assert_eq!(digest_of(&format!("redocly/cli@{DIGEST}")), DIGEST);
+    }
+
+    #[test]
+    fn named_digest_prefers_the_repository_asked_for() {
+        let digests = vec![
+            format!("mirror:5000/spectral@{DIGEST}"),
+            format!("stoplight/spectral@{DIGEST}"),
+        ];
+        let named = named_digest("stoplight/spectral:6", &digests);
+        assert_eq!(named, Some(&digests[1]));
+        assert_eq!(named_digest("spectral:local", &digests), Some(&digests[0]));
+        assert_eq!(named_digest("spectral:local", &[]), None);
+    }
+}
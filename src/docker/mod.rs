@@ -1,9 +1,18 @@
 // Docker orchestration — container management, streaming output, cancellation.
 
 pub mod engine;
+pub mod image;
+pub mod mount;
+pub mod proxy;
 pub mod run;
 pub mod types;
 
-pub use engine::{ensure_available, user_args};
-pub use run::spawn;
-pub use types::{CancelToken, ContainerCommand, ContainerResult, OutputLine};
+pub use engine::{ensure_available, image_digest, repo_digests, user_args};
+pub use image::ImageRef;
+pub use mount::{host_path, volume};
+pub use proxy::{check_reachable, host_proxy_vars, proxy_overrides};
+pub use run::{
+    container_owner, project_containers, project_label, redacted_args, remote_daemon,
+    remove_container, spawn, spawn_controlled,
+};
+pub use types::{CancelToken, ContainerCommand, ContainerControl, ContainerResult, OutputLine};
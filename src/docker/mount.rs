@@ -0,0 +1,68 @@
+use std::path::Path;
+
+/// A `-v` bind mount of host directory or file `host` at `target`.
+pub fn volume(host: &Path, target: &str) -> String {
+    format!("{}:{target}", host_path(host))
+}
+
+/// `path` as the Docker daemon expects a bind mount source on this
+/// platform. Unix paths pass through; Windows paths get the Docker Desktop
+/// treatment of [`windows_host_path`].
+pub fn host_path(path: &Path) -> String {
+    if cfg!(windows) {
+        windows_host_path(&path.to_string_lossy())
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Translate a Windows path for Docker Desktop:
+///
+/// - `C:\Users\me\api` becomes `C:/Users/me/api`, which Docker Desktop
+///   accepts and which doesn't trip `-v`'s `:` splitting on the backslashes.
+/// - UNC paths keep their host and share with forward slashes, so files in
+///   a WSL distro, `\\wsl$\Ubuntu\home\me\api`, become
+///   `//wsl$/Ubuntu/home/me/api`: the distro name says whose `/home` it is.
+/// - The `\\?\` prefix of canonicalized paths is dropped.
+fn windows_host_path(raw: &str) -> String {
+    let raw = match raw.strip_prefix(r"\\?\UNC\") {
+        Some(unc) => format!(r"\\{unc}"),
+        None => raw.strip_prefix(r"\\?\").unwrap_or(raw).to_string(),
+    };
+    raw.replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_paths_follow_docker_desktop_conventions() {
+        assert_eq!(
+            windows_host_path(r"C:\Users\me\my api"),
+            "C:/Users/me/my api"
+        );
+        assert_eq!(windows_host_path(r"\\?\D:\work\api"), "D:/work/api");
+        assert_eq!(
+            windows_host_path(r"\\wsl$\Ubuntu\home\me\api"),
+            "//wsl$/Ubuntu/home/me/api"
+        );
+        assert_eq!(
+            windows_host_path(r"\\?\UNC\wsl.localhost\Ubuntu-22.04\home\me"),
+            "//wsl.localhost/Ubuntu-22.04/home/me"
+        );
+        assert_eq!(
+            windows_host_path(r"\\fileserver\share\api"),
+            "//fileserver/share/api"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_paths_pass_through() {
+        assert_eq!(
+            volume(Path::new("/home/me/my api"), "/work"),
+            "/home/me/my api:/work"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+
+/// Proxy variables forwarded into containers when set on the host.
+pub const PROXY_VARS: &[&str] = &[
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "no_proxy",
+    "all_proxy",
+];
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Names of the proxy variables set (and non-empty) in the host environment.
+pub fn host_proxy_vars() -> Vec<String> {
+    PROXY_VARS
+        .iter()
+        .filter(|name| env_lookup(name).is_some())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Proxy variables, in both spellings, for proxies configured rather than
+/// taken from the environment. Unset and empty ones are left out.
+pub fn proxy_overrides(
+    http: Option<&str>,
+    https: Option<&str>,
+    no_proxy: Option<&str>,
+) -> Vec<(String, String)> {
+    [
+        ("HTTP_PROXY", http),
+        ("HTTPS_PROXY", https),
+        ("NO_PROXY", no_proxy),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name, value.filter(|v| !v.is_empty())?)))
+    .flat_map(|(name, value)| {
+        [
+            (name.to_string(), value.to_string()),
+            (name.to_lowercase(), value.to_string()),
+        ]
+    })
+    .collect()
+}
+
+/// Check that `url` can be reached from the host, through the configured
+/// proxy if there is one. `overrides` (from [`proxy_overrides`]) take
+/// precedence over the environment.
+///
+/// Only opens a TCP connection (to the proxy, or to the target directly);
+/// the error message says what to change.
+pub fn check_reachable(url: &str, overrides: &[(String, String)]) -> Result<()> {
+    let target = Endpoint::parse(url).ok_or_else(|| anyhow!("Not a valid URL: {url}"))?;
+    let https = url.starts_with("https://");
+    let lookup = |name: &str| {
+        overrides
+            .iter()
+            .find(|(var, _)| var == name)
+            .map(|(_, value)| value.clone())
+            .or_else(|| env_lookup(name))
+    };
+
+    match proxy_for(&target.host, https, lookup) {
+        Some((var, proxy)) => {
+            let endpoint = Endpoint::parse(&proxy)
+                .ok_or_else(|| anyhow!("{var} is set to '{proxy}', which is not a valid URL"))?;
+            endpoint.connect().with_context(|| {
+                format!(
+                    "Proxy {}:{} (from {var}) is unreachable \u{2014} check the proxy settings",
+                    endpoint.host, endpoint.port
+                )
+            })
+        }
+        None => target.connect().with_context(|| {
+            format!(
+                "Cannot reach {}:{} \u{2014} behind a proxy? Set HTTPS_PROXY or https_proxy in .oavc, or use a local spectral_ruleset",
+                target.host, target.port
+            )
+        }),
+    }
+}
+
+fn env_lookup(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// The proxy variable and value that apply to `host`, honouring `NO_PROXY`.
+fn proxy_for(
+    host: &str,
+    https: bool,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<(&'static str, String)> {
+    let no_proxy = lookup("NO_PROXY").or_else(|| lookup("no_proxy"));
+    if let Some(list) = no_proxy
+        && list
+            .split(',')
+            .map(str::trim)
+            .any(|entry| bypasses(entry, host))
+    {
+        return None;
+    }
+
+    let candidates: &[&'static str] = if https {
+        &["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+    } else {
+        &["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"]
+    };
+    candidates
+        .iter()
+        .find_map(|var| lookup(var).map(|value| (*var, value)))
+}
+
+fn bypasses(entry: &str, host: &str) -> bool {
+    let entry = entry.trim_start_matches('*').trim_start_matches('.');
+    if entry.is_empty() {
+        // A bare `*` disables the proxy for everything.
+        return true;
+    }
+    host == entry || host.ends_with(&format!(".{entry}"))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Endpoint {
+    host: String,
+    port: u16,
+}
+
+impl Endpoint {
+    /// Parse the host and port out of a URL. Proxy variables are often set
+    /// without a scheme (`proxy.corp:3128`), which is treated as `http://`.
+    fn parse(url: &str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+        let default_port = match scheme {
+            "https" => 443,
+            "http" => 80,
+            s if s.starts_with("socks") => 1080,
+            _ => return None,
+        };
+
+        let authority = rest.split(['/', '?', '#']).next()?;
+        let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+        let (host, port) = if let Some(v6) = authority.strip_prefix('[') {
+            let (host, after) = v6.split_once(']')?;
+            (host, after.strip_prefix(':'))
+        } else {
+            match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        if host.is_empty() {
+            return None;
+        }
+        let port = match port {
+            Some(p) => p.parse().ok()?,
+            None => default_port,
+        };
+        Some(Self {
+            host: host.to_string(),
+            port,
+        })
+    }
+
+    fn connect(&self) -> Result<()> {
+        let addrs = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .with_context(|| format!("Failed to resolve {}", self.host))?;
+        let mut last_err = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                Ok(_) => return Ok(()),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(match last_err {
+            Some(e) => anyhow!(e),
+            None => anyhow!("No addresses for {}", self.host),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn parse_endpoint_variants() {
+        let ep = |host: &str, port| Endpoint {
+            host: host.into(),
+            port,
+        };
+        assert_eq!(
+            Endpoint::parse("https://raw.githubusercontent.com/x/.spectral.yml"),
+            Some(ep("raw.githubusercontent.com", 443))
+        );
+        assert_eq!(
+            Endpoint::parse("proxy.corp:3128"),
+            Some(ep("proxy.corp", 3128))
+        );
+        assert_eq!(
+            Endpoint::parse("http://user:pw@proxy.corp"),
+            Some(ep("proxy.corp", 80))
+        );
+        assert_eq!(Endpoint::parse("http://[::1]:8080/"), Some(ep("::1", 8080)));
+        assert_eq!(Endpoint::parse("ftp://host"), None);
+        assert_eq!(Endpoint::parse("http://host:port"), None);
+    }
+
+    #[test]
+    fn proxy_for_picks_scheme_specific_var() {
+        let lookup = env(&[
+            ("HTTP_PROXY", "http://plain:80"),
+            ("https_proxy", "http://secure:3128"),
+        ]);
+        assert_eq!(
+            proxy_for("example.com", true, &lookup),
+            Some(("https_proxy", "http://secure:3128".into()))
+        );
+        assert_eq!(
+            proxy_for("example.com", false, &lookup),
+            Some(("HTTP_PROXY", "http://plain:80".into()))
+        );
+    }
+
+    #[test]
+    fn proxy_for_honours_no_proxy() {
+        let lookup = env(&[
+            ("HTTPS_PROXY", "http://proxy:3128"),
+            ("NO_PROXY", "localhost, .internal.corp"),
+        ]);
+        assert_eq!(proxy_for("git.internal.corp", true, &lookup), None);
+        assert_eq!(proxy_for("internal.corp", true, &lookup), None);
+        assert!(proxy_for("example.com", true, &lookup).is_some());
+
+        let everything = env(&[("HTTPS_PROXY", "http://proxy:3128"), ("no_proxy", "*")]);
+        assert_eq!(proxy_for("example.com", true, &everything), None);
+    }
+
+    #[test]
+    fn proxy_overrides_set_both_spellings() {
+        let overrides = proxy_overrides(Some("http://proxy:3128"), None, Some(""));
+        assert_eq!(
+            overrides,
+            [
+                ("HTTP_PROXY".to_string(), "http://proxy:3128".to_string()),
+                ("http_proxy".to_string(), "http://proxy:3128".to_string()),
+            ]
+        );
+        assert!(proxy_overrides(None, None, None).is_empty());
+    }
+
+    #[test]
+    fn connect_to_closed_port_fails() {
+        // Port 9 on localhost is reserved (discard) and normally closed.
+        let err = Endpoint::parse("http://127.0.0.1:9").unwrap().connect();
+        assert!(err.is_err());
+    }
+}
@@ -1,47 +1,251 @@
 use std::io::BufRead;
+use std::path::Path;
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 
-use super::types::{CancelToken, ContainerCommand, ContainerResult, OutputLine};
+use crate::trace::{self, Target};
+
+use super::types::{CancelToken, ContainerCommand, ContainerControl, ContainerResult, OutputLine};
 
 const POLL_INTERVAL: Duration = Duration::from_millis(200);
 
+/// How long before the timeout a [`OutputLine::TimeoutWarning`] is sent.
+const TIMEOUT_WARNING_LEAD: Duration = Duration::from_secs(30);
+
+/// Label on every container lazyoav starts, set to the project directory,
+/// to find leftovers with `docker ps --filter label=lazyoav=<project>`.
+const CONTAINER_LABEL: &str = "lazyoav";
+
+/// How often to check that a removed container is gone before giving up.
+const REMOVE_CHECKS: usize = 10;
+
 /// Spawn a container and return a channel that streams its output.
 ///
 /// The caller receives [`OutputLine::Stdout`]/[`Stderr`] as they arrive,
 /// followed by exactly one [`OutputLine::Done`] carrying the final result.
 pub fn spawn(cmd: ContainerCommand, cancel: CancelToken) -> Result<Receiver<OutputLine>> {
-    let mut child = Command::new("docker")
-        .args(&cmd.args)
+    spawn_controlled(cmd, cancel).map(|(rx, _)| rx)
+}
+
+/// Like [`spawn`], but also returns a sender for [`ContainerControl`]
+/// messages (e.g. extending the timeout after a
+/// [`OutputLine::TimeoutWarning`]).
+pub fn spawn_controlled(
+    cmd: ContainerCommand,
+    cancel: CancelToken,
+) -> Result<(Receiver<OutputLine>, Sender<ContainerControl>)> {
+    // Killing the `docker` client leaves its container running in the
+    // daemon, so name it to be able to remove it on cancel or timeout.
+    let name = container_name();
+    let (args, container) = match with_name(&cmd.args, &name, &project_label(&cmd.project)) {
+        Some(args) => (args, Some(name)),
+        None => (cmd.args.clone(), None),
+    };
+    if trace::enabled(Target::Docker) {
+        let shown = redacted_args(&args);
+        trace::log(
+            Target::Docker,
+            format_args!("docker {}", shell_words::join(&shown)),
+        );
+    }
+    let child = Command::new("docker")
+        .args(&args)
+        .envs(cmd.env.iter().map(|(name, value)| (name, value)))
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .context("failed to spawn docker process")?;
 
-    let stdout = child.stdout.take().expect("stdout was piped");
-    let stderr = child.stderr.take().expect("stderr was piped");
-
     let (tx, rx) = mpsc::channel();
+    let (control_tx, control_rx) = mpsc::channel();
 
     std::thread::spawn(move || {
-        orchestrate(child, stdout, stderr, tx, cancel, cmd.timeout, cmd.log_path);
+        orchestrate(
+            child,
+            container,
+            tx,
+            control_rx,
+            cancel,
+            cmd.timeout,
+            cmd.log_path,
+        );
     });
 
-    Ok(rx)
+    Ok((rx, control_tx))
+}
+
+/// A container name unique to this process and run.
+fn container_name() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    format!("lazyoav-{}-{n}", std::process::id())
+}
+
+/// The id of the lazyoav process that named container `name`.
+pub fn container_owner(name: &str) -> Option<u32> {
+    name.strip_prefix("lazyoav-")?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Whether the daemon runs on another machine, reached over `DOCKER_HOST`,
+/// where the pids in container names aren't this machine's.
+pub fn remote_daemon() -> bool {
+    std::env::var("DOCKER_HOST").is_ok_and(|host| is_remote_host(&host))
+}
+
+/// Whether `host`, a `DOCKER_HOST` value, is reached over the network.
+fn is_remote_host(host: &str) -> bool {
+    !(host.is_empty() || host.starts_with("unix://") || host.starts_with("npipe://"))
+}
+
+/// The `lazyoav=<project>` label of containers started for `project`.
+pub fn project_label(project: &Path) -> String {
+    format!("{CONTAINER_LABEL}={}", project.display())
+}
+
+/// Names of all containers, running or stopped, labelled for `project`.
+pub fn project_containers(project: &Path) -> Result<Vec<String>> {
+    let output = Command::new("docker")
+        .args([
+            "ps",
+            "-a",
+            "--filter",
+            &format!("label={}", project_label(project)),
+            "--format",
+            "{{.Names}}",
+        ])
+        .output()
+        .context("failed to list containers")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "docker ps failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// `args` with the values of `-e NAME=value` left out, as `-e NAME`, for
+/// showing or storing a command without the secrets it may pass.
+pub fn redacted_args(args: &[String]) -> Vec<String> {
+    let name = |assignment: &str| assignment.split_once('=').map(|(name, _)| name.to_string());
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut env_value = false;
+    for arg in args {
+        let shown = if env_value {
+            name(arg)
+        } else if let Some(assignment) = arg.strip_prefix("--env=") {
+            name(assignment).map(|name| format!("--env={name}"))
+        } else if let Some(assignment) = arg.strip_prefix("-e").filter(|a| !a.is_empty()) {
+            name(assignment).map(|name| format!("-e{name}"))
+        } else {
+            None
+        };
+        env_value = !env_value && (arg == "-e" || arg == "--env");
+        redacted.push(shown.unwrap_or_else(|| arg.clone()));
+    }
+    redacted
+}
+
+/// `args` with `--name` and `label` added to its `docker run` or
+/// `docker compose ... run`, or `None` if it runs no container.
+fn with_name(args: &[String], name: &str, label: &str) -> Option<Vec<String>> {
+    let run = match args.first().map(String::as_str) {
+        Some("run") => 0,
+        Some("compose") => args.iter().position(|a| a == "run")?,
+        _ => return None,
+    };
+    let mut named = args.to_vec();
+    named.splice(
+        run + 1..run + 1,
+        [
+            "--name".to_string(),
+            name.to_string(),
+            "--label".to_string(),
+            label.to_string(),
+        ],
+    );
+    Some(named)
+}
+
+/// Force-remove container `name` and wait until the daemon no longer lists
+/// it. Returns `false` if it is still there.
+pub fn remove_container(name: &str) -> bool {
+    let _ = Command::new("docker")
+        .args(["rm", "-f", name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    (0..REMOVE_CHECKS).any(|attempt| {
+        if attempt > 0 {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        Command::new("docker")
+            .args(["ps", "-aq", "--filter", &format!("name=^{name}$")])
+            .output()
+            .is_ok_and(|out| out.status.success() && out.stdout.trim_ascii().is_empty())
+    })
+}
+
+/// Kill deadline for a container run, extendable while it's running.
+struct Deadline {
+    at: Instant,
+    warned: bool,
+}
+
+impl Deadline {
+    fn new(start: Instant, timeout: Duration) -> Self {
+        Self {
+            at: start + timeout,
+            // Timeouts shorter than the lead time would warn immediately.
+            warned: timeout <= TIMEOUT_WARNING_LEAD,
+        }
+    }
+
+    fn extend(&mut self, by: Duration) {
+        self.at += by;
+        self.warned = false;
+    }
+
+    /// Time left if a warning is due now; marks the warning as sent.
+    fn take_warning(&mut self, now: Instant) -> Option<Duration> {
+        let remaining = self.at.saturating_duration_since(now);
+        if self.warned || remaining > TIMEOUT_WARNING_LEAD {
+            return None;
+        }
+        self.warned = true;
+        Some(remaining)
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        now > self.at
+    }
 }
 
 fn orchestrate(
     mut child: std::process::Child,
-    stdout: std::process::ChildStdout,
-    stderr: std::process::ChildStderr,
+    container: Option<String>,
     tx: Sender<OutputLine>,
+    control: Receiver<ContainerControl>,
     cancel: CancelToken,
     timeout: Duration,
     log_path: Option<std::path::PathBuf>,
 ) {
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let stderr = child.stderr.take().expect("stderr was piped");
+
     // Accumulates all output for the final log / log_path write.
     let log_buf = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
 
@@ -84,7 +288,8 @@ fn orchestrate(
     });
 
     // --- poll loop ---------------------------------------------------------
-    let start = Instant::now();
+    let started = Instant::now();
+    let mut deadline = Deadline::new(Instant::now(), timeout);
     let mut cancelled = false;
     let mut timed_out = false;
 
@@ -97,27 +302,66 @@ fn orchestrate(
 
         if cancel.is_cancelled() {
             cancelled = true;
-            let _ = child.kill();
-            let _ = child.wait();
             break None;
         }
 
-        if start.elapsed() > timeout {
+        while let Ok(msg) = control.try_recv() {
+            match msg {
+                ContainerControl::ExtendTimeout(by) => deadline.extend(by),
+            }
+        }
+
+        let now = Instant::now();
+        if let Some(remaining) = deadline.take_warning(now) {
+            let _ = tx.send(OutputLine::TimeoutWarning { remaining });
+        }
+
+        if deadline.expired(now) {
             timed_out = true;
-            let _ = child.kill();
-            let _ = child.wait();
             break None;
         }
 
         std::thread::sleep(POLL_INTERVAL);
     };
 
+    if cancelled || timed_out {
+        if let Some(name) = &container {
+            let removed = remove_container(name);
+            trace::log(
+                Target::Docker,
+                format_args!("removing {name} after cancel or timeout: removed {removed}"),
+            );
+            let note = if removed {
+                format!("--- Removed container {name} ---")
+            } else {
+                format!(
+                    "--- Container {name} may still be running; remove it with `docker rm -f {name}` ---"
+                )
+            };
+            if let Ok(mut buf) = log_buf.lock() {
+                buf.push_str(&note);
+                buf.push('\n');
+            }
+            let _ = tx.send(OutputLine::Stderr(note));
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
     // --- finalize ----------------------------------------------------------
     let _ = stdout_handle.join();
     let _ = stderr_handle.join();
 
     let exit_code = exit_status.and_then(|s| s.code());
     let success = exit_code == Some(0);
+    trace::log(
+        Target::Docker,
+        format_args!(
+            "{} exited with {exit_code:?} after {:.1}s (cancelled {cancelled}, timed out {timed_out})",
+            container.as_deref().unwrap_or("docker"),
+            started.elapsed().as_secs_f64()
+        ),
+    );
     let log = log_buf.lock().map(|b| b.clone()).unwrap_or_default();
 
     if let Some(path) = log_path {
@@ -135,3 +379,130 @@ fn orchestrate(
         timed_out,
     }));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn redacted_args_keep_env_names_only() {
+        assert_eq!(
+            redacted_args(&args(&[
+                "run",
+                "-e",
+                "HTTP_PROXY",
+                "-e",
+                "TOKEN=s3cret",
+                "--env",
+                "A=b=c",
+                "--env=KEY=v",
+                "-eX=y",
+                "image",
+            ])),
+            args(&[
+                "run",
+                "-e",
+                "HTTP_PROXY",
+                "-e",
+                "TOKEN",
+                "--env",
+                "A",
+                "--env=KEY",
+                "-eX",
+                "image",
+            ])
+        );
+    }
+
+    #[test]
+    fn with_name_names_run_and_compose_run_containers() {
+        let run = args(&["run", "--rm", "-v", "/p:/work", "image", "lint"]);
+        assert_eq!(
+            with_name(&run, "lazyoav-1-2", "lazyoav=/p").unwrap(),
+            args(&[
+                "run",
+                "--name",
+                "lazyoav-1-2",
+                "--label",
+                "lazyoav=/p",
+                "--rm",
+                "-v",
+                "/p:/work",
+                "image",
+                "lint"
+            ])
+        );
+
+        let compose = args(&["compose", "-f", "c.yaml", "run", "--rm", "build-spring"]);
+        let named = with_name(&compose, "n", "l").unwrap();
+        assert_eq!(
+            named[..6],
+            args(&["compose", "-f", "c.yaml", "run", "--name", "n"])
+        );
+        assert_eq!(named.last().unwrap(), "build-spring");
+
+        assert!(with_name(&args(&["image", "inspect", "x"]), "n", "l").is_none());
+    }
+
+    #[test]
+    fn container_names_are_unique() {
+        assert_ne!(container_name(), container_name());
+    }
+
+    #[test]
+    fn container_owner_reads_the_pid_from_the_name() {
+        assert_eq!(container_owner(&container_name()), Some(std::process::id()));
+        assert_eq!(container_owner("lazyoav-x-1"), None);
+        assert_eq!(container_owner("postgres"), None);
+        assert_eq!(project_label(Path::new("/p")), "lazyoav=/p");
+    }
+
+    #[test]
+    fn only_network_hosts_are_remote() {
+        assert!(is_remote_host("tcp://build-host:2376"));
+        assert!(is_remote_host("ssh://me@build-host"));
+        assert!(!is_remote_host("unix:///var/run/docker.sock"));
+        assert!(!is_remote_host("npipe:////./pipe/docker_engine"));
+        assert!(!is_remote_host(""));
+    }
+
+    #[test]
+    fn deadline_warns_once_inside_lead_window() {
+        let start = Instant::now();
+        let mut deadline = Deadline::new(start, Duration::from_secs(300));
+        assert_eq!(deadline.take_warning(start), None);
+
+        let near = start + Duration::from_secs(280);
+        assert_eq!(deadline.take_warning(near), Some(Duration::from_secs(20)));
+        assert_eq!(deadline.take_warning(near), None);
+    }
+
+    #[test]
+    fn deadline_extension_rearms_warning() {
+        let start = Instant::now();
+        let mut deadline = Deadline::new(start, Duration::from_secs(60));
+        let near = start + Duration::from_secs(50);
+        assert!(deadline.take_warning(near).is_some());
+
+        deadline.extend(Duration::from_secs(300));
+        assert!(!deadline.expired(start + Duration::from_secs(61)));
+        assert_eq!(deadline.take_warning(near), None);
+        assert!(
+            deadline
+                .take_warning(start + Duration::from_secs(340))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn short_timeout_never_warns() {
+        let start = Instant::now();
+        let mut deadline = Deadline::new(start, Duration::from_secs(10));
+        assert_eq!(deadline.take_warning(start + Duration::from_secs(5)), None);
+        assert!(deadline.expired(start + Duration::from_secs(11)));
+    }
+}
@@ -30,10 +30,17 @@ impl CancelToken {
 
 /// Describes a container invocation. The `args` field is the full argument list
 /// passed to `docker` (the pipeline layer is responsible for assembling it).
+#[derive(Debug, Clone)]
 pub struct ContainerCommand {
     pub args: Vec<String>,
+    /// Variables set on the `docker` client, for `-e NAME` flags in `args`
+    /// to copy into the container without their values showing in `args`.
+    pub env: Vec<(String, String)>,
     pub timeout: Duration,
     pub log_path: Option<PathBuf>,
+    /// The project the container runs for, labelled on it so leftovers of
+    /// a crashed session can be found.
+    pub project: PathBuf,
 }
 
 /// Outcome of a container run.
@@ -51,9 +58,21 @@ pub struct ContainerResult {
 pub enum OutputLine {
     Stdout(String),
     Stderr(String),
+    /// The container will be killed for exceeding its timeout in `remaining`
+    /// unless it's extended via [`ContainerControl::ExtendTimeout`].
+    TimeoutWarning {
+        remaining: Duration,
+    },
     Done(ContainerResult),
 }
 
+/// Messages a caller can send into a running container's poll loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerControl {
+    /// Push the kill deadline back by the given amount.
+    ExtendTimeout(Duration),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,322 @@
+//! How long each pipeline phase took in recent runs, for estimating how
+//! long a run has left.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, RunAfter};
+use crate::pipeline::{Invocation, Phase, ValidateReport};
+
+/// Relative to the project. Timings depend on the machine, so they live
+/// with the reports rather than in a file the team commits.
+pub const DURATIONS_FILE: &str = ".oav/reports/durations.json";
+
+/// Runs of a phase remembered; estimates average over these.
+const KEEP: usize = 5;
+
+/// Recent wall times in milliseconds, newest last, by [`key`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Durations {
+    phases: BTreeMap<String, Vec<u64>>,
+}
+
+/// A step of the running pipeline that hasn't finished yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pending {
+    pub phase: Phase,
+    /// How long it has been running, `None` while it waits its turn.
+    pub elapsed: Option<Duration>,
+}
+
+/// The name `phase` is remembered under, e.g. `compile server/spring`.
+pub fn key(phase: &Phase) -> String {
+    match phase {
+        Phase::Structure => "structure".into(),
+        Phase::Lint => "lint".into(),
+        Phase::Security => "security".into(),
+        Phase::Generate { generator, scope } => format!("generate {scope}/{generator}"),
+        Phase::Compile { generator, scope } => format!("compile {scope}/{generator}"),
+        Phase::Test { generator, scope } => format!("test {scope}/{generator}"),
+        Phase::CodeLint { generator, scope } => format!("code-lint {scope}/{generator}"),
+        Phase::Docs => "docs".into(),
+        Phase::Custom { name } => format!("custom {name}"),
+    }
+}
+
+impl Durations {
+    /// Read [`DURATIONS_FILE`] from `root`. A missing or unreadable file
+    /// means nothing is known yet.
+    pub fn load(root: &Path) -> Self {
+        fs::read_to_string(root.join(DURATIONS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = root.join(DURATIONS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize durations")?;
+        crate::reports::write_atomic(&path, json.as_bytes())
+    }
+
+    /// Remember how long each step of `report` took.
+    pub fn record(&mut self, report: &ValidateReport) {
+        let phases = &report.phases;
+        if let Some(r) = &phases.lint {
+            self.push(&Phase::Lint, &r.invocation);
+        }
+        if let Some(r) = &phases.security {
+            self.push(&Phase::Security, &r.invocation);
+        }
+        for r in phases.generate.iter().flatten() {
+            let (generator, scope) = (r.generator.clone(), r.scope.clone());
+            self.push(&Phase::Generate { generator, scope }, &r.invocation);
+        }
+        for r in phases.compile.iter().flatten() {
+            let (generator, scope) = (r.generator.clone(), r.scope.clone());
+            self.push(&Phase::Compile { generator, scope }, &r.invocation);
+        }
+        for r in phases.test.iter().flatten() {
+            let (generator, scope) = (r.generator.clone(), r.scope.clone());
+            self.push(&Phase::Test { generator, scope }, &r.invocation);
+        }
+        for r in phases.code_lint.iter().flatten() {
+            let (generator, scope) = (r.generator.clone(), r.scope.clone());
+            self.push(&Phase::CodeLint { generator, scope }, &r.invocation);
+        }
+        if let Some(r) = &phases.docs {
+            self.push(&Phase::Docs, &r.invocation);
+        }
+        for r in phases.custom.iter().flatten() {
+            let name = r.name.clone();
+            self.push(&Phase::Custom { name }, &r.invocation);
+        }
+    }
+
+    /// Cancelled and timed out steps say nothing about how long the next
+    /// run will take.
+    fn push(&mut self, phase: &Phase, invocation: &Option<Invocation>) {
+        let Some(inv) = invocation.as_ref().filter(|i| !i.cancelled && !i.timed_out) else {
+            return;
+        };
+        let times = self.phases.entry(key(phase)).or_default();
+        times.push(inv.duration_ms);
+        if times.len() > KEEP {
+            times.drain(..times.len() - KEEP);
+        }
+    }
+
+    /// How long `phase` usually takes, if it ran before.
+    pub fn expected(&self, phase: &Phase) -> Option<Duration> {
+        let times = self.phases.get(&key(phase)).filter(|t| !t.is_empty())?;
+        Some(Duration::from_millis(
+            times.iter().sum::<u64>() / times.len() as u64,
+        ))
+    }
+
+    /// How long the `pending` steps of a run with `cfg` should still take,
+    /// or `None` when none of them ran before.
+    ///
+    /// Follows the orchestrator's schedule: lint, security and custom phases
+    /// after lint run one after another beside the generator track, whose
+    /// generate, compile, test and code lint stages each run their steps
+    /// `jobs` at a time and wait for the slowest. Docs and the phases after
+    /// everything come last. Steps that never ran count as instant.
+    pub fn remaining(&self, cfg: &Config, pending: &[Pending]) -> Option<Duration> {
+        let jobs = cfg.jobs.resolve().max(1) as u32;
+        let after = |name: &str| {
+            cfg.custom_phases
+                .iter()
+                .find(|p| p.name == name)
+                .map_or(RunAfter::All, |p| p.after)
+        };
+
+        let mut known = false;
+        let mut analysis = Duration::ZERO;
+        // Generate, custom after generate, compile, test, code lint, custom
+        // after compile.
+        let mut stages = [(Duration::ZERO, Duration::ZERO); 6];
+        let mut tail = Duration::ZERO;
+        for step in pending {
+            let Some(expected) = self.expected(&step.phase) else {
+                continue;
+            };
+            known = true;
+            let left = expected.saturating_sub(step.elapsed.unwrap_or_default());
+            let stage = match &step.phase {
+                Phase::Structure | Phase::Lint | Phase::Security => None,
+                Phase::Generate { .. } => Some(0),
+                Phase::Compile { .. } => Some(2),
+                Phase::Test { .. } => Some(3),
+                Phase::CodeLint { .. } => Some(4),
+                Phase::Docs => Some(6),
+                Phase::Custom { name } => match after(name) {
+                    RunAfter::Lint => None,
+                    RunAfter::Generate => Some(1),
+                    RunAfter::Compile => Some(5),
+                    RunAfter::All => Some(6),
+                },
+            };
+            match stage {
+                None => analysis += left,
+                Some(6) => tail += left,
+                Some(i) => {
+                    let (sum, longest) = &mut stages[i];
+                    *sum += left;
+                    *longest = (*longest).max(left);
+                }
+            }
+        }
+        if !known {
+            return None;
+        }
+
+        let generators: Duration = stages
+            .iter()
+            .enumerate()
+            .map(|(i, &(sum, longest))| match i {
+                // The custom phases of a stage run one after another.
+                1 | 5 => sum,
+                _ => longest.max(sum / jobs),
+            })
+            .sum();
+        Some(analysis.max(generators) + tail)
+    }
+}
+
+/// A rough remaining time for the status bar: `~40s`, `~3m`, `~1h 5m`.
+pub fn format_remaining(left: Duration) -> String {
+    let secs = left.as_secs();
+    if secs < 60 {
+        // Round up so a run that's nearly done doesn't read `~0s`.
+        format!("~{}s", secs.max(1))
+    } else if secs < 3_600 {
+        format!("~{}m", secs.div_ceil(60))
+    } else {
+        format!("~{}h {}m", secs / 3_600, secs % 3_600 / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Jobs;
+    use crate::pipeline::{Phases, StepResult};
+
+    fn generate(generator: &str) -> Phase {
+        Phase::Generate {
+            generator: generator.into(),
+            scope: "server".into(),
+        }
+    }
+
+    fn step(generator: &str, duration_ms: u64) -> StepResult {
+        StepResult {
+            generator: generator.into(),
+            scope: "server".into(),
+            status: "pass".into(),
+            invocation: Some(Invocation {
+                duration_ms,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn pending(phase: Phase) -> Pending {
+        Pending {
+            phase,
+            elapsed: None,
+        }
+    }
+
+    #[test]
+    fn record_keeps_recent_runs_and_skips_cancelled_ones() {
+        let mut durations = Durations::default();
+        for ms in [1_000, 2_000, 3_000, 4_000, 5_000, 6_000] {
+            let mut cancelled = step("go", 99_000);
+            cancelled.invocation.as_mut().unwrap().cancelled = true;
+            durations.record(&ValidateReport {
+                phases: Phases {
+                    generate: Some(vec![step("spring", ms), cancelled]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+        // The first run dropped out of the last five.
+        assert_eq!(
+            durations.expected(&generate("spring")),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(durations.expected(&generate("go")), None);
+    }
+
+    #[test]
+    fn remaining_follows_the_pipeline_schedule() {
+        let mut durations = Durations::default();
+        for (phase, secs) in [
+            (Phase::Lint, 30),
+            (generate("spring"), 60),
+            (generate("go"), 20),
+            (
+                Phase::Compile {
+                    generator: "spring".into(),
+                    scope: "server".into(),
+                },
+                120,
+            ),
+            (Phase::Docs, 10),
+        ] {
+            durations.phases.insert(key(&phase), vec![secs * 1_000]);
+        }
+        let cfg = Config {
+            jobs: Jobs::Fixed(1),
+            ..Default::default()
+        };
+
+        let mut steps = vec![
+            pending(Phase::Lint),
+            pending(generate("spring")),
+            pending(generate("go")),
+            pending(Phase::Compile {
+                generator: "spring".into(),
+                scope: "server".into(),
+            }),
+            pending(Phase::Docs),
+        ];
+        // One job: both generators in turn, then compile, beside lint.
+        assert_eq!(
+            durations.remaining(&cfg, &steps),
+            Some(Duration::from_secs(80 + 120 + 10))
+        );
+
+        // Spring is a minute in and lint is done: only compile and docs left.
+        steps.remove(0);
+        steps[0].elapsed = Some(Duration::from_secs(90));
+        steps.remove(1);
+        assert_eq!(
+            durations.remaining(&cfg, &steps),
+            Some(Duration::from_secs(130))
+        );
+
+        assert_eq!(durations.remaining(&cfg, &[pending(Phase::Security)]), None);
+    }
+
+    #[test]
+    fn format_remaining_rounds_up() {
+        assert_eq!(format_remaining(Duration::ZERO), "~1s");
+        assert_eq!(format_remaining(Duration::from_secs(42)), "~42s");
+        assert_eq!(format_remaining(Duration::from_secs(61)), "~2m");
+        assert_eq!(format_remaining(Duration::from_secs(3_900)), "~1h 5m");
+    }
+}
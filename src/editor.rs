@@ -0,0 +1,186 @@
+// Editor invocation — build the command line that opens a file at a
+// line and column.
+
+use std::path::Path;
+
+/// The command to open `path` at 1-based `line` and `col`.
+///
+/// With a `template` like `nvim +{line} {file}`, the `{file}`, `{line}` and
+/// `{col}` placeholders are filled in and the file is appended if the
+/// template doesn't place it. Otherwise `editor` (from `$VISUAL`/`$EDITOR`)
+/// gets the position argument its program understands, falling back to
+/// `+{line}`.
+pub fn command(
+    template: Option<&str>,
+    editor: &str,
+    path: &Path,
+    line: usize,
+    col: usize,
+) -> Vec<String> {
+    let file = path.to_string_lossy();
+    if let Some(template) = template {
+        let mut parts = split(template);
+        let places_file = parts.iter().any(|p| p.contains("{file}"));
+        for part in &mut parts {
+            *part = part
+                .replace("{file}", &file)
+                .replace("{line}", &line.to_string())
+                .replace("{col}", &col.to_string());
+        }
+        if !places_file {
+            parts.push(file.into_owned());
+        }
+        return parts;
+    }
+
+    let mut parts = split(editor);
+    let program = Path::new(&parts[0])
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match program.trim_end_matches(".exe") {
+        "vi" | "vim" | "nvim" | "gvim" | "mvim" | "view" => {
+            parts.push(format!("+call cursor({line},{col})"));
+            parts.push(file.into_owned());
+        }
+        "code" | "code-insiders" | "codium" | "cursor" => {
+            parts.push("--goto".into());
+            parts.push(format!("{file}:{line}:{col}"));
+        }
+        "emacs" | "emacsclient" | "kak" => {
+            parts.push(format!("+{line}:{col}"));
+            parts.push(file.into_owned());
+        }
+        "hx" | "helix" | "subl" | "micro" => {
+            parts.push(format!("{file}:{line}:{col}"));
+        }
+        "nano" => {
+            parts.push(format!("+{line},{col}"));
+            parts.push(file.into_owned());
+        }
+        _ => {
+            parts.push(format!("+{line}"));
+            parts.push(file.into_owned());
+        }
+    }
+    parts
+}
+
+/// Commands that jump an editor this TUI runs inside to `path` at `line`
+/// and `col`, without leaving the TUI: Neovim's `:terminal` (which exports
+/// `$NVIM`, its RPC address) or VS Code's integrated terminal
+/// (`TERM_PROGRAM=vscode`). `None` when neither is detected.
+pub fn remote_commands(
+    nvim: Option<&str>,
+    term_program: Option<&str>,
+    path: &Path,
+    line: usize,
+    col: usize,
+) -> Option<Vec<Vec<String>>> {
+    let file = path.to_string_lossy();
+    if let Some(server) = nvim.filter(|s| !s.is_empty()) {
+        let nvim = |args: [&str; 2]| {
+            ["nvim", "--server", server, args[0], args[1]]
+                .map(String::from)
+                .to_vec()
+        };
+        return Some(vec![
+            nvim(["--remote", &file]),
+            nvim([
+                "--remote-send",
+                &format!("<C-\\><C-N>:call cursor({line},{col})<CR>"),
+            ]),
+        ]);
+    }
+    if term_program == Some("vscode") {
+        return Some(vec![vec![
+            "code".into(),
+            "--goto".into(),
+            format!("{file}:{line}:{col}"),
+        ]]);
+    }
+    None
+}
+
+/// Split a command like `code --wait` into words, keeping it whole if it
+/// doesn't parse.
+fn split(cmd: &str) -> Vec<String> {
+    match shell_words::split(cmd) {
+        Ok(parts) if !parts.is_empty() => parts,
+        _ => vec![cmd.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(editor: &str) -> Vec<String> {
+        command(None, editor, Path::new("/work/api.yaml"), 12, 5)
+    }
+
+    #[test]
+    fn known_editors_get_line_and_column() {
+        assert_eq!(
+            open("nvim"),
+            ["nvim", "+call cursor(12,5)", "/work/api.yaml"]
+        );
+        assert_eq!(
+            open("/usr/bin/code --wait"),
+            ["/usr/bin/code", "--wait", "--goto", "/work/api.yaml:12:5"]
+        );
+        assert_eq!(
+            open("emacsclient -t"),
+            ["emacsclient", "-t", "+12:5", "/work/api.yaml"]
+        );
+        assert_eq!(open("hx"), ["hx", "/work/api.yaml:12:5"]);
+        assert_eq!(open("kak"), ["kak", "+12:5", "/work/api.yaml"]);
+    }
+
+    #[test]
+    fn unknown_editors_get_the_line() {
+        assert_eq!(open("ed"), ["ed", "+12", "/work/api.yaml"]);
+    }
+
+    #[test]
+    fn remote_editors() {
+        let path = Path::new("/work/api.yaml");
+        assert_eq!(
+            remote_commands(Some("/run/nvim.sock"), Some("vscode"), path, 12, 5).unwrap(),
+            [
+                vec![
+                    "nvim",
+                    "--server",
+                    "/run/nvim.sock",
+                    "--remote",
+                    "/work/api.yaml"
+                ],
+                vec![
+                    "nvim",
+                    "--server",
+                    "/run/nvim.sock",
+                    "--remote-send",
+                    "<C-\\><C-N>:call cursor(12,5)<CR>",
+                ],
+            ]
+        );
+        assert_eq!(
+            remote_commands(None, Some("vscode"), path, 12, 5).unwrap(),
+            [vec!["code", "--goto", "/work/api.yaml:12:5"]]
+        );
+        assert_eq!(remote_commands(None, Some("iTerm.app"), path, 12, 5), None);
+    }
+
+    #[test]
+    fn template_fills_placeholders() {
+        let path = Path::new("/work/my api.yaml");
+        assert_eq!(
+            command(Some("nvim +{line} {file}"), "vi", path, 12, 5),
+            ["nvim", "+12", "/work/my api.yaml"]
+        );
+        assert_eq!(
+            command(Some("idea --line {line} --column {col}"), "vi", path, 12, 5),
+            ["idea", "--line", "12", "--column", "5", "/work/my api.yaml"]
+        );
+    }
+}
@@ -0,0 +1,442 @@
+use lazyoav::openapi::{METHODS, resolve_ref};
+use serde_yaml::Value;
+
+/// What an operation does, as far as its method, path, parameters, tags
+/// and success response tell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Operation {
+    method: String,
+    path: String,
+    operation_id: Option<String>,
+    tags: Vec<String>,
+    /// The resource the operation is about, as lowercase singular words.
+    resource: String,
+    /// The path parameter the path ends in, as words.
+    key: Option<String>,
+    /// A static segment after the last path parameter that isn't a
+    /// collection, e.g. `cancel` in `/orders/{id}/cancel`.
+    action: Option<String>,
+    /// Query parameter names, as words.
+    filters: Vec<String>,
+    /// Whether a success response has a body, and whether it's an array.
+    returns: Option<bool>,
+}
+
+impl Operation {
+    /// The operation at `pointer`, e.g. `/paths/~1pets/get`.
+    pub(super) fn at(spec: &Value, pointer: &str) -> Option<Self> {
+        let (path, method) = pointer.strip_prefix("/paths/")?.rsplit_once('/')?;
+        if !METHODS.contains(&method) {
+            return None;
+        }
+        let path = path.replace("~1", "/").replace("~0", "~");
+        let item = spec.get("paths")?.get(path.as_str())?;
+        let op = item.get(method)?;
+
+        let params = [item.get("parameters"), op.get("parameters")]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_sequence)
+            .flatten()
+            .map(|p| resolve_ref(spec, p));
+        let filters = params
+            .filter(|p| p.get("in").and_then(Value::as_str) == Some("query"))
+            .filter_map(|p| p.get("name").and_then(Value::as_str))
+            .map(words)
+            .collect();
+
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let is_param = |s: &str| s.starts_with('{') && s.ends_with('}');
+        let key = segments
+            .last()
+            .filter(|s| is_param(s))
+            .map(|s| words(s.trim_matches(|c| c == '{' || c == '}')));
+        let mut statics = segments.iter().copied().filter(|s| !is_param(s));
+        let last_static = statics.next_back();
+        // `/orders/{id}/cancel`: the last segment acts on the order.
+        let action = match segments.as_slice() {
+            [.., param, last] if is_param(param) && !is_param(last) && !is_plural(last) => {
+                Some(words(last))
+            }
+            _ => None,
+        };
+        let path_resource = if action.is_some() {
+            statics.next_back()
+        } else {
+            last_static
+        }
+        .map(|s| singular(&words(s)));
+
+        let (schema_name, returns) = success_body(spec, op);
+        let resource = schema_name
+            .map(|n| words(&n))
+            .or(path_resource)
+            .unwrap_or_else(|| "resource".to_string());
+
+        Some(Self {
+            method: method.to_string(),
+            path,
+            operation_id: op
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            tags: op
+                .get("tags")
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            resource,
+            key,
+            action,
+            filters,
+            returns,
+        })
+    }
+
+    /// E.g. "List pets filtered by status", "Get a pet by id".
+    pub(super) fn summary(&self) -> String {
+        let one = with_article(&self.resource);
+        let by_key = |verb: &str| match self.key.as_deref().map(|k| self.strip_resource(k)) {
+            Some(key) => format!("{verb} {one} by {key}"),
+            None => format!("{verb} {one}"),
+        };
+        let text = if let Some(action) = &self.action {
+            format!("{action} {one}")
+        } else {
+            match self.method.as_str() {
+                "get" if self.key.is_none() || self.returns == Some(true) => {
+                    let mut text = format!("list {}", plural(&self.resource));
+                    if !self.filters.is_empty() {
+                        text.push_str(" filtered by ");
+                        text.push_str(&join_words(&self.filters));
+                    }
+                    text
+                }
+                "get" => by_key("get"),
+                "post" => format!("create {one}"),
+                "put" => by_key("replace"),
+                "patch" => by_key("update"),
+                "delete" => by_key("delete"),
+                "head" => by_key("check for"),
+                method => format!("{} {}", method.to_uppercase(), self.path),
+            }
+        };
+        capitalize(&text)
+    }
+
+    /// The summary as a sentence, and what the operation responds with.
+    pub(super) fn description(&self) -> String {
+        let mut text = format!("{}.", self.summary());
+        match self.returns {
+            Some(true) => text.push_str(&format!(
+                " Responds with a list of {}.",
+                plural(&self.resource)
+            )),
+            Some(false) => text.push_str(&format!(" Responds with the {}.", self.resource)),
+            None => {}
+        }
+        text
+    }
+
+    /// Fill in a `fix_text` template, in one pass: text filled in is never
+    /// read for placeholders again, and unknown ones are left as they are.
+    pub(super) fn render(&self, template: &str) -> String {
+        let mut out = String::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            rest = &rest[open..];
+            let Some(close) = rest.find('}') else {
+                break;
+            };
+            match self.placeholder(&rest[1..close]) {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(&rest[..=close]),
+            }
+            rest = &rest[close + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn placeholder(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "summary" => self.summary(),
+            "description" => self.description(),
+            "method" => self.method.to_uppercase(),
+            "path" => self.path.clone(),
+            "operation_id" => self.operation_id.clone().unwrap_or(self.method.clone()),
+            "tag" => self.tags.first().cloned().unwrap_or_default(),
+            "resource" => self.resource.clone(),
+            _ => return None,
+        })
+    }
+
+    /// `pet id` on a pet is just `id`.
+    fn strip_resource<'a>(&self, key: &'a str) -> &'a str {
+        key.strip_prefix(self.resource.as_str())
+            .map(str::trim_start)
+            .filter(|k| !k.is_empty())
+            .unwrap_or(key)
+    }
+}
+
+/// The name of the schema a 2xx response returns, and whether it's an
+/// array; `(None, None)` when no success response has a body.
+fn success_body(spec: &Value, op: &Value) -> (Option<String>, Option<bool>) {
+    let Some(responses) = op.get("responses").and_then(Value::as_mapping) else {
+        return (None, None);
+    };
+    let schema = responses
+        .iter()
+        .filter(|(code, _)| match code {
+            Value::String(s) => s.starts_with('2'),
+            Value::Number(n) => n.as_u64().is_some_and(|n| (200..300).contains(&n)),
+            _ => false,
+        })
+        .filter_map(|(_, response)| {
+            let content = resolve_ref(spec, response).get("content")?.as_mapping()?;
+            content.values().find_map(|media| media.get("schema"))
+        })
+        .next();
+    let Some(schema) = schema else {
+        return (None, None);
+    };
+    let name = |node: &Value| {
+        node.get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.rsplit('/').next())
+            .map(str::to_string)
+    };
+    let resolved = resolve_ref(spec, schema);
+    if resolved.get("type").and_then(Value::as_str) == Some("array") {
+        let items = resolved.get("items");
+        return (items.and_then(name), Some(true));
+    }
+    (name(schema), Some(false))
+}
+
+/// `petId`, `pet_id`, `pet-id` and `PetID` are all "pet id".
+fn words(name: &str) -> String {
+    let mut out = String::new();
+    let mut prev: Option<char> = None;
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' || c == '.' {
+            if !out.ends_with(' ') && !out.is_empty() {
+                out.push(' ');
+            }
+            prev = None;
+            continue;
+        }
+        let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+        let boundary = prev.is_some_and(|p| {
+            c.is_uppercase()
+                && (p.is_lowercase() || p.is_ascii_digit() || next_lower && p.is_uppercase())
+        });
+        if boundary && !out.ends_with(' ') {
+            out.push(' ');
+        }
+        out.extend(c.to_lowercase());
+        prev = Some(c);
+    }
+    out.trim().to_string()
+}
+
+fn is_plural(word: &str) -> bool {
+    word.ends_with('s') && !word.ends_with("ss")
+}
+
+fn singular(words: &str) -> String {
+    let (head, last) = words.rsplit_once(' ').map_or(("", words), |(h, l)| (h, l));
+    let last = if let Some(stem) = last.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if let Some(stem) = ["ches", "shes", "sses", "xes"].iter().find_map(|s| {
+        last.strip_suffix(s)
+            .map(|stem| format!("{stem}{}", &s[..s.len() - 2]))
+    }) {
+        stem
+    } else if is_plural(last) {
+        last[..last.len() - 1].to_string()
+    } else {
+        last.to_string()
+    };
+    if head.is_empty() {
+        last
+    } else {
+        format!("{head} {last}")
+    }
+}
+
+fn plural(words: &str) -> String {
+    if let Some(stem) = words.strip_suffix('y')
+        && !stem.ends_with(['a', 'e', 'i', 'o', 'u'])
+    {
+        return format!("{stem}ies");
+    }
+    if words.ends_with(['s', 'x']) || words.ends_with("ch") || words.ends_with("sh") {
+        return format!("{words}es");
+    }
+    format!("{words}s")
+}
+
+/// `an order`, `a user`, `an hour`: the article goes by the sound the
+/// words start with, as far as their first letters tell.
+fn with_article(words: &str) -> String {
+    const SAID_WITH_CONSONANT: &[&str] = &["uni", "use", "usu", "uti", "eu", "one"];
+    const SAID_WITH_VOWEL: &[&str] = &["hour", "honest", "honor", "heir"];
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| words.starts_with(p));
+    let vowel = words.starts_with(['a', 'e', 'i', 'o', 'u']) && !starts(SAID_WITH_CONSONANT);
+    let article = if vowel || starts(SAID_WITH_VOWEL) {
+        "an"
+    } else {
+        "a"
+    };
+    format!("{article} {words}")
+}
+
+/// `a`, `a and b`, `a, b and c`.
+fn join_words(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.clone(),
+        [init @ .., last] => format!("{} and {last}", init.join(", ")),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "\
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+      parameters:
+        - name: status
+          in: query
+        - $ref: '#/components/parameters/Tag'
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/Pet'
+    post:
+      responses:
+        '201':
+          description: Created
+  /pets/{petId}:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+    delete:
+      responses:
+        '204':
+          description: Deleted
+  /order-items/{orderItemId}/cancel:
+    post:
+      responses:
+        '200':
+          description: OK
+components:
+  parameters:
+    Tag:
+      name: tag
+      in: query
+  schemas:
+    Pet:
+      type: object
+";
+
+    fn op(pointer: &str) -> Operation {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        Operation::at(&spec, pointer).unwrap()
+    }
+
+    #[test]
+    fn summaries_follow_method_path_and_parameters() {
+        assert_eq!(
+            op("/paths/~1pets/get").summary(),
+            "List pets filtered by status and tag"
+        );
+        assert_eq!(op("/paths/~1pets/post").summary(), "Create a pet");
+        assert_eq!(
+            op("/paths/~1pets~1{petId}/get").summary(),
+            "Get a pet by id"
+        );
+        assert_eq!(
+            op("/paths/~1pets~1{petId}/delete").summary(),
+            "Delete a pet by id"
+        );
+        assert_eq!(
+            op("/paths/~1order-items~1{orderItemId}~1cancel/post").summary(),
+            "Cancel an order item"
+        );
+    }
+
+    #[test]
+    fn descriptions_say_what_comes_back() {
+        assert_eq!(
+            op("/paths/~1pets/get").description(),
+            "List pets filtered by status and tag. Responds with a list of pets."
+        );
+        assert_eq!(
+            op("/paths/~1pets~1{petId}/get").description(),
+            "Get a pet by id. Responds with the pet."
+        );
+        assert_eq!(op("/paths/~1pets/post").description(), "Create a pet.");
+    }
+
+    #[test]
+    fn templates_set_the_house_style() {
+        let op = op("/paths/~1pets/get");
+        assert_eq!(
+            op.render("{tag}: {summary} ({METHOD} {path}, {operation_id})"),
+            "pets: List pets filtered by status and tag ({METHOD} /pets, listPets)"
+        );
+        assert_eq!(op.render("{method} {resource}"), "GET pet");
+        // Filled-in text isn't searched for placeholders again.
+        assert_eq!(
+            self::op("/paths/~1pets~1{petId}/get").render("{path} {resource"),
+            "/pets/{petId} {resource"
+        );
+    }
+
+    #[test]
+    fn names_become_words() {
+        assert_eq!(words("petId"), "pet id");
+        assert_eq!(words("order_item-ID"), "order item id");
+        assert_eq!(words("HTTPStatus"), "http status");
+        assert_eq!(singular("categories"), "category");
+        assert_eq!(singular("order boxes"), "order box");
+        assert_eq!(plural("category"), "categories");
+        assert_eq!(plural("address"), "addresses");
+        assert_eq!(with_article("order item"), "an order item");
+        assert_eq!(with_article("user"), "a user");
+        assert_eq!(with_article("hour"), "an hour");
+        assert!(Operation::at(&Value::Null, "/info").is_none());
+    }
+}
@@ -0,0 +1,619 @@
+use std::ops::Range;
+
+use lazyoav::format::strip_comment;
+
+use crate::spec::normalize_to_pointer;
+
+use super::Edit;
+
+/// The block structure of a YAML spec: every mapping entry and sequence
+/// item with the lines it spans. Edits are computed against it, so they land
+/// between entries rather than inside block scalars or multi-line values,
+/// and leave comments, anchors and quoting alone.
+///
+/// Flow collections and JSON specs have no block structure; `parse`
+/// returns `None` for them.
+pub struct Document<'a> {
+    lines: &'a [String],
+    root: Node,
+    /// Spaces per nesting level, from the spec itself.
+    step: usize,
+}
+
+/// A mapping entry or sequence item.
+#[derive(Debug)]
+pub struct Node {
+    /// The key, or the index of a sequence item.
+    pub key: String,
+    /// 0-based line the node starts on.
+    pub line: usize,
+    /// Column of the key, or of the `-` of a sequence item.
+    pub col: usize,
+    /// 0-based line after the node's last line. Blank and comment lines
+    /// after it belong to whatever follows.
+    pub end: usize,
+    pub kind: Kind,
+    /// Byte range of the value on `line`, for values written after the key.
+    value: Option<Range<usize>>,
+}
+
+#[derive(Debug)]
+pub enum Kind {
+    Mapping(Vec<Node>),
+    Sequence(Vec<Node>),
+    /// A scalar, alias or flow collection after the key, possibly continued
+    /// on the lines below.
+    Inline,
+    /// A `|` or `>` block scalar.
+    BlockScalar,
+    /// No value.
+    Empty,
+}
+
+/// Where [`Document::insert`] puts a new entry in a mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+    First,
+    Last,
+}
+
+impl<'a> Document<'a> {
+    pub fn parse(lines: &'a [String]) -> Option<Self> {
+        let mut start = 0;
+        while lines.get(start).is_some_and(|l| {
+            let t = l.trim();
+            t.is_empty() || t.starts_with('#') || t.starts_with('%')
+        }) {
+            start += 1;
+        }
+        if lines.get(start).is_some_and(|l| l.trim_end() == "---") {
+            start += 1;
+        }
+        let stop = (start..lines.len())
+            .find(|&i| {
+                let t = lines[i].trim_end();
+                t == "---" || t == "..." || t.starts_with("--- ")
+            })
+            .unwrap_or(lines.len());
+
+        let mut parser = Parser {
+            lines,
+            stop,
+            step: usize::MAX,
+        };
+        let (kind, end) = match parser.next_content(start) {
+            None => (Kind::Empty, start),
+            Some(first) => {
+                let col = parser.indent(first);
+                let (kind, end) = if is_dash(&lines[first][col..]) {
+                    let (items, end) = parser.sequence(first, col)?;
+                    (Kind::Sequence(items), end)
+                } else {
+                    let (entries, end) = parser.mapping(first, col)?;
+                    (Kind::Mapping(entries), end)
+                };
+                if parser.next_content(end).is_some() {
+                    return None;
+                }
+                (kind, end)
+            }
+        };
+        Some(Self {
+            lines,
+            root: Node {
+                key: String::new(),
+                line: start,
+                col: 0,
+                end,
+                kind,
+                value: None,
+            },
+            step: if parser.step == usize::MAX {
+                2
+            } else {
+                parser.step
+            },
+        })
+    }
+
+    /// Spaces per nesting level.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The node at a JSON pointer or dotted path; `""` is the document.
+    pub fn get(&self, path: &str) -> Option<&Node> {
+        let pointer = normalize_to_pointer(path);
+        if pointer.is_empty() || pointer == "/" {
+            return Some(&self.root);
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .try_fold(&self.root, |node, segment| {
+                let segment = segment.replace("~1", "/").replace("~0", "~");
+                match &node.kind {
+                    Kind::Mapping(children) | Kind::Sequence(children) => {
+                        children.iter().find(|c| c.key == segment)
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// The value written after the key of the node at `path`, without its
+    /// anchor, tag or comment; `None` unless it fits on the key's line.
+    pub fn scalar(&self, path: &str) -> Option<&'a str> {
+        let node = self.get(path)?;
+        let range = node.value.clone().filter(|_| node.end == node.line + 1)?;
+        Some(&self.lines[node.line][range])
+    }
+
+    /// Replace the value of the node at `path` with `text`, keeping the
+    /// key, anchor and comment around it. Only for one-line values.
+    pub fn set_scalar(&self, path: &str, text: &str) -> Option<Edit> {
+        let node = self.get(path)?;
+        let range = node.value.clone().filter(|_| node.end == node.line + 1)?;
+        let line = &self.lines[node.line];
+        Some(Edit {
+            target_line: node.line,
+            removed: vec![line.clone()],
+            inserted: vec![format!(
+                "{}{text}{}",
+                &line[..range.start],
+                &line[range.end..]
+            )],
+        })
+    }
+
+    /// Insert `entry` (its first line `key: ...`, nested lines indented by
+    /// multiples of [`step`](Self::step)) into the mapping at `path`. `None`
+    /// if that node holds a scalar, alias or flow collection.
+    pub fn insert(&self, path: &str, place: Place, entry: &[String]) -> Option<Edit> {
+        let node = self.get(path)?;
+        let is_root = std::ptr::eq(node, &self.root);
+        let (col, at) = match &node.kind {
+            Kind::Mapping(children) => {
+                let (first, last) = (children.first()?, children.last()?);
+                let at = match place {
+                    Place::Last => last.end,
+                    Place::First if is_root => first.line,
+                    // `- key: value` leaves no room before the first key.
+                    Place::First if first.line == node.line => first.end,
+                    Place::First => node.line + 1,
+                };
+                (first.col, at)
+            }
+            Kind::Empty if is_root => (0, node.end),
+            Kind::Empty => (node.col + self.step, node.line + 1),
+            _ => return None,
+        };
+        let pad = " ".repeat(col);
+        Some(Edit {
+            target_line: at,
+            removed: Vec::new(),
+            inserted: entry.iter().map(|l| format!("{pad}{l}")).collect(),
+        })
+    }
+
+    /// Insert `entry` as the sibling right after the mapping entry at
+    /// `path`, at its column.
+    pub fn insert_after(&self, path: &str, entry: &[String]) -> Option<Edit> {
+        let node = self.get(path)?;
+        if std::ptr::eq(node, &self.root) {
+            return None;
+        }
+        let pad = " ".repeat(node.col);
+        Some(Edit {
+            target_line: node.end,
+            removed: Vec::new(),
+            inserted: entry.iter().map(|l| format!("{pad}{l}")).collect(),
+        })
+    }
+
+    /// Remove the node at `path` with all its lines. `None` for the first
+    /// key of a `- key: value` item, which shares its line with the dash.
+    pub fn remove(&self, path: &str) -> Option<Edit> {
+        let node = self.get(path)?;
+        let line = self.lines.get(node.line)?;
+        if std::ptr::eq(node, &self.root) || line.len() - line.trim_start().len() != node.col {
+            return None;
+        }
+        Some(Edit {
+            target_line: node.line,
+            removed: self.lines[node.line..node.end].to_vec(),
+            inserted: Vec::new(),
+        })
+    }
+
+    /// The block scalar or multi-line value that 0-based position `at`
+    /// (between lines `at - 1` and `at`) falls inside, if any.
+    pub fn cut(&self, at: usize) -> Option<&Node> {
+        fn find(node: &Node, at: usize) -> Option<&Node> {
+            match &node.kind {
+                Kind::Mapping(children) | Kind::Sequence(children) => children
+                    .iter()
+                    .filter(|c| c.line < at && at < c.end)
+                    .find_map(|c| find(c, at)),
+                Kind::Inline | Kind::BlockScalar => {
+                    (node.line < at && at < node.end).then_some(node)
+                }
+                Kind::Empty => None,
+            }
+        }
+        find(&self.root, at)
+    }
+
+    /// `range` widened until neither end falls inside a value.
+    pub fn snap(&self, mut range: Range<usize>) -> Range<usize> {
+        while let Some(node) = self.cut(range.start) {
+            range.start = node.line;
+        }
+        while let Some(node) = self.cut(range.end) {
+            range.end = node.end;
+        }
+        range
+    }
+}
+
+impl Node {
+    /// What the node holds, for messages.
+    pub fn describe(&self) -> &'static str {
+        match self.kind {
+            Kind::BlockScalar => "block scalar",
+            Kind::Mapping(_) => "mapping",
+            Kind::Sequence(_) => "sequence",
+            Kind::Inline | Kind::Empty => "multi-line value",
+        }
+    }
+}
+
+struct Parser<'a> {
+    lines: &'a [String],
+    /// The end of the input or the next document's marker.
+    stop: usize,
+    /// Smallest indent between a key and its nested block seen so far.
+    step: usize,
+}
+
+impl Parser<'_> {
+    fn indent(&self, line: usize) -> usize {
+        let text = &self.lines[line];
+        text.len() - text.trim_start_matches(' ').len()
+    }
+
+    /// The first line from `from` on that isn't blank or a comment.
+    fn next_content(&self, from: usize) -> Option<usize> {
+        (from..self.stop).find(|&i| {
+            let t = self.lines[i].trim();
+            !t.is_empty() && !t.starts_with('#')
+        })
+    }
+
+    /// End of a value starting on `line` for a node at `col`: the lines
+    /// below indented deeper continue it, up to the last one not blank.
+    fn continuation(&self, line: usize, col: usize) -> usize {
+        let mut end = line + 1;
+        for i in line + 1..self.stop {
+            if self.lines[i].trim().is_empty() {
+                continue;
+            }
+            if self.indent(i) <= col {
+                break;
+            }
+            end = i + 1;
+        }
+        end
+    }
+
+    /// The entries of a block mapping whose keys sit at `col`, the first on
+    /// `start`, and the line after the last.
+    fn mapping(&mut self, start: usize, col: usize) -> Option<(Vec<Node>, usize)> {
+        let mut entries = Vec::new();
+        let mut line = start;
+        loop {
+            let (key, after) = split_key(&self.lines[line][col..])?;
+            let entry = self.value(key, line, col, col + after, false)?;
+            let end = entry.end;
+            entries.push(entry);
+            match self.next_content(end) {
+                Some(next) if self.indent(next) == col && !is_dash(&self.lines[next][col..]) => {
+                    line = next;
+                }
+                Some(next) if self.indent(next) > col => return None,
+                _ => return Some((entries, end)),
+            }
+        }
+    }
+
+    /// The items of a block sequence whose dashes sit at `col`.
+    fn sequence(&mut self, start: usize, col: usize) -> Option<(Vec<Node>, usize)> {
+        let mut items = Vec::new();
+        let mut line = start;
+        loop {
+            let text = &self.lines[line];
+            let after = &text[col + 1..];
+            let item_col = col + 1 + after.len() - after.trim_start_matches(' ').len();
+            let rest = strip_comment(&text[item_col..]);
+            let key = items.len().to_string();
+            let item = if is_dash(rest) {
+                let (nested, end) = self.sequence(line, item_col)?;
+                node(key, line, col, end, Kind::Sequence(nested))
+            } else if !rest.starts_with(['&', '!']) && split_key(rest).is_some() {
+                let (entries, end) = self.mapping(line, item_col)?;
+                node(key, line, col, end, Kind::Mapping(entries))
+            } else {
+                self.value(key, line, col, col + 1, true)?
+            };
+            let end = item.end;
+            items.push(item);
+            match self.next_content(end) {
+                Some(next) if self.indent(next) == col && is_dash(&self.lines[next][col..]) => {
+                    line = next;
+                }
+                Some(next) if self.indent(next) > col => return None,
+                _ => return Some((items, end)),
+            }
+        }
+    }
+
+    /// The node whose value starts at byte `at` of `line`, after the key or
+    /// dash at `col`.
+    fn value(
+        &mut self,
+        key: String,
+        line: usize,
+        col: usize,
+        at: usize,
+        item: bool,
+    ) -> Option<Node> {
+        let text = &self.lines[line];
+        // Skip the anchor and tag in front of the value.
+        let mut start = at;
+        loop {
+            let rest = &text[start..];
+            let trimmed = rest.trim_start();
+            start += rest.len() - trimmed.len();
+            if !trimmed.starts_with(['&', '!']) {
+                break;
+            }
+            start += trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        }
+        let value = strip_comment(&text[start..]).trim_end();
+
+        if value.starts_with(['|', '>']) {
+            let end = self.continuation(line, col);
+            return Some(node(key, line, col, end, Kind::BlockScalar));
+        }
+        if !value.is_empty() {
+            let end = self.continuation(line, col);
+            let mut inline = node(key, line, col, end, Kind::Inline);
+            inline.value = Some(start..start + value.len());
+            return Some(inline);
+        }
+
+        // The value, if any, is on the lines below.
+        let Some(next) = self.next_content(line + 1) else {
+            return Some(node(key, line, col, line + 1, Kind::Empty));
+        };
+        let indent = self.indent(next);
+        let below = &self.lines[next][indent.min(self.lines[next].len())..];
+        if indent > col {
+            if !item {
+                self.step = self.step.min(indent - col);
+            }
+            if is_dash(below) {
+                let (items, end) = self.sequence(next, indent)?;
+                return Some(node(key, line, col, end, Kind::Sequence(items)));
+            }
+            if split_key(below).is_some() {
+                let (entries, end) = self.mapping(next, indent)?;
+                return Some(node(key, line, col, end, Kind::Mapping(entries)));
+            }
+            let end = self.continuation(line, col);
+            return Some(node(key, line, col, end, Kind::Inline));
+        }
+        // A mapping's sequence may sit at the key's own indent.
+        if !item && indent == col && is_dash(below) {
+            let (items, end) = self.sequence(next, indent)?;
+            return Some(node(key, line, col, end, Kind::Sequence(items)));
+        }
+        Some(node(key, line, col, line + 1, Kind::Empty))
+    }
+}
+
+fn node(key: String, line: usize, col: usize, end: usize, kind: Kind) -> Node {
+    Node {
+        key,
+        line,
+        col,
+        end,
+        kind,
+        value: None,
+    }
+}
+
+fn is_dash(text: &str) -> bool {
+    text == "-" || text.starts_with("- ") || text.starts_with("-\t")
+}
+
+/// The key at the start of `text` and the byte offset just past its colon.
+fn split_key(text: &str) -> Option<(String, usize)> {
+    let (key, after) = match text.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let close = closing_quote(text, quote)?;
+            let key: String = serde_yaml::from_str(&text[..=close]).ok()?;
+            (key, close + 1)
+        }
+        c => {
+            // Indicators can't start a plain key, except `-`, `?` and `:`
+            // glued to what follows.
+            if "-?:,[]{}#&*!|>%@`".contains(c)
+                && !(matches!(c, '-' | '?' | ':')
+                    && text[1..].starts_with(|n: char| !n.is_whitespace()))
+            {
+                return None;
+            }
+            let colon = text.char_indices().find_map(|(i, c)| {
+                let ends = text[i + 1..].chars().next().is_none_or(char::is_whitespace);
+                (c == ':' && ends).then_some(i)
+            })?;
+            if text[..colon].contains(" #") {
+                return None;
+            }
+            (text[..colon].trim_end().to_string(), colon)
+        }
+    };
+    let rest = &text[after..];
+    let colon = after + rest.len() - rest.trim_start().len();
+    let ends = text[colon + 1..]
+        .chars()
+        .next()
+        .is_none_or(char::is_whitespace);
+    (text[colon..].starts_with(':') && ends && !key.is_empty()).then_some((key, colon + 1))
+}
+
+/// Byte index of the quote closing the quoted scalar starting `text`.
+fn closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            c if c == quote => {
+                // `''` is an escaped quote in single-quoted scalars.
+                if quote == '\'' && chars.peek().is_some_and(|&(_, n)| n == '\'') {
+                    chars.next();
+                } else {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "\
+# Pet store
+openapi: 3.0.0 # current
+info:
+  title: Pet Store
+  description: |
+    Pets: cats and dogs.
+
+    # Not a comment
+  version: '1.0'
+paths:
+  /pets:
+    get: &list
+      operationId: listPets
+      tags:
+      - pets
+      parameters:
+        - name: limit
+          in: query
+    head: *list
+components: {}
+";
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn nodes_span_their_values() {
+        let lines = lines(SPEC);
+        let doc = Document::parse(&lines).unwrap();
+        let description = doc.get("/info/description").unwrap();
+        assert!(matches!(description.kind, Kind::BlockScalar));
+        assert_eq!((description.line, description.end), (4, 8));
+        assert_eq!(doc.get("/info").unwrap().end, 9);
+        assert_eq!(doc.get("/paths/~1pets/get/tags/0").unwrap().line, 14);
+        assert_eq!(doc.get("paths./pets.get.parameters.0.in").unwrap().line, 17);
+        assert_eq!(doc.scalar("/openapi"), Some("3.0.0"));
+        assert_eq!(doc.scalar("/paths/~1pets/head"), Some("*list"));
+        assert_eq!(doc.scalar("/info/version"), Some("'1.0'"));
+        assert!(doc.get("/info/description/Pets").is_none());
+        assert_eq!(doc.step(), 2);
+    }
+
+    #[test]
+    fn inserts_follow_the_structure() {
+        let lines = lines(SPEC);
+        let doc = Document::parse(&lines).unwrap();
+        // After the last entry, not inside the block scalar above it.
+        let edit = doc
+            .insert(
+                "/info",
+                Place::Last,
+                &["contact:".into(), "  name: x".into()],
+            )
+            .unwrap();
+        assert_eq!(edit.target_line, 9);
+        assert_eq!(edit.inserted, ["  contact:", "    name: x"]);
+
+        let edit = doc
+            .insert("/paths/~1pets/get", Place::First, &["summary: List".into()])
+            .unwrap();
+        assert_eq!(
+            (edit.target_line, edit.inserted[0].as_str()),
+            (12, "      summary: List")
+        );
+
+        let edit = doc
+            .insert(
+                "/paths/~1pets/get/parameters/0",
+                Place::First,
+                &["required: true".into()],
+            )
+            .unwrap();
+        assert_eq!(edit.target_line, 17);
+        assert_eq!(edit.inserted, ["          required: true"]);
+
+        assert_eq!(
+            doc.insert("", Place::Last, &["x: 1".into()])
+                .unwrap()
+                .target_line,
+            20
+        );
+        // Aliases and flow collections have no block to add to.
+        assert!(doc.insert("/paths/~1pets/head", Place::Last, &[]).is_none());
+        assert!(doc.insert("/components", Place::Last, &[]).is_none());
+    }
+
+    #[test]
+    fn scalars_are_replaced_in_place() {
+        let lines = lines(SPEC);
+        let doc = Document::parse(&lines).unwrap();
+        let edit = doc.set_scalar("/openapi", "3.1.0").unwrap();
+        assert_eq!(edit.inserted, ["openapi: 3.1.0 # current"]);
+        let edit = doc.remove("/info/description").unwrap();
+        assert_eq!((edit.target_line, edit.removed.len()), (4, 4));
+        assert!(doc.remove("/paths/~1pets/get/parameters/0/name").is_none());
+    }
+
+    #[test]
+    fn positions_inside_values_are_cuts() {
+        let lines = lines(SPEC);
+        let doc = Document::parse(&lines).unwrap();
+        assert!(doc.cut(6).is_some());
+        assert!(doc.cut(4).is_none());
+        assert!(doc.cut(7).is_some());
+        assert!(doc.cut(8).is_none());
+        assert_eq!(doc.snap(6..7), 4..8);
+    }
+
+    #[test]
+    fn flow_documents_have_no_structure() {
+        assert!(Document::parse(&lines("{\"openapi\": \"3.0.0\"}")).is_none());
+        let marked = lines("---\nopenapi: 3.0.0\n...\n");
+        let doc = Document::parse(&marked).unwrap();
+        assert_eq!(doc.scalar("/openapi"), Some("3.0.0"));
+    }
+}
@@ -0,0 +1,304 @@
+use lazyoav::migrate;
+
+use crate::log_parser::LintError;
+use crate::spec::normalize_to_pointer;
+
+use super::document::Document;
+use super::{Edit, FixProposal, gather_context};
+
+/// Rewrite for a 3.1 migration finding. Works on YAML written in block
+/// style; flow mappings and JSON specs get no proposal.
+pub fn propose(error: &LintError, lines: &[String]) -> Option<FixProposal> {
+    let doc = Document::parse(lines)?;
+    let pointer = normalize_to_pointer(error.json_path.as_deref()?);
+    let (description, first, removed, inserted) = match error.rule.as_str() {
+        migrate::OPENAPI_VERSION => openapi_version(&doc, &pointer)?,
+        migrate::WEBHOOKS => webhooks(&doc, &pointer)?,
+        migrate::NULLABLE => nullable(&doc, lines, &pointer)?,
+        migrate::EXCLUSIVE_BOUND => exclusive_bound(&doc, lines, &pointer)?,
+        migrate::SCHEMA_EXAMPLE => example(&doc, lines, &pointer)?,
+        _ => return None,
+    };
+    let (context_before, _) = gather_context(lines, first, 3);
+    let (_, context_after) = gather_context(lines, first + removed, 3);
+    Some(FixProposal {
+        rule: error.rule.clone(),
+        description: description.into(),
+        target_line: first - 1,
+        context_before,
+        removed: lines[first - 1..first - 1 + removed].to_vec(),
+        inserted,
+        context_after,
+        edits: Vec::new(),
+        spec_hash: None,
+    })
+}
+
+/// What a rewrite does, the first 1-based line it replaces, how many lines
+/// it replaces and what goes in their place.
+type Rewrite = (&'static str, usize, usize, Vec<String>);
+
+fn openapi_version(doc: &Document, pointer: &str) -> Option<Rewrite> {
+    let value = doc.scalar(pointer)?;
+    let version = value.trim_matches(|c| c == '"' || c == '\'');
+    if !version.starts_with("3.0") {
+        return None;
+    }
+    let edit = doc.set_scalar(pointer, &value.replacen(version, "3.1.0", 1))?;
+    Some((
+        "Declare OpenAPI 3.1.0",
+        edit.target_line + 1,
+        1,
+        edit.inserted,
+    ))
+}
+
+/// Add an empty `webhooks` section after the block at `pointer`.
+fn webhooks(doc: &Document, pointer: &str) -> Option<Rewrite> {
+    let edit = doc.insert_after(pointer, &["webhooks: {}".to_string()])?;
+    Some((
+        "Add an empty 'webhooks' section",
+        edit.target_line + 1,
+        0,
+        edit.inserted,
+    ))
+}
+
+fn nullable(doc: &Document, lines: &[String], pointer: &str) -> Option<Rewrite> {
+    match word(doc.scalar(pointer)?) {
+        "false" => {
+            let edit = doc.remove(pointer)?;
+            let count = edit.removed.len();
+            Some((
+                "Drop 'nullable: false'",
+                edit.target_line + 1,
+                count,
+                Vec::new(),
+            ))
+        }
+        "true" => {
+            let type_pointer = sibling(pointer, "type");
+            let ty = doc.scalar(&type_pointer)?;
+            if ty.starts_with('[') {
+                return None;
+            }
+            let typed = doc.set_scalar(&type_pointer, &format!("[{ty}, \"null\"]"))?;
+            let (first, count, inserted) = rewrite_pair(lines, doc.remove(pointer)?, typed);
+            Some((
+                "Replace 'nullable' with a type array",
+                first,
+                count,
+                inserted,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn exclusive_bound(doc: &Document, lines: &[String], pointer: &str) -> Option<Rewrite> {
+    let bound = match pointer.rsplit('/').next()? {
+        "exclusiveMinimum" => "minimum",
+        "exclusiveMaximum" => "maximum",
+        _ => return None,
+    };
+    match word(doc.scalar(pointer)?) {
+        "false" => {
+            let edit = doc.remove(pointer)?;
+            let count = edit.removed.len();
+            Some((
+                "Drop the false exclusive bound",
+                edit.target_line + 1,
+                count,
+                Vec::new(),
+            ))
+        }
+        "true" => {
+            let bound_pointer = sibling(pointer, bound);
+            let limit = word(doc.scalar(&bound_pointer)?);
+            let exclusive = doc.set_scalar(pointer, limit)?;
+            let (first, count, inserted) =
+                rewrite_pair(lines, doc.remove(&bound_pointer)?, exclusive);
+            Some(("Make the exclusive bound numeric", first, count, inserted))
+        }
+        _ => None,
+    }
+}
+
+/// Turn `example: x`, or an `example:` block, into a one-entry `examples`
+/// list.
+fn example(doc: &Document, lines: &[String], pointer: &str) -> Option<Rewrite> {
+    let node = doc.get(pointer)?;
+    let text = &lines[node.line];
+    // What precedes the key: its indent, or the dash of a list item.
+    let prefix = &text[..node.col];
+    let indent = " ".repeat(node.col);
+    let (_, value) = text[node.col..].split_once(':')?;
+    let value = value.trim();
+    let mut inserted = vec![format!("{prefix}examples:")];
+    let mut children = lines[node.line + 1..node.end].iter();
+    if value.is_empty() {
+        // The first line of the block opens the list entry.
+        let first = children.find(|l| !l.trim().is_empty())?;
+        let child_indent = &first[..first.len() - first.trim_start().len()];
+        inserted.push(format!("{child_indent}- {}", first.trim_start()));
+    } else {
+        inserted.push(format!("{indent}  - {value}"));
+    }
+    inserted.extend(children.map(|l| {
+        if l.trim().is_empty() {
+            l.clone()
+        } else {
+            format!("  {l}")
+        }
+    }));
+    Some((
+        "Replace 'example' with an 'examples' list",
+        node.line + 1,
+        node.end - node.line,
+        inserted,
+    ))
+}
+
+/// Combine dropping the lines of `dropped` and replacing the line of
+/// `kept` into one rewrite of the lines between them. Returns the first
+/// 1-based line, the number of lines replaced, and their replacement.
+fn rewrite_pair(lines: &[String], dropped: Edit, kept: Edit) -> (usize, usize, Vec<String>) {
+    let dropped_lines = dropped.target_line..dropped.target_line + dropped.removed.len();
+    let first = dropped_lines.start.min(kept.target_line);
+    let end = dropped_lines.end.max(kept.target_line + 1);
+    let mut replacement = kept.inserted.into_iter();
+    let inserted = (first..end)
+        .filter(|n| !dropped_lines.contains(n))
+        .flat_map(|n| {
+            if n == kept.target_line {
+                replacement.by_ref().collect()
+            } else {
+                vec![lines[n].clone()]
+            }
+        })
+        .collect();
+    (first + 1, end - first, inserted)
+}
+
+/// The pointer of `key` next to the node at `pointer`, in the same mapping.
+fn sibling(pointer: &str, key: &str) -> String {
+    let parent = pointer.rsplit_once('/').map_or("", |(parent, _)| parent);
+    format!("{parent}/{key}")
+}
+
+/// The value without a trailing comment.
+fn word(value: &str) -> &str {
+    value.split_whitespace().next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_parser::Severity;
+
+    fn finding(rule: &str, pointer: &str) -> LintError {
+        LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: Severity::Info,
+            rule: rule.into(),
+            message: String::new(),
+            json_path: Some(pointer.into()),
+        }
+    }
+
+    /// Apply the proposal for `rule` at `pointer` to `spec` in memory.
+    fn migrated(spec: &str, rule: &str, pointer: &str) -> Option<String> {
+        let lines: Vec<String> = spec.lines().map(String::from).collect();
+        let p = propose(&finding(rule, pointer), &lines)?;
+        let mut out = lines.clone();
+        out.splice(
+            p.target_line..p.target_line + p.removed.len(),
+            p.inserted.iter().cloned(),
+        );
+        Some(out.join("\n"))
+    }
+
+    const SPEC: &str = "\
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      responses: {}
+components:
+  schemas:
+    Pet:
+      properties:
+        name:
+          type: string
+          description: Name
+          nullable: true
+        age:
+          minimum: 0
+          exclusiveMinimum: true
+          example: 3
+        tags:
+          example:
+            - a
+            - b
+          nullable: false";
+
+    #[test]
+    fn propose_rewrites_schema_keywords() {
+        let name = "/components/schemas/Pet/properties/name";
+        assert_eq!(
+            migrated(SPEC, migrate::NULLABLE, &format!("{name}/nullable")).unwrap(),
+            SPEC.replace(
+                "          type: string\n          description: Name\n          nullable: true\n",
+                "          type: [string, \"null\"]\n          description: Name\n"
+            )
+        );
+
+        let age = "/components/schemas/Pet/properties/age";
+        assert_eq!(
+            migrated(
+                SPEC,
+                migrate::EXCLUSIVE_BOUND,
+                &format!("{age}/exclusiveMinimum")
+            )
+            .unwrap(),
+            SPEC.replace(
+                "          minimum: 0\n          exclusiveMinimum: true\n",
+                "          exclusiveMinimum: 0\n"
+            )
+        );
+        assert_eq!(
+            migrated(SPEC, migrate::SCHEMA_EXAMPLE, &format!("{age}/example")).unwrap(),
+            SPEC.replace("example: 3\n", "examples:\n            - 3\n")
+        );
+
+        let tags = "/components/schemas/Pet/properties/tags";
+        assert_eq!(
+            migrated(SPEC, migrate::SCHEMA_EXAMPLE, &format!("{tags}/example")).unwrap(),
+            SPEC.replace(
+                "example:\n            - a\n            - b\n",
+                "examples:\n            - - a\n              - b\n"
+            )
+        );
+        assert_eq!(
+            migrated(SPEC, migrate::NULLABLE, &format!("{tags}/nullable")).unwrap(),
+            SPEC.replace("\n          nullable: false", "")
+        );
+    }
+
+    #[test]
+    fn propose_bumps_the_version_and_adds_webhooks() {
+        assert_eq!(
+            migrated(SPEC, migrate::OPENAPI_VERSION, "/openapi").unwrap(),
+            SPEC.replace("3.0.3", "3.1.0")
+        );
+        assert_eq!(
+            migrated(SPEC, migrate::WEBHOOKS, "/paths").unwrap(),
+            SPEC.replace("responses: {}\n", "responses: {}\nwebhooks: {}\n")
+        );
+
+        let json = "{\"openapi\": \"3.0.3\"}";
+        assert!(migrated(json, migrate::OPENAPI_VERSION, "/openapi").is_none());
+    }
+}
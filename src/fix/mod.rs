@@ -1,13 +1,28 @@
 // Fix workflow — propose and apply mechanical fixes for lint errors.
+mod describe;
+mod document;
+mod migrate;
+mod operation_id;
+mod rename;
 mod rules;
 
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use lazyoav::assist::{self, AssistRequest};
+use lazyoav::config::{Config, FixAssistant, Format};
+use lazyoav::format;
+use lazyoav::naming;
+use lazyoav::operation_ids;
+use lazyoav::plugins::{FixRequest, Registry};
 
 use crate::log_parser::LintError;
 use crate::spec::SpecIndex;
 
+use document::Document;
+
 /// A proposed fix for a lint error, ready for preview and application.
 pub struct FixProposal {
     /// The lint rule that triggered this fix.
@@ -18,13 +33,76 @@ pub struct FixProposal {
     pub target_line: usize,
     /// A few lines before the insertion point (for diff preview).
     pub context_before: Vec<String>,
+    /// The lines after `target_line` that `inserted` replaces; empty when
+    /// the fix only adds lines.
+    pub removed: Vec<String>,
     /// The new lines to insert.
     pub inserted: Vec<String>,
     /// A few lines after the insertion point (for diff preview).
     pub context_after: Vec<String>,
+    /// Changes elsewhere in the spec applied along with this one, e.g. the
+    /// references to a renamed definition.
+    pub edits: Vec<Edit>,
+    /// Hash of the spec the proposal was made against. Applying it fails
+    /// once the file no longer matches, as its lines may have moved.
+    pub spec_hash: Option<u64>,
+}
+
+/// What a proposal was made for, so it can be made again when the spec
+/// changes underneath it.
+#[derive(Clone)]
+pub enum FixOrigin {
+    Finding(LintError),
+    Format,
+}
+
+/// A further change a [`FixProposal`] makes: the `removed` lines starting
+/// at 0-based `target_line` become `inserted`.
+pub struct Edit {
+    pub target_line: usize,
+    pub removed: Vec<String>,
+    pub inserted: Vec<String>,
+}
+
+impl FixProposal {
+    fn made_against(mut self, spec_hash: u64) -> Self {
+        self.spec_hash = Some(spec_hash);
+        self
+    }
+
+    /// The main change with its context lines, before and after, and the
+    /// 0-based line the context starts at: all there is to preview when the
+    /// spec itself isn't at hand.
+    pub fn own_lines(&self) -> (usize, Vec<String>, Vec<String>) {
+        let start = self.target_line.saturating_sub(self.context_before.len());
+        let around = |middle: &[String]| {
+            [&self.context_before[..], middle, &self.context_after[..]].concat()
+        };
+        (start, around(&self.removed), around(&self.inserted))
+    }
+
+    /// Every change the proposal makes as `(0-based line, removed,
+    /// inserted)`, from the bottom of the file up so applying them in order
+    /// keeps line numbers valid.
+    fn hunks(&self) -> Vec<(usize, &[String], &[String])> {
+        let mut hunks: Vec<_> = std::iter::once((
+            self.target_line,
+            self.removed.as_slice(),
+            self.inserted.as_slice(),
+        ))
+        .chain(
+            self.edits
+                .iter()
+                .map(|e| (e.target_line, e.removed.as_slice(), e.inserted.as_slice())),
+        )
+        .collect();
+        hunks.sort_by_key(|h| std::cmp::Reverse(h.0));
+        hunks
+    }
 }
 
-/// Try to generate a fix proposal for the given lint error.
+/// Try to generate a built-in fix proposal for the given lint error. Rules
+/// without one can be offered to the plugins with [`propose_plugin_fix`].
 ///
 /// Returns:
 /// - `Ok(Some(..))` if a fix was successfully generated,
@@ -34,22 +112,387 @@ pub fn propose_fix(
     error: &LintError,
     spec_index: &SpecIndex,
     spec_path: &Path,
+    cfg: &Config,
 ) -> Result<Option<FixProposal>> {
-    let lines = read_spec_lines(spec_path)?;
+    let (lines, hash) = read_spec(spec_path)?;
 
     let proposal = match error.rule.as_str() {
-        "operation-summary" => rules::propose_operation_summary(error, spec_index, &lines),
-        "operation-description" => rules::propose_operation_description(error, spec_index, &lines),
-        "info-contact" => rules::propose_info_contact(error, spec_index, &lines),
-        "info-license" => rules::propose_info_license(error, spec_index, &lines),
+        "operation-summary" => rules::propose_operation_summary(error, &lines, &cfg.fix_text),
+        "operation-description" => {
+            rules::propose_operation_description(error, &lines, &cfg.fix_text)
+        }
+        "info-contact" => rules::propose_info_contact(error, &lines),
+        "info-license" => rules::propose_info_license(error, &lines),
+        rule if lazyoav::migrate::is_migration(rule) => migrate::propose(error, &lines),
+        operation_ids::DUPLICATE | operation_ids::CONFLICTING => {
+            operation_id::propose(error, spec_index, &lines)
+        }
+        naming::SCHEMA | naming::PROPERTY | naming::PATH => {
+            rename::propose(error, spec_index, &lines, &cfg.naming)
+        }
         _ => None,
     };
 
-    Ok(proposal)
+    Ok(proposal.map(|p| p.made_against(hash)))
+}
+
+/// Ask the plugins for a fix, checking it fits the spec before previewing.
+/// Plugins are host commands that may take a while, so run it off the UI
+/// thread.
+pub fn propose_plugin_fix(
+    error: &LintError,
+    spec_path: &Path,
+    plugins: &Registry,
+) -> Result<Option<FixProposal>> {
+    let (lines, hash) = read_spec(spec_path)?;
+    let request = FixRequest {
+        rule: &error.rule,
+        message: &error.message,
+        json_path: error.json_path.as_deref(),
+        line: error.line,
+        spec_lines: &lines,
+    };
+    let Some(fix) = plugins.propose(&request)? else {
+        return Ok(None);
+    };
+    let end = fix.target_line + fix.removed.len();
+    if end > lines.len() || lines[fix.target_line..end] != fix.removed {
+        anyhow::bail!(
+            "plugin fix for '{}' doesn't match the spec at line {}",
+            error.rule,
+            fix.target_line + 1
+        );
+    }
+    Ok(Some(
+        spliced_proposal(
+            &error.rule,
+            fix.description,
+            &lines,
+            fix.target_line,
+            fix.removed,
+            fix.inserted,
+        )
+        .made_against(hash),
+    ))
+}
+
+/// Ask the fix assistant for a fix, for a finding nothing else can fix.
+/// Blocks until it answers, so run it off the UI thread.
+pub fn propose_assisted_fix(
+    error: &LintError,
+    rule_description: Option<String>,
+    spec_path: &Path,
+    root: &Path,
+    assistant: &FixAssistant,
+) -> Result<Option<FixProposal>> {
+    let (lines, hash) = read_spec(spec_path)?;
+    // Findings located by their path alone have no line; send the lines
+    // around what the path points at, never the top of the file.
+    let line = match error.line {
+        0 => error
+            .json_path
+            .as_deref()
+            .and_then(|path| {
+                crate::spec::parse_spec(&lines.join("\n"))
+                    .ok()?
+                    .resolve(path)
+            })
+            .map(|span| span.line)
+            .with_context(|| format!("Can't tell where the '{}' finding is", error.rule))?,
+        line => line,
+    };
+    let mut request = AssistRequest::new(
+        &error.rule,
+        &error.message,
+        error.json_path.as_deref(),
+        line,
+        &lines,
+        assistant.context_lines,
+    );
+    request.rule_description = rule_description;
+    let Some(suggestion) = assist::suggest(root, assistant, &request)? else {
+        return Ok(None);
+    };
+    let Some(start) = suggestion.locate(&lines) else {
+        anyhow::bail!(
+            "the suggested fix for '{}' doesn't match the spec at line {}",
+            error.rule,
+            suggestion.line
+        );
+    };
+    let proposal = spliced_proposal(
+        &error.rule,
+        format!("{} (suggested)", suggestion.description),
+        &lines,
+        start,
+        suggestion.removed,
+        suggestion.inserted,
+    );
+    Ok(Some(proposal.made_against(hash)))
+}
+
+/// A proposal replacing `removed`, which starts at 0-based `start` in
+/// `lines`, with `inserted`.
+fn spliced_proposal(
+    rule: &str,
+    description: String,
+    lines: &[String],
+    start: usize,
+    removed: Vec<String>,
+    inserted: Vec<String>,
+) -> FixProposal {
+    let (context_before, _) = gather_context(lines, start + 1, 3);
+    let (_, context_after) = gather_context(lines, start + removed.len() + 1, 3);
+    FixProposal {
+        rule: rule.to_string(),
+        description,
+        target_line: start,
+        context_before,
+        removed,
+        inserted,
+        context_after,
+        edits: Vec::new(),
+        spec_hash: None,
+    }
+}
+
+/// A proposal rewriting the spec in the canonical layout of `style`, with
+/// one edit per changed region. `None` if the spec is already formatted.
+pub fn propose_format(spec_path: &Path, style: &Format) -> Result<Option<FixProposal>> {
+    let content = std::fs::read_to_string(spec_path)?;
+    let is_json = spec_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let mut losses = Vec::new();
+    let formatted = if is_json {
+        format::json(&content, style)?
+    } else {
+        if format::has_comments(&content) {
+            losses.push("drops its comments");
+        }
+        if format::has_anchors(&content) {
+            losses.push("expands its anchors into copies");
+        }
+        format::yaml(&content, style)?
+    };
+    let description = match losses.as_slice() {
+        [] => "Format the spec".to_string(),
+        losses => format!("Format the spec \u{2014} {}", losses.join(" and ")),
+    };
+    let lines: Vec<String> = content.lines().map(String::from).collect();
+    let formatted: Vec<String> = formatted.lines().map(String::from).collect();
+
+    // Widen each changed region to whole block scalars and multi-line
+    // values, so no edit lands inside one.
+    let doc = Document::parse(&lines);
+    let mut regions: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+    let ops = similar::capture_diff_slices(similar::Algorithm::Myers, &lines, &formatted);
+    for group in similar::group_diff_ops(ops, 0) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let (_, first_old, _) = first.as_tag_tuple();
+        let (_, last_old, last_new) = last.as_tag_tuple();
+        let old = first_old.start..last_old.end;
+        let mut span = doc
+            .as_ref()
+            .map_or(old.clone(), |doc| doc.snap(old.clone()));
+        while let Some((prev, _)) = regions.last()
+            && span.start <= prev.end
+        {
+            span = span.start.min(prev.start)..span.end.max(prev.end);
+            regions.pop();
+        }
+        // How far lines have moved before the region and after it.
+        let before = regions
+            .last()
+            .map_or(0, |(old, new)| new.end as isize - old.end as isize);
+        let after = last_new.end as isize - last_old.end as isize;
+        let new = span.start.saturating_add_signed(before)..span.end.saturating_add_signed(after);
+        regions.push((span, new));
+    }
+    let mut edits: Vec<Edit> = regions
+        .into_iter()
+        .map(|(old, new)| Edit {
+            target_line: old.start,
+            removed: lines[old].to_vec(),
+            inserted: formatted[new].to_vec(),
+        })
+        .collect();
+    if edits.is_empty() {
+        return Ok(None);
+    }
+    let first = edits.remove(0);
+    let mut proposal = spliced_proposal(
+        "format",
+        description,
+        &lines,
+        first.target_line,
+        first.removed,
+        first.inserted,
+    );
+    proposal.edits = edits;
+    Ok(Some(proposal.made_against(content_hash(&content))))
+}
+
+/// Fix proposals for every finding in `errors` that has one, e.g. all of a
+/// spec's 3.1 migration findings for applying in one go.
+pub fn propose_all(
+    errors: &[LintError],
+    spec_index: &SpecIndex,
+    spec_path: &Path,
+    cfg: &Config,
+) -> Result<Vec<FixProposal>> {
+    let mut proposals = Vec::new();
+    for error in errors {
+        proposals.extend(propose_fix(error, spec_index, spec_path, cfg)?);
+    }
+    Ok(proposals)
 }
 
-/// Apply a fix proposal by inserting lines into the spec file.
+/// Apply a fix proposal by inserting lines into the spec file, along with
+/// its further edits. Nothing is written if the spec changed since the
+/// proposal was made, if any edit no longer fits or cuts into a block
+/// scalar or multi-line value, or if it leaves a spec that parsed before
+/// unparseable.
 pub fn apply_fix(proposal: &FixProposal, spec_path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(spec_path)?;
+    if stale(proposal, &content) {
+        anyhow::bail!("The spec changed since the fix was proposed");
+    }
+    let lines: Vec<String> = content.lines().map(String::from).collect();
+    if let Some(doc) = Document::parse(&lines) {
+        check_structure(&doc, proposal)?;
+    }
+    let lines = applied(proposal, &lines)?;
+    check_parses(&content, &lines)?;
+    write_lines(spec_path, &lines, content.ends_with('\n'))
+}
+
+/// `lines` with every hunk of `proposal` applied, e.g. for previewing the
+/// whole change. Fails if a hunk no longer fits.
+pub fn applied(proposal: &FixProposal, lines: &[String]) -> Result<Vec<String>> {
+    let mut lines = lines.to_vec();
+    for (at, removed, inserted) in proposal.hunks() {
+        splice(&mut lines, at, removed, inserted)?;
+    }
+    Ok(lines)
+}
+
+/// Apply `proposals` in a single write, from the bottom of the file up so
+/// line numbers stay valid. A proposal touching lines another one already
+/// changed, or cutting into a block scalar or multi-line value, is skipped;
+/// re-proposing after the re-run picks it up. Returns how many were
+/// applied.
+pub fn apply_all(proposals: &[FixProposal], spec_path: &Path) -> Result<usize> {
+    let content = std::fs::read_to_string(spec_path)?;
+    if proposals.iter().any(|p| stale(p, &content)) {
+        anyhow::bail!("The spec changed since the fixes were proposed");
+    }
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    let doc = Document::parse(&lines);
+    let mut ordered: Vec<(&FixProposal, usize, usize)> = proposals
+        .iter()
+        .filter(|p| {
+            doc.as_ref()
+                .is_none_or(|doc| check_structure(doc, p).is_ok())
+        })
+        .map(|p| {
+            let hunks = p.hunks();
+            let first = hunks.iter().map(|h| h.0).min().unwrap_or(p.target_line);
+            let end = hunks.iter().map(|h| h.0 + h.1.len()).max().unwrap_or(first);
+            (p, first, end)
+        })
+        .collect();
+    drop(doc);
+    ordered.sort_by_key(|&(_, first, _)| std::cmp::Reverse(first));
+    let mut applied = 0;
+    // First line (0-based) of the topmost change so far.
+    let mut floor = usize::MAX;
+    for (proposal, first, end) in ordered {
+        if end > floor {
+            continue;
+        }
+        for (at, removed, inserted) in proposal.hunks() {
+            splice(&mut lines, at, removed, inserted)?;
+        }
+        floor = first;
+        applied += 1;
+    }
+
+    check_parses(&content, &lines)?;
+    write_lines(spec_path, &lines, content.ends_with('\n'))?;
+    Ok(applied)
+}
+
+/// Fail if a hunk of `proposal` starts or ends inside a block scalar or a
+/// multi-line value of `doc`. Rewriting lines in place, each keeping its
+/// indent, is fine: the value stays in one piece.
+fn check_structure(doc: &Document, proposal: &FixProposal) -> Result<()> {
+    for (at, removed, inserted) in proposal.hunks() {
+        if same_shape(removed, inserted) {
+            continue;
+        }
+        for edge in [at, at + removed.len()] {
+            if let Some(node) = doc.cut(edge) {
+                anyhow::bail!(
+                    "The fix would cut into the {} at line {}",
+                    node.describe(),
+                    node.line + 1
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn same_shape(removed: &[String], inserted: &[String]) -> bool {
+    let indent = |line: &String| line.len() - line.trim_start().len();
+    removed.len() == inserted.len()
+        && removed
+            .iter()
+            .zip(inserted)
+            .all(|(old, new)| indent(old) == indent(new) && !new.trim().is_empty())
+}
+
+/// Fail if `lines` no longer parse while `original` did, e.g. an edit
+/// left an alias without its anchor.
+fn check_parses(original: &str, lines: &[String]) -> Result<()> {
+    if serde_yaml::from_str::<serde_yaml::Value>(original).is_err() {
+        return Ok(());
+    }
+    if let Err(e) = serde_yaml::from_str::<serde_yaml::Value>(&lines.join("\n")) {
+        anyhow::bail!("The fix would break the spec: {e}");
+    }
+    Ok(())
+}
+
+/// Replace `original`, the spec lines starting at 1-based `first_line`, with
+/// `replacement`. Fails without writing if the lines have changed on disk
+/// since they were read.
+pub fn apply_edit(
+    spec_path: &Path,
+    first_line: usize,
+    original: &[String],
+    replacement: &[String],
+) -> Result<()> {
+    splice_lines(
+        spec_path,
+        first_line.saturating_sub(1),
+        original,
+        replacement,
+    )
+}
+
+/// Replace the `expected` lines at 0-based index `at` with `inserted`,
+/// keeping the file's trailing newline.
+fn splice_lines(
+    spec_path: &Path,
+    at: usize,
+    expected: &[String],
+    inserted: &[String],
+) -> Result<()> {
     let content = std::fs::read_to_string(spec_path)?;
     let mut lines: Vec<String> = content.lines().map(String::from).collect();
 
@@ -57,19 +500,31 @@ pub fn apply_fix(proposal: &FixProposal, spec_path: &Path) -> Result<()> {
     // our split dropped it, we'll restore it when writing back.
     let trailing_newline = content.ends_with('\n');
 
-    if proposal.target_line > lines.len() {
+    splice(&mut lines, at, expected, inserted)?;
+    write_lines(spec_path, &lines, trailing_newline)
+}
+
+fn splice(
+    lines: &mut Vec<String>,
+    at: usize,
+    expected: &[String],
+    inserted: &[String],
+) -> Result<()> {
+    if at + expected.len() > lines.len() {
         anyhow::bail!(
             "target_line {} is beyond file length {}",
-            proposal.target_line,
+            at + expected.len(),
             lines.len()
         );
     }
-
-    // Insert after target_line (1-based), so the vec index is target_line.
-    for (i, new_line) in proposal.inserted.iter().enumerate() {
-        lines.insert(proposal.target_line + i, new_line.clone());
+    if lines[at..at + expected.len()] != *expected {
+        anyhow::bail!("line {} changed on disk", at + 1);
     }
+    lines.splice(at..at + expected.len(), inserted.iter().cloned());
+    Ok(())
+}
 
+fn write_lines(spec_path: &Path, lines: &[String], trailing_newline: bool) -> Result<()> {
     let mut output = lines.join("\n");
     if trailing_newline {
         output.push('\n');
@@ -78,9 +533,32 @@ pub fn apply_fix(proposal: &FixProposal, spec_path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn read_spec_lines(spec_path: &Path) -> Result<Vec<String>> {
+/// The spec's lines and the hash of its content.
+fn read_spec(spec_path: &Path) -> Result<(Vec<String>, u64)> {
     let content = std::fs::read_to_string(spec_path)?;
-    Ok(content.lines().map(String::from).collect())
+    Ok((
+        content.lines().map(String::from).collect(),
+        content_hash(&content),
+    ))
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether the spec changed since `proposal` was made, so applying it
+/// would be refused. An unreadable spec counts as unchanged; applying
+/// reports that.
+pub fn is_stale(proposal: &FixProposal, spec_path: &Path) -> bool {
+    std::fs::read_to_string(spec_path).is_ok_and(|content| stale(proposal, &content))
+}
+
+fn stale(proposal: &FixProposal, content: &str) -> bool {
+    proposal
+        .spec_hash
+        .is_some_and(|hash| hash != content_hash(content))
 }
 
 /// Gather context lines around `target_line` (1-based) for a diff preview.
@@ -110,8 +588,11 @@ mod tests {
             description: "test fix".into(),
             target_line,
             context_before: vec![],
+            removed: vec![],
             inserted: inserted.into_iter().map(String::from).collect(),
             context_after: vec![],
+            edits: Vec::new(),
+            spec_hash: None,
         }
     }
 
@@ -159,6 +640,124 @@ mod tests {
         assert!(result.ends_with('\n'));
     }
 
+    #[test]
+    fn apply_fix_applies_every_edit() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "a: old\nb: 2\nc: old\nd: old\n").unwrap();
+
+        let mut proposal = make_proposal(0, vec!["a: new"]);
+        proposal.removed = vec!["a: old".into()];
+        // Listed bottom-up: applying them mustn't depend on the order.
+        proposal.edits = vec![
+            Edit {
+                target_line: 3,
+                removed: vec!["d: old".into()],
+                inserted: vec!["d: new".into()],
+            },
+            Edit {
+                target_line: 2,
+                removed: vec!["c: old".into()],
+                inserted: vec!["c: new".into()],
+            },
+        ];
+        apply_fix(&proposal, f.path()).unwrap();
+
+        let result = std::fs::read_to_string(f.path()).unwrap();
+        assert_eq!(result, "a: new\nb: 2\nc: new\nd: new\n");
+    }
+
+    #[test]
+    fn format_proposal_rewrites_the_spec() {
+        let mut f = NamedTempFile::with_suffix(".yaml").unwrap();
+        write!(
+            f,
+            "info:\n    title: Pets\nopenapi: \"3.0.3\"\npaths: {{}}\n"
+        )
+        .unwrap();
+
+        let proposal = propose_format(f.path(), &Format::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(proposal.description, "Format the spec");
+        apply_fix(&proposal, f.path()).unwrap();
+        let result = std::fs::read_to_string(f.path()).unwrap();
+        assert_eq!(result, "openapi: 3.0.3\ninfo:\n  title: Pets\npaths: {}\n");
+
+        assert!(
+            propose_format(f.path(), &Format::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn format_edits_keep_block_scalars_whole() {
+        let mut f = NamedTempFile::with_suffix(".yaml").unwrap();
+        write!(
+            f,
+            "info:\n  description: |\n      one\n      two\n  title: Pets\n"
+        )
+        .unwrap();
+
+        let proposal = propose_format(f.path(), &Format::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(proposal.target_line, 1);
+        assert_eq!(proposal.removed[0], "  description: |");
+        apply_fix(&proposal, f.path()).unwrap();
+        let result = std::fs::read_to_string(f.path()).unwrap();
+        assert_eq!(
+            result,
+            "info:\n  description: |\n    one\n    two\n  title: Pets\n"
+        );
+    }
+
+    #[test]
+    fn apply_fix_refuses_to_cut_into_block_scalars() {
+        let mut f = NamedTempFile::new().unwrap();
+        let spec = "info:\n  description: |\n    one\n    two\n  title: Pets\n";
+        write!(f, "{spec}").unwrap();
+
+        let proposal = make_proposal(3, vec!["  version: '1.0'"]);
+        let err = apply_fix(&proposal, f.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The fix would cut into the block scalar at line 2"
+        );
+        assert_eq!(std::fs::read_to_string(f.path()).unwrap(), spec);
+    }
+
+    #[test]
+    fn apply_fix_refuses_to_break_the_spec() {
+        let mut f = NamedTempFile::new().unwrap();
+        let spec = "base: &base\n  type: string\nname: *base\n";
+        write!(f, "{spec}").unwrap();
+
+        let mut proposal = make_proposal(0, vec!["base:"]);
+        proposal.removed = vec!["base: &base".into()];
+        let err = apply_fix(&proposal, f.path()).unwrap_err();
+        assert!(err.to_string().starts_with("The fix would break the spec"));
+        assert_eq!(std::fs::read_to_string(f.path()).unwrap(), spec);
+    }
+
+    #[test]
+    fn stale_proposals_are_refused() {
+        let mut f = NamedTempFile::with_suffix(".yaml").unwrap();
+        write!(f, "info:\n    title: Pets\n").unwrap();
+        let proposal = propose_format(f.path(), &Format::default())
+            .unwrap()
+            .unwrap();
+        assert!(!is_stale(&proposal, f.path()));
+
+        std::fs::write(f.path(), "# moved\ninfo:\n    title: Pets\n").unwrap();
+        assert!(is_stale(&proposal, f.path()));
+        let err = apply_fix(&proposal, f.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The spec changed since the fix was proposed"
+        );
+    }
+
     #[test]
     fn apply_fix_target_beyond_file_errors() {
         let mut f = NamedTempFile::new().unwrap();
@@ -168,6 +767,40 @@ mod tests {
         assert!(apply_fix(&proposal, f.path()).is_err());
     }
 
+    #[test]
+    fn apply_edit_replaces_lines() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "a: 1\nb: tpyo\nc: 3\n").unwrap();
+
+        let original = vec!["b: tpyo".to_string()];
+        let edited = vec!["b: typo".to_string(), "d: 4".to_string()];
+        apply_edit(f.path(), 2, &original, &edited).unwrap();
+        let result = std::fs::read_to_string(f.path()).unwrap();
+        assert_eq!(result, "a: 1\nb: typo\nd: 4\nc: 3\n");
+
+        // The line no longer matches what the edit started from.
+        assert!(apply_edit(f.path(), 2, &original, &edited).is_err());
+        assert_eq!(std::fs::read_to_string(f.path()).unwrap(), result);
+    }
+
+    #[test]
+    fn apply_all_skips_overlapping_proposals() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "a\nb\nc\nd\n").unwrap();
+
+        let replace = |target_line: usize, removed: &[&str], inserted: Vec<&str>| FixProposal {
+            removed: removed.iter().map(|s| s.to_string()).collect(),
+            ..make_proposal(target_line, inserted)
+        };
+        let proposals = [
+            replace(0, &["a"], vec!["A"]),
+            replace(2, &["c", "d"], vec!["CD"]),
+            replace(3, &["d"], vec!["D"]),
+        ];
+        assert_eq!(apply_all(&proposals, f.path()).unwrap(), 2);
+        assert_eq!(std::fs::read_to_string(f.path()).unwrap(), "A\nb\nc\nD\n");
+    }
+
     #[test]
     fn gather_context_normal() {
         let lines: Vec<String> = (1..=10).map(|i| format!("line{i}")).collect();
@@ -197,6 +830,7 @@ mod tests {
         let error = crate::log_parser::LintError {
             line: 1,
             col: 0,
+            end: None,
             severity: crate::log_parser::Severity::Error,
             rule: "unknown-rule".into(),
             message: "some message".into(),
@@ -208,6 +842,87 @@ mod tests {
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{raw}").unwrap();
 
-        assert!(propose_fix(&error, &index, f.path()).unwrap().is_none());
+        assert!(
+            propose_fix(&error, &index, f.path(), &Config::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn assisted_fixes_are_placed_where_their_lines_are() {
+        let error = crate::log_parser::LintError {
+            line: 2,
+            col: 0,
+            end: None,
+            severity: crate::log_parser::Severity::Warning,
+            rule: "info-x-audience".into(),
+            message: "info should have x-audience".into(),
+            json_path: Some("$.info".into()),
+        };
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            "openapi: 3.0.0\ninfo:\n  title: Pets\n  version: 1.0.0\n"
+        )
+        .unwrap();
+        // The model is a line off; the removed line is found anyway.
+        let assistant = FixAssistant {
+            command: Some(
+                r#"echo '{"fix": {"description": "Add x-audience", "line": 4, "removed": ["  title: Pets"], "inserted": ["  title: Pets", "  x-audience: public"]}}'"#
+                    .into(),
+            ),
+            ..Default::default()
+        };
+        let dir = f.path().parent().unwrap();
+
+        let proposal = propose_assisted_fix(&error, None, f.path(), dir, &assistant)
+            .unwrap()
+            .unwrap();
+        assert_eq!(proposal.target_line, 2);
+        assert_eq!(proposal.description, "Add x-audience (suggested)");
+        assert_eq!(proposal.context_before, ["openapi: 3.0.0", "info:"]);
+        assert_eq!(proposal.context_after, ["  version: 1.0.0"]);
+
+        let wrong = FixAssistant {
+            command: Some(
+                r#"echo '{"fix": {"description": "d", "line": 3, "removed": ["  title: Dogs"], "inserted": []}}'"#
+                    .into(),
+            ),
+            ..Default::default()
+        };
+        assert!(propose_assisted_fix(&error, None, f.path(), dir, &wrong).is_err());
+    }
+
+    #[test]
+    fn assisted_fixes_for_path_only_findings_send_the_lines_at_the_path() {
+        let error = crate::log_parser::LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: crate::log_parser::Severity::Warning,
+            rule: "acme-version".into(),
+            message: "version should be semver".into(),
+            json_path: Some("/info/version".into()),
+        };
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "openapi: 3.0.0\ninfo:\n  title: Pets\n  version: '1'\n").unwrap();
+        let assistant = FixAssistant {
+            command: Some(r#"grep -q '"line":4,"first_line":4,' && echo '{"fix": null}'"#.into()),
+            context_lines: 0,
+            ..Default::default()
+        };
+        let dir = f.path().parent().unwrap();
+        assert!(
+            propose_assisted_fix(&error, None, f.path(), dir, &assistant)
+                .unwrap()
+                .is_none()
+        );
+
+        let nowhere = crate::log_parser::LintError {
+            json_path: Some("/servers".into()),
+            ..error
+        };
+        assert!(propose_assisted_fix(&nowhere, None, f.path(), dir, &assistant).is_err());
     }
 }
@@ -0,0 +1,87 @@
+use lazyoav::operation_ids;
+
+use crate::log_parser::LintError;
+use crate::spec::SpecIndex;
+
+use super::{FixProposal, gather_context};
+
+/// Rename a colliding operationId to the free id the analyzer derived from
+/// its method and path.
+pub fn propose(error: &LintError, spec_index: &SpecIndex, lines: &[String]) -> Option<FixProposal> {
+    let pointer = error.json_path.as_deref()?;
+    let spec: serde_yaml::Value = serde_yaml::from_str(&lines.join("\n")).ok()?;
+    let collision = operation_ids::find(&spec)
+        .into_iter()
+        .find(|c| c.pointer == pointer)?;
+    let line = spec_index.resolve(pointer)?.line;
+    let text = lines.get(line.checked_sub(1)?)?;
+
+    // Replace the value after the key, leaving the key and quoting alone.
+    let key_end = text.find("operationId")? + "operationId".len();
+    let (key, value) = text.split_at(key_end);
+    let renamed = format!(
+        "{key}{}",
+        value.replacen(&collision.operation_id, &collision.rename, 1)
+    );
+    if renamed == *text {
+        return None;
+    }
+
+    let (context_before, _) = gather_context(lines, line, 3);
+    let (_, context_after) = gather_context(lines, line + 1, 3);
+    Some(FixProposal {
+        rule: error.rule.clone(),
+        description: format!(
+            "Rename operationId {} to {}",
+            collision.operation_id, collision.rename
+        ),
+        target_line: line - 1,
+        context_before,
+        removed: vec![text.clone()],
+        inserted: vec![renamed],
+        context_after,
+        edits: Vec::new(),
+        spec_hash: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_parser::Severity;
+    use crate::spec::parse_spec;
+
+    #[test]
+    fn renames_the_later_operation() {
+        let yaml = "\
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+  /v2/pets:
+    get:
+      operationId: \"listPets\"
+";
+        let lines: Vec<String> = yaml.lines().map(String::from).collect();
+        let index = parse_spec(yaml).unwrap();
+        let error = LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: Severity::Error,
+            rule: operation_ids::DUPLICATE.into(),
+            message: String::new(),
+            json_path: Some("/paths/~1v2~1pets/get/operationId".into()),
+        };
+
+        let proposal = propose(&error, &index, &lines).unwrap();
+        assert_eq!(proposal.target_line, 7);
+        assert_eq!(proposal.removed, ["      operationId: \"listPets\""]);
+        assert_eq!(proposal.inserted, ["      operationId: \"getV2Pets\""]);
+        assert_eq!(
+            proposal.description,
+            "Rename operationId listPets to getV2Pets"
+        );
+    }
+}
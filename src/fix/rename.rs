@@ -0,0 +1,538 @@
+use std::collections::BTreeMap;
+
+use lazyoav::config::Naming;
+use lazyoav::naming::{self, Violation};
+use lazyoav::openapi::escape;
+use serde_yaml::Value;
+
+use crate::log_parser::LintError;
+use crate::spec::SpecIndex;
+
+use super::{Edit, FixProposal, gather_context};
+
+/// Rename a schema, property or path that breaks a naming convention,
+/// together with every `$ref`, `discriminator` and `required` entry and
+/// example key naming it.
+pub fn propose(
+    error: &LintError,
+    spec_index: &SpecIndex,
+    lines: &[String],
+    naming: &Naming,
+) -> Option<FixProposal> {
+    let pointer = error.json_path.as_deref()?;
+    let spec: Value = serde_yaml::from_str(&lines.join("\n")).ok()?;
+    let violation = naming::check(&spec, naming)
+        .into_iter()
+        .find(|v| v.pointer == pointer)?;
+    let (parent, _) = pointer.rsplit_once('/')?;
+    if taken(&spec, parent, &violation.rename) {
+        return None;
+    }
+
+    // 1-based line -> its text after the rename.
+    let mut changed: BTreeMap<usize, String> = BTreeMap::new();
+    let mut rename_on = |line: usize, from: &str, to: &str| {
+        let Some(text) = changed
+            .get(&line)
+            .cloned()
+            .or_else(|| lines.get(line.checked_sub(1)?).cloned())
+        else {
+            return;
+        };
+        if let Some(renamed) = replace_token(&text, from, to) {
+            changed.insert(line, renamed);
+        }
+    };
+
+    let definition = spec_index.resolve(pointer)?.line;
+    rename_on(definition, &violation.name, &violation.rename);
+    match violation.rule {
+        naming::SCHEMA => {
+            let from = format!("#/components/schemas/{}", violation.name);
+            let to = format!("#/components/schemas/{}", violation.rename);
+            for (i, text) in lines.iter().enumerate() {
+                if text.contains(&from) {
+                    rename_on(i + 1, &from, &to);
+                }
+            }
+            for line in mapping_usages(&spec, spec_index, lines, &violation.name) {
+                rename_on(line, &violation.name, &violation.rename);
+            }
+        }
+        naming::PROPERTY => {
+            // The property's schema, above `properties`.
+            let schema = parent.strip_suffix("/properties")?;
+            for line in property_usages(&spec, spec_index, lines, schema, &violation) {
+                rename_on(line, &violation.name, &violation.rename);
+            }
+        }
+        _ => {}
+    }
+
+    let first = changed.remove(&definition)?;
+    let (context_before, _) = gather_context(lines, definition, 3);
+    let (_, context_after) = gather_context(lines, definition + 1, 3);
+    let references = changed.len();
+    Some(FixProposal {
+        rule: error.rule.clone(),
+        description: match references {
+            0 => format!("Rename {} to {}", violation.name, violation.rename),
+            n => format!(
+                "Rename {} to {} and update {n} reference(s)",
+                violation.name, violation.rename
+            ),
+        },
+        target_line: definition - 1,
+        context_before,
+        removed: vec![lines[definition - 1].clone()],
+        inserted: vec![first],
+        context_after,
+        edits: changed
+            .into_iter()
+            .map(|(line, text)| Edit {
+                target_line: line - 1,
+                removed: vec![lines[line - 1].clone()],
+                inserted: vec![text],
+            })
+            .collect(),
+        spec_hash: None,
+    })
+}
+
+/// Lines of `discriminator.mapping` entries selecting the schema `name` by
+/// name alone; those with a `$ref` are found with the other `$ref`s.
+fn mapping_usages(
+    spec: &Value,
+    spec_index: &SpecIndex,
+    lines: &[String],
+    name: &str,
+) -> Vec<usize> {
+    let mut pointers = Vec::new();
+    visit(spec, "", &mut |node, pointer| {
+        let mapping = node
+            .get("discriminator")
+            .and_then(|d| d.get("mapping"))
+            .and_then(Value::as_mapping);
+        for (key, value) in mapping.into_iter().flatten() {
+            if value.as_str() == Some(name)
+                && let Some(key) = key.as_str()
+            {
+                pointers.push(format!("{pointer}/discriminator/mapping/{}", escape(key)));
+            }
+        }
+    });
+    lines_of(spec_index, lines, &pointers, name)
+}
+
+/// Lines naming the property of the schema at `schema`: `required` and
+/// `discriminator.propertyName` entries, also in the schemas `allOf`
+/// composes it with, and keys of the examples of it.
+fn property_usages(
+    spec: &Value,
+    spec_index: &SpecIndex,
+    lines: &[String],
+    schema: &str,
+    violation: &Violation,
+) -> Vec<usize> {
+    let name = violation.name.as_str();
+    let mut pointers = Vec::new();
+    for at in composed_with(spec, schema) {
+        let Some(node) = lookup(spec, &at) else {
+            continue;
+        };
+        pointers.extend(
+            node.get("required")
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .filter(|(_, v)| v.as_str() == Some(name))
+                .map(|(i, _)| format!("{at}/required/{i}")),
+        );
+        if node
+            .get("discriminator")
+            .and_then(|d| d.get("propertyName"))
+            .and_then(Value::as_str)
+            == Some(name)
+        {
+            pointers.push(format!("{at}/discriminator/propertyName"));
+        }
+    }
+    for at in examples_of(spec, schema) {
+        match lookup(spec, &at) {
+            Some(Value::Mapping(example)) if example.contains_key(name) => {
+                pointers.push(format!("{at}/{}", escape(name)));
+            }
+            // An array of the schema's objects.
+            Some(Value::Sequence(items)) => pointers.extend(
+                items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, item)| item.get(name).is_some())
+                    .map(|(i, _)| format!("{at}/{i}/{}", escape(name))),
+            ),
+            _ => {}
+        }
+    }
+    lines_of(spec_index, lines, &pointers, name)
+}
+
+/// The schema at `schema` and the schemas `allOf` composes with it: the
+/// composing schema and its other members, when it's an `allOf` member, and
+/// every `allOf` that refers to it, when it's a component.
+fn composed_with(spec: &Value, schema: &str) -> Vec<String> {
+    let mut found = vec![schema.to_string()];
+    let add_all_of = |whole: &str, found: &mut Vec<String>| {
+        let count = lookup(spec, whole)
+            .and_then(|node| node.get("allOf"))
+            .and_then(Value::as_sequence)
+            .map_or(0, Vec::len);
+        found.push(whole.to_string());
+        found.extend((0..count).map(|i| format!("{whole}/allOf/{i}")));
+    };
+    if let Some((whole, index)) = schema.rsplit_once("/allOf/")
+        && index.parse::<usize>().is_ok()
+    {
+        add_all_of(whole, &mut found);
+    }
+    if is_component(schema) {
+        let reference = format!("#{schema}");
+        let mut composing = Vec::new();
+        visit(spec, "", &mut |node, pointer| {
+            let refers = node
+                .get("allOf")
+                .and_then(Value::as_sequence)
+                .is_some_and(|members| {
+                    members
+                        .iter()
+                        .any(|m| m.get("$ref").and_then(Value::as_str) == Some(&reference))
+                });
+            if refers {
+                composing.push(pointer.to_string());
+            }
+        });
+        for whole in composing {
+            add_all_of(&whole, &mut found);
+        }
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// Pointers to the example payloads of the schema at `schema`: its own
+/// `example`, and those of the media types whose schema it is, or holds
+/// in `items`, directly or by `$ref`.
+fn examples_of(spec: &Value, schema: &str) -> Vec<String> {
+    let mut found = vec![format!("{schema}/example")];
+    let reference = format!("#{schema}");
+    let is_schema = |node: &Value, pointer: &str| {
+        pointer == schema
+            || (is_component(schema)
+                && node.get("$ref").and_then(Value::as_str) == Some(&reference))
+    };
+    visit(spec, "", &mut |node, pointer| {
+        let Some(media) = node.get("schema") else {
+            return;
+        };
+        let at = format!("{pointer}/schema");
+        let items = media
+            .get("items")
+            .is_some_and(|items| is_schema(items, &format!("{at}/items")));
+        if !is_schema(media, &at) && !items {
+            return;
+        }
+        found.push(format!("{pointer}/example"));
+        let examples = node.get("examples").and_then(Value::as_mapping);
+        for (key, example) in examples.into_iter().flatten() {
+            match example.get("$ref").and_then(Value::as_str) {
+                Some(target) => {
+                    if let Some(target) = target.strip_prefix('#') {
+                        found.push(format!("{target}/value"));
+                    }
+                }
+                None => {
+                    if let Some(key) = key.as_str() {
+                        found.push(format!("{pointer}/examples/{}/value", escape(key)));
+                    }
+                }
+            }
+        }
+    });
+    found
+}
+
+/// Whether `pointer` is a schema under `components/schemas`, which other
+/// schemas can `$ref`.
+fn is_component(pointer: &str) -> bool {
+    pointer
+        .strip_prefix("/components/schemas/")
+        .is_some_and(|name| !name.contains('/'))
+}
+
+/// Call `f` with every mapping below `node` and its pointer.
+fn visit(node: &Value, pointer: &str, f: &mut dyn FnMut(&Value, &str)) {
+    match node {
+        Value::Mapping(map) => {
+            f(node, pointer);
+            for (key, value) in map {
+                if let Some(key) = key.as_str() {
+                    visit(value, &format!("{pointer}/{}", escape(key)), f);
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                visit(item, &format!("{pointer}/{i}"), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The lines naming `name` at `pointers`. The index only knows mapping
+/// keys in block style, so a pointer into a sequence or flow collection
+/// falls back to the first line naming `name` in the block of its nearest
+/// indexed ancestor.
+fn lines_of(
+    spec_index: &SpecIndex,
+    lines: &[String],
+    pointers: &[String],
+    name: &str,
+) -> Vec<usize> {
+    let names = |line: usize| {
+        lines
+            .get(line - 1)
+            .is_some_and(|text| replace_token(text, name, name).is_some())
+    };
+    pointers
+        .iter()
+        .filter_map(|pointer| {
+            if let Some(span) = spec_index.resolve(pointer) {
+                return Some(span.line);
+            }
+            let mut ancestor = pointer.as_str();
+            let span = loop {
+                ancestor = ancestor.rsplit_once('/')?.0;
+                if let Some(span) = spec_index.resolve(ancestor) {
+                    break span;
+                }
+            };
+            let indent = |text: &str| text.len() - text.trim_start().len();
+            let block = lines[span.line..]
+                .iter()
+                .take_while(|text| {
+                    // A block sequence may sit at its key's own indent.
+                    text.trim().is_empty()
+                        || indent(text) > span.col
+                        || (indent(text) == span.col && text.trim_start().starts_with("- "))
+                })
+                .count();
+            (span.line..=span.line + block).find(|&line| names(line))
+        })
+        .collect()
+}
+
+/// Whether `name` is already a key beside the renamed one.
+fn taken(spec: &Value, parent: &str, name: &str) -> bool {
+    lookup(spec, parent).is_some_and(|siblings| siblings.get(name).is_some())
+}
+
+fn lookup<'a>(spec: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer.split('/').skip(1).try_fold(spec, |node, segment| {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        match node {
+            Value::Sequence(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => node.get(segment.as_str()),
+        }
+    })
+}
+
+/// Replace the first occurrence of `from` in `text` that isn't part of a
+/// longer name.
+fn replace_token(text: &str, from: &str, to: &str) -> Option<String> {
+    let is_name = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    let mut search = 0;
+    while let Some(found) = text[search..].find(from) {
+        let start = search + found;
+        let end = start + from.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        if !before.is_some_and(is_name) && !after.is_some_and(is_name) {
+            return Some(format!("{}{to}{}", &text[..start], &text[end..]));
+        }
+        search = end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_parser::Severity;
+    use crate::spec::parse_spec;
+    use lazyoav::config::Case;
+
+    const SPEC: &str = "\
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/pet_owner'
+components:
+  schemas:
+    pet_owner:
+      required: [pet_name, id]
+      properties:
+        pet_name:
+          type: string
+        pets:
+          items:
+            $ref: \"#/components/schemas/pet_owner\"
+    pet_ownerList:
+      $ref: '#/components/schemas/pet_ownerList'
+";
+
+    fn error(rule: &str, pointer: &str) -> LintError {
+        LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: Severity::Warning,
+            rule: rule.into(),
+            message: String::new(),
+            json_path: Some(pointer.into()),
+        }
+    }
+
+    fn propose_in(rule: &str, pointer: &str, naming: Naming) -> FixProposal {
+        let lines: Vec<String> = SPEC.lines().map(String::from).collect();
+        let index = parse_spec(SPEC).unwrap();
+        propose(&error(rule, pointer), &index, &lines, &naming).unwrap()
+    }
+
+    #[test]
+    fn schema_renames_update_every_ref() {
+        let naming = Naming {
+            schemas: Some(Case::Pascal),
+            ..Default::default()
+        };
+        let proposal = propose_in(naming::SCHEMA, "/components/schemas/pet_owner", naming);
+        assert_eq!(proposal.target_line, 12);
+        assert_eq!(proposal.inserted, ["    PetOwner:"]);
+        assert_eq!(
+            proposal.description,
+            "Rename pet_owner to PetOwner and update 2 reference(s)"
+        );
+        let edits: Vec<(usize, &str)> = proposal
+            .edits
+            .iter()
+            .map(|e| (e.target_line, e.inserted[0].as_str()))
+            .collect();
+        // The longer pet_ownerList is left alone.
+        assert_eq!(
+            edits,
+            vec![
+                (9, "                $ref: '#/components/schemas/PetOwner'"),
+                (19, "            $ref: \"#/components/schemas/PetOwner\""),
+            ]
+        );
+    }
+
+    const COMPOSED: &str = "\
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/pet'
+              example:
+                - pet_name: Rex
+components:
+  schemas:
+    pet:
+      discriminator:
+        propertyName: kind
+        mapping:
+          dog: dog_info
+      properties:
+        pet_name:
+          type: string
+      example: {pet_name: Tom}
+    dog_info:
+      allOf:
+        - $ref: '#/components/schemas/pet'
+        - required:
+            - pet_name
+";
+
+    #[test]
+    fn renames_reach_discriminators_allof_and_examples() {
+        let lines: Vec<String> = COMPOSED.lines().map(String::from).collect();
+        let index = parse_spec(COMPOSED).unwrap();
+        let edits = |rule: &str, pointer: &str, naming: Naming| -> Vec<(usize, String)> {
+            propose(&error(rule, pointer), &index, &lines, &naming)
+                .unwrap()
+                .edits
+                .into_iter()
+                .map(|e| (e.target_line, e.inserted[0].clone()))
+                .collect()
+        };
+
+        let naming = Naming {
+            properties: Some(Case::Camel),
+            ..Default::default()
+        };
+        assert_eq!(
+            edits(
+                naming::PROPERTY,
+                "/components/schemas/pet/properties/pet_name",
+                naming
+            ),
+            vec![
+                (13, "                - petName: Rex".to_string()),
+                (24, "      example: {petName: Tom}".to_string()),
+                (29, "            - petName".to_string()),
+            ]
+        );
+
+        let naming = Naming {
+            schemas: Some(Case::Pascal),
+            ..Default::default()
+        };
+        assert_eq!(
+            edits(naming::SCHEMA, "/components/schemas/dog_info", naming),
+            vec![(20, "          dog: DogInfo".to_string())]
+        );
+    }
+
+    #[test]
+    fn property_renames_update_required() {
+        let naming = Naming {
+            properties: Some(Case::Camel),
+            ..Default::default()
+        };
+        let proposal = propose_in(
+            naming::PROPERTY,
+            "/components/schemas/pet_owner/properties/pet_name",
+            naming,
+        );
+        assert_eq!(proposal.inserted, ["        petName:"]);
+        assert_eq!(proposal.edits.len(), 1);
+        assert_eq!(
+            proposal.edits[0].inserted,
+            ["      required: [petName, id]"]
+        );
+    }
+}
@@ -1,230 +1,135 @@
-use crate::log_parser::LintError;
-use crate::spec::SpecIndex;
-
-use super::{FixProposal, gather_context};
-
-/// Detect the indentation used by children of `parent_line` (1-based).
-///
-/// Scans lines below `parent_line` for the first non-blank child and returns
-/// its whitespace prefix. Falls back to parent indent + 2 spaces.
-fn detect_child_indent(lines: &[String], parent_line: usize) -> Option<String> {
-    if parent_line == 0 {
-        return None;
-    }
-    let parent_idx = parent_line - 1;
-    let parent_indent = leading_whitespace(lines.get(parent_idx)?);
-
-    for line in lines.iter().skip(parent_idx + 1) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        let indent = leading_whitespace(line);
-        if indent.len() > parent_indent.len() {
-            return Some(indent);
-        }
-        // Reached a sibling or parent — no children found.
-        break;
-    }
-
-    // Fallback: parent indent + 2 spaces.
-    Some(format!("{parent_indent}  "))
-}
-
-fn leading_whitespace(line: &str) -> String {
-    line.chars()
-        .take_while(|c| c.is_ascii_whitespace())
-        .collect()
-}
-
-/// Resolve the operation key line (e.g. `get:`) from an error's json_path.
-///
-/// For paths like `/paths/~1pets/get`, resolves to the line of `get:` and
-/// extracts the operationId from the child `operationId:` field if present.
-fn resolve_operation_context(
-    error: &LintError,
-    spec_index: &SpecIndex,
-    lines: &[String],
-) -> Option<(usize, String)> {
-    let json_path = error.json_path.as_deref()?;
-
-    // The json_path should point to the operation (e.g. /paths/~1pets/get).
-    let span = spec_index.resolve(json_path)?;
-    let op_line = span.line;
-
-    if op_line == 0 || op_line > lines.len() {
-        return None;
-    }
-
-    // Try to extract operationId from child fields.
-    let child_indent = detect_child_indent(lines, op_line)?;
-    let op_id = find_child_field_value(lines, op_line, &child_indent, "operationId")
-        .unwrap_or_else(|| {
-            // Fall back to the HTTP method as identifier.
-            lines[op_line - 1].trim().trim_end_matches(':').to_string()
-        });
-
-    Some((op_line, op_id))
-}
+use lazyoav::config::FixText;
 
-/// Find the value of a child field below `parent_line` at the given indent.
-fn find_child_field_value(
-    lines: &[String],
-    parent_line: usize,
-    child_indent: &str,
-    field_name: &str,
-) -> Option<String> {
-    for line in lines.iter().skip(parent_line) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        let indent = leading_whitespace(line);
-        if indent.len() < child_indent.len() {
-            break; // Left the operation block.
-        }
-        if indent.len() == child_indent.len()
-            && let Some(rest) = trimmed.strip_prefix(&format!("{field_name}:"))
-        {
-            return Some(rest.trim().to_string());
-        }
-    }
-    None
+use crate::log_parser::LintError;
+use crate::spec::normalize_to_pointer;
+
+use super::describe::Operation;
+use super::document::{Document, Place};
+use super::{Edit, FixProposal, gather_context};
+
+/// The operation an error points at (e.g. `/paths/~1pets/get`) and what to
+/// call it: its operationId, or else its method.
+fn operation_context<'a>(error: &LintError, doc: &Document<'a>) -> Option<(String, String)> {
+    let pointer = normalize_to_pointer(error.json_path.as_deref()?);
+    let operation = doc.get(&pointer)?;
+    let op_id = doc
+        .scalar(&format!("{pointer}/operationId"))
+        .map(|id| id.trim_matches(['"', '\'']).to_string())
+        .unwrap_or_else(|| operation.key.clone());
+    Some((pointer, op_id))
 }
 
-/// Find the last child line of a block starting at `parent_line` (1-based).
-///
-/// Returns the 1-based line number of the last child (or the parent itself if
-/// no children are found).
-fn last_child_line(lines: &[String], parent_line: usize) -> Option<usize> {
-    if parent_line == 0 {
-        return None;
-    }
-    let parent_idx = parent_line - 1;
-    let parent_indent_len = leading_whitespace(lines.get(parent_idx)?).len();
-    let mut last = parent_line;
-
-    for (i, line) in lines.iter().enumerate().skip(parent_idx + 1) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        if leading_whitespace(line).len() <= parent_indent_len {
-            break;
-        }
-        last = i + 1; // 1-based
+/// A proposal making `edit`, an insertion.
+fn inserting(error: &LintError, description: &str, lines: &[String], edit: Edit) -> FixProposal {
+    let (context_before, context_after) = gather_context(lines, edit.target_line + 1, 3);
+    FixProposal {
+        rule: error.rule.clone(),
+        description: description.into(),
+        target_line: edit.target_line,
+        context_before,
+        removed: Vec::new(),
+        inserted: edit.inserted,
+        context_after,
+        edits: Vec::new(),
+        spec_hash: None,
     }
-
-    Some(last)
 }
 
 // ── Rule generators ──────────────────────────────────────────────────────
 
 pub fn propose_operation_summary(
     error: &LintError,
-    spec_index: &SpecIndex,
     lines: &[String],
+    text: &FixText,
 ) -> Option<FixProposal> {
-    let (op_line, op_id) = resolve_operation_context(error, spec_index, lines)?;
-    let indent = detect_child_indent(lines, op_line)?;
-    let inserted = vec![format!("{indent}summary: \"{op_id} summary\"")];
-    let (ctx_before, ctx_after) = gather_context(lines, op_line + 1, 3);
-
-    Some(FixProposal {
-        rule: error.rule.clone(),
-        description: "Add 'summary' field to the operation".into(),
-        target_line: op_line,
-        context_before: ctx_before,
-        inserted,
-        context_after: ctx_after,
-    })
+    let doc = Document::parse(lines)?;
+    let (pointer, op_id) = operation_context(error, &doc)?;
+    let summary = describe_operation(error, lines)
+        .map_or_else(|| format!("{op_id} summary"), |op| op.render(&text.summary));
+    let entry = [format!("summary: {}", quoted(&summary))];
+    let edit = doc.insert(&pointer, Place::First, &entry)?;
+    Some(inserting(
+        error,
+        "Add 'summary' field to the operation",
+        lines,
+        edit,
+    ))
 }
 
 pub fn propose_operation_description(
     error: &LintError,
-    spec_index: &SpecIndex,
     lines: &[String],
+    text: &FixText,
 ) -> Option<FixProposal> {
-    let (op_line, op_id) = resolve_operation_context(error, spec_index, lines)?;
-    let indent = detect_child_indent(lines, op_line)?;
-    let inserted = vec![format!("{indent}description: \"{op_id} description\"")];
-    let (ctx_before, ctx_after) = gather_context(lines, op_line + 1, 3);
-
-    Some(FixProposal {
-        rule: error.rule.clone(),
-        description: "Add 'description' field to the operation".into(),
-        target_line: op_line,
-        context_before: ctx_before,
-        inserted,
-        context_after: ctx_after,
-    })
+    let doc = Document::parse(lines)?;
+    let (pointer, op_id) = operation_context(error, &doc)?;
+    let description = describe_operation(error, lines).map_or_else(
+        || format!("{op_id} description"),
+        |op| op.render(&text.description),
+    );
+    let entry = [format!("description: {}", quoted(&description))];
+    let edit = doc.insert(&pointer, Place::First, &entry)?;
+    Some(inserting(
+        error,
+        "Add 'description' field to the operation",
+        lines,
+        edit,
+    ))
 }
 
-pub fn propose_info_contact(
-    error: &LintError,
-    spec_index: &SpecIndex,
-    lines: &[String],
-) -> Option<FixProposal> {
-    let span = spec_index.resolve("/info")?;
-    let info_line = span.line;
-    let child_indent = detect_child_indent(lines, info_line)?;
-    let nested_indent = format!("{child_indent}  ");
-    let target = last_child_line(lines, info_line)?;
-
-    let inserted = vec![
-        format!("{child_indent}contact:"),
-        format!("{nested_indent}name: \"\""),
-        format!("{nested_indent}url: \"\""),
-    ];
-    let (ctx_before, ctx_after) = gather_context(lines, target + 1, 3);
+/// What the operation an error points at does, read from the spec. `None`
+/// when the spec doesn't parse or the error isn't on an operation.
+fn describe_operation(error: &LintError, lines: &[String]) -> Option<Operation> {
+    let pointer = normalize_to_pointer(error.json_path.as_deref()?);
+    let spec: serde_yaml::Value = serde_yaml::from_str(&lines.join("\n")).ok()?;
+    Operation::at(&spec, &pointer)
+}
 
-    Some(FixProposal {
-        rule: error.rule.clone(),
-        description: "Add 'contact' block under /info".into(),
-        target_line: target,
-        context_before: ctx_before,
-        inserted,
-        context_after: ctx_after,
-    })
+/// `text` as a double-quoted YAML scalar.
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
 }
 
-pub fn propose_info_license(
-    error: &LintError,
-    spec_index: &SpecIndex,
-    lines: &[String],
-) -> Option<FixProposal> {
-    let span = spec_index.resolve("/info")?;
-    let info_line = span.line;
-    let child_indent = detect_child_indent(lines, info_line)?;
-    let nested_indent = format!("{child_indent}  ");
-    let target = last_child_line(lines, info_line)?;
-
-    let inserted = vec![
-        format!("{child_indent}license:"),
-        format!("{nested_indent}name: \"\""),
+pub fn propose_info_contact(error: &LintError, lines: &[String]) -> Option<FixProposal> {
+    let doc = Document::parse(lines)?;
+    let pad = " ".repeat(doc.step());
+    let entry = [
+        "contact:".to_string(),
+        format!("{pad}name: \"\""),
+        format!("{pad}url: \"\""),
     ];
-    let (ctx_before, ctx_after) = gather_context(lines, target + 1, 3);
+    let edit = doc.insert("/info", Place::Last, &entry)?;
+    Some(inserting(
+        error,
+        "Add 'contact' block under /info",
+        lines,
+        edit,
+    ))
+}
 
-    Some(FixProposal {
-        rule: error.rule.clone(),
-        description: "Add 'license' block under /info".into(),
-        target_line: target,
-        context_before: ctx_before,
-        inserted,
-        context_after: ctx_after,
-    })
+pub fn propose_info_license(error: &LintError, lines: &[String]) -> Option<FixProposal> {
+    let doc = Document::parse(lines)?;
+    let pad = " ".repeat(doc.step());
+    let entry = ["license:".to_string(), format!("{pad}name: \"\"")];
+    let edit = doc.insert("/info", Place::Last, &entry)?;
+    Some(inserting(
+        error,
+        "Add 'license' block under /info",
+        lines,
+        edit,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::log_parser::{LintError, Severity};
-    use crate::spec::parse_spec;
 
     fn make_error(rule: &str, json_path: Option<&str>) -> LintError {
         LintError {
             line: 1,
             col: 0,
+            end: None,
             severity: Severity::Error,
             rule: rule.into(),
             message: format!("{rule} message"),
@@ -249,87 +154,78 @@ paths:
 ";
 
     #[test]
-    fn detect_child_indent_normal() {
-        let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        // info: is line 2 (1-based), children are indented with 2 spaces.
-        assert_eq!(detect_child_indent(&lines, 2).unwrap(), "  ");
-    }
-
-    #[test]
-    fn detect_child_indent_deeper() {
-        let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        // get: is line 7 (1-based), children are indented with 6 spaces.
-        assert_eq!(detect_child_indent(&lines, 7).unwrap(), "      ");
-    }
-
-    #[test]
-    fn detect_child_indent_fallback() {
-        let lines = vec!["leaf_key: value".to_string()];
-        // No children, so fallback = parent indent (0) + 2.
-        assert_eq!(detect_child_indent(&lines, 1).unwrap(), "  ");
-    }
+    fn fixes_follow_the_spec_structure() {
+        let yaml = "\
+openapi: 3.0.0
+info:
+    title: Pet Store
+    description: |
+        Pets: cats and dogs.
 
-    #[test]
-    fn detect_child_indent_out_of_bounds() {
-        let lines = vec!["key: value".to_string()];
-        assert!(detect_child_indent(&lines, 0).is_none());
-        assert!(detect_child_indent(&lines, 99).is_none());
-    }
+        license: not a key
+    version: '1.0'
+paths:
+    /pets:
+        get:
+            # Lists every pet.
+            operationId: 'listPets'
+";
+        let lines: Vec<String> = yaml.lines().map(String::from).collect();
 
-    #[test]
-    fn last_child_line_info_block() {
-        let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        // info: is line 2, its children are title (3) and version (4).
-        assert_eq!(last_child_line(&lines, 2).unwrap(), 4);
-    }
+        let proposal = propose_info_license(&make_error("info-license", None), &lines).unwrap();
+        assert_eq!(proposal.target_line, 8); // after version, not in the block
+        assert_eq!(proposal.inserted, ["    license:", "        name: \"\""]);
 
-    #[test]
-    fn last_child_line_leaf() {
-        let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        // openapi: is line 1, no children.
-        assert_eq!(last_child_line(&lines, 1).unwrap(), 1);
+        let error = make_error("operation-summary", Some("/paths/~1pets/get"));
+        let proposal = propose_operation_summary(&error, &lines, &FixText::default()).unwrap();
+        assert_eq!(proposal.target_line, 11); // right after `get:`
+        assert_eq!(proposal.inserted, ["            summary: \"List pets\""]);
     }
 
     #[test]
-    fn last_child_line_out_of_bounds() {
-        let lines = vec!["key: value".to_string()];
-        assert!(last_child_line(&lines, 0).is_none());
-        assert!(last_child_line(&lines, 99).is_none());
+    fn fixes_leave_flow_values_and_aliases_alone() {
+        let yaml = "\
+openapi: 3.0.0
+info: {title: Pet Store, version: '1.0'}
+paths:
+  /pets:
+    get: &list
+      responses: {}
+    head: *list
+";
+        let lines: Vec<String> = yaml.lines().map(String::from).collect();
+        assert!(propose_info_contact(&make_error("info-contact", None), &lines).is_none());
+        let error = make_error("operation-summary", Some("/paths/~1pets/head"));
+        assert!(propose_operation_summary(&error, &lines, &FixText::default()).is_none());
     }
 
     #[test]
     fn propose_operation_summary_generates_fix() {
         let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        let index = parse_spec(PETSTORE_YAML).unwrap();
         let error = make_error("operation-summary", Some("/paths/~1pets/get"));
 
-        let proposal = propose_operation_summary(&error, &index, &lines).unwrap();
+        let proposal = propose_operation_summary(&error, &lines, &FixText::default()).unwrap();
         assert_eq!(proposal.rule, "operation-summary");
         assert_eq!(proposal.target_line, 7); // after `get:`
-        assert_eq!(proposal.inserted.len(), 1);
-        assert!(proposal.inserted[0].contains("summary:"));
-        assert!(proposal.inserted[0].contains("listPets"));
+        assert_eq!(proposal.inserted, ["      summary: \"List pets\""]);
     }
 
     #[test]
     fn propose_operation_description_generates_fix() {
         let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        let index = parse_spec(PETSTORE_YAML).unwrap();
         let error = make_error("operation-description", Some("/paths/~1pets/get"));
 
-        let proposal = propose_operation_description(&error, &index, &lines).unwrap();
+        let proposal = propose_operation_description(&error, &lines, &FixText::default()).unwrap();
         assert_eq!(proposal.target_line, 7);
-        assert!(proposal.inserted[0].contains("description:"));
-        assert!(proposal.inserted[0].contains("listPets"));
+        assert_eq!(proposal.inserted, ["      description: \"List pets.\""]);
     }
 
     #[test]
     fn propose_info_contact_generates_fix() {
         let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        let index = parse_spec(PETSTORE_YAML).unwrap();
         let error = make_error("info-contact", None);
 
-        let proposal = propose_info_contact(&error, &index, &lines).unwrap();
+        let proposal = propose_info_contact(&error, &lines).unwrap();
         assert_eq!(proposal.target_line, 4); // after last child of info
         assert_eq!(proposal.inserted.len(), 3);
         assert!(proposal.inserted[0].contains("contact:"));
@@ -340,10 +236,9 @@ paths:
     #[test]
     fn propose_info_license_generates_fix() {
         let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        let index = parse_spec(PETSTORE_YAML).unwrap();
         let error = make_error("info-license", None);
 
-        let proposal = propose_info_license(&error, &index, &lines).unwrap();
+        let proposal = propose_info_license(&error, &lines).unwrap();
         assert_eq!(proposal.target_line, 4);
         assert_eq!(proposal.inserted.len(), 2);
         assert!(proposal.inserted[0].contains("license:"));
@@ -353,50 +248,61 @@ paths:
     #[test]
     fn propose_operation_summary_no_json_path_returns_none() {
         let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        let index = parse_spec(PETSTORE_YAML).unwrap();
         let error = make_error("operation-summary", None);
 
-        assert!(propose_operation_summary(&error, &index, &lines).is_none());
+        assert!(propose_operation_summary(&error, &lines, &FixText::default()).is_none());
     }
 
     #[test]
     fn propose_operation_summary_bad_path_returns_none() {
         let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
-        let index = parse_spec(PETSTORE_YAML).unwrap();
         let error = make_error("operation-summary", Some("/nonexistent/path"));
 
-        assert!(propose_operation_summary(&error, &index, &lines).is_none());
+        assert!(propose_operation_summary(&error, &lines, &FixText::default()).is_none());
     }
 
     #[test]
     fn propose_info_contact_no_info_block_returns_none() {
         let yaml = "openapi: 3.0.0\npaths: {}\n";
         let lines: Vec<String> = yaml.lines().map(String::from).collect();
-        let index = parse_spec(yaml).unwrap();
         let error = make_error("info-contact", None);
 
-        assert!(propose_info_contact(&error, &index, &lines).is_none());
+        assert!(propose_info_contact(&error, &lines).is_none());
+    }
+
+    #[test]
+    fn operation_summary_follows_the_house_style() {
+        let lines: Vec<String> = PETSTORE_YAML.lines().map(String::from).collect();
+        let error = make_error("operation-summary", Some("/paths/~1pets/get"));
+        let text = FixText {
+            summary: "{tag}: {summary} \"{operation_id}\"".into(),
+            ..Default::default()
+        };
+
+        let proposal = propose_operation_summary(&error, &lines, &text).unwrap();
+        assert_eq!(
+            proposal.inserted,
+            ["      summary: \"pets: List pets \\\"listPets\\\"\""]
+        );
     }
 
     #[test]
-    fn operation_summary_without_operation_id_uses_method() {
+    fn operation_summary_without_operation_id_is_derived() {
         let yaml = "\
 openapi: 3.0.0
 info:
   title: Test
   version: '1.0'
 paths:
-  /pets:
-    get:
+  /pets/{petId}:
+    delete:
       tags:
         - pets
 ";
         let lines: Vec<String> = yaml.lines().map(String::from).collect();
-        let index = parse_spec(yaml).unwrap();
-        let error = make_error("operation-summary", Some("/paths/~1pets/get"));
+        let error = make_error("operation-summary", Some("/paths/~1pets~1{petId}/delete"));
 
-        let proposal = propose_operation_summary(&error, &index, &lines).unwrap();
-        // Without operationId, should fall back to HTTP method.
-        assert!(proposal.inserted[0].contains("get summary"));
+        let proposal = propose_operation_summary(&error, &lines, &FixText::default()).unwrap();
+        assert_eq!(proposal.inserted, ["      summary: \"Delete a pet by id\""]);
     }
 }
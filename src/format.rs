@@ -0,0 +1,463 @@
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use crate::config::{Format, Quotes};
+
+/// `content`, a YAML spec, rewritten in the canonical layout: `style`'s
+/// indent and quotes, top-level keys in `style.key_order`, multi-line
+/// strings as literal blocks. Comments and anchors don't survive.
+pub fn yaml(content: &str, style: &Format) -> Result<String> {
+    let spec: Value = serde_yaml::from_str(content).context("The spec isn't valid YAML")?;
+    let mut out = String::new();
+    let mut writer = Yaml {
+        style,
+        out: &mut out,
+    };
+    match ordered(spec, &style.key_order) {
+        Value::Mapping(map) if !map.is_empty() => writer.entries(&map, 0, false),
+        Value::Sequence(items) if !items.is_empty() => writer.items(&items, 0, false),
+        other => {
+            let scalar = writer.scalar(&other);
+            writer.out.push_str(&scalar);
+            writer.out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// `content`, a JSON spec, rewritten with `style`'s indent and key order.
+pub fn json(content: &str, style: &Format) -> Result<String> {
+    // JSON is YAML, and serde_yaml keeps the keys in document order.
+    let spec: Value = serde_yaml::from_str(content).context("The spec isn't valid JSON")?;
+    let mut out = String::new();
+    write_json(&ordered(spec, &style.key_order), 0, style.indent, &mut out);
+    out.push('\n');
+    Ok(out)
+}
+
+/// Whether formatting `content` drops comments, on lines of their own or
+/// after a value.
+pub fn has_comments(content: &str) -> bool {
+    code_lines(content).any(|(line, code)| code.len() < line.len())
+}
+
+/// Whether formatting `content` expands anchors and aliases into copies.
+pub fn has_anchors(content: &str) -> bool {
+    code_lines(content).any(|(_, code)| {
+        let mut quote = None;
+        let mut prev = ' ';
+        code.chars().zip(code.chars().skip(1)).any(|(c, next)| {
+            let starts = quote.is_none()
+                && matches!(c, '&' | '*')
+                && (prev.is_whitespace() || "[{,".contains(prev))
+                && (next.is_alphanumeric() || "_-".contains(next));
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (None, '"' | '\'') if prev.is_whitespace() || "[{,:".contains(prev) => {
+                    quote = Some(c)
+                }
+                _ => {}
+            }
+            prev = c;
+            starts
+        })
+    })
+}
+
+/// Each line of `content` outside block scalars, with the part before
+/// any trailing comment.
+fn code_lines(content: &str) -> impl Iterator<Item = (&str, &str)> {
+    // The indent of the line a block scalar being skipped starts on.
+    let mut block: Option<usize> = None;
+    content.lines().filter_map(move |line| {
+        let indent = line.len() - line.trim_start().len();
+        if let Some(opener) = block {
+            if line.trim().is_empty() || indent > opener {
+                return None;
+            }
+            block = None;
+        }
+        let code = strip_comment(line);
+        if starts_block_scalar(code) {
+            block = Some(indent);
+        }
+        Some((line, code))
+    })
+}
+
+/// Whether `code` ends in a block scalar indicator, like `description: |`
+/// or `- >-`.
+fn starts_block_scalar(code: &str) -> bool {
+    let code = code
+        .trim_end()
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '+' || c == '-');
+    let Some(rest) = code.strip_suffix(['|', '>']) else {
+        return false;
+    };
+    rest.is_empty() || rest.ends_with(char::is_whitespace)
+}
+
+/// `text` up to a trailing `# comment`, outside quotes.
+pub fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match quote {
+            Some(q) if c == q => {
+                if q == '\'' && chars.peek().is_some_and(|&(_, n)| n == '\'') {
+                    chars.next();
+                } else {
+                    quote = None;
+                }
+            }
+            Some('"') if c == '\\' => {
+                chars.next();
+            }
+            Some(_) => {}
+            None if c == '#' && prev.is_whitespace() => return &text[..i],
+            None if matches!(c, '"' | '\'') && (prev.is_whitespace() || "[{,:".contains(prev)) => {
+                quote = Some(c);
+            }
+            None => {}
+        }
+        prev = c;
+    }
+    text
+}
+
+/// The top-level keys of `spec` in `order`, then the rest as they were.
+fn ordered(spec: Value, order: &[String]) -> Value {
+    let Value::Mapping(map) = spec else {
+        return spec;
+    };
+    let mut sorted = Mapping::new();
+    for key in order {
+        if let Some(value) = map.get(key.as_str()) {
+            sorted.insert(Value::String(key.clone()), value.clone());
+        }
+    }
+    for (key, value) in map {
+        if !sorted.contains_key(&key) {
+            sorted.insert(key, value);
+        }
+    }
+    Value::Mapping(sorted)
+}
+
+struct Yaml<'a> {
+    style: &'a Format,
+    out: &'a mut String,
+}
+
+impl Yaml<'_> {
+    /// One `key: value` line per entry at column `indent`; with
+    /// `inline_first` the first continues the current line, after `- `.
+    fn entries(&mut self, map: &Mapping, indent: usize, inline_first: bool) {
+        for (i, (key, value)) in map.iter().enumerate() {
+            if i > 0 || !inline_first {
+                self.pad(indent);
+            }
+            let key = self.key(key);
+            self.out.push_str(&key);
+            self.out.push(':');
+            self.value(value, indent);
+        }
+    }
+
+    /// One `- item` line per item at column `indent`.
+    fn items(&mut self, items: &[Value], indent: usize, inline_first: bool) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 || !inline_first {
+                self.pad(indent);
+            }
+            self.out.push('-');
+            match item {
+                Value::Mapping(map) if !map.is_empty() => {
+                    self.out.push(' ');
+                    self.entries(map, indent + 2, true);
+                }
+                Value::Sequence(nested) if !nested.is_empty() => {
+                    self.out.push(' ');
+                    self.items(nested, indent + 2, true);
+                }
+                _ => self.value(item, indent),
+            }
+        }
+    }
+
+    /// The rest of a line ending in `key:` or `-` at column `indent`.
+    fn value(&mut self, value: &Value, indent: usize) {
+        let nested = indent + self.style.indent;
+        match value {
+            Value::Mapping(map) if !map.is_empty() => {
+                self.out.push('\n');
+                self.entries(map, nested, false);
+            }
+            Value::Sequence(items) if !items.is_empty() => {
+                self.out.push('\n');
+                self.items(items, nested, false);
+            }
+            Value::Tagged(tagged) => {
+                self.out.push_str(&format!(" {}", tagged.tag));
+                self.value(&tagged.value, indent);
+            }
+            Value::String(s) if literal_block(s) => {
+                let body = s.trim_end_matches('\n');
+                self.out.push_str(match s.len() - body.len() {
+                    0 => " |-\n",
+                    1 => " |\n",
+                    _ => " |+\n",
+                });
+                for line in body.split('\n') {
+                    if !line.is_empty() {
+                        self.pad(nested);
+                        self.out.push_str(line);
+                    }
+                    self.out.push('\n');
+                }
+                for _ in 1..s.len() - body.len() {
+                    self.out.push('\n');
+                }
+            }
+            scalar => {
+                let scalar = self.scalar(scalar);
+                self.out.push(' ');
+                self.out.push_str(&scalar);
+                self.out.push('\n');
+            }
+        }
+    }
+
+    fn key(&self, key: &Value) -> String {
+        match key {
+            Value::Mapping(_) | Value::Sequence(_) | Value::Tagged(_) => {
+                // JSON is a YAML flow collection.
+                serde_json::to_string(key).unwrap_or_default()
+            }
+            scalar => self.scalar(scalar),
+        }
+    }
+
+    fn scalar(&self, value: &Value) -> String {
+        match value {
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) if plain(s) => s.clone(),
+            Value::String(s) => match self.style.quotes {
+                Quotes::Single if !s.chars().any(char::is_control) => {
+                    format!("'{}'", s.replace('\'', "''"))
+                }
+                _ => double_quoted(s),
+            },
+            Value::Mapping(_) => "{}".to_string(),
+            Value::Sequence(_) => "[]".to_string(),
+            Value::Tagged(tagged) => format!("{} {}", tagged.tag, self.scalar(&tagged.value)),
+        }
+    }
+
+    fn pad(&mut self, indent: usize) {
+        self.out.extend(std::iter::repeat_n(' ', indent));
+    }
+}
+
+/// Whether `s` reads back as the same string unquoted.
+fn plain(s: &str) -> bool {
+    !s.is_empty()
+        && s.trim() == s
+        && !s.chars().any(char::is_control)
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && matches!(serde_yaml::from_str::<Value>(s), Ok(Value::String(read)) if read == s)
+}
+
+/// Whether `s` can be written as a `|` block: it spans lines, holds no
+/// control characters besides newlines and tabs, and its first line
+/// doesn't start with whitespace (which would set the block's indent).
+fn literal_block(s: &str) -> bool {
+    s.trim_end_matches('\n').contains('\n')
+        && !s.chars().any(|c| c.is_control() && c != '\n' && c != '\t')
+        && !s.starts_with([' ', '\t', '\n'])
+        && !s.lines().any(|line| line.ends_with([' ', '\t']))
+}
+
+fn double_quoted(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn write_json(value: &Value, level: usize, indent: usize, out: &mut String) {
+    let pad = |out: &mut String, level: usize| out.extend(std::iter::repeat_n(' ', level * indent));
+    match value {
+        Value::Mapping(map) if !map.is_empty() => {
+            out.push_str("{\n");
+            for (i, (key, value)) in map.iter().enumerate() {
+                pad(out, level + 1);
+                let key = match key {
+                    Value::String(s) => s.clone(),
+                    other => serde_yaml::to_string(other).unwrap_or_default(),
+                };
+                out.push_str(&serde_json::to_string(key.trim_end()).unwrap_or_default());
+                out.push_str(": ");
+                write_json(value, level + 1, indent, out);
+                if i + 1 < map.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            pad(out, level);
+            out.push('}');
+        }
+        Value::Sequence(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                pad(out, level + 1);
+                write_json(item, level + 1, indent, out);
+                if i + 1 < items.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            pad(out, level);
+            out.push(']');
+        }
+        Value::Mapping(_) => out.push_str("{}"),
+        Value::Sequence(_) => out.push_str("[]"),
+        Value::Tagged(tagged) => write_json(&tagged.value, level, indent, out),
+        Value::String(s) => out.push_str(&serde_json::to_string(s).unwrap_or_default()),
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(&b.to_string()),
+        Value::Number(n) => out.push_str(&n.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"paths:
+    /pets:
+        get:
+            parameters:
+            - {name: limit, in: query}
+            responses:
+                "200": {description: "OK"}
+info: {title: Pets, version: "1.0"}
+openapi: 3.0.3
+x-notes: "line one\nline two\n"
+"#;
+
+    #[test]
+    fn yaml_is_reindented_and_reordered() {
+        let formatted = yaml(SPEC, &Format::default()).unwrap();
+        assert_eq!(
+            formatted,
+            "\
+openapi: 3.0.3
+info:
+  title: Pets
+  version: '1.0'
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+      responses:
+        '200':
+          description: OK
+x-notes: |
+  line one
+  line two
+"
+        );
+    }
+
+    #[test]
+    fn yaml_round_trips_awkward_strings() {
+        let strings = [
+            "",
+            "yes",
+            "null",
+            "123",
+            "a: b",
+            "a #b",
+            " padded",
+            "- item",
+            "'quoted'",
+            "it's",
+            "tab\there",
+            "bell\u{7}",
+            "{braces}",
+            "*alias",
+            "trailing\n\n",
+            "kept\nblank lines\n\n",
+            "no newline\nat end",
+            "  indented\nfirst line",
+            "spaces \nat end",
+        ];
+        let spec = Value::Sequence(strings.iter().map(|s| Value::from(*s)).collect());
+        let content = serde_yaml::to_string(&spec).unwrap();
+        for quotes in [Quotes::Single, Quotes::Double] {
+            let style = Format {
+                quotes,
+                ..Format::default()
+            };
+            let formatted = yaml(&content, &style).unwrap();
+            let read: Value = serde_yaml::from_str(&formatted).unwrap();
+            assert_eq!(read, spec, "{formatted}");
+        }
+    }
+
+    #[test]
+    fn comments_and_anchors_that_would_be_lost_are_noticed() {
+        let block = "info:\n  description: |\n    # A heading, not a comment\n  title: 'a #b'\n";
+        assert!(!has_comments(block));
+        assert!(has_comments("openapi: 3.0.3 # current\n"));
+        assert!(has_comments("# Pets\nopenapi: 3.0.3\n"));
+
+        assert!(!has_anchors("title: '&quoted'\nsummary: a & b\n"));
+        assert!(has_anchors("base: &base\n  type: object\npet: *base\n"));
+        assert!(!has_anchors("description: >-\n  *not an alias\n"));
+    }
+
+    #[test]
+    fn json_keeps_key_order_and_uses_the_indent() {
+        let style = Format {
+            indent: 4,
+            ..Format::default()
+        };
+        let formatted = json(
+            r#"{"paths": {}, "openapi": "3.1.0", "info": {"title": "Pets", "x-tags": ["a"]}}"#,
+            &style,
+        )
+        .unwrap();
+        assert_eq!(
+            formatted,
+            r#"{
+    "openapi": "3.1.0",
+    "info": {
+        "title": "Pets",
+        "x-tags": [
+            "a"
+        ]
+    },
+    "paths": {}
+}
+"#
+        );
+    }
+}
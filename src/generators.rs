@@ -114,6 +114,35 @@ pub fn find_builtin(name: &str, scope: &str) -> Option<&'static GeneratorDef> {
         .find(|g| g.name == name)
 }
 
+/// Parse the output of `openapi-generator list` into `(name, scope)` pairs.
+///
+/// Only the `SERVER` and `CLIENT` sections are kept; documentation, schema
+/// and config generators can't be validated by the pipeline. Stability tags
+/// such as `(beta)` are dropped.
+pub fn parse_generator_list(output: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut scope: Option<&str> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_suffix("generators:") {
+            scope = match header.trim().to_ascii_lowercase().as_str() {
+                "server" => Some("server"),
+                "client" => Some("client"),
+                _ => None,
+            };
+            continue;
+        }
+        if let (Some(scope), Some(entry)) = (scope, trimmed.strip_prefix("- "))
+            && let Some(name) = entry.split_whitespace().next()
+        {
+            pairs.push((name.to_string(), scope.to_string()));
+        }
+    }
+
+    pairs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +191,37 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn parse_generator_list_keeps_server_and_client_sections() {
+        let output = "\
+The following generators are available:
+
+CLIENT generators:
+    - ada
+    - typescript-axios
+
+SERVER generators:
+    - ada-server (beta)
+    - spring
+
+DOCUMENTATION generators:
+    - html
+";
+        let pairs = parse_generator_list(output);
+        assert_eq!(
+            pairs,
+            vec![
+                ("ada".to_string(), "client".to_string()),
+                ("typescript-axios".to_string(), "client".to_string()),
+                ("ada-server".to_string(), "server".to_string()),
+                ("spring".to_string(), "server".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_generator_list_ignores_unrelated_output() {
+        assert!(parse_generator_list("Unable to find image locally\n- nope\n").is_empty());
+    }
 }
@@ -0,0 +1,169 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// openapi-generator's ignore file, read from the root of each output
+/// directory. Files it matches are left alone on the next generate.
+pub const IGNORE_FILE: &str = ".openapi-generator-ignore";
+
+const HEADER: &str = "\
+# Files openapi-generator leaves alone when it regenerates this directory.
+# Patterns follow .gitignore: `*` and `**` wildcards, `!` to re-include.
+";
+
+/// Patterns in `output_dir`'s ignore file, without comments and blank
+/// lines. A missing file means none.
+pub fn patterns(output_dir: &Path) -> Result<Vec<String>> {
+    let path = output_dir.join(IGNORE_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Append `pattern` to `output_dir`'s ignore file, creating it if needed.
+/// Returns `false` if the pattern is already there.
+pub fn add(output_dir: &Path, pattern: &str) -> Result<bool> {
+    if patterns(output_dir)?.iter().any(|p| p == pattern) {
+        return Ok(false);
+    }
+
+    let path = output_dir.join(IGNORE_FILE);
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let mut entry = String::new();
+    if existing.is_empty() {
+        entry.push_str(HEADER);
+    } else if !existing.ends_with('\n') {
+        entry.push('\n');
+    }
+    entry.push_str(pattern);
+    entry.push('\n');
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(entry.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// The pattern that ignores `rel` (a `/`-separated path inside the output
+/// directory): the path itself for a file, everything below for a directory.
+pub fn pattern_for(rel: &str, is_dir: bool) -> String {
+    if is_dir {
+        format!("{rel}/**")
+    } else {
+        rel.to_string()
+    }
+}
+
+/// Whether `patterns` ignore `rel`. As in `.gitignore`, the last matching
+/// pattern wins, and a pattern without a `/` matches the name at any depth.
+pub fn is_ignored(patterns: &[String], rel: &str) -> bool {
+    let path: Vec<&str> = rel.split('/').filter(|s| !s.is_empty()).collect();
+    let mut ignored = false;
+    for pattern in patterns {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern.as_str()),
+        };
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_start_matches('/').trim_end_matches('/');
+        let segments: Vec<&str> = pattern.split('/').collect();
+        let matched = if segments.len() == 1 && !dir_only {
+            path.last().is_some_and(|name| glob(segments[0], name))
+        } else if dir_only {
+            // `dir/` covers the directory and everything below it.
+            (1..=path.len()).any(|n| match_segments(&segments, &path[..n]))
+        } else {
+            match_segments(&segments, &path)
+        };
+        if matched {
+            ignored = !negated;
+        }
+    }
+    ignored
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(name, path)| glob(first, name) && match_segments(rest, path)),
+    }
+}
+
+/// `*` and `?` wildcards within one path segment.
+fn glob(pattern: &str, name: &str) -> bool {
+    fn go(p: &[char], n: &[char]) -> bool {
+        match p.split_first() {
+            None => n.is_empty(),
+            Some(('*', rest)) => (0..=n.len()).any(|skip| go(rest, &n[skip..])),
+            Some(('?', rest)) => !n.is_empty() && go(rest, &n[1..]),
+            Some((c, rest)) => n.first() == Some(c) && go(rest, &n[1..]),
+        }
+    }
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    go(&p, &n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_appends_once_below_a_header() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(patterns(dir.path()).unwrap().is_empty());
+
+        assert!(add(dir.path(), "pom.xml").unwrap());
+        assert!(add(dir.path(), "src/main/**").unwrap());
+        assert!(!add(dir.path(), "pom.xml").unwrap());
+
+        assert_eq!(patterns(dir.path()).unwrap(), ["pom.xml", "src/main/**"]);
+        let content = fs::read_to_string(dir.path().join(IGNORE_FILE)).unwrap();
+        assert!(content.starts_with(HEADER));
+    }
+
+    #[test]
+    fn is_ignored_follows_gitignore_rules() {
+        let patterns: Vec<String> = [
+            "ApiClient.cs",
+            "docs/*.md",
+            "!docs/README.md",
+            "src/**/impl",
+            "build/",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        assert!(is_ignored(&patterns, "src/Org/ApiClient.cs"));
+        assert!(is_ignored(&patterns, "docs/PetApi.md"));
+        assert!(!is_ignored(&patterns, "docs/README.md"));
+        assert!(!is_ignored(&patterns, "docs/models/Pet.md"));
+        assert!(is_ignored(&patterns, "src/impl"));
+        assert!(is_ignored(&patterns, "src/a/b/impl"));
+        assert!(is_ignored(&patterns, "build/classes/Api.class"));
+        assert!(!is_ignored(&patterns, "pom.xml"));
+
+        let dir = [pattern_for("src/main", true)];
+        assert!(is_ignored(&dir, "src/main"));
+        assert!(is_ignored(&dir, "src/main/java/Api.java"));
+        assert!(!is_ignored(&dir, "src/test/ApiTest.java"));
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+use serde_yaml::Value;
+
+use crate::openapi::METHODS;
+
+/// Something that can hold or be the target of a `$ref`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Node {
+    /// `components/<section>/<name>`, e.g. `("schemas", "Pet")`.
+    Component {
+        section: String,
+        name: String,
+    },
+    Operation {
+        method: String,
+        path: String,
+    },
+}
+
+impl Node {
+    /// The component or operation a JSON pointer lies in.
+    pub fn at_pointer(pointer: &str) -> Option<Self> {
+        let segments: Vec<String> = pointer
+            .split('/')
+            .skip(1)
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        match segments.as_slice() {
+            [first, section, name, ..] if first == "components" => Some(Node::Component {
+                section: section.clone(),
+                name: name.clone(),
+            }),
+            [first, path, method, ..] if first == "paths" && METHODS.contains(&method.as_str()) => {
+                Some(Node::Operation {
+                    method: method.clone(),
+                    path: path.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Component { section, name } => {
+                let kind = match section.as_str() {
+                    "requestBodies" => "request body",
+                    "securitySchemes" => "security scheme",
+                    other => other.strip_suffix('s').unwrap_or(other),
+                };
+                write!(f, "{kind} {name}")
+            }
+            Node::Operation { method, path } => write!(f, "{} {path}", method.to_uppercase()),
+        }
+    }
+}
+
+/// The spec's `$ref` dependencies between components and operations.
+#[derive(Debug, Clone, Default)]
+pub struct RefGraph {
+    uses: BTreeMap<Node, BTreeSet<Node>>,
+    used_by: BTreeMap<Node, BTreeSet<Node>>,
+}
+
+impl RefGraph {
+    pub fn build(spec: &Value) -> Self {
+        let mut graph = Self::default();
+        for (section, entries) in spec
+            .get("components")
+            .and_then(Value::as_mapping)
+            .into_iter()
+            .flatten()
+        {
+            let Some(section) = section.as_str() else {
+                continue;
+            };
+            for (name, node) in entries.as_mapping().into_iter().flatten() {
+                let Some(name) = name.as_str() else {
+                    continue;
+                };
+                let from = Node::Component {
+                    section: section.to_string(),
+                    name: name.to_string(),
+                };
+                graph.add_refs(from, node);
+            }
+        }
+        for (path, item) in spec
+            .get("paths")
+            .and_then(Value::as_mapping)
+            .into_iter()
+            .flatten()
+        {
+            let Some(path) = path.as_str() else {
+                continue;
+            };
+            for method in METHODS {
+                if let Some(op) = item.get(*method) {
+                    let from = Node::Operation {
+                        method: method.to_string(),
+                        path: path.to_string(),
+                    };
+                    graph.add_refs(from.clone(), op);
+                    // Path-level parameters apply to every operation.
+                    if let Some(params) = item.get("parameters") {
+                        graph.add_refs(from, params);
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    fn add_refs(&mut self, from: Node, node: &Value) {
+        let mut refs = Vec::new();
+        collect_refs(node, &mut refs);
+        for to in refs.iter().filter_map(|r| component_of(r)) {
+            let to = Node::Component {
+                section: to.0,
+                name: to.1,
+            };
+            if to == from {
+                continue;
+            }
+            self.used_by
+                .entry(to.clone())
+                .or_default()
+                .insert(from.clone());
+            self.uses.entry(from.clone()).or_default().insert(to);
+        }
+    }
+
+    /// Everything `node` references, directly (depth 1) or through other
+    /// components, in breadth-first order.
+    pub fn uses(&self, node: &Node) -> Vec<(usize, Node)> {
+        walk(&self.uses, node)
+    }
+
+    /// Everything that references `node`, directly or transitively, in
+    /// breadth-first order.
+    pub fn used_by(&self, node: &Node) -> Vec<(usize, Node)> {
+        walk(&self.used_by, node)
+    }
+}
+
+fn walk(edges: &BTreeMap<Node, BTreeSet<Node>>, start: &Node) -> Vec<(usize, Node)> {
+    let mut seen = BTreeSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(0, start.clone())]);
+    let mut out = Vec::new();
+    while let Some((depth, node)) = queue.pop_front() {
+        for next in edges.get(&node).into_iter().flatten() {
+            if seen.insert(next.clone()) {
+                out.push((depth + 1, next.clone()));
+                queue.push_back((depth + 1, next.clone()));
+            }
+        }
+    }
+    out
+}
+
+/// Every `$ref` string below `node`.
+pub(crate) fn collect_refs(node: &Value, out: &mut impl Extend<String>) {
+    match node {
+        Value::Mapping(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value.as_str()) {
+                    (Some("$ref"), Some(reference)) => {
+                        out.extend(std::iter::once(reference.to_string()));
+                    }
+                    _ => collect_refs(value, out),
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                collect_refs(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `#/components/schemas/Pet/properties/id` → `("schemas", "Pet")`.
+pub(crate) fn component_of(reference: &str) -> Option<(String, String)> {
+    let mut segments = reference
+        .strip_prefix("#/components/")?
+        .split('/')
+        .map(|s| s.replace("~1", "/").replace("~0", "~"));
+    Some((segments.next()?, segments.next()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(name: &str) -> Node {
+        Node::Component {
+            section: "schemas".into(),
+            name: name.into(),
+        }
+    }
+
+    #[test]
+    fn walks_references_both_ways_with_depth() {
+        let spec: Value = serde_yaml::from_str(
+            r##"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Dog'
+components:
+  schemas:
+    Pet:
+      properties:
+        owner:
+          $ref: '#/components/schemas/Owner'
+        parent:
+          $ref: '#/components/schemas/Pet'
+    Dog:
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+    Owner:
+      type: object
+"##,
+        )
+        .unwrap();
+        let graph = RefGraph::build(&spec);
+
+        let used_by: Vec<(usize, String)> = graph
+            .used_by(&schema("Pet"))
+            .into_iter()
+            .map(|(d, n)| (d, n.to_string()))
+            .collect();
+        assert_eq!(
+            used_by,
+            vec![(1, "schema Dog".into()), (2, "GET /pets".into())]
+        );
+
+        let uses: Vec<(usize, String)> = graph
+            .uses(&schema("Dog"))
+            .into_iter()
+            .map(|(d, n)| (d, n.to_string()))
+            .collect();
+        assert_eq!(
+            uses,
+            vec![(1, "schema Pet".into()), (2, "schema Owner".into())]
+        );
+    }
+
+    #[test]
+    fn node_at_pointer() {
+        assert_eq!(
+            Node::at_pointer("/components/schemas/Pet/properties/id"),
+            Some(schema("Pet"))
+        );
+        assert_eq!(
+            Node::at_pointer("/paths/~1pets/get/responses").map(|n| n.to_string()),
+            Some("GET /pets".into())
+        );
+        assert_eq!(Node::at_pointer("/info/title"), None);
+    }
+}
@@ -1,74 +1,231 @@
 mod convert;
+mod worker;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use ratatui::style::Style;
-use syntect::highlighting::{Theme, ThemeSet};
-use syntect::parsing::SyntaxSet;
 
+use worker::{Job, Update, Worker};
+
+/// Documents kept highlighted, so switching between the spec and a few
+/// generated files doesn't re-highlight them.
+const CAPACITY: usize = 8;
+
+/// Syntax highlighting done on a background thread. Until a line has been
+/// highlighted it is shown as plain text.
 pub struct HighlightEngine {
-    syntax_set: SyntaxSet,
-    theme: Theme,
-    cache: Option<CachedHighlight>,
+    worker: Worker,
+    generation: u64,
+    /// Most recently used first.
+    cache: Vec<CachedHighlight>,
+    /// Content hash and syntax of the text the worker last worked on.
+    submitted: Option<(u64, String)>,
 }
 
 struct CachedHighlight {
+    /// Version the document was last requested with, for a cheap lookup.
     version: u64,
+    hash: u64,
     syntax_name: String,
+    raw: Arc<[String]>,
     lines: Vec<Vec<(Style, String)>>,
+    /// Leading lines whose highlighting is final.
+    done: usize,
+    /// Generation of the job still filling in `lines`, if any.
+    pending: Option<u64>,
+}
+
+impl CachedHighlight {
+    fn is_complete(&self) -> bool {
+        self.done >= self.lines.len()
+    }
 }
 
 impl HighlightEngine {
     pub fn new() -> Self {
-        let syntax_set = SyntaxSet::load_defaults_newlines();
-        let theme = ThemeSet::load_defaults().themes["base16-ocean.dark"].clone();
         Self {
-            syntax_set,
-            theme,
-            cache: None,
+            worker: Worker::spawn(),
+            generation: 0,
+            cache: Vec::new(),
+            submitted: None,
         }
     }
 
     /// Highlight raw lines using the given syntax name.
     ///
-    /// Cache is keyed on `version` (from `SpecIndex::version()`) and `syntax_name`,
-    /// making cache-hit checks O(1) regardless of file size.
+    /// Documents are cached by content hash and syntax, least recently used
+    /// dropped first. A `version` seen before (from `SpecIndex::version()`)
+    /// finds its document without hashing the content. Highlighting happens
+    /// in the background: a new document keeps the lines it shares with the
+    /// previous one and shows the rest as plain text until the worker
+    /// catches up.
     pub fn highlight_lines(
         &mut self,
         raw_lines: &[String],
         syntax_name: &str,
         version: u64,
     ) -> &[Vec<(Style, String)>] {
-        let needs_highlight = match &self.cache {
-            Some(cached) => cached.version != version || cached.syntax_name != syntax_name,
-            None => true,
-        };
+        self.apply_updates();
+        let found = self
+            .cache
+            .iter()
+            .position(|c| c.version == version && c.syntax_name == syntax_name)
+            .or_else(|| {
+                let hash = content_hash(raw_lines);
+                self.cache
+                    .iter()
+                    .position(|c| c.hash == hash && c.syntax_name == syntax_name)
+            });
+        match found {
+            Some(index) => {
+                let mut cached = self.cache.remove(index);
+                cached.version = version;
+                self.cache.insert(0, cached);
+                self.resume();
+            }
+            None => self.invalidate(raw_lines, syntax_name, version),
+        }
+        &self.cache[0].lines
+    }
+
+    /// Whether lines are still being highlighted in the background.
+    pub fn is_busy(&self) -> bool {
+        self.cache.iter().any(|c| c.pending.is_some())
+    }
 
-        if needs_highlight {
-            let syntax = self
-                .syntax_set
-                .find_syntax_by_name(syntax_name)
-                .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
-
-            let mut highlighter = syntect::easy::HighlightLines::new(syntax, &self.theme);
-
-            let highlighted: Vec<Vec<(Style, String)>> = raw_lines
-                .iter()
-                .map(|line| {
-                    let ranges = highlighter
-                        .highlight_line(line, &self.syntax_set)
-                        .unwrap_or_default();
-                    convert::syntect_to_ratatui_spans(&ranges)
-                })
-                .collect();
-
-            self.cache = Some(CachedHighlight {
+    /// Start a new document, reusing the highlighting of the lines it shares
+    /// with the one the worker last highlighted.
+    fn invalidate(&mut self, raw_lines: &[String], syntax_name: &str, version: u64) {
+        let raw: Arc<[String]> = raw_lines.into();
+        let hash = content_hash(&raw);
+        let base = self
+            .submitted
+            .as_ref()
+            .filter(|(_, syntax)| syntax == syntax_name)
+            .and_then(|(hash, syntax)| {
+                self.cache
+                    .iter()
+                    .find(|c| c.hash == *hash && c.syntax_name == *syntax)
+            });
+        let (lines, done, trusted) = match base {
+            Some(old) => {
+                let trusted = old.done.min(old.raw.len());
+                let (prefix, suffix) = common_ends(&old.raw, &raw);
+                let mut lines = old.lines[..prefix].to_vec();
+                lines.extend(raw[prefix..raw.len() - suffix].iter().map(|l| plain(l)));
+                lines.extend_from_slice(&old.lines[old.raw.len() - suffix..]);
+                (lines, prefix.min(trusted), trusted)
+            }
+            None => (raw.iter().map(|l| plain(l)).collect(), 0, 0),
+        };
+        self.cache.insert(
+            0,
+            CachedHighlight {
                 version,
+                hash,
                 syntax_name: syntax_name.to_owned(),
-                lines: highlighted,
-            });
+                raw,
+                lines,
+                done,
+                pending: None,
+            },
+        );
+        self.cache.truncate(CAPACITY);
+        self.submit(trusted);
+    }
+
+    /// Restart highlighting of the most recent document if an earlier
+    /// switch away left it unfinished.
+    fn resume(&mut self) {
+        let cached = &mut self.cache[0];
+        if cached.pending.is_some() || cached.is_complete() {
+            return;
+        }
+        // The worker has moved on to other text; start this one over.
+        cached.done = 0;
+        self.submit(0);
+    }
+
+    /// Hand the most recent document to the worker. `trusted` is how many
+    /// leading lines of the previously submitted text are final.
+    fn submit(&mut self, trusted: usize) {
+        self.generation += 1;
+        for other in &mut self.cache {
+            other.pending = None;
         }
+        let cached = &mut self.cache[0];
+        cached.pending = Some(self.generation);
+        self.submitted = Some((cached.hash, cached.syntax_name.clone()));
+        self.worker.submit(Job {
+            generation: self.generation,
+            syntax_name: cached.syntax_name.clone(),
+            lines: Arc::clone(&cached.raw),
+            trusted,
+        });
+    }
 
-        &self.cache.as_ref().unwrap().lines
+    /// Fold in whatever the worker has published for the current job.
+    fn apply_updates(&mut self) {
+        while let Ok(update) = self.worker.updates.try_recv() {
+            let generation = match &update {
+                Update::Lines { generation, .. } | Update::Done { generation } => *generation,
+            };
+            let Some(cached) = self
+                .cache
+                .iter_mut()
+                .find(|c| c.pending == Some(generation))
+            else {
+                continue;
+            };
+            match update {
+                Update::Lines { start, lines, .. } => {
+                    let end = start + lines.len();
+                    for (slot, line) in cached.lines[start..end].iter_mut().zip(lines) {
+                        *slot = line;
+                    }
+                    cached.done = cached.done.max(end);
+                }
+                Update::Done { .. } => {
+                    cached.done = cached.lines.len();
+                    cached.pending = None;
+                }
+            }
+        }
     }
+
+    /// Block until the background job is done.
+    #[cfg(test)]
+    fn wait(&mut self) {
+        while self.is_busy() {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            self.apply_updates();
+        }
+    }
+}
+
+/// Number of equal lines at the start and, not overlapping those, at the
+/// end of `old` and `new`.
+fn common_ends(old: &[String], new: &[String]) -> (usize, usize) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (prefix, suffix)
+}
+
+fn content_hash(lines: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    lines.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn plain(line: &str) -> Vec<(Style, String)> {
+    vec![(Style::default(), line.to_owned())]
 }
 
 #[cfg(test)]
@@ -93,14 +250,14 @@ mod tests {
         let lines: Vec<String> = vec!["key: value\n".into()];
 
         engine.highlight_lines(&lines, "YAML", 42);
-        assert!(engine.cache.is_some());
+        assert!(!engine.cache.is_empty());
 
         // Same version → cache hit (even with different Vec instance).
         let lines2: Vec<String> = vec!["different: content\n".into()];
         let result = engine.highlight_lines(&lines2, "YAML", 42);
         // Returns the original cached result, not re-highlighted.
         assert_eq!(result.len(), 1);
-        assert_eq!(engine.cache.as_ref().unwrap().version, 42);
+        assert_eq!(engine.cache[0].version, 42);
     }
 
     #[test]
@@ -109,12 +266,87 @@ mod tests {
         let lines: Vec<String> = vec!["key: value\n".into()];
 
         engine.highlight_lines(&lines, "YAML", 1);
-        assert!(engine.cache.is_some());
+        assert!(!engine.cache.is_empty());
 
         let lines2: Vec<String> = vec!["a: b\n".into(), "c: d\n".into()];
         let result = engine.highlight_lines(&lines2, "YAML", 2);
         assert_eq!(result.len(), 2);
-        assert_eq!(engine.cache.as_ref().unwrap().version, 2);
+        assert_eq!(engine.cache[0].version, 2);
+    }
+
+    fn numbered(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("key{i}: value\n")).collect()
+    }
+
+    fn text(line: &[(Style, String)]) -> String {
+        line.iter().map(|(_, t)| t.as_str()).collect()
+    }
+
+    fn is_plain(line: &[(Style, String)]) -> bool {
+        line.iter().all(|(style, _)| *style == Style::default())
+    }
+
+    #[test]
+    fn lines_start_plain_and_fill_in_from_the_worker() {
+        let mut engine = HighlightEngine::new();
+        let lines = numbered(500);
+        let first = engine.highlight_lines(&lines, "YAML", 1).to_vec();
+        assert_eq!(first.len(), 500);
+        assert_eq!(first[0], vec![(Style::default(), lines[0].clone())]);
+
+        engine.wait();
+        let done = engine.highlight_lines(&lines, "YAML", 1);
+        assert!(done.iter().all(|line| !is_plain(line)));
+        assert!(!engine.is_busy());
+    }
+
+    #[test]
+    fn an_edit_keeps_highlighting_around_the_changed_lines() {
+        let mut engine = HighlightEngine::new();
+        let mut lines = numbered(500);
+        engine.highlight_lines(&lines, "YAML", 1);
+        engine.wait();
+
+        lines[250] = "changed: true\n".into();
+        lines.insert(251, "added: 1\n".into());
+        let pending = engine.highlight_lines(&lines, "YAML", 2).to_vec();
+        assert_eq!(pending.len(), 501);
+        assert!(!is_plain(&pending[249]));
+        assert!(is_plain(&pending[250]) && is_plain(&pending[251]));
+        assert!(!is_plain(&pending[252]));
+        assert_eq!(text(&pending[252]), lines[252]);
+
+        engine.wait();
+        let done = engine.highlight_lines(&lines, "YAML", 2);
+        assert!(done.iter().all(|line| !is_plain(line)));
+        assert_eq!(text(&done[251]), "added: 1\n");
+    }
+
+    #[test]
+    fn switching_documents_reuses_their_highlighting() {
+        let mut engine = HighlightEngine::new();
+        let spec = numbered(300);
+        let generated: Vec<String> = vec!["class Pet {}\n".into(); 50];
+        engine.highlight_lines(&spec, "YAML", 1);
+        engine.wait();
+        engine.highlight_lines(&generated, "Java", 7);
+        engine.wait();
+
+        // Back to the spec, under a new version but the same content.
+        let lines = engine.highlight_lines(&spec, "YAML", 2);
+        assert!(lines.iter().all(|line| !is_plain(line)));
+        assert!(!engine.is_busy());
+        assert_eq!(engine.cache.len(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_document_is_dropped() {
+        let mut engine = HighlightEngine::new();
+        for version in 0..=CAPACITY as u64 {
+            engine.highlight_lines(&[format!("doc{version}: x\n")], "YAML", version);
+        }
+        assert_eq!(engine.cache.len(), CAPACITY);
+        assert!(engine.cache.iter().all(|c| c.version != 0));
     }
 
     #[test]
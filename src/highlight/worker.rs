@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use ratatui::style::Style;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{HighlightState, Theme, ThemeSet};
+use syntect::parsing::{ParseState, SyntaxSet};
+
+use super::{common_ends, convert};
+
+/// Lines highlighted before the worker publishes them.
+const BATCH: usize = 200;
+
+/// Parser state is saved at the start of every this many lines, so an edit
+/// only re-highlights from the checkpoint before it.
+const CHECKPOINT_EVERY: usize = 64;
+
+/// Parser and highlighter state at the start of a line. It holds regex
+/// state that can't leave the worker thread.
+type LineState = (HighlightState, ParseState);
+
+/// A request to highlight `lines`, replacing the previous job's text.
+pub struct Job {
+    pub generation: u64,
+    pub syntax_name: String,
+    pub lines: Arc<[String]>,
+    /// How many leading lines of the previous job's text the requester
+    /// holds final highlighting for.
+    pub trusted: usize,
+}
+
+pub enum Update {
+    /// Highlighted `lines` starting at line `start`.
+    Lines {
+        generation: u64,
+        start: usize,
+        lines: Vec<Vec<(Style, String)>>,
+    },
+    /// The job finished; lines not sent were already up to date.
+    Done { generation: u64 },
+}
+
+/// Handle to the background highlighting thread. Dropping it stops the
+/// thread once its current batch is done.
+pub struct Worker {
+    jobs: Sender<Job>,
+    pub updates: Receiver<Update>,
+    latest: Arc<AtomicU64>,
+}
+
+impl Worker {
+    pub fn spawn() -> Self {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        let (update_tx, updates) = mpsc::channel();
+        let latest = Arc::new(AtomicU64::new(0));
+        let current = Arc::clone(&latest);
+        thread::spawn(move || {
+            let syntax_set = SyntaxSet::load_defaults_newlines();
+            let theme = ThemeSet::load_defaults().themes["base16-ocean.dark"].clone();
+            let mut doc = Document::default();
+            while let Ok(job) = job_rx.recv() {
+                let stale = || current.load(Ordering::Relaxed) != job.generation;
+                let (start, known) = doc.rebase(&job);
+                // A newer job is queued; it rebases on this one's text.
+                if stale() {
+                    continue;
+                }
+                let run = Run {
+                    job: &job,
+                    syntax_set: &syntax_set,
+                    theme: &theme,
+                    updates: &update_tx,
+                };
+                if !run.highlight(&mut doc, start, &known, stale) {
+                    break;
+                }
+            }
+        });
+        Self {
+            jobs,
+            updates,
+            latest,
+        }
+    }
+
+    /// Queue `job`, abandoning whatever the worker is doing.
+    pub fn submit(&self, job: Job) {
+        self.latest.store(job.generation, Ordering::Relaxed);
+        // A dead worker leaves the plain-text fallback in place.
+        let _ = self.jobs.send(job);
+    }
+}
+
+/// The text of the last job and the parser checkpoints computed for it.
+#[derive(Default)]
+struct Document {
+    syntax_name: String,
+    raw: Arc<[String]>,
+    checkpoints: BTreeMap<usize, LineState>,
+}
+
+impl Document {
+    /// Move to `job`'s text, keeping checkpoints that still hold. Returns
+    /// the line to restart from and, when the old text was fully
+    /// highlighted, the checkpoints of its unchanged tail: reaching one of
+    /// those in the same state means the rest is already right.
+    fn rebase(&mut self, job: &Job) -> (usize, BTreeMap<usize, LineState>) {
+        let old = std::mem::replace(&mut self.raw, Arc::clone(&job.lines));
+        if self.syntax_name != job.syntax_name {
+            self.syntax_name.clone_from(&job.syntax_name);
+            self.checkpoints.clear();
+            return (0, BTreeMap::new());
+        }
+        let (prefix, suffix) = common_ends(&old, &job.lines);
+        let restart = self
+            .checkpoints
+            .range(..=prefix.min(job.trusted))
+            .next_back()
+            .map_or(0, |(&i, _)| i);
+        let tail = self.checkpoints.split_off(&(restart + 1));
+        let old_tail_start = old.len() - suffix;
+        let new_tail_start = job.lines.len() - suffix;
+        let known = if job.trusted >= old.len() {
+            tail.into_iter()
+                .filter(|&(i, _)| i >= old_tail_start)
+                .map(|(i, state)| (i - old_tail_start + new_tail_start, state))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        (restart, known)
+    }
+}
+
+struct Run<'a> {
+    job: &'a Job,
+    syntax_set: &'a SyntaxSet,
+    theme: &'a Theme,
+    updates: &'a Sender<Update>,
+}
+
+impl Run<'_> {
+    /// Highlight from line `start`, publishing batches as they're ready.
+    /// Returns `false` once nobody is listening any more.
+    fn highlight(
+        &self,
+        doc: &mut Document,
+        start: usize,
+        known: &BTreeMap<usize, LineState>,
+        stale: impl Fn() -> bool,
+    ) -> bool {
+        let (job, theme) = (self.job, self.theme);
+        let syntax = self
+            .syntax_set
+            .find_syntax_by_name(&job.syntax_name)
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+        let mut highlighter = match doc.checkpoints.get(&start).cloned() {
+            Some((highlight, parse)) if start > 0 => {
+                HighlightLines::from_state(theme, highlight, parse)
+            }
+            _ => HighlightLines::new(syntax, theme),
+        };
+        let mut index = start;
+        while index < job.lines.len() {
+            if stale() {
+                return true;
+            }
+            let batch_start = index;
+            let mut lines = Vec::new();
+            while index < job.lines.len() && lines.len() < BATCH {
+                if index > start
+                    && (index.is_multiple_of(CHECKPOINT_EVERY) || known.contains_key(&index))
+                {
+                    let (highlight, parse) = highlighter.state();
+                    if known
+                        .get(&index)
+                        .is_some_and(|(h, p)| *h == highlight && *p == parse)
+                    {
+                        // Everything below matches the earlier run.
+                        doc.checkpoints
+                            .extend(known.range(index..).map(|(&i, s)| (i, s.clone())));
+                        return self.publish(batch_start, lines) && self.done();
+                    }
+                    if index.is_multiple_of(CHECKPOINT_EVERY) {
+                        doc.checkpoints
+                            .insert(index, (highlight.clone(), parse.clone()));
+                    }
+                    highlighter = HighlightLines::from_state(theme, highlight, parse);
+                }
+                let ranges = highlighter
+                    .highlight_line(&job.lines[index], self.syntax_set)
+                    .unwrap_or_default();
+                lines.push(convert::syntect_to_ratatui_spans(&ranges));
+                index += 1;
+            }
+            if !self.publish(batch_start, lines) {
+                return false;
+            }
+        }
+        self.done()
+    }
+
+    fn publish(&self, start: usize, lines: Vec<Vec<(Style, String)>>) -> bool {
+        self.updates
+            .send(Update::Lines {
+                generation: self.job.generation,
+                start,
+                lines,
+            })
+            .is_ok()
+    }
+
+    fn done(&self) -> bool {
+        self.updates
+            .send(Update::Done {
+                generation: self.job.generation,
+            })
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `job` to completion and return how many lines were highlighted.
+    fn highlighted(worker: &Worker, job: Job) -> usize {
+        let generation = job.generation;
+        worker.submit(job);
+        let mut count = 0;
+        loop {
+            match worker.updates.recv().unwrap() {
+                Update::Lines { lines, .. } => count += lines.len(),
+                Update::Done { generation: g } if g == generation => return count,
+                Update::Done { .. } => {}
+            }
+        }
+    }
+
+    #[test]
+    fn an_edit_only_rehighlights_until_the_state_settles() {
+        let worker = Worker::spawn();
+        let mut lines: Vec<String> = (0..1000).map(|i| format!("key{i}: value\n")).collect();
+        let job = |generation, lines: &[String], trusted| Job {
+            generation,
+            syntax_name: "YAML".into(),
+            lines: lines.into(),
+            trusted,
+        };
+        assert_eq!(highlighted(&worker, job(1, &lines, 0)), 1000);
+
+        lines[500] = "changed: true\n".into();
+        let count = highlighted(&worker, job(2, &lines, 1000));
+        // From the checkpoint before the edit (448) to the one after it (512).
+        assert_eq!(count, CHECKPOINT_EVERY);
+    }
+}
@@ -0,0 +1,352 @@
+//! Shell commands from `hooks` in `.oavc`, run on the host around the
+//! pipeline's steps.
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use crate::config::{Hook, HookWhen};
+use crate::docker::CancelToken;
+use crate::pipeline::Phase;
+
+/// Where hooks run: the project directory, and the spec they're told about.
+#[derive(Debug, Clone)]
+pub struct HookSite {
+    pub work_dir: PathBuf,
+    pub spec_path: PathBuf,
+}
+
+/// How a hook run ended.
+#[derive(Debug)]
+pub struct HookOutcome {
+    pub success: bool,
+    /// Combined stdout and stderr, framed by a line naming the hook.
+    pub log: String,
+}
+
+/// The `phase` a hook names to run around `phase`'s steps.
+pub fn phase_name(phase: &Phase) -> &str {
+    match phase {
+        Phase::Structure => "structure",
+        Phase::Lint => "lint",
+        Phase::Security => "security",
+        Phase::Generate { .. } => "generate",
+        Phase::Compile { .. } => "compile",
+        Phase::Test { .. } => "test",
+        Phase::CodeLint { .. } => "code-lint",
+        Phase::Docs => "docs",
+        Phase::Custom { name } => name,
+    }
+}
+
+/// Hooks of `hooks` that run `when` around `phase`, in config order.
+pub fn matching<'a>(
+    hooks: &'a [Hook],
+    when: HookWhen,
+    phase: &'a Phase,
+) -> impl Iterator<Item = &'a Hook> {
+    let generator = match phase {
+        Phase::Generate { generator, .. }
+        | Phase::Compile { generator, .. }
+        | Phase::Test { generator, .. }
+        | Phase::CodeLint { generator, .. } => Some(generator.as_str()),
+        _ => None,
+    };
+    hooks.iter().filter(move |hook| {
+        hook.when == when
+            && hook.phase == phase_name(phase)
+            && hook
+                .generator
+                .as_deref()
+                .is_none_or(|g| Some(g) == generator)
+    })
+}
+
+/// How long the output of a hook that exited may take to close: processes
+/// it left in the background can hold it open forever.
+const OUTPUT_GRACE: Duration = Duration::from_secs(2);
+
+/// The environment a hook runs in around `phase`'s steps: `OAV_PHASE`,
+/// `OAV_SPEC` and `OAV_GENERATED`, plus `OAV_GENERATOR` and `OAV_SCOPE`
+/// around a generator's steps, where `OAV_GENERATED` is that generator's
+/// output directory.
+fn environment(phase: &Phase, site: &HookSite) -> Vec<(&'static str, String)> {
+    let mut generated = site.work_dir.join(".oav/generated");
+    let mut env = vec![
+        ("OAV_PHASE", phase_name(phase).to_string()),
+        ("OAV_SPEC", site.spec_path.display().to_string()),
+    ];
+    if let Phase::Generate { generator, scope }
+    | Phase::Compile { generator, scope }
+    | Phase::Test { generator, scope }
+    | Phase::CodeLint { generator, scope } = phase
+    {
+        generated = generated.join(scope).join(generator);
+        env.push(("OAV_GENERATOR", generator.clone()));
+        env.push(("OAV_SCOPE", scope.clone()));
+    }
+    env.push(("OAV_GENERATED", generated.display().to_string()));
+    env
+}
+
+/// `hook` for `phase` as one shell command line, run from the project
+/// directory, for the dry run and the reproduction script.
+pub fn command_line(hook: &Hook, phase: &Phase, site: &HookSite) -> String {
+    let mut words: Vec<String> = environment(phase, site)
+        .into_iter()
+        .map(|(name, value)| format!("{name}={}", shell_words::quote(&value)))
+        .collect();
+    words.push(format!("sh -c {}", shell_words::quote(&hook.run)));
+    words.join(" ")
+}
+
+/// Run `hook` for `phase` with `sh -c`, killing it and everything it
+/// started on cancel or once `timeout` passes. See [`environment`] for
+/// what it's told about the step.
+pub fn run(
+    hook: &Hook,
+    phase: &Phase,
+    site: &HookSite,
+    timeout: Duration,
+    cancel: &CancelToken,
+) -> HookOutcome {
+    let when = match hook.when {
+        HookWhen::Before => "before",
+        HookWhen::After => "after",
+    };
+    let mut log = format!("--- hook {when} {phase}: {} ---\n", hook.run);
+
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(format!("exec 2>&1\n{}", hook.run))
+        .current_dir(&site.work_dir)
+        .envs(environment(phase, site))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped());
+    // In a process group of its own, so killing the hook reaches the
+    // commands it started too.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            log.push_str(&format!("Failed to start hook: {e}\n"));
+            return HookOutcome {
+                success: false,
+                log,
+            };
+        }
+    };
+    let (chunks_tx, chunks) = mpsc::channel();
+    if let Some(mut stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            while let Ok(n) = stdout.read(&mut buf)
+                && n > 0
+                && chunks_tx.send(buf[..n].to_vec()).is_ok()
+            {}
+        });
+    }
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) if cancel.is_cancelled() => break Err("cancelled"),
+            Ok(None) if started.elapsed() >= timeout => break Err("timed out"),
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(_) => break Err("lost track of the process"),
+        }
+    };
+    if status.is_err() {
+        kill_group(&mut child);
+        let _ = child.wait();
+    }
+
+    let mut output = Vec::new();
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    let mut closed = false;
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        match chunks.recv_timeout(left) {
+            Ok(chunk) => output.extend(chunk),
+            Err(RecvTimeoutError::Disconnected) => {
+                closed = true;
+                break;
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+        }
+    }
+    if !closed {
+        // Background processes still hold the output open: stop them.
+        kill_group(&mut child);
+        output.extend(chunks.try_iter().flatten());
+    }
+    let output = String::from_utf8_lossy(&output);
+    log.push_str(&output);
+    if !output.is_empty() && !output.ends_with('\n') {
+        log.push('\n');
+    }
+
+    let success = match status {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            let end = status.code().map_or("killed by a signal".to_string(), |c| {
+                format!("failed with exit code {c}")
+            });
+            log.push_str(&format!("--- hook {end} ---\n"));
+            false
+        }
+        Err(reason) => {
+            log.push_str(&format!("--- hook {reason} ---\n"));
+            false
+        }
+    };
+    HookOutcome { success, log }
+}
+
+/// Kill the hook's process group: the shell and whatever it started.
+fn kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = i32::try_from(child.id()) {
+        // SAFETY: kill(2) with a negative pid signals that process group.
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+        return;
+    }
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(phase: &str, when: HookWhen, generator: Option<&str>, run: &str) -> Hook {
+        Hook {
+            phase: phase.into(),
+            when,
+            generator: generator.map(str::to_string),
+            run: run.into(),
+        }
+    }
+
+    #[test]
+    fn matching_filters_by_phase_side_and_generator() {
+        let hooks = [
+            hook("generate", HookWhen::After, Some("spring"), "sync"),
+            hook("generate", HookWhen::Before, None, "clean"),
+            hook("generate", HookWhen::After, None, "notify"),
+            hook("compile", HookWhen::After, None, "other"),
+        ];
+        let phase = |generator: &str| Phase::Generate {
+            generator: generator.into(),
+            scope: "server".into(),
+        };
+        let runs = |when, phase| -> Vec<String> {
+            matching(&hooks, when, &phase)
+                .map(|h| h.run.clone())
+                .collect()
+        };
+
+        assert_eq!(runs(HookWhen::After, phase("spring")), ["sync", "notify"]);
+        assert_eq!(runs(HookWhen::After, phase("go-server")), ["notify"]);
+        assert_eq!(runs(HookWhen::Before, phase("go-server")), ["clean"]);
+        assert!(runs(HookWhen::After, Phase::Docs).is_empty());
+    }
+
+    #[test]
+    fn run_captures_output_and_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = HookSite {
+            work_dir: dir.path().to_path_buf(),
+            spec_path: dir.path().join("openapi.yaml"),
+        };
+        let phase = Phase::Generate {
+            generator: "spring".into(),
+            scope: "server".into(),
+        };
+        let cancel = CancelToken::new();
+        let timeout = Duration::from_secs(10);
+
+        let ok = hook(
+            "generate",
+            HookWhen::After,
+            None,
+            "echo \"$OAV_PHASE $OAV_GENERATOR\"; echo oops >&2",
+        );
+        let outcome = run(&ok, &phase, &site, timeout, &cancel);
+        assert!(outcome.success);
+        assert_eq!(
+            outcome.log,
+            "--- hook after spring generate: echo \"$OAV_PHASE $OAV_GENERATOR\"; echo oops >&2 ---\ngenerate spring\noops\n"
+        );
+
+        let failing = hook("generate", HookWhen::Before, None, "exit 3");
+        let outcome = run(&failing, &phase, &site, timeout, &cancel);
+        assert!(!outcome.success);
+        assert!(
+            outcome
+                .log
+                .ends_with("--- hook failed with exit code 3 ---\n")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_stops_what_a_hook_left_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = HookSite {
+            work_dir: dir.path().to_path_buf(),
+            spec_path: dir.path().join("openapi.yaml"),
+        };
+        let cancel = CancelToken::new();
+        let started = Instant::now();
+
+        let lingering = hook("lint", HookWhen::After, None, "sleep 30 & echo started");
+        let outcome = run(
+            &lingering,
+            &Phase::Lint,
+            &site,
+            Duration::from_secs(10),
+            &cancel,
+        );
+        assert!(outcome.success);
+        assert!(outcome.log.ends_with("started\n"), "{}", outcome.log);
+
+        let slow = hook("lint", HookWhen::After, None, "sleep 30 | cat");
+        let outcome = run(
+            &slow,
+            &Phase::Lint,
+            &site,
+            Duration::from_millis(200),
+            &cancel,
+        );
+        assert!(outcome.log.ends_with("--- hook timed out ---\n"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn command_line_carries_the_hook_environment() {
+        let site = HookSite {
+            work_dir: PathBuf::from("/work"),
+            spec_path: PathBuf::from("/work/api.yaml"),
+        };
+        let phase = Phase::Compile {
+            generator: "spring".into(),
+            scope: "server".into(),
+        };
+        let sync = hook("compile", HookWhen::After, None, "make sync");
+        assert_eq!(
+            command_line(&sync, &phase, &site),
+            "OAV_PHASE=compile OAV_SPEC=/work/api.yaml OAV_GENERATOR=spring OAV_SCOPE=server \
+             OAV_GENERATED=/work/.oav/generated/server/spring sh -c 'make sync'"
+        );
+    }
+}
@@ -13,6 +13,9 @@ pub enum KeyAction {
     PageDown,
     HalfPageUp,
     HalfPageDown,
+    ScrollLeft,
+    ScrollRight,
+    ToggleWrap,
     Select,
 
     // Panel
@@ -31,19 +34,63 @@ pub enum KeyAction {
     ExpandLayout,
     ShrinkLayout,
     ToggleView,
+    OpenGeneratorPicker,
+    ShowDiagnostics,
+    ExportShare,
+    CreateSupportBundle,
+    StartTour,
+    Copy,
+    ShowChangelog,
+    OpenDocs,
+    ShowSchema,
+    ShowScore,
+    ShowRuleStats,
+    ShowReferences,
+    ShowRuns,
+    CompareReports,
+    RunMatrix,
+    RunWorkspace,
+    SwitchProject,
+    DryRun,
+    MigrateSpec,
 
     // Validator
     FocusDetail,
     OpenEditor,
+    EditInline,
     ProposeFix,
+    FormatSpec,
     NextDetailTab,
     PrevDetailTab,
+    NextLineFinding,
+    PrevLineFinding,
+    Search,
+    FreeNavigate,
+    PinSpec,
+    GotoLine,
+    ToggleLogTimes,
+    ToggleLogErrorsOnly,
+    ToggleLogNoise,
+    OpenShell,
+    OpenLog,
+    SuppressFinding,
+    ToggleSuppressed,
+    ToggleChangedOnly,
+    SnapshotBaseline,
 
     // Browser
     NextGenerator,
     PrevGenerator,
     ToggleDiff,
     CloseDiff,
+    IgnoreGeneratedFile,
+    ToggleDirectory,
+    ShowOutline,
+    CleanGenerated,
+    NextHunk,
+    PrevHunk,
+    NextChangedFile,
+    PrevChangedFile,
 }
 
 impl KeyAction {
@@ -56,6 +103,9 @@ impl KeyAction {
         Self::PageDown,
         Self::HalfPageUp,
         Self::HalfPageDown,
+        Self::ScrollLeft,
+        Self::ScrollRight,
+        Self::ToggleWrap,
         Self::Select,
         Self::NextPanel,
         Self::PrevPanel,
@@ -70,15 +120,59 @@ impl KeyAction {
         Self::ExpandLayout,
         Self::ShrinkLayout,
         Self::ToggleView,
+        Self::OpenGeneratorPicker,
+        Self::ShowDiagnostics,
+        Self::ExportShare,
+        Self::CreateSupportBundle,
+        Self::StartTour,
+        Self::Copy,
+        Self::ShowChangelog,
+        Self::OpenDocs,
+        Self::ShowSchema,
+        Self::ShowScore,
+        Self::ShowRuleStats,
+        Self::ShowReferences,
+        Self::ShowRuns,
+        Self::CompareReports,
+        Self::RunMatrix,
+        Self::RunWorkspace,
+        Self::SwitchProject,
+        Self::DryRun,
+        Self::MigrateSpec,
         Self::FocusDetail,
         Self::OpenEditor,
+        Self::EditInline,
         Self::ProposeFix,
+        Self::FormatSpec,
         Self::NextDetailTab,
         Self::PrevDetailTab,
+        Self::NextLineFinding,
+        Self::PrevLineFinding,
+        Self::Search,
+        Self::FreeNavigate,
+        Self::PinSpec,
+        Self::GotoLine,
+        Self::ToggleLogTimes,
+        Self::ToggleLogErrorsOnly,
+        Self::ToggleLogNoise,
+        Self::OpenShell,
+        Self::OpenLog,
+        Self::SuppressFinding,
+        Self::ToggleSuppressed,
+        Self::ToggleChangedOnly,
+        Self::SnapshotBaseline,
         Self::NextGenerator,
         Self::PrevGenerator,
         Self::ToggleDiff,
         Self::CloseDiff,
+        Self::IgnoreGeneratedFile,
+        Self::ToggleDirectory,
+        Self::ShowOutline,
+        Self::CleanGenerated,
+        Self::NextHunk,
+        Self::PrevHunk,
+        Self::NextChangedFile,
+        Self::PrevChangedFile,
     ];
 
     /// The snake_case name used in `.oavc` config files.
@@ -92,6 +186,9 @@ impl KeyAction {
             Self::PageDown => "page_down",
             Self::HalfPageUp => "half_page_up",
             Self::HalfPageDown => "half_page_down",
+            Self::ScrollLeft => "scroll_left",
+            Self::ScrollRight => "scroll_right",
+            Self::ToggleWrap => "toggle_wrap",
             Self::Select => "select",
             Self::NextPanel => "next_panel",
             Self::PrevPanel => "prev_panel",
@@ -106,15 +203,59 @@ impl KeyAction {
             Self::ExpandLayout => "expand_layout",
             Self::ShrinkLayout => "shrink_layout",
             Self::ToggleView => "toggle_view",
+            Self::OpenGeneratorPicker => "open_generator_picker",
+            Self::ShowDiagnostics => "show_diagnostics",
+            Self::ExportShare => "export_share",
+            Self::CreateSupportBundle => "support_bundle",
+            Self::StartTour => "start_tour",
+            Self::Copy => "copy",
+            Self::ShowChangelog => "show_changelog",
+            Self::OpenDocs => "open_docs",
+            Self::ShowSchema => "show_schema",
+            Self::ShowScore => "show_score",
+            Self::ShowRuleStats => "show_rule_stats",
+            Self::ShowReferences => "show_references",
+            Self::ShowRuns => "show_runs",
+            Self::CompareReports => "compare_reports",
+            Self::RunMatrix => "run_matrix",
+            Self::RunWorkspace => "run_workspace",
+            Self::SwitchProject => "switch_project",
+            Self::DryRun => "dry_run",
+            Self::MigrateSpec => "migrate_spec",
             Self::FocusDetail => "focus_detail",
             Self::OpenEditor => "open_editor",
+            Self::EditInline => "edit_inline",
             Self::ProposeFix => "propose_fix",
+            Self::FormatSpec => "format_spec",
             Self::NextDetailTab => "next_detail_tab",
             Self::PrevDetailTab => "prev_detail_tab",
+            Self::NextLineFinding => "next_line_finding",
+            Self::PrevLineFinding => "prev_line_finding",
+            Self::Search => "search",
+            Self::FreeNavigate => "free_navigate",
+            Self::PinSpec => "pin_spec",
+            Self::GotoLine => "goto_line",
+            Self::ToggleLogTimes => "toggle_log_times",
+            Self::ToggleLogErrorsOnly => "toggle_log_errors_only",
+            Self::ToggleLogNoise => "toggle_log_noise",
+            Self::OpenShell => "open_shell",
+            Self::OpenLog => "open_log",
+            Self::SuppressFinding => "suppress_finding",
+            Self::ToggleSuppressed => "toggle_suppressed",
+            Self::ToggleChangedOnly => "toggle_changed_only",
+            Self::SnapshotBaseline => "snapshot_baseline",
             Self::NextGenerator => "next_generator",
             Self::PrevGenerator => "prev_generator",
             Self::ToggleDiff => "toggle_diff",
+            Self::IgnoreGeneratedFile => "ignore_generated_file",
+            Self::ToggleDirectory => "toggle_directory",
+            Self::ShowOutline => "show_outline",
+            Self::CleanGenerated => "clean_generated",
             Self::CloseDiff => "close_diff",
+            Self::NextHunk => "next_hunk",
+            Self::PrevHunk => "prev_hunk",
+            Self::NextChangedFile => "next_changed_file",
+            Self::PrevChangedFile => "prev_changed_file",
         }
     }
 
@@ -128,6 +269,9 @@ impl KeyAction {
             "page_down" => Self::PageDown,
             "half_page_up" => Self::HalfPageUp,
             "half_page_down" => Self::HalfPageDown,
+            "scroll_left" => Self::ScrollLeft,
+            "scroll_right" => Self::ScrollRight,
+            "toggle_wrap" => Self::ToggleWrap,
             "select" => Self::Select,
             "next_panel" => Self::NextPanel,
             "prev_panel" => Self::PrevPanel,
@@ -142,15 +286,59 @@ impl KeyAction {
             "expand_layout" => Self::ExpandLayout,
             "shrink_layout" => Self::ShrinkLayout,
             "toggle_view" => Self::ToggleView,
+            "open_generator_picker" => Self::OpenGeneratorPicker,
+            "show_diagnostics" => Self::ShowDiagnostics,
+            "export_share" => Self::ExportShare,
+            "support_bundle" => Self::CreateSupportBundle,
+            "start_tour" => Self::StartTour,
+            "copy" => Self::Copy,
+            "show_changelog" => Self::ShowChangelog,
+            "open_docs" => Self::OpenDocs,
+            "show_schema" => Self::ShowSchema,
+            "show_score" => Self::ShowScore,
+            "show_rule_stats" => Self::ShowRuleStats,
+            "show_references" => Self::ShowReferences,
+            "show_runs" => Self::ShowRuns,
+            "compare_reports" => Self::CompareReports,
+            "run_matrix" => Self::RunMatrix,
+            "run_workspace" => Self::RunWorkspace,
+            "switch_project" => Self::SwitchProject,
+            "dry_run" => Self::DryRun,
+            "migrate_spec" => Self::MigrateSpec,
             "focus_detail" => Self::FocusDetail,
             "open_editor" => Self::OpenEditor,
+            "edit_inline" => Self::EditInline,
             "propose_fix" => Self::ProposeFix,
+            "format_spec" => Self::FormatSpec,
             "next_detail_tab" => Self::NextDetailTab,
             "prev_detail_tab" => Self::PrevDetailTab,
+            "next_line_finding" => Self::NextLineFinding,
+            "prev_line_finding" => Self::PrevLineFinding,
+            "search" => Self::Search,
+            "free_navigate" => Self::FreeNavigate,
+            "pin_spec" => Self::PinSpec,
+            "goto_line" => Self::GotoLine,
+            "toggle_log_times" => Self::ToggleLogTimes,
+            "toggle_log_errors_only" => Self::ToggleLogErrorsOnly,
+            "toggle_log_noise" => Self::ToggleLogNoise,
+            "open_shell" => Self::OpenShell,
+            "open_log" => Self::OpenLog,
+            "suppress_finding" => Self::SuppressFinding,
+            "toggle_suppressed" => Self::ToggleSuppressed,
+            "toggle_changed_only" => Self::ToggleChangedOnly,
+            "snapshot_baseline" => Self::SnapshotBaseline,
             "next_generator" => Self::NextGenerator,
             "prev_generator" => Self::PrevGenerator,
             "toggle_diff" => Self::ToggleDiff,
+            "ignore_generated_file" => Self::IgnoreGeneratedFile,
+            "toggle_directory" => Self::ToggleDirectory,
+            "show_outline" => Self::ShowOutline,
+            "clean_generated" => Self::CleanGenerated,
             "close_diff" => Self::CloseDiff,
+            "next_hunk" => Self::NextHunk,
+            "prev_hunk" => Self::PrevHunk,
+            "next_changed_file" => Self::NextChangedFile,
+            "prev_changed_file" => Self::PrevChangedFile,
             _ => return None,
         })
     }
@@ -177,6 +365,6 @@ mod tests {
     #[test]
     fn all_array_is_exhaustive() {
         // Verify ALL contains the expected count. Update this if variants are added.
-        assert_eq!(KeyAction::ALL.len(), 31);
+        assert_eq!(KeyAction::ALL.len(), 78);
     }
 }
@@ -102,6 +102,9 @@ fn default_bindings() -> Vec<(KeyAction, Vec<KeyInput>)> {
         (PageDown, parse_keys(&["PageDown"])),
         (HalfPageUp, parse_keys(&["C-u"])),
         (HalfPageDown, parse_keys(&["C-d"])),
+        (ScrollLeft, parse_keys(&["h"])),
+        (ScrollRight, parse_keys(&["l"])),
+        (ToggleWrap, parse_keys(&["w"])),
         (Select, parse_keys(&["Enter"])),
         (NextPanel, parse_keys(&["Tab", "Right", "l"])),
         (PrevPanel, parse_keys(&["S-Tab", "Left", "h"])),
@@ -116,15 +119,59 @@ fn default_bindings() -> Vec<(KeyAction, Vec<KeyInput>)> {
         (ExpandLayout, parse_keys(&["+"])),
         (ShrinkLayout, parse_keys(&["_"])),
         (ToggleView, parse_keys(&["g"])),
+        (OpenGeneratorPicker, parse_keys(&["G"])),
+        (ShowDiagnostics, parse_keys(&["D"])),
+        (ExportShare, parse_keys(&["X"])),
+        (CreateSupportBundle, parse_keys(&["B"])),
+        (StartTour, parse_keys(&["T"])),
+        (Copy, parse_keys(&["y"])),
+        (ShowChangelog, parse_keys(&["C"])),
+        (OpenDocs, parse_keys(&["o"])),
+        (ShowSchema, parse_keys(&["S"])),
+        (ShowScore, parse_keys(&["%"])),
+        (ShowRuleStats, parse_keys(&["A"])),
+        (ShowReferences, parse_keys(&["R"])),
+        (ShowRuns, parse_keys(&["H"])),
+        (CompareReports, parse_keys(&["c"])),
+        (RunMatrix, parse_keys(&["M"])),
+        (RunWorkspace, parse_keys(&["V"])),
+        (SwitchProject, parse_keys(&["J"])),
+        (DryRun, parse_keys(&["P"])),
+        (MigrateSpec, parse_keys(&["U"])),
         (FocusDetail, parse_keys(&["d"])),
         (OpenEditor, parse_keys(&["e"])),
+        (EditInline, parse_keys(&["E"])),
         (ProposeFix, parse_keys(&["f"])),
+        (FormatSpec, parse_keys(&["F"])),
         (NextDetailTab, parse_keys(&["]"])),
         (PrevDetailTab, parse_keys(&["["])),
+        (NextLineFinding, parse_keys(&["n"])),
+        (PrevLineFinding, parse_keys(&["N"])),
+        (Search, parse_keys(&["/"])),
+        (FreeNavigate, parse_keys(&["v"])),
+        (PinSpec, parse_keys(&["p"])),
+        (GotoLine, parse_keys(&[":"])),
+        (ToggleLogTimes, parse_keys(&["t"])),
+        (ToggleLogErrorsOnly, parse_keys(&["x"])),
+        (ToggleLogNoise, parse_keys(&["z"])),
+        (OpenShell, parse_keys(&["s"])),
+        (OpenLog, parse_keys(&["L"])),
+        (SuppressFinding, parse_keys(&["i"])),
+        (ToggleSuppressed, parse_keys(&["I"])),
+        (ToggleChangedOnly, parse_keys(&["m"])),
+        (SnapshotBaseline, parse_keys(&["b"])),
         (NextGenerator, parse_keys(&["]"])),
         (PrevGenerator, parse_keys(&["["])),
         (ToggleDiff, parse_keys(&["d"])),
         (CloseDiff, parse_keys(&["d", "Esc"])),
+        (IgnoreGeneratedFile, parse_keys(&["i"])),
+        (ToggleDirectory, parse_keys(&["Space"])),
+        (ShowOutline, parse_keys(&["O"])),
+        (CleanGenerated, parse_keys(&["W"])),
+        (NextHunk, parse_keys(&["}"])),
+        (PrevHunk, parse_keys(&["{"])),
+        (NextChangedFile, parse_keys(&["."])),
+        (PrevChangedFile, parse_keys(&[","])),
     ]
 }
 
@@ -180,6 +227,20 @@ mod tests {
         assert!(actions.contains(&KeyAction::CloseDiff));
     }
 
+    #[test]
+    fn diff_keys_have_keys_of_their_own() {
+        let km = Keymap::default_keymap();
+        for action in [
+            KeyAction::NextHunk,
+            KeyAction::PrevHunk,
+            KeyAction::NextChangedFile,
+            KeyAction::PrevChangedFile,
+        ] {
+            let key = KeyInput::parse(km.label(action)).unwrap();
+            assert_eq!(km.actions_for(&key), [action], "{action:?}");
+        }
+    }
+
     #[test]
     fn config_override_replaces_default() {
         let mut user = HashMap::new();
@@ -0,0 +1,290 @@
+//! What crashed lazyoav sessions leave behind: containers still in the
+//! daemon, pinned output parked by a matrix run, half-written temp files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::docker;
+use crate::pipeline::matrix;
+
+const OAV_DIR: &str = ".oav";
+
+/// Leftovers of sessions that are no longer running.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Leftovers {
+    /// Containers labelled for this project by a process that is gone.
+    pub containers: Vec<String>,
+    /// `"{scope}/{generator}"` keys whose pinned output a matrix run
+    /// stashed and never put back.
+    pub matrix_stash: Vec<String>,
+    /// Temp files of interrupted atomic writes under `.oav/`.
+    pub temp_files: Vec<PathBuf>,
+}
+
+impl Leftovers {
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty() && self.matrix_stash.is_empty() && self.temp_files.is_empty()
+    }
+
+    /// One clause per kind of leftover, e.g. `2 containers, 1 temp file`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.containers.is_empty() {
+            parts.push(count(self.containers.len(), "container"));
+        }
+        if !self.matrix_stash.is_empty() {
+            parts.push(format!(
+                "stashed matrix output of {}",
+                count(self.matrix_stash.len(), "generator")
+            ));
+        }
+        if !self.temp_files.is_empty() {
+            parts.push(count(self.temp_files.len(), "temp file"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// What [`clean`] managed to do.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Cleanup {
+    pub removed_containers: usize,
+    /// Containers the daemon still lists after removal.
+    pub stuck_containers: Vec<String>,
+    pub restored: usize,
+    pub deleted_files: usize,
+    pub errors: Vec<String>,
+}
+
+impl Cleanup {
+    /// Whether anything could not be cleaned up.
+    pub fn is_partial(&self) -> bool {
+        !self.stuck_containers.is_empty() || !self.errors.is_empty()
+    }
+
+    /// A one-line account for the status bar.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.removed_containers > 0 {
+            parts.push(format!(
+                "removed {}",
+                count(self.removed_containers, "container")
+            ));
+        }
+        if self.restored > 0 {
+            parts.push(format!(
+                "restored pinned output of {}",
+                count(self.restored, "generator")
+            ));
+        }
+        if self.deleted_files > 0 {
+            parts.push(format!(
+                "deleted {}",
+                count(self.deleted_files, "temp file")
+            ));
+        }
+        if !self.stuck_containers.is_empty() {
+            parts.push(format!(
+                "could not remove {}",
+                self.stuck_containers.join(", ")
+            ));
+        }
+        parts.extend(self.errors.iter().cloned());
+        if parts.is_empty() {
+            return "Nothing to clean up".to_string();
+        }
+        let summary = parts.join(", ");
+        let mut chars = summary.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => summary,
+        }
+    }
+}
+
+/// Look for leftovers of crashed sessions in `work_dir`. Containers are only
+/// looked for with `docker`, since listing them needs the daemon, and not on
+/// a remote daemon, whose containers' pids belong to other machines.
+pub fn find(work_dir: &Path, docker: bool) -> Leftovers {
+    let containers = if docker && !docker::remote_daemon() {
+        docker::project_containers(work_dir)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|name| !docker::container_owner(name).is_some_and(is_running))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let matrix_stash = if matrix::stash_owner(work_dir).is_some_and(is_running) {
+        Vec::new()
+    } else {
+        matrix::stash_keys(work_dir)
+    };
+
+    let temp_files = WalkDir::new(work_dir.join(OAV_DIR))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            temp_file_owner(&e.file_name().to_string_lossy()).is_some_and(|pid| !is_running(pid))
+        })
+        .map(|e| e.into_path())
+        .collect();
+
+    Leftovers {
+        containers,
+        matrix_stash,
+        temp_files,
+    }
+}
+
+/// Remove `leftovers`: containers first, then put stashed pinned output
+/// back, then delete temp files.
+pub fn clean(work_dir: &Path, leftovers: &Leftovers) -> Cleanup {
+    let mut cleanup = Cleanup::default();
+    for name in &leftovers.containers {
+        if docker::remove_container(name) {
+            cleanup.removed_containers += 1;
+        } else {
+            cleanup.stuck_containers.push(name.clone());
+        }
+    }
+    if !leftovers.matrix_stash.is_empty() {
+        match matrix::recover_stash(work_dir) {
+            Ok(()) => cleanup.restored = leftovers.matrix_stash.len(),
+            Err(e) => cleanup.errors.push(format!("{e:#}")),
+        }
+    }
+    for path in &leftovers.temp_files {
+        match fs::remove_file(path) {
+            Ok(()) => cleanup.deleted_files += 1,
+            Err(e) => cleanup
+                .errors
+                .push(format!("Failed to delete {}: {e}", path.display())),
+        }
+    }
+    cleanup
+}
+
+/// The writer's pid in a temp file name, `.{name}.{pid}.tmp`.
+fn temp_file_owner(file_name: &str) -> Option<u32> {
+    let stem = file_name.strip_prefix('.')?.strip_suffix(".tmp")?;
+    stem.rsplit_once('.')?.1.parse().ok()
+}
+
+/// Whether process `pid` is running. This process counts as running, so its
+/// own work is never a leftover, and so does another user's process.
+fn is_running(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+    #[cfg(unix)]
+    {
+        let Ok(pid) = i32::try_from(pid) else {
+            return false;
+        };
+        if pid == 0 {
+            return false;
+        }
+        // SAFETY: kill(2) with signal 0 only checks that the process exists.
+        if unsafe { libc::kill(pid, 0) } == 0 {
+            return true;
+        }
+        // EPERM: it exists, but we may not signal it.
+        std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+    #[cfg(not(unix))]
+    {
+        // Without a cheap check, assume it is: better to keep a leftover
+        // than to pull output from under a live session.
+        true
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Above any Linux `pid_max`, so never running.
+    const DEAD_PID: u32 = 999_999_999;
+
+    #[cfg(unix)]
+    #[test]
+    fn processes_of_other_users_count_as_running() {
+        // pid 1 is init, owned by root; a non-root user may not signal it.
+        assert!(is_running(1));
+        assert!(!is_running(DEAD_PID));
+    }
+
+    #[test]
+    fn summary_capitalizes_its_first_letter() {
+        let cleanup = Cleanup {
+            errors: vec!["ébauche.yaml could not be read".to_string()],
+            ..Cleanup::default()
+        };
+        assert_eq!(cleanup.summary(), "Ébauche.yaml could not be read");
+    }
+
+    #[test]
+    fn temp_file_owner_reads_the_writer_pid() {
+        assert_eq!(temp_file_owner(".report.json.4242.tmp"), Some(4242));
+        assert_eq!(temp_file_owner("report.json"), None);
+        assert_eq!(temp_file_owner(".report.json.tmp"), None);
+    }
+
+    #[test]
+    fn find_skips_work_of_running_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let reports = dir.path().join(".oav/reports");
+        fs::create_dir_all(&reports).unwrap();
+        let stale = reports.join(format!(".report.json.{DEAD_PID}.tmp"));
+        fs::write(&stale, "{").unwrap();
+        let live = reports.join(format!(".report.json.{}.tmp", std::process::id()));
+        fs::write(&live, "{").unwrap();
+
+        let leftovers = find(dir.path(), false);
+        assert_eq!(leftovers.temp_files, std::slice::from_ref(&stale));
+        assert!(leftovers.matrix_stash.is_empty());
+        assert_eq!(leftovers.summary(), "1 temp file");
+
+        let cleanup = clean(dir.path(), &leftovers);
+        assert_eq!(cleanup.deleted_files, 1);
+        assert_eq!(cleanup.summary(), "Deleted 1 temp file");
+        assert!(!stale.exists());
+        assert!(live.exists());
+    }
+
+    #[test]
+    fn find_and_clean_recover_an_abandoned_matrix_stash() {
+        let dir = tempfile::tempdir().unwrap();
+        let stashed = dir.path().join(".oav/matrix/.pinned/server/spring");
+        fs::create_dir_all(&stashed).unwrap();
+        fs::write(stashed.join("pom.xml"), "pinned").unwrap();
+        let owner = dir.path().join(".oav/matrix/.pinned.pid");
+
+        fs::write(&owner, std::process::id().to_string()).unwrap();
+        assert!(find(dir.path(), false).is_empty());
+
+        fs::write(&owner, DEAD_PID.to_string()).unwrap();
+        let leftovers = find(dir.path(), false);
+        assert_eq!(leftovers.matrix_stash, ["server/spring"]);
+
+        let cleanup = clean(dir.path(), &leftovers);
+        assert!(!cleanup.is_partial());
+        assert_eq!(cleanup.summary(), "Restored pinned output of 1 generator");
+        let restored = dir.path().join(".oav/generated/server/spring/pom.xml");
+        assert_eq!(fs::read_to_string(restored).unwrap(), "pinned");
+        assert!(find(dir.path(), false).is_empty());
+    }
+}
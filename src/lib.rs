@@ -1,7 +1,38 @@
+pub mod assist;
+pub mod automation;
+pub mod baseline;
+pub mod changelog;
+pub mod clean;
 pub mod config;
 pub mod custom;
 pub mod docker;
+pub mod durations;
+pub mod format;
 pub mod generators;
+pub mod genignore;
+pub mod graph;
+pub mod hooks;
 pub mod keys;
+pub mod leftovers;
+pub mod logs;
+pub mod migrate;
+pub mod naming;
+pub mod openapi;
+pub mod operation_ids;
+pub mod orphans;
 pub mod pipeline;
+pub mod pitfalls;
+pub mod plugins;
+pub mod regions;
+pub mod reports;
+pub mod rulesets;
+pub mod runs;
 pub mod scaffold;
+pub mod score;
+pub mod share;
+pub mod stats;
+pub mod structure;
+pub mod support;
+pub mod suppress;
+pub mod trace;
+pub mod trust;
@@ -0,0 +1,73 @@
+//! The findings parsed from the last run's lint log, saved next to the
+//! report so a restart doesn't have to parse it again. Analysis findings
+//! depend on the spec and config as they are now, so they aren't cached.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use lazyoav::reports::write_atomic;
+
+use super::LintError;
+
+pub const CACHE_FILE: &str = ".oav/reports/errors.json";
+
+/// Bumped when what's cached changes; version 1 had analysis findings too.
+const VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct ErrorCache {
+    #[serde(default)]
+    version: u32,
+    /// CRC-32 of the lint log the findings were parsed from.
+    lint_log: u32,
+    errors: Vec<LintError>,
+}
+
+/// Save `errors`, parsed from `lint_log`, before severity overrides apply.
+pub fn save(root: &Path, lint_log: &str, errors: &[LintError]) -> Result<()> {
+    let cache = ErrorCache {
+        version: VERSION,
+        lint_log: crc32fast::hash(lint_log.as_bytes()),
+        errors: errors.to_vec(),
+    };
+    let json = serde_json::to_string(&cache).context("Failed to serialize lint findings")?;
+    write_atomic(&root.join(CACHE_FILE), json.as_bytes())
+}
+
+/// The cached findings, if they were parsed from this `lint_log`.
+pub fn load(root: &Path, lint_log: &str) -> Option<Vec<LintError>> {
+    let json = std::fs::read_to_string(root.join(CACHE_FILE)).ok()?;
+    let cache: ErrorCache = serde_json::from_str(&json).ok()?;
+    (cache.version == VERSION && cache.lint_log == crc32fast::hash(lint_log.as_bytes()))
+        .then_some(cache.errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_parser::Severity;
+
+    #[test]
+    fn cache_is_only_used_for_the_log_it_came_from() {
+        let dir = tempfile::tempdir().unwrap();
+        let errors = vec![LintError {
+            line: 4,
+            col: 5,
+            end: None,
+            severity: Severity::Warning,
+            rule: "operation-tags".into(),
+            message: "Operation must have non-empty \"tags\" array.".into(),
+            json_path: Some("/paths/~1pets/get".into()),
+        }];
+        save(dir.path(), "[]", &errors).unwrap();
+
+        assert_eq!(load(dir.path(), "[]"), Some(errors));
+        assert_eq!(load(dir.path(), "[{}]"), None);
+
+        let unversioned = format!(r#"{{"lint_log":{},"errors":[]}}"#, crc32fast::hash(b"[]"));
+        std::fs::write(dir.path().join(CACHE_FILE), unversioned).unwrap();
+        assert_eq!(load(dir.path(), "[]"), None);
+    }
+}
@@ -0,0 +1,291 @@
+use super::Severity;
+
+/// A finding of a language linter in a generated project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeFinding {
+    /// The file as the linter named it, relative to the project where the
+    /// linter printed a container path. SpotBugs names only the file, which
+    /// is put under its class's package directory.
+    pub file: String,
+    /// 1-based; `col` is 0 when the linter gives only a line.
+    pub line: usize,
+    pub col: usize,
+    pub severity: Severity,
+    pub rule: String,
+    pub message: String,
+}
+
+/// Source files the linters report on.
+const SOURCE_EXTENSIONS: &[&str] = &["java", "kt", "ts", "tsx", "js", "mjs", "cjs", "go"];
+
+/// Findings in a code lint step log, in log order.
+///
+/// Recognized by the shape each linter prints them in:
+/// ```text
+/// [WARNING] src/main/java/Pet.java:[23,5] (javadoc) JavadocMethod: Missing ...   checkstyle
+/// [WARN] /src/src/main/java/Pet.java:23:5: Missing a Javadoc comment. [JavadocMethod]
+/// [ERROR] Medium: Dead store to x in Api.get() [Api] At Api.java:[line 42] DLS_DEAD_LOCAL_STORE
+/// src/apis/PetApi.ts(12,5): error TS2304: Cannot find name 'Foo'.                  tsc
+/// /src/src/index.ts                                                                eslint
+///   12:5  error  'x' is assigned a value but never used  no-unused-vars
+/// api_pet.go:12:5: Error return value is not checked (errcheck)                    golangci-lint
+/// ```
+pub fn parse_code_lint(raw: &str) -> Vec<CodeFinding> {
+    let mut findings = Vec::new();
+    // The file ESLint's stylish output lists the findings below under.
+    let mut eslint_file: Option<String> = None;
+    for line in raw.lines().map(str::trim) {
+        if line.is_empty() {
+            eslint_file = None;
+            continue;
+        }
+        if !line.contains(' ') && is_source(line) {
+            eslint_file = Some(relative(line));
+            continue;
+        }
+        let finding = match &eslint_file {
+            Some(file) => eslint_finding(line, file),
+            None => None,
+        };
+        if let Some(finding) = finding
+            .or_else(|| maven_finding(line))
+            .or_else(|| tsc_finding(line))
+            .or_else(|| located_finding(line, Severity::Error))
+        {
+            findings.push(finding);
+        }
+    }
+    findings
+}
+
+/// `  12:5  error  message  rule`, under the file it belongs to.
+fn eslint_finding(line: &str, file: &str) -> Option<CodeFinding> {
+    let (position, rest) = line.split_once(char::is_whitespace)?;
+    let (l, c) = position.split_once(':')?;
+    let rest = rest.trim_start();
+    let (severity, rest) = rest.split_once(char::is_whitespace)?;
+    let severity = match severity {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    let (message, rule) = rest.trim().rsplit_once("  ")?;
+    Some(CodeFinding {
+        file: file.to_string(),
+        line: l.parse().ok()?,
+        col: c.parse().ok()?,
+        severity,
+        rule: rule.trim().to_string(),
+        message: message.trim().to_string(),
+    })
+}
+
+/// The package directory of the class SpotBugs names at the end of its
+/// message, `[org.example.Api]`, e.g. `org/example`; `None` in the default
+/// package.
+fn package_dir(message: &str) -> Option<String> {
+    let class = message.trim_end().strip_suffix(']')?.rsplit_once('[')?.1;
+    let (package, _) = class.rsplit_once('.')?;
+    Some(package.replace('.', "/"))
+}
+
+/// Checkstyle and SpotBugs lines as Maven logs them.
+fn maven_finding(line: &str) -> Option<CodeFinding> {
+    let (severity, rest) = if let Some(rest) = line.strip_prefix("[ERROR] ") {
+        (Severity::Error, rest)
+    } else if let Some(rest) = line
+        .strip_prefix("[WARNING] ")
+        .or_else(|| line.strip_prefix("[WARN] "))
+    {
+        (Severity::Warning, rest)
+    } else {
+        return None;
+    };
+
+    // SpotBugs: `<priority>: <message> At File.java:[line 42] RULE`.
+    if let Some((message, location)) = rest.rsplit_once(" At ")
+        && let Some((file, tail)) = location.split_once(":[line")
+        && is_source(file)
+    {
+        let tail = tail.strip_prefix('s').unwrap_or(tail);
+        let (lines, rule) = tail.split_once("] ")?;
+        let first = lines.trim().split('-').next()?;
+        let message = ["High: ", "Medium: ", "Low: "]
+            .iter()
+            .find_map(|p| message.strip_prefix(p))
+            .unwrap_or(message);
+        let file = match package_dir(message) {
+            Some(dir) => format!("{dir}/{file}"),
+            None => file.to_string(),
+        };
+        return Some(CodeFinding {
+            file,
+            line: first.parse().ok()?,
+            col: 0,
+            severity,
+            rule: rule.trim().to_string(),
+            message: message.trim().to_string(),
+        });
+    }
+
+    // Checkstyle via Maven: `File.java:[23,5] (category) Rule: message`.
+    if let Some((file, tail)) = rest.split_once(":[")
+        && is_source(file)
+    {
+        let (position, message) = tail.split_once("] ")?;
+        let (l, c) = position.split_once(',').unwrap_or((position, "0"));
+        let message = match message.strip_prefix('(') {
+            Some(rest) => rest.split_once(") ")?.1,
+            None => message,
+        };
+        let (rule, message) = match message.split_once(": ") {
+            Some((rule, text)) if !rule.contains(' ') => (rule, text),
+            _ => ("checkstyle", message),
+        };
+        return Some(CodeFinding {
+            file: relative(file),
+            line: l.parse().ok()?,
+            col: c.parse().ok()?,
+            severity,
+            rule: rule.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    // Checkstyle's own format: `File.java:23:5: message [Rule]`.
+    located_finding(rest, severity)
+}
+
+/// `src/api.ts(12,5): error TS2304: message`.
+fn tsc_finding(line: &str) -> Option<CodeFinding> {
+    let (file, rest) = line.split_once('(')?;
+    if !is_source(file) {
+        return None;
+    }
+    let (position, rest) = rest.split_once("): ")?;
+    let (l, c) = position.split_once(',')?;
+    let (severity, rest) = rest.split_once(' ')?;
+    let severity = match severity {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    let (rule, message) = rest.split_once(": ")?;
+    Some(CodeFinding {
+        file: relative(file),
+        line: l.parse().ok()?,
+        col: c.parse().ok()?,
+        severity,
+        rule: rule.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// `file:line[:col]: message`, with the rule in a trailing `(linter)` or
+/// `[Rule]` when there is one.
+fn located_finding(line: &str, severity: Severity) -> Option<CodeFinding> {
+    let (file, rest) = line.split_once(':')?;
+    if !is_source(file) {
+        return None;
+    }
+    let (l, rest) = rest.split_once(':')?;
+    let line_number: usize = l.parse().ok()?;
+    let (col, message) = match rest.split_once(": ") {
+        Some((c, message)) if c.parse::<usize>().is_ok() => (c.parse().ok()?, message),
+        _ => (0, rest.strip_prefix(' ')?),
+    };
+    let (message, rule) = [('(', ')'), ('[', ']')]
+        .iter()
+        .find_map(|(open, close)| {
+            let (message, rule) = message.strip_suffix(*close)?.rsplit_once(*open)?;
+            (!rule.contains(' ')).then_some((message.trim_end(), rule))
+        })
+        .unwrap_or((message, ""));
+    Some(CodeFinding {
+        file: relative(file),
+        line: line_number,
+        col,
+        severity,
+        rule: rule.to_string(),
+        message: message.to_string(),
+    })
+}
+
+fn is_source(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// `path` relative to the project: the compile services mount it at `/src`.
+fn relative(path: &str) -> String {
+    let path = path.strip_prefix("/src/").unwrap_or(path);
+    path.strip_prefix("./").unwrap_or(path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn located(findings: &[CodeFinding]) -> Vec<(&str, usize, usize, &str)> {
+        findings
+            .iter()
+            .map(|f| (f.file.as_str(), f.line, f.col, f.rule.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn checkstyle_and_spotbugs_through_maven() {
+        let log = "\
+[INFO] Downloading from central: https://repo.maven.apache.org/maven2/x.pom
+[WARNING] src/main/java/org/example/Pet.java:[23,5] (javadoc) JavadocMethod: Missing a Javadoc comment.
+[WARN] /src/src/main/java/org/example/Api.java:7:1: Line is longer than 80 characters. [LineLength]
+[ERROR] Medium: Dead store to x in org.example.Api.get() [org.example.Api] At Api.java:[line 42] DLS_DEAD_LOCAL_STORE
+[ERROR] Failed to execute goal com.github.spotbugs:spotbugs-maven-plugin:4.9.3.0:check
+";
+        let findings = parse_code_lint(log);
+        assert_eq!(
+            located(&findings),
+            [
+                ("src/main/java/org/example/Pet.java", 23, 5, "JavadocMethod"),
+                ("src/main/java/org/example/Api.java", 7, 1, "LineLength"),
+                ("org/example/Api.java", 42, 0, "DLS_DEAD_LOCAL_STORE"),
+            ]
+        );
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[0].message, "Missing a Javadoc comment.");
+        assert_eq!(
+            findings[2].message,
+            "Dead store to x in org.example.Api.get() [org.example.Api]"
+        );
+    }
+
+    #[test]
+    fn tsc_eslint_and_golangci_lint() {
+        let log = "\
+src/apis/PetApi.ts(12,5): error TS2304: Cannot find name 'Foo'.
+
+/src/src/index.ts
+  3:7   warning  'x' is assigned a value but never used  no-unused-vars
+  10:1  error    Unexpected var, use let or const instead  no-var
+
+\u{2716} 2 problems (1 error, 1 warning)
+api_pet.go:12:5: Error return value of `w.Write` is not checked (errcheck)
+level=info msg=\"[runner] linters took 1.2s\"
+";
+        let findings = parse_code_lint(log);
+        assert_eq!(
+            located(&findings),
+            [
+                ("src/apis/PetApi.ts", 12, 5, "TS2304"),
+                ("src/index.ts", 3, 7, "no-unused-vars"),
+                ("src/index.ts", 10, 1, "no-var"),
+                ("api_pet.go", 12, 5, "errcheck"),
+            ]
+        );
+        assert_eq!(findings[1].severity, Severity::Warning);
+        assert_eq!(
+            findings[3].message,
+            "Error return value of `w.Write` is not checked"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::step::is_error_line;
+
+/// Quick filters for the Raw Log tab.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Keep only lines that look like errors or failures.
+    pub errors_only: bool,
+    /// Drop download and progress output from gradle, maven, npm and docker.
+    pub hide_noise: bool,
+}
+
+impl LogFilter {
+    pub fn is_active(self) -> bool {
+        self.errors_only || self.hide_noise
+    }
+
+    pub fn keep(self, line: &str) -> bool {
+        (!self.errors_only || is_error_like(line)) && (!self.hide_noise || !is_noise(line))
+    }
+}
+
+/// Error lines as counted for the step, plus the looser failure lines build
+/// tools print around them (`FAILURE: Build failed`, `npm ERR!`, stack
+/// traces).
+fn is_error_like(line: &str) -> bool {
+    let trimmed = line.trim();
+    if is_error_line(trimmed) {
+        return true;
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    [
+        "error",
+        "failed",
+        "failure",
+        "fatal",
+        "exception",
+        "npm err!",
+    ]
+    .iter()
+    .any(|word| lower.contains(word))
+        || trimmed.starts_with("at ") && trimmed.ends_with(')')
+}
+
+/// Download and progress lines:
+/// ```text
+/// Download https://repo.maven.apache.org/.../kotlin-stdlib-1.9.0.pom    gradle
+/// Downloading from central: https://repo.maven.apache.org/...           maven
+/// Progress (1): 12/56 kB                                                maven
+/// <==========---> 80% EXECUTING [12s]                                   gradle
+/// npm http fetch GET 200 https://registry.npmjs.org/typescript 85ms     npm
+/// 4f4fb700ef54: Pull complete                                           docker
+/// ```
+fn is_noise(line: &str) -> bool {
+    let trimmed = line.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    const PREFIXES: &[&str] = &[
+        "download ",
+        "downloading ",
+        "downloaded ",
+        "progress (",
+        "npm http fetch",
+        "npm timing",
+        "npm warn deprecated",
+    ];
+    const DOCKER_PULL: &[&str] = &[
+        ": pulling fs layer",
+        ": waiting",
+        ": verifying checksum",
+        ": download complete",
+        ": downloading",
+        ": extracting",
+        ": pull complete",
+        ": already exists",
+    ];
+    PREFIXES.iter().any(|p| lower.starts_with(p))
+        || DOCKER_PULL.iter().any(|p| lower.contains(p))
+        || trimmed.starts_with('<') && trimmed.contains("% ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_only_keeps_failures() {
+        let filter = LogFilter {
+            errors_only: true,
+            ..LogFilter::default()
+        };
+        assert!(filter.keep("src/Api.java:12: error: cannot find symbol"));
+        assert!(filter.keep("FAILURE: Build failed with an exception."));
+        assert!(filter.keep("npm ERR! code ELIFECYCLE"));
+        assert!(filter.keep("    at com.example.Api.main(Api.java:12)"));
+        assert!(!filter.keep("> Task :compileJava"));
+        assert!(!filter.keep("BUILD SUCCESSFUL in 12s"));
+    }
+
+    #[test]
+    fn hide_noise_drops_download_and_progress_lines() {
+        let filter = LogFilter {
+            hide_noise: true,
+            ..LogFilter::default()
+        };
+        for noise in [
+            "Download https://repo.maven.apache.org/maven2/a/b/1.0/b-1.0.pom",
+            "Downloading from central: https://repo.maven.apache.org/maven2/x.jar",
+            "Progress (1): 12/56 kB",
+            "<==========---> 80% EXECUTING [12s]",
+            "npm http fetch GET 200 https://registry.npmjs.org/typescript 85ms",
+            "4f4fb700ef54: Pull complete",
+        ] {
+            assert!(!filter.keep(noise), "{noise}");
+        }
+        assert!(filter.keep("src/api.ts(3,5): error TS2304: Cannot find name 'X'."));
+        assert!(!LogFilter::default().is_active());
+    }
+}
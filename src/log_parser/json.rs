@@ -0,0 +1,298 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{LintError, Severity};
+
+/// Parse `--format json` lint output, from Spectral or Redocly, into
+/// structured errors.
+///
+/// The container log may have other output around the JSON document (pull
+/// progress, warnings on stderr), so this looks for the first line that
+/// starts a JSON array (Spectral) or report object (Redocly). Returns `None`
+/// when there is no such document, e.g. for stylish logs from older runs.
+pub fn parse_lint_json(raw: &str) -> Option<Vec<LintError>> {
+    let mut offset = 0;
+    for line in raw.split_inclusive('\n') {
+        let start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+        if !raw[start..].starts_with(['[', '{']) {
+            continue;
+        }
+        let Some(Ok(value)) = serde_json::Deserializer::from_str(&raw[start..])
+            .into_iter::<Value>()
+            .next()
+        else {
+            continue;
+        };
+        let errors = match value {
+            Value::Array(_) => spectral(value),
+            Value::Object(ref map) if map.contains_key("problems") => redocly(value),
+            _ => None,
+        };
+        if errors.is_some() {
+            return errors;
+        }
+    }
+    None
+}
+
+// ── Spectral ──────────────────────────────────────────────────────────
+
+/// One entry of Spectral's JSON output. Positions are 0-based.
+#[derive(Deserialize)]
+struct SpectralFinding {
+    code: Value,
+    message: String,
+    severity: u8,
+    #[serde(default)]
+    path: Vec<Value>,
+    range: Option<SpectralRange>,
+}
+
+#[derive(Deserialize)]
+struct SpectralRange {
+    start: SpectralPosition,
+    end: SpectralPosition,
+}
+
+#[derive(Deserialize)]
+struct SpectralPosition {
+    line: usize,
+    character: usize,
+}
+
+impl SpectralPosition {
+    fn one_based(&self) -> (usize, usize) {
+        (self.line + 1, self.character + 1)
+    }
+}
+
+fn spectral(value: Value) -> Option<Vec<LintError>> {
+    let findings: Vec<SpectralFinding> = serde_json::from_value(value).ok()?;
+    let errors = findings
+        .into_iter()
+        .map(|f| {
+            let (line, col) = f.range.as_ref().map_or((0, 0), |r| r.start.one_based());
+            LintError {
+                line,
+                col,
+                end: f.range.map(|r| r.end.one_based()),
+                severity: match f.severity {
+                    0 => Severity::Error,
+                    1 => Severity::Warning,
+                    2 => Severity::Info,
+                    _ => Severity::Hint,
+                },
+                rule: match f.code {
+                    Value::String(code) => code,
+                    other => other.to_string(),
+                },
+                message: f.message,
+                json_path: dotted_path(&f.path),
+            }
+        })
+        .collect();
+    Some(errors)
+}
+
+/// Join a Spectral path the way its stylish output prints it, e.g.
+/// `paths./pets.get` or `tags[0]`, so suppressions and baselines written
+/// from either format match.
+fn dotted_path(path: &[Value]) -> Option<String> {
+    let mut out = String::new();
+    for segment in path {
+        match segment {
+            Value::Number(n) => out.push_str(&format!("[{n}]")),
+            Value::String(s) => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(s);
+            }
+            _ => {}
+        }
+    }
+    (!out.is_empty()).then_some(out)
+}
+
+// ── Redocly ───────────────────────────────────────────────────────────
+
+/// Redocly's JSON report. Problems carry a JSON pointer but no line; the
+/// spec index resolves it.
+#[derive(Deserialize)]
+struct RedoclyReport {
+    problems: Vec<RedoclyProblem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RedoclyProblem {
+    rule_id: String,
+    severity: String,
+    message: String,
+    #[serde(default)]
+    location: Vec<RedoclyLocation>,
+}
+
+#[derive(Deserialize)]
+struct RedoclyLocation {
+    pointer: Option<String>,
+}
+
+fn redocly(value: Value) -> Option<Vec<LintError>> {
+    let report: RedoclyReport = serde_json::from_value(value).ok()?;
+    let errors = report
+        .problems
+        .into_iter()
+        .map(|p| LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: Severity::from_str_lossy(&p.severity),
+            rule: p.rule_id,
+            message: p.message,
+            json_path: p
+                .location
+                .first()
+                .and_then(|l| l.pointer.as_deref())
+                .map(|pointer| percent_decode(pointer.trim_start_matches('#')))
+                .filter(|pointer| !pointer.is_empty() && pointer != "/"),
+        })
+        .collect();
+    Some(errors)
+}
+
+/// Redocly URI-encodes pointers, e.g. `~1pets~1%7Bid%7D` for `/pets/{id}`.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spectral_json_with_ranges_and_paths() {
+        let input = r#"
+[
+	{
+		"code": "info-contact",
+		"path": ["info"],
+		"message": "Info object must have \"contact\" object.",
+		"severity": 1,
+		"range": {"start": {"line": 1, "character": 5}, "end": {"line": 4, "character": 20}},
+		"source": "/work/petstore.yaml"
+	},
+	{
+		"code": "operation-tags",
+		"path": ["paths", "/pets", "get", "tags", 0],
+		"message": "Operation must have non-empty \"tags\" array, which is long enough to wrap in the stylish output.",
+		"severity": 0,
+		"range": {"start": {"line": 9, "character": 8}, "end": {"line": 9, "character": 11}}
+	}
+]
+"#;
+        let errors = parse_lint_json(input).unwrap();
+        assert_eq!(errors.len(), 2);
+
+        assert_eq!(errors[0].rule, "info-contact");
+        assert_eq!(errors[0].severity, Severity::Warning);
+        assert_eq!((errors[0].line, errors[0].col), (2, 6));
+        assert_eq!(errors[0].end, Some((5, 21)));
+        assert_eq!(
+            errors[0].message,
+            "Info object must have \"contact\" object."
+        );
+        assert_eq!(errors[0].json_path.as_deref(), Some("info"));
+
+        assert_eq!(errors[1].severity, Severity::Error);
+        assert!(errors[1].message.ends_with("stylish output."));
+        assert_eq!(
+            errors[1].json_path.as_deref(),
+            Some("paths./pets.get.tags[0]")
+        );
+    }
+
+    #[test]
+    fn spectral_json_after_other_output() {
+        let input = "\
+Unable to find image 'stoplight/spectral:6' locally
+6: Pulling from stoplight/spectral
+[{\"code\":\"oas3-schema\",\"path\":[],\"message\":\"Invalid\",\"severity\":0}]
+";
+        let errors = parse_lint_json(input).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].end), (0, None));
+        assert_eq!(errors[0].json_path, None);
+        assert!(parse_lint_json("[]\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn redocly_json_uses_pointers() {
+        let input = r##"validating /work/api.yaml...
+{
+  "totals": {"errors": 1, "warnings": 1, "ignored": 0},
+  "version": "1.25.5",
+  "problems": [
+    {
+      "ruleId": "operation-summary",
+      "severity": "error",
+      "message": "Operation object should contain `summary` field.",
+      "location": [{"source": {"ref": "/work/api.yaml"}, "pointer": "#/paths/~1pets~1%7Bid%7D/get", "reportOnKey": true}],
+      "suggest": []
+    },
+    {
+      "ruleId": "no-empty-servers",
+      "severity": "warn",
+      "message": "Servers list should not be empty.",
+      "location": [{"source": {"ref": "/work/api.yaml"}, "pointer": "#/"}]
+    }
+  ]
+}
+/work/api.yaml: validated in 12ms
+"##;
+        let errors = parse_lint_json(input).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].rule, "operation-summary");
+        assert_eq!(errors[0].severity, Severity::Error);
+        assert_eq!(errors[0].line, 0);
+        assert_eq!(
+            errors[0].json_path.as_deref(),
+            Some("/paths/~1pets~1{id}/get")
+        );
+        assert_eq!(errors[1].severity, Severity::Warning);
+        // The document root isn't a useful location.
+        assert_eq!(errors[1].json_path, None);
+    }
+
+    #[test]
+    fn stylish_logs_are_not_json() {
+        let input = "\
+/work/spec.yaml
+  2:6  warning  info-contact  Info object should contain `contact` object.
+
+✖ 1 problem (0 errors, 1 warning, 0 infos, 0 hints)
+";
+        assert!(parse_lint_json(input).is_none());
+        assert!(parse_lint_json("{\"unrelated\": true}").is_none());
+    }
+}
@@ -1,13 +1,48 @@
-/// Lint log parsing — Spectral and Redocly stylish-format output to structured errors.
+/// Log parsing — Spectral and Redocly output to structured errors, error
+/// counts for generate and compile step logs, failed tests in test step
+/// logs, findings in code lint step logs, and Raw Log filters.
+pub mod cache;
+mod code_lint;
+mod filter;
+mod json;
 mod parse;
+mod step;
+mod test_run;
 
+pub use code_lint::{CodeFinding, parse_code_lint};
+pub use filter::LogFilter;
+pub use json::parse_lint_json;
 pub use parse::parse_lint_log;
+pub use step::count_step_errors;
+pub use test_run::failing_tests;
+
+/// Parse a lint log: the JSON report the linters are run with, or the
+/// stylish format of logs cached by older versions.
+pub fn parse_lint_output(raw: &str) -> Vec<LintError> {
+    if let Some(errors) = parse_lint_json(raw) {
+        trace::log(
+            Target::Parse,
+            format_args!("{} findings from JSON output", errors.len()),
+        );
+        return errors;
+    }
+    let errors = parse_lint_log(raw);
+    trace::log(
+        Target::Parse,
+        format_args!("{} findings from stylish output", errors.len()),
+    );
+    errors
+}
 
 use std::cmp::Ordering;
 use std::fmt;
 
+use lazyoav::trace::{self, Target};
+use serde::{Deserialize, Serialize};
+
 /// Severity level of a lint finding.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
@@ -25,7 +60,18 @@ impl Severity {
         }
     }
 
-    fn from_str_lossy(s: &str) -> Self {
+    /// Parse a configured severity, accepting `warn` as well as `warning`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "error" => Some(Self::Error),
+            "warn" | "warning" => Some(Self::Warning),
+            "info" => Some(Self::Info),
+            "hint" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_str_lossy(s: &str) -> Self {
         match s.to_ascii_lowercase().as_str() {
             "error" => Self::Error,
             "warning" => Self::Warning,
@@ -60,10 +106,14 @@ impl fmt::Display for Severity {
 }
 
 /// A single lint finding parsed from linter output.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LintError {
+    /// 1-based start of the finding, or 0 when the linter reports only a
+    /// `json_path`.
     pub line: usize,
     pub col: usize,
+    /// 1-based end line and column of the flagged range, when reported.
+    pub end: Option<(usize, usize)>,
     pub severity: Severity,
     pub rule: String,
     pub message: String,
@@ -62,6 +62,7 @@ fn parse_entry(trimmed: &str) -> Option<LintError> {
         return Some(LintError {
             line,
             col,
+            end: None,
             severity,
             rule,
             message: String::new(),
@@ -74,6 +75,7 @@ fn parse_entry(trimmed: &str) -> Option<LintError> {
     Some(LintError {
         line,
         col,
+        end: None,
         severity,
         rule,
         message,
@@ -0,0 +1,133 @@
+/// Count the error lines in a generate or compile step log.
+///
+/// There's no structured output to parse here, so this recognizes the error
+/// line shapes of the common toolchains instead:
+/// ```text
+/// error[E0308]: mismatched types                          rustc, gcc
+/// src/api.ts(3,5): error TS2304: Cannot find name 'X'.    tsc, javac, C#
+/// src/api.ts:3:5 - error TS2304: Cannot find name 'X'.    tsc --pretty
+/// [ERROR] /src/Api.java:[12,5] cannot find symbol         maven
+/// e: file:///src/Api.kt:12:5 Unresolved reference: x      kotlinc
+/// ./api.go:12:5: undefined: x                             go build
+/// [main] ERROR o.o.codegen.DefaultGenerator - ...         openapi-generator
+/// ```
+/// Summary lines such as `error: aborting due to 2 previous errors` aren't
+/// counted.
+pub fn count_step_errors(raw: &str) -> usize {
+    raw.lines()
+        .filter(|line| is_error_line(line.trim()))
+        .count()
+}
+
+pub(super) fn is_error_line(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    if lower.contains("previous error") || lower.starts_with("error: could not compile") {
+        return false;
+    }
+
+    if lower.starts_with("error:") || lower.starts_with("error[") {
+        return true;
+    }
+    if let Some(rest) = line.strip_prefix("[ERROR] ") {
+        return rest.contains(":[");
+    }
+    if line.starts_with("e: ") || line.starts_with("ERROR in ") || line.contains("] ERROR ") {
+        return true;
+    }
+    located_error(&lower) || go_error(line)
+}
+
+/// `<location>: error ...` or `<location> - error ...`, where the location
+/// ends in a line number or a `(line,col)` pair.
+fn located_error(lower: &str) -> bool {
+    [": error", ": fatal error", " - error"]
+        .iter()
+        .any(|marker| {
+            lower.match_indices(marker).any(|(at, _)| {
+                let location = lower[..at].trim_end_matches(')');
+                let after = lower[at + marker.len()..].chars().next();
+                location.ends_with(|c: char| c.is_ascii_digit())
+                    && matches!(after, Some(':' | ' ' | '['))
+            })
+        })
+}
+
+/// `file.go:line:col: message`, as printed by `go build` and `go vet`.
+fn go_error(line: &str) -> bool {
+    let Some((_, rest)) = line.split_once(".go:") else {
+        return false;
+    };
+    let mut parts = rest.splitn(3, ':');
+    let is_number =
+        |s: Option<&str>| s.is_some_and(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()));
+    is_number(parts.next()) && is_number(parts.next()) && parts.next().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustc_errors_without_summaries() {
+        let log = "\
+   Compiling api v0.1.0 (/work)
+error[E0308]: mismatched types
+  --> src/models.rs:10:5
+error: cannot find value `x` in this scope
+warning: unused import
+error: aborting due to 2 previous errors
+error: could not compile `api` (lib) due to 2 previous errors
+";
+        assert_eq!(count_step_errors(log), 2);
+    }
+
+    #[test]
+    fn located_errors() {
+        let log = "\
+src/api.ts(3,5): error TS2304: Cannot find name 'X'.
+src/api.ts:4:1 - error TS1005: ';' expected.
+src/Api.java:12: error: cannot find symbol
+api.c:3:10: fatal error: foo.h: No such file or directory
+Models.cs(7,20): error CS0103: The name 'y' does not exist
+src/api.ts: errors are fine in prose
+";
+        assert_eq!(count_step_errors(log), 5);
+    }
+
+    #[test]
+    fn maven_counts_only_located_errors() {
+        let log = "\
+[INFO] BUILD FAILURE
+[ERROR] COMPILATION ERROR :
+[ERROR] /work/src/main/java/Api.java:[12,5] cannot find symbol
+[ERROR] /work/src/main/java/Api.java:[20,1] class, interface, or enum expected
+[ERROR] Failed to execute goal org.apache.maven.plugins:maven-compiler-plugin
+[ERROR] -> [Help 1]
+";
+        assert_eq!(count_step_errors(log), 2);
+    }
+
+    #[test]
+    fn kotlin_go_and_generator_errors() {
+        let log = "\
+e: file:///work/src/Api.kt:12:5 Unresolved reference: x
+./api.go:12:5: undefined: x
+# example.com/api
+[main] ERROR o.o.codegen.DefaultGenerator - Could not process model 'Pet'
+[main] INFO  o.o.codegen.DefaultGenerator - Processing operation getPet
+ERROR in ./src/index.ts 3:1
+";
+        assert_eq!(count_step_errors(log), 4);
+    }
+
+    #[test]
+    fn clean_log_has_no_errors() {
+        let log = "\
+[main] INFO  o.o.codegen.DefaultGenerator - writing file /out/api.go
+[INFO] BUILD SUCCESS
+Finished dev [unoptimized] target(s) in 2.3s
+";
+        assert_eq!(count_step_errors(log), 0);
+        assert_eq!(count_step_errors(""), 0);
+    }
+}
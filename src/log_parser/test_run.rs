@@ -0,0 +1,102 @@
+/// Names of the failed tests in a test step log, in order, once each.
+///
+/// Like step errors, these are recognized by the shape each test runner
+/// prints them in:
+/// ```text
+/// [ERROR] com.example.ApiTest.get -- Time elapsed: 0.1 s <<< FAILURE!   maven
+/// ApiTest > get() FAILED                                                gradle
+/// --- FAIL: TestGet (0.00s)                                             go test
+/// FAILED tests/test_api.py::test_get - AssertionError                   pytest
+///   Failed Example.ApiTest.Get [12 ms]                                  dotnet
+///   ● Api › get                                                          jest
+/// ```
+pub fn failing_tests(raw: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in raw.lines().filter_map(|line| failed_test(line.trim())) {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+fn failed_test(line: &str) -> Option<&str> {
+    if line.ends_with("<<< FAILURE!") || line.ends_with("<<< ERROR!") {
+        let rest = line.strip_prefix("[ERROR] ").unwrap_or(line);
+        if rest.starts_with("Tests run:") {
+            return None;
+        }
+        let (name, _) = rest.split_once("Time elapsed")?;
+        return non_empty(name.trim_end().trim_end_matches("--").trim_end());
+    }
+    if let Some(name) = line.strip_suffix(" FAILED")
+        && name.contains(" > ")
+    {
+        return non_empty(name);
+    }
+    if let Some(rest) = line.strip_prefix("--- FAIL: ") {
+        return non_empty(rest.split_once(" (").map_or(rest, |(name, _)| name));
+    }
+    if let Some(rest) = line.strip_prefix("FAILED ") {
+        return non_empty(rest.split_once(" - ").map_or(rest, |(name, _)| name));
+    }
+    if let Some(rest) = line.strip_prefix("Failed ")
+        && line.ends_with(']')
+    {
+        return non_empty(rest.rsplit_once(" [")?.0);
+    }
+    line.strip_prefix("\u{25cf} ").and_then(non_empty)
+}
+
+fn non_empty(name: &str) -> Option<&str> {
+    let name = name.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maven_and_gradle_failures() {
+        let log = "\
+[INFO] Running com.example.ApiTest
+[ERROR] Tests run: 2, Failures: 1, Errors: 1, Skipped: 0, Time elapsed: 0.2 s <<< FAILURE! -- in com.example.ApiTest
+[ERROR] com.example.ApiTest.get -- Time elapsed: 0.1 s <<< FAILURE!
+[ERROR] list(com.example.ApiTest)  Time elapsed: 0.01 s  <<< ERROR!
+[ERROR] com.example.ApiTest.get -- Time elapsed: 0.1 s <<< FAILURE!
+ApiTest > create() FAILED
+BUILD FAILED in 3s
+";
+        assert_eq!(
+            failing_tests(log),
+            [
+                "com.example.ApiTest.get",
+                "list(com.example.ApiTest)",
+                "ApiTest > create()"
+            ]
+        );
+    }
+
+    #[test]
+    fn go_pytest_dotnet_and_jest_failures() {
+        let log = "\
+--- FAIL: TestGet (0.00s)
+FAIL\texample.com/api\t0.01s
+FAILED tests/test_api.py::test_get - AssertionError: 404
+  Failed Example.ApiTest.Get [12 ms]
+Failed!  - Failed:     1, Passed:     3, Skipped:     0, Total:     4
+  \u{25cf} Api \u{203a} get
+";
+        assert_eq!(
+            failing_tests(log),
+            [
+                "TestGet",
+                "tests/test_api.py::test_get",
+                "Example.ApiTest.Get",
+                "Api \u{203a} get"
+            ]
+        );
+        assert!(failing_tests("ok  \texample.com/api\t0.01s\n").is_empty());
+    }
+}
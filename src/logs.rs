@@ -0,0 +1,115 @@
+//! Full step logs of each run, every attempt included, kept under
+//! `.oav/logs/<run>/` for reading outside the TUI.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+use crate::pipeline::Phase;
+
+pub const LOGS_DIR: &str = ".oav/logs";
+
+/// Create the log directory for a new run, named after the Unix time it
+/// started, and delete the oldest beyond `keep` runs. Returns `None` when
+/// `keep` is 0: persisted logs are off.
+pub fn start_run(root: &Path, keep: usize) -> Result<Option<PathBuf>> {
+    if keep == 0 {
+        return Ok(None);
+    }
+    let mut timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // Runs starting within a second of each other get consecutive names.
+    while root.join(LOGS_DIR).join(timestamp.to_string()).exists() {
+        timestamp += 1;
+    }
+    let dir = root.join(LOGS_DIR).join(timestamp.to_string());
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    prune(root, keep)?;
+    Ok(Some(dir))
+}
+
+/// File name of `phase`'s log: `lint.log`, `generate-server-go.log`.
+pub fn file_name(phase: &Phase) -> String {
+    match phase {
+        Phase::Structure => "structure.log".to_string(),
+        Phase::Lint => "lint.log".to_string(),
+        Phase::Security => "security.log".to_string(),
+        Phase::Generate { generator, scope } => format!("generate-{scope}-{generator}.log"),
+        Phase::Compile { generator, scope } => format!("compile-{scope}-{generator}.log"),
+        Phase::Test { generator, scope } => format!("test-{scope}-{generator}.log"),
+        Phase::CodeLint { generator, scope } => format!("code-lint-{scope}-{generator}.log"),
+        Phase::Docs => "docs.log".to_string(),
+        Phase::Custom { name } => format!("custom-{name}.log"),
+    }
+}
+
+/// Write `phase`'s log into the run directory `dir`.
+pub fn write(dir: &Path, phase: &Phase, log: &str) -> Result<PathBuf> {
+    let path = dir.join(file_name(phase));
+    fs::write(&path, log).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// The newest run's log directory, if any run kept its logs.
+pub fn latest_run(root: &Path) -> Option<PathBuf> {
+    runs(root).ok()?.into_iter().next()
+}
+
+/// Delete all but the newest `keep` run directories.
+fn prune(root: &Path, keep: usize) -> Result<()> {
+    for path in runs(root)?.iter().skip(keep) {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Run directories, newest first.
+fn runs(root: &Path) -> Result<Vec<PathBuf>> {
+    let dir = root.join(LOGS_DIR);
+    let entries =
+        fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    let mut runs: Vec<(u64, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let run = path.file_name()?.to_str()?.parse().ok()?;
+            Some((run, path))
+        })
+        .collect();
+    runs.sort_unstable_by_key(|(run, _)| std::cmp::Reverse(*run));
+    Ok(runs.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_get_their_own_directory_and_old_ones_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let runs: Vec<PathBuf> = (0..3)
+            .map(|_| start_run(dir.path(), 2).unwrap().unwrap())
+            .collect();
+        assert!(!runs[0].exists());
+        assert!(runs[1].is_dir() && runs[2].is_dir());
+        assert_eq!(latest_run(dir.path()).as_ref(), Some(&runs[2]));
+
+        let phase = Phase::Generate {
+            generator: "go".into(),
+            scope: "server".into(),
+        };
+        let path = write(&runs[2], &phase, "line\n").unwrap();
+        assert!(path.ends_with("generate-server-go.log"));
+        assert_eq!(fs::read_to_string(path).unwrap(), "line\n");
+    }
+
+    #[test]
+    fn zero_retention_keeps_no_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(start_run(dir.path(), 0).unwrap().is_none());
+        assert!(!dir.path().join(LOGS_DIR).exists());
+        assert_eq!(latest_run(dir.path()), None);
+    }
+}
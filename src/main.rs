@@ -1,44 +1,139 @@
 mod app;
+mod cli;
+mod clipboard;
+mod editor;
 mod fix;
 mod highlight;
 #[allow(unused)]
 mod log_parser;
+mod notify;
+mod quickfix;
+mod rule_docs;
 #[allow(unused)]
 mod spec;
 mod ui;
 
 use std::io;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::Duration;
+use std::process::{Command, ExitCode, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::event::{self, Event, KeyCode, KeyEvent};
 use crossterm::execute;
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
-use app::diff::{DiffPanel, DiffViewState};
-use app::{App, BrowserPanel, Panel, StatusLevel, ViewMode};
+use app::compare::ReportComparison;
+use app::diff::{ChangeKind, DiffPanel, DiffViewState};
+use app::matrix::MatrixView;
+use app::picker::GeneratorPickerState;
+use app::search::TextSearch;
+use app::tour::{TourState, TourTarget};
+use app::wizard::{InitWizardState, WizardStep};
+use app::workspace::WorkspaceView;
+use app::{App, BrowserPanel, Panel, ScreenMode, StatusLevel, ViewMode};
+use fix::FixOrigin;
+use lazyoav::automation;
+use lazyoav::baseline;
+use lazyoav::changelog::{self, Changelog};
+use lazyoav::clean;
 use lazyoav::config;
 use lazyoav::custom;
 use lazyoav::docker::{self, CancelToken};
+use lazyoav::durations::Durations;
+use lazyoav::graph::{Node, RefGraph};
+use lazyoav::hooks::{self, HookSite};
 use lazyoav::keys::{KeyAction, KeyInput};
+use lazyoav::leftovers;
+use lazyoav::migrate;
+use lazyoav::naming;
+use lazyoav::operation_ids;
+use lazyoav::orphans;
 use lazyoav::pipeline::{self, PipelineEvent, PipelineInput};
+use lazyoav::pitfalls;
+use lazyoav::plugins;
+use lazyoav::regions;
+use lazyoav::reports;
+use lazyoav::runs;
 use lazyoav::scaffold;
+use lazyoav::score::{self, Score};
+use lazyoav::share;
+use lazyoav::stats;
+use lazyoav::support;
+use lazyoav::suppress::{self, Suppression};
+use lazyoav::trace::{self, Target};
+use lazyoav::trust;
 
 /// Action returned by `handle_key` to signal the run loop.
 enum Action {
     None,
-    OpenEditor { path: PathBuf, line: usize },
+    OpenEditor {
+        path: PathBuf,
+        line: usize,
+        col: usize,
+    },
+    OpenGenerated {
+        path: PathBuf,
+        line: usize,
+    },
+    OpenShell {
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+    },
+    OpenPager {
+        path: PathBuf,
+    },
 }
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     let args: Vec<String> = std::env::args().skip(1).collect();
-    if args.iter().any(|a| a == "--version" || a == "-V") {
-        println!("lazyoav {}", env!("CARGO_PKG_VERSION"));
-        return Ok(());
+    let target = match cli::parse(&args)? {
+        cli::Command::Run(target) => target,
+        cli::Command::Help => {
+            print!("{}", cli::USAGE);
+            return Ok(ExitCode::SUCCESS);
+        }
+        cli::Command::Version => {
+            println!("lazyoav {}", env!("CARGO_PKG_VERSION"));
+            return Ok(ExitCode::SUCCESS);
+        }
+    };
+    // Everything else works relative to the current directory.
+    if let Some(root) = &target.root {
+        std::env::set_current_dir(root)
+            .with_context(|| format!("Failed to enter {}", root.display()))?;
+    }
+
+    // A bad LAZYOAV_LOG only costs the debug log, and an empty one means off.
+    let debug_log = match (target.debug_log, std::env::var(trace::ENV_VAR)) {
+        (Some(targets), _) => Some(targets),
+        (None, Ok(filter)) if filter.trim().is_empty() => None,
+        (None, Ok(filter)) => match trace::parse_filter(&filter) {
+            Ok(targets) => Some(targets),
+            Err(e) => {
+                eprintln!("warning: {}: {e:#}; debug log off", trace::ENV_VAR);
+                None
+            }
+        },
+        (None, Err(_)) => None,
+    };
+    if let Some(targets) = debug_log {
+        let cwd = std::env::current_dir()?;
+        if let Err(e) = trace::init(&cwd, targets) {
+            eprintln!("warning: debug log unavailable: {e:#}");
+        }
+    }
+
+    if target.quickfix {
+        let passed = print_quickfix(target.spec)?;
+        return Ok(if passed {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        });
     }
 
     // Ensure terminal is restored on panic.
@@ -49,9 +144,9 @@ fn main() -> Result<()> {
     }));
 
     let mut terminal = setup_terminal()?;
-    let result = run(&mut terminal);
+    let result = run(&mut terminal, target.spec);
     restore_terminal()?;
-    result
+    result.map(|()| ExitCode::SUCCESS)
 }
 
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
@@ -69,17 +164,47 @@ fn restore_terminal() -> Result<()> {
     Ok(())
 }
 
-fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, spec: Option<String>) -> Result<()> {
     let mut app = App::new();
-    load_from_cwd(&mut app);
+    app.spec_override = spec;
+    app.docker_available = docker::ensure_available().is_ok();
+    start_project_discovery(&mut app);
+    if spec::is_project(&app.work_dir) {
+        load_project(&mut app);
+        start_held_validation(&mut app);
+    } else {
+        app.set_status("Looking for projects...", StatusLevel::Info);
+    }
+    if app::tour::first_launch() {
+        start_tour(&mut app);
+    }
 
     while app.running {
         app.tick = app.tick.wrapping_add(1);
         terminal.draw(|frame| ui::draw(frame, &app))?;
 
-        // Poll for input: use a short timeout while validating (to drain
-        // pipeline events promptly) and a longer one when idle to save CPU.
-        let poll_timeout = if app.validating {
+        // Poll for input: use a short timeout while validating or
+        // highlighting (to show events and highlighted lines promptly) and a
+        // longer one when idle to save CPU.
+        let poll_timeout = if app.validating
+            || app
+                .generator_picker
+                .as_ref()
+                .is_some_and(|p| p.live_rx.is_some())
+            || app.matrix.as_ref().is_some_and(|m| m.is_running())
+            || app.workspace.as_ref().is_some_and(|w| w.is_running())
+            || app.browser.usage_rx.is_some()
+            || app.project_rx.is_some()
+            || app.leftover_rx.is_some()
+            || app.clean_rx.is_some()
+            || app.fix_assist_rx.is_some()
+            || app.plugin_rx.is_some()
+            || app.plugin_fix_rx.is_some()
+            || !app.automation_rx.is_empty()
+            || !app.record_rx.is_empty()
+            || app.highlight_engine.borrow().is_busy()
+            || app.browser.highlight_engine.borrow().is_busy()
+        {
             Duration::from_millis(50)
         } else {
             Duration::from_millis(200)
@@ -88,41 +213,67 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
             && let Event::Key(key) = event::read()?
         {
             match handle_key(&mut app, key) {
-                Action::OpenEditor { path, line } => {
-                    open_editor(terminal, &mut app, &path, line)?;
+                Action::OpenEditor { path, line, col } => {
+                    open_editor(terminal, &mut app, &path, line, col)?;
+                }
+                Action::OpenGenerated { path, line } => {
+                    open_generated(terminal, &mut app, &path, line)?;
+                }
+                Action::OpenShell { args, env } => {
+                    open_shell(terminal, &mut app, &args, &env)?;
+                }
+                Action::OpenPager { path } => {
+                    open_pager(terminal, &mut app, &path)?;
                 }
                 Action::None => {}
             }
             app.clamp_indices();
         }
 
+        drain_project_discovery(&mut app);
+        drain_leftover_search(&mut app);
+        drain_clean_sizes(&mut app);
         drain_pipeline_events(&mut app);
+        drain_picker_events(&mut app);
+        drain_matrix_events(&mut app);
+        drain_workspace_events(&mut app);
+        app::browser::drain_usage(&mut app.browser);
+        drain_fix_assist(&mut app);
+        drain_plugin_findings(&mut app);
+        drain_plugin_fix(&mut app);
+        drain_automation(&mut app);
+        drain_run_records(&mut app);
+        check_watched_spec(&mut app);
+        check_spec_file(&mut app);
+        check_config_file(&mut app);
     }
 
     Ok(())
 }
 
-/// Load spec and report from the current working directory.
+/// Load spec and report for the project in `app.work_dir`.
 ///
 /// Looks for:
-/// - A `.oav/reports/report.json` (parsed as a ValidateReport).
+/// - A `.oav/reports/report.json` (parsed as a ValidateReport), with the
+///   lint findings cached beside it when they match its lint log.
 /// - An OpenAPI spec via config `spec` field, or auto-discovery.
 ///
-/// Surfaces Docker and config errors via `app.status_message`.
+/// Surfaces Docker and config errors via `app.status_message`, and
+/// problems setting up `.oav/` among the config diagnostics.
 /// Report and spec parse failures are silently skipped (they are optional).
-fn load_from_cwd(app: &mut App) {
-    let cwd = match std::env::current_dir() {
-        Ok(p) => p,
-        Err(_) => return,
-    };
+/// Validation isn't started here; see [`start_held_validation`].
+fn load_project(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    let mut diagnostics = Vec::new();
 
     // Scaffold .oav/ directories early — warn but don't abort on failure.
     if let Err(e) = scaffold::ensure_oav_dirs(&cwd) {
-        eprintln!("warning: failed to scaffold .oav/ dirs: {e}");
+        diagnostics.push(config::Diagnostic::warning(
+            ".oav",
+            format!("Failed to scaffold .oav/ dirs: {e:#}"),
+        ));
     }
 
-    // Check Docker availability.
-    app.docker_available = docker::ensure_available().is_ok();
     if !app.docker_available {
         app.set_status(
             "Docker not available \u{2014} only cached reports can be viewed",
@@ -130,54 +281,283 @@ fn load_from_cwd(app: &mut App) {
         );
     }
 
-    // Load config, surfacing parse errors.
-    let cfg = match config::load(&cwd) {
-        Ok(c) => c,
-        Err(e) => {
-            app.set_status(
-                format!("Config error: {e} \u{2014} using defaults"),
-                StatusLevel::Warn,
-            );
-            config::Config::default()
+    // Offer to clean up after sessions that crashed; validation waits for
+    // the answer, so a stashed matrix output is restored before it runs.
+    start_leftover_search(app);
+
+    // Ask before an unknown project runs commands on the host; until then
+    // its config loads without them.
+    if !trust::is_trusted(&cwd) {
+        let commands = trust::host_commands(&cwd);
+        if !commands.is_empty() {
+            app.trust_prompt = Some(app::TrustPrompt {
+                root: cwd.clone(),
+                commands,
+            });
         }
-    };
+    }
+
+    // Load config, collecting parse errors and unknown keys.
+    app.config_modified = config_modified(&cwd);
+    let (cfg, config_diagnostics) = config::load_checked(&cwd);
+    diagnostics.extend(config_diagnostics);
+    diagnostics.extend(apply_config(app, &cwd, &cfg));
 
     // Manage .gitignore if enabled.
     if cfg.manage_gitignore
         && let Err(e) = scaffold::manage_gitignore(&cwd)
     {
-        eprintln!("warning: failed to manage .gitignore: {e}");
+        diagnostics.push(config::Diagnostic::warning(
+            "manage_gitignore",
+            format!("Failed to manage .gitignore: {e:#}"),
+        ));
     }
 
-    // Load custom generators if configured.
-    if let Some(dir) = &cfg.custom_generators_dir {
-        match custom::load(&cwd, dir) {
-            Ok(defs) => app.custom_defs = defs,
-            Err(e) => {
-                app.set_status(format!("Custom generators error: {e}"), StatusLevel::Warn);
-            }
-        }
+    // Load accepted findings before parsing the lint log.
+    match suppress::load(&cwd) {
+        Ok(suppressions) => app.suppressions = suppressions,
+        Err(e) => diagnostics.push(config::Diagnostic::error(
+            suppress::IGNORE_FILE,
+            format!("{e:#}"),
+        )),
+    }
+    match baseline::load(&cwd) {
+        Ok(baseline) => app.baseline = baseline,
+        Err(e) => diagnostics.push(config::Diagnostic::error(
+            baseline::BASELINE_FILE,
+            format!("{e:#}"),
+        )),
+    }
+
+    let spec_path = load_spec(app, &cwd, &cfg);
+    load_report(app, &cwd);
+    if app.report.is_some() {
+        app.migration_findings = migration_findings(app);
+        start_plugin_analysis(app);
+        trace_findings(app);
+    }
+
+    if spec_path.is_none() && app.status_message.is_none() {
+        app.set_status("No OpenAPI spec found", StatusLevel::Info);
+    }
+
+    // Show problems up front; the overlay can be reopened later.
+    app.show_diagnostics = !diagnostics.is_empty();
+    app.config_diagnostics = diagnostics;
+    if let Some(summary) = app.diagnostics_summary() {
+        let level = if app
+            .config_diagnostics
+            .iter()
+            .any(|d| d.severity == config::Severity::Error)
+        {
+            StatusLevel::Error
+        } else {
+            StatusLevel::Warn
+        };
+        let review = app.keymap.label(KeyAction::ShowDiagnostics).to_string();
+        app.set_status(format!("Config: {summary} ({review} to review)"), level);
     }
 
-    // Validate config against generator registry.
-    let warnings = config::validate(&cfg, &app.custom_defs);
-    if !warnings.is_empty() {
-        app.set_status(warnings.join("; "), StatusLevel::Warn);
+    // Offer guided setup when the project has no `.oavc` yet; validation
+    // starts once the wizard is finished or skipped.
+    if !cwd.join(config::CONFIG_FILE).exists() {
+        let specs = match &app.spec_override {
+            Some(spec) => vec![spec.clone()],
+            None => spec::discover_spec(&cwd, cfg.search_depth).unwrap_or_default(),
+        };
+        app.init_wizard = Some(InitWizardState::new(
+            specs,
+            app::wizard::recommend_linter(&cwd),
+            &app.custom_defs,
+        ));
     }
 
-    // Load report if present.
-    let report_path = cwd.join(".oav/reports/report.json");
-    if let Ok(report_json) = std::fs::read_to_string(&report_path)
-        && let Ok(report) = serde_json::from_str::<pipeline::ValidateReport>(&report_json)
+    app.config = Some(cfg);
+    app.clamp_indices();
+}
+
+/// Kick off the live validation that loading the project held back, once
+/// Docker is available and no startup question is open. The cached report
+/// stays visible while the pipeline runs, then gets replaced by fresh
+/// results. A project switched to waits for the user to run it instead.
+fn start_held_validation(app: &mut App) {
+    if app.validate_on_load
+        && app.docker_available
+        && app.init_wizard.is_none()
+        && !startup_prompt_open(app)
     {
-        if let Some(lint) = &report.phases.lint {
-            app.lint_errors = log_parser::parse_lint_log(&lint.log);
+        start_pipeline(app);
+    }
+}
+
+/// Whether a question asked on startup still holds validation back.
+fn startup_prompt_open(app: &App) -> bool {
+    app.leftover_rx.is_some() || app.leftover_prompt.is_some() || app.trust_prompt.is_some()
+}
+
+/// Look for leftovers of crashed sessions on a background thread; see
+/// [`drain_leftover_search`].
+fn start_leftover_search(app: &mut App) {
+    let work_dir = app.work_dir.clone();
+    let docker = app.docker_available;
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(leftovers::find(&work_dir, docker));
+    });
+    app.leftover_rx = Some(rx);
+}
+
+/// Ask about the leftovers found, if any, or start the validation the
+/// search held back.
+fn drain_leftover_search(app: &mut App) {
+    let Some(rx) = &app.leftover_rx else {
+        return;
+    };
+    let found = match rx.try_recv() {
+        Ok(found) => found,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => leftovers::Leftovers::default(),
+    };
+    app.leftover_rx = None;
+    if found.is_empty() {
+        start_held_validation(app);
+    } else {
+        app.leftover_prompt = Some(found);
+    }
+}
+
+/// Look for projects under the start directory on a background thread;
+/// see [`drain_project_discovery`].
+fn start_project_discovery(app: &mut App) {
+    let root = app.work_dir.clone();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let depth = config::load(&root).unwrap_or_default().search_depth;
+        let _ = tx.send(spec::discover_projects(&root, depth));
+    });
+    app.project_rx = Some(rx);
+}
+
+/// Take the projects found under the start directory. A single project
+/// that is the start directory itself leaves nothing to switch between; a
+/// start directory that is no project itself loads once there's nothing
+/// else to pick, or opens the project switcher.
+fn drain_project_discovery(app: &mut App) {
+    let Some(rx) = &app.project_rx else {
+        return;
+    };
+    let projects = match rx.try_recv() {
+        Ok(projects) => projects,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => Vec::new(),
+    };
+    app.project_rx = None;
+    let loaded = app.config.is_some();
+    if projects.iter().all(|p| p.dir.as_os_str().is_empty()) {
+        if !loaded {
+            app.status_message = None;
+            load_project(app);
+            start_held_validation(app);
         }
-        app.report = Some(report);
+        return;
+    }
+    app.project = projects.iter().position(|p| p.dir.as_os_str().is_empty());
+    app.projects = projects;
+    app.project_root = Some(app.work_dir.clone());
+    if !loaded {
+        // A monorepo root that is no project itself: pick one first.
+        app.project_switcher = Some(0);
+        app.set_status(
+            format!("{} projects found \u{2014} pick one", app.projects.len()),
+            StatusLevel::Info,
+        );
+    } else if app.status_message.is_none() {
+        let switch = app.keymap.label(KeyAction::SwitchProject).to_string();
+        app.set_status(
+            format!(
+                "{} projects found \u{2014} {switch} to switch",
+                app.projects.len()
+            ),
+            StatusLevel::Info,
+        );
+    }
+}
+
+/// Load project `index` from scratch, with its own config, spec and
+/// report. Nothing runs until the user asks: the project's hooks, plugins
+/// and automation haven't been seen in this session yet.
+fn switch_project(app: &mut App, index: usize) {
+    if pipeline_running(app) {
+        app.set_status(
+            "Wait for the running pipeline to finish before switching projects",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let (Some(root), Some(project)) = (app.project_root.clone(), app.projects.get(index)) else {
+        return;
+    };
+    let label = project.label();
+    let work_dir = root.join(&project.dir);
+    trace::log(Target::Ui, format_args!("switched to project {label}"));
+    let projects = std::mem::take(&mut app.projects);
+    let docker_available = app.docker_available;
+    *app = App::new();
+    app.work_dir = work_dir;
+    app.docker_available = docker_available;
+    app.validate_on_load = false;
+    app.projects = projects;
+    app.project_root = Some(root);
+    app.project = Some(index);
+    load_project(app);
+    if app.status_message.is_none() {
+        let run = app.keymap.label(KeyAction::RunValidation).to_string();
+        app.set_status(
+            format!("Switched to {label} \u{2014} {run} to validate"),
+            StatusLevel::Info,
+        );
+    }
+}
+
+/// Load the last report and its findings, if there is one. The spec is
+/// loaded first, so the analysis findings are for the spec as it is now.
+fn load_report(app: &mut App, cwd: &Path) {
+    let Some(report) = reports::load(cwd) else {
+        return;
+    };
+    if let Some(lint) = &report.phases.lint {
+        let errors = log_parser::cache::load(cwd, &lint.log)
+            .unwrap_or_else(|| log_parser::parse_lint_output(&lint.log));
+        let analysis = analysis_findings(app);
+        app.set_lint_findings(errors, analysis);
+    }
+    app.structure_errors = structure_findings(&report);
+    app.set_security_errors(security_findings(&report));
+    app.test_errors = test_findings(&report);
+    set_code_findings(app, &report);
+    app.step_error_counts = app::step_error_counts(&report);
+    app.report = Some(report);
+    if let Err(e) = settle_lint_status(app, cwd) {
+        app.set_status(format!("{e:#}"), StatusLevel::Warn);
     }
+}
+
+/// Settle the lint status under the fail policy and, when it changed, save
+/// the report, so `report.json` agrees with what's shown. Returns whether
+/// it changed.
+fn settle_lint_status(app: &mut App, cwd: &Path) -> Result<bool> {
+    if !app.settle_lint_status() {
+        return Ok(false);
+    }
+    if let Some(report) = &app.report {
+        reports::save(cwd, report).context("Failed to save the settled report")?;
+    }
+    Ok(true)
+}
 
-    // Discover and parse spec.
-    let spec_path = resolve_spec_path(&cwd, &cfg);
+/// Discover and parse the spec. Returns its path, if one was found.
+fn load_spec(app: &mut App, cwd: &Path, cfg: &config::Config) -> Option<PathBuf> {
+    let spec_path = resolve_spec_path(cwd, cfg, app.spec_override.as_deref());
     app.spec_path = spec_path.clone();
     if let Some(path) = &spec_path
         && let Ok(raw) = std::fs::read_to_string(path)
@@ -185,37 +565,242 @@ fn load_from_cwd(app: &mut App) {
     {
         app.spec_index = Some(index);
     }
+    spec_path
+}
 
-    if spec_path.is_none() && app.status_message.is_none() {
-        app.set_status("No OpenAPI spec found", StatusLevel::Info);
+/// `--quickfix`: print the last run's findings for an editor's error list,
+/// and write them to `.oav/quickfix.txt`, without starting the TUI.
+/// Returns whether that run passed, with the fail policy applied.
+fn print_quickfix(spec: Option<String>) -> Result<bool> {
+    let cwd = std::env::current_dir()?;
+    let mut app = App::new();
+    app.spec_override = spec;
+    let cfg = config::load(&cwd)?;
+    apply_config(&mut app, &cwd, &cfg);
+    app.suppressions = suppress::load(&cwd)?;
+    app.baseline = baseline::load(&cwd)?;
+    load_spec(&mut app, &cwd, &cfg);
+    load_report(&mut app, &cwd);
+    if app.report.is_none() {
+        anyhow::bail!(
+            "No report in {} yet \u{2014} run lazyoav there first",
+            cwd.display()
+        );
+    }
+    print!("{}", export_quickfix(&app, &cwd)?);
+    if let Some(status) = &app.status_message
+        && matches!(status.level, StatusLevel::Warn)
+    {
+        eprintln!("warning: {}", status.text);
+    }
+    Ok(app.report.as_ref().is_some_and(|r| r.summary.failed == 0))
+}
+
+/// Write the loaded findings to `.oav/quickfix.txt`, returning what was
+/// written. Without a spec the findings have no file to point at.
+fn export_quickfix(app: &App, cwd: &Path) -> Result<String> {
+    let Some(path) = app.spec_path.as_deref() else {
+        anyhow::bail!("No spec found \u{2014} findings have no file to point at");
+    };
+    let spec = path.strip_prefix(cwd).unwrap_or(path).display().to_string();
+    let contents = quickfix::render(&spec, &app.located_findings());
+    quickfix::write(cwd, &contents)?;
+    Ok(contents)
+}
+
+/// Answer the startup leftover prompt, cleaning up when `clean` is set,
+/// then start the validation it held back.
+fn answer_leftover_prompt(app: &mut App, clean: bool) {
+    let Some(found) = app.leftover_prompt.take() else {
+        return;
+    };
+    if clean {
+        let cleanup = leftovers::clean(&app.work_dir, &found);
+        let level = if cleanup.is_partial() {
+            StatusLevel::Warn
+        } else {
+            StatusLevel::Info
+        };
+        app.set_status(cleanup.summary(), level);
     }
+    start_held_validation(app);
+}
 
-    // Build keymap from config, surfacing warnings.
-    if !cfg.keys.is_empty() {
-        let (keymap, key_warnings) = lazyoav::keys::Keymap::from_config(&cfg.keys);
-        app.keymap = keymap;
-        if !key_warnings.is_empty() {
-            app.set_status(key_warnings.join("; "), StatusLevel::Warn);
+/// Answer the startup trust prompt. Trusting the project records it and
+/// reloads its config with the host commands; either way the validation
+/// it held back starts.
+fn answer_trust_prompt(app: &mut App, trusted: bool) {
+    let Some(prompt) = app.trust_prompt.take() else {
+        return;
+    };
+    if trusted {
+        match trust::trust(&prompt.root) {
+            Ok(()) => {
+                let (cfg, mut diagnostics) = config::load_checked(&prompt.root);
+                diagnostics.extend(apply_config(app, &prompt.root, &cfg));
+                app.show_diagnostics = !diagnostics.is_empty();
+                app.config_diagnostics = diagnostics;
+                app.config = Some(cfg);
+                if app.report.is_some() {
+                    start_plugin_analysis(app);
+                }
+            }
+            Err(e) => app.set_status(format!("{e:#}"), StatusLevel::Error),
         }
     }
+    start_held_validation(app);
+}
 
-    app.config = Some(cfg);
-    app.clamp_indices();
+/// Apply the settings the app keeps outside `app.config`: accessible mode,
+/// custom generators, plugins, severity overrides, the fail policy, rule
+/// docs and keys. Returns what validating `cfg` against the project turned
+/// up.
+fn apply_config(app: &mut App, cwd: &Path, cfg: &config::Config) -> Vec<config::Diagnostic> {
+    let mut diagnostics = Vec::new();
+    app.accessible = cfg.accessible || app::no_color();
 
-    // Kick off a live validation if Docker is available — the cached report
-    // stays visible while the pipeline runs, then gets replaced by fresh results.
-    if app.docker_available {
-        start_pipeline(app);
+    app.custom_defs = Vec::new();
+    if let Some(dir) = &cfg.custom_generators_dir {
+        match custom::load(cwd, dir) {
+            Ok(defs) => app.custom_defs = defs,
+            Err(e) => diagnostics.push(config::Diagnostic::error(
+                "custom_generators_dir",
+                format!("{e:#}"),
+            )),
+        }
+    }
+
+    // Plugins are host commands: only an untrusted project's are skipped.
+    app.plugins = Arc::default();
+    if trust::is_trusted(cwd) {
+        match plugins::Registry::load(cwd) {
+            Ok(registry) => app.plugins = Arc::new(registry),
+            Err(e) => diagnostics.push(config::Diagnostic::error(
+                plugins::PLUGINS_DIR,
+                format!("{e:#}"),
+            )),
+        }
+    }
+
+    // Validate config against generator registry and the project layout.
+    diagnostics.extend(config::validate(cwd, cfg, &app.custom_defs));
+
+    app.set_severity_overrides(&cfg.rule_severity_overrides);
+    app.fail_policy = app::FailPolicy::from_config(cfg);
+    match rule_docs::RuleDocs::load(cwd, cfg) {
+        Ok(docs) => app.rule_docs = docs,
+        Err(e) => diagnostics.push(config::Diagnostic::warning(
+            "spectral_ruleset",
+            format!("Rule docs unavailable: {e:#}"),
+        )),
     }
+
+    let (keymap, key_warnings) = if cfg.keys.is_empty() {
+        (lazyoav::keys::Keymap::default_keymap(), Vec::new())
+    } else {
+        lazyoav::keys::Keymap::from_config(&cfg.keys)
+    };
+    app.keymap = keymap;
+    diagnostics.extend(
+        key_warnings
+            .into_iter()
+            .map(|w| config::Diagnostic::warning("keys", w)),
+    );
+    diagnostics
+}
+
+/// When the project's `.oavc` was last written, if it exists.
+fn config_modified(cwd: &Path) -> Option<SystemTime> {
+    std::fs::metadata(cwd.join(config::CONFIG_FILE))
+        .and_then(|m| m.modified())
+        .ok()
 }
 
-/// Resolve which spec file to use: explicit config value, or auto-discovery.
-fn resolve_spec_path(cwd: &Path, cfg: &config::Config) -> Option<std::path::PathBuf> {
-    // If config specifies a spec, use that.
-    if let Some(ref spec_str) = cfg.spec
-        && let Ok(path) = spec::normalize_spec_path(cwd, spec_str)
+/// Reload `.oavc` when it changed on disk. A config that doesn't parse is
+/// left out, keeping the one in use; otherwise the status bar names what
+/// changed, and the report is marked stale when that could change it.
+fn check_config_file(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    let modified = config_modified(&cwd);
+    if modified == app.config_modified || app.config.is_none() || app.init_wizard.is_some() {
+        return;
+    }
+    app.config_modified = modified;
+
+    let (cfg, mut diagnostics) = config::load_checked(&cwd);
+    if diagnostics
+        .iter()
+        .any(|d| d.severity == config::Severity::Error)
+    {
+        let review = app.keymap.label(KeyAction::ShowDiagnostics).to_string();
+        app.config_diagnostics = diagnostics;
+        app.status_message = None;
+        app.set_status(
+            format!(
+                "{} has errors \u{2014} keeping the previous config ({review} to review)",
+                config::CONFIG_FILE
+            ),
+            StatusLevel::Error,
+        );
+        return;
+    }
+    let Some(old) = app.config.take() else {
+        return;
+    };
+    let changed = config::changed_keys(&old, &cfg);
+    diagnostics.extend(apply_config(app, &cwd, &cfg));
+    if changed
+        .iter()
+        .any(|key| key == "spec" || key == "search_depth")
     {
-        return Some(path);
+        app.spec_path = resolve_spec_path(&cwd, &cfg, app.spec_override.as_deref());
+        app.spec_index = app
+            .spec_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|raw| spec::parse_spec(&raw).ok());
+    }
+    app.config = Some(cfg);
+    app.config_diagnostics = diagnostics;
+
+    if changed.is_empty() {
+        return;
+    }
+    let stale = app.report.is_some() && changed.iter().any(|key| config::affects_report(key));
+    app.report_stale |= stale;
+    let mut message = format!(
+        "Reloaded {}: {} changed",
+        config::CONFIG_FILE,
+        changed.join(", ")
+    );
+    if stale {
+        let run = app.keymap.label(KeyAction::RunValidation);
+        message.push_str(&format!(" \u{2014} report is stale, {run} to re-run"));
+    }
+    if let Some(summary) = app.diagnostics_summary() {
+        message.push_str(&format!(" ({summary})"));
+    }
+    let level = if app.config_diagnostics.is_empty() {
+        StatusLevel::Info
+    } else {
+        StatusLevel::Warn
+    };
+    app.status_message = None;
+    app.set_status(message, level);
+}
+
+/// Resolve which spec file to use: the command-line spec, the config value,
+/// or auto-discovery.
+fn resolve_spec_path(
+    cwd: &Path,
+    cfg: &config::Config,
+    spec_override: Option<&str>,
+) -> Option<std::path::PathBuf> {
+    // A spec given on the command line wins, then the one in config.
+    for spec_str in spec_override.into_iter().chain(cfg.spec.as_deref()) {
+        if let Ok(path) = spec::normalize_spec_path(cwd, spec_str) {
+            return Some(path);
+        }
     }
 
     // Otherwise auto-discover.
@@ -228,12 +813,80 @@ fn resolve_spec_path(cwd: &Path, cfg: &config::Config) -> Option<std::path::Path
     None
 }
 
+/// The spec changed on disk since the open fix was proposed: propose it
+/// again against the spec as it is now, for another look before applying.
+fn repropose_fix(app: &mut App, origin: Option<FixOrigin>, spec_path: &Path) {
+    if let Ok(raw) = std::fs::read_to_string(spec_path)
+        && let Ok(index) = spec::parse_spec(&raw)
+    {
+        app.spec_index = Some(index);
+    }
+    let cfg = app.config.clone().unwrap_or_default();
+    if let Some(FixOrigin::Finding(error)) = &origin
+        && app.plugins.handles(&error.rule)
+    {
+        start_plugin_fix(app, error.clone());
+        return;
+    }
+    let result = match &origin {
+        Some(FixOrigin::Finding(error)) => match &app.spec_index {
+            Some(index) => fix::propose_fix(error, index, spec_path, &cfg),
+            None => Ok(None),
+        },
+        Some(FixOrigin::Format) => fix::propose_format(spec_path, &cfg.format),
+        None => {
+            app.set_status(
+                "The spec changed since the fix was suggested; press f to ask again",
+                StatusLevel::Warn,
+            );
+            return;
+        }
+    };
+    match result {
+        Ok(Some(proposal)) => {
+            app.open_fix(proposal, origin);
+            app.set_status(
+                "The spec changed since the fix was proposed \u{2014} review the updated fix",
+                StatusLevel::Warn,
+            );
+        }
+        Ok(None) => app.set_status(
+            "The spec changed since the fix was proposed, and it no longer applies",
+            StatusLevel::Warn,
+        ),
+        Err(e) => app.set_status(
+            format!("Failed to propose the fix again: {e:#}"),
+            StatusLevel::Error,
+        ),
+    }
+}
+
+/// Scroll the fix overlay's diff by `by` lines, keeping its last line in
+/// reach.
+fn scroll_fix(app: &mut App, by: isize) {
+    let last = app
+        .fix_preview
+        .as_ref()
+        .map_or(0, |p| p.lines.len().saturating_sub(1));
+    let scroll = usize::from(app.fix_scroll)
+        .saturating_add_signed(by)
+        .min(last);
+    app.fix_scroll = u16::try_from(scroll).unwrap_or(u16::MAX);
+}
+
 fn handle_key(app: &mut App, key: KeyEvent) -> Action {
-    // Fix overlay: handle accept/skip/cancel before anything else (stays hardcoded).
+    // Fix overlay: scroll, accept/skip/cancel before anything else (stays hardcoded).
     if app.fix_proposal.is_some() {
         match key.code {
             KeyCode::Char('y') => {
-                let proposal = app.fix_proposal.take().unwrap();
+                let origin = app.fix_origin.clone();
+                let proposal = app.close_fix().unwrap();
+                if let Some(spec_path) = app.spec_path.clone()
+                    && fix::is_stale(&proposal, &spec_path)
+                {
+                    repropose_fix(app, origin, &spec_path);
+                    return Action::None;
+                }
                 if let Some(spec_path) = &app.spec_path {
                     match fix::apply_fix(&proposal, spec_path) {
                         Ok(()) => {
@@ -245,6 +898,13 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                             }
                             start_pipeline(app);
                             app.set_status("Fix applied, re-validating...", StatusLevel::Info);
+                            run_fix_automation(
+                                app,
+                                &proposal.rule,
+                                &proposal.description,
+                                proposal.target_line,
+                                1,
+                            );
                         }
                         Err(e) => {
                             app.set_status(format!("Failed to apply fix: {e}"), StatusLevel::Error);
@@ -253,83 +913,702 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                 }
                 return Action::None;
             }
+            KeyCode::Char('j') | KeyCode::Down => scroll_fix(app, 1),
+            KeyCode::Char('k') | KeyCode::Up => scroll_fix(app, -1),
+            KeyCode::PageDown => scroll_fix(app, 20),
+            KeyCode::PageUp => scroll_fix(app, -20),
             KeyCode::Char('n') => {
-                app.fix_proposal = None;
+                app.close_fix();
                 // Advance to next error.
                 app.error_index = app.error_index.saturating_add(1);
                 app.clamp_indices();
                 return Action::None;
             }
             KeyCode::Esc | KeyCode::Char('q') => {
-                app.fix_proposal = None;
+                app.close_fix();
                 return Action::None;
             }
-            _ => return Action::None,
+            _ => {}
         }
+        return Action::None;
     }
 
-    // Help overlay: any key dismisses it (stays hardcoded).
-    if app.show_help {
-        app.show_help = false;
+    // Inline edit overlay: swallows all keys while open (stays hardcoded).
+    if app.inline_edit.is_some() {
+        handle_inline_edit_key(app, key);
         return Action::None;
     }
 
-    // Clear transient status on any keypress.
-    app.status_message = None;
+    // Timeout prompt: extend/dismiss the oldest pending warning (stays hardcoded).
+    if !app.timeout_prompts.is_empty() {
+        match key.code {
+            KeyCode::Char('y') => {
+                let prompt = app.timeout_prompts.remove(0);
+                let secs = app.timeout_extension();
+                let extension = Duration::from_secs(secs);
+                if prompt
+                    .control
+                    .send(docker::ContainerControl::ExtendTimeout(extension))
+                    .is_ok()
+                {
+                    app.set_status(
+                        format!(
+                            "Extended {} timeout by {}",
+                            prompt.phase,
+                            app::format_secs(secs)
+                        ),
+                        StatusLevel::Info,
+                    );
+                } else {
+                    app.set_status(
+                        format!("{} already finished", prompt.phase),
+                        StatusLevel::Info,
+                    );
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                app.timeout_prompts.remove(0);
+            }
+            _ => {}
+        }
+        return Action::None;
+    }
 
-    let input = KeyInput::from_event(key);
-    let has = |a: KeyAction| app.keymap.has_action(&input, a);
+    // Clean prompt: pick what to delete or cancel (stays hardcoded).
+    if let Some(prompt) = &app.clean_prompt {
+        let key = match key.code {
+            KeyCode::Char('g') => prompt.generator.as_ref().map(|(key, _)| Some(key.clone())),
+            KeyCode::Char('a') => Some(None),
+            KeyCode::Char('n') | KeyCode::Esc => {
+                app.clean_prompt = None;
+                None
+            }
+            _ => None,
+        };
+        if let Some(key) = key {
+            app.clean_prompt = None;
+            clean_generated(app, key.as_deref());
+        }
+        return Action::None;
+    }
 
-    // Dispatch priority: when one key maps to multiple actions, the first
-    // matching branch wins. Order: global → view-specific → panel nav → panel content.
-    // This is intentional — context (view mode, focused panel) resolves ambiguity.
-    if has(KeyAction::Quit) {
-        app.running = false;
+    // Migrate prompt: rewrite the spec for 3.1 or cancel (stays hardcoded).
+    if app.migrate_prompt.is_some() {
+        match key.code {
+            KeyCode::Char('y') => migrate_spec(app),
+            KeyCode::Char('n') | KeyCode::Esc => app.migrate_prompt = None,
+            _ => {}
+        }
         return Action::None;
     }
-    if has(KeyAction::Help) {
-        app.show_help = true;
+
+    // Leftover prompt: clean up after crashed sessions or leave it (stays hardcoded).
+    if app.leftover_prompt.is_some() {
+        match key.code {
+            KeyCode::Char('y') => answer_leftover_prompt(app, true),
+            KeyCode::Char('n') | KeyCode::Esc => answer_leftover_prompt(app, false),
+            _ => {}
+        }
         return Action::None;
     }
-    if has(KeyAction::ExpandLayout) {
-        app.screen_mode = app.screen_mode.cycle_next();
+
+    // Trust prompt: let the project's host commands run or not (stays hardcoded).
+    if app.trust_prompt.is_some() {
+        match key.code {
+            KeyCode::Char('y') => answer_trust_prompt(app, true),
+            KeyCode::Char('n') | KeyCode::Esc => answer_trust_prompt(app, false),
+            _ => {}
+        }
         return Action::None;
     }
-    if has(KeyAction::ShrinkLayout) {
-        app.screen_mode = app.screen_mode.cycle_prev();
+
+    // Generator picker overlay: swallows all keys while open (stays hardcoded).
+    if app.generator_picker.is_some() {
+        handle_picker_key(app, key);
         return Action::None;
     }
-    if has(KeyAction::RunValidation) {
-        start_pipeline(app);
+
+    // Init wizard overlay: swallows all keys while open (stays hardcoded).
+    if app.init_wizard.is_some() {
+        handle_wizard_key(app, key);
         return Action::None;
     }
-    if has(KeyAction::CancelValidation) && app.validating {
-        if let Some(token) = &app.cancel_token {
-            token.cancel();
+
+    // Config diagnostics overlay: scroll or dismiss (stays hardcoded).
+    if app.show_diagnostics {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = app.config_diagnostics.len().saturating_sub(1);
+                app.diagnostics_scroll = (app.diagnostics_scroll + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.diagnostics_scroll = app.diagnostics_scroll.saturating_sub(1);
+            }
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                app.show_diagnostics = false;
+            }
+            _ => {}
         }
         return Action::None;
     }
-    if has(KeyAction::ToggleView) {
-        match app.view_mode {
-            ViewMode::Validator => {
-                sync_generators_from_report(app);
-                if let Ok(cwd) = std::env::current_dir() {
-                    app::browser::refresh_file_tree(&mut app.browser, &cwd);
-                }
-                app.view_mode = ViewMode::CodeBrowser;
+
+    // Changelog overlay: scroll, export or dismiss (stays hardcoded).
+    if let Some(view) = app.changelog.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = view.changelog.changes.len().saturating_sub(1);
+                view.scroll = (view.scroll + 1).min(max);
             }
-            ViewMode::CodeBrowser => {
-                app.view_mode = ViewMode::Validator;
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.scroll = view.scroll.saturating_sub(1);
             }
+            KeyCode::Char('w') => export_changelog(app),
+            KeyCode::Esc | KeyCode::Char('q') => app.changelog = None,
+            _ => {}
         }
         return Action::None;
     }
 
-    // Early return for browser-specific keys.
-    if app.view_mode == ViewMode::CodeBrowser {
+    // Resolved schema overlay: scroll, copy or dismiss (stays hardcoded).
+    if let Some(view) = app.schema_view.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = view.lines().len().saturating_sub(1);
+                view.scroll = (view.scroll + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.scroll = view.scroll.saturating_sub(1);
+            }
+            KeyCode::Char('p') => {
+                view.show_payload = !view.show_payload;
+                view.scroll = 0;
+            }
+            KeyCode::Char('y') => {
+                let text = view.lines().join("\n");
+                let label = if view.show_payload {
+                    "example payload"
+                } else {
+                    "resolved schema"
+                };
+                app.schema_view = None;
+                match clipboard::copy(&text) {
+                    Ok(()) => app.set_status(format!("Copied {label}"), StatusLevel::Info),
+                    Err(e) => app.set_status(format!("Failed to copy: {e}"), StatusLevel::Error),
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.schema_view = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Reference graph overlay: scroll or dismiss (stays hardcoded).
+    if let Some(view) = app.ref_graph.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = view.used_by.len() + view.uses.len() + 2;
+                view.scroll = (view.scroll + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.scroll = view.scroll.saturating_sub(1);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.ref_graph = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Report comparison overlay: scroll or dismiss (stays hardcoded).
+    if let Some(view) = app.comparison.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                view.scroll = (view.scroll + 1).min(view.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.scroll = view.scroll.saturating_sub(1);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.comparison = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Rule statistics overlay: scroll or dismiss (stays hardcoded).
+    if let Some(view) = app.rule_stats.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                view.scroll = (view.scroll + 1).min(view.stats.rules.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.scroll = view.scroll.saturating_sub(1);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.rule_stats = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Run history overlay: pick runs to compare (stays hardcoded).
+    if let Some(view) = app.run_history.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                view.selected = (view.selected + 1).min(view.runs.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.selected = view.selected.saturating_sub(1);
+            }
+            KeyCode::Char(' ') => {
+                view.base = (view.base != Some(view.selected)).then_some(view.selected);
+            }
+            KeyCode::Enter => compare_runs(app),
+            KeyCode::Char('c') => compare_run_reports(app),
+            KeyCode::Esc | KeyCode::Char('q') => app.run_history = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Dry run overlay: select, copy or dismiss (stays hardcoded).
+    if let Some(view) = app.dry_run.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                view.selected = (view.selected + 1).min(view.steps.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                view.selected = view.selected.saturating_sub(1);
+            }
+            KeyCode::Char(c @ ('y' | 'Y')) => {
+                let (label, text) = if c == 'y' {
+                    let (step, command) = &view.steps[view.selected];
+                    (format!("{step} command"), command.clone())
+                } else {
+                    let script: Vec<String> = view
+                        .steps
+                        .iter()
+                        .map(|(step, command)| format!("# {step}\n{command}\n"))
+                        .collect();
+                    ("all commands".to_string(), script.join("\n"))
+                };
+                match clipboard::copy(&text) {
+                    Ok(()) => app.set_status(format!("Copied {label}"), StatusLevel::Info),
+                    Err(e) => app.set_status(format!("Failed to copy: {e}"), StatusLevel::Error),
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.dry_run = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Symbol outline: typed keys narrow it, Enter jumps (stays hardcoded).
+    if let Some(view) = app.outline.as_mut() {
+        match key.code {
+            KeyCode::Down => {
+                view.selected = (view.selected + 1).min(view.visible().len().saturating_sub(1));
+            }
+            KeyCode::Up => view.selected = view.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                view.query.pop();
+                view.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                view.query.push(c);
+                view.selected = 0;
+            }
+            KeyCode::Enter => {
+                if let Some(symbol) = view.selected_symbol() {
+                    app.browser.file_scroll = u16::try_from(symbol.line).unwrap_or(u16::MAX);
+                    app.browser.file_wrap.offset = 0;
+                    app.browser.browser_focus = BrowserPanel::FileContent;
+                    app.outline = None;
+                }
+            }
+            KeyCode::Esc => app.outline = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Version matrix overlay: pick versions to diff (stays hardcoded).
+    if app.show_matrix
+        && let Some(view) = app.matrix.as_mut()
+    {
+        match key.code {
+            KeyCode::Char('h') | KeyCode::Left => view.move_column(false),
+            KeyCode::Char('l') | KeyCode::Right => view.move_column(true),
+            KeyCode::Char(' ') => view.base = view.column,
+            KeyCode::Enter => compare_matrix(app),
+            KeyCode::Char('r') if !view.is_running() => start_matrix(app),
+            KeyCode::Esc | KeyCode::Char('q') if view.is_running() => {
+                view.cancel.cancel();
+                app.set_status("Cancelling version matrix...", StatusLevel::Info);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.show_matrix = false,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Project switcher (stays hardcoded).
+    if let Some(selected) = app.project_switcher.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                *selected = (*selected + 1).min(app.projects.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => *selected = selected.saturating_sub(1),
+            KeyCode::Enter => {
+                let index = *selected;
+                app.project_switcher = None;
+                switch_project(app, index);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.project_switcher = None,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Workspace overlay (stays hardcoded).
+    if app.show_workspace
+        && let Some(view) = app.workspace.as_mut()
+    {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => view.scroll = view.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Char('r') if !view.is_running() => start_workspace(app),
+            KeyCode::Esc | KeyCode::Char('q') if view.is_running() => {
+                view.cancel.cancel();
+                app.set_status("Cancelling workspace run...", StatusLevel::Info);
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.show_workspace = false,
+            _ => {}
+        }
+        return Action::None;
+    }
+
+    // Score breakdown: any dismiss key closes it (stays hardcoded).
+    if app.show_score {
+        if matches!(
+            key.code,
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | '%')
+        ) {
+            app.show_score = false;
+        }
+        return Action::None;
+    }
+
+    // Onboarding tour: next/back or skip (stays hardcoded).
+    if app.tour.is_some() {
+        handle_tour_key(app, key);
+        return Action::None;
+    }
+
+    // Help overlay: any key dismisses it (stays hardcoded).
+    if app.show_help {
+        app.show_help = false;
+        return Action::None;
+    }
+
+    // Copy prefix: the next key picks what to copy (stays hardcoded).
+    if app.copy_pending {
+        app.copy_pending = false;
+        app.status_message = None;
+        if let KeyCode::Char(c) = key.code {
+            copy_to_clipboard(app, c);
+        }
+        return Action::None;
+    }
+
+    // Go-to-line prompt: digits, Enter jumps (stays hardcoded).
+    if app.goto_line.is_some() {
+        handle_goto_line_key(app, key);
+        return Action::None;
+    }
+
+    // File filter prompt: typed keys edit the globs (stays hardcoded).
+    if app.browser.filter_input.is_some() {
+        handle_file_filter_key(app, key);
+        return Action::None;
+    }
+
+    // Search prompts: typed keys edit the query (stays hardcoded).
+    if app.spec_search.as_ref().is_some_and(|s| s.editing) {
+        handle_spec_search_key(app, key);
+        return Action::None;
+    }
+    if app.log_search.as_ref().is_some_and(|s| s.editing) {
+        handle_log_search_key(app, key);
+        return Action::None;
+    }
+
+    // Clear transient status on any keypress.
+    app.status_message = None;
+
+    let input = KeyInput::from_event(key);
+    if trace::enabled(Target::Ui) {
+        let actions: Vec<&str> = app
+            .keymap
+            .actions_for(&input)
+            .iter()
+            .map(|a| a.config_name())
+            .collect();
+        trace::log(
+            Target::Ui,
+            format_args!(
+                "{} in {:?}/{:?}: {}",
+                input.display(),
+                app.view_mode,
+                app.focused_panel,
+                if actions.is_empty() {
+                    "unbound".to_string()
+                } else {
+                    actions.join(", ")
+                }
+            ),
+        );
+    }
+    let has = |a: KeyAction| app.keymap.has_action(&input, a);
+
+    // Dispatch priority: when one key maps to multiple actions, the first
+    // matching branch wins. Order: global → view-specific → panel nav → panel content.
+    // This is intentional — context (view mode, focused panel) resolves ambiguity.
+    if has(KeyAction::Quit) {
+        app.running = false;
+        return Action::None;
+    }
+    if has(KeyAction::Help) {
+        app.show_help = true;
+        return Action::None;
+    }
+    if has(KeyAction::ExpandLayout) {
+        app.screen_mode = app.screen_mode.cycle_next();
+        return Action::None;
+    }
+    if has(KeyAction::ShrinkLayout) {
+        app.screen_mode = app.screen_mode.cycle_prev();
+        return Action::None;
+    }
+    if has(KeyAction::RunValidation) {
+        start_pipeline(app);
+        return Action::None;
+    }
+    if has(KeyAction::CancelValidation) && app.validating {
+        if let Some(token) = &app.cancel_token {
+            token.cancel();
+        }
+        return Action::None;
+    }
+    if has(KeyAction::ShowDiagnostics) {
+        if app.config_diagnostics.is_empty() {
+            app.set_status("No config problems found", StatusLevel::Info);
+        } else {
+            app.show_diagnostics = true;
+            app.diagnostics_scroll = 0;
+        }
+        return Action::None;
+    }
+    if has(KeyAction::ExportShare) {
+        export_share(app);
+        return Action::None;
+    }
+    if has(KeyAction::StartTour) {
+        start_tour(app);
+        return Action::None;
+    }
+    if has(KeyAction::ShowChangelog) {
+        show_changelog(app);
+        return Action::None;
+    }
+    if has(KeyAction::ShowScore) {
+        if app.score.is_some() {
+            app.show_score = true;
+        } else {
+            app.set_status(
+                "No score yet \u{2014} run validation first",
+                StatusLevel::Info,
+            );
+        }
+        return Action::None;
+    }
+    if has(KeyAction::ShowRuleStats) {
+        show_rule_stats(app);
+        return Action::None;
+    }
+    if has(KeyAction::ShowRuns) {
+        show_run_history(app);
+        return Action::None;
+    }
+    if has(KeyAction::CompareReports) {
+        compare_with_previous_report(app);
+        return Action::None;
+    }
+    if has(KeyAction::DryRun) {
+        show_dry_run(app);
+        return Action::None;
+    }
+    if has(KeyAction::RunMatrix) {
+        if app.matrix.is_some() {
+            app.show_matrix = true;
+        } else {
+            start_matrix(app);
+        }
+        return Action::None;
+    }
+    if has(KeyAction::SwitchProject) {
+        if app.projects.is_empty() {
+            app.set_status("No other projects found", StatusLevel::Info);
+        } else {
+            app.project_switcher = Some(app.project.unwrap_or(0));
+        }
+        return Action::None;
+    }
+    if has(KeyAction::RunWorkspace) {
+        if app.workspace.is_some() {
+            app.show_workspace = true;
+        } else {
+            start_workspace(app);
+        }
+        return Action::None;
+    }
+    if has(KeyAction::ShowReferences) {
+        show_references(app);
+        return Action::None;
+    }
+    if has(KeyAction::ShowSchema) {
+        show_schema(app);
+        return Action::None;
+    }
+    if has(KeyAction::OpenDocs) {
+        open_docs(app);
+        return Action::None;
+    }
+    if has(KeyAction::Copy) {
+        app.copy_pending = true;
+        app.set_status(
+            "Copy: [y] error  [p] pointer  [s] snippet  [f] file path",
+            StatusLevel::Info,
+        );
+        return Action::None;
+    }
+    if has(KeyAction::CreateSupportBundle) {
+        create_support_bundle(app);
+        return Action::None;
+    }
+    if has(KeyAction::CleanGenerated) {
+        prompt_clean_generated(app);
+        return Action::None;
+    }
+    if has(KeyAction::MigrateSpec) {
+        prompt_migrate_spec(app);
+        return Action::None;
+    }
+    if has(KeyAction::OpenGeneratorPicker) {
+        let cfg = app.config.clone().unwrap_or_default();
+        app.generator_picker = Some(GeneratorPickerState::new(&cfg, &app.custom_defs));
+        return Action::None;
+    }
+    if has(KeyAction::ToggleView) {
+        match app.view_mode {
+            ViewMode::Validator => {
+                sync_generators_from_report(app);
+                app::browser::refresh_file_tree(&mut app.browser, &app.work_dir);
+                app::browser::start_usage_scan(&mut app.browser, &app.work_dir);
+                app.view_mode = ViewMode::CodeBrowser;
+            }
+            ViewMode::CodeBrowser => {
+                app.view_mode = ViewMode::Validator;
+            }
+        }
+        return Action::None;
+    }
+
+    // Early return for browser-specific keys.
+    if app.view_mode == ViewMode::CodeBrowser {
         return handle_browser_key(app, input);
     }
 
+    if has(KeyAction::ToggleSuppressed) {
+        app.show_suppressed = !app.show_suppressed;
+        app.clamp_indices();
+        let hidden = app.suppressed_lint_count + app.suppressed_security_count;
+        app.set_status(
+            if app.show_suppressed {
+                format!("Showing {hidden} suppressed finding(s)")
+            } else {
+                format!("Hiding {hidden} suppressed finding(s)")
+            },
+            StatusLevel::Info,
+        );
+        return Action::None;
+    }
+
+    if has(KeyAction::ToggleChangedOnly) {
+        toggle_changed_only(app);
+        return Action::None;
+    }
+
+    if has(KeyAction::SnapshotBaseline) {
+        snapshot_baseline(app);
+        return Action::None;
+    }
+
+    if has(KeyAction::FormatSpec) {
+        format_spec(app);
+        return Action::None;
+    }
+
+    if has(KeyAction::FreeNavigate) {
+        if app.active_spec_cursor().is_some() {
+            app.spec_cursor = None;
+            app.spec_scroll = 0;
+        } else if let Some(line) = app.spec_target_line() {
+            app.focused_panel = Panel::SpecContext;
+            app.move_spec_cursor(line);
+        } else if app.spec_index.is_some() {
+            app.focused_panel = Panel::SpecContext;
+            app.move_spec_cursor(1);
+        } else {
+            app.set_status("No spec loaded", StatusLevel::Info);
+        }
+        return Action::None;
+    }
+    if has(KeyAction::PinSpec) {
+        toggle_spec_pin(app);
+        return Action::None;
+    }
+    if has(KeyAction::GotoLine) {
+        if app.spec_index.is_some() {
+            app.focused_panel = Panel::SpecContext;
+            app.goto_line = Some(String::new());
+        } else {
+            app.set_status("No spec loaded", StatusLevel::Info);
+        }
+        return Action::None;
+    }
+    if has(KeyAction::Search) {
+        if app.focused_panel == Panel::Detail && app.detail_tab == 1 {
+            app.log_search = Some(TextSearch::new((app.phase_index, 0)));
+        } else if app.spec_index.is_some() {
+            app.focused_panel = Panel::SpecContext;
+            app.spec_search = Some(TextSearch::new((app.phase_index, app.error_index)));
+        } else {
+            app.set_status("No spec loaded", StatusLevel::Info);
+        }
+        return Action::None;
+    }
+
+    // With wrap off, h/l scroll the Raw Log tab sideways before they switch
+    // panels.
+    if app.focused_panel == Panel::Detail && app.detail_tab == 1 {
+        if has(KeyAction::ToggleWrap) {
+            app.log_wrap.toggle();
+            return Action::None;
+        }
+        let right = has(KeyAction::ScrollRight);
+        if (right || has(KeyAction::ScrollLeft)) && app.log_wrap.scroll(right) {
+            return Action::None;
+        }
+    }
+
     // Panel switching.
     if has(KeyAction::NextPanel) {
         app.focused_panel = app.focused_panel.next();
@@ -400,6 +1679,10 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                 app.spec_scroll = 0;
             } else if has(KeyAction::Select) {
                 app.focused_panel = Panel::Errors;
+            } else if has(KeyAction::OpenShell) {
+                return shell_action(app);
+            } else if has(KeyAction::OpenLog) {
+                return log_file_action(app);
             }
         }
         Panel::Errors => {
@@ -429,18 +1712,26 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                 app.detail_scroll = 0;
                 app.spec_scroll = 0;
             } else if has(KeyAction::Select) || has(KeyAction::FocusDetail) {
-                app.focused_panel = Panel::Detail;
+                // A code lint finding shows in the generated file it's in.
+                if !(has(KeyAction::Select) && show_code_finding(app)) {
+                    app.focused_panel = Panel::Detail;
+                }
             } else if has(KeyAction::OpenEditor) {
                 let Some(error) = app.selected_error() else {
                     app.set_status("No error selected", StatusLevel::Info);
                     return Action::None;
                 };
-                let line = error.line;
+                // Redocly findings carry only a path; resolve it to a line.
+                let (line, col) = app.error_spec_position(error).unwrap_or((1, 1));
                 let Some(path) = app.spec_path.clone() else {
                     app.set_status("No spec file found", StatusLevel::Error);
                     return Action::None;
                 };
-                return Action::OpenEditor { path, line };
+                return Action::OpenEditor { path, line, col };
+            } else if has(KeyAction::EditInline) {
+                start_inline_edit(app);
+            } else if has(KeyAction::SuppressFinding) {
+                suppress_selected_error(app);
             } else if has(KeyAction::ProposeFix) {
                 let Some(error) = app.selected_error().cloned() else {
                     app.set_status("No error selected", StatusLevel::Info);
@@ -454,9 +1745,16 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                     app.set_status("No spec file found", StatusLevel::Error);
                     return Action::None;
                 };
-                match fix::propose_fix(&error, spec_index, spec_path) {
+                let cfg = app.config.clone().unwrap_or_default();
+                match fix::propose_fix(&error, spec_index, spec_path, &cfg) {
                     Ok(Some(proposal)) => {
-                        app.fix_proposal = Some(proposal);
+                        app.open_fix(proposal, Some(FixOrigin::Finding(error)));
+                    }
+                    Ok(None) if app.plugins.handles(&error.rule) => {
+                        start_plugin_fix(app, error);
+                    }
+                    Ok(None) if has_fix_assistant(app) => {
+                        start_fix_assist(app, error);
                     }
                     Ok(None) => {
                         app.set_status(
@@ -465,13 +1763,38 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                         );
                     }
                     Err(e) => {
-                        app.set_status(format!("Failed to read spec: {e}"), StatusLevel::Error);
+                        app.set_status(
+                            format!("Failed to propose a fix: {e:#}"),
+                            StatusLevel::Error,
+                        );
                     }
                 }
             }
         }
         Panel::Detail => {
-            if has(KeyAction::ScrollDown) {
+            let raw_log = app.detail_tab == 1;
+            if raw_log && app.active_log_search().is_some() && key.code == KeyCode::Esc {
+                app.log_search = None;
+            } else if raw_log
+                && app.active_log_search().is_some()
+                && (has(KeyAction::NextLineFinding) || has(KeyAction::PrevLineFinding))
+            {
+                let forward = has(KeyAction::NextLineFinding);
+                // The live log may have grown since the last look.
+                refresh_log_search(app, usize::from(app.detail_scroll) + 1);
+                if let Some(search) = app.log_search.as_mut() {
+                    search.step(forward);
+                }
+                follow_log_match(app);
+            } else if raw_log && has(KeyAction::ToggleLogErrorsOnly) {
+                app.log_filter.errors_only = !app.log_filter.errors_only;
+                app.detail_scroll = 0;
+                refresh_log_search(app, 1);
+            } else if raw_log && has(KeyAction::ToggleLogNoise) {
+                app.log_filter.hide_noise = !app.log_filter.hide_noise;
+                app.detail_scroll = 0;
+                refresh_log_search(app, 1);
+            } else if has(KeyAction::ScrollDown) {
                 app.detail_scroll = app.detail_scroll.saturating_add(1);
             } else if has(KeyAction::ScrollUp) {
                 app.detail_scroll = app.detail_scroll.saturating_sub(1);
@@ -484,13 +1807,57 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
             } else if has(KeyAction::PageDown) || has(KeyAction::HalfPageDown) {
                 app.detail_scroll = app.detail_scroll.saturating_add(20);
             } else if has(KeyAction::NextDetailTab) {
-                app.detail_tab = (app.detail_tab + 1) % 3;
+                app.detail_tab = (app.detail_tab + 1) % 4;
             } else if has(KeyAction::PrevDetailTab) {
-                app.detail_tab = (app.detail_tab + 2) % 3;
+                app.detail_tab = (app.detail_tab + 3) % 4;
+            } else if has(KeyAction::ToggleLogTimes) {
+                app.show_log_times = !app.show_log_times;
+            } else if has(KeyAction::OpenLog) {
+                return log_file_action(app);
             }
         }
         Panel::SpecContext => {
-            if has(KeyAction::ScrollDown) {
+            let searching = app
+                .spec_search
+                .as_ref()
+                .is_some_and(|s| s.anchor == (app.phase_index, app.error_index));
+            let cursor = app.active_spec_cursor();
+            if searching && key.code == KeyCode::Esc {
+                app.spec_search = None;
+                app.spec_scroll = 0;
+            } else if cursor.is_some() && key.code == KeyCode::Esc {
+                app.spec_cursor = None;
+                app.spec_scroll = 0;
+            } else if searching
+                && (has(KeyAction::NextLineFinding) || has(KeyAction::PrevLineFinding))
+            {
+                let forward = has(KeyAction::NextLineFinding);
+                if let Some(search) = app.spec_search.as_mut() {
+                    search.step(forward);
+                    if search.matches.is_empty() {
+                        app.set_status("No matches", StatusLevel::Info);
+                    }
+                }
+                follow_search_match(app);
+            } else if let Some(cursor) = cursor {
+                let last = app.spec_index.as_ref().map_or(1, |i| i.line_count());
+                let line = if has(KeyAction::ScrollDown) {
+                    cursor.line + 1
+                } else if has(KeyAction::ScrollUp) {
+                    cursor.line.saturating_sub(1)
+                } else if has(KeyAction::JumpFirst) {
+                    1
+                } else if has(KeyAction::JumpLast) {
+                    last
+                } else if has(KeyAction::PageUp) || has(KeyAction::HalfPageUp) {
+                    cursor.line.saturating_sub(20)
+                } else if has(KeyAction::PageDown) || has(KeyAction::HalfPageDown) {
+                    cursor.line + 20
+                } else {
+                    return Action::None;
+                };
+                app.move_spec_cursor(line);
+            } else if has(KeyAction::ScrollDown) {
                 app.spec_scroll = app.spec_scroll.saturating_add(1);
             } else if has(KeyAction::ScrollUp) {
                 app.spec_scroll = app.spec_scroll.saturating_sub(1);
@@ -502,48 +1869,1735 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Action {
                 app.spec_scroll = app.spec_scroll.saturating_sub(20);
             } else if has(KeyAction::PageDown) || has(KeyAction::HalfPageDown) {
                 app.spec_scroll = app.spec_scroll.saturating_add(20);
+            } else if has(KeyAction::NextLineFinding) || has(KeyAction::PrevLineFinding) {
+                let forward = has(KeyAction::NextLineFinding);
+                if !app.cycle_line_finding(forward) {
+                    app.set_status("No other findings on this line", StatusLevel::Info);
+                }
+            }
+        }
+    }
+
+    Action::None
+}
+
+fn handle_picker_key(app: &mut App, key: KeyEvent) {
+    let Some(picker) = app.generator_picker.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => picker.move_by(1),
+        KeyCode::Char('k') | KeyCode::Up => picker.move_by(-1),
+        KeyCode::Char(' ') => picker.toggle_selected(),
+        KeyCode::Char('L') => start_generator_listing(app),
+        KeyCode::Enter => save_generator_picker(app),
+        KeyCode::Esc | KeyCode::Char('q') => app.generator_picker = None,
+        _ => {}
+    }
+}
+
+fn handle_wizard_key(app: &mut App, key: KeyEvent) {
+    let Some(wizard) = app.init_wizard.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => wizard.move_by(1),
+        KeyCode::Char('k') | KeyCode::Up => wizard.move_by(-1),
+        KeyCode::Char(' ') => wizard.toggle(),
+        KeyCode::Backspace | KeyCode::Left => {
+            wizard.back();
+        }
+        KeyCode::Enter if wizard.step == WizardStep::Review => finish_init_wizard(app),
+        KeyCode::Enter => {
+            if let Err(e) = wizard.next() {
+                app.set_status(e, StatusLevel::Warn);
+            }
+        }
+        KeyCode::Esc => {
+            app.init_wizard = None;
+            app.set_status(
+                "Skipped setup \u{2014} running with default config",
+                StatusLevel::Info,
+            );
+            start_held_validation(app);
+        }
+        _ => {}
+    }
+}
+
+/// Write the redacted share bundle for the current spec and last run.
+fn export_share(app: &mut App) {
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec to export", StatusLevel::Warn);
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    let cfg = app.config.clone().unwrap_or_default();
+    match share::export_bundle(&cwd, &spec_path, &cfg.redact) {
+        Ok(summary) => app.set_status(
+            format!(
+                "Exported {} files to {} ({} values redacted)",
+                summary.files,
+                share::SHARE_DIR,
+                summary.redactions
+            ),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(format!("Export failed: {e}"), StatusLevel::Error),
+    }
+}
+
+/// List only the findings on spec lines changed since `changelog_base`, or
+/// since the run before when git has no such version of the spec; or list
+/// all of them again.
+fn toggle_changed_only(app: &mut App) {
+    if app.changed.take().is_some() {
+        app.clamp_indices();
+        app.set_status("Showing all findings", StatusLevel::Info);
+        return;
+    }
+    if !narrow_to_changed(app, true) {
+        return;
+    }
+    let Some(changed) = &app.changed else {
+        return;
+    };
+    let lines: usize = changed.ranges.iter().map(|range| range.len()).sum();
+    let message = format!(
+        "Showing findings on {lines} line(s) changed since {}",
+        changed.base
+    );
+    app.set_status(message, StatusLevel::Info);
+}
+
+/// Take the spec's changed lines afresh and narrow the findings to them.
+/// The base is read from git again with `reread`, and otherwise taken from
+/// the last read of the same revision. Returns `false`, with the reason in
+/// the status bar, when there's no base to compare with.
+fn narrow_to_changed(app: &mut App, reread: bool) -> bool {
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec to compare", StatusLevel::Warn);
+        return false;
+    };
+    let current = match std::fs::read_to_string(&spec_path) {
+        Ok(current) => current,
+        Err(e) => {
+            let message = format!("Failed to read {}: {e}", spec_path.display());
+            app.set_status(message, StatusLevel::Error);
+            return false;
+        }
+    };
+    let base = app
+        .config
+        .as_ref()
+        .map_or_else(|| "HEAD".to_string(), |c| c.changelog_base.clone());
+    let cached = app
+        .base_spec
+        .as_ref()
+        .filter(|cached| !reread && cached.spec == spec_path && cached.revision == base);
+    let contents = match cached {
+        Some(cached) => cached.contents.clone(),
+        None => {
+            let contents =
+                changelog::read_at_revision(&spec_path, &base).map_err(|e| format!("{e:#}"));
+            app.base_spec = Some(app::BaseSpec {
+                spec: spec_path.clone(),
+                revision: base.clone(),
+                contents: contents.clone(),
+            });
+            contents
+        }
+    };
+    let (base, old) = match contents {
+        Ok(old) => (base, old),
+        Err(e) => match regions::previous_run_spec(&app.work_dir) {
+            Some(old) => ("last run".to_string(), old),
+            None => {
+                app.set_status(
+                    format!("Nothing to compare the spec with: {e}"),
+                    StatusLevel::Warn,
+                );
+                return false;
+            }
+        },
+    };
+    app.narrow_to_changed(base, regions::changed_ranges(&old, &current));
+    app.clamp_indices();
+    true
+}
+
+/// Compare the spec with its version at `changelog_base` and show the
+/// changes.
+fn show_changelog(app: &mut App) {
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec to compare", StatusLevel::Warn);
+        return;
+    };
+    let base = app
+        .config
+        .as_ref()
+        .map_or_else(|| "HEAD".to_string(), |c| c.changelog_base.clone());
+    let result = changelog::read_at_revision(&spec_path, &base).and_then(|old| {
+        let current = std::fs::read_to_string(&spec_path)
+            .with_context(|| format!("Failed to read {}", spec_path.display()))?;
+        Changelog::between_sources(&old, &current)
+    });
+    match result {
+        Ok(changelog) => {
+            app.changelog = Some(app::ChangelogView {
+                base,
+                changelog,
+                scroll: 0,
+            });
+        }
+        Err(e) => app.set_status(format!("Changelog failed: {e:#}"), StatusLevel::Error),
+    }
+}
+
+/// Show the selected finding's enclosing schema with `$ref`s expanded and
+/// `allOf` merged.
+fn show_schema(app: &mut App) {
+    let Some(pointer) = app
+        .selected_error()
+        .and_then(|e| e.json_path.as_deref())
+        .map(spec::normalize_to_pointer)
+    else {
+        app.set_status("Select a finding with a spec path", StatusLevel::Info);
+        return;
+    };
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec file found", StatusLevel::Error);
+        return;
+    };
+    let parsed = std::fs::read_to_string(&spec_path)
+        .with_context(|| format!("Failed to read {}", spec_path.display()))
+        .and_then(|raw| serde_yaml::from_str::<serde_yaml::Value>(&raw).map_err(Into::into));
+    let root = match parsed {
+        Ok(root) => root,
+        Err(e) => {
+            app.set_status(format!("Cannot parse spec: {e:#}"), StatusLevel::Error);
+            return;
+        }
+    };
+    match spec::enclosing_schema(&root, &pointer) {
+        Some((pointer, schema)) => {
+            let resolved = spec::resolve_schema(&root, schema);
+            let payload =
+                serde_json::to_string_pretty(&spec::sample_payload(&resolved)).unwrap_or_default();
+            app.schema_view = Some(app::SchemaView {
+                pointer,
+                tree: spec::render_tree(&resolved),
+                payload: payload.lines().map(str::to_string).collect(),
+                show_payload: false,
+                scroll: 0,
+            });
+        }
+        None => app.set_status(format!("No schema at {pointer}"), StatusLevel::Info),
+    }
+}
+
+/// Open the run history overlay, if there are two runs to compare.
+fn show_run_history(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    match runs::list(&cwd) {
+        Ok(runs) if runs.len() >= 2 => {
+            app.run_history = Some(app::RunHistoryView {
+                runs,
+                selected: 0,
+                base: None,
+            });
+        }
+        Ok(runs) => app.set_status(
+            format!(
+                "{} recorded run(s) \u{2014} generate at least twice to compare",
+                runs.len()
+            ),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(format!("Failed to read runs: {e:#}"), StatusLevel::Error),
+    }
+}
+
+/// The runs picked in the run history, older first: the selected run and the
+/// marked one, or the run before it.
+fn history_pair(app: &mut App) -> Option<(runs::RunRecord, runs::RunRecord)> {
+    let view = app.run_history.as_ref()?;
+    let selected = &view.runs[view.selected];
+    let other = match view.base {
+        Some(base) if base != view.selected => &view.runs[base],
+        Some(_) => {
+            app.set_status("Select a run other than the marked one", StatusLevel::Info);
+            return None;
+        }
+        None => match view.runs.get(view.selected + 1) {
+            Some(previous) => previous,
+            None => {
+                app.set_status(
+                    "No earlier run \u{2014} mark one with Space",
+                    StatusLevel::Info,
+                );
+                return None;
+            }
+        },
+    };
+    Some(if other.timestamp < selected.timestamp {
+        (other.clone(), selected.clone())
+    } else {
+        (selected.clone(), other.clone())
+    })
+}
+
+/// Diff the generated output of the selected run against the marked one (or
+/// the run before it) and show it in the code browser's diff view.
+fn compare_runs(app: &mut App) {
+    let Some((older, newer)) = history_pair(app) else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    let diffs = app::diff::compare_runs(&cwd, &older, &newer);
+    if diffs.is_empty() {
+        app.set_status(
+            "No differences in generated output between these runs",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let label = format!("{} \u{2192} {}", older.age(now), newer.age(now));
+    let mut keys: Vec<String> = diffs.keys().cloned().collect();
+    keys.sort_unstable();
+
+    app.run_history = None;
+    app.browser.diff_state = DiffViewState {
+        diffs,
+        active: true,
+        active_generator: keys.into_iter().next(),
+        label: Some(label),
+        ..DiffViewState::new()
+    };
+    sync_generators_from_report(app);
+    app::browser::refresh_file_tree(&mut app.browser, &cwd);
+    app.view_mode = ViewMode::CodeBrowser;
+}
+
+/// Compare the reports of the runs picked in the run history.
+fn compare_run_reports(app: &mut App) {
+    let Some((older, newer)) = history_pair(app) else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    let (Some(before), Some(after)) = (older.report(&cwd), newer.report(&cwd)) else {
+        app.set_status(
+            "No report recorded for that run \u{2014} it predates report history",
+            StatusLevel::Info,
+        );
+        return;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let label = format!("{} \u{2192} {}", older.age(now), newer.age(now));
+    app.run_history = None;
+    app.comparison = Some(ReportComparison::new(label, &before, &after));
+}
+
+/// Compare the last report with the newest recorded run before it. The
+/// report on disk is used rather than `app.report`, whose lint status the
+/// severity overrides may have changed, so the run it came from is skipped.
+fn compare_with_previous_report(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    let Some(current) = reports::load(&cwd) else {
+        app.set_status(
+            "No report yet \u{2014} run validation first",
+            StatusLevel::Info,
+        );
+        return;
+    };
+    let runs = match runs::list(&cwd) {
+        Ok(runs) => runs,
+        Err(e) => {
+            app.set_status(format!("Failed to read runs: {e:#}"), StatusLevel::Error);
+            return;
+        }
+    };
+    let current_json = serde_json::to_value(&current).ok();
+    let previous = runs.iter().find_map(|run| {
+        let report = run.report(&cwd)?;
+        (serde_json::to_value(&report).ok() != current_json).then_some((run, report))
+    });
+    let Some((run, before)) = previous else {
+        app.set_status(
+            "No earlier report recorded to compare with",
+            StatusLevel::Info,
+        );
+        return;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let label = format!("{} \u{2192} last run", run.age(now));
+    app.comparison = Some(ReportComparison::new(label, &before, &current));
+}
+
+/// Show what references the selected finding's component or operation, and
+/// what it references.
+fn show_references(app: &mut App) {
+    let Some(node) = app
+        .selected_error()
+        .and_then(|e| e.json_path.as_deref())
+        .map(spec::normalize_to_pointer)
+        .and_then(|pointer| Node::at_pointer(&pointer))
+    else {
+        app.set_status(
+            "Select a finding inside a component or operation",
+            StatusLevel::Info,
+        );
+        return;
+    };
+    let Some(root) = load_spec_value(app) else {
+        app.set_status("Cannot parse spec", StatusLevel::Error);
+        return;
+    };
+    let graph = RefGraph::build(&root);
+    let labels = |nodes: Vec<(usize, Node)>| {
+        nodes
+            .into_iter()
+            .map(|(depth, node)| (depth, node.to_string()))
+            .collect()
+    };
+    app.ref_graph = Some(app::RefGraphView {
+        subject: node.to_string(),
+        used_by: labels(graph.used_by(&node)),
+        uses: labels(graph.uses(&node)),
+        scroll: 0,
+    });
+}
+
+/// Score the finished run and append it to the score history.
+fn record_score(app: &mut App) {
+    let (Some(report), Some(spec)) = (&app.report, load_spec_value(app)) else {
+        return;
+    };
+    let mut lint = score::LintCounts::default();
+    for err in &app.lint_errors[..app.suppressed_start()] {
+        match err.severity {
+            log_parser::Severity::Error => lint.errors += 1,
+            log_parser::Severity::Warning => lint.warnings += 1,
+            _ => {}
+        }
+    }
+    let lines = app
+        .spec_index
+        .as_ref()
+        .map_or(0, |index| index.line_count());
+    let current = Score::compute(&spec, lines, lint, report);
+
+    let cwd = app.work_dir.clone();
+    let mut history: Vec<u8> = score::load_history(&cwd)
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| entry.score.total)
+        .collect();
+    history.push(current.total);
+    if let Err(e) = score::append_history(&cwd, &current) {
+        app.set_status(format!("Failed to record score: {e:#}"), StatusLevel::Warn);
+    }
+    app.score_history = history;
+    app.score = Some(current);
+}
+
+/// Append the finished run's lint findings to the rule history. Runs
+/// without a lint phase have nothing to add.
+fn record_rule_history(app: &mut App) {
+    if app.report.as_ref().is_none_or(|r| r.phases.lint.is_none()) {
+        return;
+    }
+    let cwd = app.work_dir.clone();
+    if let Err(e) = stats::append_history(&cwd, app.unsuppressed_findings()) {
+        app.set_status(
+            format!("Failed to record rule history: {e:#}"),
+            StatusLevel::Warn,
+        );
+    }
+}
+
+/// Open the rule statistics overlay over the recorded rule history.
+fn show_rule_stats(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    match stats::load_history(&cwd) {
+        Ok(history) if history.is_empty() => app.set_status(
+            "No rule history yet \u{2014} run validation first",
+            StatusLevel::Info,
+        ),
+        Ok(history) => {
+            app.rule_stats = Some(app::RuleStatsView {
+                stats: stats::compute(&history),
+                scroll: 0,
+            });
+        }
+        Err(e) => app.set_status(format!("{e:#}"), StatusLevel::Error),
+    }
+}
+
+/// The spec parsed as a YAML value, or `None` if it can't be read.
+fn load_spec_value(app: &App) -> Option<serde_yaml::Value> {
+    let raw = std::fs::read_to_string(app.spec_path.as_ref()?).ok()?;
+    serde_yaml::from_str(&raw).ok()
+}
+
+/// Syntax errors and schema violations from the structure check. Syntax
+/// errors carry their position, violations the pointer of what's wrong.
+fn structure_findings(report: &pipeline::ValidateReport) -> Vec<log_parser::LintError> {
+    let findings = report.phases.structure.iter().flat_map(|s| &s.findings);
+    findings
+        .map(|f| log_parser::LintError {
+            line: f.line,
+            col: f.col,
+            end: None,
+            severity: if f.is_syntax_error() {
+                log_parser::Severity::Error
+            } else {
+                log_parser::Severity::Warning
+            },
+            rule: if f.pointer.is_some() {
+                "structure"
+            } else {
+                "syntax"
+            }
+            .to_string(),
+            message: f.message.clone(),
+            json_path: f.pointer.clone(),
+        })
+        .collect()
+}
+
+/// Findings of the security phase, parsed like Spectral lint output.
+fn security_findings(report: &pipeline::ValidateReport) -> Vec<log_parser::LintError> {
+    report
+        .phases
+        .security
+        .as_ref()
+        .map(|security| log_parser::parse_lint_output(&security.log))
+        .unwrap_or_default()
+}
+
+/// Failed tests of each test step, listed as errors under the step.
+fn test_findings(report: &pipeline::ValidateReport) -> Vec<Vec<log_parser::LintError>> {
+    let steps = report.phases.test.iter().flatten();
+    steps
+        .map(|step| {
+            log_parser::failing_tests(&step.log)
+                .into_iter()
+                .map(|name| log_parser::LintError {
+                    line: 0,
+                    col: 0,
+                    end: None,
+                    severity: log_parser::Severity::Error,
+                    rule: "failed-test".to_string(),
+                    message: name,
+                    json_path: None,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Findings of each code lint step: listed as errors under the step, and
+/// kept for the code browser to mark in the generated files.
+fn set_code_findings(app: &mut App, report: &pipeline::ValidateReport) {
+    app.code_lint_errors.clear();
+    app.browser.code_findings.clear();
+    for step in report.phases.code_lint.iter().flatten() {
+        let findings = log_parser::parse_code_lint(&step.log);
+        let errors = findings
+            .iter()
+            .map(|f| log_parser::LintError {
+                line: 0,
+                col: 0,
+                end: None,
+                severity: f.severity,
+                rule: if f.rule.is_empty() {
+                    "code-lint".to_string()
+                } else {
+                    f.rule.clone()
+                },
+                message: format!("{}:{}: {}", f.file, f.line, f.message),
+                json_path: None,
+            })
+            .collect();
+        app.code_lint_errors.push(errors);
+        let key = format!("{}/{}", step.scope, step.generator);
+        app.browser.code_findings.insert(key, findings);
+    }
+}
+
+/// Findings from the local spec analyses, shown with the lint results:
+/// constructs that break the configured generators and definitions nothing
+/// references as `info`, names breaking the configured conventions and
+/// operationIds generators would merge as warnings, and duplicate
+/// operationIds as errors. Only the linter's own findings decide whether
+/// the lint phase fails, so none of these fail the run.
+fn analysis_findings(app: &App) -> Vec<log_parser::LintError> {
+    let Some(root) = load_spec_value(app) else {
+        return Vec::new();
+    };
+    let info = |rule: &str, message: String, pointer: String| log_parser::LintError {
+        line: 0,
+        col: 0,
+        end: None,
+        severity: log_parser::Severity::Info,
+        rule: rule.to_string(),
+        message,
+        json_path: Some(pointer),
+    };
+
+    let mut findings = Vec::new();
+    if let Some(cfg) = &app.config {
+        let mut generators = Vec::new();
+        if cfg.mode != config::Mode::Client {
+            generators.extend(cfg.server_generators.iter().cloned());
+        }
+        if cfg.mode != config::Mode::Server {
+            generators.extend(cfg.client_generators.iter().cloned());
+        }
+        findings.extend(pitfalls::analyze(&root, &generators).into_iter().map(|p| {
+            let message = format!("{} (affects {})", p.message, p.generators.join(", "));
+            info(p.rule, message, p.pointer)
+        }));
+        findings.extend(naming::check(&root, &cfg.naming).into_iter().map(|v| {
+            log_parser::LintError {
+                severity: log_parser::Severity::Warning,
+                ..info(v.rule, v.message, v.pointer)
+            }
+        }));
+    }
+    findings.extend(
+        orphans::find(&root)
+            .into_iter()
+            .map(|o| info(o.rule, o.message, o.pointer)),
+    );
+    // Generators refuse duplicates outright, and merge ids that only
+    // differ in case or separators.
+    findings.extend(operation_ids::find(&root).into_iter().map(|c| {
+        let severity = if c.rule == operation_ids::DUPLICATE {
+            log_parser::Severity::Error
+        } else {
+            log_parser::Severity::Warning
+        };
+        log_parser::LintError {
+            severity,
+            ..info(c.rule, c.message, c.pointer)
+        }
+    }));
+    findings
+}
+
+/// Run the plugins' analyzers on a background thread; their findings get
+/// an entry of their own once they're in.
+fn start_plugin_analysis(app: &mut App) {
+    app.plugin_findings.clear();
+    app.plugin_rx = None;
+    if !app.plugins.has_analyzers() {
+        return;
+    }
+    let Some(root) = load_spec_value(app) else {
+        return;
+    };
+    let plugins = Arc::clone(&app.plugins);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(plugins.analyze(&root));
+    });
+    app.plugin_rx = Some(rx);
+}
+
+/// Show the analyzers' findings, at the severity each reports, and warn
+/// about each analyzer that failed.
+fn drain_plugin_findings(app: &mut App) {
+    let Some(rx) = &app.plugin_rx else {
+        return;
+    };
+    let (found, failures) = match rx.try_recv() {
+        Ok(answer) => answer,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            app.plugin_rx = None;
+            return;
+        }
+    };
+    app.plugin_rx = None;
+    app.plugin_findings = found
+        .into_iter()
+        .map(|f| log_parser::LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: log_parser::Severity::parse(&f.severity)
+                .unwrap_or(log_parser::Severity::Info),
+            rule: f.rule,
+            message: f.message,
+            json_path: Some(f.pointer),
+        })
+        .collect();
+    for message in failures {
+        app.set_status(message, StatusLevel::Warn);
+    }
+    app.clamp_indices();
+}
+
+/// What a 3.0 spec needs for OpenAPI 3.1, as `info` findings for their own
+/// entry in the phases list.
+fn migration_findings(app: &App) -> Vec<log_parser::LintError> {
+    let Some(root) = load_spec_value(app) else {
+        return Vec::new();
+    };
+    migrate::analyze(&root)
+        .into_iter()
+        .map(|m| log_parser::LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: log_parser::Severity::Info,
+            rule: m.rule.to_string(),
+            message: m.message,
+            json_path: Some(m.pointer),
+        })
+        .collect()
+}
+
+/// Preview the spec rewritten in the configured canonical layout; `y`
+/// applies it like any other fix.
+fn format_spec(app: &mut App) {
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec file found", StatusLevel::Error);
+        return;
+    };
+    let style = app.config.clone().unwrap_or_default().format;
+    match fix::propose_format(&spec_path, &style) {
+        Ok(Some(proposal)) => app.open_fix(proposal, Some(FixOrigin::Format)),
+        Ok(None) => app.set_status("The spec is already formatted", StatusLevel::Info),
+        Err(e) => app.set_status(format!("Failed to format spec: {e:#}"), StatusLevel::Error),
+    }
+}
+
+/// Ask before rewriting the spec with the fixes for every migration finding.
+fn prompt_migrate_spec(app: &mut App) {
+    if app.migration_findings.is_empty() {
+        app.set_status(
+            "Nothing to migrate — the spec isn't OpenAPI 3.0",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let (Some(spec_index), Some(spec_path)) = (&app.spec_index, &app.spec_path) else {
+        app.set_status("No spec file found", StatusLevel::Error);
+        return;
+    };
+    let cfg = app.config.clone().unwrap_or_default();
+    match fix::propose_all(&app.migration_findings, spec_index, spec_path, &cfg) {
+        Ok(proposals) if proposals.is_empty() => {
+            app.set_status("No migration finding has an auto-fix", StatusLevel::Info);
+        }
+        Ok(proposals) => {
+            app.migrate_prompt = Some(app::MigratePrompt {
+                proposals,
+                findings: app.migration_findings.len(),
+            });
+        }
+        Err(e) => app.set_status(format!("Failed to read spec: {e}"), StatusLevel::Error),
+    }
+}
+
+/// Apply the confirmed migration fixes and re-validate.
+fn migrate_spec(app: &mut App) {
+    let Some(prompt) = app.migrate_prompt.take() else {
+        return;
+    };
+    let Some(spec_path) = app.spec_path.clone() else {
+        return;
+    };
+    match fix::apply_all(&prompt.proposals, &spec_path) {
+        Ok(applied) => {
+            if let Ok(raw) = std::fs::read_to_string(&spec_path)
+                && let Ok(index) = spec::parse_spec(&raw)
+            {
+                app.spec_index = Some(index);
             }
+            start_pipeline(app);
+            let skipped = prompt.proposals.len() - applied;
+            let message = if skipped > 0 {
+                format!(
+                    "Applied {applied} migration fix(es), re-validating \u{2014} {skipped} overlapped, press U again after the run"
+                )
+            } else {
+                format!("Applied {applied} migration fix(es), re-validating...")
+            };
+            app.set_status(message, StatusLevel::Info);
+            if applied > 0 {
+                run_fix_automation(app, "migrate", "Migrate to OpenAPI 3.1", 0, applied);
+            }
+        }
+        Err(e) => app.set_status(format!("Failed to migrate spec: {e}"), StatusLevel::Error),
+    }
+}
+
+/// Ask the fix assistant about `error` on a background thread; the answer
+/// opens the fix overlay like any other proposal.
+fn start_fix_assist(app: &mut App, error: log_parser::LintError) {
+    if app.fix_assist_rx.is_some() {
+        app.set_status("The fix assistant is still busy", StatusLevel::Info);
+        return;
+    }
+    let (Some(assistant), Some(spec_path)) = (
+        app.config.as_ref().and_then(|c| c.fix_assistant.clone()),
+        app.spec_path.clone(),
+    ) else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    let description = app
+        .rule_docs
+        .lookup(&error.rule)
+        .and_then(|doc| doc.description)
+        .map(str::to_string);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = fix::propose_assisted_fix(&error, description, &spec_path, &cwd, &assistant)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send((error.rule, result));
+    });
+    app.fix_assist_rx = Some(rx);
+    app.set_status(
+        "No built-in fix \u{2014} asking the fix assistant...",
+        StatusLevel::Info,
+    );
+}
+
+/// Ask the plugin fix rule for `error`'s rule on a background thread; a
+/// proposal opens the fix overlay, and without one the fix assistant is
+/// asked if there is one.
+fn start_plugin_fix(app: &mut App, error: log_parser::LintError) {
+    if app.plugin_fix_rx.is_some() {
+        app.set_status("A plugin is still proposing a fix", StatusLevel::Info);
+        return;
+    }
+    let Some(spec_path) = app.spec_path.clone() else {
+        return;
+    };
+    let plugins = Arc::clone(&app.plugins);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result =
+            fix::propose_plugin_fix(&error, &spec_path, &plugins).map_err(|e| format!("{e:#}"));
+        let _ = tx.send((error, result));
+    });
+    app.plugin_fix_rx = Some(rx);
+    app.set_status("Asking the plugin for a fix...", StatusLevel::Info);
+}
+
+/// Show the plugin's fix once it's in.
+fn drain_plugin_fix(app: &mut App) {
+    let Some(rx) = &app.plugin_fix_rx else {
+        return;
+    };
+    let (error, result) = match rx.try_recv() {
+        Ok(answer) => answer,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            app.plugin_fix_rx = None;
+            return;
         }
+    };
+    app.plugin_fix_rx = None;
+    match result {
+        Ok(Some(proposal)) => {
+            app.open_fix(proposal, Some(FixOrigin::Finding(error)));
+            app.status_message = None;
+        }
+        Ok(None) if has_fix_assistant(app) => start_fix_assist(app, error),
+        Ok(None) => app.set_status(
+            format!("No auto-fix available for '{}'", error.rule),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(format!("Failed to propose a fix: {e}"), StatusLevel::Error),
+    }
+}
+
+fn has_fix_assistant(app: &App) -> bool {
+    app.config
+        .as_ref()
+        .is_some_and(|c| c.fix_assistant.is_some())
+}
+
+/// Show the fix assistant's answer once it's in.
+fn drain_fix_assist(app: &mut App) {
+    let Some(rx) = &app.fix_assist_rx else {
+        return;
+    };
+    let (rule, result) = match rx.try_recv() {
+        Ok(answer) => answer,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            app.fix_assist_rx = None;
+            return;
+        }
+    };
+    app.fix_assist_rx = None;
+    match result {
+        Ok(Some(proposal)) => {
+            app.open_fix(proposal, None);
+            app.set_status(
+                "Suggested by the fix assistant \u{2014} review it before accepting",
+                StatusLevel::Warn,
+            );
+        }
+        Ok(None) => app.set_status(
+            format!("The fix assistant has no fix for '{rule}'"),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(format!("Fix assistant failed: {e}"), StatusLevel::Error),
+    }
+}
+
+/// Run the automation script's `on_fix_applied` handler, if one is
+/// configured, and show what it reported.
+fn run_fix_automation(app: &mut App, rule: &str, description: &str, line: usize, count: usize) {
+    let Some(script) = app.config.as_ref().and_then(|c| c.automation.clone()) else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    let (rule, description) = (rule.to_string(), description.to_string());
+    start_automation(app, move || {
+        automation::fix_applied(&cwd, &script, &rule, &description, line, count)
+    });
+}
+
+/// Run an automation handler on a background thread, so its webhooks
+/// don't hold up the UI; what it reports shows once it's done.
+fn start_automation(app: &mut App, handler: impl FnOnce() -> Result<Vec<String>> + Send + 'static) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(handler().map_err(|e| format!("{e:#}")));
+    });
+    app.automation_rx.push(rx);
+}
+
+/// Show what finished automation handlers reported, each handler's
+/// messages on one status line.
+fn drain_automation(app: &mut App) {
+    let mut answers = Vec::new();
+    app.automation_rx.retain(|rx| match rx.try_recv() {
+        Ok(answer) => {
+            answers.push(answer);
+            false
+        }
+        Err(std::sync::mpsc::TryRecvError::Empty) => true,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => false,
+    });
+    for answer in answers {
+        match answer {
+            Ok(messages) if messages.is_empty() => {}
+            Ok(messages) => app.set_status(messages.join(" \u{00b7} "), StatusLevel::Info),
+            Err(e) => app.set_status(format!("Automation failed: {e}"), StatusLevel::Warn),
+        }
+    }
+}
+
+/// Record a finished run on a background thread, so copying its generated
+/// output doesn't hold up the UI.
+fn start_run_record(app: &mut App, cwd: &Path, report: pipeline::ValidateReport) {
+    let cwd = cwd.to_path_buf();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = runs::record(&cwd, &report);
+        let _ = tx.send(result.map(|_| ()).map_err(|e| format!("{e:#}")));
+    });
+    app.record_rx.push(rx);
+}
+
+/// Warn about runs that failed to record.
+fn drain_run_records(app: &mut App) {
+    let mut errors = Vec::new();
+    app.record_rx.retain(|rx| match rx.try_recv() {
+        Ok(result) => {
+            errors.extend(result.err());
+            false
+        }
+        Err(std::sync::mpsc::TryRecvError::Empty) => true,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => false,
+    });
+    for e in errors {
+        app.set_status(format!("Failed to record run: {e}"), StatusLevel::Warn);
+    }
+}
+
+/// Open the HTML docs built by the docs phase in the system browser.
+fn open_docs(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    let path = cwd.join(pipeline::commands::DOCS_OUTPUT);
+    if !path.exists() {
+        app.set_status(
+            "No docs built yet — set `docs: true` and run validation",
+            StatusLevel::Warn,
+        );
+        return;
+    }
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let spawned = Command::new(opener)
+        .arg(&path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(_) => app.set_status(format!("Opened {}", path.display()), StatusLevel::Info),
+        Err(e) => app.set_status(format!("Failed to run {opener}: {e}"), StatusLevel::Error),
+    }
+}
+
+/// Write the open changelog as Markdown release notes.
+fn export_changelog(app: &mut App) {
+    let Some(view) = &app.changelog else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    let path = cwd.join(changelog::CHANGELOG_FILE);
+    let markdown = view.changelog.to_markdown(&view.base);
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, markdown));
+    match result {
+        Ok(()) => app.set_status(
+            format!("Wrote {}", changelog::CHANGELOG_FILE),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(
+            format!("Failed to write changelog: {e}"),
+            StatusLevel::Error,
+        ),
+    }
+}
+
+/// Zip up everything a maintainer needs to look into a problem report.
+fn create_support_bundle(app: &mut App) {
+    let cwd = app.work_dir.clone();
+    let cfg = app.config.clone().unwrap_or_default();
+    match support::create_bundle(&cwd, app.spec_path.as_deref(), &cfg) {
+        Ok(summary) => app.set_status(
+            format!(
+                "Wrote {} ({} files)",
+                support::SUPPORT_BUNDLE,
+                summary.files
+            ),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(
+            format!("Failed to create support bundle: {e}"),
+            StatusLevel::Error,
+        ),
+    }
+}
+
+/// Record the selected lint or security finding in `.oav-ignore` and hide
+/// it.
+fn suppress_selected_error(app: &mut App) {
+    let Some(error) = app.selected_error().cloned() else {
+        app.set_status("No error selected", StatusLevel::Info);
+        return;
+    };
+    if app.is_suppressed(&error) {
+        app.set_status(
+            format!(
+                "'{}' is already suppressed \u{2014} edit {} to restore it",
+                error.rule,
+                suppress::IGNORE_FILE
+            ),
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let cwd = app.work_dir.clone();
+    let suppression = Suppression {
+        rule: error.rule.clone(),
+        path: error.json_path.clone(),
+    };
+    if let Err(e) = suppress::add(&cwd, &suppression) {
+        app.set_status(format!("Failed to suppress: {e}"), StatusLevel::Error);
+        return;
+    }
+    app.suppressions.push(suppression);
+    let errors = std::mem::take(&mut app.lint_errors);
+    app.set_lint_errors(errors);
+    let errors = std::mem::take(&mut app.security_errors);
+    app.set_security_errors(errors);
+    let settled = settle_lint_status(app, &cwd);
+    app.clamp_indices();
+    let mut message = format!("Suppressed '{}' in {}", error.rule, suppress::IGNORE_FILE);
+    match settled {
+        Ok(true) => message.push_str(" \u{2014} the phase now passes"),
+        Ok(false) => {}
+        Err(e) => {
+            app.set_status(format!("{message} \u{2014} {e:#}"), StatusLevel::Warn);
+            return;
+        }
+    }
+    app.set_status(message, StatusLevel::Info);
+}
+
+/// Trace where each lint finding was placed in the spec: the linter's
+/// position, its JSON path, and the line the Spec Context panel shows.
+fn trace_findings(app: &App) {
+    if !trace::enabled(Target::Parse) {
+        return;
+    }
+    for err in &app.lint_errors {
+        let shown = app.error_spec_position(err).map_or_else(
+            || "nowhere".to_string(),
+            |(line, col)| format!("{line}:{col}"),
+        );
+        trace::log(
+            Target::Parse,
+            format_args!(
+                "{} at {}:{} path {} -> {shown}",
+                err.rule,
+                err.line,
+                err.col,
+                err.json_path.as_deref().unwrap_or("-")
+            ),
+        );
+    }
+}
+
+/// Record the current lint and security findings in the baseline, so
+/// later runs only highlight findings that aren't in it.
+fn snapshot_baseline(app: &mut App) {
+    if app
+        .report
+        .as_ref()
+        .is_none_or(|r| r.phases.lint.is_none() && r.phases.security.is_none())
+    {
+        app.set_status("No lint or security results to snapshot", StatusLevel::Info);
+        return;
+    }
+    let cwd = app.work_dir.clone();
+    let snapshot = app.snapshot_baseline();
+    if let Err(e) = baseline::save(&cwd, &snapshot) {
+        app.set_status(
+            format!("Failed to save baseline: {e:#}"),
+            StatusLevel::Error,
+        );
+        return;
+    }
+    let count = snapshot.findings.len();
+    app.baseline = Some(snapshot);
+    let errors = std::mem::take(&mut app.lint_errors);
+    app.set_lint_errors(errors);
+    let errors = std::mem::take(&mut app.security_errors);
+    app.set_security_errors(errors);
+    app.clamp_indices();
+    app.set_status(
+        format!(
+            "Baseline of {count} finding(s) saved to {}",
+            baseline::BASELINE_FILE
+        ),
+        StatusLevel::Info,
+    );
+}
+
+/// Open the onboarding tour on the validator view, where its panels live.
+fn start_tour(app: &mut App) {
+    app.view_mode = ViewMode::Validator;
+    app.screen_mode = ScreenMode::Normal;
+    app.tour = Some(TourState::new());
+    focus_tour_target(app);
+}
+
+fn handle_tour_key(app: &mut App, key: KeyEvent) {
+    let Some(tour) = app.tour.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter | KeyCode::Char(' ') => {
+            if !tour.next() {
+                tour.mark_seen();
+                app.tour = None;
+                return;
+            }
+        }
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace => tour.back(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            tour.mark_seen();
+            app.tour = None;
+            let replay = app.keymap.label(KeyAction::StartTour).to_string();
+            app.set_status(
+                format!("Tour skipped \u{2014} press {replay} to replay it"),
+                StatusLevel::Info,
+            );
+            return;
+        }
+        _ => return,
+    }
+    focus_tour_target(app);
+}
+
+/// Focus the panel the current tour step points at, so it is highlighted
+/// the same way as during normal use.
+fn focus_tour_target(app: &mut App) {
+    if let Some(tour) = &app.tour
+        && let TourTarget::Panel(panel) = tour.step().target
+    {
+        app.focused_panel = panel;
+    }
+}
+
+/// Write the wizard's `.oavc` and reload as if starting fresh.
+fn finish_init_wizard(app: &mut App) {
+    let Some(wizard) = app.init_wizard.take() else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    match config::write_init_config(&cwd, &wizard.choices()) {
+        Ok(()) => {
+            load_project(app);
+            start_held_validation(app);
+            app.set_status(format!("Wrote {}", config::CONFIG_FILE), StatusLevel::Info);
+        }
+        Err(e) => {
+            app.init_wizard = Some(wizard);
+            app.set_status(format!("Failed to write config: {e}"), StatusLevel::Error);
+        }
+    }
+}
+
+/// Query the generator image for every generator it supports.
+fn start_generator_listing(app: &mut App) {
+    if docker::ensure_available().is_err() {
+        app.set_status("Docker not available", StatusLevel::Warn);
+        return;
+    }
+    let cfg = app.config.clone().unwrap_or_default();
+    let cmd = pipeline::commands::list_generators_command(&cfg, &app.work_dir);
+    match docker::spawn(cmd, CancelToken::new()) {
+        Ok(rx) => {
+            if let Some(picker) = app.generator_picker.as_mut() {
+                picker.live_rx = Some(rx);
+            }
+            app.set_status("Listing generators...", StatusLevel::Info);
+        }
+        Err(e) => app.set_status(
+            format!("Failed to list generators: {e}"),
+            StatusLevel::Error,
+        ),
+    }
+}
+
+/// Show the command line of every step a run would start, without running
+/// anything.
+fn show_dry_run(app: &mut App) {
+    let Some(input) = pipeline_input(app) else {
+        return;
+    };
+    let site = HookSite {
+        work_dir: input.work_dir.clone(),
+        spec_path: input.spec_path.clone(),
+    };
+    let mut steps: Vec<(String, String)> = Vec::new();
+    for (phase, cmd) in pipeline::plan(&input) {
+        for hook in hooks::matching(&input.config.hooks, config::HookWhen::Before, &phase) {
+            let line = hooks::command_line(hook, &phase, &site);
+            steps.push((format!("{phase} hook before"), line));
+        }
+        steps.push((phase.to_string(), pipeline::repro::command_line(&cmd.args)));
+        for hook in hooks::matching(&input.config.hooks, config::HookWhen::After, &phase) {
+            let line = hooks::command_line(hook, &phase, &site);
+            steps.push((format!("{phase} hook after"), line));
+        }
+    }
+    if steps.is_empty() {
+        app.set_status(
+            "Nothing to run \u{2014} every phase is off",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    app.dry_run = Some(app::DryRunView { steps, selected: 0 });
+}
+
+/// Run the configured generators once per `generator_matrix` version and
+/// show the matrix as it fills in.
+fn start_matrix(app: &mut App) {
+    if app.validating || app.workspace.as_ref().is_some_and(|w| w.is_running()) {
+        app.set_status(
+            "Wait for validation to finish before running the matrix",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let Some(input) = pipeline_input(app) else {
+        return;
+    };
+    let versions = pipeline::matrix::versions(&input.config);
+    if versions.is_empty() {
+        app.set_status(
+            "No versions to compare \u{2014} configure 'generator_matrix' in .oavc",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    app.docker_available = docker::ensure_available().is_ok();
+    if !app.docker_available {
+        app.set_status(
+            "Cannot run matrix: Docker not available",
+            StatusLevel::Error,
+        );
+        return;
+    }
+    let rows: Vec<String> =
+        pipeline::commands::build_generator_list(&input.config, &app.custom_defs)
+            .iter()
+            .map(|(generator, scope)| format!("{scope}/{generator}"))
+            .collect();
+    if rows.is_empty() {
+        app.set_status("No generators configured", StatusLevel::Info);
+        return;
+    }
+
+    let cancel = CancelToken::new();
+    let rx = pipeline::matrix::run_matrix(input, versions.clone(), cancel.clone());
+    app.matrix = Some(MatrixView::new(versions, rows, rx, cancel));
+    app.show_matrix = true;
+}
+
+/// Apply pending matrix events, reporting when the run ends.
+fn drain_matrix_events(app: &mut App) {
+    let Some(view) = app.matrix.as_mut() else {
+        return;
+    };
+    if !view.drain() {
+        return;
+    }
+    match &view.error {
+        Some(reason) => {
+            let message = format!("Version matrix stopped: {reason}");
+            app.set_status(message, StatusLevel::Error);
+        }
+        None => {
+            let failing = view
+                .cells
+                .iter()
+                .flatten()
+                .flatten()
+                .filter(|cell| !cell.passed())
+                .count();
+            app.set_status(
+                format!("Version matrix done, {failing} failing cell(s)"),
+                StatusLevel::Info,
+            );
+        }
+    }
+}
+
+/// Check every workspace spec and show a row per spec as they finish.
+fn start_workspace(app: &mut App) {
+    if app.validating || app.matrix.as_ref().is_some_and(|m| m.is_running()) {
+        app.set_status(
+            "Wait for the running pipeline to finish before checking the workspace",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let Some(input) = pipeline_input(app) else {
+        return;
+    };
+    let cfg = &input.config;
+    if !(cfg.structure || cfg.lint || cfg.security) {
+        app.set_status(
+            "Nothing to check \u{2014} structure, lint and security are off",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let specs = if cfg.workspace_specs.is_empty() {
+        spec::discover_spec(&input.work_dir, cfg.search_depth).unwrap_or_default()
+    } else {
+        cfg.workspace_specs.clone()
+    };
+    if specs.is_empty() {
+        app.set_status(
+            "No specs found \u{2014} configure 'workspace_specs' in .oavc",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    if cfg.lint || cfg.security {
+        app.docker_available = docker::ensure_available().is_ok();
+        if !app.docker_available {
+            app.set_status(
+                "Cannot check workspace: Docker not available",
+                StatusLevel::Error,
+            );
+            return;
+        }
+    }
+
+    let cancel = CancelToken::new();
+    let rx = pipeline::workspace::run_workspace(input, specs.clone(), cancel.clone());
+    app.workspace = Some(WorkspaceView::new(specs, rx, cancel));
+    app.show_workspace = true;
+}
+
+/// Apply pending workspace events; once every spec ran, export the report.
+fn drain_workspace_events(app: &mut App) {
+    let Some(mut view) = app.workspace.take() else {
+        return;
+    };
+    let ended = view.drain(app);
+    let error = view.error.clone();
+    let report = view.report();
+    app.workspace = Some(view);
+    if !ended {
+        return;
+    }
+    if let Some(reason) = error {
+        let message = format!("Workspace run stopped: {reason}");
+        app.set_status(message, StatusLevel::Error);
+        return;
+    }
+    match pipeline::workspace::save(&app.work_dir, &report) {
+        Ok(()) => app.set_status(
+            format!(
+                "Workspace: {} of {} spec(s) passing, {} finding(s) \u{2014} see {}",
+                report.passed,
+                report.specs.len(),
+                report.findings,
+                pipeline::workspace::WORKSPACE_SUMMARY
+            ),
+            StatusLevel::Info,
+        ),
+        Err(e) => app.set_status(
+            format!("Failed to save workspace report: {e:#}"),
+            StatusLevel::Error,
+        ),
+    }
+}
+
+/// Diff the output of the selected matrix version against the base one and
+/// show it in the code browser's diff view.
+fn compare_matrix(app: &mut App) {
+    let Some(view) = &app.matrix else {
+        return;
+    };
+    if view.column == view.base {
+        app.set_status(
+            "Select a version other than the marked one",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    if !view.finished(view.base) || !view.finished(view.column) {
+        app.set_status("Both versions need to finish first", StatusLevel::Info);
+        return;
+    }
+    let cwd = app.work_dir.clone();
+    let (base, other) = (&view.versions[view.base], &view.versions[view.column]);
+    let keys: Vec<&String> = view.rows.iter().collect();
+    let diffs = app::diff::compare_trees(
+        &keys,
+        |key| base.output_dir(&cwd, key),
+        |key| other.output_dir(&cwd, key),
+    );
+    if diffs.is_empty() {
+        app.set_status(
+            format!(
+                "No differences in generated output between {} and {}",
+                base.label, other.label
+            ),
+            StatusLevel::Info,
+        );
+        return;
+    }
+    let label = format!("{} \u{2192} {}", base.label, other.label);
+    let mut keys: Vec<String> = diffs.keys().cloned().collect();
+    keys.sort_unstable();
+
+    app.show_matrix = false;
+    app.browser.diff_state = DiffViewState {
+        diffs,
+        active: true,
+        active_generator: keys.into_iter().next(),
+        label: Some(label),
+        ..DiffViewState::new()
+    };
+    sync_generators_from_report(app);
+    app::browser::refresh_file_tree(&mut app.browser, &cwd);
+    app.view_mode = ViewMode::CodeBrowser;
+}
+
+/// Drain the live generator listing, merging results once the container exits.
+fn drain_picker_events(app: &mut App) {
+    let Some(picker) = app.generator_picker.as_mut() else {
+        return;
+    };
+    let Some(rx) = &picker.live_rx else {
+        return;
+    };
+
+    let mut result = None;
+    while let Ok(line) = rx.try_recv() {
+        if let docker::OutputLine::Done(r) = line {
+            result = Some(r);
+            break;
+        }
+    }
+    let Some(result) = result else {
+        return;
+    };
+    picker.live_rx = None;
+
+    if !result.success {
+        app.set_status("Failed to list generators", StatusLevel::Error);
+        return;
+    }
+    let added = picker.merge_live(lazyoav::generators::parse_generator_list(&result.log));
+    app.set_status(
+        format!("Added {added} generator(s) from openapi-generator list"),
+        StatusLevel::Info,
+    );
+}
+
+/// Write the picker's selection to `.oavc` and use it for the next run.
+fn save_generator_picker(app: &mut App) {
+    let Some(picker) = app.generator_picker.as_ref() else {
+        return;
+    };
+    let empty = picker.empty_scopes();
+    if !empty.is_empty() {
+        app.set_status(
+            format!("Select at least one {} generator", empty.join("/")),
+            StatusLevel::Warn,
+        );
+        return;
+    }
+
+    let mut cfg = app.config.clone().unwrap_or_default();
+    let shown = |scope: &str| picker.entries.iter().any(|e| e.scope == scope);
+    if shown("server") {
+        cfg.server_generators = picker.enabled("server");
+    }
+    if shown("client") {
+        cfg.client_generators = picker.enabled("client");
+    }
+
+    let cwd = app.work_dir.clone();
+    match config::save_generators(&cwd, &cfg.server_generators, &cfg.client_generators) {
+        Ok(()) => {
+            let count = cfg.server_generators.len() + cfg.client_generators.len();
+            app.config = Some(cfg);
+            app.config_modified = config_modified(&cwd);
+            app.report_stale |= app.report.is_some();
+            app.generator_picker = None;
+            app.set_status(
+                format!("Saved {count} generator(s) to .oavc \u{2014} 'r' to re-run"),
+                StatusLevel::Info,
+            );
+        }
+        Err(e) => app.set_status(format!("Failed to save .oavc: {e}"), StatusLevel::Error),
+    }
+}
+
+/// Page through the selected step's full log from `.oav/logs/`.
+fn log_file_action(app: &mut App) -> Action {
+    let Some(invocation) = app.current_invocation() else {
+        app.set_status("Select a step that ran a container", StatusLevel::Info);
+        return Action::None;
+    };
+    match &invocation.log_file {
+        Some(path) if path.is_file() => Action::OpenPager { path: path.clone() },
+        Some(_) => {
+            app.set_status(
+                "This run's logs were pruned \u{2014} see log_retention",
+                StatusLevel::Info,
+            );
+            Action::None
+        }
+        None => {
+            app.set_status(
+                "No saved log for this step \u{2014} logs are kept when log_retention > 0",
+                StatusLevel::Info,
+            );
+            Action::None
+        }
+    }
+}
+
+/// Build the `docker` arguments for a shell into the selected failed step.
+fn shell_action(app: &mut App) -> Action {
+    let Some(phase) = app.selected_failed_step() else {
+        app.set_status(
+            "Shell is only available for failed generate, compile, test or code lint steps",
+            StatusLevel::Info,
+        );
+        return Action::None;
+    };
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec file found", StatusLevel::Error);
+        return Action::None;
+    };
+    let cwd = app.work_dir.clone();
+    let cfg = app.config.clone().unwrap_or_default();
+
+    let shell = pipeline::commands::step_command(&cfg, &spec_path, &cwd, &app.custom_defs, &phase)
+        .and_then(|cmd| Some((pipeline::commands::interactive_shell_args(&cmd)?, cmd.env)));
+    match shell {
+        Some((args, env)) => Action::OpenShell { args, env },
+        None => {
+            app.set_status(
+                format!("No container to open for {phase}"),
+                StatusLevel::Info,
+            );
+            Action::None
+        }
+    }
+}
+
+/// Suspend the TUI, run an interactive `docker` shell, then resume.
+fn open_shell(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    args: &[String],
+    env: &[(String, String)],
+) -> Result<()> {
+    restore_terminal()?;
+    let result = Command::new("docker")
+        .args(args)
+        .current_dir(&app.work_dir)
+        .envs(env.iter().map(|(name, value)| (name, value)))
+        .status();
+    *terminal = setup_terminal()?;
+
+    match result {
+        Err(e) => app.set_status(format!("Failed to start shell: {e}"), StatusLevel::Error),
+        Ok(status) if !status.success() => {
+            let code = status
+                .code()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "signal".into());
+            app.set_status(format!("Shell exited with {code}"), StatusLevel::Warn);
+        }
+        Ok(_) => {}
+    }
+    Ok(())
+}
+
+/// Suspend the TUI, page through `path` with `$PAGER` (or `less`), then resume.
+fn open_pager(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    path: &Path,
+) -> Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".into());
+    let mut parts = shell_words::split(&pager).unwrap_or_else(|_| vec![pager.clone()]);
+    if parts.is_empty() {
+        parts.push("less".into());
     }
 
-    Action::None
+    restore_terminal()?;
+    let result = Command::new(&parts[0])
+        .args(&parts[1..])
+        .arg(path)
+        .current_dir(&app.work_dir)
+        .status();
+    *terminal = setup_terminal()?;
+
+    if let Err(e) = result {
+        app.set_status(format!("Failed to open pager: {e}"), StatusLevel::Error);
+    }
+    Ok(())
 }
 
-/// Suspend the TUI, open `$EDITOR` at the given line, then resume.
-fn open_editor(
+/// How handing a file to the editor went.
+enum EditorLaunch {
+    /// The editor the TUI runs inside was told to open it; `false` if it
+    /// couldn't be reached.
+    Remote(bool),
+    /// The TUI was suspended for `$VISUAL`/`$EDITOR`, which has exited.
+    Local(io::Result<std::process::ExitStatus>),
+}
+
+/// Open `path` at `line` and `col`: in the editor the TUI runs inside when
+/// there is one, otherwise in `$VISUAL`/`$EDITOR` with the TUI suspended.
+fn launch_editor(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     path: &Path,
     line: usize,
-) -> Result<()> {
+    col: usize,
+) -> Result<EditorLaunch> {
+    let remote = app.config.as_ref().is_none_or(|c| c.editor_remote);
+    if remote
+        && let Some(commands) = editor::remote_commands(
+            std::env::var("NVIM").ok().as_deref(),
+            std::env::var("TERM_PROGRAM").ok().as_deref(),
+            path,
+            line,
+            col,
+        )
+    {
+        return Ok(EditorLaunch::Remote(reach_remote_editor(app, &commands)));
+    }
+
     let editor_var = std::env::var("VISUAL")
         .or_else(|_| std::env::var("EDITOR"))
         .unwrap_or_else(|_| "vi".into());
-
-    let parts: Vec<String> = match shell_words::split(&editor_var) {
-        Ok(p) if !p.is_empty() => p,
-        _ => vec![editor_var.clone()],
-    };
-    let program = &parts[0];
-    let extra_args = &parts[1..];
+    let template = app.config.as_ref().and_then(|c| c.editor_cmd.clone());
+    let parts = editor::command(template.as_deref(), &editor_var, path, line, col);
 
     restore_terminal()?;
 
-    let result = Command::new(program)
-        .args(extra_args)
-        .arg(format!("+{line}"))
-        .arg(path)
+    let result = Command::new(&parts[0])
+        .args(&parts[1..])
+        .current_dir(&app.work_dir)
         .status();
 
     // Always re-enter TUI, even if the editor failed.
     *terminal = setup_terminal()?;
 
-    match result {
-        Err(e) => {
+    Ok(EditorLaunch::Local(result))
+}
+
+/// Suspend the TUI, open `$EDITOR` at the given line, then resume.
+fn open_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    path: &Path,
+    line: usize,
+    col: usize,
+) -> Result<()> {
+    match launch_editor(terminal, app, path, line, col)? {
+        EditorLaunch::Remote(true) => {
+            // Watch the spec so saving it there re-runs validation.
+            if let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) {
+                app.watched_spec = Some((path.to_path_buf(), modified));
+            }
+            app.set_status(
+                "Opened in editor \u{2014} saving re-validates",
+                StatusLevel::Info,
+            );
+            return Ok(());
+        }
+        EditorLaunch::Remote(false) => return Ok(()),
+        EditorLaunch::Local(Err(e)) => {
             app.set_status(format!("Failed to open editor: {e}"), StatusLevel::Error);
             return Ok(());
         }
-        Ok(status) if !status.success() => {
+        EditorLaunch::Local(Ok(status)) if !status.success() => {
             let code = status
                 .code()
                 .map(|c| c.to_string())
@@ -560,7 +3614,7 @@ fn open_editor(
             }
             return Ok(());
         }
-        Ok(_) => {}
+        EditorLaunch::Local(Ok(_)) => {}
     }
 
     // Re-read and re-parse the spec (user may have edited it).
@@ -577,25 +3631,434 @@ fn open_editor(
     Ok(())
 }
 
-/// Start the validation pipeline using the stored config.
-fn start_pipeline(app: &mut App) {
-    // Cancel any in-progress pipeline before starting a new one.
-    if let Some(token) = &app.cancel_token {
-        token.cancel();
+/// Open a generated file in the editor to inspect it. Nothing is
+/// re-validated; the code browser just reloads the file if it is shown.
+fn open_generated(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    path: &Path,
+    line: usize,
+) -> Result<()> {
+    match launch_editor(terminal, app, path, line, 1)? {
+        EditorLaunch::Remote(true) => {
+            app.set_status("Opened in editor", StatusLevel::Info);
+        }
+        EditorLaunch::Remote(false) => {}
+        EditorLaunch::Local(Err(e)) => {
+            app.set_status(format!("Failed to open editor: {e}"), StatusLevel::Error);
+        }
+        EditorLaunch::Local(Ok(status)) => {
+            if !status.success() {
+                let code = status
+                    .code()
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "signal".into());
+                app.set_status(format!("Editor exited with {code}"), StatusLevel::Warn);
+            }
+            let shown = app
+                .browser
+                .opened_file_index
+                .and_then(|i| app.browser.file_tree.get(i))
+                .is_some_and(|e| e.path == path);
+            if shown {
+                let scroll = app.browser.file_scroll;
+                app.browser.file_index = app.browser.opened_file_index.unwrap_or(0);
+                app::browser::load_selected_file(&mut app.browser);
+                app.browser.file_scroll = scroll;
+            }
+        }
     }
+    Ok(())
+}
 
-    // Re-check Docker so we pick up changes since startup.
-    app.docker_available = docker::ensure_available().is_ok();
-    if !app.docker_available {
-        app.set_status("Cannot validate: Docker not available", StatusLevel::Error);
+/// Run the commands that jump the surrounding editor to a file. Returns
+/// `false`, with an error in the status bar, if one of them fails.
+fn reach_remote_editor(app: &mut App, commands: &[Vec<String>]) -> bool {
+    for cmd in commands {
+        let result = Command::new(&cmd[0])
+            .args(&cmd[1..])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+        match result {
+            Ok(status) if status.success() => {}
+            Ok(_) | Err(_) => {
+                app.set_status(
+                    format!("Failed to reach the editor via {}", cmd[0]),
+                    StatusLevel::Error,
+                );
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Re-parse and re-validate the spec when a remote editor has saved it.
+fn check_watched_spec(app: &mut App) {
+    let Some((path, seen)) = &app.watched_spec else {
+        return;
+    };
+    let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) else {
+        return;
+    };
+    if modified == *seen {
+        return;
+    }
+    let path = path.clone();
+    app.watched_spec = Some((path.clone(), modified));
+    if let Ok(raw) = std::fs::read_to_string(&path)
+        && let Ok(index) = spec::parse_spec(&raw)
+    {
+        app.spec_index = Some(index);
+    }
+    start_pipeline(app);
+    app.set_status("Spec saved \u{2014} re-validating...", StatusLevel::Info);
+}
+
+/// Notice the spec changing on disk outside a run, e.g. saved from an editor
+/// lazyoav didn't open. The report and findings no longer describe the
+/// file, so they're marked stale; the spec is re-read so the Spec Context
+/// panel shows what's on disk.
+fn check_spec_file(app: &mut App) {
+    let Some(path) = app.spec_path.clone() else {
+        return;
+    };
+    let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+        return;
+    };
+    let seen = app
+        .spec_seen
+        .as_ref()
+        .filter(|(seen_path, _)| *seen_path == path)
+        .map(|(_, seen)| *seen);
+    app.spec_seen = Some((path.clone(), modified));
+    if seen.is_none_or(|seen| seen == modified) {
+        return;
+    }
+    if let Ok(raw) = std::fs::read_to_string(&path)
+        && let Ok(index) = spec::parse_spec(&raw)
+    {
+        app.spec_index = Some(index);
+    }
+    if (app.report.is_some() || app.validating) && !app.spec_changed {
+        app.spec_changed = true;
+        let run = app.keymap.label(KeyAction::RunValidation);
+        app.set_status(
+            format!("Spec changed since the last run \u{2014} {run} to re-run"),
+            StatusLevel::Warn,
+        );
+    }
+}
+
+/// Copy the part of the selection picked by `what` after the copy prefix.
+fn copy_to_clipboard(app: &mut App, what: char) {
+    let (label, text) = match what {
+        'y' | 'e' => ("error", app.selected_error().map(|e| format_error(app, e))),
+        'p' => (
+            "JSON pointer",
+            app.selected_error()
+                .and_then(|e| e.json_path.as_deref())
+                .map(spec::normalize_to_pointer),
+        ),
+        's' => ("spec snippet", spec_snippet(app)),
+        'f' => (
+            "file path",
+            app.browser
+                .opened_file_index
+                .and_then(|i| app.browser.file_tree.get(i))
+                .map(|entry| entry.path.display().to_string()),
+        ),
+        _ => return,
+    };
+    let Some(text) = text else {
+        app.set_status(format!("No {label} to copy"), StatusLevel::Info);
+        return;
+    };
+    match clipboard::copy(&text) {
+        Ok(()) => app.set_status(format!("Copied {label}"), StatusLevel::Info),
+        Err(e) => app.set_status(format!("Failed to copy: {e}"), StatusLevel::Error),
+    }
+}
+
+/// An error as `file:line:col severity rule message`, like linter output.
+fn format_error(app: &App, err: &log_parser::LintError) -> String {
+    let file = app
+        .spec_path
+        .as_ref()
+        .and_then(|p| p.file_name())
+        .map_or_else(|| "spec".into(), |n| n.to_string_lossy());
+    let mut text = match app.error_spec_position(err) {
+        Some((line, col)) => format!("{file}:{line}:{col}"),
+        None => file.into_owned(),
+    };
+    text.push_str(&format!(" {} {} {}", err.severity, err.rule, err.message));
+    if let Some(path) = &err.json_path {
+        text.push_str(&format!(" ({path})"));
+    }
+    text
+}
+
+/// The spec lines around the selected error.
+fn spec_snippet(app: &App) -> Option<String> {
+    const RADIUS: usize = 5;
+    let line = app.error_spec_line(app.selected_error()?)?;
+    let window = app.spec_index.as_ref()?.context_window(line, RADIUS)?;
+    Some(window.lines.join("\n"))
+}
+
+/// Edit the spec search query, jumping to the first match at or after the
+/// selected finding as the user types. Enter keeps the search for `n`/`N`,
+/// Esc drops it.
+fn handle_spec_search_key(app: &mut App, key: KeyEvent) {
+    let Some(search) = app.spec_search.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            app.spec_search = None;
+            app.spec_scroll = 0;
+            return;
+        }
+        KeyCode::Enter => {
+            search.editing = false;
+            if search.query.is_empty() {
+                app.spec_search = None;
+            } else if search.matches.is_empty() {
+                let message = format!("No match for '{}'", search.query);
+                app.set_status(message, StatusLevel::Info);
+            }
+            return;
+        }
+        KeyCode::Backspace => {
+            search.query.pop();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+            search.query.push(c);
+        }
+        _ => return,
+    }
+    let from = app
+        .selected_error()
+        .and_then(|err| app.error_spec_line(err))
+        .unwrap_or(1);
+    if let (Some(search), Some(index)) = (app.spec_search.as_mut(), app.spec_index.as_ref()) {
+        search.update(index.lines().iter().map(String::as_str), from);
+    }
+    follow_search_match(app);
+}
+
+/// Edit the Raw Log search query, jumping to the first match at or below
+/// the top of the view as the user types.
+fn handle_log_search_key(app: &mut App, key: KeyEvent) {
+    let Some(search) = app.log_search.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            app.log_search = None;
+            return;
+        }
+        KeyCode::Enter => {
+            search.editing = false;
+            if search.query.is_empty() {
+                app.log_search = None;
+            } else if search.matches.is_empty() {
+                let message = format!("No match for '{}'", search.query);
+                app.set_status(message, StatusLevel::Info);
+            }
+            return;
+        }
+        KeyCode::Backspace => {
+            search.query.pop();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+            search.query.push(c);
+        }
+        _ => return,
+    }
+    refresh_log_search(app, usize::from(app.detail_scroll) + 1);
+    follow_log_match(app);
+}
+
+/// Recompute the Raw Log search against the filtered lines, selecting the
+/// first match at or after 1-based line `from`.
+fn refresh_log_search(app: &mut App, from: usize) {
+    if app.log_search.is_none() {
+        return;
+    }
+    let lines: Vec<String> = app
+        .raw_log_lines()
+        .into_iter()
+        .map(|(text, _)| text.to_string())
+        .collect();
+    if let Some(search) = app.log_search.as_mut() {
+        search.update(lines.iter().map(String::as_str), from);
+    }
+}
+
+/// Scroll the Raw Log tab so the current match sits near the top.
+fn follow_log_match(app: &mut App) {
+    let Some(search) = app.log_search.as_ref() else {
+        return;
+    };
+    match search.current_line() {
+        Some(line) => {
+            app.detail_scroll = u16::try_from(line.saturating_sub(4)).unwrap_or(u16::MAX);
+        }
+        None if !search.editing => app.set_status("No matches", StatusLevel::Info),
+        None => {}
+    }
+}
+
+/// Bring the view to the current search match; a free navigation cursor
+/// moves with it.
+fn follow_search_match(app: &mut App) {
+    let line = app.spec_search.as_ref().and_then(|s| s.current_line());
+    match (line, app.active_spec_cursor()) {
+        (Some(line), Some(_)) => app.move_spec_cursor(line),
+        _ => app.spec_scroll = 0,
+    }
+}
+
+fn handle_goto_line_key(app: &mut App, key: KeyEvent) {
+    let Some(input) = app.goto_line.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.goto_line = None,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+        KeyCode::Enter => {
+            let line = input.parse::<usize>().ok();
+            app.goto_line = None;
+            match line {
+                Some(line) => app.move_spec_cursor(line),
+                None => app.set_status("Not a line number", StatusLevel::Info),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Pin the Spec Context panel where it is, or unpin it so it follows the
+/// selected finding again once the selection moves.
+fn toggle_spec_pin(app: &mut App) {
+    match app.active_spec_cursor() {
+        Some(cursor) if cursor.pinned => {
+            app.spec_cursor = Some(app::SpecCursor {
+                pinned: false,
+                anchor: (app.phase_index, app.error_index),
+                ..cursor
+            });
+            app.set_status("Spec view unpinned", StatusLevel::Info);
+        }
+        _ => {
+            let Some(line) = app.spec_target_line() else {
+                app.set_status("No spec location to pin", StatusLevel::Info);
+                return;
+            };
+            app.move_spec_cursor(line);
+            if let Some(cursor) = app.spec_cursor.as_mut() {
+                cursor.pinned = true;
+            }
+            app.set_status(
+                format!("Spec view pinned at line {line}"),
+                StatusLevel::Info,
+            );
+        }
+    }
+}
+
+/// Open the inline edit overlay on the selected error's spec line.
+fn start_inline_edit(app: &mut App) {
+    let Some(error) = app.selected_error() else {
+        app.set_status("No error selected", StatusLevel::Info);
+        return;
+    };
+    let Some(line) = app.error_spec_line(error) else {
+        app.set_status("Error has no spec location", StatusLevel::Info);
+        return;
+    };
+    let Some(path) = app.spec_path.clone() else {
+        app.set_status("No spec file found", StatusLevel::Error);
+        return;
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => match raw.lines().nth(line - 1) {
+            Some(text) => {
+                app.inline_edit = Some(app::edit::InlineEditState::new(
+                    line,
+                    vec![text.to_string()],
+                ));
+            }
+            None => app.set_status(
+                format!("Line {line} is beyond the end of the spec"),
+                StatusLevel::Error,
+            ),
+        },
+        Err(e) => app.set_status(format!("Failed to read spec: {e}"), StatusLevel::Error),
+    }
+}
+
+fn handle_inline_edit_key(app: &mut App, key: KeyEvent) {
+    let Some(edit) = app.inline_edit.as_mut() else {
         return;
+    };
+    let ctrl = key.modifiers.contains(event::KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => app.inline_edit = None,
+        KeyCode::Char('s') if ctrl => save_inline_edit(app),
+        KeyCode::Char(c) if !ctrl => edit.insert(c),
+        KeyCode::Enter => edit.newline(),
+        KeyCode::Backspace => edit.backspace(),
+        KeyCode::Delete => edit.delete(),
+        KeyCode::Left => edit.left(),
+        KeyCode::Right => edit.right(),
+        KeyCode::Home => edit.home(),
+        KeyCode::End => edit.end(),
+        KeyCode::Up => edit.up(),
+        KeyCode::Down => edit.down(),
+        _ => {}
     }
+}
 
-    let cwd = match std::env::current_dir() {
-        Ok(p) => p,
-        Err(_) => return,
+/// Write the inline edit to the spec and re-validate, like an accepted fix.
+fn save_inline_edit(app: &mut App) {
+    let Some(edit) = app.inline_edit.take() else {
+        return;
+    };
+    if !edit.is_modified() {
+        return;
+    }
+    let Some(spec_path) = app.spec_path.clone() else {
+        app.set_status("No spec file found", StatusLevel::Error);
+        return;
     };
+    match fix::apply_edit(&spec_path, edit.first_line, &edit.original, &edit.lines) {
+        Ok(()) => {
+            if let Ok(raw) = std::fs::read_to_string(&spec_path)
+                && let Ok(index) = spec::parse_spec(&raw)
+            {
+                app.spec_index = Some(index);
+            }
+            start_pipeline(app);
+            app.set_status("Edit saved, re-validating...", StatusLevel::Info);
+        }
+        Err(e) => {
+            app.set_status(format!("Failed to save edit: {e}"), StatusLevel::Error);
+        }
+    }
+}
 
+/// The pipeline input for the project in the working directory: the stored
+/// config (loaded on first use), custom generators and the resolved spec.
+/// Sets a status and returns `None` when there's no spec to run against.
+fn pipeline_input(app: &mut App) -> Option<PipelineInput> {
+    let cwd = app.work_dir.clone();
     let cfg = match &app.config {
         Some(c) => c.clone(),
         None => {
@@ -604,38 +4067,97 @@ fn start_pipeline(app: &mut App) {
             c
         }
     };
+    let Some(spec_path) = resolve_spec_path(&cwd, &cfg, app.spec_override.as_deref()) else {
+        app.set_status(
+            "No spec file found \u{2014} configure 'spec' in .oavc",
+            StatusLevel::Error,
+        );
+        return None;
+    };
+    Some(PipelineInput {
+        config: cfg,
+        custom_defs: app.custom_defs.clone(),
+        spec_path,
+        work_dir: cwd,
+    })
+}
 
-    let spec_path = match resolve_spec_path(&cwd, &cfg) {
-        Some(p) => p,
-        None => {
-            app.set_status(
-                "No spec file found \u{2014} configure 'spec' in .oavc",
-                StatusLevel::Error,
-            );
-            return;
+/// Start the validation pipeline using the stored config.
+fn start_pipeline(app: &mut App) {
+    // The matrix swaps `.oav/generated/` out from under a regular run.
+    if app.matrix.as_ref().is_some_and(|m| m.is_running()) {
+        app.set_status(
+            "Version matrix running \u{2014} validate once it finishes",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    // The workspace run puts its own report back once it's done.
+    if app.workspace.as_ref().is_some_and(|w| w.is_running()) {
+        app.set_status(
+            "Workspace run going \u{2014} validate once it finishes",
+            StatusLevel::Info,
+        );
+        return;
+    }
+
+    // Cancel any in-progress pipeline before starting a new one.
+    if let Some(token) = &app.cancel_token {
+        if app.validating {
+            trace::log(Target::Pipeline, "cancelling the running pipeline");
         }
-    };
+        token.cancel();
+    }
+
+    // Re-check Docker so we pick up changes since startup.
+    app.docker_available = docker::ensure_available().is_ok();
+    if !app.docker_available {
+        app.set_status("Cannot validate: Docker not available", StatusLevel::Error);
+        return;
+    }
 
-    app.spec_path = Some(spec_path.clone());
+    let Some(input) = pipeline_input(app) else {
+        return;
+    };
+    app.spec_path = Some(input.spec_path.clone());
+    // Keep the spec this run checks, the base of "changed since last run".
+    if let Ok(raw) = std::fs::read_to_string(&input.spec_path)
+        && let Err(e) = regions::record_run_spec(&input.work_dir, &raw)
+    {
+        trace::log(
+            Target::Pipeline,
+            format_args!("failed to keep the run's spec: {e:#}"),
+        );
+    }
 
     app.snapshots.clear();
     app.browser.diff_state = DiffViewState::new();
-    let gen_pairs = pipeline::commands::build_generator_list(&cfg, &app.custom_defs);
+    let gen_pairs = pipeline::commands::build_generator_list(&input.config, &app.custom_defs);
     for (generator, scope) in &gen_pairs {
         let key = format!("{scope}/{generator}");
-        let gen_dir = cwd.join(".oav/generated").join(&key);
+        let gen_dir = input.work_dir.join(".oav/generated").join(&key);
         if gen_dir.is_dir() {
             let snap = app::diff::snapshot_directory(&gen_dir);
             app.snapshots.insert(key, snap);
         }
     }
 
-    let input = PipelineInput {
-        config: cfg,
-        custom_defs: app.custom_defs.clone(),
-        spec_path,
-        work_dir: cwd,
-    };
+    let plan: Vec<pipeline::Phase> = pipeline::plan(&input)
+        .into_iter()
+        .map(|(phase, _)| phase)
+        .collect();
+    if trace::enabled(Target::Pipeline) {
+        let steps: Vec<String> = plan.iter().map(|phase| phase.to_string()).collect();
+        trace::log(
+            Target::Pipeline,
+            format_args!(
+                "starting run of {}: {}",
+                input.spec_path.display(),
+                steps.join(", ")
+            ),
+        );
+    }
+    let eta = app::eta::Eta::new(Durations::load(&input.work_dir), input.config.clone(), plan);
 
     let cancel = CancelToken::new();
     let rx = pipeline::run_pipeline(input, cancel.clone());
@@ -643,7 +4165,20 @@ fn start_pipeline(app: &mut App) {
     // Clear previous state.
     app.report = None;
     app.lint_errors.clear();
+    app.structure_errors.clear();
+    app.security_errors.clear();
+    app.migration_findings.clear();
+    app.plugin_findings.clear();
+    app.plugin_rx = None;
+    app.test_errors.clear();
+    app.code_lint_errors.clear();
+    app.browser.code_findings.clear();
+    app.step_error_counts.clear();
+    app.refresh_changed();
+    app.progress = None;
+    app.eta = Some(eta);
     app.live_log.clear();
+    app.phase_logs.clear();
     app.phase_index = 0;
     app.error_index = 0;
     app.detail_scroll = 0;
@@ -651,30 +4186,108 @@ fn start_pipeline(app: &mut App) {
     app.pipeline_rx = Some(rx);
     app.cancel_token = Some(cancel);
     app.validating = true;
+    // The run sees the spec as it is now.
+    app.spec_changed = false;
+    app.spec_seen = app.spec_path.clone().and_then(|path| {
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        Some((path, modified))
+    });
 }
 
 /// Drain pending pipeline events without blocking.
 fn drain_pipeline_events(app: &mut App) {
+    let mut warnings = Vec::new();
     let done = if let Some(rx) = &app.pipeline_rx {
         let mut finished = false;
         while let Ok(ev) = rx.try_recv() {
             match ev {
-                PipelineEvent::PhaseStarted(_) => {
+                PipelineEvent::PhaseStarted(phase) => {
+                    trace::log(Target::Pipeline, format_args!("{phase} started"));
+                    if let Some(eta) = &mut app.eta {
+                        eta.started(&phase, std::time::Instant::now());
+                    }
                     app.live_log.clear();
                 }
-                PipelineEvent::Log { line, .. } => {
-                    app.live_log.push_str(&line);
-                    app.live_log.push('\n');
+                PipelineEvent::Log {
+                    phase,
+                    line,
+                    elapsed,
+                } => {
+                    let entry = app::LogLine {
+                        elapsed: Some(elapsed),
+                        text: line,
+                    };
+                    app.phase_logs.entry(phase).or_default().push(entry.clone());
+                    app.live_log.push(entry);
+                }
+                PipelineEvent::TimeoutWarning {
+                    phase,
+                    remaining,
+                    control,
+                } => {
+                    trace::log(
+                        Target::Pipeline,
+                        format_args!("{phase} times out in {}s", remaining.as_secs()),
+                    );
+                    app.timeout_prompts.retain(|p| p.phase != phase);
+                    app.timeout_prompts.push(app::TimeoutPrompt {
+                        phase,
+                        remaining,
+                        received: std::time::Instant::now(),
+                        control,
+                    });
+                }
+                PipelineEvent::PhaseFinished { phase, success } => {
+                    trace::log(
+                        Target::Pipeline,
+                        format_args!("{phase} {}", if success { "passed" } else { "failed" }),
+                    );
+                    if let Some(eta) = &mut app.eta {
+                        eta.finished(&phase, success);
+                    }
+                    app.timeout_prompts.retain(|p| p.phase != phase);
+                }
+                PipelineEvent::Progress { done, total, .. } => {
+                    app.progress = Some((done, total));
+                }
+                PipelineEvent::Warning(message) => {
+                    trace::log(Target::Pipeline, format_args!("warning: {message}"));
+                    warnings.push(message);
                 }
-                PipelineEvent::PhaseFinished { .. } => {}
                 PipelineEvent::Completed(report) => {
+                    trace::log(
+                        Target::Pipeline,
+                        format_args!(
+                            "completed: {} passed, {} failed",
+                            report.summary.passed, report.summary.failed
+                        ),
+                    );
+                    app.report_stale = false;
+                    if let Some(mut eta) = app.eta.take() {
+                        eta.durations.record(&report);
+                        if let Err(e) = eta.durations.save(&app.work_dir) {
+                            warnings.push(format!("Failed to save phase durations: {e:#}"));
+                        }
+                    }
                     if let Some(lint) = &report.phases.lint {
-                        app.lint_errors = log_parser::parse_lint_log(&lint.log);
+                        let errors = log_parser::parse_lint_output(&lint.log);
+                        if let Err(e) = log_parser::cache::save(&app.work_dir, &lint.log, &errors) {
+                            warnings.push(format!("Failed to cache lint findings: {e:#}"));
+                        }
+                        let analysis = analysis_findings(app);
+                        app.set_lint_findings(errors, analysis);
+                        trace_findings(app);
                     }
-
-                    if let Some(gen_steps) = &report.phases.generate
-                        && let Ok(cwd) = std::env::current_dir()
-                    {
+                    app.migration_findings = migration_findings(app);
+                    start_plugin_analysis(app);
+                    app.structure_errors = structure_findings(&report);
+                    app.set_security_errors(security_findings(&report));
+                    app.test_errors = test_findings(&report);
+                    set_code_findings(app, &report);
+                    app.step_error_counts = app::step_error_counts(&report);
+
+                    if let Some(gen_steps) = &report.phases.generate {
+                        let cwd = app.work_dir.clone();
                         let mut total_changed = 0usize;
                         for step in gen_steps {
                             let key = format!("{}/{}", step.scope, step.generator);
@@ -702,18 +4315,71 @@ fn drain_pipeline_events(app: &mut App) {
                     }
                     app.snapshots.clear();
 
-                    app.report = Some(report);
+                    app.report = Some(*report);
+                    let cwd = app.work_dir.clone();
+                    if let Err(e) = settle_lint_status(app, &cwd) {
+                        warnings.push(format!("{e:#}"));
+                    }
+                    // The spec may have changed since the ranges were taken.
+                    if app.changed.is_some() && !narrow_to_changed(app, false) {
+                        app.changed = None;
+                    }
+                    if app.spec_path.is_some()
+                        && let Err(e) = export_quickfix(app, &cwd)
+                    {
+                        warnings.push(format!("Failed to write quickfix list: {e:#}"));
+                    }
+                    if let Some(report) = &app.report {
+                        start_run_record(app, &cwd, report.clone());
+                    }
+                    let cfg = app.config.clone().unwrap_or_default();
+                    record_score(app);
+                    record_rule_history(app);
+                    if let Some(report) = &app.report {
+                        let lint_errors = app.lint_errors[..app.suppressed_start()]
+                            .iter()
+                            .filter(|e| e.severity == log_parser::Severity::Error)
+                            .count();
+                        notify::completed(&cfg.notify, report, lint_errors);
+                        if let Some(script) = &cfg.automation {
+                            let lint_warnings = app.lint_errors[..app.suppressed_start()]
+                                .iter()
+                                .filter(|e| e.severity == log_parser::Severity::Warning)
+                                .count();
+                            let (script, report) = (script.clone(), report.clone());
+                            start_automation(app, move || {
+                                automation::run_complete(
+                                    &cwd,
+                                    &script,
+                                    &report,
+                                    (lint_errors, lint_warnings),
+                                )
+                            });
+                        }
+                    }
+                    app.timeout_prompts.clear();
                     app.validating = false;
+                    app.progress = None;
+                    app.eta = None;
                     app.live_log.clear();
+                    if cfg.auto_focus_failure {
+                        app.focus_first_failure();
+                    }
                     app.clamp_indices();
                     finished = true;
                     break;
                 }
                 PipelineEvent::Aborted(reason) => {
-                    app.live_log
-                        .push_str(&format!("\n--- Aborted: {reason} ---\n"));
+                    trace::log(Target::Pipeline, format_args!("aborted: {reason}"));
+                    app.live_log.push(app::LogLine {
+                        elapsed: None,
+                        text: format!("--- Aborted: {reason} ---"),
+                    });
                     app.snapshots.clear();
+                    app.timeout_prompts.clear();
                     app.validating = false;
+                    app.progress = None;
+                    app.eta = None;
                     finished = true;
                     break;
                 }
@@ -724,6 +4390,10 @@ fn drain_pipeline_events(app: &mut App) {
         false
     };
 
+    for message in warnings {
+        app.set_status(message, StatusLevel::Warn);
+    }
+
     if done {
         app.pipeline_rx = None;
         app.cancel_token = None;
@@ -731,11 +4401,107 @@ fn drain_pipeline_events(app: &mut App) {
         // If viewing the code browser, refresh to pick up new output.
         if app.view_mode == ViewMode::CodeBrowser {
             sync_generators_from_report(app);
-            if let Ok(cwd) = std::env::current_dir() {
-                app::browser::refresh_file_tree(&mut app.browser, &cwd);
-            }
+            app::browser::refresh_file_tree(&mut app.browser, &app.work_dir);
+            app::browser::start_usage_scan(&mut app.browser, &app.work_dir);
+        }
+    }
+}
+
+/// Ask before deleting generated output: the code browser's active
+/// generator's or all of it. What each would reclaim is measured on a
+/// background thread; see [`drain_clean_sizes`].
+fn prompt_clean_generated(app: &mut App) {
+    if pipeline_running(app) {
+        app.set_status(
+            "A pipeline is running \u{2014} clean up once it finishes",
+            StatusLevel::Info,
+        );
+        return;
+    }
+    if app.clean_rx.is_some() {
+        return;
+    }
+    let work_dir = app.work_dir.clone();
+    let generator = app
+        .browser
+        .active_generator_dir()
+        .filter(|_| app.view_mode == ViewMode::CodeBrowser);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let all = clean::reclaimable(&clean::generated_dir(&work_dir, None));
+        let generator = generator
+            .map(|key| {
+                let size = clean::reclaimable(&clean::generated_dir(&work_dir, Some(&key)));
+                (key, size)
+            })
+            .filter(|(_, size)| *size > 0);
+        let _ = tx.send(app::CleanPrompt { generator, all });
+    });
+    app.clean_rx = Some(rx);
+    app.set_status("Measuring generated output...", StatusLevel::Info);
+}
+
+/// Open the clean prompt once its sizes are measured.
+fn drain_clean_sizes(app: &mut App) {
+    let Some(rx) = &app.clean_rx else {
+        return;
+    };
+    let prompt = match rx.try_recv() {
+        Ok(prompt) => prompt,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            app.clean_rx = None;
+            return;
+        }
+    };
+    app.clean_rx = None;
+    app.status_message = None;
+    if prompt.all == 0 {
+        app.set_status("No generated output to delete", StatusLevel::Info);
+    } else if pipeline_running(app) {
+        app.set_status(
+            "A pipeline is running \u{2014} clean up once it finishes",
+            StatusLevel::Info,
+        );
+    } else {
+        app.clean_prompt = Some(prompt);
+    }
+}
+
+/// Whether validation, a generator matrix or a workspace run is writing
+/// generated output.
+fn pipeline_running(app: &App) -> bool {
+    app.validating
+        || app.matrix.as_ref().is_some_and(|m| m.is_running())
+        || app.workspace.as_ref().is_some_and(|w| w.is_running())
+}
+
+/// Delete the generated output of `key` (`"{scope}/{generator}"`), or all
+/// of it, and reset the code browser and diffs that showed it.
+fn clean_generated(app: &mut App, key: Option<&str>) {
+    let cwd = app.work_dir.clone();
+    match clean::remove(&cwd, key) {
+        Ok(bytes) => {
+            let what = key.unwrap_or("all generated output");
+            app.set_status(
+                format!(
+                    "Deleted {what} \u{2014} reclaimed {}",
+                    clean::format_size(bytes)
+                ),
+                StatusLevel::Info,
+            );
+        }
+        Err(e) => app.set_status(format!("{e:#}"), StatusLevel::Error),
+    }
+    match key {
+        Some(key) => {
+            app.browser.diff_state.diffs.remove(key);
+            app.browser.diff_state.active = false;
         }
+        None => app.browser.diff_state = DiffViewState::new(),
     }
+    app::browser::refresh_file_tree(&mut app.browser, &cwd);
+    app::browser::start_usage_scan(&mut app.browser, &cwd);
 }
 
 /// Populate `browser.generators` from the current report's generate phase.
@@ -759,6 +4525,55 @@ fn sync_generators_from_report(app: &mut App) {
     app.browser.generators = generators;
 }
 
+/// Open the code browser on the line of the selected code lint finding.
+/// Returns `false` when the selection isn't one, or its file isn't in the
+/// generated output.
+fn show_code_finding(app: &mut App) -> bool {
+    let Some(pipeline::Phase::CodeLint { generator, scope }) = app.selected_phase() else {
+        return false;
+    };
+    let key = format!("{scope}/{generator}");
+    let Some(finding) = app
+        .browser
+        .code_findings
+        .get(&key)
+        .and_then(|findings| findings.get(app.error_index))
+        .cloned()
+    else {
+        return false;
+    };
+    let cwd = app.work_dir.clone();
+    sync_generators_from_report(app);
+    let Some(index) = app
+        .browser
+        .generators
+        .iter()
+        .position(|(g, s)| *g == generator && *s == scope)
+    else {
+        return false;
+    };
+    if app.browser.generator_index != index || app.browser.all_files.is_empty() {
+        app.browser.generator_index = index;
+        app::browser::refresh_file_tree(&mut app.browser, &cwd);
+    }
+    let Some(path) = app
+        .browser
+        .all_files
+        .iter()
+        .find(|e| !e.is_dir && e.path.ends_with(&finding.file))
+        .map(|e| e.path.clone())
+    else {
+        return false;
+    };
+    app::browser::reveal(&mut app.browser, &path);
+    app::browser::load_selected_file(&mut app.browser);
+    // A few lines of context above the finding.
+    app.browser.file_scroll = u16::try_from(finding.line.saturating_sub(4)).unwrap_or(u16::MAX);
+    app.browser.browser_focus = BrowserPanel::FileContent;
+    app.view_mode = ViewMode::CodeBrowser;
+    true
+}
+
 /// Handle keys when the code browser view is active.
 fn handle_browser_key(app: &mut App, input: KeyInput) -> Action {
     if app.browser.diff_state.active {
@@ -777,6 +4592,51 @@ fn handle_browser_key(app: &mut App, input: KeyInput) -> Action {
         return Action::None;
     }
 
+    let content = app.browser.browser_focus == BrowserPanel::FileContent;
+    if has(KeyAction::IgnoreGeneratedFile) {
+        let index = if content {
+            app.browser.opened_file_index
+        } else {
+            Some(app.browser.file_index)
+        };
+        if let Some(index) = index {
+            ignore_generated_file(app, index);
+        }
+        return Action::None;
+    }
+    if has(KeyAction::ShowOutline) {
+        show_outline(app);
+        return Action::None;
+    }
+    if has(KeyAction::OpenEditor) {
+        return match generated_editor_target(&app.browser) {
+            Some((path, line)) => Action::OpenGenerated { path, line },
+            None => {
+                app.set_status("Select a file to open", StatusLevel::Info);
+                Action::None
+            }
+        };
+    }
+    if has(KeyAction::Search) {
+        app.browser.browser_focus = BrowserPanel::FileTree;
+        app.browser.filter_input = Some(app.browser.filter.clone().unwrap_or_default());
+        return Action::None;
+    }
+    if !content
+        && (has(KeyAction::ToggleDirectory) || has(KeyAction::Select))
+        && app::browser::toggle_selected_dir(&mut app.browser)
+    {
+        return Action::None;
+    }
+    if content && has(KeyAction::ToggleWrap) {
+        app.browser.file_wrap.toggle();
+        return Action::None;
+    }
+    let right = has(KeyAction::ScrollRight);
+    if content && (right || has(KeyAction::ScrollLeft)) && app.browser.file_wrap.scroll(right) {
+        return Action::None;
+    }
+
     // Panel focus switching.
     if has(KeyAction::NextPanel) {
         app.browser.browser_focus = BrowserPanel::FileContent;
@@ -788,17 +4648,13 @@ fn handle_browser_key(app: &mut App, input: KeyInput) -> Action {
         if !app.browser.generators.is_empty() {
             app.browser.generator_index =
                 (app.browser.generator_index + 1) % app.browser.generators.len();
-            if let Ok(cwd) = std::env::current_dir() {
-                app::browser::refresh_file_tree(&mut app.browser, &cwd);
-            }
+            app::browser::refresh_file_tree(&mut app.browser, &app.work_dir);
         }
     } else if has(KeyAction::PrevGenerator) {
         if !app.browser.generators.is_empty() {
             let len = app.browser.generators.len();
             app.browser.generator_index = (app.browser.generator_index + len - 1) % len;
-            if let Ok(cwd) = std::env::current_dir() {
-                app::browser::refresh_file_tree(&mut app.browser, &cwd);
-            }
+            app::browser::refresh_file_tree(&mut app.browser, &app.work_dir);
         }
     }
     // Navigation — dispatched based on focused sub-panel.
@@ -870,6 +4726,117 @@ fn handle_browser_key(app: &mut App, input: KeyInput) -> Action {
     Action::None
 }
 
+/// The generated file `e` opens and the 1-based line to open it at: the
+/// shown file at its scroll position, or the file selected in the tree.
+fn generated_editor_target(browser: &app::state::CodeBrowserState) -> Option<(PathBuf, usize)> {
+    let index = match browser.browser_focus {
+        BrowserPanel::FileContent => browser.opened_file_index?,
+        BrowserPanel::FileTree => browser.file_index,
+    };
+    let entry = browser.file_tree.get(index).filter(|e| !e.is_dir)?;
+    let line = match (&browser.file_content, browser.opened_file_index) {
+        (Some(content), Some(opened)) if opened == index => {
+            usize::from(browser.file_scroll).min(content.len().saturating_sub(1)) + 1
+        }
+        _ => 1,
+    };
+    Some((entry.path.clone(), line))
+}
+
+/// Open the symbol outline of the file shown in the code browser.
+fn show_outline(app: &mut App) {
+    let Some((entry, lines)) = app
+        .browser
+        .opened_file_index
+        .and_then(|i| app.browser.file_tree.get(i))
+        .zip(app.browser.file_content.as_ref())
+    else {
+        app.set_status("Open a file first", StatusLevel::Info);
+        return;
+    };
+    let symbols = app::outline::symbols(&entry.path, lines);
+    if symbols.is_empty() {
+        app.set_status(
+            format!("No symbols found in {}", entry.name),
+            StatusLevel::Info,
+        );
+        return;
+    }
+    app.outline = Some(app::outline::OutlineView::new(entry.name.clone(), symbols));
+}
+
+fn handle_file_filter_key(app: &mut App, key: KeyEvent) {
+    let Some(input) = app.browser.filter_input.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            app.browser.filter_input = None;
+            app.browser.filter = None;
+            app::browser::rebuild_visible(&mut app.browser);
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        KeyCode::Enter => {
+            let filter = input.trim().to_string();
+            app.browser.filter_input = None;
+            app.browser.filter = (!filter.is_empty()).then_some(filter);
+            app::browser::rebuild_visible(&mut app.browser);
+            if app.browser.filter.is_some() && app.browser.file_tree.is_empty() {
+                app.set_status("No files match the filter", StatusLevel::Info);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Leave the diff view on the file it had selected, expanding the tree down
+/// to it and opening it.
+fn close_diff(app: &mut App) {
+    app.browser.diff_state.active = false;
+    let diff = &app.browser.diff_state;
+    let Some((key, rel_path)) = diff.active_generator.as_ref().and_then(|key| {
+        let file = diff.diffs.get(key)?.files.get(diff.file_index)?;
+        (file.kind != ChangeKind::Deleted).then(|| (key.clone(), file.rel_path.clone()))
+    }) else {
+        return;
+    };
+    let cwd = app.work_dir.clone();
+    // The diff may have been cycled to another generator than the tree's.
+    if app.browser.active_generator_dir().as_ref() != Some(&key) {
+        let Some(index) = app
+            .browser
+            .generators
+            .iter()
+            .position(|(generator, scope)| format!("{scope}/{generator}") == key)
+        else {
+            return;
+        };
+        app.browser.generator_index = index;
+        app::browser::refresh_file_tree(&mut app.browser, &cwd);
+    }
+    let path = cwd.join(".oav/generated").join(key).join(rel_path);
+    if app::browser::reveal(&mut app.browser, &path) {
+        app::browser::load_selected_file(&mut app.browser);
+    }
+}
+
+/// Add a generated file or directory to its output's
+/// `.openapi-generator-ignore`.
+fn ignore_generated_file(app: &mut App, index: usize) {
+    let cwd = app.work_dir.clone();
+    match app::browser::ignore_entry(&mut app.browser, &cwd, index) {
+        Ok(Some(pattern)) => app.set_status(
+            format!("Added {pattern} to .openapi-generator-ignore \u{2014} kept on regenerate"),
+            StatusLevel::Info,
+        ),
+        Ok(None) => app.set_status("Already in .openapi-generator-ignore", StatusLevel::Info),
+        Err(e) => app.set_status(format!("{e:#}"), StatusLevel::Error),
+    }
+}
+
 fn activate_diff_mode(app: &mut App) {
     let key = app.browser.active_generator_dir();
     let has_diff = key
@@ -890,14 +4857,36 @@ fn activate_diff_mode(app: &mut App) {
 fn handle_diff_key(app: &mut App, input: KeyInput) -> Action {
     let has = |a: KeyAction| app.keymap.has_action(&input, a);
 
+    let content = app.browser.diff_state.focus == DiffPanel::DiffContent;
+    let right = has(KeyAction::ScrollRight);
+    if content && (right || has(KeyAction::ScrollLeft)) && app.browser.diff_state.wrap.scroll(right)
+    {
+        return Action::None;
+    }
+
     if has(KeyAction::CloseDiff) {
-        app.browser.diff_state.active = false;
+        close_diff(app);
+    } else if content && has(KeyAction::ToggleWrap) {
+        app.browser.diff_state.wrap.toggle();
+    } else if content && (has(KeyAction::NextHunk) || has(KeyAction::PrevHunk)) {
+        let forward = has(KeyAction::NextHunk);
+        if !app.browser.diff_state.jump_hunk(forward) {
+            let which = if forward { "next" } else { "previous" };
+            app.set_status(format!("No {which} hunk in this file"), StatusLevel::Info);
+        }
+    } else if content && (has(KeyAction::NextChangedFile) || has(KeyAction::PrevChangedFile)) {
+        let forward = has(KeyAction::NextChangedFile);
+        if !app.browser.diff_state.step_file(forward) {
+            let which = if forward { "next" } else { "previous" };
+            app.set_status(format!("No {which} changed file"), StatusLevel::Info);
+        }
     } else if has(KeyAction::NextPanel) {
         app.browser.diff_state.focus = DiffPanel::DiffContent;
     } else if has(KeyAction::PrevPanel) {
         app.browser.diff_state.focus = DiffPanel::FileList;
     } else if has(KeyAction::Select) && app.browser.diff_state.focus == DiffPanel::FileList {
         app.browser.diff_state.scroll = 0;
+        app.browser.diff_state.wrap.offset = 0;
         app.browser.diff_state.focus = DiffPanel::DiffContent;
     } else if has(KeyAction::NextGenerator) {
         cycle_diff_generator(app, true);
@@ -1316,6 +5305,102 @@ mod tests {
         assert_eq!(app.detail_scroll, 5);
     }
 
+    #[test]
+    fn detail_t_toggles_log_times() {
+        let mut app = App::new();
+        app.focused_panel = Panel::Detail;
+
+        handle_key(&mut app, key_char('t'));
+        assert!(app.show_log_times);
+        handle_key(&mut app, key_char('t'));
+        assert!(!app.show_log_times);
+    }
+
+    // ── Timeout prompt ───────────────────────────────────────────────
+
+    fn push_timeout_prompt(app: &mut App) -> std::sync::mpsc::Receiver<docker::ContainerControl> {
+        let (control, rx) = std::sync::mpsc::channel();
+        app.timeout_prompts.push(app::TimeoutPrompt {
+            phase: pipeline::Phase::Lint,
+            remaining: Duration::from_secs(30),
+            received: std::time::Instant::now(),
+            control,
+        });
+        rx
+    }
+
+    #[test]
+    fn timeout_prompt_y_sends_extension() {
+        let mut app = App::new();
+        let rx = push_timeout_prompt(&mut app);
+
+        handle_key(&mut app, key_char('y'));
+        assert!(app.timeout_prompts.is_empty());
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            docker::ContainerControl::ExtendTimeout(Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn timeout_prompt_n_dismisses_without_extending() {
+        let mut app = App::new();
+        let rx = push_timeout_prompt(&mut app);
+
+        handle_key(&mut app, key_char('n'));
+        assert!(app.timeout_prompts.is_empty());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn timeout_prompt_swallows_other_keys() {
+        let mut app = App::new();
+        let _rx = push_timeout_prompt(&mut app);
+
+        handle_key(&mut app, key_char('q'));
+        assert!(app.running);
+        assert_eq!(app.timeout_prompts.len(), 1);
+    }
+
+    // ── Generator picker ─────────────────────────────────────────────
+
+    #[test]
+    fn shift_g_opens_generator_picker() {
+        let mut app = App::new();
+        handle_key(&mut app, key_char('G'));
+        assert!(app.generator_picker.is_some());
+    }
+
+    #[test]
+    fn picker_swallows_keys_and_esc_closes() {
+        let mut app = App::new();
+        handle_key(&mut app, key_char('G'));
+        handle_key(&mut app, key_char('j'));
+        handle_key(&mut app, key_char(' '));
+        let picker = app.generator_picker.as_ref().unwrap();
+        assert_eq!(picker.index, 1);
+        assert!(!picker.entries[1].enabled);
+        assert_eq!(app.phase_index, 0);
+
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.generator_picker.is_none());
+        assert!(app.running);
+    }
+
+    #[test]
+    fn picker_refuses_to_save_empty_scope() {
+        let mut app = App::new();
+        app.config = Some(config::Config {
+            server_generators: vec!["spring".into()],
+            ..config::Config::default()
+        });
+        handle_key(&mut app, key_char('G'));
+        handle_key(&mut app, key_char(' '));
+        handle_key(&mut app, key(KeyCode::Enter));
+        assert!(app.generator_picker.is_some());
+        assert_eq!(app.status_message.unwrap().level, StatusLevel::Warn);
+    }
+
     // ── SpecContext panel scroll ─────────────────────────────────────
 
     #[test]
@@ -1357,6 +5442,32 @@ mod tests {
         assert_eq!(app.spec_scroll, 10);
     }
 
+    #[test]
+    fn spec_n_cycles_findings_on_same_line() {
+        let mut app = App::new();
+        app.focused_panel = Panel::SpecContext;
+        app.report = Some(make_report_with_lint());
+        app.lint_errors = make_lint_errors(3);
+        app.lint_errors[2].line = 1;
+
+        handle_key(&mut app, key_char('n'));
+        assert_eq!(app.error_index, 2);
+        handle_key(&mut app, key_char('N'));
+        assert_eq!(app.error_index, 0);
+    }
+
+    #[test]
+    fn spec_n_without_siblings_sets_status() {
+        let mut app = App::new();
+        app.focused_panel = Panel::SpecContext;
+        app.report = Some(make_report_with_lint());
+        app.lint_errors = make_lint_errors(2);
+
+        handle_key(&mut app, key_char('n'));
+        assert_eq!(app.error_index, 0);
+        assert!(app.status_message.is_some());
+    }
+
     // ── start_pipeline guards ────────────────────────────────────────
 
     #[test]
@@ -1402,32 +5513,193 @@ mod tests {
         app.report = Some(make_report_with_lint());
         app.lint_errors = make_lint_errors(3);
         app.error_index = 0;
-        // spec_path is None.
+        // spec_path is None.
+
+        let action = handle_key(&mut app, key_char('e'));
+        assert!(matches!(action, Action::None));
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("No spec file"));
+    }
+
+    #[test]
+    fn e_with_error_and_spec_path_returns_open_editor() {
+        let mut app = App::new();
+        app.focused_panel = Panel::Errors;
+        app.report = Some(make_report_with_lint());
+        app.lint_errors = make_lint_errors(3);
+        app.error_index = 1; // line = 2
+        app.spec_path = Some(PathBuf::from("/tmp/spec.yaml"));
+
+        let action = handle_key(&mut app, key_char('e'));
+        match action {
+            Action::OpenEditor { path, line, col } => {
+                assert_eq!(path, PathBuf::from("/tmp/spec.yaml"));
+                assert_eq!((line, col), (2, 1));
+            }
+            _ => panic!("expected OpenEditor action"),
+        }
+    }
+
+    #[test]
+    fn shift_e_edits_the_error_line_in_place() {
+        let tmp = tempfile::tempdir().unwrap();
+        let spec = tmp.path().join("spec.yaml");
+        std::fs::write(&spec, "openapi: 3.0.0\ninfo:\n  title: x\n").unwrap();
+        let mut app = App::new();
+        app.focused_panel = Panel::Errors;
+        app.report = Some(make_report_with_lint());
+        app.lint_errors = make_lint_errors(3);
+        app.error_index = 2; // line = 3
+        app.spec_path = Some(spec.clone());
+
+        handle_key(&mut app, key_char('E'));
+        let edit = app.inline_edit.as_ref().expect("edit overlay should open");
+        assert_eq!(
+            (edit.first_line, edit.lines.clone()),
+            (3, vec!["  title: x".into()])
+        );
+
+        // Keys go to the overlay, not the key map.
+        handle_key(&mut app, key(KeyCode::End));
+        handle_key(&mut app, key_char('q'));
+        assert!(app.running);
+        assert_eq!(app.inline_edit.as_ref().unwrap().lines, ["  title: xq"]);
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.inline_edit.is_none());
+        assert_eq!(
+            std::fs::read_to_string(&spec).unwrap(),
+            "openapi: 3.0.0\ninfo:\n  title: x\n"
+        );
+    }
+
+    #[test]
+    fn slash_searches_the_spec_and_n_cycles_matches() {
+        let mut app = App::new();
+        app.focused_panel = Panel::Errors;
+        app.report = Some(make_report_with_lint());
+        app.lint_errors = make_lint_errors(1);
+        app.spec_index = Some(
+            spec::parse_spec("openapi: 3.0.0\npaths:\n  /pets: {}\n  /pets/{id}: {}\n").unwrap(),
+        );
+
+        handle_key(&mut app, key_char('/'));
+        assert_eq!(app.focused_panel, Panel::SpecContext);
+        for c in "pets".chars() {
+            handle_key(&mut app, key_char(c));
+        }
+        // Typing doesn't trigger bindings.
+        assert!(app.running);
+        assert_eq!(app.spec_target_line(), Some(3));
+        handle_key(&mut app, key(KeyCode::Enter));
+
+        handle_key(&mut app, key_char('n'));
+        assert_eq!(app.spec_target_line(), Some(4));
+        handle_key(&mut app, key_char('n'));
+        assert_eq!(app.spec_target_line(), Some(3));
+        handle_key(&mut app, key_char('N'));
+        assert_eq!(app.spec_target_line(), Some(4));
+
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.spec_search.is_none());
+        assert_eq!(app.spec_target_line(), Some(1));
+    }
+
+    #[test]
+    fn raw_log_search_and_filters() {
+        let mut app = App::new();
+        let mut report = make_report_with_lint();
+        report.phases.lint.as_mut().unwrap().log = [
+            "Download https://repo.maven.apache.org/a.pom",
+            "> Task :compileJava",
+            "Api.java:3: error: cannot find symbol",
+            "Download https://repo.maven.apache.org/b.pom",
+            "Api.java:9: error: cannot find symbol",
+            "BUILD FAILED",
+        ]
+        .join("\n");
+        app.report = Some(report);
+        app.focused_panel = Panel::Detail;
+        app.detail_tab = 1;
+
+        handle_key(&mut app, key_char('/'));
+        assert_eq!(app.focused_panel, Panel::Detail);
+        for c in "symbol".chars() {
+            handle_key(&mut app, key_char(c));
+        }
+        handle_key(&mut app, key(KeyCode::Enter));
+        let search = app.active_log_search().unwrap();
+        assert_eq!(search.matches, vec![3, 5]);
+        assert_eq!(app.detail_scroll, 0);
+        handle_key(&mut app, key_char('n'));
+        assert_eq!(app.active_log_search().unwrap().current_line(), Some(5));
+        assert_eq!(app.detail_scroll, 1);
+
+        handle_key(&mut app, key_char('z'));
+        assert_eq!(app.raw_log_lines().len(), 4);
+        assert_eq!(app.active_log_search().unwrap().matches, vec![2, 3]);
+        handle_key(&mut app, key_char('x'));
+        let lines: Vec<&str> = app.raw_log_lines().iter().map(|(l, _)| *l).collect();
+        assert_eq!(lines[2], "BUILD FAILED");
+        assert_eq!(lines.len(), 3);
+
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.log_search.is_none());
+    }
+
+    #[test]
+    fn free_navigation_moves_a_cursor_and_pinning_keeps_it() {
+        let mut app = App::new();
+        app.focused_panel = Panel::Errors;
+        app.report = Some(make_report_with_lint());
+        app.lint_errors = make_lint_errors(2);
+        app.spec_index = Some(spec::parse_spec("a: 1\nb: 2\nc: 3\nd: 4\n").unwrap());
+
+        handle_key(&mut app, key_char('v'));
+        assert_eq!(app.focused_panel, Panel::SpecContext);
+        handle_key(&mut app, key_char('j'));
+        handle_key(&mut app, key_char('j'));
+        assert_eq!(app.spec_target_line(), Some(3));
+        handle_key(&mut app, key_char('>'));
+        assert_eq!(app.spec_target_line(), Some(4));
+
+        // An unpinned cursor gives way to a new selection.
+        app.error_index = 1;
+        assert_eq!(app.spec_target_line(), Some(2));
+
+        handle_key(&mut app, key_char(':'));
+        handle_key(&mut app, key_char('3'));
+        handle_key(&mut app, key(KeyCode::Enter));
+        handle_key(&mut app, key_char('p'));
+        assert!(app.spec_cursor.is_some_and(|c| c.pinned));
+        app.error_index = 0;
+        assert_eq!(app.spec_target_line(), Some(3));
 
-        let action = handle_key(&mut app, key_char('e'));
-        assert!(matches!(action, Action::None));
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Error);
-        assert!(msg.text.contains("No spec file"));
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert_eq!(app.spec_target_line(), Some(1));
     }
 
     #[test]
-    fn e_with_error_and_spec_path_returns_open_editor() {
+    fn copy_prefix_takes_the_next_key() {
         let mut app = App::new();
         app.focused_panel = Panel::Errors;
         app.report = Some(make_report_with_lint());
-        app.lint_errors = make_lint_errors(3);
-        app.error_index = 1; // line = 2
-        app.spec_path = Some(PathBuf::from("/tmp/spec.yaml"));
+        app.lint_errors = make_lint_errors(2);
+        app.spec_path = Some(PathBuf::from("/work/spec.yaml"));
+
+        handle_key(&mut app, key_char('y'));
+        assert!(app.copy_pending);
+        // The second key isn't dispatched through the key map.
+        handle_key(&mut app, key_char('p'));
+        assert!(!app.copy_pending);
+        assert_eq!(app.focused_panel, Panel::Errors);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.text, "No JSON pointer to copy");
 
-        let action = handle_key(&mut app, key_char('e'));
-        match action {
-            Action::OpenEditor { path, line } => {
-                assert_eq!(path, PathBuf::from("/tmp/spec.yaml"));
-                assert_eq!(line, 2);
-            }
-            Action::None => panic!("expected OpenEditor action"),
-        }
+        assert_eq!(
+            format_error(&app, &app.lint_errors[1]),
+            "spec.yaml:2:1 error rule-1 error 1"
+        );
     }
 
     #[test]
@@ -1507,8 +5779,11 @@ mod tests {
             description: "test".into(),
             target_line: 1,
             context_before: vec![],
+            removed: vec![],
             inserted: vec!["  new".into()],
             context_after: vec![],
+            edits: Vec::new(),
+            spec_hash: None,
         });
 
         handle_key(&mut app, key_char('n'));
@@ -1525,8 +5800,11 @@ mod tests {
             description: "test".into(),
             target_line: 1,
             context_before: vec![],
+            removed: vec![],
             inserted: vec!["  new".into()],
             context_after: vec![],
+            edits: Vec::new(),
+            spec_hash: None,
         });
 
         handle_key(&mut app, key(KeyCode::Esc));
@@ -1542,8 +5820,11 @@ mod tests {
             description: "test".into(),
             target_line: 1,
             context_before: vec![],
+            removed: vec![],
             inserted: vec!["  new".into()],
             context_after: vec![],
+            edits: Vec::new(),
+            spec_hash: None,
         });
 
         // 'j' should not navigate — overlay absorbs it.
@@ -1551,6 +5832,100 @@ mod tests {
         assert!(app.fix_proposal.is_some()); // still open
     }
 
+    #[test]
+    fn fix_overlay_previews_every_edit_and_scrolls() {
+        let mut app = App::new();
+        let spec: String = (0..30).map(|i| format!("k{i}: old\n")).collect();
+        app.spec_index = Some(spec::parse_spec(&spec).unwrap());
+        app.open_fix(
+            fix::FixProposal {
+                rule: "test".into(),
+                description: "test".into(),
+                target_line: 2,
+                context_before: vec![],
+                removed: vec!["k2: old".into()],
+                inserted: vec!["k2: new".into()],
+                context_after: vec![],
+                edits: vec![fix::Edit {
+                    target_line: 25,
+                    removed: vec!["k25: old".into()],
+                    inserted: vec!["k25: new".into()],
+                }],
+                spec_hash: None,
+            },
+            None,
+        );
+        let preview = app.fix_preview.as_ref().unwrap();
+        let inserted: Vec<(Option<usize>, &app::diff::DiffLine)> = preview
+            .lines
+            .iter()
+            .filter(|l| matches!(l.line, app::diff::DiffLine::Insert(_)))
+            .map(|l| (l.new, &l.line))
+            .collect();
+        assert_eq!(
+            inserted,
+            vec![
+                (Some(2), &app::diff::DiffLine::Insert("k2: new".into())),
+                (Some(25), &app::diff::DiffLine::Insert("k25: new".into())),
+            ]
+        );
+        let last = preview.lines.len() - 1;
+
+        handle_key(&mut app, key_char('j'));
+        handle_key(&mut app, key_char('j'));
+        handle_key(&mut app, key_char('k'));
+        assert_eq!(app.fix_scroll, 1);
+        handle_key(&mut app, key(KeyCode::PageDown));
+        assert_eq!(usize::from(app.fix_scroll), last);
+        assert!(app.fix_proposal.is_some());
+    }
+
+    #[test]
+    fn stale_fix_is_proposed_again_instead_of_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openapi.yaml");
+        std::fs::write(&path, "info:\n    title: Pets\n").unwrap();
+        let mut app = App::new();
+        app.spec_path = Some(path.clone());
+        format_spec(&mut app);
+        assert!(app.fix_proposal.is_some());
+
+        let edited = "info:\n    title: Pets\n    version: '1'\n";
+        std::fs::write(&path, edited).unwrap();
+        handle_key(&mut app, key_char('y'));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), edited);
+        let proposal = app.fix_proposal.as_ref().unwrap();
+        assert_eq!(proposal.inserted.last().unwrap(), "  version: '1'");
+        assert!(matches!(app.fix_origin, Some(FixOrigin::Format)));
+    }
+
+    #[test]
+    fn external_spec_edits_mark_the_report_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openapi.yaml");
+        std::fs::write(&path, "openapi: 3.0.0\n").unwrap();
+        let mut app = App::new();
+        app.report = Some(make_report_with_lint());
+        app.spec_path = Some(path.clone());
+        check_spec_file(&mut app);
+        assert!(!app.spec_changed);
+
+        std::fs::write(&path, "openapi: 3.0.0\ninfo: {}\n").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        check_spec_file(&mut app);
+        assert!(app.spec_changed);
+        assert_eq!(app.spec_index.as_ref().unwrap().line_count(), 2);
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .text
+                .starts_with("Spec changed since the last run")
+        );
+    }
+
     // ── spec_path storage ───────────────────────────────────────────
 
     #[test]
@@ -1559,6 +5934,26 @@ mod tests {
         assert!(app.spec_path.is_none());
     }
 
+    #[test]
+    fn command_line_spec_wins_over_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.yaml"), "openapi: 3.0.3\n").unwrap();
+        std::fs::write(tmp.path().join("b.yaml"), "openapi: 3.0.3\n").unwrap();
+        let cfg = config::Config {
+            spec: Some("a.yaml".into()),
+            ..config::Config::default()
+        };
+        assert_eq!(
+            resolve_spec_path(tmp.path(), &cfg, Some("b.yaml")),
+            Some(PathBuf::from("b.yaml"))
+        );
+        // A missing override falls back to the config.
+        assert_eq!(
+            resolve_spec_path(tmp.path(), &cfg, Some("gone.yaml")),
+            Some(PathBuf::from("a.yaml"))
+        );
+    }
+
     // ── Code browser toggle ────────────────────────────────────────
 
     #[test]
@@ -1586,18 +5981,21 @@ mod tests {
                 name: "a".into(),
                 is_dir: false,
                 path: "a".into(),
+                ignored: false,
             },
             app::state::FileEntry {
                 depth: 0,
                 name: "b".into(),
                 is_dir: false,
                 path: "b".into(),
+                ignored: false,
             },
             app::state::FileEntry {
                 depth: 0,
                 name: "c".into(),
                 is_dir: false,
                 path: "c".into(),
+                ignored: false,
             },
         ];
 
@@ -1615,6 +6013,89 @@ mod tests {
         assert_eq!(app.browser.file_index, 1);
     }
 
+    #[test]
+    fn clean_prompt_swallows_keys_until_cancelled() {
+        let mut app = App::new();
+        app.clean_prompt = Some(app::CleanPrompt {
+            generator: None,
+            all: 2048,
+        });
+
+        // Without a generator to offer, `g` does nothing.
+        handle_key(&mut app, key_char('g'));
+        handle_key(&mut app, key_char('q'));
+        assert!(app.clean_prompt.is_some());
+        assert!(app.running);
+
+        handle_key(&mut app, key_char('n'));
+        assert!(app.clean_prompt.is_none());
+    }
+
+    #[test]
+    fn leftover_prompt_holds_keys_until_answered() {
+        let mut app = App::new();
+        app.leftover_prompt = Some(leftovers::Leftovers {
+            containers: vec!["lazyoav-1-1".into()],
+            ..Default::default()
+        });
+
+        handle_key(&mut app, key_char('q'));
+        assert!(app.leftover_prompt.is_some());
+        assert!(app.running);
+
+        // Leaving them touches nothing and, without Docker, starts nothing.
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.leftover_prompt.is_none());
+        assert!(app.pipeline_rx.is_none());
+    }
+
+    #[test]
+    fn trust_prompt_holds_keys_until_answered() {
+        let mut app = App::new();
+        app.trust_prompt = Some(app::TrustPrompt {
+            root: PathBuf::from("/nowhere"),
+            commands: vec!["1 hook".into()],
+        });
+
+        handle_key(&mut app, key_char('q'));
+        assert!(app.trust_prompt.is_some());
+        assert!(app.running);
+
+        // Declining records nothing and, without Docker, starts nothing.
+        handle_key(&mut app, key_char('n'));
+        assert!(app.trust_prompt.is_none());
+        assert!(app.pipeline_rx.is_none());
+    }
+
+    #[test]
+    fn browser_e_opens_the_generated_file_at_the_scroll_line() {
+        let mut app = App::new();
+        app.view_mode = ViewMode::CodeBrowser;
+        let entry = |name: &str, is_dir| app::state::FileEntry {
+            depth: 0,
+            name: name.into(),
+            is_dir,
+            path: PathBuf::from("/gen").join(name),
+            ignored: false,
+        };
+        app.browser.file_tree = vec![entry("api", true), entry("Pet.java", false)];
+        app.browser.file_content = Some(vec![String::new(); 50]);
+        app.browser.opened_file_index = Some(1);
+        app.browser.file_scroll = 29;
+
+        // A directory in the tree has nothing to open.
+        assert!(matches!(handle_key(&mut app, key_char('e')), Action::None));
+
+        app.browser.browser_focus = BrowserPanel::FileContent;
+        match handle_key(&mut app, key_char('e')) {
+            Action::OpenGenerated { path, line } => {
+                assert_eq!(path, PathBuf::from("/gen/Pet.java"));
+                assert_eq!(line, 30);
+            }
+            _ => panic!("expected OpenGenerated action"),
+        }
+    }
+
     #[test]
     fn browser_j_k_scrolls_file_content() {
         let mut app = App::new();
@@ -1680,6 +6161,46 @@ mod tests {
         assert_eq!(app.browser.browser_focus, BrowserPanel::FileTree);
     }
 
+    #[test]
+    fn browser_h_l_scroll_sideways_without_wrap() {
+        let mut app = App::new();
+        app.view_mode = ViewMode::CodeBrowser;
+        app.browser.browser_focus = BrowserPanel::FileContent;
+
+        handle_key(&mut app, key_char('w'));
+        assert!(!app.browser.file_wrap.wrap);
+        handle_key(&mut app, key_char('l'));
+        handle_key(&mut app, key_char('l'));
+        assert_eq!(app.browser.file_wrap.offset, 16);
+        handle_key(&mut app, key_char('h'));
+        handle_key(&mut app, key_char('h'));
+        assert_eq!(app.browser.file_wrap.offset, 0);
+        assert_eq!(app.browser.browser_focus, BrowserPanel::FileContent);
+
+        // At the left edge, h goes back to switching panels.
+        handle_key(&mut app, key_char('h'));
+        assert_eq!(app.browser.browser_focus, BrowserPanel::FileTree);
+    }
+
+    #[test]
+    fn raw_log_wrap_toggle_keeps_focus_on_l() {
+        let mut app = App::new();
+        app.focused_panel = Panel::Detail;
+        app.detail_tab = 1;
+
+        handle_key(&mut app, key_char('l'));
+        assert_eq!(app.focused_panel, Panel::SpecContext);
+
+        app.focused_panel = Panel::Detail;
+        handle_key(&mut app, key_char('w'));
+        handle_key(&mut app, key_char('l'));
+        assert_eq!(app.focused_panel, Panel::Detail);
+        assert_eq!(app.log_wrap.offset, 8);
+        handle_key(&mut app, key_char('w'));
+        assert!(app.log_wrap.wrap);
+        assert_eq!(app.log_wrap.offset, 0);
+    }
+
     // ── sync_generators_from_report ─────────────────────────────────
 
     #[test]
@@ -1728,22 +6249,375 @@ mod tests {
                 scope: "server".into(),
                 status: "pass".into(),
                 log: String::new(),
+                attempts: 1,
+                ..Default::default()
             })
             .collect();
         pipeline::ValidateReport {
             spec: "test.yaml".into(),
             mode: "both".into(),
             phases: Phases {
+                structure: None,
                 lint: None,
                 generate: Some(steps),
                 compile: None,
+                test: None,
+                code_lint: None,
+                docs: None,
+                security: None,
+                custom: None,
             },
             summary: Summary {
                 total: n,
                 passed: n,
                 failed: 0,
             },
+            images: Default::default(),
+        }
+    }
+
+    // ── pipeline warnings ───────────────────────────────────────────
+
+    #[test]
+    fn pipeline_warning_sets_status_and_keeps_running() {
+        let mut app = App::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        app.pipeline_rx = Some(rx);
+        app.validating = true;
+        tx.send(PipelineEvent::Warning("Ruleset: proxy unreachable".into()))
+            .unwrap();
+
+        drain_pipeline_events(&mut app);
+        let status = app.status_message.as_ref().unwrap();
+        assert_eq!(status.text, "Ruleset: proxy unreachable");
+        assert_eq!(status.level, StatusLevel::Warn);
+        assert!(app.validating);
+        assert!(app.pipeline_rx.is_some());
+    }
+
+    // ── config diagnostics ──────────────────────────────────────────
+
+    #[test]
+    fn diagnostics_key_without_problems_sets_status() {
+        let mut app = App::new();
+        handle_key(&mut app, key_char('D'));
+        assert!(!app.show_diagnostics);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn diagnostics_overlay_scrolls_and_closes() {
+        let mut app = App::new();
+        app.config_diagnostics = vec![
+            config::Diagnostic::error("mode", "bad"),
+            config::Diagnostic::warning("compile", "odd"),
+        ];
+        handle_key(&mut app, key_char('D'));
+        assert!(app.show_diagnostics);
+
+        handle_key(&mut app, key_char('j'));
+        handle_key(&mut app, key_char('j'));
+        assert_eq!(app.diagnostics_scroll, 1);
+        // `q` closes the overlay instead of quitting.
+        handle_key(&mut app, key_char('q'));
+        assert!(!app.show_diagnostics);
+        assert!(app.running);
+    }
+
+    #[test]
+    fn config_reload_with_unknown_key_applies_other_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(config::CONFIG_FILE),
+            "mode: both\nlintr: redocly\n",
+        )
+        .unwrap();
+        let mut app = App::new();
+        app.work_dir = dir.path().to_path_buf();
+        app.config = Some(config::Config::default());
+
+        check_config_file(&mut app);
+        assert_eq!(app.config.as_ref().unwrap().mode, config::Mode::Both);
+        let unknown = app
+            .config_diagnostics
+            .iter()
+            .find(|d| d.key == "lintr")
+            .unwrap();
+        assert_eq!(unknown.severity, config::Severity::Warning);
+    }
+
+    // ── open shell ──────────────────────────────────────────────────
+
+    #[test]
+    fn shell_on_passing_step_sets_status() {
+        let mut app = App::new();
+        app.report = Some(make_report_with_phases(2));
+        app.focused_panel = Panel::Phases;
+        let action = handle_key(&mut app, key_char('s'));
+        assert!(matches!(action, Action::None));
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .text
+                .contains("failed generate, compile, test or code lint")
+        );
+    }
+
+    #[test]
+    fn shell_on_failed_lint_sets_status() {
+        let mut app = App::new();
+        app.report = Some(make_report_with_lint());
+        app.focused_panel = Panel::Phases;
+        let action = handle_key(&mut app, key_char('s'));
+        assert!(matches!(action, Action::None));
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn shell_on_failed_step_opens_container() {
+        let mut app = App::new();
+        let mut report = make_report_with_phases(2);
+        report.phases.generate.as_mut().unwrap()[1].status = "fail".into();
+        app.report = Some(report);
+        app.spec_path = Some(PathBuf::from("/tmp/spec.yaml"));
+        app.focused_panel = Panel::Phases;
+        app.phase_index = 1;
+        match handle_key(&mut app, key_char('s')) {
+            Action::OpenShell { args, .. } => {
+                assert!(args.contains(&"-it".to_string()));
+                assert!(args.windows(2).any(|w| w == ["--entrypoint", "sh"]));
+            }
+            _ => panic!("expected OpenShell"),
+        }
+    }
+
+    #[test]
+    fn open_log_pages_the_saved_step_log() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log_file = tmp.path().join("generate-server-gen0.log");
+        std::fs::write(&log_file, "log\n").unwrap();
+        let mut app = App::new();
+        let mut report = make_report_with_phases(1);
+        report.phases.generate.as_mut().unwrap()[0].invocation = Some(pipeline::Invocation {
+            log_file: Some(log_file.clone()),
+            ..Default::default()
+        });
+        app.report = Some(report);
+        app.focused_panel = Panel::Phases;
+        match handle_key(&mut app, key_char('L')) {
+            Action::OpenPager { path } => assert_eq!(path, log_file),
+            _ => panic!("expected OpenPager"),
+        }
+
+        std::fs::remove_file(&log_file).unwrap();
+        app.focused_panel = Panel::Detail;
+        assert!(matches!(handle_key(&mut app, key_char('L')), Action::None));
+        assert!(app.status_message.as_ref().unwrap().text.contains("pruned"));
+    }
+
+    // ── suppressions ────────────────────────────────────────────────
+
+    fn app_with_suppressed_error() -> App {
+        let mut app = App::new();
+        app.report = Some(make_report_with_lint());
+        app.suppressions.push(Suppression {
+            rule: "rule-0".into(),
+            path: None,
+        });
+        app.set_lint_errors(make_lint_errors(2));
+        app
+    }
+
+    #[test]
+    fn shift_i_toggles_suppressed_findings() {
+        let mut app = app_with_suppressed_error();
+        assert_eq!(app.current_errors().len(), 1);
+        handle_key(&mut app, key_char('I'));
+        assert!(app.show_suppressed);
+        assert_eq!(app.current_errors().len(), 2);
+        assert_eq!(app.current_errors()[1].rule, "rule-0");
+    }
+
+    #[test]
+    fn i_on_suppressed_finding_explains_how_to_restore() {
+        let mut app = app_with_suppressed_error();
+        app.show_suppressed = true;
+        app.focused_panel = Panel::Errors;
+        app.error_index = 1;
+        handle_key(&mut app, key_char('i'));
+        let msg = &app.status_message.as_ref().unwrap().text;
+        assert!(msg.contains("already suppressed"), "{msg}");
+        assert!(msg.contains(suppress::IGNORE_FILE));
+    }
+
+    // ── export share ────────────────────────────────────────────────
+
+    #[test]
+    fn export_without_spec_warns() {
+        let mut app = App::new();
+        handle_key(&mut app, key_char('X'));
+        assert_eq!(
+            app.status_message.as_ref().unwrap().level,
+            StatusLevel::Warn
+        );
+    }
+
+    // ── onboarding tour ─────────────────────────────────────────────
+
+    fn app_with_tour() -> App {
+        let mut app = App::new();
+        // Default state records nothing, so tests don't touch the user config dir.
+        app.tour = Some(TourState::default());
+        app
+    }
+
+    #[test]
+    fn tour_focuses_highlighted_panels() {
+        let mut app = app_with_tour();
+        handle_key(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.focused_panel, Panel::Phases);
+        handle_key(&mut app, key(KeyCode::Right));
+        assert_eq!(app.focused_panel, Panel::Errors);
+        handle_key(&mut app, key(KeyCode::Left));
+        assert_eq!(app.tour.as_ref().unwrap().index, 1);
+    }
+
+    #[test]
+    fn tour_closes_after_last_step() {
+        let mut app = app_with_tour();
+        for _ in 0..app::tour::TOUR.len() {
+            assert!(app.tour.is_some());
+            handle_key(&mut app, key(KeyCode::Enter));
+        }
+        assert!(app.tour.is_none());
+        assert!(app.running);
+    }
+
+    #[test]
+    fn tour_esc_skips_with_replay_hint() {
+        let mut app = app_with_tour();
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.tour.is_none());
+        assert!(app.status_message.as_ref().unwrap().text.contains('T'));
+    }
+
+    // ── init wizard ─────────────────────────────────────────────────
+
+    fn app_with_wizard() -> App {
+        let mut app = App::new();
+        app.init_wizard = Some(InitWizardState::new(
+            vec!["openapi.yaml".into()],
+            config::Linter::Spectral,
+            &[],
+        ));
+        app
+    }
+
+    #[test]
+    fn wizard_navigates_steps() {
+        let mut app = app_with_wizard();
+        handle_key(&mut app, key(KeyCode::Enter));
+        handle_key(&mut app, key(KeyCode::Enter));
+        assert_eq!(
+            app.init_wizard.as_ref().unwrap().step,
+            WizardStep::Generators
+        );
+
+        handle_key(&mut app, key_char(' '));
+        handle_key(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.init_wizard.as_ref().unwrap().step, WizardStep::Review);
+
+        handle_key(&mut app, key(KeyCode::Left));
+        assert_eq!(
+            app.init_wizard.as_ref().unwrap().step,
+            WizardStep::Generators
+        );
+    }
+
+    #[test]
+    fn wizard_requires_a_generator() {
+        let mut app = app_with_wizard();
+        handle_key(&mut app, key(KeyCode::Enter));
+        handle_key(&mut app, key(KeyCode::Enter));
+        handle_key(&mut app, key(KeyCode::Enter));
+        assert_eq!(
+            app.init_wizard.as_ref().unwrap().step,
+            WizardStep::Generators
+        );
+        assert_eq!(
+            app.status_message.as_ref().unwrap().level,
+            StatusLevel::Warn
+        );
+    }
+
+    #[test]
+    fn wizard_esc_skips_setup() {
+        let mut app = app_with_wizard();
+        handle_key(&mut app, key(KeyCode::Esc));
+        assert!(app.init_wizard.is_none());
+        assert!(app.running);
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .text
+                .contains("Skipped setup")
+        );
+    }
+
+    #[test]
+    fn wizard_swallows_global_keys() {
+        let mut app = app_with_wizard();
+        handle_key(&mut app, key_char('q'));
+        assert!(app.running);
+        assert!(app.init_wizard.is_some());
+    }
+
+    #[test]
+    fn dry_run_overlay_selects_steps_and_closes() {
+        let mut app = App::new();
+        app.dry_run = Some(app::DryRunView {
+            steps: vec![
+                ("lint".into(), "docker run --rm spectral lint".into()),
+                ("go generate".into(), "docker run --rm cli generate".into()),
+            ],
+            selected: 0,
+        });
+        for _ in 0..3 {
+            handle_key(&mut app, key_char('j'));
+        }
+        assert_eq!(app.dry_run.as_ref().unwrap().selected, 1);
+        handle_key(&mut app, key_char('r'));
+        assert!(!app.validating);
+        handle_key(&mut app, key_char('q'));
+        assert!(app.dry_run.is_none());
+        assert!(app.running);
+    }
+
+    #[test]
+    fn project_switcher_opens_on_the_current_project() {
+        let mut app = App::new();
+        handle_key(&mut app, key_char('J'));
+        assert!(app.project_switcher.is_none());
+
+        app.projects = ["", "services/orders", "services/billing"]
+            .into_iter()
+            .map(|dir| spec::Project {
+                dir: dir.into(),
+                has_config: true,
+            })
+            .collect();
+        app.project = Some(1);
+        handle_key(&mut app, key_char('J'));
+        assert_eq!(app.project_switcher, Some(1));
+        for _ in 0..3 {
+            handle_key(&mut app, key_char('j'));
         }
+        assert_eq!(app.project_switcher, Some(2));
+        handle_key(&mut app, key_char('q'));
+        assert!(app.project_switcher.is_none());
+        assert!(app.running);
     }
 
     /// Build a report with a lint phase so current_errors works.
@@ -1753,19 +6627,28 @@ mod tests {
             spec: "test.yaml".into(),
             mode: "both".into(),
             phases: Phases {
+                structure: None,
                 lint: Some(LintResult {
                     linter: "spectral".into(),
                     status: "fail".into(),
                     log: String::new(),
+                    attempts: 1,
+                    ..Default::default()
                 }),
                 generate: None,
                 compile: None,
+                test: None,
+                code_lint: None,
+                docs: None,
+                security: None,
+                custom: None,
             },
             summary: Summary {
                 total: 1,
                 passed: 0,
                 failed: 1,
             },
+            images: Default::default(),
         }
     }
 
@@ -1774,6 +6657,7 @@ mod tests {
             .map(|i| log_parser::LintError {
                 line: i + 1,
                 col: 1,
+                end: None,
                 severity: log_parser::Severity::Error,
                 rule: format!("rule-{i}"),
                 message: format!("error {i}"),
@@ -0,0 +1,278 @@
+//! What an OpenAPI 3.0 spec has to change to become a valid 3.1 one.
+
+use serde_yaml::Value;
+
+use crate::openapi::{escape, key};
+
+/// A 3.0 construct that 3.1 spells differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// Rule id, e.g. `migrate-nullable`.
+    pub rule: &'static str,
+    /// JSON pointer to the keyword to rewrite.
+    pub pointer: String,
+    pub message: String,
+}
+
+pub const OPENAPI_VERSION: &str = "migrate-openapi-version";
+pub const NULLABLE: &str = "migrate-nullable";
+pub const EXCLUSIVE_BOUND: &str = "migrate-exclusive-bound";
+pub const SCHEMA_EXAMPLE: &str = "migrate-schema-example";
+pub const WEBHOOKS: &str = "migrate-webhooks";
+
+/// Whether `rule` is one of the migration rules.
+pub fn is_migration(rule: &str) -> bool {
+    [
+        OPENAPI_VERSION,
+        NULLABLE,
+        EXCLUSIVE_BOUND,
+        SCHEMA_EXAMPLE,
+        WEBHOOKS,
+    ]
+    .contains(&rule)
+}
+
+/// Everything in a 3.0 spec that needs rewriting for 3.1, version first.
+/// Specs that aren't 3.0 need nothing.
+pub fn analyze(spec: &Value) -> Vec<Migration> {
+    let Some(version) = spec.get("openapi").and_then(version_str) else {
+        return Vec::new();
+    };
+    if !version.starts_with("3.0") {
+        return Vec::new();
+    }
+
+    let mut found = vec![Migration {
+        rule: OPENAPI_VERSION,
+        pointer: "/openapi".into(),
+        message: format!("openapi {version} → 3.1.0"),
+    }];
+    if spec.get("webhooks").is_none() {
+        let anchor = if spec.get("paths").is_some() {
+            "/paths"
+        } else {
+            "/openapi"
+        };
+        found.push(Migration {
+            rule: WEBHOOKS,
+            pointer: anchor.into(),
+            message: "3.1 describes incoming webhooks in a top-level `webhooks` section; add an empty one to fill in".into(),
+        });
+    }
+
+    let mut walker = Walker { found };
+    if let Some(schemas) = spec
+        .get("components")
+        .and_then(|c| c.get("schemas"))
+        .and_then(Value::as_mapping)
+    {
+        for (name, schema) in schemas {
+            walker.schema(
+                schema,
+                &format!("/components/schemas/{}", escape(&key(name))),
+            );
+        }
+    }
+    if let Some(paths) = spec.get("paths") {
+        walker.find_schemas(paths, "/paths");
+    }
+    if let Some(components) = spec.get("components").and_then(Value::as_mapping) {
+        for (section, value) in components {
+            let section = key(section);
+            if section != "schemas" {
+                walker.find_schemas(value, &format!("/components/{}", escape(&section)));
+            }
+        }
+    }
+    walker.found
+}
+
+/// `openapi: 3.0.3` parses as a string, `openapi: 3.0` as a number.
+fn version_str(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+struct Walker {
+    found: Vec<Migration>,
+}
+
+impl Walker {
+    /// Look for `schema` keys (parameters, media types, headers) outside of
+    /// `components/schemas`.
+    fn find_schemas(&mut self, node: &Value, pointer: &str) {
+        match node {
+            Value::Mapping(map) => {
+                for (k, value) in map {
+                    let k = key(k);
+                    let child = format!("{pointer}/{}", escape(&k));
+                    if k == "schema" {
+                        self.schema(value, &child);
+                    } else {
+                        self.find_schemas(value, &child);
+                    }
+                }
+            }
+            Value::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.find_schemas(item, &format!("{pointer}/{i}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check one schema and recurse into its subschemas. Outside schemas,
+    /// `example` is still valid in 3.1, so only schemas are looked at.
+    fn schema(&mut self, node: &Value, pointer: &str) {
+        if !node.is_mapping() || node.get("$ref").is_some() {
+            return;
+        }
+
+        if let Some(nullable) = node.get("nullable") {
+            let message = match (nullable.as_bool(), node.get("type").and_then(Value::as_str)) {
+                (Some(false), _) => "nullable: false is the default; drop it".to_string(),
+                (_, Some(ty)) => format!("nullable is gone in 3.1; use type: [{ty}, \"null\"]"),
+                _ => "nullable is gone in 3.1; allow null with a type array or oneOf".to_string(),
+            };
+            self.report(NULLABLE, format!("{pointer}/nullable"), message);
+        }
+        for (keyword, bound) in [
+            ("exclusiveMinimum", "minimum"),
+            ("exclusiveMaximum", "maximum"),
+        ] {
+            let Some(exclusive) = node.get(keyword).and_then(Value::as_bool) else {
+                continue;
+            };
+            let message = if exclusive {
+                format!("{keyword} is a number in 3.1; move the {bound} into it")
+            } else {
+                format!("{keyword}: false is the default; drop it")
+            };
+            self.report(EXCLUSIVE_BOUND, format!("{pointer}/{keyword}"), message);
+        }
+        if node.get("example").is_some() {
+            self.report(
+                SCHEMA_EXAMPLE,
+                format!("{pointer}/example"),
+                "example is deprecated in 3.1 schemas; use an examples list".into(),
+            );
+        }
+
+        if let Some(properties) = node.get("properties").and_then(Value::as_mapping) {
+            for (name, prop) in properties {
+                self.schema(
+                    prop,
+                    &format!("{pointer}/properties/{}", escape(&key(name))),
+                );
+            }
+        }
+        for keyword in ["items", "additionalProperties", "not"] {
+            if let Some(sub) = node.get(keyword) {
+                self.schema(sub, &format!("{pointer}/{keyword}"));
+            }
+        }
+        for keyword in ["allOf", "oneOf", "anyOf"] {
+            for (i, part) in node
+                .get(keyword)
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                self.schema(part, &format!("{pointer}/{keyword}/{i}"));
+            }
+        }
+    }
+
+    fn report(&mut self, rule: &'static str, pointer: String, message: String) {
+        self.found.push(Migration {
+            rule,
+            pointer,
+            message,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(yaml: &str) -> Vec<(&'static str, String)> {
+        let spec: Value = serde_yaml::from_str(yaml).unwrap();
+        analyze(&spec)
+            .into_iter()
+            .map(|m| (m.rule, m.pointer))
+            .collect()
+    }
+
+    #[test]
+    fn analyze_finds_what_3_1_spells_differently() {
+        let found = rules(
+            r#"
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          example: 10
+          schema:
+            type: integer
+            minimum: 0
+            exclusiveMinimum: true
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+          nullable: true
+          example: Rex
+        tags:
+          type: array
+          items:
+            type: string
+            exclusiveMaximum: false
+"#,
+        );
+        assert_eq!(
+            found,
+            [
+                (OPENAPI_VERSION, "/openapi".to_string()),
+                (WEBHOOKS, "/paths".to_string()),
+                (
+                    NULLABLE,
+                    "/components/schemas/Pet/properties/name/nullable".to_string()
+                ),
+                (
+                    SCHEMA_EXAMPLE,
+                    "/components/schemas/Pet/properties/name/example".to_string()
+                ),
+                (
+                    EXCLUSIVE_BOUND,
+                    "/components/schemas/Pet/properties/tags/items/exclusiveMaximum".to_string()
+                ),
+                (
+                    EXCLUSIVE_BOUND,
+                    "/paths/~1pets/get/parameters/0/schema/exclusiveMinimum".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn analyze_leaves_3_1_specs_alone() {
+        assert!(rules("openapi: 3.1.0\npaths: {}\n").is_empty());
+        assert!(rules("swagger: '2.0'\n").is_empty());
+        assert_eq!(
+            rules("openapi: 3.0.0\nwebhooks: {}\n"),
+            [(OPENAPI_VERSION, "/openapi".to_string())]
+        );
+    }
+}
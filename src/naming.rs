@@ -0,0 +1,332 @@
+use serde_yaml::Value;
+
+use crate::config::{Case, Naming};
+use crate::openapi::escape;
+
+/// Rule id for a schema name breaking `naming.schemas`.
+pub const SCHEMA: &str = "naming-schema";
+/// Rule id for a property name breaking `naming.properties`.
+pub const PROPERTY: &str = "naming-property";
+/// Rule id for a path breaking `naming.paths`.
+pub const PATH: &str = "naming-path";
+
+/// A name that doesn't follow its configured convention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// [`SCHEMA`], [`PROPERTY`] or [`PATH`].
+    pub rule: &'static str,
+    /// JSON pointer to the schema, property or path item.
+    pub pointer: String,
+    pub name: String,
+    /// The name in the expected case.
+    pub rename: String,
+    pub message: String,
+}
+
+/// Check schema, property and path names against the conventions set in
+/// `naming`.
+pub fn check(spec: &Value, naming: &Naming) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    if let Some(case) = naming.schemas {
+        let schemas = spec
+            .get("components")
+            .and_then(|c| c.get("schemas"))
+            .and_then(Value::as_mapping);
+        for name in schemas
+            .into_iter()
+            .flatten()
+            .filter_map(|(k, _)| k.as_str())
+            .filter(|name| !reserved(name))
+        {
+            let rename = convert(name, case);
+            if rename != name {
+                violations.push(Violation {
+                    rule: SCHEMA,
+                    pointer: format!("/components/schemas/{}", escape(name)),
+                    message: format!(
+                        "schema {name} is not {} \u{2014} rename to {rename}",
+                        case.as_str()
+                    ),
+                    name: name.to_string(),
+                    rename,
+                });
+            }
+        }
+    }
+
+    if let Some(case) = naming.properties {
+        properties(spec, String::new(), case, &mut violations);
+    }
+
+    if let Some(case) = naming.paths {
+        let paths = spec.get("paths").and_then(Value::as_mapping);
+        for path in paths.into_iter().flatten().filter_map(|(k, _)| k.as_str()) {
+            let rename = path
+                .split('/')
+                .map(|segment| convert_segment(segment, case))
+                .collect::<Vec<_>>()
+                .join("/");
+            if rename != path {
+                violations.push(Violation {
+                    rule: PATH,
+                    pointer: format!("/paths/{}", escape(path)),
+                    message: format!(
+                        "path {path} is not {} \u{2014} rename to {rename}",
+                        case.as_str()
+                    ),
+                    name: path.to_string(),
+                    rename,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+/// Collect the property names under `node`, at `pointer`, that break `case`.
+fn properties(node: &Value, pointer: String, case: Case, out: &mut Vec<Violation>) {
+    match node {
+        Value::Mapping(map) => {
+            for (key, value) in map {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                // Examples are data, not schemas.
+                if key == "example" || key == "examples" || key.starts_with("x-") {
+                    continue;
+                }
+                let here = format!("{pointer}/{}", escape(key));
+                let Some(props) = value.as_mapping().filter(|_| key == "properties") else {
+                    properties(value, here, case, out);
+                    continue;
+                };
+                for (name, schema) in props {
+                    let Some(name) = name.as_str() else {
+                        continue;
+                    };
+                    let at = format!("{here}/{}", escape(name));
+                    let rename = convert(name, case);
+                    if rename != name && !reserved(name) {
+                        out.push(Violation {
+                            rule: PROPERTY,
+                            pointer: at.clone(),
+                            message: format!(
+                                "property {name} is not {} \u{2014} rename to {rename}",
+                                case.as_str()
+                            ),
+                            name: name.to_string(),
+                            rename,
+                        });
+                    }
+                    properties(schema, at, case, out);
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                properties(item, format!("{pointer}/{i}"), case, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `name` follows a convention of its own, like HAL's `_links`
+/// and `_embedded`, that no case applies to.
+fn reserved(name: &str) -> bool {
+    name.starts_with('_')
+}
+
+/// A path segment in `case`, converting only the text around its path
+/// parameters: `file_{fileId}` is `file-{fileId}` in kebab-case. Reserved
+/// segments stay as they are.
+fn convert_segment(segment: &str, case: Case) -> String {
+    if reserved(segment) {
+        return segment.to_string();
+    }
+    let mut out = String::new();
+    let mut rest = segment;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&convert_text(&rest[..open], case));
+        let Some(close) = rest[open..].find('}') else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+        out.push_str(&rest[open..=open + close]);
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(&convert_text(rest, case));
+    out
+}
+
+/// Text of a path segment in `case`. Separators at either end, beside a
+/// path parameter or the segment's edge, stay, in the case's own style.
+fn convert_text(text: &str, case: Case) -> String {
+    let is_word = |c: char| c.is_alphanumeric();
+    let (Some(start), Some(end)) = (text.find(is_word), text.rfind(is_word)) else {
+        return text.to_string();
+    };
+    let end = end + text[end..].chars().next().map_or(0, char::len_utf8);
+    let separators = |s: &str| -> String {
+        s.chars()
+            .map(|c| match (c, case) {
+                ('_' | '-', Case::Snake) => '_',
+                ('_' | '-', Case::Kebab) => '-',
+                _ => c,
+            })
+            .collect()
+    };
+    format!(
+        "{}{}{}",
+        separators(&text[..start]),
+        convert(&text[start..end], case),
+        separators(&text[end..])
+    )
+}
+
+/// `name` in `case`: `pet_owner`, `petOwner` and `PetOwner` are all
+/// `PetOwner` in PascalCase.
+pub fn convert(name: &str, case: Case) -> String {
+    let words = words(name);
+    let capitalized = |w: &str| {
+        let mut chars = w.chars();
+        chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+    match case {
+        Case::Pascal => words.iter().map(|w| capitalized(w)).collect(),
+        Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.clone() } else { capitalized(w) })
+            .collect(),
+        Case::Snake => words.join("_"),
+        Case::Kebab => words.join("-"),
+    }
+}
+
+/// Lowercase words of an identifier, split at `_`, `-`, spaces and case
+/// changes; `HTTPStatus2xx` is `http`, `status2xx`.
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+        let boundary = c.is_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_lowercase() || p.is_ascii_digit() || (p.is_uppercase() && next_lower)
+            });
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_convert_between_cases() {
+        assert_eq!(convert("pet_owner", Case::Pascal), "PetOwner");
+        assert_eq!(convert("HTTPStatus", Case::Pascal), "HttpStatus");
+        assert_eq!(convert("PetOwner", Case::Camel), "petOwner");
+        assert_eq!(convert("petOwnerID", Case::Snake), "pet_owner_id");
+        assert_eq!(convert("petOwners", Case::Kebab), "pet-owners");
+        assert_eq!(convert("v2", Case::Kebab), "v2");
+    }
+
+    #[test]
+    fn path_parameters_keep_their_names() {
+        assert_eq!(
+            convert_segment("file_{fileId}", Case::Kebab),
+            "file-{fileId}"
+        );
+        assert_eq!(
+            convert_segment("{fileId}.json", Case::Kebab),
+            "{fileId}.json"
+        );
+        assert_eq!(convert_segment("petOwners", Case::Snake), "pet_owners");
+        assert_eq!(convert_segment("_internal", Case::Kebab), "_internal");
+    }
+
+    #[test]
+    fn checks_only_the_configured_conventions() {
+        let spec: Value = serde_yaml::from_str(
+            r#"
+paths:
+  /petOwners/{ownerId}:
+    get:
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                properties:
+                  total_count: {type: integer}
+                example:
+                  some_value: 1
+components:
+  schemas:
+    pet_owner:
+      properties:
+        firstName: {type: string}
+        last_name: {type: string}
+        _links: {type: object}
+    _Internal:
+      type: object
+"#,
+        )
+        .unwrap();
+
+        let naming = Naming {
+            schemas: Some(Case::Pascal),
+            properties: Some(Case::Camel),
+            paths: Some(Case::Kebab),
+        };
+        let violations = check(&spec, &naming);
+        let found: Vec<(&str, &str, &str)> = violations
+            .iter()
+            .map(|v| (v.rule, v.pointer.as_str(), v.rename.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (SCHEMA, "/components/schemas/pet_owner", "PetOwner"),
+                (
+                    PROPERTY,
+                    "/paths/~1petOwners~1{ownerId}/get/responses/200/content/application~1json/schema/properties/total_count",
+                    "totalCount"
+                ),
+                (
+                    PROPERTY,
+                    "/components/schemas/pet_owner/properties/last_name",
+                    "lastName"
+                ),
+                (
+                    PATH,
+                    "/paths/~1petOwners~1{ownerId}",
+                    "/pet-owners/{ownerId}"
+                ),
+            ]
+        );
+        assert!(check(&spec, &Naming::default()).is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+// Completion notifications — the terminal bell and desktop notifications.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use lazyoav::config::Notify;
+use lazyoav::pipeline::ValidateReport;
+
+const TITLE: &str = "lazyoav";
+
+/// Signal a finished run as configured. Failures are ignored: a missing
+/// notifier shouldn't interrupt the session.
+pub fn completed(cfg: &Notify, report: &ValidateReport, lint_errors: usize) {
+    if cfg.bell {
+        let mut out = io::stdout();
+        let _ = out.write_all(b"\x07").and_then(|()| out.flush());
+    }
+    if cfg.desktop {
+        let _ = desktop(&summary(report, lint_errors));
+    }
+}
+
+/// One-line result, e.g. `Failed: 1 of 3 steps, 4 lint errors`.
+pub fn summary(report: &ValidateReport, lint_errors: usize) -> String {
+    let s = &report.summary;
+    let mut text = if s.failed == 0 {
+        format!("Passed: {} step(s)", s.passed)
+    } else {
+        format!("Failed: {} of {} step(s)", s.failed, s.total)
+    };
+    if lint_errors > 0 {
+        text.push_str(&format!(", {lint_errors} lint error(s)"));
+    }
+    text
+}
+
+/// Hand the message to the platform's notifier without waiting for it.
+fn desktop(body: &str) -> io::Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(TITLE)
+        );
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &script]);
+        cmd
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.args([TITLE, body]);
+        cmd
+    };
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lazyoav::pipeline::{Phases, Summary};
+
+    fn report(total: usize, passed: usize) -> ValidateReport {
+        ValidateReport {
+            spec: "openapi.yaml".into(),
+            mode: "server".into(),
+            phases: Phases::default(),
+            summary: Summary {
+                total,
+                passed,
+                failed: total - passed,
+            },
+            images: Default::default(),
+        }
+    }
+
+    #[test]
+    fn summary_states_result_and_error_count() {
+        assert_eq!(summary(&report(3, 3), 0), "Passed: 3 step(s)");
+        assert_eq!(
+            summary(&report(3, 2), 4),
+            "Failed: 1 of 3 step(s), 4 lint error(s)"
+        );
+        assert_eq!(applescript_string(r#"say "hi"\"#), r#""say \"hi\"\\""#);
+    }
+}
@@ -0,0 +1,95 @@
+//! Walking an OpenAPI document: its operations, its mapping keys and JSON
+//! pointers into it, shared by the analyses.
+
+use serde_yaml::Value;
+
+/// The fields of a path item that hold an operation.
+pub const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Every operation with its path and method, in document order.
+pub fn operations(spec: &Value) -> impl Iterator<Item = (&str, &'static str, &Value)> {
+    spec.get("paths")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+        .filter_map(|(path, item)| Some((path.as_str()?, item)))
+        .flat_map(|(path, item)| {
+            METHODS
+                .iter()
+                .filter_map(move |&method| Some((path, method, item.get(method)?)))
+        })
+}
+
+/// A mapping key as text, also when YAML read it as a number or boolean,
+/// like the status code `200`.
+pub fn key(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Escape a JSON pointer segment.
+pub fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// The node a local `$ref` like `#/components/schemas/Pet` points at.
+pub fn lookup_ref<'a>(spec: &'a Value, reference: &str) -> Option<&'a Value> {
+    reference
+        .strip_prefix("#/")?
+        .split('/')
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .try_fold(spec, |node, segment| node.get(segment.as_str()))
+}
+
+/// Follow `node`'s local `$ref`, once; `node` itself when it has none or
+/// it doesn't resolve.
+pub fn resolve_ref<'a>(spec: &'a Value, node: &'a Value) -> &'a Value {
+    node.get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| lookup_ref(spec, reference))
+        .unwrap_or(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations_come_with_their_path_and_method() {
+        let spec: Value = serde_yaml::from_str(
+            "paths:\n  /pets:\n    parameters: []\n    get: {operationId: a}\n    post: {operationId: b}\n  /pets/{id}:\n    delete: {}\n",
+        )
+        .unwrap();
+        let found: Vec<_> = operations(&spec).map(|(p, m, _)| (p, m)).collect();
+        assert_eq!(
+            found,
+            [
+                ("/pets", "get"),
+                ("/pets", "post"),
+                ("/pets/{id}", "delete")
+            ]
+        );
+        assert_eq!(key(&serde_yaml::from_str("200").unwrap()), "200");
+        assert_eq!(escape("/pets/{id}~"), "~1pets~1{id}~0");
+    }
+
+    #[test]
+    fn refs_resolve_locally_or_stay_put() {
+        let spec: Value = serde_yaml::from_str(
+            "components:\n  schemas:\n    a/b: {type: string}\n    Pet: {$ref: '#/components/schemas/a~1b'}\n",
+        )
+        .unwrap();
+        let pet = &spec["components"]["schemas"]["Pet"];
+        assert_eq!(resolve_ref(&spec, pet)["type"], "string");
+        let remote: Value = serde_yaml::from_str("$ref: 'other.yaml#/Pet'").unwrap();
+        assert_eq!(resolve_ref(&spec, &remote), &remote);
+        assert_eq!(lookup_ref(&spec, "#/components/missing"), None);
+    }
+}
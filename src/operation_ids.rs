@@ -0,0 +1,197 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde_yaml::Value;
+
+use crate::openapi::{self, escape};
+
+/// Rule id for an operationId another operation already uses.
+pub const DUPLICATE: &str = "duplicate-operation-id";
+/// Rule id for an operationId that only differs from another one in case
+/// or separators, so generators derive the same method name from both.
+pub const CONFLICTING: &str = "conflicting-operation-id";
+
+/// An operation whose operationId collides with an earlier one's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+    /// [`DUPLICATE`] or [`CONFLICTING`].
+    pub rule: &'static str,
+    /// JSON pointer to the `operationId` field.
+    pub pointer: String,
+    pub operation_id: String,
+    pub message: String,
+    /// An operationId free in the spec, derived from the method and path.
+    pub rename: String,
+}
+
+/// Find operations whose operationId an earlier operation (in document
+/// order) already uses, exactly or up to case and separators. The first
+/// operation of each group keeps its id; each later one gets a rename.
+pub fn find(spec: &Value) -> Vec<Collision> {
+    let operations = operations(spec);
+    let mut taken: BTreeSet<String> = operations.iter().map(|o| normalize(&o.id)).collect();
+    let mut first: HashMap<String, &Operation> = HashMap::new();
+    let mut collisions = Vec::new();
+
+    for op in &operations {
+        let Some(earlier) = first.get(&normalize(&op.id)) else {
+            first.insert(normalize(&op.id), op);
+            continue;
+        };
+        let (rule, message) = if earlier.id == op.id {
+            (
+                DUPLICATE,
+                format!(
+                    "operationId {} is also used by {}",
+                    op.id,
+                    earlier.describe()
+                ),
+            )
+        } else {
+            (
+                CONFLICTING,
+                format!(
+                    "operationId {} collides with {} ({}) once generators normalize names",
+                    op.id,
+                    earlier.id,
+                    earlier.describe()
+                ),
+            )
+        };
+        let rename = unique(&op.derived_id(), &taken);
+        taken.insert(normalize(&rename));
+        collisions.push(Collision {
+            rule,
+            pointer: format!("/paths/{}/{}/operationId", escape(&op.path), op.method),
+            operation_id: op.id.clone(),
+            message,
+            rename,
+        });
+    }
+    collisions
+}
+
+struct Operation {
+    path: String,
+    method: &'static str,
+    id: String,
+}
+
+impl Operation {
+    fn describe(&self) -> String {
+        format!("{} {}", self.method.to_uppercase(), self.path)
+    }
+
+    /// `GET /pets/{petId}/owner` is `getPetsByPetIdOwner`.
+    fn derived_id(&self) -> String {
+        let mut id = self.method.to_string();
+        for segment in self.path.split('/').filter(|s| !s.is_empty()) {
+            let param = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'));
+            if param.is_some() {
+                id.push_str("By");
+            }
+            for word in param
+                .unwrap_or(segment)
+                .split(|c: char| !c.is_ascii_alphanumeric())
+            {
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    id.extend(first.to_uppercase());
+                    id.push_str(chars.as_str());
+                }
+            }
+        }
+        id
+    }
+}
+
+fn operations(spec: &Value) -> Vec<Operation> {
+    openapi::operations(spec)
+        .filter_map(|(path, method, op)| {
+            Some(Operation {
+                path: path.to_string(),
+                method,
+                id: op.get("operationId")?.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// What generators make of a name: `list_pets`, `listPets` and `ListPets`
+/// all become the same method.
+fn normalize(id: &str) -> String {
+    id.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// `id`, or `id2`, `id3`, ... if it's taken; `taken` holds normalized ids.
+fn unique(id: &str, taken: &BTreeSet<String>) -> String {
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                id.to_string()
+            } else {
+                format!("{id}{n}")
+            }
+        })
+        .find(|candidate| !taken.contains(&normalize(candidate)))
+        .unwrap_or_else(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_operations_get_a_free_derived_id() {
+        let spec: Value = serde_yaml::from_str(
+            r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+    post:
+      operationId: listPets
+  /pets/{petId}:
+    get:
+      operationId: list_pets
+    delete:
+      operationId: deletePet
+  /v2/pets:
+    post:
+      operationId: listPets
+  /legacy:
+    post:
+      operationId: postPets
+"#,
+        )
+        .unwrap();
+
+        let collisions = find(&spec);
+        let found: Vec<(&str, &str, &str)> = collisions
+            .iter()
+            .map(|c| (c.rule, c.pointer.as_str(), c.rename.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DUPLICATE, "/paths/~1pets/post/operationId", "postPets2"),
+                (
+                    CONFLICTING,
+                    "/paths/~1pets~1{petId}/get/operationId",
+                    "getPetsByPetId"
+                ),
+                (
+                    DUPLICATE,
+                    "/paths/~1v2~1pets/post/operationId",
+                    "postV2Pets"
+                ),
+            ]
+        );
+        assert_eq!(
+            collisions[1].message,
+            "operationId list_pets collides with listPets (GET /pets) once generators normalize names"
+        );
+    }
+}
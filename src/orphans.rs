@@ -0,0 +1,278 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use serde_yaml::Value;
+
+use crate::graph::{collect_refs, component_of};
+use crate::openapi::{escape, operations};
+
+/// Component sections checked for references, with the rule id and the noun
+/// used in messages.
+const SECTIONS: &[(&str, &str, &str)] = &[
+    ("schemas", "unused-schema", "schema"),
+    ("parameters", "unused-parameter", "parameter"),
+    ("responses", "unused-response", "response"),
+    ("requestBodies", "unused-request-body", "request body"),
+    ("headers", "unused-header", "header"),
+    ("examples", "unused-example", "example"),
+];
+
+/// A definition nothing in the spec uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orphan {
+    /// Rule id, e.g. `unused-schema`.
+    pub rule: &'static str,
+    /// JSON pointer to the definition.
+    pub pointer: String,
+    pub message: String,
+}
+
+/// List components that can't be reached from the paths (or webhooks) by
+/// following local `$ref`s, security schemes no requirement names, and
+/// declared tags no operation uses.
+///
+/// A component only referenced by other unused components is unused too. A
+/// schema a `discriminator.mapping` names counts as referenced.
+pub fn find(spec: &Value) -> Vec<Orphan> {
+    let mut orphans = Vec::new();
+    let components = spec.get("components");
+
+    let mut reached = BTreeSet::new();
+    let mut queue: VecDeque<String> = VecDeque::new();
+    if let Some(root) = spec.as_mapping() {
+        for (key, value) in root {
+            if key.as_str() != Some("components") {
+                collect_uses(value, &mut queue);
+            }
+        }
+    }
+    while let Some(reference) = queue.pop_front() {
+        let Some(target) = component_of(&reference) else {
+            continue;
+        };
+        if reached.insert(target.clone())
+            && let Some(node) = components
+                .and_then(|c| c.get(target.0.as_str()))
+                .and_then(|s| s.get(target.1.as_str()))
+        {
+            collect_uses(node, &mut queue);
+        }
+    }
+
+    for (section, rule, noun) in SECTIONS {
+        for name in names(components.and_then(|c| c.get(*section))) {
+            if !reached.contains(&(section.to_string(), name.clone())) {
+                orphans.push(Orphan {
+                    rule,
+                    pointer: format!("/components/{section}/{}", escape(&name)),
+                    message: format!("{noun} {name} is defined but never referenced"),
+                });
+            }
+        }
+    }
+
+    let required_schemes: BTreeSet<String> = std::iter::once(spec)
+        .chain(operations(spec).map(|(_, _, op)| op))
+        .filter_map(|node| node.get("security")?.as_sequence())
+        .flatten()
+        .flat_map(names_of)
+        .collect();
+    for name in names(components.and_then(|c| c.get("securitySchemes"))) {
+        if !required_schemes.contains(&name) {
+            orphans.push(Orphan {
+                rule: "unused-security-scheme",
+                pointer: format!("/components/securitySchemes/{}", escape(&name)),
+                message: format!("security scheme {name} is never required"),
+            });
+        }
+    }
+
+    let used_tags: BTreeSet<&str> = operations(spec)
+        .map(|(_, _, op)| op)
+        .filter_map(|op| op.get("tags")?.as_sequence())
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    for (i, tag) in spec
+        .get("tags")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        if let Some(name) = tag.get("name").and_then(Value::as_str)
+            && !used_tags.contains(name)
+        {
+            orphans.push(Orphan {
+                rule: "unused-tag",
+                pointer: format!("/tags/{i}"),
+                message: format!("tag {name} is declared but no operation uses it"),
+            });
+        }
+    }
+
+    orphans
+}
+
+/// Every `$ref` below `node`, and every schema a `discriminator.mapping`
+/// below it selects, by reference or by name.
+fn collect_uses(node: &Value, out: &mut VecDeque<String>) {
+    collect_refs(node, out);
+    collect_mappings(node, out);
+}
+
+fn collect_mappings(node: &Value, out: &mut VecDeque<String>) {
+    match node {
+        Value::Mapping(map) => {
+            let mapping = map
+                .get("discriminator")
+                .and_then(|d| d.get("mapping"))
+                .and_then(Value::as_mapping);
+            for target in mapping
+                .into_iter()
+                .flatten()
+                .filter_map(|(_, v)| v.as_str())
+            {
+                if target.contains('#') || target.contains('/') {
+                    out.push_back(target.to_string());
+                } else {
+                    out.push_back(format!("#/components/schemas/{}", escape(target)));
+                }
+            }
+            for value in map.values() {
+                collect_mappings(value, out);
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                collect_mappings(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn names(section: Option<&Value>) -> Vec<String> {
+    section.map_or_else(Vec::new, names_of)
+}
+
+fn names_of(node: &Value) -> Vec<String> {
+    node.as_mapping()
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, _)| k.as_str().map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_a_discriminator_maps_to_are_used() {
+        let spec: Value = serde_yaml::from_str(
+            r##"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+      discriminator:
+        propertyName: kind
+        mapping:
+          cat: '#/components/schemas/Cat'
+          dog: Dog
+    Cat:
+      type: object
+    Dog:
+      type: object
+    Bird:
+      type: object
+"##,
+        )
+        .unwrap();
+
+        let found: Vec<String> = find(&spec).into_iter().map(|o| o.pointer).collect();
+        assert_eq!(found, ["/components/schemas/Bird"]);
+    }
+
+    #[test]
+    fn finds_unreferenced_definitions() {
+        let spec: Value = serde_yaml::from_str(
+            r##"
+security:
+  - apiKey: []
+tags:
+  - name: pets
+  - name: legacy
+paths:
+  /pets:
+    get:
+      tags: [pets]
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      properties:
+        owner:
+          $ref: '#/components/schemas/Owner'
+    Owner:
+      type: object
+    Old:
+      properties:
+        helper:
+          $ref: '#/components/schemas/OldHelper'
+    OldHelper:
+      type: string
+  parameters:
+    Limit:
+      name: limit
+      in: query
+    Offset:
+      name: offset
+      in: query
+  securitySchemes:
+    apiKey:
+      type: apiKey
+    oauth:
+      type: oauth2
+"##,
+        )
+        .unwrap();
+
+        let found: Vec<(&str, String)> = find(&spec)
+            .into_iter()
+            .map(|o| (o.rule, o.pointer))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("unused-schema", "/components/schemas/Old".to_string()),
+                ("unused-schema", "/components/schemas/OldHelper".to_string()),
+                (
+                    "unused-parameter",
+                    "/components/parameters/Offset".to_string()
+                ),
+                (
+                    "unused-security-scheme",
+                    "/components/securitySchemes/oauth".to_string()
+                ),
+                ("unused-tag", "/tags/1".to_string()),
+            ]
+        );
+    }
+}
@@ -1,38 +1,120 @@
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::time::Duration;
 
-use crate::config::Config;
+use crate::config::{self, Config, CustomPhase, GeneratorOptions, Linter};
 use crate::custom::CustomGeneratorDef;
 use crate::docker::{self, ContainerCommand};
 use crate::generators;
 
+use super::types::Phase;
+
+/// `-e` flags for the proxy variables, `env_passthrough` and
+/// `container_env`.
+///
+/// Passthrough names are given without a value so docker copies them from
+/// the host environment (and skips them when unset); keeping the values out
+/// of the args also keeps them out of logs and `repro.sh`. Proxies set in
+/// `.oavc` are passed the same way, through the `docker` client's
+/// environment ([`proxy_env`]). An explicit `container_env` entry wins over
+/// passing the same name through.
+pub fn env_args(cfg: &Config) -> Vec<String> {
+    let mut proxy_vars = if cfg.forward_proxy {
+        docker::host_proxy_vars()
+    } else {
+        Vec::new()
+    };
+    for (name, _) in proxy_env(cfg) {
+        if !proxy_vars.contains(&name) {
+            proxy_vars.push(name);
+        }
+    }
+    env_args_with(cfg, &proxy_vars)
+}
+
+/// The proxies set in `.oavc` (`http_proxy`, `https_proxy`, `no_proxy`), as
+/// variables in both spellings tools look for. They take precedence over
+/// the host's.
+pub fn proxy_env(cfg: &Config) -> Vec<(String, String)> {
+    docker::proxy_overrides(
+        cfg.http_proxy.as_deref(),
+        cfg.https_proxy.as_deref(),
+        cfg.no_proxy.as_deref(),
+    )
+}
+
+fn env_args_with(cfg: &Config, proxy_vars: &[String]) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut passed: Vec<&String> = Vec::new();
+    for name in proxy_vars.iter().chain(&cfg.env_passthrough) {
+        if !cfg.container_env.contains_key(name) && !passed.contains(&name) {
+            passed.push(name);
+            args.extend(["-e".into(), name.clone()]);
+        }
+    }
+    for (name, value) in &cfg.container_env {
+        args.extend(["-e".into(), format!("{name}={value}")]);
+    }
+    args
+}
+
 /// Build a `docker run` command for Spectral linting.
 pub fn spectral_command(cfg: &Config, spec_path: &Path, work_dir: &Path) -> ContainerCommand {
+    spectral_run(
+        cfg,
+        spec_path,
+        work_dir,
+        &cfg.spectral_ruleset,
+        ".oav/reports/lint/spectral.log",
+    )
+}
+
+/// Build a `docker run` command for the security phase: Spectral with the
+/// OWASP ruleset, logged apart from the style lint.
+pub fn security_command(cfg: &Config, spec_path: &Path, work_dir: &Path) -> ContainerCommand {
+    spectral_run(
+        cfg,
+        spec_path,
+        work_dir,
+        &cfg.security_ruleset,
+        ".oav/reports/security/spectral.log",
+    )
+}
+
+fn spectral_run(
+    cfg: &Config,
+    spec_path: &Path,
+    work_dir: &Path,
+    ruleset: &str,
+    log: &str,
+) -> ContainerCommand {
     let spec_name = spec_path.file_name().unwrap_or_default().to_string_lossy();
 
     let mut args = vec![
         "run".into(),
         "--rm".into(),
         "-v".into(),
-        format!("{}:/work", work_dir.display()),
+        docker::volume(work_dir, "/work"),
     ];
     args.extend(docker::user_args());
+    args.extend(env_args(cfg));
     args.extend([
         cfg.spectral_image.clone(),
         "lint".into(),
         format!("/work/{spec_name}"),
         "--ruleset".into(),
-        cfg.spectral_ruleset.clone(),
+        ruleset.to_string(),
         "--fail-severity".into(),
         cfg.spectral_fail_severity.clone(),
         "-f".into(),
-        "stylish".into(),
+        "json".into(),
     ]);
 
     ContainerCommand {
         args,
-        timeout: Duration::from_secs(cfg.docker_timeout),
-        log_path: Some(work_dir.join(".oav/reports/lint/spectral.log")),
+        env: proxy_env(cfg),
+        timeout: lint_timeout(cfg),
+        log_path: Some(work_dir.join(log)),
+        project: work_dir.to_path_buf(),
     }
 }
 
@@ -44,9 +126,10 @@ pub fn redocly_command(cfg: &Config, spec_path: &Path, work_dir: &Path) -> Conta
         "run".into(),
         "--rm".into(),
         "-v".into(),
-        format!("{}:/work", work_dir.display()),
+        docker::volume(work_dir, "/work"),
     ];
     args.extend(docker::user_args());
+    args.extend(env_args(cfg));
     args.extend([
         "-w".into(),
         "/work".into(),
@@ -54,20 +137,59 @@ pub fn redocly_command(cfg: &Config, spec_path: &Path, work_dir: &Path) -> Conta
         "lint".into(),
         format!("/work/{spec_name}"),
         "--format".into(),
-        "stylish".into(),
+        "json".into(),
     ]);
 
     ContainerCommand {
         args,
-        timeout: Duration::from_secs(cfg.docker_timeout),
+        env: proxy_env(cfg),
+        timeout: lint_timeout(cfg),
         log_path: Some(work_dir.join(".oav/reports/lint/redocly.log")),
+        project: work_dir.to_path_buf(),
+    }
+}
+
+/// Where the docs phase writes its HTML, relative to the project.
+pub const DOCS_OUTPUT: &str = ".oav/generated/docs/index.html";
+
+/// Build a `docker run` command that renders the spec as static Redoc HTML
+/// with the Redocly CLI.
+pub fn docs_command(cfg: &Config, spec_path: &Path, work_dir: &Path) -> ContainerCommand {
+    let spec_name = spec_path.file_name().unwrap_or_default().to_string_lossy();
+
+    let mut args = vec![
+        "run".into(),
+        "--rm".into(),
+        "-v".into(),
+        docker::volume(work_dir, "/work"),
+    ];
+    args.extend(docker::user_args());
+    args.extend(env_args(cfg));
+    args.extend([
+        "-w".into(),
+        "/work".into(),
+        cfg.redocly_image.clone(),
+        "build-docs".into(),
+        format!("/work/{spec_name}"),
+        "--output".into(),
+        format!("/work/{DOCS_OUTPUT}"),
+    ]);
+
+    ContainerCommand {
+        args,
+        env: proxy_env(cfg),
+        timeout: Duration::from_secs(cfg.docker_timeout),
+        log_path: Some(work_dir.join(".oav/reports/docs/redoc.log")),
+        project: work_dir.to_path_buf(),
     }
 }
 
 /// Build a `docker run` command for code generation.
 ///
 /// If a config file path is provided (from builtin registry or user override),
-/// it is passed via `-c` to the generator CLI.
+/// it is passed via `-c` to the generator CLI, and a `template_dirs` entry
+/// via `-t`. Entries from `generator_options` for this generator are
+/// appended as CLI flags.
 pub fn generator_command(
     cfg: &Config,
     spec_path: &Path,
@@ -83,9 +205,28 @@ pub fn generator_command(
         "run".into(),
         "--rm".into(),
         "-v".into(),
-        format!("{}:/work", work_dir.display()),
+        docker::volume(work_dir, "/work"),
     ];
+    if let Some(file) = user_config_file(cfg, work_dir, generator)
+        && let Some(host) = &file.mount
+        && config_path == Some(file.container.as_str())
+    {
+        args.extend([
+            "-v".into(),
+            format!("{}:ro", docker::volume(host, &file.container)),
+        ]);
+    }
+    let templates = user_template_dir(cfg, work_dir, generator);
+    if let Some(dir) = &templates
+        && let Some(host) = &dir.mount
+    {
+        args.extend([
+            "-v".into(),
+            format!("{}:ro", docker::volume(host, &dir.container)),
+        ]);
+    }
     args.extend(docker::user_args());
+    args.extend(env_args(cfg));
     args.extend([
         cfg.generator_image.clone(),
         "generate".into(),
@@ -100,11 +241,57 @@ pub fn generator_command(
     if let Some(path) = config_path {
         args.extend(["-c".into(), path.to_string()]);
     }
+    if let Some(templates) = templates {
+        args.extend(["-t".into(), templates.container]);
+    }
+
+    if let Some(options) = cfg.generator_options.get(generator) {
+        args.extend(generator_option_args(options));
+    }
 
     ContainerCommand {
         args,
-        timeout: Duration::from_secs(cfg.docker_timeout),
+        env: proxy_env(cfg),
+        timeout: generate_timeout(cfg),
         log_path: Some(work_dir.join(format!(".oav/reports/generate/{scope}/{generator}.log"))),
+        project: work_dir.to_path_buf(),
+    }
+}
+
+/// Translate `generator_options` into openapi-generator CLI flags.
+///
+/// Each entry becomes its own flag/value pair so values containing commas
+/// aren't split by the generator's `k1=v1,k2=v2` parsing.
+fn generator_option_args(options: &GeneratorOptions) -> Vec<String> {
+    let groups = [
+        ("--additional-properties", &options.additional_properties),
+        ("--global-property", &options.global_properties),
+        ("--server-variables", &options.server_variables),
+    ];
+
+    let mut args = Vec::new();
+    for (flag, entries) in groups {
+        for (key, value) in entries {
+            args.push(flag.to_string());
+            args.push(format!("{key}={value}"));
+        }
+    }
+    args
+}
+
+/// Build a `docker run` command that prints the generators the configured
+/// openapi-generator image supports (parse with `generators::parse_generator_list`).
+pub fn list_generators_command(cfg: &Config, work_dir: &Path) -> ContainerCommand {
+    let mut args = vec!["run".into(), "--rm".into()];
+    args.extend(env_args(cfg));
+    args.extend([cfg.generator_image.clone(), "list".into()]);
+
+    ContainerCommand {
+        args,
+        env: proxy_env(cfg),
+        timeout: Duration::from_secs(cfg.docker_timeout),
+        log_path: None,
+        project: work_dir.to_path_buf(),
     }
 }
 
@@ -134,15 +321,125 @@ pub fn compile_command(
         "--rm".into(),
     ];
     args.extend(docker::user_args());
+    args.extend(env_args(cfg));
     args.push(service);
 
     ContainerCommand {
         args,
-        timeout: Duration::from_secs(cfg.docker_timeout),
+        env: proxy_env(cfg),
+        timeout: compile_timeout(cfg),
         log_path: Some(work_dir.join(format!(".oav/reports/compile/{scope}/{generator}.log"))),
+        project: work_dir.to_path_buf(),
     }
 }
 
+/// Build a `docker compose run` command that runs a generated project's
+/// test suite: the generator's compile service, with its command replaced
+/// by the ecosystem's test runner.
+///
+/// Returns `None` for generators without a known test runner.
+pub fn test_command(
+    cfg: &Config,
+    work_dir: &Path,
+    generator: &str,
+    scope: &str,
+) -> Option<ContainerCommand> {
+    let script = test_script(generator)?;
+    let mut cmd = compile_command(cfg, work_dir, generator, scope);
+    cmd.args.extend(["sh".into(), "-c".into(), script.into()]);
+    cmd.log_path = Some(work_dir.join(format!(".oav/reports/test/{scope}/{generator}.log")));
+    Some(cmd)
+}
+
+/// Test runner for a built-in generator's project. Projects without tests
+/// pass: npm skips a missing `test` script and pytest's "no tests
+/// collected" exit code is accepted.
+fn test_script(generator: &str) -> Option<&'static str> {
+    Some(match generator {
+        "spring" | "java" => "mvn -B test",
+        "kotlin-spring" | "kotlin" => "gradle --no-daemon test",
+        "go-server" => "set -e; test -f go.mod || cd go; go mod tidy; go test ./...",
+        "go" => "go mod tidy && go test ./...",
+        "python-fastapi" => {
+            "pip install -q -r requirements.txt pytest && python -m pytest -rf; \
+             status=$?; [ $status -eq 5 ] && exit 0; exit $status"
+        }
+        "python" => {
+            "(pip install -q -e . || pip install -q -r requirements.txt) && pip install -q pytest \
+             && python -m pytest -rf; status=$?; [ $status -eq 5 ] && exit 0; exit $status"
+        }
+        "aspnetcore" => "cd /src && dotnet test",
+        "csharp" => "dotnet test",
+        "typescript-nestjs" | "typescript-axios" | "typescript-fetch" | "typescript-node" => {
+            "npm install && npm run test --if-present"
+        }
+        _ => return None,
+    })
+}
+
+/// Build a `docker compose run` command that runs the language's linters
+/// over a generated project, in the generator's compile service like
+/// [`test_command`].
+///
+/// Returns `None` for generators without known linters.
+pub fn code_lint_command(
+    cfg: &Config,
+    work_dir: &Path,
+    generator: &str,
+    scope: &str,
+) -> Option<ContainerCommand> {
+    let script = code_lint_script(generator)?;
+    let mut cmd = compile_command(cfg, work_dir, generator, scope);
+    cmd.args.extend(["sh".into(), "-c".into(), script.into()]);
+    cmd.log_path = Some(work_dir.join(format!(".oav/reports/code-lint/{scope}/{generator}.log")));
+    Some(cmd)
+}
+
+/// Linters for a built-in generator's project. Each runs even when one
+/// before it found something, so the log holds every finding; the step
+/// fails if any of them did.
+fn code_lint_script(generator: &str) -> Option<&'static str> {
+    Some(match generator {
+        "spring" | "java" => {
+            "status=0; \
+             mvn -B -DskipTests compile \
+             org.apache.maven.plugins:maven-checkstyle-plugin:3.6.0:check || status=1; \
+             mvn -B -DskipTests compile \
+             com.github.spotbugs:spotbugs-maven-plugin:4.9.3.0:check || status=1; \
+             exit $status"
+        }
+        "go-server" | "go" => {
+            "set -e; test -f go.mod || cd go; go mod tidy; \
+             go run github.com/golangci/golangci-lint/cmd/golangci-lint@v1.64.8 \
+             run --out-format=line-number ./..."
+        }
+        "typescript-nestjs" | "typescript-axios" | "typescript-fetch" | "typescript-node" => {
+            "npm install --no-audit --no-fund || exit 1; status=0; \
+             npx --yes -p typescript tsc --noEmit -p . || status=1; \
+             if ls .eslintrc* eslint.config.* >/dev/null 2>&1; then \
+             npx --yes eslint . || status=1; fi; \
+             exit $status"
+        }
+        _ => return None,
+    })
+}
+
+/// Build a `docker run` command for a custom generator's code lint step,
+/// run in the generated output directory like its compile step.
+pub fn custom_code_lint_command(
+    cfg: &Config,
+    work_dir: &Path,
+    def: &CustomGeneratorDef,
+    lint: &crate::custom::CompileBlock,
+) -> ContainerCommand {
+    let mut cmd = custom_compile_command(cfg, work_dir, def, lint);
+    cmd.log_path = Some(work_dir.join(format!(
+        ".oav/reports/code-lint/{}/{}.log",
+        def.scope, def.name
+    )));
+    cmd
+}
+
 fn compile_service_name(generator: &str, scope: &str) -> String {
     match scope {
         "server" => format!("build-{generator}"),
@@ -170,19 +467,22 @@ pub fn custom_generate_command(
         "run".into(),
         "--rm".into(),
         "-v".into(),
-        format!("{}:/work", work_dir.display()),
+        docker::volume(work_dir, "/work"),
     ];
     args.extend(docker::user_args());
+    args.extend(env_args(cfg));
     args.push(def.generate.image.clone());
     args.extend(cmd_args);
 
     ContainerCommand {
         args,
-        timeout: Duration::from_secs(cfg.docker_timeout),
+        env: proxy_env(cfg),
+        timeout: generate_timeout(cfg),
         log_path: Some(work_dir.join(format!(
             ".oav/reports/generate/{}/{}.log",
             def.scope, def.name
         ))),
+        project: work_dir.to_path_buf(),
     }
 }
 
@@ -203,19 +503,155 @@ pub fn custom_compile_command(
         "run".into(),
         "--rm".into(),
         "-v".into(),
-        format!("{}:/work", work_dir.display()),
+        docker::volume(work_dir, "/work"),
     ];
     args.extend(docker::user_args());
+    args.extend(env_args(cfg));
     args.extend(["-w".into(), workdir, compile.image.clone()]);
     args.extend(cmd_args);
 
     ContainerCommand {
         args,
-        timeout: Duration::from_secs(cfg.docker_timeout),
+        env: proxy_env(cfg),
+        timeout: compile_timeout(cfg),
         log_path: Some(work_dir.join(format!(
             ".oav/reports/compile/{}/{}.log",
             def.scope, def.name
         ))),
+        project: work_dir.to_path_buf(),
+    }
+}
+
+/// Build a `docker run` command for a custom generator's test step, run in
+/// the generated output directory like its compile step.
+pub fn custom_test_command(
+    cfg: &Config,
+    work_dir: &Path,
+    def: &CustomGeneratorDef,
+    test: &crate::custom::CompileBlock,
+) -> ContainerCommand {
+    let mut cmd = custom_compile_command(cfg, work_dir, def, test);
+    cmd.log_path = Some(work_dir.join(format!(".oav/reports/test/{}/{}.log", def.scope, def.name)));
+    cmd
+}
+
+/// Build a `docker run` command for a phase declared under `custom_phases`,
+/// or `None` when its name isn't one a log file can be named after.
+///
+/// The project is mounted at `/work`, which is also the working directory.
+/// `{spec}`, `{work}` and `{generated}` in the phase's arguments expand to
+/// the spec, `/work` and the generated output directory inside the container.
+pub fn custom_phase_command(
+    cfg: &Config,
+    spec_path: &Path,
+    work_dir: &Path,
+    phase: &CustomPhase,
+) -> Option<ContainerCommand> {
+    if !config::is_valid_phase_name(&phase.name) {
+        return None;
+    }
+    let spec_name = spec_path.file_name().unwrap_or_default().to_string_lossy();
+    let spec = format!("/work/{spec_name}");
+
+    let mut args = vec![
+        "run".into(),
+        "--rm".into(),
+        "-v".into(),
+        docker::volume(work_dir, "/work"),
+    ];
+    args.extend(docker::user_args());
+    args.extend(env_args(cfg));
+    args.extend(["-w".into(), "/work".into(), phase.image.clone()]);
+    args.extend(phase.args.iter().map(|arg| {
+        arg.replace("{spec}", &spec)
+            .replace("{work}", "/work")
+            .replace("{generated}", "/work/.oav/generated")
+    }));
+
+    Some(ContainerCommand {
+        args,
+        env: proxy_env(cfg),
+        timeout: Duration::from_secs(cfg.docker_timeout),
+        log_path: Some(work_dir.join(format!(".oav/reports/custom/{}.log", phase.name))),
+        project: work_dir.to_path_buf(),
+    })
+}
+
+/// Container directory for `generator_configs` files that live outside the project.
+const EXTERNAL_CONFIG_DIR: &str = "/oav-configs";
+
+/// Container directory for `template_dirs` that live outside the project.
+const EXTERNAL_TEMPLATE_DIR: &str = "/oav-templates";
+
+/// A `generator_configs` or `template_dirs` entry resolved to host and
+/// container locations.
+struct UserConfigFile {
+    host: PathBuf,
+    container: String,
+    /// Host path to bind-mount when it isn't already under `/work`.
+    mount: Option<PathBuf>,
+}
+
+fn lint_timeout(cfg: &Config) -> Duration {
+    Duration::from_secs(cfg.lint_timeout.unwrap_or(cfg.docker_timeout))
+}
+
+fn generate_timeout(cfg: &Config) -> Duration {
+    Duration::from_secs(cfg.generate_timeout.unwrap_or(cfg.docker_timeout))
+}
+
+fn compile_timeout(cfg: &Config) -> Duration {
+    Duration::from_secs(cfg.compile_timeout.unwrap_or(cfg.docker_timeout))
+}
+
+fn user_config_file(cfg: &Config, work_dir: &Path, generator: &str) -> Option<UserConfigFile> {
+    let raw = cfg.generator_configs.get(generator)?;
+    Some(host_path(
+        work_dir,
+        raw,
+        format!("{EXTERNAL_CONFIG_DIR}/{generator}.yaml"),
+    ))
+}
+
+fn user_template_dir(cfg: &Config, work_dir: &Path, generator: &str) -> Option<UserConfigFile> {
+    let raw = cfg.template_dirs.get(generator)?;
+    Some(host_path(
+        work_dir,
+        raw,
+        format!("{EXTERNAL_TEMPLATE_DIR}/{generator}"),
+    ))
+}
+
+/// Resolve `raw` (relative to the project or absolute) to where the
+/// container sees it: under `/work` when inside the project, otherwise at
+/// `external`, mounted there.
+fn host_path(work_dir: &Path, raw: &str, external: String) -> UserConfigFile {
+    let raw = Path::new(raw);
+    let host = if raw.is_absolute() {
+        raw.to_path_buf()
+    } else {
+        work_dir.join(raw)
+    };
+
+    let inside = host
+        .strip_prefix(work_dir)
+        .ok()
+        .filter(|rel| rel.components().all(|c| matches!(c, Component::Normal(_))));
+
+    match inside {
+        Some(rel) => {
+            let rel: Vec<_> = rel.iter().map(|c| c.to_string_lossy()).collect();
+            UserConfigFile {
+                container: format!("/work/{}", rel.join("/")),
+                host,
+                mount: None,
+            }
+        }
+        None => UserConfigFile {
+            container: external,
+            mount: Some(host.clone()),
+            host,
+        },
     }
 }
 
@@ -223,18 +659,176 @@ pub fn custom_compile_command(
 ///
 /// Resolution order:
 /// 1. User override in `generator_config_overrides` → use that path directly
-/// 2. Built-in registry match → `/work/.oav/configs/{scope}/{generator}.yaml`
-/// 3. No match → `None` (bare `-g` only)
-pub fn resolve_config_path(cfg: &Config, generator: &str, scope: &str) -> Option<String> {
+/// 2. Host file in `generator_configs` → `/work/{relative}` inside the project,
+///    or `/oav-configs/{generator}.yaml` (mounted) outside it
+/// 3. Built-in registry match → `/work/.oav/configs/{scope}/{generator}.yaml`
+/// 4. No match → `None` (bare `-g` only)
+pub fn resolve_config_path(
+    cfg: &Config,
+    work_dir: &Path,
+    generator: &str,
+    scope: &str,
+) -> Option<String> {
     if let Some(user_path) = cfg.generator_config_overrides.get(generator) {
         return Some(user_path.clone());
     }
+    if let Some(file) = user_config_file(cfg, work_dir, generator) {
+        return Some(file.container);
+    }
     if generators::find_builtin(generator, scope).is_some() {
         return Some(format!("/work/.oav/configs/{scope}/{generator}.yaml"));
     }
     None
 }
 
+/// Build the container command for a pipeline phase, exactly as the
+/// orchestrator runs it.
+///
+/// Returns `None` when there's nothing to run: linting disabled via
+/// `linter: none`, a custom generator without a compile, test or code lint
+/// block, a generator without a known test runner or linters, or an
+/// undeclared custom phase.
+pub fn step_command(
+    cfg: &Config,
+    spec_path: &Path,
+    work_dir: &Path,
+    custom_defs: &[CustomGeneratorDef],
+    phase: &Phase,
+) -> Option<ContainerCommand> {
+    let find_custom = |name: &str, scope: &str| {
+        custom_defs
+            .iter()
+            .find(|d| d.name == name && d.scope == scope)
+    };
+
+    match phase {
+        Phase::Structure => None,
+        Phase::Lint => match cfg.linter {
+            Linter::Spectral => Some(spectral_command(cfg, spec_path, work_dir)),
+            Linter::Redocly => Some(redocly_command(cfg, spec_path, work_dir)),
+            Linter::None => None,
+        },
+        Phase::Security => Some(security_command(cfg, spec_path, work_dir)),
+        Phase::Generate { generator, scope } => Some(match find_custom(generator, scope) {
+            Some(def) => custom_generate_command(cfg, spec_path, work_dir, def),
+            None => {
+                let config_path = resolve_config_path(cfg, work_dir, generator, scope);
+                generator_command(
+                    cfg,
+                    spec_path,
+                    work_dir,
+                    generator,
+                    scope,
+                    config_path.as_deref(),
+                )
+            }
+        }),
+        Phase::Compile { generator, scope } => match find_custom(generator, scope) {
+            Some(def) => def
+                .compile
+                .as_ref()
+                .map(|compile| custom_compile_command(cfg, work_dir, def, compile)),
+            None => Some(compile_command(cfg, work_dir, generator, scope)),
+        },
+        Phase::Test { generator, scope } => match find_custom(generator, scope) {
+            Some(def) => def
+                .test
+                .as_ref()
+                .map(|test| custom_test_command(cfg, work_dir, def, test)),
+            None => test_command(cfg, work_dir, generator, scope),
+        },
+        Phase::CodeLint { generator, scope } => match find_custom(generator, scope) {
+            Some(def) => def
+                .code_lint
+                .as_ref()
+                .map(|lint| custom_code_lint_command(cfg, work_dir, def, lint)),
+            None => code_lint_command(cfg, work_dir, generator, scope),
+        },
+        Phase::Docs => Some(docs_command(cfg, spec_path, work_dir)),
+        Phase::Custom { name } => cfg
+            .custom_phases
+            .iter()
+            .find(|p| &p.name == name)
+            .and_then(|p| custom_phase_command(cfg, spec_path, work_dir, p)),
+    }
+}
+
+/// `docker run` flags that take a separate value argument.
+const RUN_FLAGS_WITH_VALUE: &[&str] = &[
+    "-v",
+    "--volume",
+    "-e",
+    "--env",
+    "-w",
+    "--workdir",
+    "-u",
+    "--user",
+    "--entrypoint",
+    "--name",
+    "--network",
+    "--env-file",
+];
+
+/// Index of the image argument in `docker run` args, skipping the run
+/// flags (and their values) that precede it.
+pub fn run_image_index(args: &[String]) -> Option<usize> {
+    if args.first().map(String::as_str) != Some("run") {
+        return None;
+    }
+    let mut i = 1;
+    while let Some(arg) = args.get(i) {
+        if !arg.starts_with('-') {
+            return Some(i);
+        }
+        i += if RUN_FLAGS_WITH_VALUE.contains(&arg.as_str()) {
+            2
+        } else {
+            1
+        };
+    }
+    None
+}
+
+/// Rewrite a step's command into an interactive `sh` session in the same
+/// container setup (image, mounts, env, user).
+///
+/// `docker run` commands get `-it --entrypoint sh` in place of the image's
+/// command and start in `/work` unless they set a working directory;
+/// `docker compose run` commands keep their service. Returns `None` if the
+/// command isn't a recognizable `run`.
+pub fn interactive_shell_args(cmd: &ContainerCommand) -> Option<Vec<String>> {
+    let args = &cmd.args;
+    match args.first().map(String::as_str) {
+        Some("run") => {
+            let image_at = run_image_index(args)?;
+            let options: Vec<String> = args[1..image_at]
+                .iter()
+                .filter(|a| *a != "--rm")
+                .cloned()
+                .collect();
+            let image = args[image_at].clone();
+
+            let mut shell = vec!["run".into(), "--rm".into(), "-it".into()];
+            if !options.iter().any(|o| o == "-w" || o == "--workdir") {
+                shell.extend(["-w".into(), "/work".into()]);
+            }
+            shell.extend(options);
+            shell.extend(["--entrypoint".into(), "sh".into(), image]);
+            Some(shell)
+        }
+        Some("compose") => {
+            let run_pos = args.iter().position(|a| a == "run")?;
+            let mut shell = args[..=run_pos].to_vec();
+            shell.extend(["--entrypoint".into(), "sh".into()]);
+            shell.extend_from_slice(&args[run_pos + 1..]);
+            // Override the service's `command:` so it isn't fed to `sh`.
+            shell.push("-i".into());
+            Some(shell)
+        }
+        _ => None,
+    }
+}
+
 /// Resolve the full list of `(generator, scope)` pairs from config.
 ///
 /// When generator lists are empty and the mode includes that scope,
@@ -282,17 +876,37 @@ pub fn build_generator_list(
 /// Write builtin config files to `.oav/configs/{scope}/` on the host filesystem.
 ///
 /// Called before the generate phase so Docker containers can mount them.
-/// Only writes configs for generators that don't have a user override.
-/// Returns an error listing the first generator whose config could not be written.
+/// Only writes configs for generators that don't have a user override; user
+/// files from `generator_configs`, and `template_dirs`, are checked for
+/// existence instead.
+/// Returns an error for the first generator whose config is missing or could
+/// not be written.
 pub fn write_builtin_configs(
     cfg: &Config,
     work_dir: &Path,
     generators: &[(String, String)],
 ) -> Result<(), String> {
     for (name, scope) in generators {
+        if let Some(dir) = user_template_dir(cfg, work_dir, name)
+            && !dir.host.is_dir()
+        {
+            return Err(format!(
+                "template directory for '{name}' not found: {}",
+                dir.host.display()
+            ));
+        }
         if cfg.generator_config_overrides.contains_key(name.as_str()) {
             continue;
         }
+        if let Some(file) = user_config_file(cfg, work_dir, name) {
+            if !file.host.is_file() {
+                return Err(format!(
+                    "config file for '{name}' not found: {}",
+                    file.host.display()
+                ));
+            }
+            continue;
+        }
         if let Some(def) = crate::generators::find_builtin(name, scope) {
             let config_dir = work_dir.join(format!(".oav/configs/{scope}"));
             std::fs::create_dir_all(&config_dir).map_err(|e| {
@@ -341,7 +955,7 @@ mod tests {
         assert!(cmd.args.contains(&"/work/spec.yaml".into()));
         assert!(cmd.args.contains(&"--ruleset".into()));
         assert!(cmd.args.contains(&cfg.spectral_ruleset));
-        assert!(cmd.args.contains(&"stylish".into()));
+        assert!(cmd.args.contains(&"json".into()));
     }
 
     #[test]
@@ -358,6 +972,36 @@ mod tests {
             .position(|a| a == "-w")
             .expect("-w flag missing");
         assert_eq!(cmd.args[w_pos + 1], "/work");
+        let f_pos = cmd.args.iter().position(|a| a == "--format").unwrap();
+        assert_eq!(cmd.args[f_pos + 1], "json");
+    }
+
+    #[test]
+    fn security_command_uses_owasp_ruleset() {
+        let cfg = test_config();
+        let cmd = security_command(&cfg, Path::new("/tmp/spec.yaml"), Path::new("/tmp"));
+        assert!(cmd.args.contains(&cfg.spectral_image));
+        let r_pos = cmd.args.iter().position(|a| a == "--ruleset").unwrap();
+        assert_eq!(cmd.args[r_pos + 1], cfg.security_ruleset);
+        assert_eq!(
+            cmd.log_path.as_deref(),
+            Some(Path::new("/tmp/.oav/reports/security/spectral.log"))
+        );
+    }
+
+    #[test]
+    fn docs_command_builds_redoc_html() {
+        let cfg = test_config();
+        let cmd = docs_command(&cfg, Path::new("/tmp/spec.yaml"), Path::new("/tmp"));
+        assert!(cmd.args.contains(&cfg.redocly_image));
+        assert!(cmd.args.contains(&"build-docs".into()));
+        assert!(cmd.args.contains(&"/work/spec.yaml".into()));
+        let o_pos = cmd.args.iter().position(|a| a == "--output").unwrap();
+        assert_eq!(cmd.args[o_pos + 1], "/work/.oav/generated/docs/index.html");
+        assert_eq!(
+            cmd.log_path.as_deref(),
+            Some(Path::new("/tmp/.oav/reports/docs/redoc.log"))
+        );
     }
 
     #[test]
@@ -400,6 +1044,271 @@ mod tests {
         );
     }
 
+    #[test]
+    fn generator_command_appends_generator_options() {
+        let mut cfg = test_config();
+        let mut options = GeneratorOptions::default();
+        options
+            .additional_properties
+            .insert("useSpringBoot3".into(), "true".into());
+        options
+            .global_properties
+            .insert("skipFormModel".into(), "false".into());
+        cfg.generator_options.insert("spring".into(), options);
+
+        let cmd = generator_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            "spring",
+            "server",
+            None,
+        );
+        let tail: Vec<&str> = cmd
+            .args
+            .iter()
+            .rev()
+            .take(4)
+            .rev()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            tail,
+            [
+                "--additional-properties",
+                "useSpringBoot3=true",
+                "--global-property",
+                "skipFormModel=false"
+            ]
+        );
+    }
+
+    #[test]
+    fn generator_command_ignores_options_for_other_generators() {
+        let mut cfg = test_config();
+        let mut options = GeneratorOptions::default();
+        options
+            .additional_properties
+            .insert("npmName".into(), "api".into());
+        cfg.generator_options
+            .insert("typescript-axios".into(), options);
+
+        let cmd = generator_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            "spring",
+            "server",
+            None,
+        );
+        assert!(!cmd.args.contains(&"--additional-properties".into()));
+    }
+
+    #[test]
+    fn list_generators_command_runs_list() {
+        let cfg = test_config();
+        let cmd = list_generators_command(&cfg, Path::new("/work"));
+        assert_eq!(cmd.args.last().unwrap(), "list");
+        assert!(cmd.args.contains(&cfg.generator_image));
+        assert!(cmd.log_path.is_none());
+    }
+
+    #[test]
+    fn step_command_runs_custom_tests_in_the_output_directory() {
+        let cfg = test_config();
+        let phase = Phase::Test {
+            generator: "my-gen".into(),
+            scope: "server".into(),
+        };
+        let cmd = step_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            &[custom_def("my-gen", "server")],
+            &phase,
+        )
+        .unwrap();
+        assert!(cmd.args.ends_with(&["npm".into(), "test".into()]));
+        assert!(
+            cmd.args
+                .contains(&"/work/.oav/generated/server/my-gen".into())
+        );
+        assert_eq!(
+            cmd.log_path.unwrap(),
+            Path::new("/tmp/.oav/reports/test/server/my-gen.log")
+        );
+    }
+
+    #[test]
+    fn step_command_skips_custom_without_compile() {
+        let cfg = test_config();
+        let def = CustomGeneratorDef {
+            compile: None,
+            ..custom_def("my-gen", "server")
+        };
+        let phase = Phase::Compile {
+            generator: "my-gen".into(),
+            scope: "server".into(),
+        };
+        let cmd = step_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            &[def],
+            &phase,
+        );
+        assert!(cmd.is_none());
+    }
+
+    #[test]
+    fn step_command_lint_follows_linter() {
+        let mut cfg = test_config();
+        cfg.linter = Linter::Redocly;
+        let cmd = step_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            &[],
+            &Phase::Lint,
+        )
+        .unwrap();
+        assert!(cmd.args.contains(&cfg.redocly_image));
+
+        cfg.linter = Linter::None;
+        let cmd = step_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            &[],
+            &Phase::Lint,
+        );
+        assert!(cmd.is_none());
+    }
+
+    #[test]
+    fn interactive_shell_replaces_generator_command() {
+        let cfg = test_config();
+        let cmd = generator_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            "spring",
+            "server",
+            None,
+        );
+        let shell = interactive_shell_args(&cmd).unwrap();
+        assert_eq!(&shell[..3], ["run", "--rm", "-it"]);
+        assert!(shell.contains(&"/tmp:/work".into()));
+        assert_eq!(
+            &shell[shell.len() - 3..],
+            ["--entrypoint", "sh", cfg.generator_image.as_str()]
+        );
+        assert!(!shell.contains(&"generate".into()));
+        assert_eq!(shell.iter().filter(|a| *a == "--rm").count(), 1);
+    }
+
+    #[test]
+    fn interactive_shell_keeps_existing_workdir() {
+        let cfg = test_config();
+        let def = custom_def("my-gen", "server");
+        let cmd =
+            custom_compile_command(&cfg, Path::new("/tmp"), &def, def.compile.as_ref().unwrap());
+        let shell = interactive_shell_args(&cmd).unwrap();
+        assert_eq!(shell.iter().filter(|a| *a == "-w").count(), 1);
+        assert!(shell.contains(&"/work/.oav/generated/server/my-gen".into()));
+        assert_eq!(shell.last().unwrap(), "build-image:latest");
+    }
+
+    #[test]
+    fn run_image_index_skips_flag_values() {
+        let cfg = test_config();
+        let cmd = spectral_command(&cfg, Path::new("/tmp/spec.yaml"), Path::new("/tmp"));
+        let at = run_image_index(&cmd.args).unwrap();
+        assert_eq!(cmd.args[at], cfg.spectral_image);
+
+        let compose = compile_command(&cfg, Path::new("/tmp"), "spring", "server");
+        assert_eq!(run_image_index(&compose.args), None);
+    }
+
+    #[test]
+    fn interactive_shell_for_compose_overrides_command() {
+        let cfg = test_config();
+        let cmd = compile_command(&cfg, Path::new("/tmp"), "spring", "server");
+        let shell = interactive_shell_args(&cmd).unwrap();
+        let run_pos = shell.iter().position(|a| a == "run").unwrap();
+        assert_eq!(shell[run_pos + 1], "--entrypoint");
+        assert_eq!(shell[run_pos + 2], "sh");
+        assert_eq!(&shell[shell.len() - 2..], ["build-spring", "-i"]);
+    }
+
+    #[test]
+    fn env_args_pass_through_names_and_set_values() {
+        let mut cfg = test_config();
+        cfg.env_passthrough = vec!["HTTP_PROXY".into(), "JAVA_OPTS".into()];
+        cfg.container_env = [("JAVA_OPTS".to_string(), "-Xmx2g".to_string())].into();
+        assert_eq!(
+            env_args_with(&cfg, &[]),
+            vec!["-e", "HTTP_PROXY", "-e", "JAVA_OPTS=-Xmx2g"]
+        );
+    }
+
+    #[test]
+    fn env_args_forward_proxy_vars_once() {
+        let mut cfg = test_config();
+        cfg.env_passthrough = vec!["HTTPS_PROXY".into()];
+        let proxies = vec!["HTTPS_PROXY".to_string(), "NO_PROXY".to_string()];
+        assert_eq!(
+            env_args_with(&cfg, &proxies),
+            vec!["-e", "HTTPS_PROXY", "-e", "NO_PROXY"]
+        );
+    }
+
+    #[test]
+    fn oavc_proxies_reach_containers_without_showing_in_args() {
+        let mut cfg = test_config();
+        cfg.forward_proxy = false;
+        cfg.https_proxy = Some("http://user:pw@proxy.corp:3128".into());
+
+        let cmd = compile_command(&cfg, Path::new("/tmp"), "spring", "server");
+        assert!(cmd.args.windows(2).any(|w| w == ["-e", "HTTPS_PROXY"]));
+        assert!(cmd.args.windows(2).any(|w| w == ["-e", "https_proxy"]));
+        assert!(!cmd.args.iter().any(|a| a.contains("proxy.corp")));
+        assert!(cmd.env.contains(&(
+            "HTTPS_PROXY".into(),
+            "http://user:pw@proxy.corp:3128".into()
+        )));
+    }
+
+    #[test]
+    fn env_flags_precede_image_and_service() {
+        let mut cfg = test_config();
+        cfg.env_passthrough = vec!["HTTPS_PROXY".into()];
+
+        let cmd = generator_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            "spring",
+            "server",
+            None,
+        );
+        let env_pos = cmd.args.iter().position(|a| a == "HTTPS_PROXY").unwrap();
+        assert_eq!(cmd.args[env_pos - 1], "-e");
+        assert!(env_pos < run_image_index(&cmd.args).unwrap());
+
+        let compose = compile_command(&cfg, Path::new("/tmp"), "spring", "server");
+        let env_pos = compose
+            .args
+            .iter()
+            .position(|a| a == "HTTPS_PROXY")
+            .unwrap();
+        assert!(env_pos < compose.args.len() - 1);
+        assert_eq!(compose.args.last().unwrap(), "build-spring");
+
+        let list = list_generators_command(&cfg, Path::new("/work"));
+        assert!(list.args.contains(&"HTTPS_PROXY".into()));
+    }
+
     #[test]
     fn compile_command_uses_docker_compose() {
         let cfg = test_config();
@@ -428,6 +1337,121 @@ mod tests {
         assert!(cmd.args.contains(&"build-client-typescript-axios".into()));
     }
 
+    #[test]
+    fn test_command_runs_the_test_suite_in_the_compile_service() {
+        let cfg = test_config();
+        let cmd = test_command(&cfg, Path::new("/tmp"), "spring", "server").unwrap();
+        let service = cmd.args.iter().position(|a| a == "build-spring").unwrap();
+        assert_eq!(cmd.args[service + 1..], ["sh", "-c", "mvn -B test"]);
+        assert_eq!(
+            cmd.log_path.unwrap(),
+            Path::new("/tmp/.oav/reports/test/server/spring.log")
+        );
+        assert!(test_command(&cfg, Path::new("/tmp"), "unknown", "server").is_none());
+    }
+
+    #[test]
+    fn code_lint_command_runs_the_linters_in_the_compile_service() {
+        let cfg = test_config();
+        let cmd = code_lint_command(&cfg, Path::new("/tmp"), "go", "client").unwrap();
+        let service = cmd
+            .args
+            .iter()
+            .position(|a| a == "build-client-go")
+            .unwrap();
+        assert_eq!(cmd.args[service + 1..service + 3], ["sh", "-c"]);
+        assert!(cmd.args[service + 3].contains("golangci-lint"));
+        assert_eq!(
+            cmd.log_path.unwrap(),
+            Path::new("/tmp/.oav/reports/code-lint/client/go.log")
+        );
+        assert!(code_lint_command(&cfg, Path::new("/tmp"), "kotlin", "client").is_none());
+    }
+
+    #[test]
+    fn custom_phase_command_expands_placeholders() {
+        let cfg = Config {
+            custom_phases: vec![CustomPhase {
+                name: "breaking".into(),
+                image: "tufin/oasdiff".into(),
+                args: vec![
+                    "breaking".into(),
+                    "{work}/base.yaml".into(),
+                    "{spec}".into(),
+                    "--out={generated}/breaking.txt".into(),
+                ],
+                after: Default::default(),
+            }],
+            ..test_config()
+        };
+        let phase = Phase::Custom {
+            name: "breaking".into(),
+        };
+        let cmd = step_command(
+            &cfg,
+            Path::new("/tmp/spec.yaml"),
+            Path::new("/tmp"),
+            &[],
+            &phase,
+        )
+        .unwrap();
+        let image = cmd.args.iter().position(|a| a == "tufin/oasdiff").unwrap();
+        assert_eq!(cmd.args[image - 1], "/work");
+        assert_eq!(
+            cmd.args[image + 1..],
+            [
+                "breaking",
+                "/work/base.yaml",
+                "/work/spec.yaml",
+                "--out=/work/.oav/generated/breaking.txt"
+            ]
+        );
+        assert_eq!(
+            cmd.log_path.unwrap(),
+            Path::new("/tmp/.oav/reports/custom/breaking.log")
+        );
+
+        let undeclared = Phase::Custom {
+            name: "fuzz".into(),
+        };
+        assert!(
+            step_command(
+                &cfg,
+                Path::new("/tmp/spec.yaml"),
+                Path::new("/tmp"),
+                &[],
+                &undeclared
+            )
+            .is_none()
+        );
+
+        let mut escaping = cfg.clone();
+        escaping.custom_phases[0].name = "../../../home/me/.bashrc".into();
+        let escaping_phase = Phase::Custom {
+            name: escaping.custom_phases[0].name.clone(),
+        };
+        assert!(
+            step_command(
+                &escaping,
+                Path::new("/tmp/spec.yaml"),
+                Path::new("/tmp"),
+                &[],
+                &escaping_phase
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn every_builtin_generator_has_a_test_runner() {
+        let builtins = generators::builtin_server_generators()
+            .iter()
+            .chain(generators::builtin_client_generators());
+        for def in builtins {
+            assert!(test_script(def.name).is_some(), "{}", def.name);
+        }
+    }
+
     #[test]
     fn compile_service_name_server() {
         assert_eq!(compile_service_name("spring", "server"), "build-spring");
@@ -486,7 +1510,7 @@ mod tests {
     #[test]
     fn resolve_config_path_builtin() {
         let cfg = test_config();
-        let path = resolve_config_path(&cfg, "spring", "server");
+        let path = resolve_config_path(&cfg, Path::new("/tmp"), "spring", "server");
         assert_eq!(
             path.as_deref(),
             Some("/work/.oav/configs/server/spring.yaml")
@@ -498,14 +1522,133 @@ mod tests {
         let mut cfg = test_config();
         cfg.generator_config_overrides
             .insert("spring".into(), "/work/custom/spring.yaml".into());
-        let path = resolve_config_path(&cfg, "spring", "server");
+        let path = resolve_config_path(&cfg, Path::new("/tmp"), "spring", "server");
         assert_eq!(path.as_deref(), Some("/work/custom/spring.yaml"));
     }
 
+    #[test]
+    fn resolve_config_path_project_file_maps_under_work() {
+        let mut cfg = test_config();
+        cfg.generator_configs
+            .insert("spring".into(), "configs/spring.yaml".into());
+        let path = resolve_config_path(&cfg, Path::new("/proj"), "spring", "server");
+        assert_eq!(path.as_deref(), Some("/work/configs/spring.yaml"));
+
+        let cmd = generator_command(
+            &cfg,
+            Path::new("/proj/spec.yaml"),
+            Path::new("/proj"),
+            "spring",
+            "server",
+            path.as_deref(),
+        );
+        assert!(!cmd.args.iter().any(|a| a.ends_with(":ro")));
+    }
+
+    #[test]
+    fn resolve_config_path_external_file_is_mounted() {
+        let mut cfg = test_config();
+        cfg.generator_configs
+            .insert("spring".into(), "/etc/shared/spring.yaml".into());
+        let path = resolve_config_path(&cfg, Path::new("/proj"), "spring", "server");
+        assert_eq!(path.as_deref(), Some("/oav-configs/spring.yaml"));
+
+        let cmd = generator_command(
+            &cfg,
+            Path::new("/proj/spec.yaml"),
+            Path::new("/proj"),
+            "spring",
+            "server",
+            path.as_deref(),
+        );
+        assert!(
+            cmd.args
+                .contains(&"/etc/shared/spring.yaml:/oav-configs/spring.yaml:ro".into())
+        );
+    }
+
+    #[test]
+    fn resolve_config_path_parent_relative_file_is_mounted() {
+        let mut cfg = test_config();
+        cfg.generator_configs
+            .insert("spring".into(), "../shared/spring.yaml".into());
+        let path = resolve_config_path(&cfg, Path::new("/proj"), "spring", "server");
+        assert_eq!(path.as_deref(), Some("/oav-configs/spring.yaml"));
+    }
+
+    #[test]
+    fn resolve_config_path_override_beats_generator_configs() {
+        let mut cfg = test_config();
+        cfg.generator_configs
+            .insert("spring".into(), "configs/spring.yaml".into());
+        cfg.generator_config_overrides
+            .insert("spring".into(), "/work/custom/spring.yaml".into());
+        let path = resolve_config_path(&cfg, Path::new("/proj"), "spring", "server");
+        assert_eq!(path.as_deref(), Some("/work/custom/spring.yaml"));
+    }
+
+    #[test]
+    fn write_builtin_configs_rejects_missing_user_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut cfg = test_config();
+        cfg.generator_configs
+            .insert("spring".into(), "missing.yaml".into());
+        let generators = vec![("spring".into(), "server".into())];
+        let err = write_builtin_configs(&cfg, tmp.path(), &generators).unwrap_err();
+        assert!(err.contains("not found"));
+        assert!(!tmp.path().join(".oav/configs/server/spring.yaml").exists());
+    }
+
+    #[test]
+    fn template_dirs_are_passed_with_t() {
+        let mut cfg = test_config();
+        cfg.template_dirs
+            .insert("spring".into(), "templates/spring".into());
+        cfg.template_dirs
+            .insert("go-server".into(), "/opt/templates/go".into());
+        let command = |generator: &str| {
+            generator_command(
+                &cfg,
+                Path::new("/proj/spec.yaml"),
+                Path::new("/proj"),
+                generator,
+                "server",
+                None,
+            )
+            .args
+        };
+
+        let args = command("spring");
+        let t_pos = args.iter().position(|a| a == "-t").unwrap();
+        assert_eq!(args[t_pos + 1], "/work/templates/spring");
+        assert!(!args.iter().any(|a| a.ends_with(":ro")));
+
+        let args = command("go-server");
+        let t_pos = args.iter().position(|a| a == "-t").unwrap();
+        assert_eq!(args[t_pos + 1], "/oav-templates/go-server");
+        assert!(args.contains(&"/opt/templates/go:/oav-templates/go-server:ro".into()));
+
+        assert!(!command("kotlin-spring").contains(&"-t".into()));
+    }
+
+    #[test]
+    fn write_builtin_configs_rejects_missing_template_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut cfg = test_config();
+        cfg.template_dirs
+            .insert("spring".into(), "templates/spring".into());
+        let generators = vec![("spring".into(), "server".into())];
+        let err = write_builtin_configs(&cfg, tmp.path(), &generators).unwrap_err();
+        assert!(err.starts_with("template directory for 'spring' not found"));
+
+        std::fs::create_dir_all(tmp.path().join("templates/spring")).unwrap();
+        write_builtin_configs(&cfg, tmp.path(), &generators).unwrap();
+    }
+
     #[test]
     fn resolve_config_path_unknown_generator() {
         let cfg = test_config();
-        let path = resolve_config_path(&cfg, "unknown-gen", "server");
+        let path = resolve_config_path(&cfg, Path::new("/tmp"), "unknown-gen", "server");
         assert!(path.is_none());
     }
 
@@ -535,6 +1678,37 @@ mod tests {
         assert!(!config_path.exists());
     }
 
+    #[test]
+    fn phase_timeouts_fall_back_to_docker_timeout() {
+        let mut cfg = test_config();
+        cfg.docker_timeout = 60;
+        cfg.compile_timeout = Some(1800);
+        let work = Path::new("/tmp");
+        let spec = Path::new("/tmp/spec.yaml");
+
+        assert_eq!(
+            redocly_command(&cfg, spec, work).timeout,
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            compile_command(&cfg, work, "spring", "server").timeout,
+            Duration::from_secs(1800)
+        );
+
+        cfg.lint_timeout = Some(20);
+        cfg.generate_timeout = Some(240);
+        assert_eq!(
+            security_command(&cfg, spec, work).timeout,
+            Duration::from_secs(20)
+        );
+        let generate = generator_command(&cfg, spec, work, "spring", "server", None);
+        assert_eq!(generate.timeout, Duration::from_secs(240));
+        assert_eq!(
+            docs_command(&cfg, spec, work).timeout,
+            Duration::from_secs(60)
+        );
+    }
+
     #[test]
     fn spectral_command_timeout_from_config() {
         let mut cfg = test_config();
@@ -555,6 +1729,14 @@ mod tests {
                 image: "build-image:latest".into(),
                 command: "npm run build".into(),
             }),
+            test: Some(crate::custom::CompileBlock {
+                image: "build-image:latest".into(),
+                command: "npm test".into(),
+            }),
+            code_lint: Some(crate::custom::CompileBlock {
+                image: "build-image:latest".into(),
+                command: "npx eslint .".into(),
+            }),
         }
     }
 
@@ -0,0 +1,187 @@
+//! The images a run used, resolved to the digests it actually ran, so a
+//! report says exactly which containers produced it.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::docker::image::{ImageRef, digest_of, named_digest};
+
+use super::types::{Invocation, Phases};
+
+/// What resolving a run's images found: image reference to the repo digest
+/// (`name@sha256:...`) it ran, and anything worth a warning.
+#[derive(Debug, Default)]
+pub struct ResolvedImages {
+    pub images: BTreeMap<String, String>,
+    pub warnings: Vec<String>,
+}
+
+/// Every image reference a step of `phases` ran.
+pub fn used(phases: &Phases) -> BTreeSet<&str> {
+    let steps = [
+        &phases.generate,
+        &phases.compile,
+        &phases.test,
+        &phases.code_lint,
+    ]
+    .into_iter()
+    .flatten()
+    .flatten()
+    .map(|s| &s.invocation);
+    let lints = [&phases.lint, &phases.security]
+        .into_iter()
+        .flatten()
+        .map(|r| &r.invocation);
+    let docs = phases.docs.iter().map(|r| &r.invocation);
+    let custom = phases.custom.iter().flatten().map(|r| &r.invocation);
+    steps
+        .chain(lints)
+        .chain(docs)
+        .chain(custom)
+        .flatten()
+        .filter_map(|i: &Invocation| i.image.as_deref())
+        .collect()
+}
+
+/// Resolve `images` with `repo_digests` (see [`crate::docker::repo_digests`]).
+///
+/// A pinned reference is checked against the digests of the local copy
+/// under its tag, since docker can't inspect a tag and digest together. A tag
+/// whose digest differs from the one in `previous`, the last report's
+/// images, has moved since that run.
+pub fn resolve<'a>(
+    images: impl IntoIterator<Item = &'a str>,
+    repo_digests: impl Fn(&str) -> Vec<String>,
+    previous: &BTreeMap<String, String>,
+) -> ResolvedImages {
+    let mut resolved = ResolvedImages::default();
+    for image in images {
+        let reference = ImageRef::parse(image);
+        let digests = match reference {
+            ImageRef {
+                repository,
+                tag: Some(tag),
+                digest: Some(_),
+            } => repo_digests(&format!("{repository}:{tag}")),
+            _ => repo_digests(image),
+        };
+        if let Some(pinned) = reference.digest {
+            if !digests.is_empty() && !digests.iter().any(|d| digest_of(d) == pinned) {
+                resolved.warnings.push(format!(
+                    "Image {image}: the local copy doesn't carry the pinned digest"
+                ));
+                continue;
+            }
+            let used = format!("{}@{pinned}", reference.repository);
+            resolved.images.insert(image.to_string(), used);
+            continue;
+        }
+        // Built locally rather than pulled: nothing to record.
+        let Some(current) = named_digest(image, &digests) else {
+            continue;
+        };
+        if let Some(before) = previous.get(image)
+            && digest_of(before) != digest_of(current)
+        {
+            resolved.warnings.push(format!(
+                "Image {image} changed since the last run ({} \u{2192} {}); pin it as {current} to keep runs reproducible",
+                short(before),
+                short(current),
+            ));
+        }
+        resolved.images.insert(image.to_string(), current.clone());
+    }
+    resolved
+}
+
+/// `sha256:` and the first 12 hex digits of a digest.
+fn short(repo_digest: &str) -> &str {
+    let digest = digest_of(repo_digest);
+    &digest[..digest.len().min("sha256:".len() + 12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{LintResult, StepResult};
+
+    const OLD: &str = "sha256:1111111111111111111111111111111111111111111111111111111111111111";
+    const NEW: &str = "sha256:2222222222222222222222222222222222222222222222222222222222222222";
+
+    fn invocation(image: &str) -> Option<Invocation> {
+        Some(Invocation {
+            image: Some(image.into()),
+            ..Invocation::default()
+        })
+    }
+
+    #[test]
+    fn used_collects_each_image_once() {
+        let step = |image: &str| StepResult {
+            invocation: invocation(image),
+            ..StepResult::default()
+        };
+        let phases = Phases {
+            lint: Some(LintResult {
+                invocation: invocation("stoplight/spectral:6"),
+                ..LintResult::default()
+            }),
+            generate: Some(vec![step("cli:v7"), step("cli:v7")]),
+            compile: Some(vec![step("maven:3")]),
+            ..Phases::default()
+        };
+        let used: Vec<_> = used(&phases).into_iter().collect();
+        assert_eq!(used, ["cli:v7", "maven:3", "stoplight/spectral:6"]);
+    }
+
+    #[test]
+    fn resolve_records_digests_and_warns_when_a_tag_moved() {
+        let local = |image: &str| match image {
+            "stoplight/spectral:6" => vec![format!("stoplight/spectral@{NEW}")],
+            "maven:3" => vec![format!("maven@{OLD}")],
+            _ => Vec::new(),
+        };
+        let previous = BTreeMap::from([
+            (
+                "stoplight/spectral:6".to_string(),
+                format!("stoplight/spectral@{OLD}"),
+            ),
+            ("maven:3".to_string(), format!("maven@{OLD}")),
+        ]);
+        let resolved = resolve(
+            ["stoplight/spectral:6", "maven:3", "built:local"],
+            local,
+            &previous,
+        );
+        assert_eq!(
+            resolved.images.get("stoplight/spectral:6"),
+            Some(&format!("stoplight/spectral@{NEW}"))
+        );
+        assert!(!resolved.images.contains_key("built:local"));
+        assert_eq!(resolved.warnings.len(), 1);
+        assert!(resolved.warnings[0].contains("sha256:111111111111 \u{2192} sha256:222222222222"));
+    }
+
+    #[test]
+    fn resolve_checks_pinned_references() {
+        let pinned = format!("redocly/cli:1@{NEW}");
+        let stale = format!("maven@{NEW}");
+        let local = |image: &str| match image {
+            // Inspected by its tag, not the tag and digest together.
+            "redocly/cli:1" => vec![format!("redocly/cli@{NEW}")],
+            _ if image.starts_with("maven@") => vec![format!("maven@{OLD}")],
+            _ => Vec::new(),
+        };
+        let resolved = resolve([pinned.as_str(), stale.as_str()], local, &BTreeMap::new());
+        assert_eq!(
+            resolved.images.get(&pinned),
+            Some(&format!("redocly/cli@{NEW}"))
+        );
+        assert!(!resolved.images.contains_key(&stale));
+        assert_eq!(
+            resolved.warnings,
+            [format!(
+                "Image {stale}: the local copy doesn't carry the pinned digest"
+            )]
+        );
+    }
+}
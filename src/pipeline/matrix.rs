@@ -0,0 +1,439 @@
+//! Generator version matrix: the configured generators run once per
+//! openapi-generator image in `generator_matrix`, to see what an upgrade
+//! would break or change before bumping `generator_image`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+use crate::docker::{CancelToken, ImageRef};
+use crate::reports::{self, REPORT_FILE};
+
+use super::commands::build_generator_list;
+use super::orchestrator::run_pipeline;
+use super::types::{PipelineEvent, PipelineInput, ValidateReport};
+
+/// Each version's generated output is kept under here, by label.
+pub const MATRIX_DIR: &str = ".oav/matrix";
+
+/// Where the pinned version's output waits while the matrix runs.
+const STASH_DIR: &str = ".oav/matrix/.pinned";
+
+/// The id of the process that stashed the pinned output, to tell the stash
+/// of a matrix run that crashed from one still running.
+const STASH_OWNER: &str = ".oav/matrix/.pinned.pid";
+const GENERATED_DIR: &str = ".oav/generated";
+
+/// One column of the matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixVersion {
+    /// The tag, or the whole entry when it names another image.
+    pub label: String,
+    pub image: String,
+}
+
+impl MatrixVersion {
+    /// Where this version's output of `key` (`scope/generator`) is kept.
+    pub fn output_dir(&self, root: &Path, key: &str) -> PathBuf {
+        let dir: String = self
+            .label
+            .chars()
+            .map(|c| if matches!(c, '/' | ':') { '_' } else { c })
+            .collect();
+        root.join(MATRIX_DIR).join(dir).join(key)
+    }
+}
+
+/// The versions to compare. An entry without `/` or `:`, a digest aside
+/// (`v7.10.0@sha256:...`), is a tag of `generator_image`'s repository;
+/// anything else is a full image reference.
+pub fn versions(cfg: &Config) -> Vec<MatrixVersion> {
+    let repository = ImageRef::parse(&cfg.generator_image).repository;
+    cfg.generator_matrix
+        .iter()
+        .map(|entry| {
+            let name = entry
+                .split_once('@')
+                .map_or(entry.as_str(), |(name, _)| name);
+            if name.contains(['/', ':']) {
+                MatrixVersion {
+                    label: entry.clone(),
+                    image: entry.clone(),
+                }
+            } else {
+                MatrixVersion {
+                    label: entry.clone(),
+                    image: format!("{repository}:{entry}"),
+                }
+            }
+        })
+        .collect()
+}
+
+/// How one generator fared under one version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixCell {
+    pub generate: bool,
+    /// `None` when compile didn't run: it's off, or a generator failed.
+    pub compile: Option<bool>,
+}
+
+impl MatrixCell {
+    pub fn passed(self) -> bool {
+        self.generate && self.compile != Some(false)
+    }
+}
+
+pub enum MatrixEvent {
+    /// Version `index` started.
+    VersionStarted(usize),
+    /// Version `index` finished, with a cell per `scope/generator`.
+    VersionFinished {
+        index: usize,
+        cells: Vec<(String, MatrixCell)>,
+    },
+    /// All versions ran; the pinned output is back in `.oav/generated/`.
+    Completed,
+    Aborted(String),
+}
+
+/// Run the generate (and compile) steps of `input` once per version on a
+/// background thread. Lint, security, test, code lint and docs are
+/// skipped. The output in `.oav/generated/` and the last report are
+/// restored afterwards.
+pub fn run_matrix(
+    input: PipelineInput,
+    versions: Vec<MatrixVersion>,
+    cancel: CancelToken,
+) -> Receiver<MatrixEvent> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let keys: Vec<String> = build_generator_list(&input.config, &input.custom_defs)
+            .iter()
+            .map(|(generator, scope)| format!("{scope}/{generator}"))
+            .collect();
+        let root = input.work_dir.clone();
+        let report = fs::read(root.join(REPORT_FILE)).ok();
+
+        let outcome =
+            stash(&root, &keys).and_then(|()| run_versions(&input, &versions, &keys, &cancel, &tx));
+        let restored = restore(&root, &keys, report.as_deref());
+        let _ = tx.send(match outcome.and(restored) {
+            Ok(()) if cancel.is_cancelled() => MatrixEvent::Aborted("Cancelled by user".into()),
+            Ok(()) => MatrixEvent::Completed,
+            Err(e) => MatrixEvent::Aborted(format!("{e:#}")),
+        });
+    });
+    rx
+}
+
+fn run_versions(
+    input: &PipelineInput,
+    versions: &[MatrixVersion],
+    keys: &[String],
+    cancel: &CancelToken,
+    tx: &Sender<MatrixEvent>,
+) -> Result<()> {
+    for (index, version) in versions.iter().enumerate() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let _ = tx.send(MatrixEvent::VersionStarted(index));
+        let config = Config {
+            generator_image: version.image.clone(),
+            structure: false,
+            lint: false,
+            security: false,
+            docs: false,
+            test: false,
+            code_lint: false,
+            custom_phases: Vec::new(),
+            hooks: Vec::new(),
+            ..input.config.clone()
+        };
+        let events = run_pipeline(
+            PipelineInput {
+                config,
+                custom_defs: input.custom_defs.clone(),
+                spec_path: input.spec_path.clone(),
+                work_dir: input.work_dir.clone(),
+            },
+            cancel.clone(),
+        );
+        // Timeout prompts go unanswered; such steps time out as configured.
+        let report = events.iter().find_map(|event| match event {
+            PipelineEvent::Completed(report) => Some(Ok(report)),
+            PipelineEvent::Aborted(reason) => Some(Err(reason)),
+            _ => None,
+        });
+        let report = match report {
+            Some(Ok(report)) => report,
+            Some(Err(reason)) => anyhow::bail!("{}: {reason}", version.label),
+            None => anyhow::bail!("{}: pipeline stopped without a result", version.label),
+        };
+        for key in keys {
+            move_tree(
+                &input.work_dir.join(GENERATED_DIR).join(key),
+                &version.output_dir(&input.work_dir, key),
+            )?;
+        }
+        let _ = tx.send(MatrixEvent::VersionFinished {
+            index,
+            cells: cells(&report),
+        });
+    }
+    Ok(())
+}
+
+/// A cell per generate step, with the matching compile step's result.
+fn cells(report: &ValidateReport) -> Vec<(String, MatrixCell)> {
+    let compile = report.phases.compile.as_deref().unwrap_or_default();
+    report
+        .phases
+        .generate
+        .iter()
+        .flatten()
+        .map(|step| {
+            let compiled = compile
+                .iter()
+                .find(|c| c.generator == step.generator && c.scope == step.scope)
+                .map(|c| c.status == "pass");
+            (
+                format!("{}/{}", step.scope, step.generator),
+                MatrixCell {
+                    generate: step.status == "pass",
+                    compile: compiled,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Move the pinned version's output out of the matrix's way.
+fn stash(root: &Path, keys: &[String]) -> Result<()> {
+    let owner = root.join(STASH_OWNER);
+    if let Some(parent) = owner.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&owner, std::process::id().to_string())
+        .with_context(|| format!("Failed to write {}", owner.display()))?;
+    for key in keys {
+        move_tree(
+            &root.join(GENERATED_DIR).join(key),
+            &root.join(STASH_DIR).join(key),
+        )?;
+    }
+    Ok(())
+}
+
+/// Put the pinned output and the last report back.
+fn restore(root: &Path, keys: &[String], report: Option<&[u8]>) -> Result<()> {
+    for key in keys {
+        let generated = root.join(GENERATED_DIR).join(key);
+        if generated.exists() {
+            fs::remove_dir_all(&generated)
+                .with_context(|| format!("Failed to remove {}", generated.display()))?;
+        }
+        move_tree(&root.join(STASH_DIR).join(key), &generated)?;
+    }
+    if let Some(report) = report {
+        reports::write_atomic(&root.join(REPORT_FILE), report)?;
+    }
+    let _ = fs::remove_file(root.join(STASH_OWNER));
+    Ok(())
+}
+
+/// `"{scope}/{generator}"` keys with pinned output waiting in the stash.
+pub fn stash_keys(root: &Path) -> Vec<String> {
+    let dirs = |dir: &Path| -> Vec<String> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    };
+    let stash = root.join(STASH_DIR);
+    dirs(&stash)
+        .into_iter()
+        .flat_map(|scope| {
+            dirs(&stash.join(&scope))
+                .into_iter()
+                .map(move |generator| format!("{scope}/{generator}"))
+        })
+        .collect()
+}
+
+/// The process that stashed the pinned output, if it recorded itself.
+pub fn stash_owner(root: &Path) -> Option<u32> {
+    fs::read_to_string(root.join(STASH_OWNER))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Put pinned output left in the stash by a matrix run that never finished
+/// back in `.oav/generated/`.
+pub fn recover_stash(root: &Path) -> Result<()> {
+    restore(root, &stash_keys(root), None)?;
+    let stash = root.join(STASH_DIR);
+    if stash.exists() {
+        fs::remove_dir_all(&stash)
+            .with_context(|| format!("Failed to remove {}", stash.display()))?;
+    }
+    Ok(())
+}
+
+/// Move directory `from` to `to`, replacing `to`. A missing `from` is fine:
+/// the step that should have filled it failed.
+fn move_tree(from: &Path, to: &Path) -> Result<()> {
+    if !from.exists() {
+        return Ok(());
+    }
+    if to.exists() {
+        fs::remove_dir_all(to).with_context(|| format!("Failed to remove {}", to.display()))?;
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::rename(from, to).with_context(|| format!("Failed to move {}", from.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{Phases, StepResult, Summary};
+
+    fn step(generator: &str, status: &str) -> StepResult {
+        StepResult {
+            generator: generator.into(),
+            scope: "server".into(),
+            status: status.into(),
+            log: String::new(),
+            attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tags_reuse_the_pinned_repository() {
+        let cfg = Config {
+            generator_image: "mirror.example.com:5000/openapi-generator-cli:v7.17.0".into(),
+            generator_matrix: vec!["v7.10.0".into(), "other/cli:v8".into()],
+            ..Config::default()
+        };
+        let versions = versions(&cfg);
+        assert_eq!(
+            versions[0].image,
+            "mirror.example.com:5000/openapi-generator-cli:v7.10.0"
+        );
+        assert_eq!(versions[1].image, "other/cli:v8");
+        assert!(
+            versions[1]
+                .output_dir(Path::new("/p"), "server/go")
+                .ends_with("other_cli_v8/server/go")
+        );
+    }
+
+    #[test]
+    fn tags_follow_a_digest_pinned_image() {
+        let digest = format!("sha256:{}", "ab".repeat(32));
+        let cfg = Config {
+            generator_image: format!("openapitools/openapi-generator-cli:v7.17.0@{digest}"),
+            generator_matrix: vec!["v7.10.0".into(), format!("v7.12.0@{digest}")],
+            ..Config::default()
+        };
+        let versions = versions(&cfg);
+        assert_eq!(
+            versions[0].image,
+            "openapitools/openapi-generator-cli:v7.10.0"
+        );
+        assert_eq!(
+            versions[1].image,
+            format!("openapitools/openapi-generator-cli:v7.12.0@{digest}")
+        );
+    }
+
+    #[test]
+    fn cells_pair_generate_with_compile() {
+        let report = ValidateReport {
+            spec: "openapi.yaml".into(),
+            mode: "server".into(),
+            phases: Phases {
+                generate: Some(vec![step("go", "pass"), step("spring", "fail")]),
+                compile: Some(vec![step("go", "fail")]),
+                ..Phases::default()
+            },
+            summary: Summary {
+                total: 3,
+                passed: 1,
+                failed: 2,
+            },
+            images: Default::default(),
+        };
+        let cells = cells(&report);
+        assert_eq!(cells[0].0, "server/go");
+        assert!(!cells[0].1.passed());
+        assert_eq!(
+            cells[1].1,
+            MatrixCell {
+                generate: false,
+                compile: None
+            }
+        );
+    }
+
+    #[test]
+    fn stash_and_restore_keep_the_pinned_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = vec!["server/go".to_string()];
+        let generated = dir.path().join(GENERATED_DIR).join("server/go");
+        fs::create_dir_all(&generated).unwrap();
+        fs::write(generated.join("api.go"), "pinned").unwrap();
+
+        stash(dir.path(), &keys).unwrap();
+        assert!(!generated.exists());
+        assert_eq!(stash_owner(dir.path()), Some(std::process::id()));
+        fs::create_dir_all(&generated).unwrap();
+        fs::write(generated.join("api.go"), "matrix").unwrap();
+
+        restore(dir.path(), &keys, None).unwrap();
+        assert_eq!(
+            fs::read_to_string(generated.join("api.go")).unwrap(),
+            "pinned"
+        );
+        assert_eq!(stash_owner(dir.path()), None);
+        assert!(stash_keys(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn recover_stash_restores_what_a_crashed_run_left() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = vec!["server/go".to_string(), "client/java".to_string()];
+        for key in &keys {
+            let generated = dir.path().join(GENERATED_DIR).join(key);
+            fs::create_dir_all(&generated).unwrap();
+            fs::write(generated.join("out"), "pinned").unwrap();
+        }
+        stash(dir.path(), &keys).unwrap();
+        assert_eq!(stash_keys(dir.path()), ["client/java", "server/go"]);
+
+        recover_stash(dir.path()).unwrap();
+        for key in &keys {
+            let out = dir.path().join(GENERATED_DIR).join(key).join("out");
+            assert_eq!(fs::read_to_string(out).unwrap(), "pinned");
+        }
+        assert!(!dir.path().join(STASH_DIR).exists());
+        assert_eq!(stash_owner(dir.path()), None);
+    }
+}
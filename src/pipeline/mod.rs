@@ -1,9 +1,14 @@
 pub mod commands;
+pub mod images;
+pub mod matrix;
 pub mod orchestrator;
+pub mod repro;
 mod types;
+pub mod workspace;
 
-pub use orchestrator::run_pipeline;
+pub use orchestrator::{plan, run_pipeline};
 #[allow(unused_imports)]
 pub use types::{
-    LintResult, Phase, Phases, PipelineEvent, PipelineInput, StepResult, Summary, ValidateReport,
+    CustomPhaseResult, DocsResult, Invocation, LintResult, Phase, Phases, PipelineEvent,
+    PipelineInput, StepResult, StructureResult, Summary, ValidateReport,
 };
@@ -1,17 +1,26 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
 
-use crate::config::Linter;
+use crate::config::{Config, HookWhen, Linter, RunAfter};
 use crate::docker::{self, CancelToken, OutputLine};
-
-use crate::custom::CustomGeneratorDef;
+use crate::hooks::{self, HookSite};
+use crate::logs;
+use crate::reports;
+use crate::rulesets;
+use crate::structure::{self, StructureFinding};
 
 use super::commands::{
-    build_generator_list, compile_command, custom_compile_command, custom_generate_command,
-    generator_command, redocly_command, resolve_config_path, spectral_command,
+    build_generator_list, docs_command, proxy_env, run_image_index, security_command, step_command,
     write_builtin_configs,
 };
+use super::images;
+use super::repro::ReproScript;
 use super::types::{
-    LintResult, Phase, Phases, PipelineEvent, PipelineInput, StepResult, Summary, ValidateReport,
+    CustomPhaseResult, DocsResult, Invocation, LintResult, Phase, Phases, PipelineEvent,
+    PipelineInput, StepResult, StructureResult, Summary, ValidateReport,
 };
 
 /// Launch the validation pipeline on a background thread.
@@ -27,319 +36,1158 @@ pub fn run_pipeline(input: PipelineInput, cancel: CancelToken) -> Receiver<Pipel
 }
 
 fn run_inner(input: PipelineInput, cancel: CancelToken, tx: Sender<PipelineEvent>) {
-    if cancel.is_cancelled() {
-        let _ = tx.send(PipelineEvent::Aborted("Cancelled by user".into()));
-        return;
+    let mut repro =
+        ReproScript::new(&input.work_dir).with_hooks(&input.config.hooks, &input.spec_path);
+    let outcome = run_phases(&input, &cancel, &tx, &mut repro);
+
+    // Write the reproduction script before announcing the outcome, so it's
+    // on disk by the time the UI reacts.
+    if !repro.is_empty()
+        && let Err(e) = repro.write()
+    {
+        let _ = tx.send(PipelineEvent::Warning(format!("{e:#}")));
     }
+    let _ = tx.send(outcome);
+}
 
+/// The container commands a run with `input` would start, phase by phase,
+/// without running or writing anything. Compile and test steps are listed
+/// as if every step before them passes, custom phases where they'd finish.
+pub fn plan(input: &PipelineInput) -> Vec<(Phase, docker::ContainerCommand)> {
     let cfg = &input.config;
+    let generators = build_generator_list(cfg, &input.custom_defs);
+    let custom = |after: RunAfter| {
+        cfg.custom_phases
+            .iter()
+            .filter(move |p| p.after == after)
+            .map(|p| Phase::Custom {
+                name: p.name.clone(),
+            })
+    };
+    let mut phases = Vec::new();
+    if cfg.lint {
+        phases.push(Phase::Lint);
+    }
+    if cfg.security {
+        phases.push(Phase::Security);
+    }
+    phases.extend(custom(RunAfter::Lint));
+    if cfg.generate {
+        phases.extend(generators.iter().map(|(generator, scope)| Phase::Generate {
+            generator: generator.clone(),
+            scope: scope.clone(),
+        }));
+    }
+    phases.extend(custom(RunAfter::Generate));
+    if cfg.generate && cfg.compile {
+        phases.extend(generators.iter().map(|(generator, scope)| Phase::Compile {
+            generator: generator.clone(),
+            scope: scope.clone(),
+        }));
+        if cfg.test {
+            phases.extend(generators.iter().map(|(generator, scope)| Phase::Test {
+                generator: generator.clone(),
+                scope: scope.clone(),
+            }));
+        }
+    }
+    if cfg.generate && cfg.code_lint {
+        phases.extend(generators.iter().map(|(generator, scope)| Phase::CodeLint {
+            generator: generator.clone(),
+            scope: scope.clone(),
+        }));
+    }
+    phases.extend(custom(RunAfter::Compile));
+    if cfg.docs {
+        phases.push(Phase::Docs);
+    }
+    phases.extend(custom(RunAfter::All));
+    phases
+        .into_iter()
+        .filter_map(|phase| {
+            let cmd = step_command(
+                cfg,
+                &input.spec_path,
+                &input.work_dir,
+                &input.custom_defs,
+                &phase,
+            )?;
+            Some((phase, cmd))
+        })
+        .collect()
+}
+
+/// `cfg` with its remote rulesets resolved against the cache: kept as URLs
+/// while they can be fetched, swapped for cached copies when they can't or
+/// `offline` is set. A phase left without a ruleset is turned off. Spectral
+/// fetches rulesets from inside the container, where a network problem only
+/// shows up as an opaque error, so the reasons go to `tx` up front.
+fn localize_rulesets(cfg: &Config, work_dir: &Path, tx: &Sender<PipelineEvent>) -> Config {
+    let mut cfg = cfg.clone();
+    let offline = cfg.offline;
+    let proxy = proxy_env(&cfg);
+    let phases = [
+        (
+            cfg.lint && cfg.linter == Linter::Spectral,
+            "Lint",
+            &mut cfg.spectral_ruleset,
+            &mut cfg.lint,
+        ),
+        (
+            cfg.security,
+            "Security",
+            &mut cfg.security_ruleset,
+            &mut cfg.security,
+        ),
+    ];
+    for (enabled, name, ruleset, run) in phases {
+        if !enabled || !rulesets::is_remote(ruleset) {
+            continue;
+        }
+        let resolved = rulesets::resolve(work_dir, ruleset, offline, &proxy);
+        let skip = if resolved.ruleset.is_none() {
+            " \u{2014} skipped"
+        } else {
+            ""
+        };
+        if let Some(warning) = resolved.warning {
+            let _ = tx.send(PipelineEvent::Warning(format!(
+                "{name} ruleset: {warning}{skip}"
+            )));
+        }
+        match resolved.ruleset {
+            Some(local) => *ruleset = local,
+            None => *run = false,
+        }
+    }
+    cfg
+}
+
+/// Run every enabled phase, returning the final `Completed` or `Aborted`
+/// event for the caller to send.
+fn run_phases(
+    input: &PipelineInput,
+    cancel: &CancelToken,
+    tx: &Sender<PipelineEvent>,
+    repro: &mut ReproScript,
+) -> PipelineEvent {
+    if cancel.is_cancelled() {
+        return PipelineEvent::Aborted("Cancelled by user".into());
+    }
+
+    // ── Structure, before any container ──────────────────────────────
     let mut phases = Phases::default();
     let mut total: usize = 0;
     let mut passed: usize = 0;
     let mut failed: usize = 0;
+    if input.config.structure {
+        let result = check_structure(&input.spec_path, tx);
+        total += 1;
+        if result.status == "pass" {
+            passed += 1;
+        } else {
+            failed += 1;
+        }
+        let broken = failed > 0;
+        phases.structure = Some(result);
+        if broken {
+            let summary = Summary {
+                total,
+                passed,
+                failed,
+            };
+            return complete(input, phases, summary, tx);
+        }
+    }
 
-    // ── Lint ──────────────────────────────────────────────────────────
-    if cfg.lint && cfg.linter != Linter::None {
-        let phase = Phase::Lint;
-        let _ = tx.send(PipelineEvent::PhaseStarted(phase.clone()));
+    let cfg = &localize_rulesets(&input.config, &input.work_dir, tx);
+    let generators = build_generator_list(cfg, &input.custom_defs);
+    let progress = Progress::shared(cfg, generators.len());
+    let log_dir = match logs::start_run(&input.work_dir, cfg.log_retention) {
+        Ok(dir) => dir,
+        Err(e) => {
+            let _ = tx.send(PipelineEvent::Warning(format!("Step logs: {e:#}")));
+            None
+        }
+    };
+
+    // `Linter::None` has no command, so no lint step.
+    let lint_cmd = cfg
+        .lint
+        .then(|| {
+            step_command(
+                cfg,
+                &input.spec_path,
+                &input.work_dir,
+                &input.custom_defs,
+                &Phase::Lint,
+            )
+        })
+        .flatten()
+        .inspect(|cmd| repro.record(&Phase::Lint, cmd));
+    let security_cmd = cfg.security.then(|| {
+        let cmd = security_command(cfg, &input.spec_path, &input.work_dir);
+        repro.record(&Phase::Security, &cmd);
+        cmd
+    });
+    let custom_steps = |after: RunAfter| -> Vec<(Phase, docker::ContainerCommand)> {
+        cfg.custom_phases
+            .iter()
+            .filter(|p| p.after == after)
+            .filter_map(|p| {
+                let phase = Phase::Custom {
+                    name: p.name.clone(),
+                };
+                let cmd = step_command(
+                    cfg,
+                    &input.spec_path,
+                    &input.work_dir,
+                    &input.custom_defs,
+                    &phase,
+                )?;
+                Some((phase, cmd))
+            })
+            .collect()
+    };
+    let after_lint = custom_steps(RunAfter::Lint);
+    for (phase, cmd) in &after_lint {
+        repro.record(phase, cmd);
+    }
+    let generate = cfg.generate && !generators.is_empty();
+    if generate && let Err(e) = write_builtin_configs(cfg, &input.work_dir, &generators) {
+        return PipelineEvent::Aborted(format!("Failed to prepare generator configs: {e}"));
+    }
 
-        let cmd = match cfg.linter {
-            Linter::Spectral => spectral_command(cfg, &input.spec_path, &input.work_dir),
-            Linter::Redocly => redocly_command(cfg, &input.spec_path, &input.work_dir),
-            Linter::None => unreachable!(),
+    // ── Lint and security, alongside generate and compile ────────────
+    // Neither depends on the other, so lint (then security, then custom
+    // phases that run after lint) runs on its own thread while the
+    // generators run, all within the jobs limit.
+    let slots = JobSlots::new(cfg.jobs.resolve().max(1));
+    let site = HookSite {
+        work_dir: input.work_dir.clone(),
+        spec_path: input.spec_path.clone(),
+    };
+    let ctx = StepContext {
+        input,
+        site: &site,
+        cancel,
+        tx,
+        progress: &progress,
+        slots: &slots,
+        log_dir: log_dir.as_deref(),
+    };
+    let (
+        (lint, security, mut custom_results),
+        (gen_results, compile_results, test_results, code_lint_results),
+    ) = std::thread::scope(|scope| {
+        let analysis = if lint_cmd.is_some() || security_cmd.is_some() || !after_lint.is_empty() {
+            slots.acquire(cancel).map(|slot| {
+                let ctx = &ctx;
+                let analysis = Analysis {
+                    lint: lint_cmd,
+                    security: security_cmd,
+                    custom: after_lint,
+                };
+                scope.spawn(move || run_analysis(ctx, analysis, slot))
+            })
+        } else {
+            None
         };
 
-        let outcome = run_container(cmd, &cancel, &phase, &tx);
+        let mut gen_results = None;
+        let mut compile_results = None;
+        let mut test_results = None;
+        let mut code_lint_results = None;
+        let mut all_passed = true;
+        if generate {
+            let results = run_steps_parallel(&ctx, &generators, repro, StepKind::Generate);
+            all_passed = results.iter().all(|r| r.status == "pass");
+            gen_results = Some(results);
+        }
+        let mut custom_results = run_custom_phases(&ctx, custom_steps(RunAfter::Generate), repro);
+
+        if generate {
+            // ── Compile (only if all generators passed) ──────────────
+            if cfg.compile && all_passed && !cancel.is_cancelled() {
+                let results = run_steps_parallel(&ctx, &generators, repro, StepKind::Compile);
+                all_passed = results.iter().all(|r| r.status == "pass");
+                compile_results = Some(results);
+
+                // ── Test (only if everything compiled) ───────────────
+                if cfg.test && all_passed && !cancel.is_cancelled() {
+                    test_results =
+                        Some(run_steps_parallel(&ctx, &generators, repro, StepKind::Test));
+                }
+            }
+
+            // ── Code lint (only over output that built) ──────────────
+            if cfg.code_lint && all_passed && !cancel.is_cancelled() {
+                code_lint_results = Some(run_steps_parallel(
+                    &ctx,
+                    &generators,
+                    repro,
+                    StepKind::CodeLint,
+                ));
+            }
+        }
+        custom_results.extend(run_custom_phases(
+            &ctx,
+            custom_steps(RunAfter::Compile),
+            repro,
+        ));
+
+        let (lint, security, mut analysis_custom) = analysis
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+        analysis_custom.extend(custom_results);
+        (
+            (lint, security, analysis_custom),
+            (
+                gen_results,
+                compile_results,
+                test_results,
+                code_lint_results,
+            ),
+        )
+    });
+
+    if cancel.is_cancelled() {
+        return PipelineEvent::Aborted("Cancelled by user".into());
+    }
+
+    for status in lint.iter().chain(&security).map(|r| &r.status) {
+        total += 1;
+        if status == "pass" {
+            passed += 1;
+        } else {
+            failed += 1;
+        }
+    }
+    for r in gen_results
+        .iter()
+        .chain(&compile_results)
+        .chain(&test_results)
+        .chain(&code_lint_results)
+        .flatten()
+    {
         total += 1;
+        if r.status == "pass" {
+            passed += 1;
+        } else {
+            failed += 1;
+        }
+    }
+    phases.lint = lint;
+    phases.security = security;
+    phases.generate = gen_results;
+    phases.compile = compile_results;
+    phases.test = test_results;
+    phases.code_lint = code_lint_results;
+
+    // ── Docs ──────────────────────────────────────────────────────────
+    if cfg.docs {
+        let phase = Phase::Docs;
+        let _ = tx.send(PipelineEvent::PhaseStarted(phase.clone()));
 
-        let lint_success = outcome.success;
-        if lint_success {
+        let _ = std::fs::create_dir_all(input.work_dir.join(".oav/generated/docs"));
+        let cmd = docs_command(cfg, &input.spec_path, &input.work_dir);
+        repro.record(&phase, &cmd);
+
+        let mut outcome = run_hooked(cmd, cfg, &site, cancel, &phase, tx, &progress);
+        persist_log(log_dir.as_deref(), &phase, &mut outcome, tx);
+        finish_step(&progress, &phase, tx);
+        total += 1;
+        if outcome.success {
             passed += 1;
         } else {
             failed += 1;
         }
 
-        phases.lint = Some(LintResult {
-            linter: cfg.linter.as_str().to_string(),
-            status: if lint_success { "pass" } else { "fail" }.to_string(),
+        phases.docs = Some(DocsResult {
+            status: if outcome.success { "pass" } else { "fail" }.to_string(),
             log: outcome.log,
+            attempts: outcome.attempts,
+            invocation: Some(outcome.invocation),
         });
 
         let _ = tx.send(PipelineEvent::PhaseFinished {
-            phase: phase.clone(),
-            success: lint_success,
+            phase,
+            success: outcome.success,
         });
 
         if cancel.is_cancelled() {
-            let _ = tx.send(PipelineEvent::Aborted("Cancelled by user".into()));
-            return;
+            return PipelineEvent::Aborted("Cancelled by user".into());
         }
     }
 
-    // ── Generate ─────────────────────────────────────────────────────
-    let generators = build_generator_list(cfg, &input.custom_defs);
-
-    if cfg.generate && !generators.is_empty() {
-        if let Err(e) = write_builtin_configs(cfg, &input.work_dir, &generators) {
-            let _ = tx.send(PipelineEvent::Aborted(format!(
-                "Failed to write generator configs: {e}"
-            )));
-            return;
-        }
-        let gen_results = run_steps_parallel(
-            &generators,
-            cfg,
-            &input,
-            &input.custom_defs,
-            &cancel,
-            &tx,
-            StepKind::Generate,
-        );
-
-        if cancel.is_cancelled() {
-            let _ = tx.send(PipelineEvent::Aborted("Cancelled by user".into()));
-            return;
-        }
-
-        let all_passed = gen_results.iter().all(|r| r.status == "pass");
-        for r in &gen_results {
-            total += 1;
-            if r.status == "pass" {
-                passed += 1;
-            } else {
-                failed += 1;
-            }
-        }
-        phases.generate = Some(gen_results);
-
-        // ── Compile (only if all generators passed) ──────────────────
-        if cfg.compile && all_passed {
-            let compile_results = run_steps_parallel(
-                &generators,
-                cfg,
-                &input,
-                &input.custom_defs,
-                &cancel,
-                &tx,
-                StepKind::Compile,
-            );
-
-            if cancel.is_cancelled() {
-                let _ = tx.send(PipelineEvent::Aborted("Cancelled by user".into()));
-                return;
-            }
-
-            for r in &compile_results {
-                total += 1;
-                if r.status == "pass" {
-                    passed += 1;
-                } else {
-                    failed += 1;
-                }
-            }
-            phases.compile = Some(compile_results);
+    // ── Custom phases that run last ───────────────────────────────────
+    custom_results.extend(run_custom_phases(&ctx, custom_steps(RunAfter::All), repro));
+    if cancel.is_cancelled() {
+        return PipelineEvent::Aborted("Cancelled by user".into());
+    }
+    for r in &custom_results {
+        total += 1;
+        if r.status == "pass" {
+            passed += 1;
+        } else {
+            failed += 1;
         }
     }
+    phases.custom = (!custom_results.is_empty()).then_some(custom_results);
 
-    let report = ValidateReport {
+    let summary = Summary {
+        total,
+        passed,
+        failed,
+    };
+    complete(input, phases, summary, tx)
+}
+
+/// Save the report of a finished run and announce it.
+fn complete(
+    input: &PipelineInput,
+    phases: Phases,
+    summary: Summary,
+    tx: &Sender<PipelineEvent>,
+) -> PipelineEvent {
+    let mut report = ValidateReport {
         spec: input
             .spec_path
             .file_name()
             .unwrap_or_default()
             .to_string_lossy()
             .to_string(),
-        mode: cfg.mode.as_str().to_string(),
+        mode: input.config.mode.as_str().to_string(),
         phases,
-        summary: Summary {
-            total,
-            passed,
-            failed,
-        },
+        summary,
+        images: BTreeMap::new(),
     };
+    report.images = record_images(input, &report.phases, tx);
 
     // Persist report to disk.
-    let report_path = input.work_dir.join(".oav/reports/report.json");
-    if let Ok(json) = serde_json::to_string_pretty(&report) {
-        let _ = std::fs::write(&report_path, json);
+    if let Err(e) = reports::save(&input.work_dir, &report) {
+        let _ = tx.send(PipelineEvent::Warning(format!("{e:#}")));
     }
 
-    let _ = tx.send(PipelineEvent::Completed(report));
+    PipelineEvent::Completed(Box::new(report))
+}
+
+/// Resolve the images the run used to their digests, warning about tags
+/// that moved since the last report and pinned images that don't match.
+fn record_images(
+    input: &PipelineInput,
+    phases: &Phases,
+    tx: &Sender<PipelineEvent>,
+) -> BTreeMap<String, String> {
+    let previous = reports::load(&input.work_dir)
+        .map(|report| report.images)
+        .unwrap_or_default();
+    let resolved = images::resolve(images::used(phases), docker::repo_digests, &previous);
+    for warning in resolved.warnings {
+        let _ = tx.send(PipelineEvent::Warning(warning));
+    }
+    resolved.images
+}
+
+/// Parse the spec and look over its OpenAPI fields. Runs no container, so
+/// a spec that doesn't parse fails in milliseconds; schema violations are
+/// reported without failing the phase.
+fn check_structure(spec_path: &Path, tx: &Sender<PipelineEvent>) -> StructureResult {
+    let phase = Phase::Structure;
+    let _ = tx.send(PipelineEvent::PhaseStarted(phase.clone()));
+    let started = Instant::now();
+    let findings = match std::fs::read_to_string(spec_path) {
+        Ok(source) => structure::check(&source),
+        Err(e) => vec![StructureFinding {
+            message: format!("Failed to read {}: {e}", spec_path.display()),
+            ..Default::default()
+        }],
+    };
+    let elapsed = started.elapsed();
+
+    let mut lines: Vec<String> = findings
+        .iter()
+        .map(|f| match f.pointer.as_deref() {
+            Some("") => format!("(root): {}", f.message),
+            Some(pointer) => format!("{pointer}: {}", f.message),
+            None if f.line > 0 => format!("{}:{}: {}", f.line, f.col, f.message),
+            None => f.message.clone(),
+        })
+        .collect();
+    let success = !findings.iter().any(StructureFinding::is_syntax_error);
+    lines.push(if findings.is_empty() {
+        format!("Structure check passed in {} ms", elapsed.as_millis())
+    } else if success {
+        format!(
+            "{} schema problem(s) in {} ms \u{2014} the linter has the final word",
+            findings.len(),
+            elapsed.as_millis()
+        )
+    } else {
+        format!(
+            "Spec didn't parse in {} ms \u{2014} skipped the other phases",
+            elapsed.as_millis()
+        )
+    });
+    for line in &lines {
+        let _ = tx.send(PipelineEvent::Log {
+            phase: phase.clone(),
+            line: line.clone(),
+            elapsed,
+        });
+    }
+    let _ = tx.send(PipelineEvent::PhaseFinished { phase, success });
+    StructureResult {
+        status: if success { "pass" } else { "fail" }.to_string(),
+        log: lines.join("\n"),
+        findings,
+    }
+}
+
+/// Completion across the pipeline's steps, shared by the step threads.
+struct Progress {
+    total: usize,
+    finished: usize,
+    /// Sub-progress (0..1) of running steps that report it.
+    running: HashMap<Phase, f32>,
+}
+
+type SharedProgress = Arc<Mutex<Progress>>;
+
+impl Progress {
+    /// Lint, docs and each custom phase are one unit each, and each
+    /// generator one unit per generate, compile, test and code lint. If a
+    /// step is skipped after a failed one, the pipeline completes short of
+    /// the total.
+    fn shared(cfg: &crate::config::Config, generators: usize) -> SharedProgress {
+        let mut total = usize::from(cfg.lint && cfg.linter != Linter::None)
+            + usize::from(cfg.docs)
+            + usize::from(cfg.security)
+            + cfg
+                .custom_phases
+                .iter()
+                .filter(|p| crate::config::is_valid_phase_name(&p.name))
+                .count();
+        if cfg.generate {
+            total += generators;
+            if cfg.compile {
+                total += generators * (1 + usize::from(cfg.test));
+            }
+            total += generators * usize::from(cfg.code_lint);
+        }
+        Arc::new(Mutex::new(Self {
+            total,
+            finished: 0,
+            running: HashMap::new(),
+        }))
+    }
+
+    fn event(&self, phase: &Phase) -> PipelineEvent {
+        PipelineEvent::Progress {
+            phase: phase.clone(),
+            done: self.finished as f32 + self.running.values().sum::<f32>(),
+            total: self.total,
+        }
+    }
+}
+
+fn step_progress(
+    progress: &SharedProgress,
+    phase: &Phase,
+    fraction: f32,
+    tx: &Sender<PipelineEvent>,
+) {
+    let Ok(mut p) = progress.lock() else {
+        return;
+    };
+    p.running.insert(phase.clone(), fraction.clamp(0.0, 1.0));
+    let _ = tx.send(p.event(phase));
+}
+
+fn finish_step(progress: &SharedProgress, phase: &Phase, tx: &Sender<PipelineEvent>) {
+    let Ok(mut p) = progress.lock() else {
+        return;
+    };
+    p.running.remove(phase);
+    p.finished += 1;
+    let _ = tx.send(p.event(phase));
+}
+
+/// Sub-progress from a step's output line. Maven's reactor prints
+/// `Building <module> <version>  [n/m]` as it starts module n of m.
+fn step_fraction(line: &str) -> Option<f32> {
+    if !line.contains("Building ") {
+        return None;
+    }
+    let counter = line.trim_end().strip_suffix(']')?;
+    let (_, counter) = counter.rsplit_once('[')?;
+    let (n, m) = counter.split_once('/')?;
+    let (n, m): (usize, usize) = (n.trim().parse().ok()?, m.trim().parse().ok()?);
+    (n >= 1 && n <= m).then(|| (n - 1) as f32 / m as f32)
 }
 
 #[derive(Clone, Copy)]
 enum StepKind {
     Generate,
     Compile,
+    Test,
+    CodeLint,
+}
+
+/// What the step threads share: the run's input, its cancel token and
+/// event channel, progress, and the jobs limit.
+struct StepContext<'a> {
+    input: &'a PipelineInput,
+    site: &'a HookSite,
+    cancel: &'a CancelToken,
+    tx: &'a Sender<PipelineEvent>,
+    progress: &'a SharedProgress,
+    slots: &'a Arc<JobSlots>,
+    /// This run's directory under `.oav/logs/`, when logs are kept.
+    log_dir: Option<&'a Path>,
+}
+
+/// Counts free job slots so that lint and the generator steps together
+/// never run more than `jobs` containers at once.
+struct JobSlots {
+    free: Mutex<usize>,
+    freed: Condvar,
 }
 
-/// Run a set of generator/compile steps with bounded parallelism.
+/// A held job slot, returned when dropped.
+struct JobSlot(Arc<JobSlots>);
+
+impl JobSlots {
+    fn new(jobs: usize) -> Arc<Self> {
+        Arc::new(Self {
+            free: Mutex::new(jobs),
+            freed: Condvar::new(),
+        })
+    }
+
+    /// Wait for a free slot. Returns `None` if the run is cancelled first.
+    fn acquire(self: &Arc<Self>, cancel: &CancelToken) -> Option<JobSlot> {
+        let mut free = self.free.lock().ok()?;
+        loop {
+            if cancel.is_cancelled() {
+                return None;
+            }
+            if *free > 0 {
+                *free -= 1;
+                return Some(JobSlot(self.clone()));
+            }
+            free = self
+                .freed
+                .wait_timeout(free, Duration::from_millis(100))
+                .ok()?
+                .0;
+        }
+    }
+}
+
+impl Drop for JobSlot {
+    fn drop(&mut self) {
+        if let Ok(mut free) = self.0.free.lock() {
+            *free += 1;
+        }
+        self.0.freed.notify_one();
+    }
+}
+
+/// The steps the analysis thread runs, in order.
+struct Analysis {
+    lint: Option<docker::ContainerCommand>,
+    security: Option<docker::ContainerCommand>,
+    /// Custom phases that run after lint.
+    custom: Vec<(Phase, docker::ContainerCommand)>,
+}
+
+/// Run lint, the security phase and then the custom phases that follow
+/// lint, whichever are configured, in the one job slot `_slot`.
+fn run_analysis(
+    ctx: &StepContext,
+    analysis: Analysis,
+    _slot: JobSlot,
+) -> (
+    Option<LintResult>,
+    Option<LintResult>,
+    Vec<CustomPhaseResult>,
+) {
+    let linter = ctx.input.config.linter.as_str();
+    let lint = analysis
+        .lint
+        .map(|cmd| run_lint_step(ctx, cmd, Phase::Lint, linter));
+    let security = analysis
+        .security
+        .filter(|_| !ctx.cancel.is_cancelled())
+        // The security phase runs Spectral, whatever the style linter is.
+        .map(|cmd| run_lint_step(ctx, cmd, Phase::Security, Linter::Spectral.as_str()));
+    let mut custom = Vec::new();
+    for (phase, cmd) in analysis.custom {
+        if ctx.cancel.is_cancelled() {
+            break;
+        }
+        custom.push(run_custom_step(ctx, cmd, phase));
+    }
+    (lint, security, custom)
+}
+
+/// Run one step on the current thread: announce it, run its container,
+/// keep its log and count it done.
+fn run_step(ctx: &StepContext, cmd: docker::ContainerCommand, phase: Phase) -> StepOutcome {
+    let _ = ctx.tx.send(PipelineEvent::PhaseStarted(phase.clone()));
+    let mut outcome = run_hooked(
+        cmd,
+        &ctx.input.config,
+        ctx.site,
+        ctx.cancel,
+        &phase,
+        ctx.tx,
+        ctx.progress,
+    );
+    persist_log(ctx.log_dir, &phase, &mut outcome, ctx.tx);
+    finish_step(ctx.progress, &phase, ctx.tx);
+    let _ = ctx.tx.send(PipelineEvent::PhaseFinished {
+        phase,
+        success: outcome.success,
+    });
+    outcome
+}
+
+fn run_lint_step(
+    ctx: &StepContext,
+    cmd: docker::ContainerCommand,
+    phase: Phase,
+    linter: &str,
+) -> LintResult {
+    let outcome = run_step(ctx, cmd, phase);
+    LintResult {
+        linter: linter.to_string(),
+        status: if outcome.success { "pass" } else { "fail" }.to_string(),
+        log: outcome.log,
+        attempts: outcome.attempts,
+        invocation: Some(outcome.invocation),
+    }
+}
+
+fn run_custom_step(
+    ctx: &StepContext,
+    cmd: docker::ContainerCommand,
+    phase: Phase,
+) -> CustomPhaseResult {
+    let name = phase.to_string();
+    let outcome = run_step(ctx, cmd, phase);
+    CustomPhaseResult {
+        name,
+        status: if outcome.success { "pass" } else { "fail" }.to_string(),
+        log: outcome.log,
+        attempts: outcome.attempts,
+        invocation: Some(outcome.invocation),
+    }
+}
+
+/// Run custom phases one after another, each in a job slot.
+fn run_custom_phases(
+    ctx: &StepContext,
+    steps: Vec<(Phase, docker::ContainerCommand)>,
+    repro: &mut ReproScript,
+) -> Vec<CustomPhaseResult> {
+    let mut results = Vec::new();
+    for (phase, cmd) in steps {
+        let Some(_slot) = ctx.slots.acquire(ctx.cancel) else {
+            break;
+        };
+        repro.record(&phase, &cmd);
+        results.push(run_custom_step(ctx, cmd, phase));
+    }
+    results
+}
+
+/// Run a set of generate, compile, test or code lint steps, each in a job
+/// slot as one frees up. Results keep the order of `generators`.
 fn run_steps_parallel(
+    ctx: &StepContext,
     generators: &[(String, String)],
-    cfg: &crate::config::Config,
-    input: &PipelineInput,
-    custom_defs: &[CustomGeneratorDef],
-    cancel: &CancelToken,
-    tx: &Sender<PipelineEvent>,
+    repro: &mut ReproScript,
     kind: StepKind,
 ) -> Vec<StepResult> {
-    let jobs = cfg.jobs.resolve().max(1);
-    let mut results = Vec::with_capacity(generators.len());
+    let input = ctx.input;
+    let cfg = &input.config;
+    let mut handles = Vec::with_capacity(generators.len());
 
-    for chunk in generators.chunks(jobs) {
-        if cancel.is_cancelled() {
+    for (gen_name, scope) in generators {
+        if ctx.cancel.is_cancelled() {
             break;
         }
+        let phase = match kind {
+            StepKind::Generate => Phase::Generate {
+                generator: gen_name.clone(),
+                scope: scope.clone(),
+            },
+            StepKind::Compile => Phase::Compile {
+                generator: gen_name.clone(),
+                scope: scope.clone(),
+            },
+            StepKind::Test => Phase::Test {
+                generator: gen_name.clone(),
+                scope: scope.clone(),
+            },
+            StepKind::CodeLint => Phase::CodeLint {
+                generator: gen_name.clone(),
+                scope: scope.clone(),
+            },
+        };
 
-        let handles: Vec<_> = chunk
-            .iter()
-            .map(|(gen_name, scope)| {
-                let phase = match kind {
-                    StepKind::Generate => Phase::Generate {
-                        generator: gen_name.clone(),
-                        scope: scope.clone(),
-                    },
-                    StepKind::Compile => Phase::Compile {
-                        generator: gen_name.clone(),
-                        scope: scope.clone(),
-                    },
-                };
+        let Some(cmd) = step_command(
+            cfg,
+            &input.spec_path,
+            &input.work_dir,
+            &input.custom_defs,
+            &phase,
+        ) else {
+            // Custom generator without a compile, test or code lint block,
+            // or no known test runner or linter — skip as a no-op.
+            let _ = ctx.tx.send(PipelineEvent::PhaseStarted(phase.clone()));
+            finish_step(ctx.progress, &phase, ctx.tx);
+            let _ = ctx.tx.send(PipelineEvent::PhaseFinished {
+                phase,
+                success: true,
+            });
+            let result = StepResult {
+                generator: gen_name.clone(),
+                scope: scope.clone(),
+                status: "pass".to_string(),
+                log: String::new(),
+                attempts: 0,
+                invocation: None,
+            };
+            handles.push(std::thread::spawn(move || result));
+            continue;
+        };
 
-                let custom_def = find_custom_def(custom_defs, gen_name, scope);
-
-                let cmd = match kind {
-                    StepKind::Generate => {
-                        if let Some(def) = &custom_def {
-                            custom_generate_command(cfg, &input.spec_path, &input.work_dir, def)
-                        } else {
-                            let config_path = resolve_config_path(cfg, gen_name, scope);
-                            generator_command(
-                                cfg,
-                                &input.spec_path,
-                                &input.work_dir,
-                                gen_name,
-                                scope,
-                                config_path.as_deref(),
-                            )
-                        }
-                    }
-                    StepKind::Compile => {
-                        if let Some(def) = &custom_def {
-                            if let Some(compile) = &def.compile {
-                                custom_compile_command(cfg, &input.work_dir, def, compile)
-                            } else {
-                                // No compile block — skip by returning a no-op.
-                                let tx = tx.clone();
-                                let phase_clone = phase.clone();
-                                let gen_name = gen_name.clone();
-                                let scope = scope.clone();
-                                return std::thread::spawn(move || {
-                                    let _ =
-                                        tx.send(PipelineEvent::PhaseStarted(phase_clone.clone()));
-                                    let _ = tx.send(PipelineEvent::PhaseFinished {
-                                        phase: phase_clone,
-                                        success: true,
-                                    });
-                                    StepResult {
-                                        generator: gen_name,
-                                        scope,
-                                        status: "pass".to_string(),
-                                        log: String::new(),
-                                    }
-                                });
-                            }
-                        } else {
-                            compile_command(cfg, &input.work_dir, gen_name, scope)
-                        }
-                    }
-                };
+        let Some(slot) = ctx.slots.acquire(ctx.cancel) else {
+            break;
+        };
+        repro.record(&phase, &cmd);
 
-                let cancel = cancel.clone();
-                let tx = tx.clone();
-                let phase_clone = phase.clone();
-                let gen_name = gen_name.clone();
-                let scope = scope.clone();
-
-                std::thread::spawn(move || {
-                    let _ = tx.send(PipelineEvent::PhaseStarted(phase_clone.clone()));
-                    let outcome = run_container(cmd, &cancel, &phase_clone, &tx);
-                    let success = outcome.success;
-                    let _ = tx.send(PipelineEvent::PhaseFinished {
-                        phase: phase_clone,
-                        success,
-                    });
-                    StepResult {
-                        generator: gen_name,
-                        scope,
-                        status: if success { "pass" } else { "fail" }.to_string(),
-                        log: outcome.log,
-                    }
-                })
-            })
-            .collect();
+        let cancel = ctx.cancel.clone();
+        let tx = ctx.tx.clone();
+        let progress = ctx.progress.clone();
+        let gen_name = gen_name.clone();
+        let scope = scope.clone();
+        let cfg = cfg.clone();
+        let site = ctx.site.clone();
+        let log_dir = ctx.log_dir.map(Path::to_path_buf);
 
-        for handle in handles {
-            if let Ok(result) = handle.join() {
-                results.push(result);
+        handles.push(std::thread::spawn(move || {
+            let _slot = slot;
+            let _ = tx.send(PipelineEvent::PhaseStarted(phase.clone()));
+            let mut outcome = run_hooked(cmd, &cfg, &site, &cancel, &phase, &tx, &progress);
+            persist_log(log_dir.as_deref(), &phase, &mut outcome, &tx);
+            finish_step(&progress, &phase, &tx);
+            let success = outcome.success;
+            let _ = tx.send(PipelineEvent::PhaseFinished { phase, success });
+            StepResult {
+                generator: gen_name,
+                scope,
+                status: if success { "pass" } else { "fail" }.to_string(),
+                log: outcome.log,
+                attempts: outcome.attempts,
+                invocation: Some(outcome.invocation),
             }
-        }
+        }));
     }
 
-    results
+    handles
+        .into_iter()
+        .filter_map(|handle| handle.join().ok())
+        .collect()
 }
 
-fn find_custom_def(
-    defs: &[CustomGeneratorDef],
-    name: &str,
-    scope: &str,
-) -> Option<CustomGeneratorDef> {
-    defs.iter()
-        .find(|d| d.name == name && d.scope == scope)
-        .cloned()
+struct ContainerOutcome {
+    success: bool,
+    log: String,
+    /// Whether the failure looks environmental (pull error, network hiccup)
+    /// rather than a genuine lint/generate/compile failure.
+    transient: bool,
+    exit_code: Option<i32>,
+    timed_out: bool,
+    cancelled: bool,
 }
 
-struct ContainerOutcome {
+struct StepOutcome {
     success: bool,
     log: String,
+    attempts: u32,
+    invocation: Invocation,
+}
+
+/// Exit code `docker run` uses when the daemon itself fails (e.g. image pull).
+const DOCKER_DAEMON_EXIT: i32 = 125;
+
+/// Log fragments that indicate a network or registry problem, not a spec problem.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "TLS handshake timeout",
+    "i/o timeout",
+    "connection reset by peer",
+    "connection refused",
+    "ECONNRESET",
+    "ETIMEDOUT",
+    "EAI_AGAIN",
+    "toomanyrequests",
+    "Could not resolve host",
+    "Temporary failure in name resolution",
+];
+
+/// Run a container, retrying transient failures up to `cfg.retries` times.
+///
+/// Each retry waits `retry_backoff * 2^(attempt-1)` seconds (cancellable).
+/// The returned log contains every attempt, separated by a marker line.
+fn run_with_retries(
+    cmd: docker::ContainerCommand,
+    cfg: &crate::config::Config,
+    cancel: &CancelToken,
+    phase: &Phase,
+    tx: &Sender<PipelineEvent>,
+    progress: &SharedProgress,
+) -> StepOutcome {
+    let max_attempts = cfg.retries.saturating_add(1);
+    let started = Instant::now();
+    let mut log = String::new();
+    let mut attempt = 1;
+
+    loop {
+        let outcome = run_container(cmd.clone(), cancel, phase, tx, progress, started);
+        if attempt > 1 {
+            log.push_str(&format!("--- attempt {attempt}/{max_attempts} ---\n"));
+        }
+        log.push_str(&outcome.log);
+
+        if outcome.success || !outcome.transient || attempt >= max_attempts || cancel.is_cancelled()
+        {
+            return StepOutcome {
+                success: outcome.success,
+                log,
+                attempts: attempt,
+                invocation: invocation(&cmd, &outcome, started),
+            };
+        }
+
+        let delay = retry_delay(cfg.retry_backoff, attempt);
+        let notice = format!(
+            "Transient failure on attempt {attempt}/{max_attempts}, retrying in {}s",
+            delay.as_secs()
+        );
+        let _ = tx.send(PipelineEvent::Log {
+            phase: phase.clone(),
+            line: notice,
+            elapsed: started.elapsed(),
+        });
+        if !sleep_unless_cancelled(delay, cancel) {
+            return StepOutcome {
+                success: false,
+                log,
+                attempts: attempt,
+                invocation: Invocation {
+                    cancelled: true,
+                    ..invocation(&cmd, &outcome, started)
+                },
+            };
+        }
+        attempt += 1;
+    }
+}
+
+/// Run a step's container between its hooks, with their output in its log.
+///
+/// A failing `before` hook fails the step without starting the container.
+/// `after` hooks only run once the container passed, and a failing one
+/// fails the step.
+fn run_hooked(
+    cmd: docker::ContainerCommand,
+    cfg: &crate::config::Config,
+    site: &HookSite,
+    cancel: &CancelToken,
+    phase: &Phase,
+    tx: &Sender<PipelineEvent>,
+    progress: &SharedProgress,
+) -> StepOutcome {
+    let started = Instant::now();
+    let timeout = Duration::from_secs(cfg.hook_timeout);
+    let run_hook = |hook, log: &mut String| {
+        let outcome = hooks::run(hook, phase, site, timeout, cancel);
+        for line in outcome.log.lines() {
+            let _ = tx.send(PipelineEvent::Log {
+                phase: phase.clone(),
+                line: line.to_string(),
+                elapsed: started.elapsed(),
+            });
+        }
+        log.push_str(&outcome.log);
+        outcome.success
+    };
+
+    let mut log = String::new();
+    for hook in hooks::matching(&cfg.hooks, HookWhen::Before, phase) {
+        if !run_hook(hook, &mut log) {
+            return StepOutcome {
+                success: false,
+                log,
+                attempts: 0,
+                invocation: Invocation {
+                    image: run_image_index(&cmd.args).map(|at| cmd.args[at].clone()),
+                    args: docker::redacted_args(&cmd.args),
+                    ..Invocation::default()
+                },
+            };
+        }
+    }
+
+    let mut outcome = run_with_retries(cmd, cfg, cancel, phase, tx, progress);
+    log.push_str(&outcome.log);
+    if outcome.success {
+        outcome.success = hooks::matching(&cfg.hooks, HookWhen::After, phase)
+            .all(|hook| run_hook(hook, &mut log));
+    }
+    outcome.log = log;
+    outcome
+}
+
+/// What ran for a step, ending with the last attempt's `outcome`.
+fn invocation(
+    cmd: &docker::ContainerCommand,
+    outcome: &ContainerOutcome,
+    started: Instant,
+) -> Invocation {
+    Invocation {
+        args: docker::redacted_args(&cmd.args),
+        image: run_image_index(&cmd.args).map(|at| cmd.args[at].clone()),
+        exit_code: outcome.exit_code,
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        timed_out: outcome.timed_out,
+        cancelled: outcome.cancelled,
+        log_file: None,
+    }
+}
+
+/// Keep `outcome`'s full log in the run's log directory, if there is one,
+/// warning on `tx` when it can't be written.
+fn persist_log(
+    log_dir: Option<&Path>,
+    phase: &Phase,
+    outcome: &mut StepOutcome,
+    tx: &Sender<PipelineEvent>,
+) {
+    let Some(dir) = log_dir else {
+        return;
+    };
+    match logs::write(dir, phase, &outcome.log) {
+        Ok(file) => outcome.invocation.log_file = Some(file),
+        Err(e) => {
+            let _ = tx.send(PipelineEvent::Warning(format!("Step logs: {e:#}")));
+        }
+    }
+}
+
+fn retry_delay(backoff_secs: u64, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(6);
+    Duration::from_secs(backoff_secs.saturating_mul(factor))
+}
+
+/// Sleep in short slices so a cancel is honoured promptly.
+/// Returns `false` if cancelled before the delay elapsed.
+fn sleep_unless_cancelled(delay: Duration, cancel: &CancelToken) -> bool {
+    const SLICE: Duration = Duration::from_millis(100);
+    let mut remaining = delay;
+    while !remaining.is_zero() {
+        if cancel.is_cancelled() {
+            return false;
+        }
+        let step = remaining.min(SLICE);
+        std::thread::sleep(step);
+        remaining -= step;
+    }
+    !cancel.is_cancelled()
+}
+
+fn is_transient_failure(result: &docker::ContainerResult) -> bool {
+    // A timed-out step would only wait out its timeout again.
+    if result.success || result.cancelled || result.timed_out {
+        return false;
+    }
+    if result.exit_code == Some(DOCKER_DAEMON_EXIT) {
+        return true;
+    }
+    TRANSIENT_MARKERS.iter().any(|m| result.log.contains(m))
 }
 
 /// Run a single container, draining its output channel and forwarding
-/// lines as `PipelineEvent::Log`.
+/// lines as `PipelineEvent::Log` stamped relative to `started`.
 fn run_container(
     cmd: docker::ContainerCommand,
     cancel: &CancelToken,
     phase: &Phase,
     tx: &Sender<PipelineEvent>,
+    progress: &SharedProgress,
+    started: Instant,
 ) -> ContainerOutcome {
-    let container_rx = match docker::spawn(cmd, cancel.clone()) {
-        Ok(rx) => rx,
+    let (container_rx, control) = match docker::spawn_controlled(cmd, cancel.clone()) {
+        Ok(channels) => channels,
         Err(e) => {
             return ContainerOutcome {
                 success: false,
                 log: format!("Failed to spawn container: {e}"),
+                transient: false,
+                exit_code: None,
+                timed_out: false,
+                cancelled: false,
             };
         }
     };
 
-    let mut log = String::new();
-    let mut success = false;
+    let mut outcome = ContainerOutcome {
+        success: false,
+        log: String::new(),
+        transient: false,
+        exit_code: None,
+        timed_out: false,
+        cancelled: false,
+    };
 
     for line in container_rx {
         match line {
             OutputLine::Stdout(s) | OutputLine::Stderr(s) => {
+                if let Some(fraction) = step_fraction(&s) {
+                    step_progress(progress, phase, fraction, tx);
+                }
                 let _ = tx.send(PipelineEvent::Log {
                     phase: phase.clone(),
                     line: s,
+                    elapsed: started.elapsed(),
+                });
+            }
+            OutputLine::TimeoutWarning { remaining } => {
+                let _ = tx.send(PipelineEvent::TimeoutWarning {
+                    phase: phase.clone(),
+                    remaining,
+                    control: control.clone(),
                 });
             }
             OutputLine::Done(result) => {
-                success = result.success && !result.cancelled;
-                log = result.log;
+                outcome = ContainerOutcome {
+                    success: result.success && !result.cancelled,
+                    transient: is_transient_failure(&result),
+                    exit_code: result.exit_code,
+                    timed_out: result.timed_out,
+                    cancelled: result.cancelled,
+                    log: result.log,
+                };
                 break;
             }
         }
     }
 
-    ContainerOutcome { success, log }
+    outcome
 }
 
 #[cfg(test)]
@@ -359,6 +1207,39 @@ mod tests {
         assert_eq!(pairs.len(), 3);
     }
 
+    #[test]
+    fn offline_lints_with_cached_rulesets_and_skips_uncached_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = Config {
+            offline: true,
+            security: true,
+            ..Config::default()
+        };
+        let cached = rulesets::cache_path(dir.path(), &cfg.spectral_ruleset);
+        std::fs::create_dir_all(cached.parent().unwrap()).unwrap();
+        std::fs::write(&cached, "extends: spectral:oas\n").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let local = localize_rulesets(&cfg, dir.path(), &tx);
+        assert!(local.lint);
+        assert!(
+            local
+                .spectral_ruleset
+                .starts_with("/work/.oav/cache/rulesets/")
+        );
+        assert!(!local.security);
+        let warnings: Vec<String> = rx
+            .try_iter()
+            .filter_map(|e| match e {
+                PipelineEvent::Warning(w) => Some(w),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Security ruleset: Offline"));
+        assert!(warnings[0].ends_with("skipped"));
+    }
+
     #[test]
     fn report_assembly_with_empty_phases() {
         let report = ValidateReport {
@@ -370,6 +1251,7 @@ mod tests {
                 passed: 0,
                 failed: 0,
             },
+            images: Default::default(),
         };
         assert_eq!(report.summary.total, 0);
         assert!(report.phases.lint.is_none());
@@ -383,19 +1265,28 @@ mod tests {
             spec: "test.yaml".into(),
             mode: "server".into(),
             phases: Phases {
+                structure: None,
                 lint: Some(LintResult {
                     linter: "spectral".into(),
                     status: "pass".into(),
                     log: "all good".into(),
+                    attempts: 1,
+                    ..Default::default()
                 }),
                 generate: None,
                 compile: None,
+                test: None,
+                code_lint: None,
+                docs: None,
+                security: None,
+                custom: None,
             },
             summary: Summary {
                 total: 1,
                 passed: 1,
                 failed: 0,
             },
+            images: Default::default(),
         };
         assert_eq!(report.summary.total, 1);
         assert!(report.phases.lint.is_some());
@@ -408,12 +1299,16 @@ mod tests {
             scope: "server".into(),
             status: "pass".into(),
             log: String::new(),
+            attempts: 1,
+            ..Default::default()
         };
         let fail = StepResult {
             generator: "go".into(),
             scope: "client".into(),
             status: "fail".into(),
             log: "compile error".into(),
+            attempts: 1,
+            ..Default::default()
         };
         assert_eq!(pass.status, "pass");
         assert_eq!(fail.status, "fail");
@@ -438,10 +1333,192 @@ mod tests {
         assert_ne!(a, c);
     }
 
+    fn container_result(exit_code: Option<i32>, log: &str) -> docker::ContainerResult {
+        docker::ContainerResult {
+            success: exit_code == Some(0),
+            exit_code,
+            log: log.into(),
+            cancelled: false,
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn transient_failure_detects_daemon_exit_code() {
+        assert!(is_transient_failure(&container_result(Some(125), "")));
+    }
+
+    #[test]
+    fn transient_failure_detects_network_markers() {
+        let result = container_result(Some(2), "Error: connect ETIMEDOUT 1.2.3.4:443");
+        assert!(is_transient_failure(&result));
+    }
+
+    #[test]
+    fn lint_findings_are_not_transient() {
+        let result = container_result(Some(1), "  1:1  error  info-contact  Missing contact.");
+        assert!(!is_transient_failure(&result));
+    }
+
+    #[test]
+    fn timed_out_run_is_not_transient() {
+        let mut result = container_result(Some(DOCKER_DAEMON_EXIT), "i/o timeout");
+        result.timed_out = true;
+        assert!(!is_transient_failure(&result));
+    }
+
+    #[test]
+    fn cancelled_run_is_not_transient() {
+        let mut result = container_result(None, "");
+        result.cancelled = true;
+        result.timed_out = true;
+        assert!(!is_transient_failure(&result));
+    }
+
+    #[test]
+    fn unwritable_step_log_is_a_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        let not_a_dir = dir.path().join("file");
+        std::fs::write(&not_a_dir, "").unwrap();
+        let mut outcome = StepOutcome {
+            success: true,
+            log: "output".into(),
+            attempts: 1,
+            invocation: Invocation::default(),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        persist_log(Some(&not_a_dir), &Phase::Lint, &mut outcome, &tx);
+        assert_eq!(outcome.invocation.log_file, None);
+        match rx.try_recv() {
+            Ok(PipelineEvent::Warning(w)) => assert!(w.starts_with("Step logs: "), "{w}"),
+            other => panic!("expected a warning, got: {other:?}"),
+        }
+
+        persist_log(Some(dir.path()), &Phase::Lint, &mut outcome, &tx);
+        assert!(outcome.invocation.log_file.is_some());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn retry_delay_doubles_per_attempt() {
+        assert_eq!(retry_delay(2, 1), Duration::from_secs(2));
+        assert_eq!(retry_delay(2, 2), Duration::from_secs(4));
+        assert_eq!(retry_delay(2, 3), Duration::from_secs(8));
+        assert_eq!(retry_delay(0, 3), Duration::ZERO);
+    }
+
+    #[test]
+    fn sleep_unless_cancelled_returns_early() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(!sleep_unless_cancelled(Duration::from_secs(60), &cancel));
+    }
+
+    #[test]
+    fn plan_lists_each_phase_without_running() {
+        let cfg = Config {
+            linter: Linter::None,
+            server_generators: vec!["go-server".into()],
+            mode: crate::config::Mode::Server,
+            docs: true,
+            test: true,
+            ..Config::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let input = PipelineInput {
+            work_dir: tmp.path().to_path_buf(),
+            ..test_input(cfg)
+        };
+
+        let phases: Vec<String> = plan(&input)
+            .iter()
+            .map(|(phase, _)| phase.to_string())
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                "go-server generate",
+                "go-server compile",
+                "go-server test",
+                "docs"
+            ]
+        );
+        assert!(!tmp.path().join(".oav").exists());
+    }
+
+    #[test]
+    fn plan_places_custom_phases_by_when_they_run() {
+        let phase = |name: &str, after: RunAfter| crate::config::CustomPhase {
+            name: name.into(),
+            image: "alpine".into(),
+            args: Vec::new(),
+            after,
+        };
+        let cfg = Config {
+            linter: Linter::None,
+            server_generators: vec!["go-server".into()],
+            mode: crate::config::Mode::Server,
+            docs: true,
+            custom_phases: vec![
+                phase("report", RunAfter::All),
+                phase("breaking", RunAfter::Lint),
+                phase("vet", RunAfter::Compile),
+                phase("license", RunAfter::Generate),
+            ],
+            ..Config::default()
+        };
+        let phases: Vec<String> = plan(&test_input(cfg.clone()))
+            .iter()
+            .map(|(phase, _)| phase.to_string())
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                "breaking",
+                "go-server generate",
+                "license",
+                "go-server compile",
+                "vet",
+                "docs",
+                "report"
+            ]
+        );
+
+        let progress = Progress::shared(&cfg, 1);
+        assert_eq!(progress.lock().unwrap().total, 4 + 1 + 2);
+    }
+
+    #[test]
+    fn job_slots_wait_for_a_free_slot() {
+        let slots = JobSlots::new(1);
+        let cancel = CancelToken::new();
+        let held = slots.acquire(&cancel).unwrap();
+
+        let waiter = {
+            let slots = slots.clone();
+            let cancel = cancel.clone();
+            std::thread::spawn(move || slots.acquire(&cancel).is_some())
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+        drop(held);
+        assert!(waiter.join().unwrap());
+
+        cancel.cancel();
+        assert!(slots.acquire(&cancel).is_none());
+    }
+
     /// Helper: build a `PipelineInput` with the given config and a dummy spec path.
     fn test_input(cfg: Config) -> PipelineInput {
         PipelineInput {
-            config: cfg,
+            // Runs against /tmp shouldn't leave step logs behind, and the
+            // spec there isn't real.
+            config: Config {
+                log_retention: 0,
+                structure: false,
+                ..cfg
+            },
             custom_defs: Vec::new(),
             spec_path: std::path::PathBuf::from("/tmp/spec.yaml"),
             work_dir: std::path::PathBuf::from("/tmp"),
@@ -483,6 +1560,111 @@ mod tests {
         }
     }
 
+    #[test]
+    fn failing_before_hook_fails_the_step_without_running_it() {
+        let cfg = Config {
+            lint: false,
+            generate: false,
+            docs: true,
+            hooks: vec![crate::config::Hook {
+                phase: "docs".into(),
+                when: HookWhen::Before,
+                generator: None,
+                run: "echo not today; exit 1".into(),
+            }],
+            ..Config::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let input = PipelineInput {
+            work_dir: tmp.path().to_path_buf(),
+            ..test_input(cfg)
+        };
+        let events = collect_events(run_pipeline(input, CancelToken::new()));
+
+        let Some(PipelineEvent::Completed(report)) = events.last() else {
+            panic!("expected Completed, got: {:?}", events.last());
+        };
+        let docs = report.phases.docs.as_ref().unwrap();
+        assert_eq!(docs.status, "fail");
+        assert_eq!(docs.attempts, 0);
+        assert!(
+            docs.log
+                .contains("not today\n--- hook failed with exit code 1 ---")
+        );
+        assert_eq!(report.summary.failed, 1);
+        assert!(events.iter().any(|ev| matches!(
+            ev,
+            PipelineEvent::Log { line, .. } if line == "not today"
+        )));
+    }
+
+    #[test]
+    fn broken_spec_fails_the_structure_check_before_any_container() {
+        let tmp = tempfile::tempdir().unwrap();
+        let spec_path = tmp.path().join("spec.yaml");
+        std::fs::write(&spec_path, "openapi: 3.0.3\ninfo:\n  title: [oops\n").unwrap();
+        let cfg = Config {
+            log_retention: 0,
+            server_generators: vec!["spring".into()],
+            ..Config::default()
+        };
+        let input = PipelineInput {
+            config: cfg,
+            custom_defs: Vec::new(),
+            spec_path,
+            work_dir: tmp.path().to_path_buf(),
+        };
+        let events = collect_events(run_pipeline(input, CancelToken::new()));
+
+        let started: Vec<&Phase> = events
+            .iter()
+            .filter_map(|ev| match ev {
+                PipelineEvent::PhaseStarted(phase) => Some(phase),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(started, [&Phase::Structure]);
+        let Some(PipelineEvent::Completed(report)) = events.last() else {
+            panic!("expected Completed, got: {:?}", events.last());
+        };
+        let structure = report.phases.structure.as_ref().unwrap();
+        assert_eq!(structure.status, "fail");
+        assert_eq!(structure.findings.len(), 1);
+        assert!(report.phases.lint.is_none());
+        assert_eq!(report.summary.failed, 1);
+    }
+
+    #[test]
+    fn schema_problems_are_reported_without_failing_the_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let spec_path = tmp.path().join("spec.yaml");
+        std::fs::write(
+            &spec_path,
+            "openapi: 3.0.3\ninfo:\n  title: Pets\npaths: {}\n",
+        )
+        .unwrap();
+        let input = PipelineInput {
+            config: Config {
+                log_retention: 0,
+                lint: false,
+                generate: false,
+                ..Config::default()
+            },
+            custom_defs: Vec::new(),
+            spec_path,
+            work_dir: tmp.path().to_path_buf(),
+        };
+        let events = collect_events(run_pipeline(input, CancelToken::new()));
+
+        let Some(PipelineEvent::Completed(report)) = events.last() else {
+            panic!("expected Completed, got: {:?}", events.last());
+        };
+        let structure = report.phases.structure.as_ref().unwrap();
+        assert_eq!(structure.status, "pass");
+        assert_eq!(structure.findings.len(), 1);
+        assert_eq!(report.summary.failed, 0);
+    }
+
     #[test]
     fn pipeline_lint_disabled_skips_lint() {
         let cfg = Config {
@@ -567,4 +1749,64 @@ mod tests {
             other => panic!("expected Completed, got: {other:?}"),
         }
     }
+
+    #[test]
+    fn progress_counts_lint_and_each_step() {
+        let cfg = Config {
+            lint: true,
+            linter: crate::config::Linter::Spectral,
+            generate: true,
+            compile: true,
+            ..Config::default()
+        };
+        let progress = Progress::shared(&cfg, 2);
+        let (tx, rx) = mpsc::channel();
+        let phase = Phase::Compile {
+            generator: "spring".into(),
+            scope: "server".into(),
+        };
+        finish_step(&progress, &Phase::Lint, &tx);
+        step_progress(&progress, &phase, 0.5, &tx);
+        finish_step(&progress, &phase, &tx);
+        drop(tx);
+
+        let done: Vec<(f32, usize)> = collect_events(rx)
+            .into_iter()
+            .map(|ev| match ev {
+                PipelineEvent::Progress { done, total, .. } => (done, total),
+                other => panic!("expected Progress, got: {other:?}"),
+            })
+            .collect();
+        assert_eq!(done, vec![(1.0, 5), (1.5, 5), (2.0, 5)]);
+
+        let cfg = Config {
+            lint: false,
+            generate: true,
+            compile: false,
+            ..Config::default()
+        };
+        assert_eq!(Progress::shared(&cfg, 3).lock().unwrap().total, 3);
+
+        let cfg = Config {
+            lint: false,
+            test: true,
+            ..Config::default()
+        };
+        assert_eq!(Progress::shared(&cfg, 2).lock().unwrap().total, 6);
+    }
+
+    #[test]
+    fn step_fraction_reads_maven_reactor_counter() {
+        let line = "[INFO] Building openapi-spring 1.0.0                              [2/4]";
+        assert_eq!(step_fraction(line), Some(0.25));
+        assert_eq!(
+            step_fraction("[INFO] Building jar: /work/target/api.jar"),
+            None
+        );
+        assert_eq!(step_fraction("[INFO] Building api [0/4]"), None);
+        assert_eq!(
+            step_fraction("[ERROR] /src/Api.java:[12,5] cannot find symbol"),
+            None
+        );
+    }
 }
@@ -0,0 +1,250 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::{Hook, HookWhen};
+use crate::docker::{self, ContainerCommand};
+use crate::hooks::{self, HookSite};
+
+use super::commands::run_image_index;
+use super::types::Phase;
+
+/// Where the script for the latest run is written, relative to the project.
+pub const REPRO_SCRIPT: &str = ".oav/reports/repro.sh";
+
+/// The docker commands a pipeline run executed, in start order, with the
+/// hooks run around them, for writing out as a standalone shell script.
+/// `-e NAME=value` values are left out, so the script can be shared; the
+/// variables are passed on from the environment instead.
+pub struct ReproScript {
+    work_dir: PathBuf,
+    steps: Vec<(Phase, Vec<String>)>,
+    hooks: Vec<Hook>,
+    spec_path: PathBuf,
+    /// Variables whose values were left out, sorted.
+    env: BTreeSet<String>,
+}
+
+impl ReproScript {
+    pub fn new(work_dir: &Path) -> Self {
+        Self {
+            work_dir: work_dir.to_path_buf(),
+            steps: Vec::new(),
+            hooks: Vec::new(),
+            spec_path: PathBuf::new(),
+            env: BTreeSet::new(),
+        }
+    }
+
+    /// Write `hooks` around the steps they ran around, telling them about
+    /// the spec at `spec_path`.
+    pub fn with_hooks(mut self, hooks: &[Hook], spec_path: &Path) -> Self {
+        self.hooks = hooks.to_vec();
+        self.spec_path = spec_path.to_path_buf();
+        self
+    }
+
+    /// Record a step's command. Retries re-run the same command, so each
+    /// step only needs recording once.
+    pub fn record(&mut self, phase: &Phase, cmd: &ContainerCommand) {
+        let args = docker::redacted_args(&cmd.args);
+        for (arg, redacted) in cmd.args.iter().zip(&args) {
+            if arg != redacted {
+                let name = redacted
+                    .trim_start_matches("--env=")
+                    .trim_start_matches("-e");
+                self.env.insert(name.to_string());
+            }
+        }
+        self.steps.push((phase.clone(), args));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Render the script. `pin` maps an image reference to a digest-pinned
+    /// one; images it returns `None` for are left as they are.
+    ///
+    /// Steps run one after another and failures don't stop the script, so
+    /// every step's output ends up in a single transcript.
+    pub fn render(&self, pin: impl Fn(&str) -> Option<String>) -> String {
+        let mut out = String::from("#!/bin/sh\n");
+        out.push_str(&format!(
+            "# Docker commands from the last lazyoav {} run.\n",
+            env!("CARGO_PKG_VERSION")
+        ));
+        out.push_str("# Run from anywhere; mounts use absolute host paths.\n");
+        if !self.env.is_empty() {
+            let names: Vec<&str> = self.env.iter().map(String::as_str).collect();
+            out.push_str(&format!(
+                "# Values left out; set these first: {}\n",
+                names.join(", ")
+            ));
+        }
+        out.push('\n');
+        out.push_str(&format!(
+            "cd {} || exit 1\n",
+            shell_words::quote(&self.work_dir.display().to_string())
+        ));
+
+        let site = HookSite {
+            work_dir: self.work_dir.clone(),
+            spec_path: self.spec_path.clone(),
+        };
+        let hook_lines = |out: &mut String, when: HookWhen, phase: &Phase| {
+            for hook in hooks::matching(&self.hooks, when, phase) {
+                out.push_str(&hooks::command_line(hook, phase, &site));
+                out.push('\n');
+            }
+        };
+        for (phase, args) in &self.steps {
+            let mut args = args.clone();
+            out.push_str(&format!("\n# {phase}\n"));
+            hook_lines(&mut out, HookWhen::Before, phase);
+            if let Some(at) = run_image_index(&args)
+                && let Some(pinned) = pin(&args[at])
+            {
+                out.push_str(&format!("# image: {}\n", args[at]));
+                args[at] = pinned;
+            }
+            out.push_str(&command_line(&args));
+            out.push('\n');
+            hook_lines(&mut out, HookWhen::After, phase);
+        }
+        out
+    }
+
+    /// Write the script to [`REPRO_SCRIPT`], pinning images to the digests
+    /// of the local copies the run used.
+    pub fn write(&self) -> Result<PathBuf> {
+        let path = self.work_dir.join(REPRO_SCRIPT);
+        let script = self.render(crate::docker::image_digest);
+        fs::write(&path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .with_context(|| format!("Failed to make {} executable", path.display()))?;
+        }
+        Ok(path)
+    }
+}
+
+/// A step's `docker` arguments as one shell command line.
+pub fn command_line(args: &[String]) -> String {
+    format!("docker {}", shell_words::join(args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn command(args: &[&str]) -> ContainerCommand {
+        ContainerCommand {
+            args: args.iter().map(|s| s.to_string()).collect(),
+            env: Vec::new(),
+            timeout: Duration::from_secs(60),
+            log_path: None,
+            project: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn render_lists_steps_in_order_and_pins_images() {
+        let mut repro = ReproScript::new(Path::new("/home/me/my api"));
+        repro.record(
+            &Phase::Lint,
+            &command(&[
+                "run",
+                "--rm",
+                "-v",
+                "/home/me/my api:/work",
+                "spectral:6",
+                "lint",
+            ]),
+        );
+        repro.record(
+            &Phase::Compile {
+                generator: "spring".into(),
+                scope: "server".into(),
+            },
+            &command(&["compose", "-f", "/x/compose.yaml", "run", "build-spring"]),
+        );
+
+        let script = repro.render(|image| Some(format!("{image}@sha256:abc")));
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("cd '/home/me/my api' || exit 1\n"));
+        assert!(script.contains(
+            "# lint\n# image: spectral:6\ndocker run --rm -v '/home/me/my api:/work' spectral:6@sha256:abc lint\n"
+        ));
+        assert!(
+            script
+                .contains("# spring compile\ndocker compose -f /x/compose.yaml run build-spring\n")
+        );
+    }
+
+    #[test]
+    fn render_leaves_out_env_values() {
+        let mut repro = ReproScript::new(Path::new("/tmp"));
+        repro.record(
+            &Phase::Lint,
+            &command(&["run", "-e", "HTTP_PROXY", "-e", "TOKEN=s3cret", "local:dev"]),
+        );
+        let script = repro.render(|_| None);
+        assert!(!script.contains("s3cret"));
+        assert!(script.contains("# Values left out; set these first: TOKEN\n"));
+        assert!(script.contains("docker run -e HTTP_PROXY -e TOKEN local:dev\n"));
+    }
+
+    #[test]
+    fn render_keeps_unpinned_images() {
+        let mut repro = ReproScript::new(Path::new("/tmp"));
+        repro.record(&Phase::Lint, &command(&["run", "--rm", "local:dev"]));
+        let script = repro.render(|_| None);
+        assert!(script.contains("docker run --rm local:dev\n"));
+        assert!(!script.contains("# image:"));
+    }
+
+    #[test]
+    fn render_wraps_steps_in_their_hooks() {
+        let hook = |when, run: &str| Hook {
+            phase: "lint".into(),
+            when,
+            generator: None,
+            run: run.into(),
+        };
+        let hooks = [
+            hook(HookWhen::Before, "make prepare"),
+            hook(HookWhen::After, "make publish"),
+        ];
+        let mut repro =
+            ReproScript::new(Path::new("/w")).with_hooks(&hooks, Path::new("/w/api.yaml"));
+        repro.record(&Phase::Lint, &command(&["run", "--rm", "local:dev"]));
+        let script = repro.render(|_| None);
+        assert!(script.contains(
+            "# lint\n\
+             OAV_PHASE=lint OAV_SPEC=/w/api.yaml OAV_GENERATED=/w/.oav/generated sh -c 'make prepare'\n\
+             docker run --rm local:dev\n\
+             OAV_PHASE=lint OAV_SPEC=/w/api.yaml OAV_GENERATED=/w/.oav/generated sh -c 'make publish'\n"
+        ));
+    }
+
+    #[test]
+    fn write_creates_executable_script() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join(".oav/reports")).unwrap();
+        let repro = ReproScript::new(tmp.path());
+        let path = repro.write().unwrap();
+        assert!(fs::read_to_string(&path).unwrap().starts_with("#!/bin/sh"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+    }
+}
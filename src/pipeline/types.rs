@@ -1,9 +1,15 @@
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
 use crate::custom::CustomGeneratorDef;
+use crate::docker::ContainerControl;
+use crate::structure::StructureFinding;
 
 /// Mirrors the CLI's ValidateReport JSON structure.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -12,28 +18,168 @@ pub struct ValidateReport {
     pub mode: String,
     pub phases: Phases,
     pub summary: Summary,
+    /// Image references the run's containers used, each mapped to the repo
+    /// digest (`name@sha256:...`) it resolved to.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub images: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Phases {
+    /// The local structure check that runs before any container.
+    #[serde(default)]
+    pub structure: Option<StructureResult>,
     pub lint: Option<LintResult>,
     pub generate: Option<Vec<StepResult>>,
     pub compile: Option<Vec<StepResult>>,
+    /// The generated projects' test suites, run after compile passed.
+    #[serde(default)]
+    pub test: Option<Vec<StepResult>>,
+    /// Language linters over the generated projects, run after compile.
+    #[serde(default)]
+    pub code_lint: Option<Vec<StepResult>>,
+    #[serde(default)]
+    pub docs: Option<DocsResult>,
+    /// Findings of the OWASP ruleset, kept apart from the style lint.
+    #[serde(default)]
+    pub security: Option<LintResult>,
+    /// Phases declared under `custom_phases`, in the order they ran.
+    #[serde(default)]
+    pub custom: Option<Vec<CustomPhaseResult>>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LintResult {
     pub linter: String,
     pub status: String,
     pub log: String,
+    /// Number of container runs it took to reach the final status.
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// How the container was started and how its last run ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<Invocation>,
+}
+
+impl Default for LintResult {
+    fn default() -> Self {
+        Self {
+            linter: Default::default(),
+            status: Default::default(),
+            log: Default::default(),
+            attempts: default_attempts(),
+            invocation: Default::default(),
+        }
+    }
 }
 
+/// Result of parsing the spec and checking it against the OpenAPI schema,
+/// without a container.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructureResult {
+    pub status: String,
+    pub log: String,
+    pub findings: Vec<StructureFinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StepResult {
     pub generator: String,
     pub scope: String,
     pub status: String,
     pub log: String,
+    /// Number of container runs it took to reach the final status.
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// How the container was started and how its last run ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<Invocation>,
+}
+
+impl Default for StepResult {
+    fn default() -> Self {
+        Self {
+            generator: Default::default(),
+            scope: Default::default(),
+            status: Default::default(),
+            log: Default::default(),
+            attempts: default_attempts(),
+            invocation: Default::default(),
+        }
+    }
+}
+
+/// Result of building HTML docs from the spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocsResult {
+    pub status: String,
+    pub log: String,
+    /// Number of container runs it took to reach the final status.
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// How the container was started and how its last run ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<Invocation>,
+}
+
+impl Default for DocsResult {
+    fn default() -> Self {
+        Self {
+            status: Default::default(),
+            log: Default::default(),
+            attempts: default_attempts(),
+            invocation: Default::default(),
+        }
+    }
+}
+
+/// Result of a phase declared under `custom_phases` in `.oavc`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPhaseResult {
+    pub name: String,
+    pub status: String,
+    pub log: String,
+    /// Number of container runs it took to reach the final status.
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// How the container was started and how its last run ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<Invocation>,
+}
+
+impl Default for CustomPhaseResult {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            status: Default::default(),
+            log: Default::default(),
+            attempts: default_attempts(),
+            invocation: Default::default(),
+        }
+    }
+}
+
+/// The container a step ran: its `docker` arguments and how the last
+/// attempt ended.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Invocation {
+    /// With `-e NAME=value` values left out (see
+    /// [`crate::docker::redacted_args`]), as it's kept in reports and runs.
+    pub args: Vec<String>,
+    /// `None` for `docker compose` steps, whose image is in the compose file.
+    pub image: Option<String>,
+    pub exit_code: Option<i32>,
+    /// Wall time across every attempt, in milliseconds.
+    pub duration_ms: u64,
+    pub timed_out: bool,
+    pub cancelled: bool,
+    /// The step's full log under `.oav/logs/`, when logs are kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+}
+
+fn default_attempts() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -52,11 +198,50 @@ pub struct PipelineInput {
 }
 
 /// Identifies which pipeline phase is running.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Phase {
+    /// The local structure check; runs no container.
+    Structure,
     Lint,
-    Generate { generator: String, scope: String },
-    Compile { generator: String, scope: String },
+    Security,
+    Generate {
+        generator: String,
+        scope: String,
+    },
+    Compile {
+        generator: String,
+        scope: String,
+    },
+    Test {
+        generator: String,
+        scope: String,
+    },
+    /// Language linters over a generator's output.
+    CodeLint {
+        generator: String,
+        scope: String,
+    },
+    Docs,
+    /// A phase declared under `custom_phases`.
+    Custom {
+        name: String,
+    },
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Structure => f.write_str("structure"),
+            Phase::Lint => f.write_str("lint"),
+            Phase::Security => f.write_str("security"),
+            Phase::Generate { generator, .. } => write!(f, "{generator} generate"),
+            Phase::Compile { generator, .. } => write!(f, "{generator} compile"),
+            Phase::Test { generator, .. } => write!(f, "{generator} test"),
+            Phase::CodeLint { generator, .. } => write!(f, "{generator} code lint"),
+            Phase::Docs => f.write_str("docs"),
+            Phase::Custom { name } => f.write_str(name),
+        }
+    }
 }
 
 /// Events emitted by the pipeline orchestrator.
@@ -64,8 +249,36 @@ pub enum Phase {
 #[allow(dead_code)]
 pub enum PipelineEvent {
     PhaseStarted(Phase),
-    Log { phase: Phase, line: String },
-    PhaseFinished { phase: Phase, success: bool },
-    Completed(ValidateReport),
+    /// A line of container output. `elapsed` is measured from the start of
+    /// the step's first container run, so retries keep counting.
+    Log {
+        phase: Phase,
+        line: String,
+        elapsed: Duration,
+    },
+    /// A step is about to hit its timeout; send
+    /// `ContainerControl::ExtendTimeout` on `control` to keep it running.
+    TimeoutWarning {
+        phase: Phase,
+        remaining: Duration,
+        control: Sender<ContainerControl>,
+    },
+    PhaseFinished {
+        phase: Phase,
+        success: bool,
+    },
+    /// Overall completion: `done` of `total` work units, where lint, docs,
+    /// each custom phase and each generate, compile, test or code lint step
+    /// are one unit. `done` is fractional while running steps report
+    /// sub-progress; `phase` is the step that moved it.
+    Progress {
+        phase: Phase,
+        done: f32,
+        total: usize,
+    },
+    /// A non-fatal problem the user should know about, e.g. a failed
+    /// connectivity pre-check. The pipeline keeps running.
+    Warning(String),
+    Completed(Box<ValidateReport>),
     Aborted(String),
 }
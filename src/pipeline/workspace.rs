@@ -0,0 +1,407 @@
+//! Workspace run: every spec of a repository checked in one go, for a
+//! pass/fail matrix of its whole API surface and a report to hand around.
+
+use std::fs;
+use std::path::{Component, Path};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::config::Config;
+use crate::docker::CancelToken;
+use crate::reports::{self, REPORTS_DIR};
+
+use super::orchestrator::run_pipeline;
+use super::types::{PipelineEvent, PipelineInput, ValidateReport};
+
+/// The last workspace run, as JSON and as a Markdown table.
+pub const WORKSPACE_REPORT: &str = ".oav/reports/workspace.json";
+pub const WORKSPACE_SUMMARY: &str = ".oav/reports/workspace.md";
+
+/// Where `.oav/reports` is kept while the workspace run fills it with the
+/// other specs' reports and logs.
+const REPORTS_BACKUP: &str = ".oav/workspace-reports";
+
+/// How one spec fared. A check is `None` when it's off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecRow {
+    pub spec: String,
+    pub structure: Option<bool>,
+    pub lint: Option<bool>,
+    pub security: Option<bool>,
+    /// Findings that aren't suppressed.
+    pub findings: usize,
+    /// How many of `findings` are in the baseline.
+    #[serde(default)]
+    pub known: usize,
+    /// Why the spec couldn't be checked at all.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SpecRow {
+    /// The row for a finished run of `spec` with `findings` found in all,
+    /// `known` of them in the baseline. The lint status is taken as it is
+    /// in `report`, so settle it first.
+    pub fn from_report(spec: &str, report: &ValidateReport, findings: usize, known: usize) -> Self {
+        let phases = &report.phases;
+        Self {
+            spec: spec.to_string(),
+            structure: phases.structure.as_ref().map(|s| s.status == "pass"),
+            lint: phases.lint.as_ref().map(|l| l.status == "pass"),
+            security: phases.security.as_ref().map(|s| s.status == "pass"),
+            findings,
+            known,
+            error: None,
+        }
+    }
+
+    pub fn failed(spec: &str, reason: String) -> Self {
+        Self {
+            spec: spec.to_string(),
+            structure: None,
+            lint: None,
+            security: None,
+            findings: 0,
+            known: 0,
+            error: Some(reason),
+        }
+    }
+
+    /// The finding count, e.g. `5 (2 known)`.
+    pub fn findings_label(&self) -> String {
+        match self.known {
+            0 => self.findings.to_string(),
+            known => format!("{} ({known} known)", self.findings),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+            && [self.structure, self.lint, self.security]
+                .iter()
+                .all(|check| *check != Some(false))
+    }
+}
+
+/// Every spec's row, with the totals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceReport {
+    pub specs: Vec<SpecRow>,
+    pub passed: usize,
+    pub failed: usize,
+    pub findings: usize,
+}
+
+impl WorkspaceReport {
+    pub fn new(specs: Vec<SpecRow>) -> Self {
+        let passed = specs.iter().filter(|row| row.passed()).count();
+        Self {
+            passed,
+            failed: specs.len() - passed,
+            findings: specs.iter().map(|row| row.findings).sum(),
+            specs,
+        }
+    }
+
+    /// A Markdown table of the specs, for a pull request or a wiki page.
+    pub fn markdown(&self) -> String {
+        let check = |result: Option<bool>| match result {
+            Some(true) => "pass",
+            Some(false) => "fail",
+            None => "-",
+        };
+        let mut out = format!(
+            "# Workspace report\n\n{} of {} spec(s) passing, {} finding(s)\n\n\
+             | Spec | Structure | Lint | Security | Findings |\n\
+             |------|-----------|------|----------|----------|\n",
+            self.passed,
+            self.specs.len(),
+            self.findings
+        );
+        for row in &self.specs {
+            match &row.error {
+                Some(error) => out.push_str(&format!(
+                    "| `{}` | error: {} | | | |\n",
+                    row.spec,
+                    error.replace('|', "\\|")
+                )),
+                None => out.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} |\n",
+                    row.spec,
+                    check(row.structure),
+                    check(row.lint),
+                    check(row.security),
+                    row.findings_label()
+                )),
+            }
+        }
+        out
+    }
+}
+
+/// Write `report` to [`WORKSPACE_REPORT`] and [`WORKSPACE_SUMMARY`].
+pub fn save(root: &Path, report: &WorkspaceReport) -> Result<()> {
+    let json = serde_json::to_string_pretty(report).context("Failed to serialize report")?;
+    reports::write_atomic(&root.join(WORKSPACE_REPORT), json.as_bytes())?;
+    reports::write_atomic(&root.join(WORKSPACE_SUMMARY), report.markdown().as_bytes())
+}
+
+pub enum WorkspaceEvent {
+    /// Spec `index` started.
+    SpecStarted(usize),
+    SpecFinished {
+        index: usize,
+        report: Box<ValidateReport>,
+    },
+    /// Spec `index` couldn't be checked; the others still run.
+    SpecFailed {
+        index: usize,
+        reason: String,
+    },
+    /// Every spec ran; the last report is back in place.
+    Completed,
+    Aborted(String),
+}
+
+/// Run the spec checks of `input` (structure, lint and security, as
+/// configured) once per spec in `specs`, relative to the project, on a
+/// background thread. Generate, compile, test, docs, custom phases and
+/// hooks are skipped, so `.oav/generated/` is left alone; `.oav/reports`
+/// is backed up first and put back afterwards.
+pub fn run_workspace(
+    input: PipelineInput,
+    specs: Vec<String>,
+    cancel: CancelToken,
+) -> Receiver<WorkspaceEvent> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let root = input.work_dir.clone();
+        if let Err(e) = back_up_reports(&root) {
+            let _ = tx.send(WorkspaceEvent::Aborted(format!("{e:#}")));
+            return;
+        }
+
+        run_specs(&input, &specs, &cancel, &tx);
+        let restored = restore_reports(&root);
+        let _ = tx.send(match restored {
+            Ok(()) if cancel.is_cancelled() => WorkspaceEvent::Aborted("Cancelled by user".into()),
+            Ok(()) => WorkspaceEvent::Completed,
+            Err(e) => WorkspaceEvent::Aborted(format!("{e:#}")),
+        });
+    });
+    rx
+}
+
+fn run_specs(
+    input: &PipelineInput,
+    specs: &[String],
+    cancel: &CancelToken,
+    tx: &Sender<WorkspaceEvent>,
+) {
+    for (index, spec) in specs.iter().enumerate() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let _ = tx.send(WorkspaceEvent::SpecStarted(index));
+        if !is_inside_project(spec) {
+            let _ = tx.send(WorkspaceEvent::SpecFailed {
+                index,
+                reason: "outside the project".into(),
+            });
+            continue;
+        }
+        let events = run_pipeline(
+            PipelineInput {
+                config: spec_checks(&input.config),
+                custom_defs: input.custom_defs.clone(),
+                spec_path: input.work_dir.join(spec),
+                work_dir: input.work_dir.clone(),
+            },
+            cancel.clone(),
+        );
+        let outcome = events.into_iter().find_map(|event| match event {
+            PipelineEvent::Completed(report) => Some(Ok(report)),
+            PipelineEvent::Aborted(reason) => Some(Err(reason)),
+            _ => None,
+        });
+        let _ = tx.send(match outcome {
+            Some(Ok(report)) => WorkspaceEvent::SpecFinished { index, report },
+            Some(Err(reason)) => WorkspaceEvent::SpecFailed { index, reason },
+            None => WorkspaceEvent::SpecFailed {
+                index,
+                reason: "pipeline stopped without a result".into(),
+            },
+        });
+    }
+}
+
+/// `cfg` with only the checks that look at the spec itself left on.
+fn spec_checks(cfg: &Config) -> Config {
+    Config {
+        generate: false,
+        compile: false,
+        test: false,
+        code_lint: false,
+        docs: false,
+        custom_phases: Vec::new(),
+        hooks: Vec::new(),
+        ..cfg.clone()
+    }
+}
+
+/// Whether `spec` is a path relative to the project that stays inside it,
+/// as the containers only see the project.
+pub fn is_inside_project(spec: &str) -> bool {
+    let path = Path::new(spec);
+    !spec.is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Copy `.oav/reports` to [`REPORTS_BACKUP`]. A backup left by a run that
+/// never finished holds the reports from before it, so it's put back
+/// first.
+fn back_up_reports(root: &Path) -> Result<()> {
+    let backup = root.join(REPORTS_BACKUP);
+    if backup.exists() {
+        restore_reports(root)?;
+    }
+    let reports = root.join(REPORTS_DIR);
+    fs::create_dir_all(&backup)
+        .with_context(|| format!("Failed to create {}", backup.display()))?;
+    for entry in WalkDir::new(&reports).into_iter().filter_map(|e| e.ok()) {
+        let rel = entry.path().strip_prefix(&reports).unwrap_or(entry.path());
+        let target = backup.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .with_context(|| format!("Failed to create {}", target.display()))?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to back up {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace `.oav/reports` with the backup, dropping what the workspace run
+/// wrote there.
+fn restore_reports(root: &Path) -> Result<()> {
+    let backup = root.join(REPORTS_BACKUP);
+    let reports = root.join(REPORTS_DIR);
+    if reports.exists() {
+        fs::remove_dir_all(&reports)
+            .with_context(|| format!("Failed to remove {}", reports.display()))?;
+    }
+    fs::rename(&backup, &reports)
+        .with_context(|| format!("Failed to put back {}", reports.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{LintResult, Phases, StructureResult};
+
+    fn report(structure: &str, lint: &str) -> ValidateReport {
+        ValidateReport {
+            spec: "api.yaml".into(),
+            phases: Phases {
+                structure: Some(StructureResult {
+                    status: structure.into(),
+                    ..StructureResult::default()
+                }),
+                lint: Some(LintResult {
+                    status: lint.into(),
+                    ..LintResult::default()
+                }),
+                ..Phases::default()
+            },
+            ..ValidateReport::default()
+        }
+    }
+
+    #[test]
+    fn rows_pass_when_no_check_failed() {
+        let passing = SpecRow::from_report("a.yaml", &report("pass", "pass"), 2, 0);
+        assert_eq!(passing.security, None);
+        assert!(passing.passed());
+        assert!(!SpecRow::from_report("b.yaml", &report("pass", "fail"), 5, 0).passed());
+        assert!(!SpecRow::failed("c.yaml", "Spec file not found".into()).passed());
+    }
+
+    #[test]
+    fn report_totals_and_renders_a_table() {
+        let report = WorkspaceReport::new(vec![
+            SpecRow::from_report("a/openapi.yaml", &report("pass", "pass"), 2, 0),
+            SpecRow::from_report("b/openapi.yaml", &report("pass", "fail"), 5, 2),
+            SpecRow::failed("c/openapi.yaml", "exit 1 | oops".into()),
+        ]);
+        assert_eq!((report.passed, report.failed, report.findings), (1, 2, 7));
+        let markdown = report.markdown();
+        assert!(markdown.contains("1 of 3 spec(s) passing, 7 finding(s)"));
+        assert!(markdown.contains("| `b/openapi.yaml` | pass | fail | - | 5 (2 known) |"));
+        assert!(markdown.contains("error: exit 1 \\| oops"));
+    }
+
+    #[test]
+    fn save_writes_json_and_markdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = WorkspaceReport::new(vec![SpecRow::from_report(
+            "openapi.yaml",
+            &report("pass", "pass"),
+            0,
+            0,
+        )]);
+        save(dir.path(), &report).unwrap();
+        let json = fs::read_to_string(dir.path().join(WORKSPACE_REPORT)).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorkspaceReport>(&json).unwrap(),
+            report
+        );
+        assert!(dir.path().join(WORKSPACE_SUMMARY).is_file());
+    }
+
+    #[test]
+    fn reports_are_put_back_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let lint_log = root.join(".oav/reports/lint/spectral.log");
+        fs::create_dir_all(lint_log.parent().unwrap()).unwrap();
+        fs::write(root.join(REPORTS_DIR).join("report.json"), "{}").unwrap();
+        fs::write(&lint_log, "[]").unwrap();
+
+        back_up_reports(root).unwrap();
+        fs::write(&lint_log, "[{\"code\": \"other-spec\"}]").unwrap();
+        fs::write(root.join(".oav/reports/repro.sh"), "docker run").unwrap();
+        restore_reports(root).unwrap();
+        assert_eq!(fs::read_to_string(&lint_log).unwrap(), "[]");
+        assert!(!root.join(".oav/reports/repro.sh").exists());
+        assert!(!root.join(REPORTS_BACKUP).exists());
+
+        // A backup left by a crashed run wins over what's there now.
+        back_up_reports(root).unwrap();
+        fs::write(&lint_log, "crashed").unwrap();
+        back_up_reports(root).unwrap();
+        restore_reports(root).unwrap();
+        assert_eq!(fs::read_to_string(&lint_log).unwrap(), "[]");
+    }
+
+    #[test]
+    fn specs_must_stay_inside_the_project() {
+        assert!(is_inside_project("apis/pets/openapi.yaml"));
+        assert!(is_inside_project("./openapi.yaml"));
+        assert!(!is_inside_project("../other/openapi.yaml"));
+        assert!(!is_inside_project("/etc/openapi.yaml"));
+        assert!(!is_inside_project(""));
+    }
+
+    #[test]
+    fn spec_checks_leave_generated_output_alone() {
+        let cfg = spec_checks(&Config::default());
+        assert!(cfg.structure && cfg.lint);
+        assert!(!cfg.generate && !cfg.compile && !cfg.test && !cfg.code_lint && !cfg.docs);
+    }
+}
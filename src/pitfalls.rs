@@ -0,0 +1,307 @@
+use serde_yaml::Value;
+
+use crate::openapi::{escape, key};
+
+/// A construct in the spec that a configured generator is known to get wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pitfall {
+    /// Rule id, e.g. `pitfall-oneof-without-discriminator`.
+    pub rule: &'static str,
+    /// JSON pointer to the offending schema.
+    pub pointer: String,
+    pub message: &'static str,
+    /// The configured generators the construct affects.
+    pub generators: Vec<String>,
+}
+
+struct Rule {
+    id: &'static str,
+    message: &'static str,
+    /// Generator families the rule applies to; `java` matches `java` and
+    /// `java-micronaut-client`.
+    families: &'static [&'static str],
+}
+
+const ONEOF_WITHOUT_DISCRIMINATOR: Rule = Rule {
+    id: "pitfall-oneof-without-discriminator",
+    message: "oneOf without a discriminator generates an empty marker interface or an untyped wrapper",
+    families: &[
+        "java",
+        "spring",
+        "kotlin",
+        "jaxrs",
+        "csharp",
+        "aspnetcore",
+        "dart",
+        "swift5",
+    ],
+};
+
+const NESTED_INLINE_ENUM: Rule = Rule {
+    id: "pitfall-nested-inline-enum",
+    message: "enum nested inside an inline schema gets a generated, unstable type name; move it to components/schemas",
+    families: &[
+        "java",
+        "spring",
+        "kotlin",
+        "jaxrs",
+        "csharp",
+        "aspnetcore",
+        "typescript",
+        "go",
+        "swift5",
+    ],
+};
+
+const ADDITIONAL_PROPERTIES_WITH_PROPERTIES: Rule = Rule {
+    id: "pitfall-additional-properties-with-properties",
+    message: "additionalProperties next to properties is dropped or turns the model into a map",
+    families: &["java", "spring", "kotlin", "jaxrs", "csharp", "aspnetcore"],
+};
+
+const FREE_FORM_OBJECT: Rule = Rule {
+    id: "pitfall-free-form-object",
+    message: "object without properties generates Object, Map<String, Object> or any",
+    families: &[
+        "java",
+        "spring",
+        "kotlin",
+        "jaxrs",
+        "csharp",
+        "aspnetcore",
+        "typescript",
+        "go",
+        "swift5",
+    ],
+};
+
+/// Walk every schema in the spec and report constructs that break any of
+/// `generators`. Pitfalls that affect none of them are left out.
+pub fn analyze(spec: &Value, generators: &[String]) -> Vec<Pitfall> {
+    let mut walker = Walker {
+        generators,
+        found: Vec::new(),
+    };
+    if let Some(schemas) = spec
+        .get("components")
+        .and_then(|c| c.get("schemas"))
+        .and_then(Value::as_mapping)
+    {
+        for (name, schema) in schemas {
+            let pointer = format!("/components/schemas/{}", escape(&key(name)));
+            walker.schema(schema, &pointer, 0);
+        }
+    }
+    if let Some(paths) = spec.get("paths") {
+        walker.find_schemas(paths, "/paths");
+    }
+    if let Some(components) = spec.get("components").and_then(Value::as_mapping) {
+        for (section, value) in components {
+            let section = key(section);
+            if section != "schemas" {
+                walker.find_schemas(value, &format!("/components/{}", escape(&section)));
+            }
+        }
+    }
+    walker.found
+}
+
+struct Walker<'a> {
+    generators: &'a [String],
+    found: Vec<Pitfall>,
+}
+
+impl Walker<'_> {
+    /// Look for `schema` keys (parameters, media types, headers) outside of
+    /// `components/schemas`.
+    fn find_schemas(&mut self, node: &Value, pointer: &str) {
+        match node {
+            Value::Mapping(map) => {
+                for (k, value) in map {
+                    let k = key(k);
+                    let child = format!("{pointer}/{}", escape(&k));
+                    if k == "schema" {
+                        self.schema(value, &child, 1);
+                    } else {
+                        self.find_schemas(value, &child);
+                    }
+                }
+            }
+            Value::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.find_schemas(item, &format!("{pointer}/{i}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check one schema and recurse into its subschemas. `depth` counts the
+    /// inline levels below a named schema.
+    fn schema(&mut self, node: &Value, pointer: &str, depth: usize) {
+        if !node.is_mapping() || node.get("$ref").is_some() {
+            return;
+        }
+
+        if node.get("oneOf").is_some() && node.get("discriminator").is_none() {
+            self.report(&ONEOF_WITHOUT_DISCRIMINATOR, pointer);
+        }
+        if node.get("enum").is_some() && depth >= 2 {
+            self.report(&NESTED_INLINE_ENUM, pointer);
+        }
+        let properties = node.get("properties").and_then(Value::as_mapping);
+        let additional = node
+            .get("additionalProperties")
+            .is_some_and(|a| a.as_bool() != Some(false));
+        if additional && properties.is_some_and(|p| !p.is_empty()) {
+            self.report(&ADDITIONAL_PROPERTIES_WITH_PROPERTIES, pointer);
+        }
+        let composed = ["allOf", "oneOf", "anyOf"]
+            .iter()
+            .any(|k| node.get(k).is_some());
+        let free_form = node.get("type").and_then(Value::as_str) == Some("object")
+            && properties.is_none_or(|p| p.is_empty())
+            && node
+                .get("additionalProperties")
+                .is_none_or(|a| a.as_bool() == Some(true))
+            && !composed;
+        if free_form {
+            self.report(&FREE_FORM_OBJECT, pointer);
+        }
+
+        for (name, prop) in properties.into_iter().flatten() {
+            let child = format!("{pointer}/properties/{}", escape(&key(name)));
+            self.schema(prop, &child, depth + 1);
+        }
+        if let Some(items) = node.get("items") {
+            self.schema(items, &format!("{pointer}/items"), depth + 1);
+        }
+        if let Some(additional) = node.get("additionalProperties") {
+            self.schema(
+                additional,
+                &format!("{pointer}/additionalProperties"),
+                depth + 1,
+            );
+        }
+        for keyword in ["allOf", "oneOf", "anyOf"] {
+            for (i, part) in node
+                .get(keyword)
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                // allOf members are merged into this schema, so they sit at
+                // the same level.
+                let depth = if keyword == "allOf" { depth } else { depth + 1 };
+                self.schema(part, &format!("{pointer}/{keyword}/{i}"), depth);
+            }
+        }
+    }
+
+    fn report(&mut self, rule: &Rule, pointer: &str) {
+        let generators: Vec<String> = self
+            .generators
+            .iter()
+            .filter(|g| {
+                rule.families
+                    .iter()
+                    .any(|f| g.as_str() == *f || g.starts_with(&format!("{f}-")))
+            })
+            .cloned()
+            .collect();
+        if !generators.is_empty() {
+            self.found.push(Pitfall {
+                rule: rule.id,
+                pointer: pointer.to_string(),
+                message: rule.message,
+                generators,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  owner:
+                    type: object
+                    properties:
+                      kind:
+                        type: string
+                        enum: [person, company]
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: '#/components/schemas/Cat'
+        - $ref: '#/components/schemas/Dog'
+    Cat:
+      type: object
+      properties:
+        color:
+          type: string
+          enum: [black, white]
+      additionalProperties: true
+    Dog:
+      type: object
+      properties:
+        meta:
+          type: object
+"#;
+
+    fn rules(found: &[Pitfall]) -> Vec<(&str, &str)> {
+        found.iter().map(|p| (p.rule, p.pointer.as_str())).collect()
+    }
+
+    #[test]
+    fn flags_constructs_for_affected_generators() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let found = analyze(&spec, &["spring".into(), "typescript-axios".into()]);
+        assert_eq!(
+            rules(&found),
+            vec![
+                (
+                    "pitfall-oneof-without-discriminator",
+                    "/components/schemas/Pet"
+                ),
+                (
+                    "pitfall-additional-properties-with-properties",
+                    "/components/schemas/Cat"
+                ),
+                (
+                    "pitfall-free-form-object",
+                    "/components/schemas/Dog/properties/meta"
+                ),
+                (
+                    "pitfall-nested-inline-enum",
+                    "/paths/~1pets/get/responses/200/content/application~1json/schema/properties/owner/properties/kind"
+                ),
+            ]
+        );
+        assert_eq!(found[0].generators, vec!["spring".to_string()]);
+        assert_eq!(
+            found[2].generators,
+            vec!["spring".to_string(), "typescript-axios".to_string()]
+        );
+    }
+
+    #[test]
+    fn skips_pitfalls_no_configured_generator_hits() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        assert!(analyze(&spec, &["python".into()]).is_empty());
+        assert!(analyze(&spec, &[]).is_empty());
+    }
+}
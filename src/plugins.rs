@@ -0,0 +1,463 @@
+//! Plugins: analyzers that add findings about the spec, and fix rules that
+//! propose edits for the findings of the rules they name.
+//!
+//! Both are traits, so a crate embedding lazyoav can register its own. At
+//! runtime, plugins are discovered from YAML manifests in `.oav/plugins/`,
+//! each naming a host command that speaks JSON over stdin and stdout:
+//!
+//! ```yaml
+//! name: acme-naming
+//! command: [python3, naming.py]   # run from .oav/plugins/
+//! analyze: true
+//! fixes: [acme-path-kebab-case]
+//! ```
+//!
+//! An analyzer gets `{"action": "analyze", "spec": <the spec as JSON>}` and
+//! answers `{"findings": [{"rule", "message", "pointer", "severity"}]}`. A fix
+//! rule gets `{"action": "fix", "rule", "message", "json_path", "line",
+//! "spec_lines"}` and answers `{"fix": null}` or `{"fix": {"description",
+//! "target_line", "removed", "inserted"}}`, replacing the `removed` lines
+//! after 1-based `target_line` with `inserted`.
+//!
+//! Manifests run commands on the host, so lazyoav only loads them for
+//! projects the user trusts (see [`crate::trust`]).
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// Relative to the project.
+pub const PLUGINS_DIR: &str = ".oav/plugins";
+
+/// How long a plugin command may take to answer.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A finding an analyzer reports about the spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginFinding {
+    pub rule: String,
+    pub message: String,
+    /// JSON pointer to what the finding is about.
+    pub pointer: String,
+    /// `error`, `warn`, `info` or `hint`; anything else is shown as `info`.
+    #[serde(default = "default_severity")]
+    pub severity: String,
+}
+
+fn default_severity() -> String {
+    "info".to_string()
+}
+
+/// The finding a fix rule is asked to fix, with the spec it's in.
+#[derive(Debug, Clone, Serialize)]
+pub struct FixRequest<'a> {
+    pub rule: &'a str,
+    pub message: &'a str,
+    pub json_path: Option<&'a str>,
+    /// 1-based line the linter reported, or 0.
+    pub line: usize,
+    pub spec_lines: &'a [String],
+}
+
+/// A fix as a fix rule proposes it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PluginFix {
+    pub description: String,
+    /// 1-based line after which `removed` is replaced with `inserted`.
+    pub target_line: usize,
+    #[serde(default)]
+    pub removed: Vec<String>,
+    pub inserted: Vec<String>,
+}
+
+/// Adds findings about a parsed spec.
+pub trait Analyzer: Send + Sync {
+    fn name(&self) -> &str;
+    fn analyze(&self, spec: &Value) -> Result<Vec<PluginFinding>>;
+}
+
+/// Proposes fixes for the findings of some rules.
+pub trait FixRule: Send + Sync {
+    fn name(&self) -> &str;
+    fn handles(&self, rule: &str) -> bool;
+    /// `Ok(None)` when this finding can't be fixed mechanically.
+    fn propose(&self, request: &FixRequest) -> Result<Option<PluginFix>>;
+}
+
+/// A plugin manifest in `.oav/plugins/`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginManifest {
+    pub name: String,
+    /// Program and arguments, run from the plugins directory. A program
+    /// starting with `./` is resolved against that directory.
+    pub command: Vec<String>,
+    /// Whether the plugin is asked for findings after each run.
+    #[serde(default)]
+    pub analyze: bool,
+    /// Rule ids the plugin proposes fixes for.
+    #[serde(default)]
+    pub fixes: Vec<String>,
+}
+
+/// A plugin run as a host command, per its manifest.
+#[derive(Debug, Clone)]
+pub struct ExternalPlugin {
+    manifest: PluginManifest,
+    dir: PathBuf,
+}
+
+impl ExternalPlugin {
+    pub fn new(manifest: PluginManifest, dir: PathBuf) -> Self {
+        Self { manifest, dir }
+    }
+
+    /// Send `request` and parse the answer, failing on a non-zero exit, a
+    /// timeout, or output that isn't JSON.
+    fn call<T: for<'de> Deserialize<'de>>(&self, request: &impl Serialize) -> Result<T> {
+        let input = serde_json::to_vec(request)?;
+        let (program, args) = self
+            .manifest
+            .command
+            .split_first()
+            .context("empty command")?;
+        let program = if program.starts_with("./") || program.starts_with("../") {
+            self.dir.join(program)
+        } else {
+            PathBuf::from(program)
+        };
+        let stdout = exchange(
+            Command::new(&program).args(args).current_dir(&self.dir),
+            &input,
+            TIMEOUT,
+        )?;
+        serde_json::from_slice(&stdout).context("answer is not the expected JSON")
+    }
+}
+
+/// Run `command` with `input` on its stdin and return its stdout, failing
+/// on a non-zero exit or when it takes longer than `timeout`.
+pub(crate) fn exchange(command: &mut Command, input: &[u8], timeout: Duration) -> Result<Vec<u8>> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| {
+            format!(
+                "Failed to start {}",
+                command.get_program().to_string_lossy()
+            )
+        })?;
+
+    // Write and read on threads so a command that answers before reading
+    // all its input can't deadlock either side.
+    let stdin = child.stdin.take();
+    let input = input.to_vec();
+    let writer = std::thread::spawn(move || {
+        if let Some(mut stdin) = stdin {
+            let _ = stdin.write_all(&input);
+        }
+    });
+    let readers = [
+        child.stdout.take().map(read_all),
+        child.stderr.take().map(read_all),
+    ];
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("no answer within {}s", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    let _ = writer.join();
+    let [stdout, stderr] = readers.map(|r| r.and_then(|h| h.join().ok()).unwrap_or_default());
+    if !status.success() {
+        bail!("{status}: {}", String::from_utf8_lossy(&stderr).trim());
+    }
+    Ok(stdout)
+}
+
+fn read_all(mut source: impl Read + Send + 'static) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = source.read_to_end(&mut output);
+        output
+    })
+}
+
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum Request<'a> {
+    Analyze { spec: &'a Value },
+    Fix(&'a FixRequest<'a>),
+}
+
+#[derive(Deserialize)]
+struct AnalyzeAnswer {
+    #[serde(default)]
+    findings: Vec<PluginFinding>,
+}
+
+#[derive(Deserialize)]
+struct FixAnswer {
+    fix: Option<PluginFix>,
+}
+
+impl Analyzer for ExternalPlugin {
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn analyze(&self, spec: &Value) -> Result<Vec<PluginFinding>> {
+        let answer: AnalyzeAnswer = self.call(&Request::Analyze { spec })?;
+        Ok(answer.findings)
+    }
+}
+
+impl FixRule for ExternalPlugin {
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn handles(&self, rule: &str) -> bool {
+        self.manifest.fixes.iter().any(|r| r == rule)
+    }
+
+    fn propose(&self, request: &FixRequest) -> Result<Option<PluginFix>> {
+        let answer: FixAnswer = self.call(&Request::Fix(request))?;
+        Ok(answer.fix)
+    }
+}
+
+/// The analyzers and fix rules in use, in registration order.
+#[derive(Default)]
+pub struct Registry {
+    analyzers: Vec<Box<dyn Analyzer>>,
+    fix_rules: Vec<Box<dyn FixRule>>,
+}
+
+impl Registry {
+    /// Register the plugins described by the manifests in
+    /// [`PLUGINS_DIR`], in file name order. Empty without the directory.
+    pub fn load(root: &Path) -> Result<Self> {
+        let mut registry = Self::default();
+        for plugin in load_manifests(&root.join(PLUGINS_DIR))? {
+            let analyze = plugin.manifest.analyze;
+            let fixes = !plugin.manifest.fixes.is_empty();
+            if analyze {
+                registry.register_analyzer(Box::new(plugin.clone()));
+            }
+            if fixes {
+                registry.register_fix_rule(Box::new(plugin));
+            }
+        }
+        Ok(registry)
+    }
+
+    pub fn register_analyzer(&mut self, analyzer: Box<dyn Analyzer>) {
+        self.analyzers.push(analyzer);
+    }
+
+    pub fn register_fix_rule(&mut self, rule: Box<dyn FixRule>) {
+        self.fix_rules.push(rule);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.analyzers.is_empty() && self.fix_rules.is_empty()
+    }
+
+    pub fn has_analyzers(&self) -> bool {
+        !self.analyzers.is_empty()
+    }
+
+    /// Whether a fix rule proposes fixes for `rule`.
+    pub fn handles(&self, rule: &str) -> bool {
+        self.fix_rules.iter().any(|r| r.handles(rule))
+    }
+
+    /// Every analyzer's findings. An analyzer that fails is left out, with
+    /// a message naming it in the second list.
+    pub fn analyze(&self, spec: &Value) -> (Vec<PluginFinding>, Vec<String>) {
+        let mut findings = Vec::new();
+        let mut failures = Vec::new();
+        for analyzer in &self.analyzers {
+            match analyzer.analyze(spec) {
+                Ok(found) => findings.extend(found),
+                Err(e) => failures.push(format!("Plugin '{}' failed: {e:#}", analyzer.name())),
+            }
+        }
+        (findings, failures)
+    }
+
+    /// The fix the first fix rule handling `request.rule` proposes.
+    pub fn propose(&self, request: &FixRequest) -> Result<Option<PluginFix>> {
+        let Some(rule) = self.fix_rules.iter().find(|r| r.handles(request.rule)) else {
+            return Ok(None);
+        };
+        rule.propose(request)
+            .with_context(|| format!("Plugin '{}' failed", rule.name()))
+    }
+}
+
+fn load_manifests(dir: &Path) -> Result<Vec<ExternalPlugin>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yaml" | "yml")))
+        .collect();
+    paths.sort();
+
+    let mut names = HashSet::new();
+    let mut plugins = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let manifest: PluginManifest = serde_yaml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        if manifest.name.trim().is_empty() {
+            bail!("plugin in {} has an empty name", path.display());
+        }
+        if manifest.command.is_empty() {
+            bail!("plugin '{}' has an empty command", manifest.name);
+        }
+        if !manifest.analyze && manifest.fixes.is_empty() {
+            bail!(
+                "plugin '{}' neither analyzes nor fixes anything (set analyze or fixes)",
+                manifest.name
+            );
+        }
+        if !names.insert(manifest.name.clone()) {
+            bail!("duplicate plugin '{}'", manifest.name);
+        }
+        plugins.push(ExternalPlugin::new(manifest, dir.to_path_buf()));
+    }
+    Ok(plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Upper;
+
+    impl FixRule for Upper {
+        fn name(&self) -> &str {
+            "upper"
+        }
+
+        fn handles(&self, rule: &str) -> bool {
+            rule == "shout"
+        }
+
+        fn propose(&self, request: &FixRequest) -> Result<Option<PluginFix>> {
+            let line = &request.spec_lines[request.line - 1];
+            Ok(Some(PluginFix {
+                description: "Shout".into(),
+                target_line: request.line - 1,
+                removed: vec![line.clone()],
+                inserted: vec![line.to_uppercase()],
+            }))
+        }
+    }
+
+    fn request<'a>(rule: &'a str, lines: &'a [String]) -> FixRequest<'a> {
+        FixRequest {
+            rule,
+            message: "",
+            json_path: None,
+            line: 1,
+            spec_lines: lines,
+        }
+    }
+
+    #[test]
+    fn registered_fix_rules_handle_their_rules() {
+        let mut registry = Registry::default();
+        assert!(registry.is_empty());
+        registry.register_fix_rule(Box::new(Upper));
+        let lines = vec!["title: hi".to_string()];
+
+        let fix = registry
+            .propose(&request("shout", &lines))
+            .unwrap()
+            .unwrap();
+        assert_eq!(fix.inserted, vec!["TITLE: HI"]);
+        assert!(
+            registry
+                .propose(&request("other", &lines))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn manifests_are_validated() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(Registry::load(tmp.path()).unwrap().is_empty());
+
+        let dir = tmp.path().join(PLUGINS_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("a.yaml"),
+            "name: a\ncommand: [./a.sh]\nfixes: [x]\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a manifest").unwrap();
+        let registry = Registry::load(tmp.path()).unwrap();
+        assert!(registry.analyzers.is_empty());
+        assert_eq!(registry.fix_rules.len(), 1);
+
+        fs::write(dir.join("b.yaml"), "name: b\ncommand: [b]\n").unwrap();
+        assert!(Registry::load(tmp.path()).is_err());
+        fs::write(dir.join("b.yaml"), "name: a\ncommand: [b]\nanalyze: true\n").unwrap();
+        assert!(Registry::load(tmp.path()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn external_plugins_answer_over_stdio() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(PLUGINS_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("answer.json"),
+            r#"{"findings": [{"rule": "acme-name", "message": "Bad name", "pointer": "/info"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("acme.yaml"),
+            "name: acme\ncommand: [sh, -c, 'cat >/dev/null; cat answer.json']\nanalyze: true\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("broken.yaml"),
+            "name: broken\ncommand: [sh, -c, 'echo oops >&2; exit 3']\nanalyze: true\n",
+        )
+        .unwrap();
+
+        let registry = Registry::load(tmp.path()).unwrap();
+        let (findings, failures) = registry.analyze(&Value::Null);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "acme-name");
+        assert_eq!(findings[0].severity, "info");
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("'broken'") && failures[0].contains("oops"));
+    }
+}
@@ -0,0 +1,64 @@
+// Findings as `file:line:col: severity: message` lines, the form vim's
+// quickfix list (`:cfile`) and emacs' compilation mode read.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use lazyoav::reports;
+
+use crate::log_parser::LintError;
+
+/// Rewritten after every run, relative to the project.
+pub const QUICKFIX_FILE: &str = ".oav/quickfix.txt";
+
+/// One line per finding in `spec`, ordered by position. `findings` pairs
+/// each finding with its 1-based line and column.
+pub fn render(spec: &str, findings: &[(usize, usize, &LintError)]) -> String {
+    let mut sorted: Vec<_> = findings.iter().collect();
+    sorted.sort_by_key(|(line, col, _)| (*line, *col));
+    sorted
+        .into_iter()
+        .map(|(line, col, err)| {
+            let message = err.message.replace('\n', " ");
+            format!(
+                "{spec}:{line}:{col}: {}: {message} [{}]\n",
+                err.severity, err.rule
+            )
+        })
+        .collect()
+}
+
+pub fn write(root: &Path, contents: &str) -> Result<()> {
+    reports::write_atomic(&root.join(QUICKFIX_FILE), contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_parser::Severity;
+
+    fn finding(rule: &str, message: &str) -> LintError {
+        LintError {
+            line: 0,
+            col: 0,
+            end: None,
+            severity: Severity::Warning,
+            rule: rule.into(),
+            message: message.into(),
+            json_path: None,
+        }
+    }
+
+    #[test]
+    fn render_sorts_by_position_and_keeps_one_line_per_finding() {
+        let late = finding("operation-tags", "Operation should have tags.");
+        let early = finding("info-contact", "Info object must have\n\"contact\".");
+        let out = render("api/openapi.yaml", &[(12, 7, &late), (2, 1, &early)]);
+        assert_eq!(
+            out,
+            "api/openapi.yaml:2:1: warning: Info object must have \"contact\". [info-contact]\n\
+             api/openapi.yaml:12:7: warning: Operation should have tags. [operation-tags]\n"
+        );
+    }
+}
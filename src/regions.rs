@@ -0,0 +1,95 @@
+//! The spec's changed regions: the lines that differ from a base version,
+//! to narrow the findings of a legacy spec down to what's being worked on.
+
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use similar::{Algorithm, DiffOp};
+
+use crate::reports;
+
+/// The spec as the last run checked it, and as the run before that did.
+pub const LAST_RUN_SPEC: &str = ".oav/reports/spec.last";
+const PREVIOUS_RUN_SPEC: &str = ".oav/reports/spec.previous";
+
+/// 1-based, end-exclusive ranges of the lines of `new` that differ from
+/// `old`. Lines only removed mark the line that took their place, so
+/// findings about what went missing still count.
+pub fn changed_ranges(old: &str, new: &str) -> Vec<Range<usize>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for op in similar::capture_diff_slices(Algorithm::Myers, &old, &new) {
+        let range = match op {
+            DiffOp::Equal { .. } => continue,
+            DiffOp::Delete { new_index, .. } => new_index + 1..new_index + 2,
+            DiffOp::Insert {
+                new_index, new_len, ..
+            }
+            | DiffOp::Replace {
+                new_index, new_len, ..
+            } => new_index + 1..new_index + new_len + 1,
+        };
+        match ranges.last_mut() {
+            Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+            _ => ranges.push(range),
+        }
+    }
+    ranges
+}
+
+/// Whether 1-based `line` falls in one of `ranges`.
+pub fn contains(ranges: &[Range<usize>], line: usize) -> bool {
+    ranges.iter().any(|range| range.contains(&line))
+}
+
+/// Keep `spec` as the one the last run checked, and the one it replaces as
+/// the base of "changed since the last run".
+pub fn record_run_spec(root: &Path, spec: &str) -> Result<()> {
+    let last = root.join(LAST_RUN_SPEC);
+    if last.is_file() {
+        let previous = root.join(PREVIOUS_RUN_SPEC);
+        fs::rename(&last, &previous)
+            .with_context(|| format!("Failed to move {}", last.display()))?;
+    }
+    reports::write_atomic(&last, spec.as_bytes())
+}
+
+/// The spec as the run before the last one checked it.
+pub fn previous_run_spec(root: &Path) -> Option<String> {
+    fs::read_to_string(root.join(PREVIOUS_RUN_SPEC)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_ranges_cover_edits_insertions_and_removals() {
+        let old = "a\nb\nc\nd\ne\nf\n";
+        let new = "a\nB\nc\nnew\nd\nf\n";
+        assert_eq!(changed_ranges(old, new), vec![2..3, 4..5, 6..7]);
+        assert!(changed_ranges(old, old).is_empty());
+    }
+
+    #[test]
+    fn adjacent_changes_merge_into_one_range() {
+        let ranges = changed_ranges("a\nb\nc\nd\n", "A\nB\nc\nD\n");
+        assert_eq!(ranges, vec![1..3, 4..5]);
+        assert!(contains(&ranges, 2));
+        assert!(!contains(&ranges, 3));
+    }
+
+    #[test]
+    fn recording_a_run_keeps_the_one_before() {
+        let dir = tempfile::tempdir().unwrap();
+        record_run_spec(dir.path(), "first").unwrap();
+        assert_eq!(previous_run_spec(dir.path()), None);
+        record_run_spec(dir.path(), "second").unwrap();
+        assert_eq!(previous_run_spec(dir.path()).as_deref(), Some("first"));
+        let last = fs::read_to_string(dir.path().join(LAST_RUN_SPEC)).unwrap();
+        assert_eq!(last, "second");
+    }
+}
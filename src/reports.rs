@@ -0,0 +1,83 @@
+//! The last run's report on disk, written so that a crash or a second
+//! process never sees half a file.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::pipeline::ValidateReport;
+
+/// Where each run's report and logs go.
+pub const REPORTS_DIR: &str = ".oav/reports";
+pub const REPORT_FILE: &str = ".oav/reports/report.json";
+
+/// Write `contents` to `path` by way of a temporary file in the same
+/// directory, renamed into place once it's flushed to disk.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    let written = fs::File::create(&tmp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| fs::rename(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e).with_context(|| format!("Failed to write {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Save `report` as the project's last report.
+pub fn save(root: &Path, report: &ValidateReport) -> Result<()> {
+    let json = serde_json::to_string_pretty(report).context("Failed to serialize report")?;
+    write_atomic(&root.join(REPORT_FILE), json.as_bytes())
+}
+
+/// The project's last report, if there is a readable one.
+pub fn load(root: &Path) -> Option<ValidateReport> {
+    let json = fs::read_to_string(root.join(REPORT_FILE)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Summary;
+
+    #[test]
+    fn save_and_load_roundtrip_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = ValidateReport {
+            spec: "openapi.yaml".into(),
+            mode: "server".into(),
+            summary: Summary {
+                total: 1,
+                passed: 1,
+                failed: 0,
+            },
+            ..ValidateReport::default()
+        };
+        save(dir.path(), &report).unwrap();
+        save(dir.path(), &report).unwrap();
+
+        assert_eq!(load(dir.path()).unwrap().summary.passed, 1);
+        let files: Vec<_> = fs::read_dir(dir.path().join(".oav/reports"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["report.json"]);
+    }
+
+    #[test]
+    fn load_ignores_a_missing_or_broken_report() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path()).is_none());
+        write_atomic(&dir.path().join(REPORT_FILE), b"{").unwrap();
+        assert!(load(dir.path()).is_none());
+    }
+}
@@ -0,0 +1,333 @@
+/// Reference page for the rules below.
+pub const SPECTRAL_DOCS_URL: &str =
+    "https://docs.stoplight.io/docs/spectral/4dec24461f3af-open-api-rules";
+
+/// Spectral's `spectral:oas` rules.
+pub const SPECTRAL: &[(&str, &str)] = &[
+    (
+        "contact-properties",
+        "The info.contact object should have a name, url and email.",
+    ),
+    (
+        "duplicated-entry-in-enum",
+        "Each value of an enum must be unique; duplicates are usually copy-paste mistakes.",
+    ),
+    (
+        "info-contact",
+        "The info object should have a contact object, so consumers know who owns the API.",
+    ),
+    (
+        "info-description",
+        "The info object should have a description explaining what the API is for.",
+    ),
+    (
+        "info-license",
+        "The info object should have a license object, so consumers know the terms of use.",
+    ),
+    (
+        "license-url",
+        "The license object should have a url pointing at the full license text.",
+    ),
+    (
+        "no-eval-in-markdown",
+        "Markdown descriptions must not contain eval(), which could run code in rendered docs.",
+    ),
+    (
+        "no-script-tags-in-markdown",
+        "Markdown descriptions must not contain <script> tags, which could run code in rendered docs.",
+    ),
+    (
+        "openapi-tags",
+        "The root tags array should list the tags used by operations.",
+    ),
+    (
+        "openapi-tags-alphabetical",
+        "The root tags array should be sorted alphabetically by name.",
+    ),
+    (
+        "openapi-tags-uniqueness",
+        "Each tag in the root tags array must have a unique name.",
+    ),
+    (
+        "operation-description",
+        "Every operation should have a description explaining what it does.",
+    ),
+    (
+        "operation-operationId",
+        "Every operation should have an operationId; generators use it to name methods.",
+    ),
+    (
+        "operation-operationId-unique",
+        "Every operationId must be unique across the spec, or generated method names clash.",
+    ),
+    (
+        "operation-operationId-valid-in-url",
+        "operationIds should only contain characters that are safe in a URL.",
+    ),
+    (
+        "operation-parameters",
+        "Operation parameters must be unique by name and location (in).",
+    ),
+    (
+        "operation-singular-tag",
+        "Operations should have at most one tag, so generators group them in one place.",
+    ),
+    (
+        "operation-success-response",
+        "Every operation should define at least one 2xx or 3xx response.",
+    ),
+    (
+        "operation-tags",
+        "Every operation should have at least one tag.",
+    ),
+    (
+        "operation-tag-defined",
+        "Tags used by operations should be declared in the root tags array.",
+    ),
+    (
+        "path-declarations-must-exist",
+        "Path parameter declarations like {id} must not be empty.",
+    ),
+    (
+        "path-keys-no-trailing-slash",
+        "Path keys should not end with a slash.",
+    ),
+    (
+        "path-not-include-query",
+        "Path keys must not include a query string; use query parameters instead.",
+    ),
+    (
+        "path-params",
+        "Path parameters must be defined for every {placeholder} in the path, and used if defined.",
+    ),
+    (
+        "tag-description",
+        "Tags in the root tags array should have a description.",
+    ),
+    ("typed-enum", "Enum values must match the schema's type."),
+    (
+        "oas3-api-servers",
+        "The spec should define at least one server with a url.",
+    ),
+    (
+        "oas3-examples-value-or-externalValue",
+        "An example must have either value or externalValue, not both.",
+    ),
+    (
+        "oas3-operation-security-defined",
+        "Security requirements must reference schemes defined in components.securitySchemes.",
+    ),
+    (
+        "oas3-parameter-description",
+        "Parameters should have a description.",
+    ),
+    (
+        "oas3-schema",
+        "The document must be valid against the OpenAPI 3 schema; the message names the invalid part.",
+    ),
+    (
+        "oas3-server-not-example.com",
+        "Server urls should not point at example.com.",
+    ),
+    (
+        "oas3-server-trailing-slash",
+        "Server urls should not end with a slash.",
+    ),
+    (
+        "oas3-server-variables",
+        "Server url variables must be defined, and their defaults must be in their enum.",
+    ),
+    (
+        "oas3-unused-component",
+        "Components should be referenced somewhere; unused ones are likely leftovers.",
+    ),
+    (
+        "oas3-valid-media-example",
+        "Media type examples must be valid against their schema.",
+    ),
+    (
+        "oas3-valid-schema-example",
+        "Schema examples and defaults must be valid against the schema.",
+    ),
+    (
+        "oas3-callbacks-in-callbacks",
+        "Callbacks should not define callbacks of their own.",
+    ),
+    ("array-items", "Schemas of type array must define items."),
+];
+
+/// Reference page for the rules below.
+pub const REDOCLY_DOCS_URL: &str = "https://redocly.com/docs/cli/rules";
+
+/// Redocly's built-in rules.
+pub const REDOCLY: &[(&str, &str)] = &[
+    (
+        "struct",
+        "The document must be structurally valid OpenAPI; the message names the invalid part.",
+    ),
+    (
+        "no-unresolved-refs",
+        "Every $ref must resolve to an existing location.",
+    ),
+    (
+        "info-contact",
+        "The info object should have a contact object, so consumers know who owns the API.",
+    ),
+    (
+        "info-license",
+        "The info object should have a license object, so consumers know the terms of use.",
+    ),
+    (
+        "info-license-url",
+        "The license object should have a url pointing at the full license text.",
+    ),
+    (
+        "no-empty-servers",
+        "The spec should define at least one server.",
+    ),
+    (
+        "no-server-example.com",
+        "Server urls should not point at example.com.",
+    ),
+    (
+        "no-server-trailing-slash",
+        "Server urls should not end with a slash.",
+    ),
+    (
+        "no-server-variables-empty-enum",
+        "Server variables with an enum must list at least one value.",
+    ),
+    (
+        "no-undefined-server-variable",
+        "Variables used in server urls must be defined.",
+    ),
+    (
+        "operation-2xx-response",
+        "Every operation should define at least one 2xx response.",
+    ),
+    (
+        "operation-4xx-response",
+        "Every operation should define at least one 4xx response.",
+    ),
+    (
+        "operation-4xx-problem-details-rfc7807",
+        "4xx responses should use application/problem+json with the RFC 7807 fields.",
+    ),
+    (
+        "operation-description",
+        "Every operation should have a description explaining what it does.",
+    ),
+    (
+        "operation-summary",
+        "Every operation should have a summary; documentation tools use it as the title.",
+    ),
+    (
+        "operation-operationId",
+        "Every operation should have an operationId; generators use it to name methods.",
+    ),
+    (
+        "operation-operationId-unique",
+        "Every operationId must be unique across the spec, or generated method names clash.",
+    ),
+    (
+        "operation-operationId-url-safe",
+        "operationIds should only contain characters that are safe in a URL.",
+    ),
+    (
+        "operation-parameters-unique",
+        "Operation parameters must be unique by name and location (in).",
+    ),
+    (
+        "operation-singular-tag",
+        "Operations should have at most one tag, so generators group them in one place.",
+    ),
+    (
+        "operation-tag-defined",
+        "Tags used by operations should be declared in the root tags array.",
+    ),
+    (
+        "path-declaration-must-exist",
+        "Path parameter declarations like {id} must not be empty.",
+    ),
+    (
+        "path-not-include-query",
+        "Path keys must not include a query string; use query parameters instead.",
+    ),
+    (
+        "path-parameters-defined",
+        "Path parameters must be defined for every {placeholder} in the path, and used if defined.",
+    ),
+    (
+        "path-http-verbs-order",
+        "Operations in a path item should follow a consistent HTTP method order.",
+    ),
+    ("paths-kebab-case", "Path segments should be kebab-case."),
+    (
+        "no-path-trailing-slash",
+        "Path keys should not end with a slash.",
+    ),
+    (
+        "no-ambiguous-paths",
+        "Paths must not be ambiguous, e.g. /{a}/x and /y/{b} both match /y/x.",
+    ),
+    (
+        "no-identical-paths",
+        "Paths must not be identical apart from parameter names.",
+    ),
+    (
+        "no-http-verbs-in-paths",
+        "Path segments should not contain HTTP verbs like get or delete.",
+    ),
+    (
+        "tag-description",
+        "Tags in the root tags array should have a description.",
+    ),
+    (
+        "tags-alphabetical",
+        "The root tags array should be sorted alphabetically by name.",
+    ),
+    (
+        "no-enum-type-mismatch",
+        "Enum values must match the schema's type.",
+    ),
+    (
+        "no-invalid-media-type-examples",
+        "Media type examples must be valid against their schema.",
+    ),
+    (
+        "no-invalid-schema-examples",
+        "Schema examples must be valid against the schema.",
+    ),
+    (
+        "no-invalid-parameter-examples",
+        "Parameter examples must be valid against the parameter's schema.",
+    ),
+    (
+        "no-example-value-and-externalValue",
+        "An example must have either value or externalValue, not both.",
+    ),
+    (
+        "no-unused-components",
+        "Components should be referenced somewhere; unused ones are likely leftovers.",
+    ),
+    (
+        "security-defined",
+        "Every operation must be covered by a security requirement, or opt out with security: [].",
+    ),
+    (
+        "boolean-parameter-prefixes",
+        "Boolean parameters should be named with a prefix like is or has.",
+    ),
+    (
+        "spec-components-invalid-map-name",
+        "Component names may only contain letters, digits, '.', '-' and '_'.",
+    ),
+    (
+        "no-required-schema-properties-undefined",
+        "Properties listed in required must be defined in the schema.",
+    ),
+    (
+        "component-name-unique",
+        "Component names should be unique across component types.",
+    ),
+];
@@ -0,0 +1,325 @@
+// Rule documentation — built-in text for the standard Spectral and Redocly
+// rules, plus descriptions from the project's own ruleset.
+mod builtin;
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+
+use lazyoav::config::{Config, Linter};
+
+/// Redocly reads its config from the project root.
+const REDOCLY_CONFIGS: &[&str] = &["redocly.yaml", "redocly.yml", ".redocly.yaml"];
+
+/// Documentation for one lint rule, borrowed from [`RuleDocs`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct RuleDoc<'a> {
+    pub description: Option<&'a str>,
+    /// Message template from the ruleset, with `{{placeholders}}` intact.
+    pub message: Option<&'a str>,
+    pub url: Option<&'a str>,
+    /// Where the documentation comes from: a ruleset file or the linter.
+    pub source: &'a str,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CustomRule {
+    description: Option<String>,
+    message: Option<String>,
+    url: Option<String>,
+    source: String,
+}
+
+/// Rule documentation for the configured linter.
+#[derive(Debug, Default)]
+pub struct RuleDocs {
+    linter: Option<Linter>,
+    custom: HashMap<String, CustomRule>,
+    /// Rulesets referenced by URL, whose rules can't be described offline.
+    pub remote: Vec<String>,
+}
+
+impl RuleDocs {
+    /// Read the rules defined by the project's ruleset: the local Spectral
+    /// ruleset and whatever it extends, or the Redocly config.
+    pub fn load(root: &Path, cfg: &Config) -> Result<Self> {
+        let mut docs = Self {
+            linter: Some(cfg.linter),
+            ..Self::default()
+        };
+        match cfg.linter {
+            Linter::Spectral => {
+                let mut seen = HashSet::new();
+                docs.load_spectral(root, &cfg.spectral_ruleset, &mut seen)?;
+            }
+            Linter::Redocly => {
+                if let Some(path) = REDOCLY_CONFIGS
+                    .iter()
+                    .map(|name| root.join(name))
+                    .find(|path| path.is_file())
+                {
+                    docs.load_rules(root, &path)?;
+                }
+            }
+            Linter::None => {}
+        }
+        Ok(docs)
+    }
+
+    /// Follow a Spectral ruleset and its `extends`. Built-in rulesets like
+    /// `spectral:oas` are covered by the built-in text.
+    fn load_spectral(
+        &mut self,
+        root: &Path,
+        ruleset: &str,
+        seen: &mut HashSet<PathBuf>,
+    ) -> Result<()> {
+        if ruleset.starts_with("http://") || ruleset.starts_with("https://") {
+            if !self.remote.iter().any(|url| url == ruleset) {
+                self.remote.push(ruleset.to_string());
+            }
+            return Ok(());
+        }
+        if ruleset.starts_with("spectral:") {
+            return Ok(());
+        }
+        let path = root.join(ruleset);
+        // JavaScript rulesets can't be read without running them, and a
+        // missing ruleset is already reported by config validation.
+        let is_data = matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("yaml" | "yml" | "json")
+        );
+        if !is_data || !path.is_file() || !seen.insert(path.clone()) {
+            return Ok(());
+        }
+
+        let value = self.load_rules(root, &path)?;
+        let dir = path.parent().unwrap_or(root);
+        for parent in extends(&value) {
+            let parent = if parent.contains(':') {
+                parent
+            } else {
+                dir.join(parent.trim_start_matches("./"))
+                    .to_string_lossy()
+                    .into_owned()
+            };
+            self.load_spectral(root, &parent, seen)?;
+        }
+        Ok(())
+    }
+
+    /// Record the documented rules of one ruleset file, returning the parsed
+    /// file. Rules already seen win, so a ruleset overrides what it extends.
+    fn load_rules(&mut self, root: &Path, path: &Path) -> Result<Value> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value: Value = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let source = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned();
+        let ruleset_url = string_field(&value, "documentationUrl");
+
+        let Some(rules) = value.get("rules").and_then(Value::as_mapping) else {
+            return Ok(value);
+        };
+        for (id, rule) in rules {
+            let (Some(id), Some(_)) = (id.as_str(), rule.as_mapping()) else {
+                // Severity overrides like `info-contact: off` don't document.
+                continue;
+            };
+            let custom = CustomRule {
+                description: string_field(rule, "description"),
+                message: string_field(rule, "message"),
+                url: string_field(rule, "documentationUrl").or_else(|| ruleset_url.clone()),
+                source: source.clone(),
+            };
+            if custom.description.is_some() || custom.message.is_some() {
+                self.custom.entry(id.to_string()).or_insert(custom);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Documentation for `rule`: the project's ruleset first, then the
+    /// built-in text of the configured linter, then of the other one.
+    pub fn lookup(&self, rule: &str) -> Option<RuleDoc<'_>> {
+        if let Some(custom) = self.custom.get(rule) {
+            return Some(RuleDoc {
+                description: custom.description.as_deref(),
+                message: custom.message.as_deref(),
+                url: custom.url.as_deref(),
+                source: &custom.source,
+            });
+        }
+
+        let spectral = (builtin::SPECTRAL, builtin::SPECTRAL_DOCS_URL, "Spectral");
+        let redocly = (builtin::REDOCLY, builtin::REDOCLY_DOCS_URL, "Redocly");
+        let tables = if self.linter == Some(Linter::Redocly) {
+            [redocly, spectral]
+        } else {
+            [spectral, redocly]
+        };
+        tables.into_iter().find_map(|(table, url, source)| {
+            let (_, text) = table.iter().find(|(id, _)| *id == rule)?;
+            Some(RuleDoc {
+                description: Some(text),
+                message: None,
+                url: Some(url),
+                source,
+            })
+        })
+    }
+}
+
+/// The rulesets a Spectral ruleset extends: a single entry or a list, where
+/// each entry is a ruleset or a `[ruleset, severity]` pair.
+fn extends(ruleset: &Value) -> Vec<String> {
+    let entry = |v: &Value| match v {
+        Value::Sequence(pair) => pair.first().and_then(Value::as_str).map(String::from),
+        other => other.as_str().map(String::from),
+    };
+    match ruleset.get("extends") {
+        Some(Value::Sequence(list)) => list.iter().filter_map(entry).collect(),
+        Some(other) => entry(other).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spectral_config(ruleset: &str) -> Config {
+        Config {
+            linter: Linter::Spectral,
+            spectral_ruleset: ruleset.into(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn builtin_rules_prefer_configured_linter() {
+        let docs = RuleDocs::default();
+        let doc = docs.lookup("info-contact").unwrap();
+        assert_eq!(doc.source, "Spectral");
+        assert_eq!(doc.url, Some(builtin::SPECTRAL_DOCS_URL));
+
+        let docs = RuleDocs {
+            linter: Some(Linter::Redocly),
+            ..RuleDocs::default()
+        };
+        assert_eq!(docs.lookup("info-contact").unwrap().source, "Redocly");
+        // Rules only one linter has are still found.
+        assert_eq!(docs.lookup("oas3-schema").unwrap().source, "Spectral");
+        assert_eq!(docs.lookup("no-such-rule"), None);
+    }
+
+    #[test]
+    fn spectral_ruleset_and_its_extends() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir(tmp.path().join("rules")).unwrap();
+        fs::write(
+            tmp.path().join("rules/.spectral.yaml"),
+            "\
+extends: [[spectral:oas, recommended], ./base.yaml, https://example.com/r.yaml]
+documentationUrl: https://example.com/guidelines
+rules:
+  info-contact: off
+  paths-kebab-case:
+    description: Paths must be kebab-case.
+    message: '{{property}} is not kebab-case'
+    given: $.paths
+    then: { function: pattern }
+",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("rules/base.yaml"),
+            "\
+rules:
+  paths-kebab-case:
+    description: Overridden by the extending ruleset.
+  no-x-headers:
+    description: Don't use X- headers.
+    documentationUrl: https://example.com/x-headers
+",
+        )
+        .unwrap();
+
+        let docs = RuleDocs::load(tmp.path(), &spectral_config("rules/.spectral.yaml")).unwrap();
+        let kebab = docs.lookup("paths-kebab-case").unwrap();
+        assert_eq!(kebab.description, Some("Paths must be kebab-case."));
+        assert_eq!(kebab.message, Some("{{property}} is not kebab-case"));
+        assert_eq!(kebab.url, Some("https://example.com/guidelines"));
+        assert_eq!(kebab.source, "rules/.spectral.yaml");
+
+        let x = docs.lookup("no-x-headers").unwrap();
+        assert_eq!(x.url, Some("https://example.com/x-headers"));
+        assert_eq!(x.source, "rules/base.yaml");
+
+        // A severity override keeps the built-in text.
+        assert_eq!(docs.lookup("info-contact").unwrap().source, "Spectral");
+        assert_eq!(docs.remote, vec!["https://example.com/r.yaml"]);
+    }
+
+    #[test]
+    fn remote_js_and_missing_rulesets_are_not_read() {
+        let tmp = tempfile::tempdir().unwrap();
+        let docs = RuleDocs::load(
+            tmp.path(),
+            &spectral_config("https://example.com/.spectral.yml"),
+        )
+        .unwrap();
+        assert_eq!(docs.remote, vec!["https://example.com/.spectral.yml"]);
+
+        let docs = RuleDocs::load(tmp.path(), &spectral_config(".spectral.js")).unwrap();
+        assert!(docs.custom.is_empty());
+        let docs = RuleDocs::load(tmp.path(), &spectral_config("missing.yaml")).unwrap();
+        assert!(docs.custom.is_empty());
+    }
+
+    #[test]
+    fn redocly_config_rules() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(
+            tmp.path().join("redocly.yaml"),
+            "\
+extends: [recommended]
+rules:
+  operation-summary: error
+  rule/summary-length:
+    subject: { type: Operation, property: summary }
+    message: Summary must be at least 20 characters
+    assertions: { minLength: 20 }
+",
+        )
+        .unwrap();
+        let cfg = Config {
+            linter: Linter::Redocly,
+            ..Config::default()
+        };
+        let docs = RuleDocs::load(tmp.path(), &cfg).unwrap();
+        let doc = docs.lookup("rule/summary-length").unwrap();
+        assert_eq!(doc.message, Some("Summary must be at least 20 characters"));
+        assert_eq!(doc.source, "redocly.yaml");
+        assert_eq!(docs.lookup("operation-summary").unwrap().source, "Redocly");
+    }
+
+    #[test]
+    fn malformed_ruleset_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(".spectral.yaml"), "rules: [unclosed\n").unwrap();
+        assert!(RuleDocs::load(tmp.path(), &spectral_config(".spectral.yaml")).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Local copies of remote Spectral rulesets under `.oav/cache/rulesets/`,
+//! kept fresh while online so linting still works without a network.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::docker;
+
+pub const CACHE_DIR: &str = ".oav/cache/rulesets";
+
+/// Cached copies refreshed by this process, which later runs don't fetch
+/// again.
+static REFRESHED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Whether `ruleset` is fetched over the network rather than read from the
+/// project or built into Spectral.
+pub fn is_remote(ruleset: &str) -> bool {
+    ruleset.starts_with("http://") || ruleset.starts_with("https://")
+}
+
+/// Where the copy of `url` is kept: its file name, which Spectral reads the
+/// format from, behind a hash of the whole URL.
+pub fn cache_path(work_dir: &Path, url: &str) -> PathBuf {
+    let name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|u| u.rsplit('/').next())
+        .filter(|n| !n.is_empty())
+        .unwrap_or("ruleset.yml");
+    work_dir
+        .join(CACHE_DIR)
+        .join(format!("{:08x}-{name}", crc32fast::hash(url.as_bytes())))
+}
+
+/// The ruleset a lint run should use in place of a remote one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Resolved {
+    /// The URL itself, the cached copy as the container sees it, or `None`
+    /// when there is nothing to lint with.
+    pub ruleset: Option<String>,
+    /// Why the run isn't linting against a fresh copy, for the status bar.
+    pub warning: Option<String>,
+}
+
+/// Pick the ruleset for remote `url`. Online, the cached copy is refreshed
+/// once per session and Spectral fetches the URL as before; the copy stands
+/// in when the URL can't be reached. `offline` never touches the network.
+/// `proxy` holds proxy variables that take precedence over the
+/// environment's.
+pub fn resolve(work_dir: &Path, url: &str, offline: bool, proxy: &[(String, String)]) -> Resolved {
+    let cached = cache_path(work_dir, url);
+    let container_path = || {
+        let name = cached.file_name().unwrap_or_default().to_string_lossy();
+        format!("/work/{CACHE_DIR}/{name}")
+    };
+    if offline {
+        return if cached.is_file() {
+            Resolved {
+                ruleset: Some(container_path()),
+                warning: None,
+            }
+        } else {
+            Resolved {
+                ruleset: None,
+                warning: Some(format!(
+                    "Offline and {url} was never cached \u{2014} run once online to cache it"
+                )),
+            }
+        };
+    }
+    if cached.is_file() && was_refreshed(&cached) {
+        return Resolved {
+            ruleset: Some(url.to_string()),
+            warning: None,
+        };
+    }
+    match refresh(work_dir, url, proxy) {
+        Ok(()) => {
+            mark_refreshed(&cached);
+            Resolved {
+                ruleset: Some(url.to_string()),
+                warning: None,
+            }
+        }
+        Err(e) if cached.is_file() => Resolved {
+            ruleset: Some(container_path()),
+            warning: Some(format!("{e:#} \u{2014} linting with the cached copy")),
+        },
+        // Without a copy, let Spectral try; say why it will likely fail.
+        Err(_) => Resolved {
+            ruleset: Some(url.to_string()),
+            warning: docker::check_reachable(url, proxy)
+                .err()
+                .map(|e| format!("{e:#}")),
+        },
+    }
+}
+
+fn was_refreshed(cached: &Path) -> bool {
+    REFRESHED.lock().is_ok_and(|set| set.contains(cached))
+}
+
+fn mark_refreshed(cached: &Path) {
+    if let Ok(mut set) = REFRESHED.lock() {
+        set.insert(cached.to_path_buf());
+    }
+}
+
+/// Download `url` into the cache with `curl`, revalidating an existing copy
+/// by its ETag. curl picks its proxy from the environment, with `proxy`
+/// set on top.
+pub fn refresh(work_dir: &Path, url: &str, proxy: &[(String, String)]) -> Result<()> {
+    let path = cache_path(work_dir, url);
+    let dir = path.parent().unwrap_or(work_dir);
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{name}.{}.tmp", std::process::id()));
+    let etag_path = dir.join(format!("{name}.etag"));
+
+    let mut curl = Command::new("curl");
+    curl.envs(proxy.iter().map(|(name, value)| (name, value)))
+        .args(["-sSL", "--connect-timeout", "5", "--max-time", "30"])
+        .args(["-D", "-", "-o"])
+        .arg(&tmp);
+    if path.is_file()
+        && let Ok(etag) = fs::read_to_string(&etag_path)
+    {
+        curl.args(["-H", &format!("If-None-Match: {}", etag.trim())]);
+    }
+    let output = curl.arg(url).output().context("Failed to run curl")?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&tmp);
+        bail!(
+            "Failed to fetch {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let (status, etag) = parse_headers(&String::from_utf8_lossy(&output.stdout));
+    let stored = match status {
+        Some(304) => Ok(()),
+        Some(200..=299) => {
+            fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))
+        }
+        Some(status) => Err(anyhow!("Failed to fetch {url}: HTTP {status}")),
+        None => Err(anyhow!("Failed to fetch {url}: no response")),
+    };
+    let _ = fs::remove_file(&tmp);
+    if stored.is_ok() && status != Some(304) {
+        match etag {
+            Some(etag) => {
+                let _ = fs::write(&etag_path, etag);
+            }
+            None => {
+                let _ = fs::remove_file(&etag_path);
+            }
+        }
+    }
+    stored
+}
+
+/// Status and ETag of the final response in curl's `-D` header dump, which
+/// holds one block per redirect followed.
+fn parse_headers(dump: &str) -> (Option<u16>, Option<String>) {
+    let mut status = None;
+    let mut etag = None;
+    for line in dump.lines() {
+        if line.starts_with("HTTP/") {
+            status = line.split_whitespace().nth(1).and_then(|s| s.parse().ok());
+            etag = None;
+        } else if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("etag")
+        {
+            etag = Some(value.trim().to_string());
+        }
+    }
+    (status, etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://example.com/rules/.spectral.yml?ref=v2";
+
+    #[test]
+    fn cache_path_keeps_the_file_name() {
+        let path = cache_path(Path::new("/p"), URL);
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.ends_with("-.spectral.yml"), "{name}");
+        assert!(path.starts_with("/p/.oav/cache/rulesets"));
+        assert_ne!(
+            path,
+            cache_path(Path::new("/p"), "https://example.com/.spectral.yml")
+        );
+    }
+
+    #[test]
+    fn parse_headers_reads_the_final_response() {
+        let dump = "HTTP/1.1 301 Moved Permanently\r\nLocation: /x\r\nETag: \"old\"\r\n\r\n\
+                    HTTP/2 200\r\ncontent-type: text/yaml\r\netag: W/\"abc\"\r\n\r\n";
+        assert_eq!(parse_headers(dump), (Some(200), Some("W/\"abc\"".into())));
+        assert_eq!(
+            parse_headers("HTTP/1.1 304 Not Modified\r\n"),
+            (Some(304), None)
+        );
+        assert_eq!(parse_headers(""), (None, None));
+    }
+
+    #[test]
+    fn offline_uses_the_cached_copy_or_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = resolve(dir.path(), URL, true, &[]);
+        assert_eq!(missing.ruleset, None);
+        assert!(missing.warning.unwrap().contains("never cached"));
+
+        let cached = cache_path(dir.path(), URL);
+        fs::create_dir_all(cached.parent().unwrap()).unwrap();
+        fs::write(&cached, "extends: spectral:oas\n").unwrap();
+        let name = cached.file_name().unwrap().to_string_lossy();
+        assert_eq!(
+            resolve(dir.path(), URL, true, &[]),
+            Resolved {
+                ruleset: Some(format!("/work/.oav/cache/rulesets/{name}")),
+                warning: None,
+            }
+        );
+    }
+
+    #[test]
+    fn refreshed_copies_are_not_fetched_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let cached = cache_path(dir.path(), URL);
+        fs::create_dir_all(cached.parent().unwrap()).unwrap();
+        fs::write(&cached, "extends: spectral:oas\n").unwrap();
+        mark_refreshed(&cached);
+        // No proxy can be reached, so a fetch would come back with a warning.
+        let proxy = [("HTTPS_PROXY".to_string(), "http://127.0.0.1:1".to_string())];
+        assert_eq!(
+            resolve(dir.path(), URL, false, &proxy),
+            Resolved {
+                ruleset: Some(URL.to_string()),
+                warning: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Recorded pipeline runs: the report and a copy of the generated output of
+//! each finished run, so any two runs can be compared later.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::pipeline::ValidateReport;
+
+pub const RUNS_DIR: &str = ".oav/runs";
+
+/// Runs kept on disk; recording a new one deletes the oldest beyond this.
+pub const MAX_RUNS: usize = 10;
+
+const GENERATED_DIR: &str = ".oav/generated";
+const RECORD_FILE: &str = "run.json";
+const REPORT_FILE: &str = "report.json";
+
+/// Files larger than this aren't copied; the diff view doesn't compare them.
+pub const MAX_FILE_SIZE: u64 = 512 * 1024;
+
+/// A run recorded under [`RUNS_DIR`], in a directory named after its
+/// timestamp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Unix time the run finished.
+    pub timestamp: u64,
+    pub spec: String,
+    /// `scope/generator` directories whose output was copied.
+    pub generators: Vec<String>,
+    pub passed: usize,
+    pub failed: usize,
+    /// `scope/generator/path` of the files left out for being larger than
+    /// [`MAX_FILE_SIZE`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub too_large: Vec<String>,
+}
+
+impl RunRecord {
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        root.join(RUNS_DIR).join(self.timestamp.to_string())
+    }
+
+    /// Where the run's copy of `key` (`scope/generator`) lives.
+    pub fn generated_dir(&self, root: &Path, key: &str) -> PathBuf {
+        self.dir(root).join("generated").join(key)
+    }
+
+    /// The run's report. Runs recorded by older versions have none.
+    pub fn report(&self, root: &Path) -> Option<ValidateReport> {
+        let json = fs::read_to_string(self.dir(root).join(REPORT_FILE)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// How long before `now` the run finished: `just now`, `12m ago`,
+    /// `3h ago`, `2d ago`.
+    pub fn age(&self, now: u64) -> String {
+        let secs = now.saturating_sub(self.timestamp);
+        match secs {
+            0..60 => "just now".to_string(),
+            60..3_600 => format!("{}m ago", secs / 60),
+            3_600..86_400 => format!("{}h ago", secs / 3_600),
+            _ => format!("{}d ago", secs / 86_400),
+        }
+    }
+}
+
+/// Copy the generated output of `report`'s generate steps from
+/// `.oav/generated/` and record the run, dropping runs beyond [`MAX_RUNS`].
+/// Returns `None` when the run generated nothing.
+pub fn record(root: &Path, report: &ValidateReport) -> Result<Option<RunRecord>> {
+    let Some(steps) = &report.phases.generate else {
+        return Ok(None);
+    };
+    let generators: Vec<String> = steps
+        .iter()
+        .map(|step| format!("{}/{}", step.scope, step.generator))
+        .filter(|key| root.join(GENERATED_DIR).join(key).is_dir())
+        .collect();
+    if generators.is_empty() {
+        return Ok(None);
+    }
+
+    // Two runs finishing within a second get consecutive timestamps.
+    let mut timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    while root.join(RUNS_DIR).join(timestamp.to_string()).exists() {
+        timestamp += 1;
+    }
+    let mut run = RunRecord {
+        timestamp,
+        spec: report.spec.clone(),
+        generators,
+        passed: report.summary.passed,
+        failed: report.summary.failed,
+        too_large: Vec::new(),
+    };
+
+    let dir = run.dir(root);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for key in &run.generators {
+        let skipped = copy_tree(
+            &root.join(GENERATED_DIR).join(key),
+            &run.generated_dir(root, key),
+        )?;
+        run.too_large
+            .extend(skipped.into_iter().map(|rel| format!("{key}/{rel}")));
+    }
+    let path = dir.join(REPORT_FILE);
+    let json = serde_json::to_string_pretty(report).context("Failed to serialize report")?;
+    fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    let path = dir.join(RECORD_FILE);
+    let json = serde_json::to_string_pretty(&run).context("Failed to serialize run")?;
+    fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    prune(root, MAX_RUNS)?;
+    Ok(Some(run))
+}
+
+/// Recorded runs, newest first. Directories without a readable record are
+/// skipped.
+pub fn list(root: &Path) -> Result<Vec<RunRecord>> {
+    let dir = root.join(RUNS_DIR);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut runs: Vec<RunRecord> = entries
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.ok()?.path().join(RECORD_FILE)).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    runs.sort_unstable_by_key(|run| std::cmp::Reverse(run.timestamp));
+    Ok(runs)
+}
+
+/// Delete all but the newest `keep` runs.
+fn prune(root: &Path, keep: usize) -> Result<()> {
+    for run in list(root)?.iter().skip(keep) {
+        let dir = run.dir(root);
+        fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// Copy the files under `from` to `to`, returning the `/`-separated paths
+/// of those left out for being larger than [`MAX_FILE_SIZE`].
+fn copy_tree(from: &Path, to: &Path) -> Result<Vec<String>> {
+    let mut too_large = Vec::new();
+    for entry in WalkDir::new(from).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(from).unwrap_or(entry.path());
+        if entry.metadata().is_ok_and(|m| m.len() > MAX_FILE_SIZE) {
+            let parts: Vec<_> = rel.iter().map(|part| part.to_string_lossy()).collect();
+            too_large.push(parts.join("/"));
+            continue;
+        }
+        let target = to.join(rel);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::copy(entry.path(), &target)
+            .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+    }
+    too_large.sort_unstable();
+    Ok(too_large)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{Phases, StepResult, Summary};
+
+    fn report(generators: &[&str]) -> ValidateReport {
+        ValidateReport {
+            spec: "openapi.yaml".into(),
+            mode: "server".into(),
+            phases: Phases {
+                generate: Some(
+                    generators
+                        .iter()
+                        .map(|g| StepResult {
+                            generator: (*g).into(),
+                            scope: "server".into(),
+                            status: "pass".into(),
+                            log: String::new(),
+                            attempts: 1,
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Phases::default()
+            },
+            summary: Summary {
+                total: generators.len(),
+                passed: generators.len(),
+                failed: 0,
+            },
+            images: Default::default(),
+        }
+    }
+
+    #[test]
+    fn records_generated_output_and_lists_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let gen_dir = dir.path().join(GENERATED_DIR).join("server/go");
+        fs::create_dir_all(gen_dir.join("api")).unwrap();
+        fs::write(gen_dir.join("api/api.go"), "package api\n").unwrap();
+
+        let first = record(dir.path(), &report(&["go", "java"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.generators, vec!["server/go"]);
+        let copied = first
+            .generated_dir(dir.path(), "server/go")
+            .join("api/api.go");
+        assert_eq!(fs::read_to_string(copied).unwrap(), "package api\n");
+        assert_eq!(first.report(dir.path()).unwrap().summary.total, 2);
+        assert!(first.too_large.is_empty());
+
+        let second = record(dir.path(), &report(&["go"])).unwrap().unwrap();
+        assert!(second.timestamp > first.timestamp);
+        assert_eq!(list(dir.path()).unwrap(), vec![second, first]);
+    }
+
+    #[test]
+    fn large_files_are_listed_instead_of_copied() {
+        let dir = tempfile::tempdir().unwrap();
+        let gen_dir = dir.path().join(GENERATED_DIR).join("server/go");
+        fs::create_dir_all(gen_dir.join("api")).unwrap();
+        fs::write(gen_dir.join("api/small.go"), "package api\n").unwrap();
+        fs::write(
+            gen_dir.join("api/big.go"),
+            vec![b'a'; MAX_FILE_SIZE as usize + 1],
+        )
+        .unwrap();
+
+        let run = record(dir.path(), &report(&["go"])).unwrap().unwrap();
+        assert_eq!(run.too_large, vec!["server/go/api/big.go"]);
+        let copied = run.generated_dir(dir.path(), "server/go").join("api");
+        assert!(copied.join("small.go").is_file());
+        assert!(!copied.join("big.go").exists());
+        assert_eq!(list(dir.path()).unwrap(), vec![run]);
+    }
+
+    #[test]
+    fn nothing_generated_records_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(record(dir.path(), &report(&["go"])).unwrap().is_none());
+        assert!(list(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn age_rounds_down_to_the_largest_unit() {
+        let run = RunRecord {
+            timestamp: 1_000_000,
+            spec: String::new(),
+            generators: Vec::new(),
+            passed: 0,
+            failed: 0,
+            too_large: Vec::new(),
+        };
+        assert_eq!(run.age(1_000_030), "just now");
+        assert_eq!(run.age(1_000_000 + 59 * 60), "59m ago");
+        assert_eq!(run.age(1_000_000 + 3 * 3_600 + 5), "3h ago");
+        assert_eq!(run.age(1_000_000 + 2 * 86_400), "2d ago");
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(GENERATED_DIR).join("server/go")).unwrap();
+        for _ in 0..3 {
+            record(dir.path(), &report(&["go"])).unwrap();
+        }
+        let runs = list(dir.path()).unwrap();
+        prune(dir.path(), 2).unwrap();
+        assert_eq!(list(dir.path()).unwrap(), runs[..2]);
+    }
+}
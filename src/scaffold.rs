@@ -11,7 +11,15 @@ const OAV_DIRS: &[&str] = &[
     ".oav/reports/compile",
 ];
 
-const GITIGNORE_ENTRIES: &[&str] = &[".oav/generated/", ".oav/reports/"];
+const GITIGNORE_ENTRIES: &[&str] = &[
+    ".oav/generated/",
+    ".oav/reports/",
+    ".oav/runs/",
+    ".oav/matrix/",
+    ".oav/workspace-reports/",
+    ".oav/logs/",
+    ".oav/lazyoav.log",
+];
 
 const DOCKER_COMPOSE_YAML: &str = include_str!("../assets/docker-compose.yaml");
 
@@ -32,7 +40,9 @@ pub fn ensure_oav_dirs(work_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Ensure `.oav/generated/` and `.oav/reports/` are in `.gitignore`.
+/// Ensure `.oav/generated/`, `.oav/reports/`, `.oav/runs/`, `.oav/matrix/`,
+/// `.oav/workspace-reports/`, `.oav/logs/` and the debug log are in
+/// `.gitignore`.
 ///
 /// Creates `.gitignore` if it doesn't exist. Appends missing entries if it does.
 pub fn manage_gitignore(work_dir: &Path) -> Result<()> {
@@ -0,0 +1,354 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::openapi::operations;
+use crate::pipeline::ValidateReport;
+
+/// One score per run, one JSON object per line, relative to the project.
+/// Like the baseline it lives outside `.oav/reports/` so the team can
+/// commit it and follow the trend.
+pub const HISTORY_FILE: &str = ".oav/score-history.jsonl";
+
+/// One part of the quality score, 0–100.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Component {
+    pub name: String,
+    pub score: u8,
+    /// What the score is based on, e.g. `12 of 15 operations`.
+    pub detail: String,
+}
+
+/// Composite spec quality score: the mean of its components.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub total: u8,
+    pub components: Vec<Component>,
+}
+
+/// Lint findings that count against the score, after suppressions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LintCounts {
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl Score {
+    /// Score a parsed spec of `lines` lines from its lint findings and the
+    /// run's generator results. Generator pass rate is left out when the run
+    /// had no generate or compile steps.
+    pub fn compute(spec: &Value, lines: usize, lint: LintCounts, report: &ValidateReport) -> Self {
+        let mut components = vec![
+            lint_density(lines, lint),
+            documentation(spec),
+            examples(spec),
+            polymorphism(spec),
+        ];
+        components.extend(generator_pass_rate(report));
+        let total =
+            components.iter().map(|c| u32::from(c.score)).sum::<u32>() / components.len() as u32;
+        Self {
+            total: total as u8,
+            components,
+        }
+    }
+}
+
+/// Weighted findings per 100 lines; an error weighs three warnings.
+fn lint_density(lines: usize, lint: LintCounts) -> Component {
+    let weighted = (lint.errors * 3 + lint.warnings) as f64;
+    let density = weighted * 100.0 / lines.max(1) as f64;
+    Component {
+        name: "Lint density".into(),
+        score: (100.0 - density * 5.0).clamp(0.0, 100.0).round() as u8,
+        detail: format!(
+            "{} error(s), {} warning(s) in {lines} lines",
+            lint.errors, lint.warnings
+        ),
+    }
+}
+
+/// Operations, parameters and component schemas with a description.
+fn documentation(spec: &Value) -> Component {
+    let mut documented = 0;
+    let mut total = 0;
+    let mut count = |node: &Value, keys: &[&str]| {
+        total += 1;
+        if keys.iter().any(|k| node.get(*k).is_some_and(non_empty)) {
+            documented += 1;
+        }
+    };
+    for (_, _, op) in operations(spec) {
+        count(op, &["summary", "description"]);
+        for param in op
+            .get("parameters")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter(|p| p.get("$ref").is_none())
+        {
+            count(param, &["description"]);
+        }
+    }
+    for (_, schema) in spec
+        .get("components")
+        .and_then(|c| c.get("schemas"))
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+    {
+        count(schema, &["description"]);
+    }
+    ratio("Documentation", documented, total, "described items")
+}
+
+/// Request and response bodies with an example.
+fn examples(spec: &Value) -> Component {
+    let mut with_example = 0;
+    let mut total = 0;
+    for (_, _, op) in operations(spec) {
+        let request = op.get("requestBody").and_then(|b| b.get("content"));
+        let responses = op
+            .get("responses")
+            .and_then(Value::as_mapping)
+            .into_iter()
+            .flatten()
+            .filter_map(|(_, r)| r.get("content"));
+        for content in request.into_iter().chain(responses) {
+            for (_, media) in content.as_mapping().into_iter().flatten() {
+                total += 1;
+                let has_example = media.get("example").is_some()
+                    || media.get("examples").is_some()
+                    || media
+                        .get("schema")
+                        .is_some_and(|s| s.get("example").is_some());
+                if has_example {
+                    with_example += 1;
+                }
+            }
+        }
+    }
+    ratio("Examples", with_example, total, "bodies with an example")
+}
+
+/// `oneOf`/`anyOf` schemas that carry a discriminator.
+fn polymorphism(spec: &Value) -> Component {
+    fn walk(node: &Value, safe: &mut usize, total: &mut usize) {
+        match node {
+            Value::Mapping(map) => {
+                if map.contains_key("oneOf") || map.contains_key("anyOf") {
+                    *total += 1;
+                    if map.contains_key("discriminator") {
+                        *safe += 1;
+                    }
+                }
+                for (_, value) in map {
+                    walk(value, safe, total);
+                }
+            }
+            Value::Sequence(items) => {
+                for item in items {
+                    walk(item, safe, total);
+                }
+            }
+            _ => {}
+        }
+    }
+    let (mut safe, mut total) = (0, 0);
+    walk(spec, &mut safe, &mut total);
+    ratio("Polymorphism", safe, total, "unions with a discriminator")
+}
+
+fn generator_pass_rate(report: &ValidateReport) -> Option<Component> {
+    let steps: Vec<_> = [&report.phases.generate, &report.phases.compile]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    let passed = steps.iter().filter(|s| s.status == "pass").count();
+    Some(ratio("Generators", passed, steps.len(), "steps passed"))
+}
+
+/// A percentage component; nothing to measure counts as full marks.
+fn ratio(name: &str, good: usize, total: usize, what: &str) -> Component {
+    let score = (good * 100 + total / 2).checked_div(total).unwrap_or(100);
+    Component {
+        name: name.into(),
+        score: score as u8,
+        detail: format!("{good} of {total} {what}"),
+    }
+}
+
+fn non_empty(value: &Value) -> bool {
+    value.as_str().is_none_or(|s| !s.trim().is_empty())
+}
+
+/// A recorded score with the Unix time of the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub score: Score,
+}
+
+/// Read [`HISTORY_FILE`] from `root`, oldest first. Missing file means no
+/// history; unreadable lines are skipped.
+pub fn load_history(root: &Path) -> Result<Vec<HistoryEntry>> {
+    let path = root.join(HISTORY_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append `score` to [`HISTORY_FILE`], stamped with the current time.
+pub fn append_history(root: &Path, score: &Score) -> Result<()> {
+    let path = root.join(HISTORY_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let entry = HistoryEntry {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        score: score.clone(),
+    };
+    let line = serde_json::to_string(&entry).context("Failed to serialize score")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{line}").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{Phases, StepResult, Summary};
+
+    const SPEC: &str = r#"
+paths:
+  /pets:
+    get:
+      summary: List pets
+      parameters:
+        - name: limit
+          in: query
+      responses:
+        "200":
+          content:
+            application/json:
+              example: []
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              oneOf:
+                - type: string
+                - type: integer
+      responses:
+        "201":
+          description: created
+components:
+  schemas:
+    Pet:
+      description: A pet
+"#;
+
+    fn report(statuses: &[&str]) -> ValidateReport {
+        ValidateReport {
+            spec: "spec.yaml".into(),
+            mode: "server".into(),
+            phases: Phases {
+                generate: Some(
+                    statuses
+                        .iter()
+                        .map(|status| StepResult {
+                            generator: "spring".into(),
+                            scope: "server".into(),
+                            status: (*status).into(),
+                            log: String::new(),
+                            attempts: 1,
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Phases::default()
+            },
+            summary: Summary {
+                total: 0,
+                passed: 0,
+                failed: 0,
+            },
+            images: Default::default(),
+        }
+    }
+
+    #[test]
+    fn scores_each_component() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let lint = LintCounts {
+            errors: 1,
+            warnings: 2,
+        };
+        let score = Score::compute(&spec, 100, lint, &report(&["pass", "fail"]));
+        let parts: Vec<(&str, u8)> = score
+            .components
+            .iter()
+            .map(|c| (c.name.as_str(), c.score))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("Lint density", 75),
+                ("Documentation", 50),
+                ("Examples", 50),
+                ("Polymorphism", 0),
+                ("Generators", 50),
+            ]
+        );
+        assert_eq!(score.total, 45);
+        assert_eq!(score.components[1].detail, "2 of 4 described items");
+    }
+
+    #[test]
+    fn generators_are_left_out_without_steps() {
+        let spec: Value = serde_yaml::from_str("openapi: 3.0.0").unwrap();
+        let score = Score::compute(&spec, 1, LintCounts::default(), &report(&[]));
+        assert_eq!(score.components.len(), 4);
+        assert_eq!(score.total, 100);
+    }
+
+    #[test]
+    fn history_appends_and_loads_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_history(dir.path()).unwrap().is_empty());
+
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let first = Score::compute(&spec, 100, LintCounts::default(), &report(&["pass"]));
+        let second = Score::compute(&spec, 100, LintCounts::default(), &report(&["fail"]));
+        append_history(dir.path(), &first).unwrap();
+        append_history(dir.path(), &second).unwrap();
+
+        let history = load_history(dir.path()).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].score, first);
+        assert_eq!(history[1].score, second);
+    }
+}
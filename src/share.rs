@@ -0,0 +1,405 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+use crate::config::Redaction;
+use crate::reports::REPORTS_DIR;
+
+/// Where the redacted bundle is written, relative to the project.
+pub const SHARE_DIR: &str = ".oav/reports/share";
+
+/// Replaces sensitive values in text before it leaves the machine.
+pub struct Redactor {
+    /// Exact strings and their placeholder, longest first so a server URL
+    /// is replaced whole before any shorter value inside it.
+    literals: Vec<(String, &'static str)>,
+    hosts: Vec<String>,
+    /// What templated server hosts end in, e.g. `.staging.example.net` for
+    /// `{env}.staging.example.net`.
+    host_suffixes: Vec<String>,
+    emails: bool,
+}
+
+impl Redactor {
+    /// Build a redactor from the config and the spec being shared.
+    pub fn new(cfg: &Redaction, spec: &str, work_dir: &Path) -> Self {
+        let mut literals = Vec::new();
+        let mut host_suffixes = Vec::new();
+        let mut hosts: Vec<String> = cfg
+            .hosts
+            .iter()
+            .map(|h| {
+                h.trim_start_matches('*')
+                    .trim_start_matches('.')
+                    .to_ascii_lowercase()
+            })
+            .filter(|h| !h.is_empty())
+            .collect();
+
+        if cfg.server_urls {
+            for url in server_urls(spec) {
+                if let Some(host) = url_host(&url) {
+                    match host.rsplit_once('}') {
+                        None => hosts.push(host.to_ascii_lowercase()),
+                        Some((_, suffix)) if is_host_suffix(suffix) => {
+                            host_suffixes.push(suffix.to_ascii_lowercase());
+                        }
+                        Some(_) => {}
+                    }
+                }
+                literals.push((url, "<server-url>"));
+            }
+        }
+        literals.extend(
+            cfg.values
+                .iter()
+                .filter(|v| !v.is_empty())
+                .map(|v| (v.clone(), "<redacted>")),
+        );
+        // Absolute paths in logs and repro.sh give away the user name.
+        let dir = work_dir.display().to_string();
+        if dir.len() > 1 {
+            literals.push((dir, "<project>"));
+        }
+
+        literals.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+        literals.dedup_by(|a, b| a.0 == b.0);
+        hosts.sort();
+        hosts.dedup();
+        host_suffixes.sort();
+        host_suffixes.dedup();
+
+        Self {
+            literals,
+            hosts,
+            host_suffixes,
+            emails: cfg.emails,
+        }
+    }
+
+    /// Redact `text`, returning the result and how many values were replaced.
+    pub fn redact(&self, text: &str) -> (String, usize) {
+        let mut out = text.to_string();
+        let mut count = 0;
+
+        for (value, token) in &self.literals {
+            let n = out.matches(value.as_str()).count();
+            if n > 0 {
+                out = out.replace(value.as_str(), token);
+                count += n;
+            }
+        }
+        if self.emails {
+            out = replace_words(&out, is_email_char, is_email, "<email>", &mut count);
+        }
+        if !self.hosts.is_empty() || !self.host_suffixes.is_empty() {
+            out = replace_words(
+                &out,
+                is_host_char,
+                |word| self.is_hidden_host(word),
+                "<host>",
+                &mut count,
+            );
+        }
+        (out, count)
+    }
+
+    fn is_hidden_host(&self, word: &str) -> bool {
+        let word = word.to_ascii_lowercase();
+        self.hosts
+            .iter()
+            .any(|h| word == *h || word.ends_with(&format!(".{h}")))
+            || self
+                .host_suffixes
+                .iter()
+                .any(|s| word.len() > s.len() && word.ends_with(s.as_str()))
+    }
+}
+
+/// What [`export_bundle`] wrote.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub dir: PathBuf,
+    pub files: usize,
+    pub redactions: usize,
+}
+
+/// Write a redacted copy of the spec and the last run's reports and logs
+/// to [`SHARE_DIR`], replacing whatever an earlier export left there.
+pub fn export_bundle(work_dir: &Path, spec_path: &Path, cfg: &Redaction) -> Result<ExportSummary> {
+    let spec = fs::read_to_string(spec_path)
+        .with_context(|| format!("Failed to read {}", spec_path.display()))?;
+    let redactor = Redactor::new(cfg, &spec, work_dir);
+
+    let dir = work_dir.join(SHARE_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).with_context(|| format!("Failed to clear {}", dir.display()))?;
+    }
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut summary = ExportSummary {
+        dir: dir.clone(),
+        files: 0,
+        redactions: 0,
+    };
+    let mut write = |rel: &Path, text: &str| -> Result<()> {
+        let (redacted, n) = redactor.redact(text);
+        let path = dir.join(rel);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, redacted)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        summary.files += 1;
+        summary.redactions += n;
+        Ok(())
+    };
+
+    let spec_name = spec_path
+        .file_name()
+        .map_or_else(|| PathBuf::from("spec.yaml"), PathBuf::from);
+    write(&Path::new("spec").join(spec_name), &spec)?;
+
+    let reports = work_dir.join(REPORTS_DIR);
+    let bundle = work_dir.join(crate::support::SUPPORT_BUNDLE);
+    for entry in WalkDir::new(&reports)
+        .into_iter()
+        .filter_entry(|e| e.path() != dir && e.path() != bundle)
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        // Binary files can't be redacted, so they stay out of the bundle.
+        let Ok(text) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let rel = entry.path().strip_prefix(&reports).unwrap_or(entry.path());
+        write(rel, &text)?;
+    }
+
+    Ok(summary)
+}
+
+/// Absolute `servers[].url` values anywhere in the spec (root, path and
+/// operation level). Relative URLs like `/v1` give nothing away.
+fn server_urls(spec: &str) -> Vec<String> {
+    fn walk(value: &serde_yaml::Value, out: &mut Vec<String>) {
+        match value {
+            serde_yaml::Value::Mapping(map) => {
+                for (key, value) in map {
+                    if key.as_str() == Some("servers")
+                        && let Some(servers) = value.as_sequence()
+                    {
+                        out.extend(
+                            servers
+                                .iter()
+                                .filter_map(|s| s.get("url")?.as_str())
+                                .filter(|url| url.contains("://"))
+                                .map(str::to_string),
+                        );
+                    }
+                    walk(value, out);
+                }
+            }
+            serde_yaml::Value::Sequence(seq) => seq.iter().for_each(|v| walk(v, out)),
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    if let Ok(doc) = serde_yaml::from_str::<serde_yaml::Value>(spec) {
+        walk(&doc, &mut out);
+    }
+    out
+}
+
+/// Host of an absolute URL, as written: `{env}.example.com` when templated.
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://")?.1;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+    let host = authority.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// Whether what a templated host ends in names a domain, not just a
+/// top-level one like `.net` that would hide every host under it.
+fn is_host_suffix(suffix: &str) -> bool {
+    !suffix.contains('{') && suffix.split('.').filter(|label| !label.is_empty()).count() >= 2
+}
+
+fn is_email_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "._%+-@".contains(c)
+}
+
+fn is_email(word: &str) -> bool {
+    match word.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && !domain.contains('@') && domain.contains('.'),
+        None => false,
+    }
+}
+
+fn is_host_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '.' || c == '-'
+}
+
+/// Replace every maximal run of `is_char` characters that `matches` accepts.
+/// Trailing dots are treated as punctuation, not part of the word.
+fn replace_words(
+    text: &str,
+    is_char: fn(char) -> bool,
+    matches: impl Fn(&str) -> bool,
+    token: &str,
+    count: &mut usize,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_char) {
+        out.push_str(&rest[..start]);
+        let run = &rest[start..];
+        let len = run.find(|c| !is_char(c)).unwrap_or(run.len());
+        let word = run[..len].trim_end_matches('.');
+        if matches(word) {
+            out.push_str(token);
+            out.push_str(&run[word.len()..len]);
+            *count += 1;
+        } else {
+            out.push_str(&run[..len]);
+        }
+        rest = &run[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "\
+openapi: 3.0.3
+info:
+  title: Orders
+  contact:
+    email: team-orders@example.com
+servers:
+  - url: https://orders.internal.example.net/v1
+  - url: /relative
+paths:
+  /orders:
+    get:
+      servers:
+        - url: https://{env}.staging.example.net
+";
+
+    fn redactor(cfg: &Redaction) -> Redactor {
+        Redactor::new(cfg, SPEC, Path::new("/home/alex/orders"))
+    }
+
+    #[test]
+    fn collects_absolute_server_urls() {
+        assert_eq!(
+            server_urls(SPEC),
+            vec![
+                "https://orders.internal.example.net/v1",
+                "https://{env}.staging.example.net",
+            ]
+        );
+    }
+
+    #[test]
+    fn redacts_server_urls_hosts_and_emails() {
+        let r = redactor(&Redaction::default());
+        let (text, n) = r.redact(
+            "GET https://orders.internal.example.net/v1/orders failed\n\
+             resolving orders.internal.example.net. Contact team-orders@example.com.",
+        );
+        assert_eq!(
+            text,
+            "GET <server-url>/orders failed\n\
+             resolving <host>. Contact <email>."
+        );
+        assert_eq!(n, 3);
+    }
+
+    #[test]
+    fn templated_server_hosts_are_hidden_by_suffix() {
+        let r = redactor(&Redaction::default());
+        let (text, n) = r.redact(
+            "connect prod.staging.example.net:443 via staging.example.net, see https://{env}.staging.example.net",
+        );
+        assert_eq!(
+            text,
+            "connect <host>:443 via staging.example.net, see <server-url>"
+        );
+        assert_eq!(n, 2);
+
+        let spec = "servers:\n  - url: https://{env}.net\n";
+        let r = Redactor::new(&Redaction::default(), spec, Path::new("/p"));
+        assert_eq!(r.redact("api.example.net").1, 0);
+    }
+
+    #[test]
+    fn configured_hosts_match_subdomains() {
+        let cfg = Redaction {
+            hosts: vec!["*.corp.local".into()],
+            ..Redaction::default()
+        };
+        let (text, _) =
+            redactor(&cfg).redact("db.eu.corp.local:5432 and corp.local and notcorp.local");
+        assert_eq!(text, "<host>:5432 and <host> and notcorp.local");
+    }
+
+    #[test]
+    fn values_and_project_dir_are_replaced() {
+        let cfg = Redaction {
+            values: vec!["ACME-4711".into()],
+            ..Redaction::default()
+        };
+        let (text, n) = redactor(&cfg).redact("cd /home/alex/orders # tenant ACME-4711");
+        assert_eq!(text, "cd <project> # tenant <redacted>");
+        assert_eq!(n, 2);
+    }
+
+    #[test]
+    fn disabled_categories_are_kept() {
+        let cfg = Redaction {
+            server_urls: false,
+            emails: false,
+            ..Redaction::default()
+        };
+        let input = "https://orders.internal.example.net/v1 a@b.io";
+        assert_eq!(redactor(&cfg).redact(input), (input.to_string(), 0));
+    }
+
+    #[test]
+    fn export_writes_redacted_spec_and_logs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let spec_path = root.join("openapi.yaml");
+        fs::write(&spec_path, SPEC).unwrap();
+        fs::create_dir_all(root.join(".oav/reports/lint")).unwrap();
+        fs::write(
+            root.join(".oav/reports/lint/spectral.log"),
+            format!("{}/openapi.yaml: error at servers[0]\n", root.display()),
+        )
+        .unwrap();
+        // Leftovers from an earlier export are replaced, not nested.
+        fs::create_dir_all(root.join(SHARE_DIR)).unwrap();
+        fs::write(root.join(SHARE_DIR).join("stale.txt"), "old").unwrap();
+
+        let summary = export_bundle(root, &spec_path, &Redaction::default()).unwrap();
+        assert_eq!(summary.files, 2);
+
+        let spec = fs::read_to_string(root.join(SHARE_DIR).join("spec/openapi.yaml")).unwrap();
+        assert!(!spec.contains("internal.example.net"));
+        assert!(!spec.contains("team-orders@"));
+        assert!(spec.contains("url: /relative"));
+
+        let log = fs::read_to_string(root.join(SHARE_DIR).join("lint/spectral.log")).unwrap();
+        assert_eq!(log, "<project>/openapi.yaml: error at servers[0]\n");
+        assert!(!root.join(SHARE_DIR).join("stale.txt").exists());
+    }
+}
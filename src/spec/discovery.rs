@@ -3,8 +3,30 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
+use lazyoav::config::CONFIG_FILE;
 use walkdir::WalkDir;
 
+const WELL_KNOWN: [&str; 3] = ["openapi.yaml", "openapi.yml", "openapi.json"];
+
+/// A project of a monorepo: a directory with its own `.oavc`, or with a
+/// spec under a well-known name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    /// Relative to the repository root; empty for the root itself.
+    pub dir: PathBuf,
+    pub has_config: bool,
+}
+
+impl Project {
+    pub fn label(&self) -> String {
+        if self.dir.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            self.dir.display().to_string()
+        }
+    }
+}
+
 /// Resolve a spec path (from config or CLI) to a relative path within the project root.
 pub fn normalize_spec_path(root: &Path, spec: &str) -> Result<PathBuf> {
     if spec.trim().is_empty() {
@@ -29,7 +51,7 @@ pub fn normalize_spec_path(root: &Path, spec: &str) -> Result<PathBuf> {
 /// Returns a sorted list of relative paths.
 pub fn discover_spec(root: &Path, max_depth: usize) -> Result<Vec<String>> {
     // Check well-known names first.
-    for name in ["openapi.yaml", "openapi.yml"] {
+    for name in WELL_KNOWN {
         if root.join(name).is_file() {
             return Ok(vec![name.to_string()]);
         }
@@ -47,7 +69,7 @@ pub fn discover_spec(root: &Path, max_depth: usize) -> Result<Vec<String>> {
             continue;
         }
         let path = entry.path();
-        if is_yaml(path)
+        if is_spec_file(path)
             && is_openapi_spec(path)
             && let Ok(rel) = path.strip_prefix(root)
         {
@@ -59,15 +81,47 @@ pub fn discover_spec(root: &Path, max_depth: usize) -> Result<Vec<String>> {
     Ok(matches)
 }
 
-fn is_yaml(path: &Path) -> bool {
+/// Walk the directory tree for projects, the root first if it is one, then
+/// the others sorted by path.
+pub fn discover_projects(root: &Path, max_depth: usize) -> Vec<Project> {
+    let walker = WalkDir::new(root)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !should_skip(e));
+
+    walker
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_dir())
+        .filter_map(|entry| {
+            let dir = entry.path();
+            if !is_project(dir) {
+                return None;
+            }
+            Some(Project {
+                dir: dir.strip_prefix(root).ok()?.to_path_buf(),
+                has_config: dir.join(CONFIG_FILE).is_file(),
+            })
+        })
+        .collect()
+}
+
+/// Whether `dir` is a project itself, without looking below it.
+pub fn is_project(dir: &Path) -> bool {
+    dir.join(CONFIG_FILE).is_file() || WELL_KNOWN.iter().any(|name| dir.join(name).is_file())
+}
+
+fn is_spec_file(path: &Path) -> bool {
     matches!(
         path.extension().and_then(|ext| ext.to_str()),
-        Some("yaml" | "yml" | "YAML" | "YML")
+        Some("yaml" | "yml" | "json" | "YAML" | "YML" | "JSON")
     )
 }
 
 fn is_openapi_spec(path: &Path) -> bool {
-    // Cheap pre-check: scan first 256 bytes for "openapi:" before doing a full parse.
+    // Cheap pre-check: scan first 256 bytes for an `openapi` key, in YAML or
+    // JSON form, before doing a full parse.
     let mut file = match File::open(path) {
         Ok(f) => f,
         Err(_) => return false,
@@ -78,7 +132,7 @@ fn is_openapi_spec(path: &Path) -> bool {
         Err(_) => return false,
     };
     let prefix = String::from_utf8_lossy(&head[..n]);
-    if !prefix.contains("openapi:") {
+    if !prefix.contains("openapi:") && !prefix.contains("\"openapi\"") {
         return false;
     }
 
@@ -87,6 +141,12 @@ fn is_openapi_spec(path: &Path) -> bool {
     if file.read_to_string(&mut content).is_err() {
         return false;
     }
+    if content.trim_start().starts_with('{') {
+        // Real JSON, which YAML doesn't always accept (tab indentation).
+        if let Ok(serde_json::Value::Object(object)) = serde_json::from_str(&content) {
+            return object.contains_key("openapi");
+        }
+    }
     let doc: serde_yaml::Value = match serde_yaml::from_str(&content) {
         Ok(v) => v,
         Err(_) => return false,
@@ -171,6 +231,43 @@ mod tests {
         assert!(specs.is_empty());
     }
 
+    #[test]
+    fn discover_finds_json_specs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("api.json"),
+            "{\n\t\"openapi\": \"3.0.0\",\n\t\"info\": {\"title\": \"Tabs\", \"version\": \"1\"}\n}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("package.json"), "{\"name\": \"app\"}\n").unwrap();
+
+        let specs = discover_spec(dir.path(), 4).unwrap();
+        assert_eq!(specs, vec!["api.json"]);
+    }
+
+    #[test]
+    fn discover_projects_finds_configs_and_well_known_specs() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["services/orders", "services/billing", "docs", ".oav/x"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        fs::write(dir.path().join("services/orders/.oavc"), "lint: false\n").unwrap();
+        fs::write(
+            dir.path().join("services/billing/openapi.yaml"),
+            "openapi: 3.0.0\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join(".oav/x/.oavc"), "").unwrap();
+
+        let projects = discover_projects(dir.path(), 4);
+        let labels: Vec<String> = projects.iter().map(Project::label).collect();
+        assert_eq!(labels, ["services/billing", "services/orders"]);
+        assert!(!projects[0].has_config && projects[1].has_config);
+
+        fs::write(dir.path().join(".oavc"), "").unwrap();
+        assert_eq!(discover_projects(dir.path(), 4)[0].label(), ".");
+    }
+
     #[test]
     fn normalize_rejects_missing_file() {
         let dir = tempfile::tempdir().unwrap();
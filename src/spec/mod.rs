@@ -1,7 +1,9 @@
 mod discovery;
 mod parser;
+mod schema;
 mod types;
 
-pub use discovery::{discover_spec, normalize_spec_path};
-pub use parser::parse_spec;
+pub use discovery::{Project, discover_projects, discover_spec, is_project, normalize_spec_path};
+pub use parser::{normalize_to_pointer, parse_spec};
+pub use schema::{enclosing_schema, render_tree, resolve_schema, sample_payload};
 pub use types::{ContextWindow, SourceSpan, SpecIndex};
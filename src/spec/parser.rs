@@ -4,10 +4,15 @@ use anyhow::Result;
 
 use super::types::{SourceSpan, SpecIndex};
 
-/// Parse a raw spec string (YAML or prettified JSON) and build a `SpecIndex`
-/// mapping JSON pointers to source line numbers.
+/// Parse a raw spec string (YAML or JSON) and build a `SpecIndex` mapping
+/// JSON pointers to source line numbers.
 pub fn parse_spec(raw: &str) -> Result<SpecIndex> {
     let lines: Vec<String> = raw.lines().map(String::from).collect();
+    if raw.trim_start().starts_with('{')
+        && let Some(spans) = JsonScanner::new(raw).document()
+    {
+        return Ok(SpecIndex::new(spans, lines).with_json(true));
+    }
     let mut spans = HashMap::new();
     // Stack of (indent_level, key_name).
     let mut stack: Vec<(usize, String)> = Vec::new();
@@ -55,6 +60,172 @@ pub fn parse_spec(raw: &str) -> Result<SpecIndex> {
     Ok(SpecIndex::new(spans, lines))
 }
 
+/// Walks a JSON document recording where each object member and array
+/// element starts. Unlike the line-based YAML scan, this copes with any
+/// layout, from minified to one member per line, and indexes array
+/// elements.
+struct JsonScanner<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    col: usize,
+    path: Vec<String>,
+    spans: HashMap<String, SourceSpan>,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn new(raw: &'a str) -> Self {
+        Self {
+            chars: raw.chars().peekable(),
+            line: 1,
+            col: 0,
+            path: Vec::new(),
+            spans: HashMap::new(),
+        }
+    }
+
+    /// The spans of the whole document, or `None` if it isn't valid JSON.
+    fn document(mut self) -> Option<HashMap<String, SourceSpan>> {
+        self.value()?;
+        self.skip_whitespace();
+        self.chars.peek().is_none().then_some(self.spans)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        self.skip_whitespace();
+        (self.bump()? == expected).then_some(())
+    }
+
+    fn here(&self) -> SourceSpan {
+        SourceSpan {
+            line: self.line,
+            col: self.col,
+        }
+    }
+
+    /// Descend into the member or element `segment`, which starts at `span`.
+    fn enter(&mut self, segment: String, span: SourceSpan) {
+        self.path.push(segment);
+        let mut pointer = String::new();
+        for segment in &self.path {
+            pointer.push('/');
+            escape_pointer_segment(segment, &mut pointer);
+        }
+        self.spans.insert(pointer, span);
+    }
+
+    fn value(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        match *self.chars.peek()? {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' => self.string().map(drop),
+            _ => {
+                // Numbers, `true`, `false` and `null`.
+                let mut any = false;
+                while self
+                    .chars
+                    .peek()
+                    .is_some_and(|c| !c.is_whitespace() && !matches!(c, ',' | '}' | ']'))
+                {
+                    self.bump();
+                    any = true;
+                }
+                any.then_some(())
+            }
+        }
+    }
+
+    fn object(&mut self) -> Option<()> {
+        self.bump();
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'}') {
+            self.bump();
+            return Some(());
+        }
+        loop {
+            self.skip_whitespace();
+            let start = self.here();
+            let key = self.string()?;
+            self.enter(key, start);
+            self.expect(':')?;
+            self.value()?;
+            self.path.pop();
+            self.skip_whitespace();
+            match self.bump()? {
+                ',' => continue,
+                '}' => return Some(()),
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<()> {
+        self.bump();
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&']') {
+            self.bump();
+            return Some(());
+        }
+        for index in 0.. {
+            self.skip_whitespace();
+            self.enter(index.to_string(), self.here());
+            self.value()?;
+            self.path.pop();
+            self.skip_whitespace();
+            match self.bump()? {
+                ',' => continue,
+                ']' => break,
+                _ => return None,
+            }
+        }
+        Some(())
+    }
+
+    /// A string literal, unescaped.
+    fn string(&mut self) -> Option<String> {
+        if self.bump()? != '"' {
+            return None;
+        }
+        let mut out = String::new();
+        loop {
+            match self.bump()? {
+                '"' => return Some(out),
+                '\\' => match self.bump()? {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'u' => {
+                        let hex: String = (0..4).filter_map(|_| self.bump()).collect();
+                        let code = u32::from_str_radix(&hex, 16).ok()?;
+                        out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                    }
+                    other => out.push(other),
+                },
+                c => out.push(c),
+            }
+        }
+    }
+}
+
 /// Count leading ASCII spaces (tabs count as 1 for simplicity).
 fn leading_spaces(line: &str) -> usize {
     line.len() - line.trim_start_matches([' ', '\t']).len()
@@ -351,6 +522,30 @@ components:
         );
     }
 
+    #[test]
+    fn parse_json_indexes_minified_documents_and_arrays() {
+        let json = r#"{"openapi":"3.0.0","tags":[{"name":"pets"},{"name":"a\"b"}],
+"paths":{"/pets":{"get":{"parameters":[{"name":"limit"}]}}}}"#;
+        let index = parse_spec(json).unwrap();
+        assert_eq!(index.syntax_name(), "JSON");
+        assert_eq!(
+            index.resolve("/openapi"),
+            Some(SourceSpan { line: 1, col: 1 })
+        );
+        assert_eq!(
+            index.resolve("/tags/1/name"),
+            Some(SourceSpan { line: 1, col: 44 })
+        );
+        assert_eq!(
+            index.resolve("/paths/~1pets/get/parameters/0"),
+            Some(SourceSpan { line: 2, col: 39 })
+        );
+        assert_eq!(
+            parse_spec("openapi: 3.0.0\n").unwrap().syntax_name(),
+            "YAML"
+        );
+    }
+
     #[test]
     fn parse_dotted_path_resolves() {
         let yaml = "\
@@ -402,6 +597,14 @@ paths:
         assert!(index.context_window(5, 2).is_none());
     }
 
+    #[test]
+    fn pointer_at_finds_innermost_node() {
+        let yaml = "paths:\n  /pets:\n    get:\n      summary: >\n        List\n        pets\n";
+        let index = parse_spec(yaml).unwrap();
+        assert_eq!(index.pointer_at(3), Some("/paths/~1pets/get"));
+        assert_eq!(index.pointer_at(6), Some("/paths/~1pets/get/summary"));
+    }
+
     #[test]
     fn unknown_pointer_returns_none() {
         let yaml = "openapi: 3.0.0\n";
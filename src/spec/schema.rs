@@ -0,0 +1,381 @@
+use lazyoav::openapi::lookup_ref;
+use serde_yaml::{Mapping, Value};
+
+/// Find the schema enclosing `pointer`: the deepest `components/schemas/<name>`
+/// or `schema` node on the path. Returns the schema's pointer and value.
+pub fn enclosing_schema<'a>(spec: &'a Value, pointer: &str) -> Option<(String, &'a Value)> {
+    let segments: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect();
+
+    let mut node = spec;
+    let mut found = None;
+    for (i, segment) in segments.iter().enumerate() {
+        node = match node {
+            Value::Sequence(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => node.get(segment.as_str())?,
+        };
+        let is_component = i == 2 && segments[0] == "components" && segments[1] == "schemas";
+        if is_component || segment == "schema" {
+            let prefix: Vec<String> = segments[..=i]
+                .iter()
+                .map(|s| s.replace('~', "~0").replace('/', "~1"))
+                .collect();
+            found = Some((format!("/{}", prefix.join("/")), node));
+        }
+    }
+    found
+}
+
+/// Expand local `$ref`s and merge `allOf` members into one schema.
+///
+/// A reference back to a schema already being expanded is kept as a `$ref`
+/// marked with `x-circular: true` instead of recursing forever.
+pub fn resolve_schema(spec: &Value, schema: &Value) -> Value {
+    resolve_with(spec, schema, &mut Vec::new())
+}
+
+fn resolve_with(spec: &Value, schema: &Value, stack: &mut Vec<String>) -> Value {
+    match schema {
+        Value::Mapping(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                let Some(target) = lookup_ref(spec, reference) else {
+                    return schema.clone();
+                };
+                if stack.iter().any(|r| r == reference) {
+                    let mut circular = Mapping::new();
+                    circular.insert("$ref".into(), reference.into());
+                    circular.insert("x-circular".into(), true.into());
+                    return Value::Mapping(circular);
+                }
+                stack.push(reference.to_string());
+                let resolved = resolve_with(spec, target, stack);
+                stack.pop();
+                return resolved;
+            }
+
+            let mut out = Mapping::new();
+            for (key, value) in map {
+                if key.as_str() != Some("allOf") {
+                    out.insert(key.clone(), resolve_with(spec, value, stack));
+                }
+            }
+            if let Some(parts) = map.get("allOf").and_then(Value::as_sequence) {
+                for part in parts {
+                    merge_into(&mut out, resolve_with(spec, part, stack));
+                }
+            }
+            Value::Mapping(out)
+        }
+        Value::Sequence(items) => Value::Sequence(
+            items
+                .iter()
+                .map(|item| resolve_with(spec, item, stack))
+                .collect(),
+        ),
+        _ => schema.clone(),
+    }
+}
+
+/// Merge an `allOf` member: properties and `required` are combined, other
+/// keywords are kept from whichever side set them first.
+fn merge_into(out: &mut Mapping, part: Value) {
+    let Value::Mapping(part) = part else {
+        return;
+    };
+    for (key, value) in part {
+        match (key.as_str(), out.get_mut(&key)) {
+            (Some("properties"), Some(Value::Mapping(existing))) => {
+                if let Value::Mapping(props) = value {
+                    existing.extend(props);
+                }
+            }
+            (Some("required"), Some(Value::Sequence(existing))) => {
+                for name in value.as_sequence().into_iter().flatten() {
+                    if !existing.contains(name) {
+                        existing.push(name.clone());
+                    }
+                }
+            }
+            (_, Some(_)) => {}
+            (_, None) => {
+                out.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Render a resolved schema as indented tree lines: one line per property,
+/// `*` marking required ones, nested objects and array items indented below.
+pub fn render_tree(schema: &Value) -> Vec<String> {
+    let mut lines = vec![describe(schema)];
+    render_children(schema, 1, &mut lines);
+    lines
+}
+
+fn render_children(schema: &Value, depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    if let Some(props) = schema.get("properties").and_then(Value::as_mapping) {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        for (name, prop) in props {
+            let name = name.as_str().unwrap_or_default();
+            let marker = if required.contains(&name) { "*" } else { "" };
+            lines.push(format!("{indent}{name}{marker}: {}", describe(prop)));
+            render_children(prop, depth + 1, lines);
+        }
+    }
+    if let Some(items) = schema.get("items") {
+        render_children(items, depth, lines);
+    }
+    for keyword in ["oneOf", "anyOf"] {
+        for variant in schema
+            .get(keyword)
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+        {
+            lines.push(format!("{indent}{keyword}: {}", describe(variant)));
+            render_children(variant, depth + 1, lines);
+        }
+    }
+}
+
+/// One-line summary of a schema: type, format, enum values, circular refs.
+fn describe(schema: &Value) -> String {
+    if schema.get("x-circular").and_then(Value::as_bool) == Some(true) {
+        let reference = schema.get("$ref").and_then(Value::as_str).unwrap_or("");
+        return format!("\u{21bb} {reference} (circular)");
+    }
+
+    let ty = schema.get("type").and_then(Value::as_str);
+    let mut out = match ty {
+        Some("array") => match schema.get("items") {
+            Some(items) => format!("array of {}", describe(items)),
+            None => "array".to_string(),
+        },
+        Some(ty) => ty.to_string(),
+        None if schema.get("properties").is_some() => "object".to_string(),
+        None if schema.get("oneOf").is_some() => "oneOf".to_string(),
+        None if schema.get("anyOf").is_some() => "anyOf".to_string(),
+        None => "any".to_string(),
+    };
+    if let Some(format) = schema.get("format").and_then(Value::as_str) {
+        out.push_str(&format!(" ({format})"));
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_sequence) {
+        let values: Vec<String> = values.iter().map(scalar).collect();
+        out.push_str(&format!(" enum [{}]", values.join(", ")));
+    }
+    if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        out.push_str(" nullable");
+    }
+    out
+}
+
+/// Build an example payload for a resolved schema.
+///
+/// Uses `example`, `default` or the first `enum` value when the schema has
+/// one, otherwise a placeholder matching the type and format. Every property
+/// is filled in; optional properties that would recurse are left out and
+/// required ones become `null`.
+pub fn sample_payload(schema: &Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+
+    if schema.get("x-circular").and_then(Value::as_bool) == Some(true) {
+        return Json::Null;
+    }
+    if let Some(value) = schema
+        .get("example")
+        .or_else(|| schema.get("default"))
+        .or_else(|| schema.get("enum").and_then(|e| e.as_sequence()?.first()))
+    {
+        return serde_json::to_value(value).unwrap_or(Json::Null);
+    }
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(first) = schema
+            .get(keyword)
+            .and_then(Value::as_sequence)
+            .and_then(|v| v.first())
+        {
+            return sample_payload(first);
+        }
+    }
+
+    let ty = schema.get("type").and_then(Value::as_str);
+    let format = schema.get("format").and_then(Value::as_str);
+    let number = |key: &str| schema.get(key).and_then(Value::as_f64);
+    match ty {
+        Some("string") => Json::String(
+            match format {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("time") => "00:00:00",
+                Some("email") => "user@example.com",
+                Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                Some("uri" | "url") => "https://example.com",
+                Some("hostname") => "example.com",
+                Some("ipv4") => "192.0.2.1",
+                Some("ipv6") => "2001:db8::1",
+                Some("byte") => "c3RyaW5n",
+                _ => "string",
+            }
+            .to_string(),
+        ),
+        Some("integer") => Json::from(number("minimum").unwrap_or(0.0) as i64),
+        Some("number") => Json::from(number("minimum").unwrap_or(0.0)),
+        Some("boolean") => Json::Bool(false),
+        Some("array") => Json::Array(
+            schema
+                .get("items")
+                .map(sample_payload)
+                .into_iter()
+                .collect(),
+        ),
+        Some("object") | None if schema.get("properties").is_some() => {
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            let mut object = serde_json::Map::new();
+            for (name, prop) in schema
+                .get("properties")
+                .and_then(Value::as_mapping)
+                .into_iter()
+                .flatten()
+            {
+                let Some(name) = name.as_str() else {
+                    continue;
+                };
+                let circular = prop.get("x-circular").and_then(Value::as_bool) == Some(true);
+                if circular && !required.contains(&name) {
+                    continue;
+                }
+                object.insert(name.to_string(), sample_payload(prop));
+            }
+            Json::Object(object)
+        }
+        Some("object") => Json::Object(serde_json::Map::new()),
+        _ => Json::Null,
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Dog'
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+        parent:
+          $ref: '#/components/schemas/Pet'
+    Dog:
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+        - type: object
+          required: [breed]
+          properties:
+            breed:
+              type: string
+              enum: [lab, pug]
+"##;
+
+    #[test]
+    fn finds_the_enclosing_schema() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let (pointer, _) = enclosing_schema(
+            &spec,
+            "/paths/~1pets/get/responses/200/content/application~1json/schema/$ref",
+        )
+        .unwrap();
+        assert_eq!(
+            pointer,
+            "/paths/~1pets/get/responses/200/content/application~1json/schema"
+        );
+
+        let (pointer, _) =
+            enclosing_schema(&spec, "/components/schemas/Dog/allOf/1/properties").unwrap();
+        assert_eq!(pointer, "/components/schemas/Dog");
+        assert!(enclosing_schema(&spec, "/paths/~1pets/get").is_none());
+    }
+
+    #[test]
+    fn resolves_refs_merges_all_of_and_stops_at_cycles() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let (_, dog) = enclosing_schema(&spec, "/components/schemas/Dog").unwrap();
+        let lines = render_tree(&resolve_schema(&spec, dog));
+        assert_eq!(
+            lines,
+            vec![
+                "object",
+                "  name*: string",
+                "  parent: \u{21bb} #/components/schemas/Pet (circular)",
+                "  breed*: string enum [lab, pug]",
+            ]
+        );
+    }
+
+    #[test]
+    fn sample_payload_honors_enums_formats_and_examples() {
+        let spec: Value = serde_yaml::from_str(SPEC).unwrap();
+        let (_, dog) = enclosing_schema(&spec, "/components/schemas/Dog").unwrap();
+        assert_eq!(
+            sample_payload(&resolve_schema(&spec, dog)),
+            serde_json::json!({ "name": "string", "breed": "lab" })
+        );
+
+        let schema: Value = serde_yaml::from_str(
+            r#"
+type: object
+properties:
+  id: { type: string, format: uuid }
+  count: { type: integer, minimum: 1 }
+  tags: { type: array, items: { type: string, example: red } }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sample_payload(&schema),
+            serde_json::json!({
+                "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                "count": 1,
+                "tags": ["red"],
+            })
+        );
+    }
+}
@@ -24,6 +24,7 @@ pub struct SpecIndex {
     spans: HashMap<String, SourceSpan>,
     raw_lines: Vec<String>,
     version: u64,
+    json: bool,
 }
 
 impl SpecIndex {
@@ -33,9 +34,26 @@ impl SpecIndex {
             spans,
             raw_lines,
             version,
+            json: false,
         }
     }
 
+    /// Mark the source as JSON rather than YAML.
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    /// Syntax to highlight the source with, going by its content rather than
+    /// the file extension.
+    pub fn syntax_name(&self) -> &'static str {
+        if self.json { "JSON" } else { "YAML" }
+    }
+
     /// Monotonic version that increments on each re-parse.
     pub fn version(&self) -> u64 {
         self.version
@@ -62,6 +80,16 @@ impl SpecIndex {
         })
     }
 
+    /// JSON pointer of the innermost node starting at or above 1-based
+    /// `line`, for showing where a line sits in the document.
+    pub fn pointer_at(&self, line: usize) -> Option<&str> {
+        self.spans
+            .iter()
+            .filter(|(_, span)| span.line <= line)
+            .max_by_key(|(pointer, span)| (span.line, pointer.len()))
+            .map(|(pointer, _)| pointer.as_str())
+    }
+
     pub fn line_count(&self) -> usize {
         self.raw_lines.len()
     }
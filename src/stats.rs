@@ -0,0 +1,290 @@
+//! Lint rule statistics across runs: which rules fire most, how many
+//! findings a run has on average, and how long findings take to get fixed.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::Write as _;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::baseline::Finding;
+
+/// The findings of each run, one JSON object per line, relative to the
+/// project. Like the score history the team can commit it.
+pub const HISTORY_FILE: &str = ".oav/rule-history.jsonl";
+
+/// Runs kept in [`HISTORY_FILE`]; appending drops the oldest beyond this.
+pub const MAX_RUNS: usize = 200;
+
+/// The lint findings of one run, with the Unix time it finished.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunFindings {
+    pub timestamp: u64,
+    pub findings: Vec<Finding>,
+}
+
+/// How one rule fared over the recorded runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleStats {
+    pub rule: String,
+    /// Findings over all runs.
+    pub findings: usize,
+    /// Runs with at least one finding.
+    pub runs: usize,
+    /// Findings per recorded run.
+    pub per_run: f64,
+    /// Findings in the latest run.
+    pub open: usize,
+    /// Findings that went away in a later run.
+    pub fixed: usize,
+    /// Mean seconds from a finding's first run to the run that no longer
+    /// had it.
+    pub mean_fix_secs: Option<u64>,
+}
+
+/// Rule statistics over the recorded runs, most frequent rule first.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Stats {
+    pub runs: usize,
+    /// Unix times of the first and last recorded run.
+    pub span: Option<(u64, u64)>,
+    pub per_run: f64,
+    pub mean_fix_secs: Option<u64>,
+    pub rules: Vec<RuleStats>,
+}
+
+/// A finding's identity across runs, as in the baseline: rule and spec
+/// path, or the message without a path.
+fn identity(finding: &Finding) -> (String, String) {
+    let location = finding.path.as_ref().unwrap_or(&finding.message);
+    (finding.rule.clone(), location.clone())
+}
+
+#[derive(Default)]
+struct Tally {
+    findings: usize,
+    runs: usize,
+    open: usize,
+    fix_secs: Vec<u64>,
+}
+
+fn mean(values: &[u64]) -> Option<u64> {
+    (!values.is_empty()).then(|| values.iter().sum::<u64>() / values.len() as u64)
+}
+
+/// Compute [`Stats`] over `history`, oldest run first.
+pub fn compute(history: &[RunFindings]) -> Stats {
+    let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+    // Identity -> run it was first seen in, for findings still present.
+    let mut open: HashMap<(String, String), u64> = HashMap::new();
+    let mut total = 0;
+
+    for run in history {
+        total += run.findings.len();
+        let mut fired = HashSet::new();
+        for finding in &run.findings {
+            let tally = tallies.entry(finding.rule.clone()).or_default();
+            tally.findings += 1;
+            if fired.insert(finding.rule.as_str()) {
+                tally.runs += 1;
+            }
+        }
+
+        let present: HashSet<(String, String)> = run.findings.iter().map(identity).collect();
+        open.retain(|key, since| {
+            let still_there = present.contains(key);
+            if !still_there && let Some(tally) = tallies.get_mut(&key.0) {
+                tally.fix_secs.push(run.timestamp.saturating_sub(*since));
+            }
+            still_there
+        });
+        for key in present {
+            open.entry(key).or_insert(run.timestamp);
+        }
+    }
+    if let Some(last) = history.last() {
+        for finding in &last.findings {
+            if let Some(tally) = tallies.get_mut(&finding.rule) {
+                tally.open += 1;
+            }
+        }
+    }
+
+    let runs = history.len();
+    let per_run = |n: usize| {
+        if runs == 0 {
+            0.0
+        } else {
+            n as f64 / runs as f64
+        }
+    };
+    let all_fixes: Vec<u64> = tallies.values().flat_map(|t| t.fix_secs.clone()).collect();
+    let mut rules: Vec<RuleStats> = tallies
+        .into_iter()
+        .map(|(rule, tally)| RuleStats {
+            rule,
+            findings: tally.findings,
+            runs: tally.runs,
+            per_run: per_run(tally.findings),
+            open: tally.open,
+            fixed: tally.fix_secs.len(),
+            mean_fix_secs: mean(&tally.fix_secs),
+        })
+        .collect();
+    rules.sort_by(|a, b| {
+        b.findings
+            .cmp(&a.findings)
+            .then_with(|| a.rule.cmp(&b.rule))
+    });
+
+    Stats {
+        runs,
+        span: history
+            .first()
+            .zip(history.last())
+            .map(|(first, last)| (first.timestamp, last.timestamp)),
+        per_run: per_run(total),
+        mean_fix_secs: mean(&all_fixes),
+        rules,
+    }
+}
+
+/// A duration in its two largest units, e.g. `3d 4h`, `2h 15m`, `40s`.
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    match (days, hours, mins) {
+        (0, 0, 0) => format!("{secs}s"),
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h {m}m"),
+        (d, h, _) => format!("{d}d {h}h"),
+    }
+}
+
+/// Read [`HISTORY_FILE`] from `root`, oldest first. Missing file means no
+/// history; unreadable lines are skipped.
+pub fn load_history(root: &Path) -> Result<Vec<RunFindings>> {
+    let path = root.join(HISTORY_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append `findings` to [`HISTORY_FILE`], stamped with the current time,
+/// and drop runs beyond [`MAX_RUNS`].
+pub fn append_history(root: &Path, findings: Vec<Finding>) -> Result<()> {
+    let path = root.join(HISTORY_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let run = RunFindings {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        findings,
+    };
+    let line = serde_json::to_string(&run).context("Failed to serialize findings")?;
+
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let kept = existing.lines().count();
+    if kept >= MAX_RUNS {
+        let mut lines: Vec<&str> = existing.lines().skip(kept + 1 - MAX_RUNS).collect();
+        lines.push(&line);
+        let mut content = lines.join("\n");
+        content.push('\n');
+        return crate::reports::write_atomic(&path, content.as_bytes());
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{line}").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(rule: &str, path: &str) -> Finding {
+        Finding {
+            rule: rule.into(),
+            path: Some(path.into()),
+            message: String::new(),
+        }
+    }
+
+    fn run(timestamp: u64, findings: &[(&str, &str)]) -> RunFindings {
+        RunFindings {
+            timestamp,
+            findings: findings.iter().map(|(r, p)| finding(r, p)).collect(),
+        }
+    }
+
+    #[test]
+    fn compute_counts_rules_and_times_fixes() {
+        let history = [
+            run(
+                0,
+                &[
+                    ("operation-description", "#/paths/~1pets/get"),
+                    ("operation-description", "#/paths/~1pets/post"),
+                    ("info-contact", "#/info"),
+                ],
+            ),
+            run(
+                3_600,
+                &[
+                    ("operation-description", "#/paths/~1pets/post"),
+                    ("info-contact", "#/info"),
+                ],
+            ),
+            run(7_200, &[]),
+        ];
+        let stats = compute(&history);
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.span, Some((0, 7_200)));
+        assert!((stats.per_run - 5.0 / 3.0).abs() < f64::EPSILON);
+
+        let description = &stats.rules[0];
+        assert_eq!(description.rule, "operation-description");
+        assert_eq!(
+            (description.findings, description.runs, description.open),
+            (3, 2, 0)
+        );
+        assert_eq!(description.fixed, 2);
+        // Fixed after one hour and after two.
+        assert_eq!(description.mean_fix_secs, Some(5_400));
+
+        let contact = &stats.rules[1];
+        assert_eq!((contact.findings, contact.open, contact.fixed), (2, 0, 1));
+        assert_eq!(contact.mean_fix_secs, Some(7_200));
+        assert_eq!(stats.mean_fix_secs, Some(6_000));
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        for _ in 0..MAX_RUNS + 2 {
+            append_history(dir.path(), vec![finding("r", "#/")]).unwrap();
+        }
+        assert_eq!(load_history(dir.path()).unwrap().len(), MAX_RUNS);
+    }
+
+    #[test]
+    fn format_duration_uses_two_units() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(600), "10m");
+        assert_eq!(format_duration(8_100), "2h 15m");
+        assert_eq!(format_duration(3 * 86_400 + 4 * 3_600 + 60), "3d 4h");
+    }
+}
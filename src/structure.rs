@@ -0,0 +1,457 @@
+//! The structure check that runs before any container: parse the spec, so
+//! a broken one fails in milliseconds with its location rather than after
+//! a linter container boots, and look over the fields the OpenAPI 3.0 and
+//! 3.1 schemas require. The look-over isn't a full schema validation, so
+//! what it finds is reported without failing the run; the linter has the
+//! final word.
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::openapi::{METHODS, escape, key};
+
+/// A syntax error or a violation of the OpenAPI schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructureFinding {
+    /// 1-based position of a syntax error; 0 for schema violations, which
+    /// carry a `pointer` instead.
+    pub line: usize,
+    pub col: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+    pub message: String,
+}
+
+impl StructureFinding {
+    /// Whether the spec couldn't be read or parsed at all, which stops the
+    /// run; schema violations don't.
+    pub fn is_syntax_error(&self) -> bool {
+        self.pointer.is_none()
+    }
+}
+
+const PATH_ITEM_FIELDS: &[&str] = &["$ref", "summary", "description", "servers", "parameters"];
+
+const PARAMETER_LOCATIONS: &[&str] = &["query", "header", "path", "cookie"];
+
+const COMPONENT_SECTIONS: &[&str] = &[
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "pathItems",
+];
+
+/// Everything structurally wrong with the spec in `source`: the syntax
+/// error that stops parsing, or else every schema violation found.
+pub fn check(source: &str) -> Vec<StructureFinding> {
+    let spec = if source.trim_start().starts_with('{') {
+        serde_json::from_str::<serde_json::Value>(source)
+            .map_err(|e| StructureFinding {
+                line: e.line(),
+                col: e.column(),
+                pointer: None,
+                message: format!("invalid JSON: {}", without_location(&e.to_string())),
+            })
+            .and_then(|json| {
+                serde_yaml::to_value(json).map_err(|e| StructureFinding {
+                    message: e.to_string(),
+                    ..Default::default()
+                })
+            })
+    } else {
+        serde_yaml::from_str::<Value>(source).map_err(|e| {
+            let (line, col) = e.location().map_or((0, 0), |l| (l.line(), l.column()));
+            StructureFinding {
+                line,
+                col,
+                pointer: None,
+                message: format!("invalid YAML: {}", without_location(&e.to_string())),
+            }
+        })
+    };
+    match spec {
+        Ok(spec) => validate(&spec),
+        Err(finding) => vec![finding],
+    }
+}
+
+/// Parser messages end in `at line 3 column 5`, which the finding already
+/// carries.
+fn without_location(message: &str) -> &str {
+    message.split(" at line ").next().unwrap_or(message)
+}
+
+/// Hold a parsed spec against the OpenAPI schema's required fields, types
+/// and allowed keys. Scalars a YAML parser reads as numbers, such as
+/// `openapi: 3.0` or `version: 1.0`, are taken as the text they were
+/// written as.
+pub fn validate(spec: &Value) -> Vec<StructureFinding> {
+    let mut v = Validator { found: Vec::new() };
+    if !spec.is_mapping() {
+        v.report("", "the document must be a mapping of OpenAPI fields");
+        return v.found;
+    }
+
+    let version = match spec.get("openapi") {
+        None if spec.get("swagger").is_some() => {
+            v.report(
+                "/swagger",
+                "Swagger 2.0 isn't supported; convert the spec to OpenAPI 3",
+            );
+            return v.found;
+        }
+        None => {
+            v.report("", "missing required field `openapi`");
+            return v.found;
+        }
+        Some(version @ (Value::String(_) | Value::Number(_))) => key(version),
+        Some(_) => {
+            v.report("/openapi", "must be a version such as \"3.0.3\"");
+            return v.found;
+        }
+    };
+    let v31 = version.starts_with("3.1");
+    if !["3.0", "3.1"].contains(&version.as_str())
+        && !version.starts_with("3.0.")
+        && !version.starts_with("3.1.")
+    {
+        v.report(
+            "/openapi",
+            &format!("unsupported OpenAPI version {version}; expected 3.0.x or 3.1.x"),
+        );
+    }
+
+    match spec.get("info") {
+        None => v.report("", "missing required field `info`"),
+        Some(info) if info.is_mapping() => {
+            v.required_string(info, "/info", "title");
+            v.required_string(info, "/info", "version");
+        }
+        Some(_) => v.report("/info", "must be a mapping"),
+    }
+
+    match spec.get("paths") {
+        Some(paths) => v.paths(paths, v31),
+        None if !v31 => v.report("", "missing required field `paths`"),
+        None if spec.get("components").is_none() && spec.get("webhooks").is_none() => v.report(
+            "",
+            "needs at least one of `paths`, `components` or `webhooks`",
+        ),
+        None => {}
+    }
+
+    if let Some(components) = spec.get("components") {
+        v.components(components);
+    }
+    if let Some(servers) = spec.get("servers") {
+        v.named_list(servers, "/servers", "url");
+    }
+    if let Some(tags) = spec.get("tags") {
+        v.named_list(tags, "/tags", "name");
+    }
+    v.found
+}
+
+struct Validator {
+    found: Vec<StructureFinding>,
+}
+
+impl Validator {
+    fn paths(&mut self, paths: &Value, v31: bool) {
+        let Some(paths) = paths.as_mapping() else {
+            self.report("/paths", "must be a mapping of paths");
+            return;
+        };
+        for (path, item) in paths {
+            let path = key(path);
+            let pointer = format!("/paths/{}", escape(&path));
+            if path.starts_with("x-") {
+                continue;
+            }
+            if !path.starts_with('/') {
+                self.report(&pointer, &format!("path `{path}` must start with `/`"));
+            }
+            let Some(item) = item.as_mapping() else {
+                self.report(&pointer, "must be a mapping of operations");
+                continue;
+            };
+            for (field, value) in item {
+                let field = key(field);
+                let child = format!("{pointer}/{}", escape(&field));
+                if METHODS.contains(&field.as_str()) {
+                    self.operation(value, &child, v31);
+                } else if field == "parameters" {
+                    self.parameters(value, &child);
+                } else if !PATH_ITEM_FIELDS.contains(&field.as_str()) && !field.starts_with("x-") {
+                    self.report(&child, &format!("unknown field `{field}` in a path item"));
+                }
+            }
+        }
+    }
+
+    fn operation(&mut self, op: &Value, pointer: &str, v31: bool) {
+        if !op.is_mapping() {
+            self.report(pointer, "must be a mapping");
+            return;
+        }
+        if let Some(parameters) = op.get("parameters") {
+            self.parameters(parameters, &format!("{pointer}/parameters"));
+        }
+        let pointer = format!("{pointer}/responses");
+        let Some(responses) = op.get("responses") else {
+            if !v31 {
+                self.report(&pointer, "missing required field `responses`");
+            }
+            return;
+        };
+        let Some(responses) = responses.as_mapping() else {
+            self.report(&pointer, "must be a mapping of status codes");
+            return;
+        };
+        if responses.is_empty() && !v31 {
+            self.report(&pointer, "must list at least one response");
+        }
+        for (code, response) in responses {
+            let code = key(code);
+            if code.starts_with("x-") {
+                continue;
+            }
+            let child = format!("{pointer}/{}", escape(&code));
+            if !is_status_code(&code) {
+                self.report(
+                    &child,
+                    &format!("`{code}` isn't a status code, a range like 4XX, or `default`"),
+                );
+            }
+            if response.get("$ref").is_none() {
+                self.required_string(response, &child, "description");
+            }
+        }
+    }
+
+    fn parameters(&mut self, parameters: &Value, pointer: &str) {
+        let Some(parameters) = parameters.as_sequence() else {
+            self.report(pointer, "must be a list of parameters");
+            return;
+        };
+        for (i, parameter) in parameters.iter().enumerate() {
+            let pointer = format!("{pointer}/{i}");
+            if !parameter.is_mapping() {
+                self.report(&pointer, "must be a mapping");
+                continue;
+            }
+            if parameter.get("$ref").is_some() {
+                continue;
+            }
+            self.required_string(parameter, &pointer, "name");
+            match parameter.get("in").and_then(Value::as_str) {
+                None => self.report(&pointer, "missing required field `in`"),
+                Some(location) if !PARAMETER_LOCATIONS.contains(&location) => self.report(
+                    &format!("{pointer}/in"),
+                    &format!("`in` must be one of query, header, path or cookie, not `{location}`"),
+                ),
+                Some("path")
+                    if parameter.get("required").and_then(Value::as_bool) != Some(true) =>
+                {
+                    self.report(&pointer, "path parameters must have `required: true`")
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn components(&mut self, components: &Value) {
+        let Some(components) = components.as_mapping() else {
+            self.report("/components", "must be a mapping");
+            return;
+        };
+        for (section, entries) in components {
+            let section = key(section);
+            let pointer = format!("/components/{}", escape(&section));
+            if section.starts_with("x-") {
+                continue;
+            }
+            if !COMPONENT_SECTIONS.contains(&section.as_str()) {
+                self.report(&pointer, &format!("unknown components section `{section}`"));
+                continue;
+            }
+            let Some(entries) = entries.as_mapping() else {
+                self.report(&pointer, "must be a mapping of named components");
+                continue;
+            };
+            for name in entries.keys().map(key) {
+                let valid = !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+                if !valid {
+                    self.report(
+                        &format!("{pointer}/{}", escape(&name)),
+                        &format!(
+                            "component name `{name}` may only use letters, digits, `.`, `_` and `-`"
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// A list of mappings that each need a string `field`, like servers
+    /// and tags.
+    fn named_list(&mut self, list: &Value, pointer: &str, field: &str) {
+        let Some(items) = list.as_sequence() else {
+            self.report(pointer, "must be a list");
+            return;
+        };
+        for (i, item) in items.iter().enumerate() {
+            self.required_string(item, &format!("{pointer}/{i}"), field);
+        }
+    }
+
+    /// A field that must be text. Numbers pass, since `version: 1.0` is
+    /// text to anyone but a YAML parser.
+    fn required_string(&mut self, node: &Value, pointer: &str, field: &str) {
+        match node.get(field) {
+            Some(Value::String(_) | Value::Number(_)) => {}
+            None => self.report(pointer, &format!("missing required field `{field}`")),
+            Some(_) => self.report(
+                &format!("{pointer}/{field}"),
+                &format!("`{field}` must be a string"),
+            ),
+        }
+    }
+
+    fn report(&mut self, pointer: &str, message: &str) {
+        self.found.push(StructureFinding {
+            line: 0,
+            col: 0,
+            pointer: Some(pointer.to_string()),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// `default`, a range like `4XX`, or a three-digit code from 100 to 599.
+fn is_status_code(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    code == "default"
+        || (bytes.len() == 3
+            && (b'1'..=b'5').contains(&bytes[0])
+            && (code[1..] == *"XX" || bytes[1..].iter().all(u8::is_ascii_digit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(source: &str) -> Vec<(String, String)> {
+        check(source)
+            .into_iter()
+            .map(|f| (f.pointer.unwrap_or_default(), f.message))
+            .collect()
+    }
+
+    #[test]
+    fn check_reports_syntax_errors_with_their_position() {
+        let found = check("openapi: 3.0.3\ninfo:\n  title: [unclosed\n");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].message.starts_with("invalid YAML"), "{found:?}");
+        assert!(found[0].line >= 3, "{found:?}");
+
+        let found = check("{\n  \"openapi\": \"3.0.3\",\n  \"info\": }\n");
+        assert_eq!((found[0].line, found[0].col), (3, 11));
+        assert!(found[0].message.starts_with("invalid JSON"));
+    }
+
+    #[test]
+    fn check_holds_the_spec_against_the_schema() {
+        let found = problems(
+            r#"
+openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0
+servers:
+  - description: no url
+paths:
+  pets:
+    get:
+      parameters:
+        - name: id
+          in: path
+        - name: q
+          in: body
+      responses:
+        "200":
+          content: {}
+        "999":
+          description: nope
+    post: {}
+components:
+  schemas:
+    Pet Name:
+      type: string
+  models: {}
+"#,
+        );
+        let expect = |pointer: &str, message: &str| (pointer.to_string(), message.to_string());
+        assert_eq!(
+            found,
+            [
+                expect("/paths/pets", "path `pets` must start with `/`"),
+                expect(
+                    "/paths/pets/get/parameters/0",
+                    "path parameters must have `required: true`"
+                ),
+                expect(
+                    "/paths/pets/get/parameters/1/in",
+                    "`in` must be one of query, header, path or cookie, not `body`"
+                ),
+                expect(
+                    "/paths/pets/get/responses/200",
+                    "missing required field `description`"
+                ),
+                expect(
+                    "/paths/pets/get/responses/999",
+                    "`999` isn't a status code, a range like 4XX, or `default`"
+                ),
+                expect(
+                    "/paths/pets/post/responses",
+                    "missing required field `responses`"
+                ),
+                expect(
+                    "/components/schemas/Pet Name",
+                    "component name `Pet Name` may only use letters, digits, `.`, `_` and `-`"
+                ),
+                expect("/components/models", "unknown components section `models`"),
+                expect("/servers/0", "missing required field `url`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_accepts_valid_specs() {
+        let spec = "openapi: 3.1.0\ninfo:\n  title: Pets\n  version: \"1.0\"\ncomponents: {}\n";
+        assert!(check(spec).is_empty());
+        let json = r#"{"openapi": "3.0.3", "info": {"title": "t", "version": "1"}, "paths": {}}"#;
+        assert!(check(json).is_empty());
+        let unquoted =
+            "openapi: 3.0\ninfo:\n  title: Pets\n  version: 1.0\npaths: {}\nx-owner: me\n";
+        assert!(check(unquoted).is_empty());
+        let vendor = "openapi: 3.1.0\ninfo: {title: t, version: '1'}\npaths: {}\n$schema: x\n";
+        assert!(check(vendor).is_empty());
+        assert_eq!(
+            problems("swagger: \"2.0\"\n"),
+            [(
+                "/swagger".to_string(),
+                "Swagger 2.0 isn't supported; convert the spec to OpenAPI 3".to_string()
+            )]
+        );
+    }
+}
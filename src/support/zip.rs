@@ -0,0 +1,230 @@
+// Minimal zip writer: deflated entries, UTF-8 names, no zip64.
+
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR: u32 = 0x0605_4b50;
+const VERSION: u16 = 20;
+const FLAG_UTF8: u16 = 0x0800;
+const METHOD_DEFLATE: u16 = 8;
+
+struct Entry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Writes a zip archive entry by entry. Call [`ZipWriter::finish`] to write
+/// the central directory; without it the archive is unreadable.
+pub struct ZipWriter<W: Write> {
+    out: W,
+    offset: u32,
+    entries: Vec<Entry>,
+    time: u16,
+    date: u16,
+}
+
+impl<W: Write> ZipWriter<W> {
+    pub fn new(out: W) -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let (time, date) = dos_datetime(secs);
+        Self {
+            out,
+            offset: 0,
+            entries: Vec::new(),
+            time,
+            date,
+        }
+    }
+
+    pub fn add(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        let compressed = encoder.finish()?;
+
+        let entry = Entry {
+            name: name.to_string(),
+            crc: crc32fast::hash(data),
+            compressed: to_u32(compressed.len())?,
+            size: to_u32(data.len())?,
+            offset: self.offset,
+        };
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        put32(&mut header, LOCAL_HEADER);
+        put16(&mut header, VERSION);
+        put16(&mut header, FLAG_UTF8);
+        put16(&mut header, METHOD_DEFLATE);
+        put16(&mut header, self.time);
+        put16(&mut header, self.date);
+        put32(&mut header, entry.crc);
+        put32(&mut header, entry.compressed);
+        put32(&mut header, entry.size);
+        put16(&mut header, to_u16(name.len())?);
+        put16(&mut header, 0);
+        header.extend_from_slice(name.as_bytes());
+
+        self.write(&header)?;
+        self.write(&compressed)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        let start = self.offset;
+        let mut central = Vec::new();
+        for entry in &self.entries {
+            put32(&mut central, CENTRAL_HEADER);
+            put16(&mut central, VERSION);
+            put16(&mut central, VERSION);
+            put16(&mut central, FLAG_UTF8);
+            put16(&mut central, METHOD_DEFLATE);
+            put16(&mut central, self.time);
+            put16(&mut central, self.date);
+            put32(&mut central, entry.crc);
+            put32(&mut central, entry.compressed);
+            put32(&mut central, entry.size);
+            put16(&mut central, to_u16(entry.name.len())?);
+            put16(&mut central, 0); // extra field
+            put16(&mut central, 0); // comment
+            put16(&mut central, 0); // disk number
+            put16(&mut central, 0); // internal attributes
+            put32(&mut central, 0); // external attributes
+            put32(&mut central, entry.offset);
+            central.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let count = to_u16(self.entries.len())?;
+        let mut end = Vec::with_capacity(22);
+        put32(&mut end, END_OF_CENTRAL_DIR);
+        put16(&mut end, 0);
+        put16(&mut end, 0);
+        put16(&mut end, count);
+        put16(&mut end, count);
+        put32(&mut end, to_u32(central.len())?);
+        put32(&mut end, start);
+        put16(&mut end, 0);
+
+        self.write(&central)?;
+        self.write(&end)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.out.write_all(bytes)?;
+        self.offset = self
+            .offset
+            .checked_add(to_u32(bytes.len())?)
+            .ok_or_else(too_large)?;
+        Ok(())
+    }
+}
+
+fn put16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn to_u16(n: usize) -> io::Result<u16> {
+    u16::try_from(n).map_err(|_| too_large())
+}
+
+fn to_u32(n: usize) -> io::Result<u32> {
+    u32::try_from(n).map_err(|_| too_large())
+}
+
+fn too_large() -> io::Error {
+    io::Error::other("archive too large for zip without zip64")
+}
+
+/// MS-DOS `(time, date)` for a Unix timestamp (UTC, 2-second resolution).
+/// Dates before 1980 can't be represented and clamp to 1980-01-01.
+fn dos_datetime(secs: u64) -> (u16, u16) {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    if year < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let time = ((rem / 3600) << 11) | (((rem % 3600) / 60) << 5) | ((rem % 60) / 2);
+    let date = (((year - 1980).min(127)) << 9) | (month << 5) | day;
+    (time as u16, date as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn u16_at(buf: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([buf[at], buf[at + 1]])
+    }
+
+    fn u32_at(buf: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn archive_layout_is_readable() {
+        let mut zip = ZipWriter::new(Vec::new());
+        zip.add("a.txt", b"hello hello hello").unwrap();
+        zip.add("dir/b.txt", b"").unwrap();
+        let buf = zip.finish().unwrap();
+
+        assert_eq!(u32_at(&buf, 0), LOCAL_HEADER);
+        let end = buf.len() - 22;
+        assert_eq!(u32_at(&buf, end), END_OF_CENTRAL_DIR);
+        assert_eq!(u16_at(&buf, end + 10), 2);
+
+        // Central directory points back at the second local header.
+        let central = u32_at(&buf, end + 16) as usize;
+        let name_len = u16_at(&buf, central + 28) as usize;
+        let second = central + 46 + name_len;
+        assert_eq!(u32_at(&buf, second), CENTRAL_HEADER);
+        let offset = u32_at(&buf, second + 42) as usize;
+        assert_eq!(&buf[offset + 30..offset + 39], b"dir/b.txt");
+
+        // First entry inflates back to the input.
+        let size = u32_at(&buf, 18) as usize;
+        let data = &buf[30 + 5..30 + 5 + size];
+        let mut out = String::new();
+        flate2::read::DeflateDecoder::new(data)
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, "hello hello hello");
+        assert_eq!(u32_at(&buf, 14), crc32fast::hash(b"hello hello hello"));
+    }
+
+    #[test]
+    fn dos_datetime_known_values() {
+        // 2024-02-29 13:45:30 UTC
+        let (time, date) = dos_datetime(1_709_214_330);
+        assert_eq!(time, (13 << 11) | (45 << 5) | 15);
+        assert_eq!(date, (44 << 9) | (2 << 5) | 29);
+        assert_eq!(dos_datetime(0), (0, (1 << 5) | 1));
+    }
+}
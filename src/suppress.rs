@@ -0,0 +1,121 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Accepted lint findings, next to `.oavc` in the project root.
+pub const IGNORE_FILE: &str = ".oav-ignore";
+
+const HEADER: &str = "\
+# Lint findings accepted by the team; lazyoav hides them and doesn't fail
+# the lint phase over them. Remove an entry to report it again. Leaving out
+# `path` accepts the rule everywhere.
+";
+
+/// A lint rule accepted at one spec location, or everywhere.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    pub rule: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl Suppression {
+    pub fn matches(&self, rule: &str, path: Option<&str>) -> bool {
+        self.rule == rule && (self.path.is_none() || self.path.as_deref() == path)
+    }
+}
+
+/// Read [`IGNORE_FILE`] from `root`. A missing file means no suppressions.
+pub fn load(root: &Path) -> Result<Vec<Suppression>> {
+    let path = root.join(IGNORE_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let list: Option<Vec<Suppression>> = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(list.unwrap_or_default())
+}
+
+/// Append `suppression` to [`IGNORE_FILE`], creating it if needed.
+/// Existing entries and comments are left as they are. Returns `false` if
+/// an identical entry is already there.
+pub fn add(root: &Path, suppression: &Suppression) -> Result<bool> {
+    let existing = load(root)?;
+    if existing.contains(suppression) {
+        return Ok(false);
+    }
+
+    let path = root.join(IGNORE_FILE);
+    let mut entry = serde_yaml::to_string(&[suppression]).context("Failed to serialize entry")?;
+    if !path.exists() {
+        entry.insert_str(0, HEADER);
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(entry.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suppression(rule: &str, path: Option<&str>) -> Suppression {
+        Suppression {
+            rule: rule.into(),
+            path: path.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn matches_rule_and_path() {
+        let at = suppression("operation-description", Some("paths./orders.get"));
+        assert!(at.matches("operation-description", Some("paths./orders.get")));
+        assert!(!at.matches("operation-description", Some("paths./orders.post")));
+        assert!(!at.matches("operation-tags", Some("paths./orders.get")));
+
+        let everywhere = suppression("operation-description", None);
+        assert!(everywhere.matches("operation-description", None));
+        assert!(everywhere.matches("operation-description", Some("paths./x.get")));
+    }
+
+    #[test]
+    fn add_appends_and_skips_duplicates() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load(tmp.path()).unwrap().is_empty());
+
+        let a = suppression("info-contact", None);
+        let b = suppression("operation-tags", Some("paths./a.get"));
+        assert!(add(tmp.path(), &a).unwrap());
+        assert!(add(tmp.path(), &b).unwrap());
+        assert!(!add(tmp.path(), &a).unwrap());
+
+        assert_eq!(load(tmp.path()).unwrap(), vec![a, b]);
+        let content = fs::read_to_string(tmp.path().join(IGNORE_FILE)).unwrap();
+        assert!(content.starts_with("# Lint findings accepted"));
+        assert_eq!(content.matches("# Lint findings").count(), 1);
+    }
+
+    #[test]
+    fn comment_only_file_is_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(IGNORE_FILE), HEADER).unwrap();
+        assert!(load(tmp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn malformed_file_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(IGNORE_FILE), "rule: [unclosed\n").unwrap();
+        assert!(load(tmp.path()).is_err());
+    }
+}
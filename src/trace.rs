@@ -0,0 +1,148 @@
+//! lazyoav's own debug log in `.oav/lazyoav.log`: pipeline events, docker
+//! invocations, how findings were parsed and mapped onto the spec, and UI
+//! actions. Off unless `--debug-log` or `LAZYOAV_LOG` turns it on, so a bug
+//! report about a hung run or a finding on the wrong line can carry a trace.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+
+/// Relative to the project. Each session replaces the previous log.
+pub const LOG_FILE: &str = ".oav/lazyoav.log";
+
+/// Environment variable selecting targets like `--debug-log=...` does.
+pub const ENV_VAR: &str = "LAZYOAV_LOG";
+
+/// What a log line is about; the log can be narrowed to some of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Pipeline,
+    Docker,
+    /// Parsing linter output and mapping findings onto spec lines.
+    Parse,
+    /// Keys pressed and the actions they resolved to.
+    Ui,
+}
+
+impl Target {
+    pub const ALL: &[Target] = &[Self::Pipeline, Self::Docker, Self::Parse, Self::Ui];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pipeline => "pipeline",
+            Self::Docker => "docker",
+            Self::Parse => "parse",
+            Self::Ui => "ui",
+        }
+    }
+}
+
+/// Parse a comma-separated list of targets, e.g. `docker,pipeline`.
+/// `all`, `debug`, `trace`, `1` or an empty filter select every target.
+pub fn parse_filter(filter: &str) -> Result<Vec<Target>> {
+    let mut targets = Vec::new();
+    for name in filter.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match name.to_ascii_lowercase().as_str() {
+            "all" | "debug" | "trace" | "1" => return Ok(Target::ALL.to_vec()),
+            other => match Target::ALL.iter().find(|t| t.as_str() == other) {
+                Some(&target) if !targets.contains(&target) => targets.push(target),
+                Some(_) => {}
+                None => bail!(
+                    "Unknown debug log target '{name}' (expected all, pipeline, docker, parse or ui)"
+                ),
+            },
+        }
+    }
+    if targets.is_empty() {
+        return Ok(Target::ALL.to_vec());
+    }
+    Ok(targets)
+}
+
+struct Logger {
+    file: Mutex<File>,
+    targets: Vec<Target>,
+    start: Instant,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Start logging `targets` to [`LOG_FILE`] under `root`, replacing the
+/// previous session's log. Only the first call in a process takes effect.
+pub fn init(root: &Path, targets: Vec<Target>) -> Result<PathBuf> {
+    let path = root.join(LOG_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut file =
+        File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let names: Vec<&str> = targets.iter().map(|t| t.as_str()).collect();
+    writeln!(
+        file,
+        "lazyoav {} in {}, logging {}",
+        env!("CARGO_PKG_VERSION"),
+        root.display(),
+        names.join(", ")
+    )?;
+    let _ = LOGGER.set(Logger {
+        file: Mutex::new(file),
+        targets,
+        start: Instant::now(),
+    });
+    Ok(path)
+}
+
+/// Whether lines for `target` are written; check before building costly
+/// messages.
+pub fn enabled(target: Target) -> bool {
+    LOGGER.get().is_some_and(|l| l.targets.contains(&target))
+}
+
+/// Append a line for `target`. Pass `format_args!` so nothing is formatted
+/// while logging is off. Write errors are ignored.
+pub fn log(target: Target, message: impl fmt::Display) {
+    let Some(logger) = LOGGER.get().filter(|l| l.targets.contains(&target)) else {
+        return;
+    };
+    let line = format_line(logger.start.elapsed(), target, &message);
+    if let Ok(mut file) = logger.file.lock() {
+        let _ = file.write_all(line.as_bytes());
+    }
+}
+
+/// `[  12.345s] docker   message`, newline-terminated.
+fn format_line(elapsed: Duration, target: Target, message: &dyn fmt::Display) -> String {
+    format!(
+        "[{:>8.3}s] {:<8} {message}\n",
+        elapsed.as_secs_f64(),
+        target.as_str()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_selects_targets() {
+        assert_eq!(parse_filter("").unwrap(), Target::ALL);
+        assert_eq!(parse_filter("debug").unwrap(), Target::ALL);
+        assert_eq!(
+            parse_filter("Docker, pipeline,docker").unwrap(),
+            vec![Target::Docker, Target::Pipeline]
+        );
+        assert!(parse_filter("docker,network").is_err());
+    }
+
+    #[test]
+    fn lines_carry_elapsed_time_and_target() {
+        let line = format_line(Duration::from_millis(12_345), Target::Ui, &"key r");
+        assert_eq!(line, "[  12.345s] ui       key r\n");
+    }
+}
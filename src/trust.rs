@@ -0,0 +1,149 @@
+//! Projects the user trusts to run commands on this machine.
+//!
+//! A project's `.oavc` can name `hooks`, shell commands run on the host
+//! around the pipeline's steps, and an `automation` script, and
+//! `.oav/plugins/` can hold plugins that are host commands too. A cloned
+//! repository shouldn't get to run them just by being opened, so they stay
+//! off until the user trusts the project once. Trusted project directories are listed, one per line, in
+//! `trusted-projects` next to the user config.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::{CONFIG_FILE, user_config_path};
+use crate::plugins::PLUGINS_DIR;
+
+/// Keys of a project's `.oavc` that run commands on the host.
+pub const HOST_COMMAND_KEYS: &[&str] = &["hooks", "automation"];
+
+const TRUST_FILE: &str = "trusted-projects";
+
+/// Where the trusted projects are listed, beside the user config.
+pub fn trust_file() -> Option<PathBuf> {
+    Some(user_config_path()?.parent()?.join(TRUST_FILE))
+}
+
+/// Whether the user trusts the project at `root`.
+pub fn is_trusted(root: &Path) -> bool {
+    trust_file().is_some_and(|file| is_trusted_in(&file, root))
+}
+
+/// Trust the project at `root` from now on.
+pub fn trust(root: &Path) -> Result<()> {
+    let file = trust_file().context("No config directory to record trust in")?;
+    trust_in(&file, root)
+}
+
+fn is_trusted_in(file: &Path, root: &Path) -> bool {
+    let Ok(listed) = fs::read_to_string(file) else {
+        return false;
+    };
+    let root = canonical(root);
+    listed.lines().any(|line| Path::new(line) == root)
+}
+
+fn trust_in(file: &Path, root: &Path) -> Result<()> {
+    if is_trusted_in(file, root) {
+        return Ok(());
+    }
+    let mut listed = fs::read_to_string(file).unwrap_or_default();
+    if !listed.is_empty() && !listed.ends_with('\n') {
+        listed.push('\n');
+    }
+    listed.push_str(&canonical(root).to_string_lossy());
+    listed.push('\n');
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(file, listed).with_context(|| format!("Failed to write {}", file.display()))
+}
+
+fn canonical(root: &Path) -> PathBuf {
+    root.canonicalize().unwrap_or_else(|_| root.to_path_buf())
+}
+
+/// What the project at `root` would run on the host once trusted, e.g.
+/// `2 hooks` and `1 plugin`; empty when it runs nothing.
+pub fn host_commands(root: &Path) -> Vec<String> {
+    let mut found = Vec::new();
+    let project: serde_yaml::Value = fs::read_to_string(root.join(CONFIG_FILE))
+        .ok()
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or_default();
+    let hooks = project
+        .get("hooks")
+        .and_then(|h| h.as_sequence())
+        .map_or(0, Vec::len);
+    let plugins = fs::read_dir(root.join(PLUGINS_DIR))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yaml" | "yml")))
+        .count();
+    for (count, what) in [(hooks, "hook"), (plugins, "plugin")] {
+        match count {
+            0 => {}
+            1 => found.push(format!("1 {what}")),
+            n => found.push(format!("{n} {what}s")),
+        }
+    }
+    if project.get("automation").is_some() {
+        found.push("an automation script".to_string());
+    }
+    found
+}
+
+/// Drop the host command keys from a project's `.oavc` layer.
+pub(crate) fn strip_host_commands(layer: &mut serde_yaml::Value) {
+    if let serde_yaml::Value::Mapping(map) = layer {
+        for key in HOST_COMMAND_KEYS {
+            map.remove(*key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trust_is_recorded_once_per_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("lazyoav").join(TRUST_FILE);
+        let project = tmp.path().join("api");
+        fs::create_dir_all(&project).unwrap();
+
+        assert!(!is_trusted_in(&file, &project));
+        trust_in(&file, &project).unwrap();
+        trust_in(&file, &project.join(".")).unwrap();
+        assert!(is_trusted_in(&file, &project));
+        assert!(!is_trusted_in(&file, tmp.path()));
+        assert_eq!(fs::read_to_string(&file).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn host_commands_lists_what_would_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(host_commands(tmp.path()).is_empty());
+
+        fs::write(
+            tmp.path().join(CONFIG_FILE),
+            "hooks:\n  - {phase: lint, when: after, run: make}\n  - {phase: lint, when: before, run: make}\n",
+        )
+        .unwrap();
+        assert_eq!(host_commands(tmp.path()), ["2 hooks"]);
+
+        fs::create_dir_all(tmp.path().join(PLUGINS_DIR)).unwrap();
+        fs::write(tmp.path().join(PLUGINS_DIR).join("naming.yaml"), "").unwrap();
+        fs::write(tmp.path().join(PLUGINS_DIR).join("README.md"), "").unwrap();
+        assert_eq!(host_commands(tmp.path()), ["2 hooks", "1 plugin"]);
+
+        fs::write(tmp.path().join(CONFIG_FILE), "automation: ci.rhai\n").unwrap();
+        assert_eq!(
+            host_commands(tmp.path()),
+            ["1 plugin", "an automation script"]
+        );
+    }
+}
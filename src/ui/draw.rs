@@ -1,16 +1,26 @@
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
+use crate::app::tour::TourTarget;
 use crate::app::{App, BrowserPanel, Panel, ScreenMode, StatusLevel, ViewMode};
 use lazyoav::keys::KeyAction;
 
 use super::overlay;
 use super::panels;
+use super::style;
+use super::width;
 
 pub fn draw(frame: &mut Frame, app: &App) {
+    draw_screen(frame, app);
+    if app.accessible {
+        style::strip_colors(frame.buffer_mut());
+    }
+}
+
+fn draw_screen(frame: &mut Frame, app: &App) {
     let size = frame.area();
 
     // Reserve 1 line at the bottom for the status bar.
@@ -34,7 +44,130 @@ pub fn draw(frame: &mut Frame, app: &App) {
     if app.view_mode == ViewMode::Validator
         && let Some(ref proposal) = app.fix_proposal
     {
-        overlay::draw_fix_overlay(frame, proposal, size);
+        overlay::draw_fix_overlay(frame, app, proposal, size);
+        return;
+    }
+
+    if app.view_mode == ViewMode::Validator
+        && let Some(ref edit) = app.inline_edit
+    {
+        overlay::draw_inline_edit(frame, edit, size);
+        return;
+    }
+
+    if let Some(prompt) = app.timeout_prompts.first() {
+        overlay::draw_timeout_prompt(frame, prompt, app.timeout_extension(), size);
+        return;
+    }
+
+    if let Some(ref prompt) = app.clean_prompt {
+        overlay::draw_clean_prompt(frame, prompt, size);
+        return;
+    }
+
+    if let Some(ref prompt) = app.migrate_prompt {
+        overlay::draw_migrate_prompt(frame, prompt, size);
+        return;
+    }
+
+    if let Some(ref found) = app.leftover_prompt {
+        overlay::draw_leftover_prompt(frame, found, size);
+        return;
+    }
+
+    if let Some(ref prompt) = app.trust_prompt {
+        overlay::draw_trust_prompt(frame, prompt, size);
+        return;
+    }
+
+    if let Some(ref picker) = app.generator_picker {
+        overlay::draw_generator_picker(frame, picker, app.accessible, size);
+        return;
+    }
+
+    if let Some(ref wizard) = app.init_wizard {
+        overlay::draw_init_wizard(frame, wizard, app.accessible, size);
+        return;
+    }
+
+    if app.show_diagnostics {
+        overlay::draw_config_diagnostics(frame, app, size);
+        return;
+    }
+
+    if let Some(ref view) = app.changelog {
+        overlay::draw_changelog(frame, view, app.accessible, size);
+        return;
+    }
+
+    if let Some(ref view) = app.schema_view {
+        overlay::draw_schema(frame, view, size);
+        return;
+    }
+
+    if let Some(ref view) = app.ref_graph {
+        overlay::draw_ref_graph(frame, view, size);
+        return;
+    }
+
+    if let Some(ref view) = app.comparison {
+        overlay::draw_comparison(frame, view, app.accessible, size);
+        return;
+    }
+
+    if let Some(ref view) = app.run_history {
+        overlay::draw_run_history(frame, view, app.accessible, size);
+        return;
+    }
+
+    if let Some(ref view) = app.dry_run {
+        overlay::draw_dry_run(frame, view, size);
+        return;
+    }
+
+    if let Some(ref view) = app.outline {
+        overlay::draw_outline(frame, view, app.accessible, size);
+        return;
+    }
+
+    if app.show_matrix
+        && let Some(ref view) = app.matrix
+    {
+        overlay::draw_matrix(frame, view, app.accessible, size);
+        return;
+    }
+
+    if let Some(selected) = app.project_switcher {
+        overlay::draw_projects(frame, app, selected, size);
+        return;
+    }
+
+    if app.show_workspace
+        && let Some(ref view) = app.workspace
+    {
+        overlay::draw_workspace(frame, view, app.accessible, size);
+        return;
+    }
+
+    if let Some(ref view) = app.rule_stats {
+        overlay::draw_rule_stats(frame, view, size);
+        return;
+    }
+
+    if app.show_score {
+        overlay::draw_score(frame, app, size);
+        return;
+    }
+
+    if let Some(ref tour) = app.tour {
+        let highlight = match tour.step().target {
+            TourTarget::Panel(panel) if app.view_mode == ViewMode::Validator => {
+                Some(panel_areas(app, outer[0])[panel.index()])
+            }
+            TourTarget::StatusBar => Some(outer[1]),
+            _ => None,
+        };
+        overlay::draw_tour(frame, tour, &app.keymap, highlight, size);
         return;
     }
 
@@ -43,7 +176,21 @@ pub fn draw(frame: &mut Frame, app: &App) {
     }
 }
 
-fn draw_panels(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+fn draw_panels(frame: &mut Frame, app: &App, area: Rect) {
+    let [phases, errors, detail, spec_context] = panel_areas(app, area);
+    panels::draw_phases(frame, app, phases, app.focused_panel == Panel::Phases);
+    panels::draw_errors(frame, app, errors, app.focused_panel == Panel::Errors);
+    panels::draw_detail(frame, app, detail, app.focused_panel == Panel::Detail);
+    panels::draw_spec_context(
+        frame,
+        app,
+        spec_context,
+        app.focused_panel == Panel::SpecContext,
+    );
+}
+
+/// Validator panel areas, indexed by [`Panel::index`].
+fn panel_areas(app: &App, area: Rect) -> [Rect; 4] {
     // Outer split: left panels | right panels.
     let horizontal = Layout::default()
         .direction(Direction::Horizontal)
@@ -66,21 +213,40 @@ fn draw_panels(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(horizontal[1]);
 
-    panels::draw_phases(frame, app, left[0], app.focused_panel == Panel::Phases);
-    panels::draw_errors(frame, app, left[1], app.focused_panel == Panel::Errors);
-    panels::draw_detail(frame, app, right[0], app.focused_panel == Panel::Detail);
-    panels::draw_spec_context(
-        frame,
-        app,
-        right[1],
-        app.focused_panel == Panel::SpecContext,
-    );
+    [left[0], left[1], right[0], right[1]]
 }
 
-fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    // Spinner occupies fixed width on the right when validating.
-    const SPINNER_WIDTH: u16 = 16; // " ⠋ Validating "
-    let spinner_len = if app.validating { SPINNER_WIDTH } else { 0 };
+fn draw_bottom_bar(frame: &mut Frame, app: &App, area: Rect) {
+    // A spinner on the right when validating, widened to a progress bar once
+    // the pipeline reports its progress, after the remaining time once
+    // earlier runs allow an estimate.
+    let spinner = app.validating.then(|| {
+        const BRAILLE: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+        const ASCII: [char; 4] = ['|', '/', '-', '\\'];
+        let frames: &[char] = if app.accessible { &ASCII } else { &BRAILLE };
+        let frame_char = frames[app.tick / 3 % frames.len()];
+        let eta = app
+            .eta
+            .as_ref()
+            .and_then(|eta| eta.label(std::time::Instant::now()));
+        match (app.progress, eta) {
+            (Some((done, total)), Some(eta)) if total > 0 => {
+                format!(
+                    " {frame_char} {eta}  {} ",
+                    progress_bar(done, total, 10, app.accessible)
+                )
+            }
+            (Some((done, total)), None) if total > 0 => {
+                format!(
+                    " {frame_char} {} ",
+                    progress_bar(done, total, 10, app.accessible)
+                )
+            }
+            (_, Some(eta)) => format!(" {frame_char} {eta} "),
+            (_, None) => format!(" {frame_char} Validating "),
+        }
+    });
+    let spinner_len = spinner.as_ref().map_or(0, |text| width::width(text) as u16);
 
     let bar_layout = Layout::default()
         .direction(Direction::Horizontal)
@@ -105,6 +271,21 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         km.label(KeyAction::PrevGenerator),
         km.label(KeyAction::NextGenerator)
     );
+    let hunk_label = format!(
+        "{}/{}",
+        km.label(KeyAction::NextHunk),
+        km.label(KeyAction::PrevHunk)
+    );
+    let changed_file_label = format!(
+        "{}/{}",
+        km.label(KeyAction::NextChangedFile),
+        km.label(KeyAction::PrevChangedFile)
+    );
+    let finding_label = format!(
+        "{}/{}",
+        km.label(KeyAction::NextLineFinding),
+        km.label(KeyAction::PrevLineFinding)
+    );
     let detail_label = format!(
         "{}/{}",
         km.label(KeyAction::Select),
@@ -118,8 +299,14 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
             StatusLevel::Warn => Color::Yellow,
             StatusLevel::Error => Color::Red,
         };
+        // Without colour, the level has to be spelled out.
+        let level = match (app.accessible, msg.level) {
+            (true, StatusLevel::Warn) => "Warning: ",
+            (true, StatusLevel::Error) => "Error: ",
+            _ => "",
+        };
         let mut spans = vec![
-            Span::styled(&msg.text, Style::default().fg(color)),
+            Span::styled(format!("{level}{}", msg.text), Style::default().fg(color)),
             Span::raw("  "),
         ];
         if app.validating {
@@ -142,6 +329,8 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
                     ],
                     DiffPanel::DiffContent => vec![
                         (scroll_label.as_str(), "scroll"),
+                        (hunk_label.as_str(), "hunk"),
+                        (changed_file_label.as_str(), "file"),
                         (gen_label.as_str(), "generator"),
                         (km.label(KeyAction::NextPanel), "panel"),
                         (km.label(KeyAction::CloseDiff), "close diff"),
@@ -154,7 +343,10 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
                         let mut h = vec![
                             (scroll_label.as_str(), "navigate"),
                             (km.label(KeyAction::Select), "open"),
+                            (km.label(KeyAction::ToggleDirectory), "fold"),
+                            (km.label(KeyAction::Search), "filter"),
                             (gen_label.as_str(), "generator"),
+                            (km.label(KeyAction::IgnoreGeneratedFile), "keep"),
                             (km.label(KeyAction::NextPanel), "panel"),
                             (km.label(KeyAction::ToggleView), "validator"),
                         ];
@@ -166,6 +358,8 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
                     BrowserPanel::FileContent => {
                         let mut h = vec![
                             (scroll_label.as_str(), "scroll"),
+                            (km.label(KeyAction::ShowOutline), "outline"),
+                            (km.label(KeyAction::OpenEditor), "editor"),
                             (km.label(KeyAction::NextPanel), "panel"),
                             (km.label(KeyAction::ToggleView), "validator"),
                         ];
@@ -178,12 +372,19 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
             }
         } else {
             match app.focused_panel {
-                Panel::Phases => vec![
-                    (scroll_label.as_str(), "navigate"),
-                    (km.label(KeyAction::Select), "select"),
-                    (km.label(KeyAction::RunValidation), "run"),
-                    (km.label(KeyAction::ToggleView), "browser"),
-                ],
+                Panel::Phases => {
+                    let mut h = vec![
+                        (scroll_label.as_str(), "navigate"),
+                        (km.label(KeyAction::Select), "select"),
+                        (km.label(KeyAction::RunValidation), "run"),
+                    ];
+                    if app.selected_failed_step().is_some() {
+                        h.push((km.label(KeyAction::OpenShell), "shell"));
+                    }
+                    h.push((km.label(KeyAction::OpenGeneratorPicker), "generators"));
+                    h.push((km.label(KeyAction::ToggleView), "browser"));
+                    h
+                }
                 Panel::Errors => vec![
                     (scroll_label.as_str(), "navigate"),
                     (detail_label.as_str(), "detail"),
@@ -191,11 +392,23 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
                     (km.label(KeyAction::ProposeFix), "fix"),
                     (km.label(KeyAction::RunValidation), "run"),
                 ],
-                Panel::Detail => vec![
-                    (scroll_label.as_str(), "scroll"),
-                    (tab_label.as_str(), "tab"),
-                ],
-                Panel::SpecContext => vec![(scroll_label.as_str(), "scroll")],
+                Panel::Detail => {
+                    let mut h = vec![
+                        (scroll_label.as_str(), "scroll"),
+                        (tab_label.as_str(), "tab"),
+                    ];
+                    if app.detail_tab == 1 {
+                        h.push((km.label(KeyAction::ToggleLogTimes), "times"));
+                    }
+                    h
+                }
+                Panel::SpecContext => {
+                    let mut h = vec![(scroll_label.as_str(), "scroll")];
+                    if app.line_findings().len() > 1 {
+                        h.push((finding_label.as_str(), "cycle findings"));
+                    }
+                    h
+                }
             }
         };
         if app.validating {
@@ -215,13 +428,8 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     frame.render_widget(Paragraph::new(Line::from(left_spans)), bar_layout[0]);
 
     // ── Right side: spinner when validating ──
-    if app.validating {
-        const BRAILLE: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
-        let frame_char = BRAILLE[app.tick / 3 % BRAILLE.len()];
-        let spinner = Line::from(Span::styled(
-            format!(" {frame_char} Validating "),
-            Style::default().fg(Color::Yellow),
-        ));
+    if let Some(text) = spinner {
+        let spinner = Line::from(Span::styled(text, Style::default().fg(Color::Yellow)));
         frame.render_widget(
             Paragraph::new(spinner).alignment(ratatui::layout::Alignment::Right),
             bar_layout[1],
@@ -229,6 +437,20 @@ fn draw_bottom_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     }
 }
 
+/// A `width`-cell bar and percentage for `done` of `total` units, drawn
+/// with `#` and `.` when `ascii`.
+fn progress_bar(done: f32, total: usize, width: usize, ascii: bool) -> String {
+    let fraction = (done / total as f32).clamp(0.0, 1.0);
+    let filled = (fraction * width as f32).round() as usize;
+    let (full, empty) = if ascii { ("#", ".") } else { ("█", "░") };
+    format!(
+        "{}{} {:>3}%",
+        full.repeat(filled),
+        empty.repeat(width - filled),
+        (fraction * 100.0).floor() as usize
+    )
+}
+
 fn push_hint_spans<'a>(spans: &mut Vec<Span<'a>>, key: &'a str, action: &'a str) {
     spans.push(Span::styled(
         format!("[{key}]"),
@@ -2,5 +2,7 @@ mod draw;
 mod overlay;
 mod panels;
 pub mod style;
+mod viewport;
+mod width;
 
 pub use draw::draw;
@@ -1,32 +1,42 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap,
+};
 
+use crate::app::compare::ReportComparison;
+use crate::app::diff::{DiffLine, NumberedLine};
+use crate::app::edit::InlineEditState;
+use crate::app::matrix::MatrixView;
+use crate::app::outline::{OutlineView, SymbolKind};
+use crate::app::picker::{GeneratorPickerState, PickerSource};
+use crate::app::tour::{TOUR, TourState};
+use crate::app::wizard::{InitWizardState, LINTERS, WizardStep};
+use crate::app::workspace::WorkspaceView;
+use crate::app::{
+    App, ChangelogView, CleanPrompt, DryRunView, FixPreview, MigratePrompt, PhaseStatus,
+    RefGraphView, RuleStatsView, RunHistoryView, SchemaView, TimeoutPrompt, TrustPrompt,
+    format_secs,
+};
 use crate::fix::FixProposal;
+use crate::log_parser::LintError;
+use crate::ui::style::{self, score_color, severity_color};
+use crate::ui::viewport::Viewport;
+use crate::ui::width;
+use lazyoav::changelog::Impact;
+use lazyoav::clean;
+use lazyoav::config::Severity;
 use lazyoav::keys::{KeyAction, Keymap};
+use lazyoav::leftovers::Leftovers;
+use lazyoav::pipeline::matrix::MatrixCell;
+use lazyoav::stats;
 
 /// Draw the help overlay centered on the screen.
 pub fn draw_help_overlay(frame: &mut Frame, area: Rect, keymap: &Keymap) {
-    let popup = centered_rect(62, 28, area);
-
-    frame.render_widget(Clear, popup);
-
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(" Keybindings ");
-
-    let inner = block.inner(popup);
-    frame.render_widget(block, popup);
-
-    // Two-column layout.
-    let columns = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(inner);
-
     let nav_lines = keybinding_lines(&[
         ("Navigation", None),
         (keymap.label(KeyAction::ScrollDown), Some("Move down")),
@@ -43,6 +53,18 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, keymap: &Keymap) {
             ),
             Some("Half-page (detail/spec)"),
         ),
+        (
+            &format!(
+                "{}/{}",
+                keymap.label(KeyAction::ScrollLeft),
+                keymap.label(KeyAction::ScrollRight)
+            ),
+            Some("Scroll sideways (no wrap)"),
+        ),
+        (
+            keymap.label(KeyAction::ToggleWrap),
+            Some("Toggle line wrap"),
+        ),
         (keymap.label(KeyAction::NextPanel), Some("Next panel")),
         (keymap.label(KeyAction::PrevPanel), Some("Previous panel")),
         (
@@ -62,7 +84,9 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, keymap: &Keymap) {
         (keymap.label(KeyAction::Select), Some("Select / focus next")),
         (keymap.label(KeyAction::FocusDetail), Some("Jump to detail")),
         (keymap.label(KeyAction::OpenEditor), Some("Open in $EDITOR")),
+        (keymap.label(KeyAction::EditInline), Some("Edit spec line")),
         (keymap.label(KeyAction::ProposeFix), Some("Propose fix")),
+        (keymap.label(KeyAction::FormatSpec), Some("Format spec")),
         (
             keymap.label(KeyAction::RunValidation),
             Some("Run validation"),
@@ -81,19 +105,200 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, keymap: &Keymap) {
             ),
             Some("Switch detail tab"),
         ),
+        (
+            &format!(
+                "{}/{}",
+                keymap.label(KeyAction::NextLineFinding),
+                keymap.label(KeyAction::PrevLineFinding)
+            ),
+            Some("Cycle findings on line"),
+        ),
+        (
+            keymap.label(KeyAction::Search),
+            Some("Search spec or raw log (n/N next/prev)"),
+        ),
+        (
+            keymap.label(KeyAction::FreeNavigate),
+            Some("Free spec navigation"),
+        ),
+        (keymap.label(KeyAction::PinSpec), Some("Pin spec location")),
+        (keymap.label(KeyAction::GotoLine), Some("Go to spec line")),
+        (
+            keymap.label(KeyAction::ToggleLogTimes),
+            Some("Toggle log timestamps"),
+        ),
+        (
+            keymap.label(KeyAction::ToggleLogErrorsOnly),
+            Some("Raw log: errors only"),
+        ),
+        (
+            keymap.label(KeyAction::ToggleLogNoise),
+            Some("Raw log: hide download noise"),
+        ),
+        (
+            keymap.label(KeyAction::OpenShell),
+            Some("Shell into failed step"),
+        ),
+        (
+            keymap.label(KeyAction::OpenLog),
+            Some("Open step's full log in pager"),
+        ),
+        (
+            keymap.label(KeyAction::SuppressFinding),
+            Some("Suppress finding"),
+        ),
+        (
+            keymap.label(KeyAction::ToggleSuppressed),
+            Some("Show suppressed"),
+        ),
+        (
+            keymap.label(KeyAction::ToggleChangedOnly),
+            Some("Only findings on changed lines"),
+        ),
+        (
+            keymap.label(KeyAction::SnapshotBaseline),
+            Some("Snapshot findings baseline"),
+        ),
         (
             keymap.label(KeyAction::ToggleView),
             Some("Toggle code browser"),
         ),
+        (
+            keymap.label(KeyAction::OpenGeneratorPicker),
+            Some("Pick generators"),
+        ),
+        (
+            keymap.label(KeyAction::ShowDiagnostics),
+            Some("Config diagnostics"),
+        ),
+        (
+            keymap.label(KeyAction::ExportShare),
+            Some("Export redacted bundle"),
+        ),
+        (
+            keymap.label(KeyAction::CreateSupportBundle),
+            Some("Create support bundle"),
+        ),
+        (keymap.label(KeyAction::StartTour), Some("Onboarding tour")),
+        (
+            keymap.label(KeyAction::ShowChangelog),
+            Some("API changelog"),
+        ),
+        (
+            keymap.label(KeyAction::OpenDocs),
+            Some("Open generated docs"),
+        ),
+        (keymap.label(KeyAction::ShowSchema), Some("Resolved schema")),
+        (keymap.label(KeyAction::ShowScore), Some("Quality score")),
+        (
+            keymap.label(KeyAction::ShowRuleStats),
+            Some("Rule statistics across runs"),
+        ),
+        (
+            keymap.label(KeyAction::ShowReferences),
+            Some("References to/from schema"),
+        ),
+        (
+            keymap.label(KeyAction::ShowRuns),
+            Some("Compare runs' generated output"),
+        ),
+        (
+            keymap.label(KeyAction::CompareReports),
+            Some("Compare with the previous report"),
+        ),
+        (
+            keymap.label(KeyAction::RunMatrix),
+            Some("Generator version matrix"),
+        ),
+        (
+            keymap.label(KeyAction::RunWorkspace),
+            Some("Check every workspace spec"),
+        ),
+        (
+            keymap.label(KeyAction::SwitchProject),
+            Some("Switch project"),
+        ),
+        (
+            keymap.label(KeyAction::DryRun),
+            Some("Show commands without running"),
+        ),
+        (
+            keymap.label(KeyAction::MigrateSpec),
+            Some("Apply all 3.1 migration fixes"),
+        ),
+        (
+            keymap.label(KeyAction::CleanGenerated),
+            Some("Delete generated output"),
+        ),
+        (
+            &format!("{} y/p/s/f", keymap.label(KeyAction::Copy)),
+            Some("Copy error/pointer/snippet/file"),
+        ),
         ("Code Browser", None),
         (
             keymap.label(KeyAction::ToggleDiff),
             Some("Toggle generation diff"),
         ),
+        (
+            &format!(
+                "{}/{}",
+                keymap.label(KeyAction::NextHunk),
+                keymap.label(KeyAction::PrevHunk)
+            ),
+            Some("Next/previous diff hunk"),
+        ),
+        (
+            &format!(
+                "{}/{}",
+                keymap.label(KeyAction::NextChangedFile),
+                keymap.label(KeyAction::PrevChangedFile)
+            ),
+            Some("Next/previous changed file"),
+        ),
+        (
+            keymap.label(KeyAction::IgnoreGeneratedFile),
+            Some("Keep file on regenerate"),
+        ),
+        (
+            keymap.label(KeyAction::ToggleDirectory),
+            Some("Collapse/expand directory"),
+        ),
+        (
+            keymap.label(KeyAction::Search),
+            Some("Filter files by glob"),
+        ),
+        (
+            keymap.label(KeyAction::ShowOutline),
+            Some("Jump to a symbol in the file"),
+        ),
+        (
+            keymap.label(KeyAction::OpenEditor),
+            Some("Open file in $EDITOR"),
+        ),
         (keymap.label(KeyAction::Quit), Some("Quit")),
         (keymap.label(KeyAction::Help), Some("Toggle this help")),
     ]);
 
+    // Height: border(2) + the longer column + dismiss hint.
+    let height = nav_lines.len().max(action_lines.len()) as u16 + 3;
+    let popup = centered_rect(62, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Keybindings ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    // Two-column layout.
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+
     let dismiss = vec![Line::from(Span::styled(
         "Press any key to close",
         Style::default()
@@ -128,54 +333,61 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, keymap: &Keymap) {
     );
 }
 
-/// Draw the fix proposal overlay centered on the screen.
-pub fn draw_fix_overlay(frame: &mut Frame, proposal: &FixProposal, area: Rect) {
-    let content_lines = build_fix_lines(proposal);
-    // Height: border(2) + content lines + 1 blank + keybindings line.
-    let height = (content_lines.len() as u16) + 4;
-    let popup = centered_rect(70, height, area);
+/// Draw the generator picker overlay centered on the screen.
+pub fn draw_generator_picker(
+    frame: &mut Frame,
+    picker: &GeneratorPickerState,
+    ascii: bool,
+    area: Rect,
+) {
+    let popup = centered_rect(50, 24, area);
 
     frame.render_widget(Clear, popup);
 
-    let title = format!(" Fix: {} ", proposal.rule);
+    let title = if picker.live_rx.is_some() {
+        " Generators (listing\u{2026}) "
+    } else {
+        " Generators "
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green))
+        .border_style(Style::default().fg(Color::Cyan))
         .title(title);
 
     let inner = block.inner(popup);
     frame.render_widget(block, popup);
 
-    // Content area (everything except the last line for keybindings).
-    let content_area = Rect {
+    let dim = Style::default().fg(Color::DarkGray);
+    let (items, selected_row) = generator_items(picker);
+
+    let list_area = Rect {
         height: inner.height.saturating_sub(2),
         ..inner
     };
-    frame.render_widget(Paragraph::new(content_lines), content_area);
-
-    // Keybindings hint at the bottom.
-    let hint_line = Line::from(vec![
-        Span::styled(
-            "[y]",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" accept  ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            "[n]",
+    let mut state = ListState::default();
+    state.select(selected_row);
+    let list = List::new(items)
+        .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .bg(Color::DarkGray)
                 .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" skip  ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            "[Esc]",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
-    ]);
+        )
+        .highlight_symbol(style::selection_marker(ascii));
+    frame.render_stateful_widget(list, list_area, &mut state);
 
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[Space]", key),
+        Span::styled(" toggle  ", dim),
+        Span::styled("[Enter]", key),
+        Span::styled(" save  ", dim),
+        Span::styled("[L]", key),
+        Span::styled(" list all  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" cancel", dim),
+    ]);
     let hint_area = Rect {
         x: inner.x,
         y: inner.y + inner.height.saturating_sub(1),
@@ -188,52 +400,2027 @@ pub fn draw_fix_overlay(frame: &mut Frame, proposal: &FixProposal, area: Rect) {
     );
 }
 
-fn build_fix_lines(proposal: &FixProposal) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
+/// Picker entries grouped under scope headers, plus the row of the selected
+/// entry (headers take up rows too).
+fn generator_items(picker: &GeneratorPickerState) -> (Vec<ListItem<'static>>, Option<usize>) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut items = Vec::new();
+    let mut selected_row = None;
+    let mut current_scope = "";
+    for (i, entry) in picker.entries.iter().enumerate() {
+        if entry.scope != current_scope {
+            current_scope = &entry.scope;
+            let header = if current_scope == "server" {
+                "Server"
+            } else {
+                "Client"
+            };
+            items.push(ListItem::new(Line::from(Span::styled(
+                header,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ))));
+        }
+        if i == picker.index {
+            selected_row = Some(items.len());
+        }
+        let check = if entry.enabled { "[x]" } else { "[ ]" };
+        let tag = match entry.source {
+            PickerSource::Builtin => "",
+            PickerSource::Custom => " (custom)",
+            PickerSource::Live => " (no curated config)",
+            PickerSource::Configured => " (unknown)",
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::raw(format!("  {check} ")),
+            Span::raw(entry.name.clone()),
+            Span::styled(tag, dim),
+        ])));
+    }
+
+    (items, selected_row)
+}
+
+/// Draw the `.oavc` init wizard centered on the screen.
+pub fn draw_init_wizard(frame: &mut Frame, wizard: &InitWizardState, ascii: bool, area: Rect) {
+    let popup = centered_rect(64, 26, area);
+
+    frame.render_widget(Clear, popup);
+
+    let title = format!(
+        " Set up {} ({}/4): {} ",
+        lazyoav::config::CONFIG_FILE,
+        wizard.step.number(),
+        wizard.step.title()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
     let dim = Style::default().fg(Color::DarkGray);
-    let green = Style::default()
-        .fg(Color::Green)
+    let body = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let highlight = Style::default()
+        .bg(Color::DarkGray)
         .add_modifier(Modifier::BOLD);
 
-    // Description.
-    lines.push(Line::from(Span::styled(
-        proposal.description.clone(),
-        Style::default().fg(Color::White),
-    )));
-    lines.push(Line::from(""));
+    let simple_list = |frame: &mut Frame, prompt: &str, rows: Vec<ListItem<'static>>, selected| {
+        let [prompt_area, list_area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .areas(body);
+        frame.render_widget(
+            Paragraph::new(Span::styled(prompt.to_string(), dim)),
+            prompt_area,
+        );
+        let mut state = ListState::default();
+        state.select(selected);
+        frame.render_stateful_widget(
+            List::new(rows)
+                .highlight_style(highlight)
+                .highlight_symbol(style::selection_marker(ascii)),
+            list_area,
+            &mut state,
+        );
+    };
 
-    // Context before.
-    let ctx_start = proposal
-        .target_line
-        .saturating_sub(proposal.context_before.len());
-    for (i, line) in proposal.context_before.iter().enumerate() {
-        let line_num = ctx_start + i + 1;
-        lines.push(Line::from(vec![
-            Span::styled(format!("  {line_num:>4} │ "), dim),
-            Span::styled(line.clone(), dim),
-        ]));
+    let hints: &[(&str, &str)] = match wizard.step {
+        WizardStep::Spec => {
+            if wizard.specs.is_empty() {
+                frame.render_widget(
+                    Paragraph::new(vec![
+                        Line::from("No OpenAPI spec found in this directory."),
+                        Line::from(""),
+                        Line::from(Span::styled(
+                            "The config will leave `spec` commented out for you to fill in.",
+                            dim,
+                        )),
+                    ])
+                    .wrap(Wrap { trim: true }),
+                    body,
+                );
+            } else {
+                let rows = wizard
+                    .specs
+                    .iter()
+                    .map(|s| ListItem::new(format!("  {s}")))
+                    .collect();
+                simple_list(
+                    frame,
+                    "Which spec should be validated?",
+                    rows,
+                    Some(wizard.spec_index),
+                );
+            }
+            &[("Enter", "next"), ("Esc", "skip setup")]
+        }
+        WizardStep::Linter => {
+            let rows = LINTERS
+                .iter()
+                .map(|linter| {
+                    let tag = if *linter == wizard.recommended_linter {
+                        " (recommended)"
+                    } else {
+                        ""
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("  {}", linter.as_str())),
+                        Span::styled(tag, dim),
+                    ]))
+                })
+                .collect();
+            simple_list(
+                frame,
+                "Which linter should check the spec?",
+                rows,
+                Some(wizard.linter_index),
+            );
+            &[
+                ("Enter", "next"),
+                ("\u{2190}", "back"),
+                ("Esc", "skip setup"),
+            ]
+        }
+        WizardStep::Generators => {
+            let (rows, selected) = generator_items(&wizard.generators);
+            simple_list(
+                frame,
+                "Which generators should run? Pick at least one.",
+                rows,
+                selected,
+            );
+            &[
+                ("Space", "toggle"),
+                ("Enter", "next"),
+                ("\u{2190}", "back"),
+                ("Esc", "skip setup"),
+            ]
+        }
+        WizardStep::Review => {
+            let preview: Vec<Line> = wizard
+                .choices()
+                .render()
+                .lines()
+                .map(|l| {
+                    if l.starts_with('#') {
+                        Line::from(Span::styled(l.to_string(), dim))
+                    } else {
+                        Line::from(l.to_string())
+                    }
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(preview), body);
+            &[
+                ("Enter", "write"),
+                ("\u{2190}", "back"),
+                ("Esc", "skip setup"),
+            ]
+        }
+    };
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    for (i, (k, label)) in hints.iter().enumerate() {
+        let sep = if i + 1 < hints.len() { "  " } else { "" };
+        spans.push(Span::styled(format!("[{k}]"), key));
+        spans.push(Span::styled(format!(" {label}{sep}"), dim));
     }
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(spans)]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the current onboarding tour step, outlining `highlight` (the region
+/// the step is about) and keeping the popup clear of it.
+pub fn draw_tour(
+    frame: &mut Frame,
+    tour: &TourState,
+    keymap: &Keymap,
+    highlight: Option<Rect>,
+    area: Rect,
+) {
+    let step = tour.step();
+    let accent = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
 
-    // Inserted lines (green, with + prefix).
-    for (i, line) in proposal.inserted.iter().enumerate() {
-        let line_num = proposal.target_line + i + 1;
+    let popup_area = match highlight {
+        Some(rect) => {
+            if rect.height >= 3 {
+                frame.render_widget(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick)
+                        .border_style(accent),
+                    rect,
+                );
+            } else {
+                frame
+                    .buffer_mut()
+                    .set_style(rect, Style::default().fg(Color::Black).bg(Color::Yellow));
+            }
+            // Use whichever half of the screen the highlight isn't in.
+            let half = area.width / 2;
+            let x = if rect.x + rect.width / 2 < area.x + half {
+                area.x + half
+            } else {
+                area.x
+            };
+            Rect {
+                x,
+                width: area.width - half,
+                height: area.height.saturating_sub(1),
+                ..area
+            }
+        }
+        None => area,
+    };
+    let popup = centered_rect(56, 14, popup_area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " {} ({}/{}) ",
+            step.title,
+            tour.index + 1,
+            TOUR.len()
+        ));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from(step.body), Line::from("")];
+    for (action, description) in step.keys {
         lines.push(Line::from(vec![
-            Span::styled(format!("+ {line_num:>4} │ "), green),
-            Span::styled(line.clone(), green),
+            Span::styled(format!("  {:<8}", keymap.label(*action)), key),
+            Span::raw(*description),
         ]));
     }
+    let body_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), body_area);
+
+    let next = if tour.is_last() {
+        " finish  "
+    } else {
+        " next  "
+    };
+    let hint_line = Line::from(vec![
+        Span::styled("[Enter]", key),
+        Span::styled(next, dim),
+        Span::styled("[\u{2190}]", key),
+        Span::styled(" back  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" skip tour", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
 
-    // Context after.
-    let after_start = proposal.target_line + proposal.inserted.len() + 1;
-    for (i, line) in proposal.context_after.iter().enumerate() {
-        let line_num = after_start + i;
+/// Draw the config diagnostics overlay centered on the screen.
+pub fn draw_config_diagnostics(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(76, 20, area);
+
+    frame.render_widget(Clear, popup);
+
+    let has_errors = app
+        .config_diagnostics
+        .iter()
+        .any(|d| d.severity == Severity::Error);
+    let title = format!(
+        " Config: {} ",
+        app.diagnostics_summary().unwrap_or_default()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if has_errors {
+            Color::Red
+        } else {
+            Color::Yellow
+        }))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    for diagnostic in app.config_diagnostics.iter().skip(app.diagnostics_scroll) {
+        let (marker, color) = match diagnostic.severity {
+            Severity::Error => (
+                style::phase_status_icon(PhaseStatus::Fail, app.accessible),
+                Color::Red,
+            ),
+            Severity::Warning => ("!", Color::Yellow),
+        };
         lines.push(Line::from(vec![
-            Span::styled(format!("  {line_num:>4} │ "), dim),
-            Span::styled(line.clone(), dim),
+            Span::styled(
+                format!("{marker} "),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                diagnostic.key.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("  {}", diagnostic.message)),
         ]));
+        if let Some(hint) = &diagnostic.hint {
+            lines.push(Line::from(Span::styled(format!("  \u{2192} {hint}"), dim)));
+        }
     }
 
-    lines
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), list_area);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the API changelog against the configured base revision.
+pub fn draw_changelog(frame: &mut Frame, view: &ChangelogView, ascii: bool, area: Rect) {
+    let popup = centered_rect(90, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let log = &view.changelog;
+    let breaking = log.breaking_count();
+    let title = format!(
+        " Changes since {}: {} breaking, {} other ",
+        view.base,
+        breaking,
+        log.changes.len() - breaking
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if breaking > 0 {
+            Color::Red
+        } else {
+            Color::Cyan
+        }))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines: Vec<Line> = if log.is_empty() {
+        vec![Line::from(Span::styled(
+            "No changes to operations or schemas",
+            dim,
+        ))]
+    } else {
+        log.changes
+            .iter()
+            .skip(view.scroll)
+            .map(|change| {
+                let (marker, color) = match change.impact {
+                    Impact::Breaking => (
+                        style::phase_status_icon(PhaseStatus::Fail, ascii),
+                        Color::Red,
+                    ),
+                    Impact::NonBreaking => ("+", Color::Green),
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{marker} "),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        change.subject.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("  {}", change.description)),
+                ])
+            })
+            .collect()
+    };
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[w]", key),
+        Span::styled(" write Markdown  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the resolved schema tree, or its example payload, for the selected
+/// finding.
+pub fn draw_schema(frame: &mut Frame, view: &SchemaView, area: Rect) {
+    let popup = centered_rect(90, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " {}: {} ",
+            if view.show_payload {
+                "Example payload"
+            } else {
+                "Resolved schema"
+            },
+            view.pointer
+        ));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines: Vec<Line> = view
+        .lines()
+        .iter()
+        .skip(view.scroll)
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            match trimmed.split_once(": ") {
+                Some((name, rest)) => Line::from(vec![
+                    Span::raw(indent.to_string()),
+                    Span::styled(
+                        format!("{name}: "),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(rest.to_string(), Style::default().fg(Color::Yellow)),
+                ]),
+                None => Line::from(Span::styled(
+                    line.clone(),
+                    Style::default().fg(Color::Yellow),
+                )),
+            }
+        })
+        .collect();
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[p]", key),
+        Span::styled(
+            if view.show_payload {
+                " schema  "
+            } else {
+                " example  "
+            },
+            dim,
+        ),
+        Span::styled("[y]", key),
+        Span::styled(" copy  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw what references the selected component and what it references.
+pub fn draw_ref_graph(frame: &mut Frame, view: &RefGraphView, area: Rect) {
+    let popup = centered_rect(70, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" References: {} ", view.subject));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let heading = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = Vec::new();
+    for (title, nodes) in [("Referenced by", &view.used_by), ("References", &view.uses)] {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            format!("{title} ({})", nodes.len()),
+            heading,
+        )));
+        if nodes.is_empty() {
+            lines.push(Line::from(Span::styled("  nothing", dim)));
+        }
+        for (depth, node) in nodes {
+            lines.push(Line::from(vec![
+                Span::raw("  ".repeat(*depth)),
+                Span::raw(node.clone()),
+                Span::styled(
+                    if *depth > 1 {
+                        format!("  (depth {depth})")
+                    } else {
+                        String::new()
+                    },
+                    dim,
+                ),
+            ]));
+        }
+    }
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(
+        Paragraph::new(lines.into_iter().skip(view.scroll).collect::<Vec<_>>()),
+        list_area,
+    );
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the recorded runs, newest first, with the marked base run.
+pub fn draw_run_history(frame: &mut Frame, view: &RunHistoryView, ascii: bool, area: Rect) {
+    let height = (view.runs.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let popup = centered_rect(70, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Run history ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let items: Vec<ListItem> = view
+        .runs
+        .iter()
+        .enumerate()
+        .map(|(i, run)| {
+            let marker = if view.base == Some(i) {
+                "\u{25cf} "
+            } else {
+                "  "
+            };
+            let status = if run.failed > 0 {
+                Span::styled(
+                    format!("{} failed", run.failed),
+                    Style::default().fg(Color::Red),
+                )
+            } else {
+                Span::styled(
+                    format!("{} passed", run.passed),
+                    Style::default().fg(Color::Green),
+                )
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::raw(format!("{:<10}", run.age(now))),
+                status,
+                Span::styled(
+                    format!("  {}  {} generator(s)", run.spec, run.generators.len()),
+                    dim,
+                ),
+            ]))
+        })
+        .collect();
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let mut state = ListState::default();
+    state.select(Some(view.selected));
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(style::selection_marker(ascii));
+    frame.render_stateful_widget(list, list_area, &mut state);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[Space]", key),
+        Span::styled(" mark base  ", dim),
+        Span::styled("[Enter]", key),
+        Span::styled(" diff output  ", dim),
+        Span::styled("[c]", key),
+        Span::styled(" compare reports  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw a report comparison: phases that started or stopped failing,
+/// findings that came and went, and finding counts per rule.
+pub fn draw_comparison(frame: &mut Frame, view: &ReportComparison, ascii: bool, area: Rect) {
+    let popup = centered_rect(90, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let regressed = !view.newly_failing.is_empty() || !view.new_findings.is_empty();
+    let title = format!(
+        " Report comparison {}: {} failing, {} fixed, {} new finding(s), {} fixed ",
+        view.label,
+        view.newly_failing.len(),
+        view.fixed_phases.len(),
+        view.new_findings.len(),
+        view.fixed_findings.len()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if regressed { Color::Red } else { Color::Cyan }))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let red = Style::default().fg(Color::Red);
+    let green = Style::default().fg(Color::Green);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut section = |heading: &str, rows: Vec<Line<'static>>| {
+        if !rows.is_empty() {
+            lines.push(Line::from(Span::styled(heading.to_string(), bold)));
+            lines.extend(rows);
+        }
+    };
+    let phase = |status: PhaseStatus, color: Style, name: &String| {
+        let marker = format!("  {} ", style::phase_status_icon(status, ascii));
+        Line::from(vec![Span::styled(marker, color), Span::raw(name.clone())])
+    };
+    let finding = |marker: &'static str, style: Style, e: &LintError| {
+        let location = e
+            .json_path
+            .clone()
+            .unwrap_or_else(|| format!("line {}", e.line));
+        Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(
+                format!("{:<8}", e.severity),
+                Style::default().fg(severity_color(e.severity)),
+            ),
+            Span::styled(format!("{}  ", e.rule), bold),
+            Span::raw(e.message.clone()),
+            Span::styled(format!("  {location}"), dim),
+        ])
+    };
+    section(
+        "Newly failing",
+        view.newly_failing
+            .iter()
+            .map(|name| phase(PhaseStatus::Fail, red, name))
+            .collect(),
+    );
+    section(
+        "Fixed",
+        view.fixed_phases
+            .iter()
+            .map(|name| phase(PhaseStatus::Pass, green, name))
+            .collect(),
+    );
+    section(
+        "New findings",
+        view.new_findings
+            .iter()
+            .map(|e| finding("  + ", red, e))
+            .collect(),
+    );
+    section(
+        "Fixed findings",
+        view.fixed_findings
+            .iter()
+            .map(|e| finding("  - ", green, e))
+            .collect(),
+    );
+    section(
+        "Findings per rule",
+        view.rule_deltas
+            .iter()
+            .map(|(rule, before, after)| {
+                let delta = *after as i64 - *before as i64;
+                Line::from(vec![
+                    Span::raw(format!("  {}", width::pad(rule, 40))),
+                    Span::styled(format!("{before:>4} \u{2192} {after:<4}"), dim),
+                    Span::styled(format!("{delta:+}"), if delta > 0 { red } else { green }),
+                ])
+            })
+            .collect(),
+    );
+    if view.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No changes in step results or findings",
+            dim,
+        )));
+    }
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(
+        Paragraph::new(lines.into_iter().skip(view.scroll).collect::<Vec<_>>()),
+        list_area,
+    );
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw how often each rule fired over the recorded runs, most frequent
+/// first, with how long its findings took to fix.
+pub fn draw_rule_stats(frame: &mut Frame, view: &RuleStatsView, area: Rect) {
+    let popup = centered_rect(90, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let stats = &view.stats;
+    let fix = |secs: Option<u64>| secs.map_or("\u{2014}".to_string(), stats::format_duration);
+    let span = stats
+        .span
+        .map(|(first, last)| {
+            format!(
+                " over {}",
+                stats::format_duration(last.saturating_sub(first))
+            )
+        })
+        .unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " Rule statistics: {} run(s){span}, {:.1} findings per run, mean time to fix {} ",
+            stats.runs,
+            stats.per_run,
+            fix(stats.mean_fix_secs)
+        ));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let rule_width = stats
+        .rules
+        .iter()
+        .map(|r| width::width(&r.rule))
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let header = Line::from(Span::styled(
+        format!(
+            "{:<rule_width$}  {:>8}  {:>6}  {:>7}  {:>5}  {:>5}  {:>11}",
+            "Rule", "Findings", "Runs", "Per run", "Open", "Fixed", "Time to fix"
+        ),
+        bold,
+    ));
+    let mut lines: Vec<Line> = stats
+        .rules
+        .iter()
+        .skip(view.scroll)
+        .map(|r| {
+            Line::from(vec![
+                Span::styled(format!("{}  ", width::pad(&r.rule, rule_width)), bold),
+                Span::raw(format!(
+                    "{:>8}  {:>6}  {:>7.1}  ",
+                    r.findings,
+                    format!("{}/{}", r.runs, stats.runs),
+                    r.per_run
+                )),
+                Span::styled(
+                    format!("{:>5}", r.open),
+                    if r.open > 0 {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        dim
+                    },
+                ),
+                Span::raw(format!("  {:>5}  ", r.fixed)),
+                Span::styled(format!("{:>11}", fix(r.mean_fix_secs)), dim),
+            ])
+        })
+        .collect();
+    if stats.rules.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No findings in any recorded run",
+            dim,
+        )));
+    }
+    lines.insert(0, header);
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the dry run's command lines, one step after another, keeping the
+/// selected step in view.
+pub fn draw_dry_run(frame: &mut Frame, view: &DryRunView, area: Rect) {
+    let popup = centered_rect(90, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Dry run: {} step(s) ", view.steps.len()));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let width = usize::from(inner.width.max(1));
+    let mut lines = Vec::new();
+    let mut selected_end = 0;
+    for (i, (step, command)) in view.steps.iter().enumerate() {
+        let selected = i == view.selected;
+        let (marker, command_style) = if selected {
+            ("\u{25b6} ", Style::default().fg(Color::Yellow))
+        } else {
+            ("  ", Style::default())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Yellow)),
+            Span::styled(format!("# {step}"), dim),
+        ]));
+        lines.push(Line::from(Span::styled(command.clone(), command_style)));
+        lines.push(Line::default());
+        if i <= view.selected {
+            selected_end += 2 + width::width(command).div_ceil(width).max(1);
+        }
+    }
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let scroll = selected_end.saturating_sub(usize::from(list_area.height));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll as u16, 0)),
+        list_area,
+    );
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" select  ", dim),
+        Span::styled("[y]", key),
+        Span::styled(" copy  ", dim),
+        Span::styled("[Y]", key),
+        Span::styled(" copy all  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the project switcher: every project found under the start
+/// directory, the one in use marked.
+pub fn draw_projects(frame: &mut Frame, app: &App, selected: usize, area: Rect) {
+    let height = (app.projects.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let popup = centered_rect(60, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Projects: {} ", app.projects.len()));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let current = if app.accessible { "* " } else { "\u{25cf} " };
+    let lines: Vec<Line> = app
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(i, project)| {
+            let (marker, label_style) = if i == selected {
+                ("\u{25b6} ", Style::default().fg(Color::Yellow))
+            } else {
+                ("  ", Style::default())
+            };
+            let source = if project.has_config {
+                "  .oavc"
+            } else {
+                "  spec only"
+            };
+            Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    if app.project == Some(i) {
+                        current
+                    } else {
+                        "  "
+                    },
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(project.label(), label_style),
+                Span::styled(source, dim),
+            ])
+        })
+        .collect();
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let scroll = (selected + 1).saturating_sub(usize::from(list_area.height));
+    let shown = Viewport::new(lines.len(), scroll as u16, list_area.height);
+    frame.render_widget(Paragraph::new(lines[shown.range()].to_vec()), list_area);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" select  ", dim),
+        Span::styled("[Enter]", key),
+        Span::styled(" switch  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the symbol outline of the opened file as a jump list, narrowed by
+/// the typed query.
+pub fn draw_outline(frame: &mut Frame, view: &OutlineView, ascii: bool, area: Rect) {
+    let popup = centered_rect(60, area.height.saturating_sub(4), area);
+
+    frame.render_widget(Clear, popup);
+
+    let visible = view.visible();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " Outline: {} \u{00b7} {}/{} ",
+            view.file,
+            visible.len(),
+            view.symbols.len()
+        ));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{}\u{258f}", view.query)),
+        ])),
+        sections[0],
+    );
+
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|symbol| {
+            let (icon, color) = match symbol.kind {
+                SymbolKind::Type => ("\u{25c6} ", Color::Cyan),
+                SymbolKind::Function => ("\u{0192} ", Color::Yellow),
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw("  ".repeat(symbol.depth)),
+                Span::styled(icon, Style::default().fg(color)),
+                Span::raw(symbol.name.clone()),
+                Span::styled(format!("  L{}", symbol.line + 1), dim),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(style::selection_marker(ascii));
+    let mut list_state = ListState::default();
+    list_state.select((!visible.is_empty()).then_some(view.selected));
+    frame.render_stateful_widget(list, sections[1], &mut list_state);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[type]", key),
+        Span::styled(" narrow  ", dim),
+        Span::styled("[\u{2191}/\u{2193}]", key),
+        Span::styled(" select  ", dim),
+        Span::styled("[Enter]", key),
+        Span::styled(" jump  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        sections[2],
+    );
+}
+
+/// Draw the generator version matrix: a row per generator, a column per
+/// version, with the generate and compile result in each cell.
+pub fn draw_matrix(frame: &mut Frame, view: &MatrixView, ascii: bool, area: Rect) {
+    let height = (view.rows.len() as u16 + 7).min(area.height.saturating_sub(4));
+    let popup = centered_rect(80, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let title = if view.is_running() {
+        " Version matrix (running\u{2026}) "
+    } else {
+        " Version matrix "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let name_width = view
+        .rows
+        .iter()
+        .map(|r| width::width(r))
+        .max()
+        .unwrap_or(0)
+        .max(9)
+        + 2;
+    let widths: Vec<usize> = view
+        .versions
+        .iter()
+        .map(|v| v.label.len().max(5) + 4)
+        .collect();
+
+    let mut header = vec![Span::styled(format!("{:<name_width$}", "generator"), dim)];
+    for (i, version) in view.versions.iter().enumerate() {
+        let marker = match (i == view.base, ascii) {
+            (true, false) => "\u{25cf} ",
+            (true, true) => "* ",
+            (false, _) => "  ",
+        };
+        let mut style = Style::default().add_modifier(Modifier::BOLD);
+        if i == view.column {
+            style = style.bg(Color::DarkGray);
+        }
+        header.push(Span::styled(marker, Style::default().fg(Color::Yellow)));
+        header.push(Span::styled(
+            format!("{:<width$}", version.label, width = widths[i] - 2),
+            style,
+        ));
+    }
+    let mut lines = vec![Line::from(header)];
+
+    for (row, key) in view.rows.iter().enumerate() {
+        let mut spans = vec![Span::raw(width::pad(key, name_width))];
+        for (i, cell) in view.cells[row].iter().enumerate() {
+            let (text, style) = match cell {
+                Some(cell) => matrix_cell(*cell, ascii),
+                None if view.running == Some(i) => ("\u{2026}".to_string(), dim),
+                None => (String::new(), dim),
+            };
+            spans.push(Span::styled(
+                format!("  {text:<width$}", width = widths[i] - 2),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    if let Some(error) = &view.error {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let table_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines), table_area);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let close = if view.is_running() {
+        " cancel"
+    } else {
+        " close"
+    };
+    let hint_line = Line::from(vec![
+        Span::styled("[h/l]", key),
+        Span::styled(" version  ", dim),
+        Span::styled("[Space]", key),
+        Span::styled(" mark base  ", dim),
+        Span::styled("[Enter]", key),
+        Span::styled(" diff  ", dim),
+        Span::styled("[r]", key),
+        Span::styled(" rerun  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(close, dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// `gen build` marks for a matrix cell: ✓, ✗, or – when compile didn't run;
+/// `+`, `x` and `-` with `ascii`.
+fn matrix_cell(cell: MatrixCell, ascii: bool) -> (String, Style) {
+    let mark = |ok: bool| match (ok, ascii) {
+        (true, false) => "\u{2713}",
+        (false, false) => "\u{2717}",
+        (true, true) => style::ASCII_PASS,
+        (false, true) => style::ASCII_FAIL,
+    };
+    let compile = cell
+        .compile
+        .map_or(if ascii { "-" } else { "\u{2013}" }, mark);
+    let color = if cell.passed() {
+        Color::Green
+    } else {
+        Color::Red
+    };
+    (
+        format!("{} {compile}", mark(cell.generate)),
+        Style::default().fg(color),
+    )
+}
+
+/// Draw the workspace run: a pass/fail row per spec and the totals.
+pub fn draw_workspace(frame: &mut Frame, view: &WorkspaceView, ascii: bool, area: Rect) {
+    let height = (view.specs.len() as u16 + 8).min(area.height.saturating_sub(4));
+    let popup = centered_rect(90, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let title = if view.is_running() {
+        " Workspace (running\u{2026}) "
+    } else {
+        " Workspace "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let name_width = view
+        .specs
+        .iter()
+        .map(|s| width::width(s))
+        .max()
+        .unwrap_or(0)
+        .max(4)
+        + 2;
+    let mark = |check: Option<bool>| {
+        let (text, color) = match (check, ascii) {
+            (Some(true), false) => ("\u{2713}", Color::Green),
+            (Some(false), false) => ("\u{2717}", Color::Red),
+            (Some(true), true) => (style::ASCII_PASS, Color::Green),
+            (Some(false), true) => (style::ASCII_FAIL, Color::Red),
+            (None, false) => ("\u{2013}", Color::DarkGray),
+            (None, true) => ("-", Color::DarkGray),
+        };
+        Span::styled(format!("{text:<11}"), Style::default().fg(color))
+    };
+
+    let header = Line::from(Span::styled(
+        format!(
+            "{:<name_width$}{:<11}{:<11}{:<11}findings",
+            "spec", "structure", "lint", "security"
+        ),
+        dim,
+    ));
+    let rows: Vec<Line> = view
+        .specs
+        .iter()
+        .zip(&view.rows)
+        .enumerate()
+        .map(|(i, (spec, row))| {
+            let mut spans = vec![Span::raw(width::pad(spec, name_width))];
+            match row {
+                Some(row) if row.error.is_some() => spans.push(Span::styled(
+                    row.error.clone().unwrap_or_default(),
+                    Style::default().fg(Color::Red),
+                )),
+                Some(row) => {
+                    spans.extend([mark(row.structure), mark(row.lint), mark(row.security)]);
+                    spans.push(Span::raw(row.findings_label()));
+                }
+                None if view.running == Some(i) => spans.push(Span::styled("\u{2026}", dim)),
+                None => {}
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(header), Rect { height: 1, ..inner });
+    let table_area = Rect {
+        y: inner.y + 1,
+        height: inner.height.saturating_sub(4),
+        ..inner
+    };
+    let shown = Viewport::new(rows.len(), view.scroll, table_area.height);
+    frame.render_widget(Paragraph::new(rows[shown.range()].to_vec()), table_area);
+
+    let report = view.report();
+    let totals = match &view.error {
+        Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))),
+        None => Line::from(Span::styled(
+            format!(
+                "{} of {} spec(s) passing, {} finding(s)",
+                report.passed,
+                view.specs.len(),
+                report.findings
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    };
+    frame.render_widget(
+        Paragraph::new(totals),
+        Rect {
+            y: inner.y + inner.height.saturating_sub(2),
+            height: 1,
+            ..inner
+        },
+    );
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let close = if view.is_running() {
+        " cancel"
+    } else {
+        " close"
+    };
+    let hint_line = Line::from(vec![
+        Span::styled("[j/k]", key),
+        Span::styled(" scroll  ", dim),
+        Span::styled("[r]", key),
+        Span::styled(" rerun  ", dim),
+        Span::styled("[Esc]", key),
+        Span::styled(close, dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// Draw the quality score breakdown and the trend of recorded runs.
+pub fn draw_score(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(score) = &app.score else {
+        return;
+    };
+    let height = score.components.len() as u16 + 8;
+    let popup = centered_rect(70, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(score_color(score.total)))
+        .title(format!(" Quality score: {} ", score.total));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let name_width = score
+        .components
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<Line> = score
+        .components
+        .iter()
+        .map(|c| {
+            let filled = usize::from(c.score) / 10;
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<name_width$}  ", c.name),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "{}{}",
+                        "\u{2588}".repeat(filled),
+                        "\u{2591}".repeat(10 - filled)
+                    ),
+                    Style::default().fg(score_color(c.score)),
+                ),
+                Span::raw(format!(" {:>3}  ", c.score)),
+                Span::styled(c.detail.clone(), dim),
+            ])
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    let recent = &app.score_history[app.score_history.len().saturating_sub(40)..];
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("{:<name_width$}  ", "History"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(sparkline(recent), Style::default().fg(Color::Cyan)),
+        Span::styled(format!("  {} run(s)", app.score_history.len()), dim),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+
+    let key = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let hint_line = Line::from(vec![
+        Span::styled("[Esc]", key),
+        Span::styled(" close", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// One block character per value, scaled from 0 to 100.
+fn sparkline(values: &[u8]) -> String {
+    const BARS: [char; 8] = [
+        '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}',
+        '\u{2588}',
+    ];
+    values
+        .iter()
+        .map(|v| BARS[usize::from(*v).min(100) * (BARS.len() - 1) / 100])
+        .collect()
+}
+
+/// Draw the confirmation before deleting generated output.
+pub fn draw_clean_prompt(frame: &mut Frame, prompt: &CleanPrompt, area: Rect) {
+    let popup = centered_rect(60, 7, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Clean generated output ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let key = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let mut hint = Vec::new();
+    if let Some((generator, size)) = &prompt.generator {
+        hint.push(Span::styled("[g]", key));
+        hint.push(Span::styled(
+            format!(" {generator} ({})  ", clean::format_size(*size)),
+            dim,
+        ));
+    }
+    hint.push(Span::styled("[a]", key));
+    hint.push(Span::styled(
+        format!(" all ({})  ", clean::format_size(prompt.all)),
+        dim,
+    ));
+    hint.push(Span::styled(
+        "[n]",
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    ));
+    hint.push(Span::styled(" cancel", dim));
+
+    let message = Line::from("Delete generated output under .oav/generated?");
+    let note = Line::from(Span::styled("The next run generates it again.", dim));
+    frame.render_widget(
+        Paragraph::new(vec![message, note, Line::from(""), Line::from(hint)])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
+/// Draw the confirmation before applying every 3.1 migration fix.
+pub fn draw_migrate_prompt(frame: &mut Frame, prompt: &MigratePrompt, area: Rect) {
+    let popup = centered_rect(60, 7, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Migrate to OpenAPI 3.1 ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let message = Line::from(format!(
+        "Rewrite the spec with {} of {} migration fixes?",
+        prompt.proposals.len(),
+        prompt.findings
+    ));
+    let note = Line::from(Span::styled(
+        "The rest need a manual edit; validation re-runs afterwards.",
+        dim,
+    ));
+    let hint = Line::from(vec![
+        Span::styled(
+            "[y]",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" apply  ", dim),
+        Span::styled(
+            "[n]",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" cancel", dim),
+    ]);
+    frame.render_widget(
+        Paragraph::new(vec![message, note, Line::from(""), hint])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
+/// Draw the startup offer to clean up after crashed sessions.
+pub fn draw_leftover_prompt(frame: &mut Frame, found: &Leftovers, area: Rect) {
+    let popup = centered_rect(60, 7, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Leftovers from a crashed session ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let message = Line::from(format!("Found {}.", found.summary()));
+    let note = Line::from(Span::styled(
+        "Containers are removed, stashed output put back, temp files deleted.",
+        dim,
+    ));
+    let hint = Line::from(vec![
+        Span::styled(
+            "[y]",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" clean up  ", dim),
+        Span::styled(
+            "[n]",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" leave them", dim),
+    ]);
+    frame.render_widget(
+        Paragraph::new(vec![message, note, Line::from(""), hint])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
+/// Draw the question whether to trust a project's host commands.
+pub fn draw_trust_prompt(frame: &mut Frame, prompt: &TrustPrompt, area: Rect) {
+    let popup = centered_rect(60, 8, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Trust this project? ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let message = Line::from(format!(
+        "This project runs {} on this machine.",
+        prompt.commands.join(" and ")
+    ));
+    let note = Line::from(Span::styled(
+        "Only trust projects you know; trust is remembered.",
+        dim,
+    ));
+    let hint = Line::from(vec![
+        Span::styled(
+            "[y]",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" trust  ", dim),
+        Span::styled(
+            "[n]",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" run without them", dim),
+    ]);
+    frame.render_widget(
+        Paragraph::new(vec![message, note, Line::from(""), hint])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
+/// Draw the "step is about to time out" prompt centered on the screen.
+pub fn draw_timeout_prompt(frame: &mut Frame, prompt: &TimeoutPrompt, extension: u64, area: Rect) {
+    let popup = centered_rect(60, 6, area);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Timeout ");
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let message = Line::from(format!(
+        "{} will time out in {}s \u{2014} extend by {}?",
+        prompt.phase,
+        prompt.time_left().as_secs(),
+        format_secs(extension)
+    ));
+    let hint_line = Line::from(vec![
+        Span::styled(
+            "[y]",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" extend  ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            "[n]",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" let it time out", Style::default().fg(Color::DarkGray)),
+    ]);
+
+    frame.render_widget(
+        Paragraph::new(vec![message, Line::from(""), hint_line])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
+/// Draw the fix proposal overlay centered on the screen: its description
+/// and a scrollable diff of every line it changes, highlighted like the
+/// spec.
+pub fn draw_fix_overlay(frame: &mut Frame, app: &App, proposal: &FixProposal, area: Rect) {
+    let fallback;
+    let preview = match &app.fix_preview {
+        Some(preview) => preview,
+        None => {
+            fallback = FixPreview::new(proposal, None);
+            &fallback
+        }
+    };
+    // Height: border(2) + description + blank + diff + blank + keybindings
+    // line, as far as the screen allows.
+    let height = (preview.lines.len() as u16).saturating_add(6);
+    let popup = centered_rect(100, height.min(area.height.saturating_sub(2)), area);
+
+    frame.render_widget(Clear, popup);
+
+    let title = format!(" Fix: {} ", proposal.rule);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            proposal.description.clone(),
+            Style::default().fg(Color::White),
+        ))),
+        Rect { height: 1, ..inner },
+    );
+
+    let diff_area = Rect {
+        y: inner.y + 2,
+        height: inner.height.saturating_sub(4),
+        ..inner
+    };
+    let view = Viewport::new(preview.lines.len(), app.fix_scroll, diff_area.height);
+    let rows = &preview.lines[view.range()];
+    let highlighted = highlight_fix_rows(app, preview, rows);
+    let lines: Vec<Line> = rows
+        .iter()
+        .zip(highlighted)
+        .map(|(row, segments)| fix_row(row, segments, preview.first_line))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), diff_area);
+
+    let key = |k: &'static str, color: Color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    let dim = Style::default().fg(Color::DarkGray);
+    let hint_line = Line::from(vec![
+        key("[y]", Color::Green),
+        Span::styled(" accept  ", dim),
+        key("[n]", Color::Yellow),
+        Span::styled(" skip  ", dim),
+        key("[j/k]", Color::Cyan),
+        Span::styled(" scroll  ", dim),
+        key("[Esc]", Color::Red),
+        Span::styled(" cancel", dim),
+    ]);
+
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
+}
+
+/// The syntax-highlighted segments of each of `rows`: removed lines from
+/// the spec, the rest from the spec with the fix applied. `None` where that
+/// text isn't at hand, e.g. the spec changed since the preview was built.
+fn highlight_fix_rows(
+    app: &App,
+    preview: &FixPreview,
+    rows: &[NumberedLine],
+) -> Vec<Option<Vec<(Style, String)>>> {
+    let mut out = vec![None; rows.len()];
+    let mut engine = app.highlight_engine.borrow_mut();
+    if let Some(spec) = app
+        .spec_index
+        .as_ref()
+        .filter(|spec| Some(spec.version()) == preview.spec_version)
+    {
+        let all = engine.highlight_lines(spec.lines(), spec.syntax_name(), spec.version());
+        for (slot, row) in out.iter_mut().zip(rows) {
+            if matches!(row.line, DiffLine::Delete(_)) {
+                *slot = row.old.and_then(|i| all.get(i)).cloned();
+            }
+        }
+    }
+    if let Some(after) = &preview.after {
+        let all = engine.highlight_lines(after.lines(), after.syntax_name(), after.version());
+        for (slot, row) in out.iter_mut().zip(rows) {
+            if matches!(row.line, DiffLine::Insert(_) | DiffLine::Context(_)) {
+                *slot = row.new.and_then(|i| all.get(i)).cloned();
+            }
+        }
+    }
+    out
+}
+
+/// One line of the fix diff: a `+`/`-` marker and the spec line number,
+/// then the text, highlighted if `segments` has it.
+fn fix_row(
+    row: &NumberedLine,
+    segments: Option<Vec<(Style, String)>>,
+    first_line: usize,
+) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let (marker, number, color, text) = match &row.line {
+        DiffLine::HunkHeader(text) => {
+            return Line::from(Span::styled(
+                text.clone(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        DiffLine::Insert(text) => ('+', row.new, Some(Color::Green), text),
+        DiffLine::Delete(text) => ('-', row.old, Some(Color::Red), text),
+        DiffLine::Context(text) => (' ', row.new, None, text),
+    };
+    let number = number.map_or(String::new(), |n| (first_line + n + 1).to_string());
+    let style = color.map_or(dim, |c| Style::default().fg(c));
+    let mut spans = vec![Span::styled(
+        format!("{marker} {number:>4} \u{2502} "),
+        style.add_modifier(Modifier::BOLD),
+    )];
+    match segments {
+        Some(segments) => spans.extend(
+            segments
+                .into_iter()
+                .map(|(style, text)| Span::styled(text, style)),
+        ),
+        None => spans.push(Span::styled(text.clone(), style)),
+    }
+    Line::from(spans)
+}
+
+/// Draw the inline edit overlay, with the terminal cursor at the edit point.
+pub fn draw_inline_edit(frame: &mut Frame, edit: &InlineEditState, area: Rect) {
+    // Height: border(2) + edited lines + 1 blank + keybindings line.
+    let height = (edit.lines.len() as u16) + 4;
+    let popup = centered_rect(80, height, area);
+
+    frame.render_widget(Clear, popup);
+
+    let title = if edit.is_modified() {
+        format!(" Edit: line {} (modified) ", edit.first_line)
+    } else {
+        format!(" Edit: line {} ", edit.first_line)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(title);
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    // Scroll horizontally so the cursor stays visible on long lines.
+    const GUTTER: u16 = 7; // "1234 │ "
+    let text_width = inner.width.saturating_sub(GUTTER).max(1) as usize;
+    let cursor = width::width_of_chars(&edit.lines[edit.row], edit.col);
+    let skip = (cursor + 1).saturating_sub(text_width);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines: Vec<Line> = edit
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let visible = width::columns(text, skip, text_width);
+            Line::from(vec![
+                Span::styled(format!("{:>4} │ ", edit.first_line + i), dim),
+                Span::raw(visible),
+            ])
+        })
+        .collect();
+    let content_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines), content_area);
+
+    if (edit.row as u16) < content_area.height {
+        frame.set_cursor_position((
+            inner.x + GUTTER + (cursor - skip) as u16,
+            inner.y + edit.row as u16,
+        ));
+    }
+
+    let key = |k: &'static str, color: Color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    let hint_line = Line::from(vec![
+        key("[C-s]", Color::Green),
+        Span::styled(" save  ", dim),
+        key("[Enter]", Color::Yellow),
+        Span::styled(" new line  ", dim),
+        key("[Esc]", Color::Red),
+        Span::styled(" cancel", dim),
+    ]);
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(vec![hint_line]).alignment(Alignment::Center),
+        hint_area,
+    );
 }
 
 fn keybinding_lines(items: &[(&str, Option<&str>)]) -> Vec<Line<'static>> {
@@ -6,7 +6,12 @@ use ratatui::widgets::{List, ListItem, ListState, Paragraph, Tabs, Wrap};
 
 use crate::app::browser::syntax_name_for_path;
 use crate::app::{App, BrowserPanel};
-use crate::ui::style::{COLOR_GUTTER, COLOR_SELECTED_BG, make_block};
+use crate::log_parser::{CodeFinding, Severity};
+use crate::ui::style::{
+    COLOR_GUTTER, COLOR_SELECTED_BG, make_block, selection_marker, severity_color, severity_icon,
+};
+use crate::ui::viewport::{Viewport, skip_columns, wrap_suffix};
+use lazyoav::clean;
 
 pub fn draw_code_browser(frame: &mut Frame, app: &App, area: Rect) {
     let horizontal = Layout::default()
@@ -20,7 +25,16 @@ pub fn draw_code_browser(frame: &mut Frame, app: &App, area: Rect) {
 
 fn draw_file_tree(frame: &mut Frame, app: &App, area: Rect) {
     let focused = app.browser.browser_focus == BrowserPanel::FileTree;
-    let block = make_block("Files", focused);
+    let mut title = match app.browser.ignore_patterns.len() {
+        0 => "Files".to_string(),
+        n => format!("Files \u{00b7} {n} in .openapi-generator-ignore"),
+    };
+    match (&app.browser.filter_input, &app.browser.filter) {
+        (Some(input), _) => title.push_str(&format!(" /{input}\u{258f}")),
+        (None, Some(filter)) => title.push_str(&format!(" \u{00b7} filter: {filter}")),
+        (None, None) => {}
+    }
+    let block = make_block(&title, focused, app.accessible);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -37,18 +51,30 @@ fn draw_file_tree(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    // Split inner into tab bar (1 line) + file list (rest).
+    // Split inner into tab bar (1 line) + usage line (1 line) + file list (rest).
     let sections = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
         .split(inner);
 
-    // Generator tab bar.
+    // Generator tab bar, each with the size of its output once measured.
     let tab_titles: Vec<Line> = app
         .browser
         .generators
         .iter()
-        .map(|(generator, scope)| Line::from(format!("{generator}/{scope}")))
+        .map(
+            |(generator, scope)| match app.browser.usage.get(&format!("{scope}/{generator}")) {
+                Some(usage) => Line::from(format!(
+                    "{generator}/{scope} {}",
+                    clean::format_size(usage.bytes)
+                )),
+                None => Line::from(format!("{generator}/{scope}")),
+            },
+        )
         .collect();
 
     let tabs = Tabs::new(tab_titles)
@@ -63,13 +89,39 @@ fn draw_file_tree(frame: &mut Frame, app: &App, area: Rect) {
 
     frame.render_widget(tabs, sections[0]);
 
+    let usage = app
+        .browser
+        .active_generator_dir()
+        .and_then(|key| app.browser.usage.get(&key));
+    let summary = match usage {
+        Some(usage) => format!(
+            "{} files \u{00b7} {} on disk",
+            usage.files,
+            clean::format_size(usage.bytes)
+        ),
+        None if app.browser.usage_rx.is_some() => "Measuring output\u{2026}".to_string(),
+        None => String::new(),
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            summary,
+            Style::default().fg(Color::DarkGray),
+        ))),
+        sections[1],
+    );
+
     // File tree list.
     if app.browser.file_tree.is_empty() {
+        let message = if app.browser.all_files.is_empty() {
+            "Empty generator output"
+        } else {
+            "No files match the filter"
+        };
         let empty = Paragraph::new(Line::from(Span::styled(
-            "Empty generator output",
+            message,
             Style::default().fg(Color::DarkGray),
         )));
-        frame.render_widget(empty, sections[1]);
+        frame.render_widget(empty, sections[2]);
         return;
     }
 
@@ -79,46 +131,87 @@ fn draw_file_tree(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .map(|entry| {
             let indent = "  ".repeat(entry.depth);
-            let icon = if entry.is_dir { "▸ " } else { "  " };
-            let style = if entry.is_dir {
+            let collapsed = app.browser.collapsed.contains(&entry.path);
+            let icon = if !entry.is_dir {
+                "  "
+            } else if app.accessible {
+                if collapsed { "+ " } else { "- " }
+            } else if collapsed {
+                "▸ "
+            } else {
+                "▾ "
+            };
+            let style = if entry.ignored {
+                Style::default().fg(Color::DarkGray)
+            } else if entry.is_dir {
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(Color::White)
             };
-            ListItem::new(Line::from(Span::styled(
-                format!("{indent}{icon}{}", entry.name),
-                style,
-            )))
+            let mut spans = vec![Span::styled(format!("{indent}{icon}{}", entry.name), style)];
+            if entry.ignored {
+                spans.push(Span::styled(
+                    " (kept)",
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+            }
+            let findings = if entry.is_dir {
+                Vec::new()
+            } else {
+                app.browser.findings_in(&entry.path)
+            };
+            if !findings.is_empty() {
+                let severity = worst(&findings);
+                spans.push(Span::styled(
+                    format!(
+                        " {} {}",
+                        severity_icon(severity, app.accessible),
+                        findings.len()
+                    ),
+                    Style::default().fg(severity_color(severity)),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let list = List::new(items).highlight_style(
-        Style::default()
-            .bg(COLOR_SELECTED_BG)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(COLOR_SELECTED_BG)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_marker(app.accessible));
 
     let mut list_state = ListState::default();
     list_state.select(Some(app.browser.file_index));
 
-    frame.render_stateful_widget(list, sections[1], &mut list_state);
+    frame.render_stateful_widget(list, sections[2], &mut list_state);
 }
 
 fn draw_file_content(frame: &mut Frame, app: &App, area: Rect) {
     let focused = app.browser.browser_focus == BrowserPanel::FileContent;
 
-    let title = match app
+    let opened = app
         .browser
         .opened_file_index
         .and_then(|i| app.browser.file_tree.get(i))
-    {
-        Some(entry) if app.browser.file_content.is_some() && !entry.is_dir => entry.name.as_str(),
-        _ => "Content",
+        .filter(|entry| app.browser.file_content.is_some() && !entry.is_dir);
+    let findings = opened.map_or_else(Vec::new, |entry| app.browser.findings_in(&entry.path));
+    let title = match (opened, findings.len()) {
+        (Some(entry), 0) => entry.name.clone(),
+        (Some(entry), 1) => format!("{} \u{00b7} 1 finding", entry.name),
+        (Some(entry), n) => format!("{} \u{00b7} {n} findings", entry.name),
+        (None, _) => "Content".to_string(),
     };
 
-    let block = make_block(title, focused);
+    let wrap = app.browser.file_wrap;
+    let title = format!("{title}{}", wrap_suffix(wrap));
+    let block = make_block(&title, focused, app.accessible);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -149,23 +242,62 @@ fn draw_file_content(frame: &mut Frame, app: &App, area: Rect) {
     let mut engine = app.browser.highlight_engine.borrow_mut();
     let highlighted = engine.highlight_lines(lines, syntax_name, app.browser.content_version);
 
-    let display_lines: Vec<Line> = highlighted
+    let view = Viewport::new(highlighted.len(), app.browser.file_scroll, inner.height);
+    let display_lines: Vec<Line> = highlighted[view.range()]
         .iter()
         .enumerate()
         .map(|(i, segments)| {
-            let line_num = i + 1;
-            let gutter = Span::styled(format!("{line_num:>4} "), Style::default().fg(COLOR_GUTTER));
-            let mut spans = vec![gutter];
-            for (style, text) in segments {
-                spans.push(Span::styled(text.as_str(), *style));
+            let line_num = view.start + i + 1;
+            let gutter = Span::styled(format!("{line_num:>4}"), Style::default().fg(COLOR_GUTTER));
+            let text = segments
+                .iter()
+                .map(|(style, text)| Span::styled(text.as_str(), *style))
+                .collect();
+            let flagged: Vec<&CodeFinding> = findings
+                .iter()
+                .copied()
+                .filter(|f| f.line == line_num)
+                .collect();
+            let marker = if flagged.is_empty() {
+                Span::raw(" ")
+            } else {
+                let severity = worst(&flagged);
+                Span::styled(
+                    severity_icon(severity, app.accessible),
+                    Style::default().fg(severity_color(severity)),
+                )
+            };
+            let mut spans = vec![gutter, marker];
+            spans.extend(skip_columns(text, usize::from(wrap.offset)));
+            // The line's findings trail it, so the rows stay one per line.
+            for finding in flagged {
+                let rule = match finding.rule.as_str() {
+                    "" => String::new(),
+                    rule => format!(" [{rule}]"),
+                };
+                spans.push(Span::styled(
+                    format!("  {}{rule}", finding.message),
+                    Style::default()
+                        .fg(severity_color(finding.severity))
+                        .add_modifier(Modifier::DIM),
+                ));
             }
             Line::from(spans)
         })
         .collect();
 
-    let paragraph = Paragraph::new(display_lines)
-        .wrap(Wrap { trim: false })
-        .scroll((app.browser.file_scroll, 0));
+    let mut paragraph = Paragraph::new(display_lines);
+    if wrap.wrap {
+        paragraph = paragraph.wrap(Wrap { trim: false });
+    }
 
     frame.render_widget(paragraph, inner);
 }
+
+/// The most severe of `findings`.
+fn worst(findings: &[&CodeFinding]) -> Severity {
+    [Severity::Error, Severity::Warning, Severity::Info]
+        .into_iter()
+        .find(|s| findings.iter().any(|f| f.severity == *s))
+        .unwrap_or(Severity::Hint)
+}
@@ -5,12 +5,17 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Paragraph, Tabs, Wrap};
 
 use crate::app::App;
-use crate::ui::style::make_block;
+use crate::app::search::TextSearch;
+use crate::ui::style::{COLOR_GUTTER, COLOR_SELECTED_BG, make_block, push_with_matches};
+use crate::ui::viewport::{Viewport, skip_columns, wrap_suffix};
+use lazyoav::docker::image::digest_of;
+use lazyoav::pipeline::repro::command_line;
 
-const TAB_TITLES: [&str; 3] = ["Detail", "Raw Log", "Metadata"];
+const TAB_TITLES: [&str; 4] = ["Detail", "Raw Log", "Metadata", "Docs"];
 
 pub fn draw_detail(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
-    let block = make_block("Detail", focused);
+    let title = detail_title(app);
+    let block = make_block(&title, focused, app.accessible);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -54,20 +59,53 @@ pub fn draw_detail(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
     frame.render_widget(tabs, chunks[0]);
 
     // ── Tab content ───────────────────────────────────────────────────
-    let content: Vec<Line> = match app.detail_tab {
-        0 => detail_tab_content(app),
-        1 => raw_log_tab_content(app),
-        2 => metadata_tab_content(app),
-        _ => vec![],
+    // Logs can run to tens of thousands of lines: build only what's visible
+    // and scroll by line. The short tabs scroll by wrapped row.
+    let (content, scroll): (Vec<Line>, u16) = match app.detail_tab {
+        0 => (detail_tab_content(app), app.detail_scroll),
+        1 => {
+            let lines = raw_log_tab_content(app, app.detail_scroll, chunks[1].height);
+            (lines, 0)
+        }
+        2 => (metadata_tab_content(app), app.detail_scroll),
+        3 => (docs_tab_content(app), app.detail_scroll),
+        _ => (vec![], 0),
     };
 
-    let paragraph = Paragraph::new(content)
-        .wrap(Wrap { trim: false })
-        .scroll((app.detail_scroll, 0));
+    let mut paragraph = Paragraph::new(content).scroll((scroll, 0));
+    if app.detail_tab != 1 || app.log_wrap.wrap {
+        paragraph = paragraph.wrap(Wrap { trim: false });
+    }
 
     frame.render_widget(paragraph, chunks[1]);
 }
 
+/// "Detail", plus the search prompt, active filters and wrap state on the
+/// Raw Log tab.
+fn detail_title(app: &App) -> String {
+    let mut title = String::from("Detail");
+    if app.detail_tab != 1 {
+        return title;
+    }
+    if let Some(search) = app.active_log_search() {
+        let cursor = if search.editing { "\u{258f}" } else { "" };
+        let position = match search.current_line() {
+            Some(_) => format!("[{}/{}]", search.current + 1, search.matches.len()),
+            None if search.query.is_empty() => String::new(),
+            None => "[no match]".to_string(),
+        };
+        title = format!("{title} /{}{cursor} {position}", search.query);
+    }
+    if app.log_filter.errors_only {
+        title.push_str(" \u{00b7} errors only");
+    }
+    if app.log_filter.hide_noise {
+        title.push_str(" \u{00b7} quiet");
+    }
+    title.push_str(&wrap_suffix(app.log_wrap));
+    title
+}
+
 fn detail_tab_content(app: &App) -> Vec<Line<'static>> {
     let Some(err) = app.selected_error() else {
         return vec![Line::from(Span::styled(
@@ -83,15 +121,31 @@ fn detail_tab_content(app: &App) -> Vec<Line<'static>> {
         Span::raw(err.rule.clone()),
     ]));
 
-    lines.push(Line::from(vec![
+    let mut severity = vec![
         Span::styled("Severity: ", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(err.severity.to_string()),
-    ]));
+    ];
+    if app.severity_overrides.contains_key(&err.rule) {
+        severity.push(Span::styled(
+            " (rule_severity_overrides)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines.push(Line::from(severity));
 
-    lines.push(Line::from(vec![
-        Span::styled("Location: ", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(format!("{}:{}", err.line, err.col)),
-    ]));
+    // Redocly reports only a path; its line comes from the spec index.
+    if err.line > 0 {
+        let location = match err.end {
+            Some((line, col)) if (line, col) != (err.line, err.col) => {
+                format!("{}:{} \u{2013} {line}:{col}", err.line, err.col)
+            }
+            _ => format!("{}:{}", err.line, err.col),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Location: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(location),
+        ]));
+    }
 
     if let Some(ref path) = err.json_path {
         lines.push(Line::from(vec![
@@ -110,15 +164,45 @@ fn detail_tab_content(app: &App) -> Vec<Line<'static>> {
     lines
 }
 
-fn raw_log_tab_content(app: &App) -> Vec<Line<'static>> {
-    let log = app.current_phase_log();
-    if log.is_empty() {
+fn raw_log_tab_content(app: &App, scroll: u16, height: u16) -> Vec<Line<'_>> {
+    let rows = app.raw_log_lines();
+    if rows.is_empty() {
+        let message = if app.log_filter.is_active() && !app.current_phase_log().is_empty() {
+            "No lines match the log filters"
+        } else {
+            "No log available"
+        };
         return vec![Line::from(Span::styled(
-            "No log available",
+            message,
             Style::default().fg(Color::DarkGray),
         ))];
     }
-    log.lines().map(|l| Line::raw(l.to_string())).collect()
+    let search = app.active_log_search();
+    let current = search.and_then(TextSearch::current_line);
+    let view = Viewport::new(rows.len(), scroll, height);
+    rows[view.range()]
+        .iter()
+        .enumerate()
+        .map(|(i, &(text, timed))| {
+            let mut spans = Vec::new();
+            if let Some(l) = timed.filter(|_| app.show_log_times) {
+                spans.push(Span::styled(
+                    format!("{} ", l.time_prefix()),
+                    Style::default().fg(COLOR_GUTTER),
+                ));
+            }
+            let style = if current == Some(view.start + i + 1) {
+                Style::default().bg(COLOR_SELECTED_BG)
+            } else {
+                Style::default()
+            };
+            let matches = search.map_or_else(Vec::new, |s| s.ranges(text));
+            let mut text_spans = Vec::new();
+            push_with_matches(&mut text_spans, text, 0, style, &matches);
+            spans.extend(skip_columns(text_spans, usize::from(app.log_wrap.offset)));
+            Line::from(spans)
+        })
+        .collect()
 }
 
 fn metadata_tab_content(app: &App) -> Vec<Line<'static>> {
@@ -129,7 +213,7 @@ fn metadata_tab_content(app: &App) -> Vec<Line<'static>> {
         ))];
     };
 
-    vec![
+    let mut lines = vec![
         Line::from(vec![
             Span::styled("Spec:    ", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(report.spec.clone()),
@@ -151,5 +235,128 @@ fn metadata_tab_content(app: &App) -> Vec<Line<'static>> {
             Span::styled("Failed:  ", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(report.summary.failed.to_string()),
         ]),
-    ]
+        Line::from(vec![
+            Span::styled("Fail on: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(app.fail_policy.describe()),
+        ]),
+    ];
+    lines.extend(invocation_lines(app));
+    lines
+}
+
+/// The selected phase's container: image, how it ended, and the command.
+fn invocation_lines(app: &App) -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let Some(invocation) = app.current_invocation() else {
+        return Vec::new();
+    };
+    let step = app
+        .selected_phase()
+        .map_or_else(String::new, |phase| phase.to_string());
+
+    let mut outcome = match invocation.exit_code {
+        Some(code) => format!("exit {code}"),
+        None => "no exit code".to_string(),
+    };
+    if invocation.timed_out {
+        outcome.push_str(", timed out");
+    }
+    if invocation.cancelled {
+        outcome.push_str(", cancelled");
+    }
+    let outcome_color = if invocation.exit_code == Some(0) {
+        Color::Green
+    } else {
+        Color::Red
+    };
+
+    let mut lines = vec![
+        Line::raw(""),
+        Line::from(vec![Span::styled("Step:    ", bold), Span::raw(step)]),
+    ];
+    if let Some(image) = &invocation.image {
+        lines.push(Line::from(vec![
+            Span::styled("Image:   ", bold),
+            Span::raw(image.clone()),
+        ]));
+        let digest = app.report.as_ref().and_then(|r| r.images.get(image));
+        if let Some(digest) = digest {
+            lines.push(Line::from(vec![
+                Span::styled("Digest:  ", bold),
+                Span::styled(
+                    digest_of(digest).to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Result:  ", bold),
+        Span::styled(outcome, Style::default().fg(outcome_color)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Took:    ", bold),
+        Span::raw(format!("{:.1}s", invocation.duration_ms as f64 / 1000.0)),
+    ]));
+    lines.push(Line::from(Span::styled("Command:", bold)));
+    lines.push(Line::from(Span::styled(
+        command_line(&invocation.args),
+        Style::default().fg(Color::Yellow),
+    )));
+    lines
+}
+
+fn docs_tab_content(app: &App) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let Some(err) = app.selected_error() else {
+        return vec![Line::from(Span::styled(
+            "Select an error to view its rule's documentation",
+            dim,
+        ))];
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Rule:   ", bold),
+            Span::raw(err.rule.clone()),
+        ]),
+        Line::raw(""),
+    ];
+
+    let Some(doc) = app.rule_docs.lookup(&err.rule) else {
+        lines.push(Line::from(Span::styled(
+            "No documentation found for this rule",
+            dim,
+        )));
+        for url in &app.rule_docs.remote {
+            lines.push(Line::from(Span::styled(
+                format!("It may be defined in {url}"),
+                dim,
+            )));
+        }
+        return lines;
+    };
+
+    if let Some(description) = doc.description {
+        lines.push(Line::raw(description.to_string()));
+        lines.push(Line::raw(""));
+    }
+    if let Some(message) = doc.message {
+        lines.push(Line::from(vec![
+            Span::styled("Message: ", bold),
+            Span::raw(message.to_string()),
+        ]));
+    }
+    if let Some(url) = doc.url {
+        lines.push(Line::from(vec![
+            Span::styled("More:    ", bold),
+            Span::styled(url.to_string(), Style::default().fg(Color::Cyan)),
+        ]));
+    }
+    lines.push(Line::from(Span::styled(
+        format!("Source:  {}", doc.source),
+        dim,
+    )));
+    lines
 }
@@ -6,7 +6,8 @@ use ratatui::widgets::{List, ListItem, ListState, Paragraph, Wrap};
 
 use crate::app::App;
 use crate::app::diff::{ChangeKind, DiffLine, DiffPanel};
-use crate::ui::style::{COLOR_GUTTER, COLOR_SELECTED_BG, make_block};
+use crate::ui::style::{COLOR_GUTTER, COLOR_SELECTED_BG, make_block, selection_marker};
+use crate::ui::viewport::{Viewport, skip_columns, wrap_suffix};
 
 pub fn draw_diff_browser(frame: &mut Frame, app: &App, area: Rect) {
     let horizontal = Layout::default()
@@ -23,8 +24,11 @@ fn draw_change_list(frame: &mut Frame, app: &App, area: Rect) {
     let diff = app.browser.diff_state.active_diff();
     let file_count = diff.map(|d| d.files.len()).unwrap_or(0);
 
-    let title = format!("Changes ({file_count} files)");
-    let block = make_block(&title, focused);
+    let title = match &app.browser.diff_state.label {
+        Some(label) => format!("Changes ({file_count} files) \u{00b7} {label}"),
+        None => format!("Changes ({file_count} files)"),
+    };
+    let block = make_block(&title, focused, app.accessible);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -72,6 +76,7 @@ fn draw_change_list(frame: &mut Frame, app: &App, area: Rect) {
                 ChangeKind::Added => ("[A]", Color::Green),
                 ChangeKind::Modified => ("[M]", Color::Yellow),
                 ChangeKind::Deleted => ("[D]", Color::Red),
+                ChangeKind::TooLarge => ("[L]", Color::DarkGray),
             };
             ListItem::new(Line::from(vec![
                 Span::styled(
@@ -83,11 +88,13 @@ fn draw_change_list(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
-    let list = List::new(items).highlight_style(
-        Style::default()
-            .bg(COLOR_SELECTED_BG)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(COLOR_SELECTED_BG)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_marker(app.accessible));
 
     let mut list_state = ListState::default();
     list_state.select(Some(app.browser.diff_state.file_index));
@@ -100,8 +107,10 @@ fn draw_diff_content(frame: &mut Frame, app: &App, area: Rect) {
     let diff = app.browser.diff_state.active_diff();
     let file = diff.and_then(|d| d.files.get(app.browser.diff_state.file_index));
 
+    let wrap = app.browser.diff_state.wrap;
     let title = file.map(|f| f.rel_path.as_str()).unwrap_or("Diff");
-    let block = make_block(title, focused);
+    let title = format!("{title}{}", wrap_suffix(wrap));
+    let block = make_block(&title, focused, app.accessible);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -118,42 +127,44 @@ fn draw_diff_content(frame: &mut Frame, app: &App, area: Rect) {
         return;
     };
 
-    let display_lines: Vec<Line> = file
-        .lines
+    let view = Viewport::new(
+        file.lines.len(),
+        app.browser.diff_state.scroll,
+        inner.height,
+    );
+    let display_lines: Vec<Line> = file.lines[view.range()]
         .iter()
         .enumerate()
         .map(|(i, diff_line)| {
-            let line_num = i + 1;
+            let line_num = view.start + i + 1;
             let gutter = Span::styled(format!("{line_num:>4} "), Style::default().fg(COLOR_GUTTER));
-            match diff_line {
-                DiffLine::HunkHeader(text) => Line::from(vec![
-                    gutter,
-                    Span::styled(
-                        text.to_string(),
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                ]),
-                DiffLine::Insert(text) => Line::from(vec![
-                    gutter,
-                    Span::styled(format!("+ {text}"), Style::default().fg(Color::Green)),
-                ]),
-                DiffLine::Delete(text) => Line::from(vec![
-                    gutter,
-                    Span::styled(format!("- {text}"), Style::default().fg(Color::Red)),
-                ]),
-                DiffLine::Context(text) => Line::from(vec![
-                    gutter,
-                    Span::styled(format!("  {text}"), Style::default().fg(Color::DarkGray)),
-                ]),
-            }
+            let text = match diff_line {
+                DiffLine::HunkHeader(text) => Span::styled(
+                    text.to_string(),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                DiffLine::Insert(text) => {
+                    Span::styled(format!("+ {text}"), Style::default().fg(Color::Green))
+                }
+                DiffLine::Delete(text) => {
+                    Span::styled(format!("- {text}"), Style::default().fg(Color::Red))
+                }
+                DiffLine::Context(text) => {
+                    Span::styled(format!("  {text}"), Style::default().fg(Color::DarkGray))
+                }
+            };
+            let mut spans = vec![gutter];
+            spans.extend(skip_columns(vec![text], usize::from(wrap.offset)));
+            Line::from(spans)
         })
         .collect();
 
-    let paragraph = Paragraph::new(display_lines)
-        .wrap(Wrap { trim: false })
-        .scroll((app.browser.diff_state.scroll, 0));
+    let mut paragraph = Paragraph::new(display_lines);
+    if wrap.wrap {
+        paragraph = paragraph.wrap(Wrap { trim: false });
+    }
 
     frame.render_widget(paragraph, inner);
 }
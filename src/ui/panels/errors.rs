@@ -1,43 +1,83 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{List, ListItem, ListState};
 
 use crate::app::App;
-use crate::ui::style::{COLOR_SELECTED_BG, ICON_SEVERITY, make_block, severity_color};
-
-/// Truncate a string to at most `max` characters, appending "…" if shortened.
-fn truncate_chars(s: &str, max: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max || max == 0 {
-        return s.to_string();
-    }
-    let truncated: String = s.chars().take(max.saturating_sub(1)).collect();
-    format!("{truncated}…")
-}
+use crate::ui::style::{
+    COLOR_SELECTED_BG, make_block, selection_marker, severity_color, severity_icon,
+};
+use crate::ui::width;
+use lazyoav::pipeline::Phase;
 
 pub fn draw_errors(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
-    let block = make_block("Errors", focused);
+    // Baseline and suppression markers only apply to the lint and security
+    // findings.
+    let (all, new, known, suppressed, bounds) = match app.selected_phase() {
+        Some(Phase::Lint) => (
+            app.lint_errors.len(),
+            app.known_start(),
+            app.known_lint_count,
+            app.suppressed_lint_count,
+            Some(app.lint_bounds()),
+        ),
+        Some(Phase::Security) => (
+            app.security_errors.len(),
+            app.security_known_start(),
+            app.known_security_count,
+            app.suppressed_security_count,
+            Some(app.security_bounds()),
+        ),
+        _ => (0, 0, 0, 0, None),
+    };
+    let mut notes = Vec::new();
+    if app.baseline.is_some() && all > 0 {
+        notes.push(format!("{new} new, {known} known"));
+    }
+    match suppressed {
+        _ if bounds.is_none() => {}
+        0 => {}
+        n if app.show_suppressed => notes.push(format!("{n} suppressed shown")),
+        n => notes.push(format!("{n} suppressed")),
+    }
+    if let Some(changed) = &app.changed {
+        notes.push(format!("changed since {}", changed.base));
+    }
+    let title = if notes.is_empty() {
+        "Errors".to_string()
+    } else {
+        format!("Errors ({})", notes.join(", "))
+    };
+    let block = make_block(&title, focused, app.accessible);
     let errors = app.current_errors();
+    let (known_start, suppressed_start) = bounds.unwrap_or((errors.len(), errors.len()));
 
     if errors.is_empty() {
-        let msg = if app.report.is_some() {
-            "No errors in this phase"
+        // Step errors are only counted, not parsed; point at the log.
+        let step_errors = app
+            .phase_entries()
+            .get(app.phase_index)
+            .map_or(0, |entry| entry.error_count);
+        let msg = if step_errors > 0 {
+            format!("{step_errors} error line(s) in this step's log \u{2014} see Detail")
+        } else if app.report.is_some() {
+            "No errors in this phase".to_string()
         } else {
-            "No data"
+            "No data".to_string()
         };
         let item = ListItem::new(Line::from(Span::styled(
             msg,
-            Style::default().fg(ratatui::style::Color::DarkGray),
+            Style::default().fg(Color::DarkGray),
         )));
         let list = List::new(vec![item]).block(block);
         frame.render_widget(list, area);
         return;
     }
 
-    // Compute available width inside the block borders.
-    let inner_width = area.width.saturating_sub(2) as usize;
+    // Compute available width inside the block borders and selection marker.
+    let inner_width = (area.width.saturating_sub(2) as usize)
+        .saturating_sub(width::width(selection_marker(app.accessible)));
 
     let items: Vec<ListItem> = errors
         .iter()
@@ -45,22 +85,44 @@ pub fn draw_errors(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
         .map(|(i, err)| {
             let sev_color = severity_color(err.severity);
 
-            // Truncate rule to ~20 chars (char-safe).
-            let rule_display: String = truncate_chars(&err.rule, 20);
+            // Truncate rule to ~20 cells.
+            let rule_display: String = width::truncate(&err.rule, 20);
 
-            // "● rule_id  " takes up prefix_len chars.
-            let prefix_len = 2 + rule_display.chars().count() + 2; // icon+space + rule + 2 spaces
+            // "● rule_id  " takes up prefix_len cells.
+            let prefix_len = 2 + width::width(&rule_display) + 2; // icon+space + rule + 2 spaces
             let msg_budget = inner_width.saturating_sub(prefix_len);
-            let msg_display: String = truncate_chars(&err.message, msg_budget);
+            let msg_display: String = width::truncate(&err.message, msg_budget);
 
+            // Suppressed findings only show when toggled on, dimmed. Known
+            // findings from the baseline keep their severity icon but are
+            // dimmed so the new ones stand out.
+            let suppressed = i >= suppressed_start;
+            let known = i >= known_start && !suppressed;
+            let text = if suppressed || known {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
             let spans = vec![
-                Span::styled(format!("{ICON_SEVERITY} "), Style::default().fg(sev_color)),
-                Span::styled(rule_display, Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("{} ", severity_icon(err.severity, app.accessible)),
+                    Style::default().fg(if suppressed {
+                        Color::DarkGray
+                    } else {
+                        sev_color
+                    }),
+                ),
+                Span::styled(rule_display, text.add_modifier(Modifier::BOLD)),
                 Span::raw("  "),
-                Span::raw(msg_display),
+                Span::styled(msg_display, text),
             ];
 
+            // Findings from before the spec changed on disk are dimmed
+            // until the next run.
             let mut style = Style::default();
+            if app.spec_changed {
+                style = style.add_modifier(Modifier::DIM);
+            }
             if focused && i == app.error_index {
                 style = style.bg(COLOR_SELECTED_BG);
             }
@@ -74,11 +136,14 @@ pub fn draw_errors(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
         state.select(Some(app.error_index));
     }
 
-    let list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .bg(COLOR_SELECTED_BG)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(COLOR_SELECTED_BG)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_marker(app.accessible));
 
     frame.render_stateful_widget(list, area, &mut state);
 }
@@ -1,22 +1,68 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{List, ListItem, ListState};
 
-use crate::app::App;
+use crate::app::{App, PhaseStatus};
 use crate::ui::style::{
-    COLOR_FAIL, COLOR_SELECTED_BG, make_block, phase_status_color, phase_status_icon,
+    COLOR_FAIL, COLOR_PASS, COLOR_SELECTED_BG, make_block, phase_status_color, phase_status_icon,
+    score_color, selection_marker,
 };
+use lazyoav::keys::KeyAction;
 
 pub fn draw_phases(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
-    let block = make_block("Phases", focused);
+    let mut block = make_block("Phases", focused, app.accessible);
+    if let Some(score) = &app.score {
+        let mut spans = vec![Span::styled(
+            format!(" score {} ", score.total),
+            Style::default()
+                .fg(score_color(score.total))
+                .add_modifier(Modifier::BOLD),
+        )];
+        if let [.., previous, _] = app.score_history[..] {
+            let delta = i16::from(score.total) - i16::from(previous);
+            if delta != 0 {
+                let (arrow, color) = if delta > 0 {
+                    ("\u{25b2}", COLOR_PASS)
+                } else {
+                    ("\u{25bc}", COLOR_FAIL)
+                };
+                spans.push(Span::styled(
+                    format!("{arrow}{} ", delta.abs()),
+                    Style::default().fg(color),
+                ));
+            }
+        }
+        block = block.title_top(Line::from(spans).right_aligned());
+    }
+    if let Some(project) = app.project.and_then(|i| app.projects.get(i)) {
+        block = block.title_top(
+            Line::from(Span::styled(
+                format!(" {} ", project.label()),
+                Style::default().fg(Color::DarkGray),
+            ))
+            .centered(),
+        );
+    }
+    if app.spec_changed {
+        let run = app.keymap.label(KeyAction::RunValidation);
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" spec changed since last run \u{2014} press {run} "),
+            Style::default().fg(Color::Yellow),
+        )));
+    } else if app.report_stale {
+        block = block.title_bottom(Line::from(Span::styled(
+            " stale: config changed ",
+            Style::default().fg(Color::Yellow),
+        )));
+    }
     let entries = app.phase_entries();
 
     if entries.is_empty() {
         let item = ListItem::new(Line::from(Span::styled(
             "No validation report loaded",
-            Style::default().fg(ratatui::style::Color::DarkGray),
+            Style::default().fg(Color::DarkGray),
         )));
         let list = List::new(vec![item]).block(block);
         frame.render_widget(list, area);
@@ -28,21 +74,47 @@ pub fn draw_phases(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
         .enumerate()
         .map(|(i, entry)| {
             let icon_color = phase_status_color(entry.status);
-            let icon = phase_status_icon(entry.status);
+            let icon = phase_status_icon(entry.status, app.accessible);
 
             let mut spans = vec![
                 Span::styled(format!("{icon} "), Style::default().fg(icon_color)),
                 Span::raw(&entry.label),
             ];
 
-            if entry.error_count > 0 {
+            // Findings that didn't fail their phase, e.g. warnings under
+            // `fail_on: error`, are counted in yellow rather than red.
+            let count_color = if entry.status == PhaseStatus::Fail {
+                COLOR_FAIL
+            } else {
+                Color::Yellow
+            };
+            if let Some(known) = entry.known_count
+                && entry.error_count > 0
+            {
+                let new = entry.error_count - known;
+                spans.push(Span::styled(
+                    format!(" [{new} new"),
+                    Style::default().fg(if new > 0 {
+                        count_color
+                    } else {
+                        Color::DarkGray
+                    }),
+                ));
+                spans.push(Span::styled(
+                    format!(", {known} known]"),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            } else if entry.error_count > 0 {
                 spans.push(Span::styled(
                     format!(" [{} errors]", entry.error_count),
-                    Style::default().fg(COLOR_FAIL),
+                    Style::default().fg(count_color),
                 ));
             }
 
             let mut style = Style::default();
+            if app.spec_changed {
+                style = style.add_modifier(Modifier::DIM);
+            }
             if focused && i == app.phase_index {
                 style = style.bg(COLOR_SELECTED_BG);
             }
@@ -56,11 +128,14 @@ pub fn draw_phases(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
         state.select(Some(app.phase_index));
     }
 
-    let list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .bg(COLOR_SELECTED_BG)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(COLOR_SELECTED_BG)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(selection_marker(app.accessible));
 
     frame.render_stateful_widget(list, area, &mut state);
 }
@@ -5,10 +5,52 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Paragraph, Wrap};
 
 use crate::app::App;
-use crate::ui::style::{COLOR_GUTTER, COLOR_SELECTED_BG, make_block};
+use crate::ui::style::{
+    COLOR_GUTTER, COLOR_SELECTED_BG, make_block, push_with_matches, severity_color, severity_icon,
+};
+use crate::ui::width;
 
 pub fn draw_spec_context(frame: &mut Frame, app: &App, area: Rect, focused: bool) {
-    let block = make_block("Spec Context", focused);
+    // Findings sharing the selected error's line, listed under the target line.
+    let siblings = app.line_findings();
+    let search = app
+        .spec_search
+        .as_ref()
+        .filter(|s| s.anchor == (app.phase_index, app.error_index));
+    let title = match (search, siblings.iter().position(|&i| i == app.error_index)) {
+        (Some(search), _) => {
+            let cursor = if search.editing { "\u{258f}" } else { "" };
+            let position = match search.current_line() {
+                Some(_) => format!("[{}/{}]", search.current + 1, search.matches.len()),
+                None if search.query.is_empty() => String::new(),
+                None => "[no match]".to_string(),
+            };
+            format!("Spec Context /{}{cursor} {position}", search.query)
+        }
+        (None, Some(pos)) if siblings.len() > 1 => {
+            format!("Spec Context [{}/{}]", pos + 1, siblings.len())
+        }
+        _ => "Spec Context".to_string(),
+    };
+    let cursor = app.active_spec_cursor();
+    let title = match (&app.goto_line, cursor) {
+        (Some(input), _) => format!("{title} :{input}\u{258f}"),
+        (None, Some(c)) if c.pinned => format!("{title} \u{00b7} pinned L{}", c.line),
+        (None, Some(c)) => format!("{title} \u{00b7} L{}", c.line),
+        (None, None) => title,
+    };
+    let mut block = make_block(&title, focused, app.accessible);
+    if let Some(crumb) = app
+        .spec_target_line()
+        .and_then(|line| app.spec_index.as_ref()?.pointer_at(line))
+        .map(|pointer| breadcrumb(pointer, area.width.saturating_sub(4) as usize))
+        .filter(|crumb| !crumb.is_empty())
+    {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" {crumb} "),
+            Style::default().fg(Color::Gray),
+        )));
+    }
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -28,17 +70,8 @@ pub fn draw_spec_context(frame: &mut Frame, app: &App, area: Rect, focused: bool
         }
     };
 
-    // Resolve the target line from the selected error.
-    let target_line = app.selected_error().and_then(|err| {
-        // Try json_path resolution first, fall back to the error's line number.
-        if let Some(ref path) = err.json_path {
-            spec_index.resolve(path).map(|span| span.line)
-        } else if err.line > 0 {
-            Some(err.line)
-        } else {
-            None
-        }
-    });
+    // Resolve the target line from the search or the selected error.
+    let target_line = app.spec_target_line();
 
     let radius = (inner.height as usize) / 2;
 
@@ -60,17 +93,7 @@ pub fn draw_spec_context(frame: &mut Frame, app: &App, area: Rect, focused: bool
         return;
     };
 
-    // Determine syntax from file extension.
-    let syntax_name = app
-        .spec_path
-        .as_ref()
-        .and_then(|p| p.extension())
-        .and_then(|ext| ext.to_str())
-        .map(|ext| match ext.to_ascii_lowercase().as_str() {
-            "json" => "JSON",
-            _ => "YAML",
-        })
-        .unwrap_or("YAML");
+    let syntax_name = spec_index.syntax_name();
 
     // Hold the engine borrow through span construction and render so we can
     // reference cached Strings directly (via Cow::Borrowed) instead of cloning.
@@ -79,36 +102,100 @@ pub fn draw_spec_context(frame: &mut Frame, app: &App, area: Rect, focused: bool
         engine.highlight_lines(spec_index.lines(), syntax_name, spec_index.version());
     let start_idx = window.start_line - 1;
 
-    let lines: Vec<Line> = window
-        .lines
-        .iter()
-        .enumerate()
-        .map(|(i, _)| {
-            let line_num = window.start_line + i;
-            let gutter = Span::styled(format!("{line_num:>4} "), Style::default().fg(COLOR_GUTTER));
-
-            let is_target = line_num == window.target_line;
-
-            let mut spans = vec![gutter];
-
-            if let Some(segments) = all_highlighted.get(start_idx + i) {
-                for (style, text) in segments {
-                    let style = if is_target {
-                        style.bg(COLOR_SELECTED_BG).add_modifier(Modifier::BOLD)
-                    } else {
-                        *style
-                    };
-                    spans.push(Span::styled(text.as_str(), style));
-                }
+    let errors = app.current_errors();
+    let markers = app.findings_by_line();
+    let mut lines: Vec<Line> = Vec::with_capacity(window.lines.len() + siblings.len());
+    for (i, _) in window.lines.iter().enumerate() {
+        let line_num = window.start_line + i;
+        let gutter = Span::styled(format!("{line_num:>4} "), Style::default().fg(COLOR_GUTTER));
+        let marker = match markers.get(&line_num) {
+            Some((severity, _)) => Span::styled(
+                format!("{} ", severity_icon(*severity, app.accessible)),
+                Style::default().fg(severity_color(*severity)),
+            ),
+            None => Span::raw("  "),
+        };
+
+        let is_target = line_num == window.target_line;
+
+        let mut spans = vec![gutter, marker];
+
+        let matches = search.map_or_else(Vec::new, |s| s.ranges(&window.lines[i]));
+        if let Some(segments) = all_highlighted.get(start_idx + i) {
+            let mut offset = 0;
+            for (style, text) in segments {
+                let style = if is_target {
+                    style.bg(COLOR_SELECTED_BG).add_modifier(Modifier::BOLD)
+                } else {
+                    *style
+                };
+                push_with_matches(&mut spans, text, offset, style, &matches);
+                offset += text.len();
             }
+        }
 
-            Line::from(spans)
-        })
-        .collect();
+        lines.push(Line::from(spans));
+
+        if is_target && siblings.len() > 1 {
+            for &idx in &siblings {
+                let Some(err) = errors.get(idx) else {
+                    continue;
+                };
+                let selected = idx == app.error_index;
+                let marker = match (selected, app.accessible) {
+                    (true, true) => ">",
+                    (true, false) => "▸",
+                    (false, _) => " ",
+                };
+                let mut text_style = Style::default().fg(Color::Gray);
+                if selected {
+                    text_style = text_style.add_modifier(Modifier::BOLD);
+                }
+                lines.push(Line::from(vec![
+                    Span::styled(format!("   {marker} "), Style::default().fg(COLOR_GUTTER)),
+                    Span::styled(
+                        format!("{} ", severity_icon(err.severity, app.accessible)),
+                        Style::default().fg(severity_color(err.severity)),
+                    ),
+                    Span::styled(format!("{}: ", err.rule), text_style),
+                    Span::styled(err.message.as_str(), text_style),
+                ]));
+            }
+        }
+    }
 
     let paragraph = Paragraph::new(lines)
         .wrap(Wrap { trim: false })
         .scroll((app.spec_scroll, 0));
 
     frame.render_widget(paragraph, inner);
+
+    // Findings outside the window, counted on the right of the borders.
+    let last_line = window.start_line + window.lines.len().saturating_sub(1);
+    let above: usize = markers
+        .range(..window.start_line)
+        .map(|(_, (_, n))| n)
+        .sum();
+    let below: usize = markers.range(last_line + 1..).map(|(_, (_, n))| n).sum();
+    let edge = |y: u16| Rect::new(area.x + 1, y, area.width.saturating_sub(2), 1);
+    let count_style = Style::default().fg(Color::Yellow);
+    if above > 0 {
+        let text = Line::styled(format!(" \u{25b2}{above} "), count_style).right_aligned();
+        frame.render_widget(text, edge(area.y));
+    }
+    if below > 0 {
+        let text = Line::styled(format!(" \u{25bc}{below} "), count_style).right_aligned();
+        frame.render_widget(text, edge(area.bottom().saturating_sub(1)));
+    }
+}
+
+/// `/paths/~1pets/get` → `paths › /pets › get`, keeping the innermost
+/// segments when it is wider than `width`.
+fn breadcrumb(pointer: &str, width: usize) -> String {
+    let segments: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    width::truncate_start(&segments.join(" \u{203a} "), width)
 }
@@ -1,4 +1,8 @@
+use std::ops::Range;
+
+use ratatui::buffer::Buffer;
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
 use ratatui::widgets::{Block, Borders};
 
 use crate::app::PhaseStatus;
@@ -11,6 +15,7 @@ pub const COLOR_RUNNING: Color = Color::Yellow;
 pub const COLOR_PENDING: Color = Color::DarkGray;
 pub const COLOR_SELECTED_BG: Color = Color::DarkGray;
 pub const COLOR_GUTTER: Color = Color::DarkGray;
+pub const COLOR_SEARCH_MATCH: Color = Color::Yellow;
 
 // ── Icon constants ────────────────────────────────────────────────────
 pub const ICON_PASS: &str = "✓";
@@ -20,6 +25,16 @@ pub const ICON_PENDING: &str = "─";
 
 pub const ICON_SEVERITY: &str = "●";
 
+// ── ASCII stand-ins for accessible mode ───────────────────────────────
+pub const ASCII_PASS: &str = "+";
+pub const ASCII_FAIL: &str = "x";
+pub const ASCII_RUNNING: &str = "*";
+pub const ASCII_PENDING: &str = "-";
+
+/// Prefix of the selected row in accessible mode, where there is no
+/// background colour to show it.
+pub const SELECTION_MARKER: &str = "> ";
+
 // ── Helpers ───────────────────────────────────────────────────────────
 
 pub fn severity_color(sev: Severity) -> Color {
@@ -40,16 +55,54 @@ pub fn phase_status_color(status: PhaseStatus) -> Color {
     }
 }
 
-pub fn phase_status_icon(status: PhaseStatus) -> &'static str {
-    match status {
-        PhaseStatus::Pass => ICON_PASS,
-        PhaseStatus::Fail => ICON_FAIL,
-        PhaseStatus::Running => ICON_RUNNING,
-        PhaseStatus::Pending => ICON_PENDING,
+pub fn phase_status_icon(status: PhaseStatus, ascii: bool) -> &'static str {
+    match (status, ascii) {
+        (PhaseStatus::Pass, false) => ICON_PASS,
+        (PhaseStatus::Fail, false) => ICON_FAIL,
+        (PhaseStatus::Running, false) => ICON_RUNNING,
+        (PhaseStatus::Pending, false) => ICON_PENDING,
+        (PhaseStatus::Pass, true) => ASCII_PASS,
+        (PhaseStatus::Fail, true) => ASCII_FAIL,
+        (PhaseStatus::Running, true) => ASCII_RUNNING,
+        (PhaseStatus::Pending, true) => ASCII_PENDING,
     }
 }
 
-pub fn make_block(title: &str, focused: bool) -> Block<'_> {
+/// The dot before a finding, or in accessible mode its severity's initial
+/// so the severity doesn't rest on colour alone.
+pub fn severity_icon(sev: Severity, ascii: bool) -> &'static str {
+    match (sev, ascii) {
+        (_, false) => ICON_SEVERITY,
+        (Severity::Error, true) => "E",
+        (Severity::Warning, true) => "W",
+        (Severity::Info, true) => "I",
+        (Severity::Hint, true) => "H",
+    }
+}
+
+/// The list highlight symbol: [`SELECTION_MARKER`] in accessible mode,
+/// nothing when the highlight colour shows the selection.
+pub fn selection_marker(ascii: bool) -> &'static str {
+    if ascii { SELECTION_MARKER } else { "" }
+}
+
+/// Quality score color: green from 80, yellow from 50, red below.
+pub fn score_color(score: u8) -> Color {
+    match score {
+        80.. => COLOR_PASS,
+        50.. => COLOR_RUNNING,
+        _ => COLOR_FAIL,
+    }
+}
+
+/// A bordered panel. Focus shows in the border colour, and in accessible
+/// mode as a [`SELECTION_MARKER`] before the title.
+pub fn make_block(title: &str, focused: bool, ascii: bool) -> Block<'static> {
+    let title = if focused && ascii {
+        format!("{SELECTION_MARKER}{title}")
+    } else {
+        title.to_string()
+    };
     let style = if focused {
         Style::default()
             .fg(Color::Green)
@@ -63,3 +116,45 @@ pub fn make_block(title: &str, focused: bool) -> Block<'_> {
         .borders(Borders::ALL)
         .border_style(style)
 }
+
+/// Push `text`, starting at byte `offset` of its line, splitting out the
+/// parts covered by search `matches` so they are highlighted over the
+/// syntax colours.
+pub fn push_with_matches<'a>(
+    spans: &mut Vec<Span<'a>>,
+    text: &'a str,
+    offset: usize,
+    style: Style,
+    matches: &[Range<usize>],
+) {
+    let end = offset + text.len();
+    let mut pos = offset;
+    for m in matches.iter().filter(|m| m.start < end && m.end > offset) {
+        let start = m.start.max(offset);
+        let stop = m.end.min(end);
+        if start > pos {
+            spans.push(Span::styled(&text[pos - offset..start - offset], style));
+        }
+        spans.push(Span::styled(
+            &text[start - offset..stop - offset],
+            style.fg(Color::Black).bg(COLOR_SEARCH_MATCH),
+        ));
+        pos = stop;
+    }
+    if pos < end {
+        spans.push(Span::styled(&text[pos - offset..], style));
+    }
+}
+
+/// Drop the colours from everything drawn into `buf`. Cells on a
+/// highlight background turn to reverse video, so search matches and the
+/// selection stay visible on a monochrome terminal.
+pub fn strip_colors(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        if cell.bg != Color::Reset {
+            cell.modifier |= Modifier::REVERSED;
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
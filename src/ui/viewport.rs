@@ -0,0 +1,69 @@
+use std::ops::Range;
+
+use ratatui::text::Span;
+
+use crate::app::LineWrap;
+
+use super::width;
+
+/// Lines built past the bottom of the panel, so a frame still fills it when
+/// the content is a little ahead of the scroll position.
+const OVERSCAN: usize = 16;
+
+/// The part of a long list of lines a scrolled panel shows, so only that
+/// slice gets turned into `Line`s.
+///
+/// `scroll` counts source lines, not wrapped rows. Scrolling past the end
+/// (e.g. `u16::MAX` for "jump to last") shows the last page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Viewport {
+    pub fn new(total: usize, scroll: u16, height: u16) -> Self {
+        let height = usize::from(height);
+        let start = usize::from(scroll).min(total.saturating_sub(height));
+        Self {
+            start,
+            end: (start + height + OVERSCAN).min(total),
+        }
+    }
+
+    pub fn range(self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// `spans` with their first `columns` terminal cells dropped, for a panel
+/// scrolled sideways. Callers leave the gutter out so it stays in place.
+pub fn skip_columns(spans: Vec<Span<'_>>, columns: usize) -> Vec<Span<'_>> {
+    let mut skip = columns;
+    spans
+        .into_iter()
+        .filter_map(|span| {
+            if skip == 0 {
+                return Some(span);
+            }
+            let cells = width::width(&span.content);
+            if cells <= skip {
+                skip -= cells;
+                return None;
+            }
+            let content = width::columns(&span.content, skip, cells - skip);
+            skip = 0;
+            Some(Span::styled(content, span.style))
+        })
+        .collect()
+}
+
+/// Title suffix for a panel that isn't wrapping: ` · no wrap`, or the first
+/// visible column once scrolled sideways.
+pub fn wrap_suffix(wrap: LineWrap) -> String {
+    match (wrap.wrap, wrap.offset) {
+        (true, _) => String::new(),
+        (false, 0) => " \u{00b7} no wrap".to_string(),
+        (false, offset) => format!(" \u{00b7} col {}", offset + 1),
+    }
+}
@@ -0,0 +1,134 @@
+//! Text measured in terminal cells rather than chars: CJK characters and
+//! most emoji take two cells, combining marks none.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Cells `s` takes on screen.
+pub fn width(s: &str) -> usize {
+    s.width()
+}
+
+/// `s` cut to at most `max` cells, ending in `…` when shortened.
+pub fn truncate(s: &str, max: usize) -> String {
+    if width(s) <= max {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > max.saturating_sub(1) {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if max > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// `s` cut from the front to at most `max` cells, starting with `…` when
+/// shortened.
+pub fn truncate_start(s: &str, max: usize) -> String {
+    if width(s) <= max {
+        return s.to_string();
+    }
+    let mut kept = Vec::new();
+    let mut used = 0;
+    for c in s.chars().rev() {
+        let w = c.width().unwrap_or(0);
+        if used + w > max.saturating_sub(1) {
+            break;
+        }
+        kept.push(c);
+        used += w;
+    }
+    let tail: String = kept.into_iter().rev().collect();
+    if max > 0 { format!("…{tail}") } else { tail }
+}
+
+/// `s` padded with spaces to `cells` wide. Unlike `{:<n}`, which counts
+/// chars, this keeps columns after wide text aligned.
+pub fn pad(s: &str, cells: usize) -> String {
+    format!("{s}{}", " ".repeat(cells.saturating_sub(width(s))))
+}
+
+/// The columns of `s` from `skip` on, at most `take` wide. A wide char cut
+/// in half by either edge turns into spaces, so what follows keeps its
+/// column. Zero-width chars, like combining marks, go with the char before
+/// them.
+pub fn columns(s: &str, skip: usize, take: usize) -> String {
+    let mut out = String::new();
+    let mut col = 0;
+    // Whether the char before is shown, so zero-width chars after it are.
+    let mut shown = skip == 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if w == 0 {
+            if shown {
+                out.push(c);
+            }
+            continue;
+        }
+        let (start, end) = (col, col + w);
+        col = end;
+        if end <= skip {
+            continue;
+        }
+        if end > skip + take {
+            // Fill what's left of the window with the visible half.
+            out.push_str(&" ".repeat((skip + take).saturating_sub(start.max(skip))));
+            break;
+        }
+        shown = start >= skip;
+        if shown {
+            out.push(c);
+        } else {
+            out.push_str(&" ".repeat(end - skip));
+        }
+    }
+    out
+}
+
+/// Cells taken by the first `chars` chars of `s`, e.g. for placing the
+/// cursor after them.
+pub fn width_of_chars(s: &str, chars: usize) -> usize {
+    s.chars().take(chars).map(|c| c.width().unwrap_or(0)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_text_is_measured_and_cut_in_cells() {
+        assert_eq!(width("日本"), 4);
+        assert_eq!(width("e\u{301}"), 1);
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate_start("日本語", 5), "…本語");
+        assert_eq!(pad("日本", 6), "日本  ");
+        assert_eq!(width_of_chars("日本語", 2), 4);
+    }
+
+    #[test]
+    fn columns_turn_halved_wide_chars_into_spaces() {
+        assert_eq!(columns("日本語", 1, 4), " 本 ");
+        assert_eq!(columns("abcdef", 2, 3), "cde");
+        assert_eq!(columns("abc", 5, 3), "");
+    }
+
+    #[test]
+    fn columns_keep_zero_width_chars_with_the_char_before() {
+        // "e" with a combining acute accent, at either edge of the window.
+        let s = "ae\u{301}be\u{301}";
+        assert_eq!(columns(s, 0, 2), "ae\u{301}");
+        assert_eq!(columns(s, 2, 2), "be\u{301}");
+        assert_eq!(columns(s, 1, 1), "e\u{301}");
+        assert_eq!(columns("\u{301}a", 0, 1), "\u{301}a");
+        // The accent's char is cut away, so is the accent.
+        assert_eq!(columns("日\u{301}a", 1, 2), " a");
+    }
+}
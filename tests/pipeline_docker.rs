@@ -432,6 +432,8 @@ fn custom_generator_runs_via_openapi_cli() {
                 .into(),
         },
         compile: None,
+        test: None,
+        code_lint: None,
     }];
 
     let cfg = Config {